@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+/// One value refetched once it is older than `ttl`. The lock is held while
+/// fetching, so a burst of requests on an expired value costs one RPC call.
+pub struct Cached<T> {
+    ttl: Duration,
+    value: Mutex<Option<(Instant, Arc<T>)>>,
+}
+
+impl<T> Cached<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            value: Mutex::new(None),
+        }
+    }
+
+    pub async fn get<E, Fut>(&self, fetch: impl FnOnce() -> Fut) -> Result<Arc<T>, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut value = self.value.lock().await;
+        if let Some((fetched_at, cached)) = value.as_ref() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(cached.clone());
+            }
+        }
+        let fresh = Arc::new(fetch().await?);
+        *value = Some((Instant::now(), fresh.clone()));
+        Ok(fresh)
+    }
+}
+
+/// Values that never change once they exist, such as a settled round's
+/// result. Misses are not remembered, so a round shows up once written.
+pub struct Immutable<K, V> {
+    values: Mutex<HashMap<K, Arc<V>>>,
+}
+
+impl<K: std::hash::Hash + Eq, V> Immutable<K, V> {
+    pub fn new() -> Self {
+        Self {
+            values: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get<E, Fut>(
+        &self,
+        key: K,
+        fetch: impl FnOnce() -> Fut,
+    ) -> Result<Option<Arc<V>>, E>
+    where
+        Fut: Future<Output = Result<Option<V>, E>>,
+    {
+        if let Some(cached) = self.values.lock().await.get(&key) {
+            return Ok(Some(cached.clone()));
+        }
+        let Some(fetched) = fetch().await? else {
+            return Ok(None);
+        };
+        let fetched = Arc::new(fetched);
+        self.values.lock().await.insert(key, fetched.clone());
+        Ok(Some(fetched))
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use tokio_postgres::{Client, NoTls};
+
+use crate::view::BidView;
+
+/// Read side of the indexer's Postgres tables (see `sovra-indexer`).
+pub struct Db {
+    client: Client,
+}
+
+impl Db {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                tracing::error!(%err, "postgres connection closed");
+            }
+        });
+        Ok(Self { client })
+    }
+
+    /// One page of bids, highest first, and the number matching overall.
+    pub async fn bids(
+        &self,
+        all: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(usize, Vec<BidView>)> {
+        let total: i64 = self
+            .client
+            .query_one("SELECT count(*) FROM bids WHERE active OR $1", &[&all])
+            .await?
+            .get(0);
+        let rows = self
+            .client
+            .query(
+                "SELECT address, bidder, amount, max_amount, proxy, active, created_at, updated_at
+                 FROM bids WHERE active OR $1
+                 ORDER BY amount DESC, address
+                 OFFSET $2 LIMIT $3",
+                &[&all, &(offset as i64), &(limit as i64)],
+            )
+            .await?;
+        let bids = rows
+            .iter()
+            .map(|row| BidView {
+                address: row.get("address"),
+                bidder: row.get("bidder"),
+                amount: row.get::<_, i64>("amount") as u64,
+                max_amount: row.get::<_, i64>("max_amount") as u64,
+                proxy: row.get("proxy"),
+                active: row.get("active"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            })
+            .collect();
+        Ok((total as usize, bids))
+    }
+}
@@ -0,0 +1,74 @@
+//! Read-only REST API over one auction, so frontends and partners need
+//! neither RPC access nor Anchor decoding:
+//!
+//! - `GET /auction`: the auction state.
+//! - `GET /bids?offset=&limit=&all=`: bids, highest first, paginated.
+//! - `GET /leaderboard`: the program's ranked top bids.
+//! - `GET /rounds/{id}`: a settled round's result, from one.
+//!
+//! Everything is read over RPC and cached for `--cache-ms`; settled rounds,
+//! which never change, are cached for good. With `--database-url`, `/bids`
+//! is served from the indexer's Postgres tables instead of a
+//! `getProgramAccounts` scan.
+
+mod cache;
+mod db;
+mod routes;
+mod view;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use sovra_client::nonblocking::SovraClient;
+use tower_http::cors::CorsLayer;
+
+use crate::cache::{Cached, Immutable};
+use crate::db::Db;
+use crate::routes::App;
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long, env = "SOVRA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    #[arg(long, env = "SOVRA_AUCTION_ID", default_value_t = 0)]
+    auction_id: u64,
+    /// The indexer's database, for `/bids`.
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: Option<String>,
+    #[arg(long, env = "SOVRA_API_LISTEN", default_value = "0.0.0.0:8080")]
+    listen: SocketAddr,
+    /// How long RPC reads are served from cache.
+    #[arg(long, default_value_t = 2_000)]
+    cache_ms: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+    let db = match &args.database_url {
+        // The indexer's bid rows carry no auction id, so they only describe
+        // a deployment running auction 0 alone.
+        Some(_) if args.auction_id != 0 => bail!("--database-url only serves auction 0"),
+        Some(url) => Some(Db::connect(url).await.context("connecting to postgres")?),
+        None => None,
+    };
+    let ttl = Duration::from_millis(args.cache_ms);
+    let app = Arc::new(App {
+        client: SovraClient::new(&args.rpc_url).with_auction_id(args.auction_id),
+        db,
+        state: Cached::new(ttl),
+        bids: Cached::new(ttl),
+        rounds: Immutable::new(),
+    });
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    tracing::info!(listen = %args.listen, auction_id = args.auction_id, "serving");
+    axum::serve(listener, routes::router(app).layer(CorsLayer::permissive())).await?;
+    Ok(())
+}
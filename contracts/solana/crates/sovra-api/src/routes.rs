@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use anchor_lang::prelude::Pubkey;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{pda, AuctionState, Bid};
+
+use crate::cache::{Cached, Immutable};
+use crate::db::Db;
+use crate::view::{self, AuctionView, BidPage, BidView, LeaderboardEntryView, RoundView};
+
+const DEFAULT_PAGE: usize = 50;
+const MAX_PAGE: usize = 500;
+
+pub struct App {
+    pub client: SovraClient,
+    /// Serves `/bids` from the indexer instead of `getProgramAccounts`.
+    pub db: Option<Db>,
+    pub state: Cached<AuctionState>,
+    pub bids: Cached<Vec<(Pubkey, Bid)>>,
+    pub rounds: Immutable<u64, RoundView>,
+}
+
+pub fn router(app: Arc<App>) -> Router {
+    Router::new()
+        .route("/auction", get(auction))
+        .route("/bids", get(bids))
+        .route("/leaderboard", get(leaderboard))
+        .route("/rounds/{id}", get(round))
+        .with_state(app)
+}
+
+pub enum ApiError {
+    NotFound(String),
+    /// RPC or Postgres failed; details are logged, not returned.
+    Upstream(anyhow::Error),
+}
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        Self::Upstream(err.into())
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            Self::NotFound(what) => (StatusCode::NOT_FOUND, format!("{what} not found")),
+            Self::Upstream(err) => {
+                tracing::error!(?err, "upstream request failed");
+                (StatusCode::BAD_GATEWAY, "upstream unavailable".to_string())
+            }
+        };
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+impl App {
+    async fn auction_state(&self) -> Result<Arc<AuctionState>, ApiError> {
+        Ok(self.state.get(|| self.client.auction_state()).await?)
+    }
+}
+
+async fn auction(State(app): State<Arc<App>>) -> ApiResult<AuctionView> {
+    let state = app.auction_state().await?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let address = pda::auction_state_in(state.auction_id);
+    Ok(Json(AuctionView::new(address, &state, now)))
+}
+
+async fn leaderboard(State(app): State<Arc<App>>) -> ApiResult<Vec<LeaderboardEntryView>> {
+    Ok(Json(view::leaderboard(&*app.auction_state().await?)))
+}
+
+#[derive(Deserialize)]
+struct BidQuery {
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+    /// Include inactive bids.
+    #[serde(default)]
+    all: bool,
+}
+
+/// Bids, highest first, `limit` (at most `MAX_PAGE`) at a time.
+async fn bids(State(app): State<Arc<App>>, Query(query): Query<BidQuery>) -> ApiResult<BidPage> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE).min(MAX_PAGE);
+    let (total, bids) = match &app.db {
+        Some(db) => db.bids(query.all, query.offset, limit).await?,
+        None => {
+            let bids = app.bids.get(|| app.client.bids()).await?;
+            let mut matching: Vec<_> = bids
+                .iter()
+                .filter(|(_, bid)| query.all || bid.active)
+                .collect();
+            matching.sort_by_key(|(address, bid)| (std::cmp::Reverse(bid.amount), *address));
+            let page = matching
+                .iter()
+                .skip(query.offset)
+                .take(limit)
+                .map(|(address, bid)| BidView::new(address, bid))
+                .collect();
+            (matching.len(), page)
+        }
+    };
+    Ok(Json(BidPage {
+        total,
+        offset: query.offset,
+        limit,
+        bids,
+    }))
+}
+
+async fn round(State(app): State<Arc<App>>, Path(id): Path<u64>) -> ApiResult<Arc<RoundView>> {
+    let fetch = || async {
+        let result = app.client.round_result(id).await?;
+        Ok::<_, ApiError>(result.as_ref().map(RoundView::from))
+    };
+    match app.rounds.get(id, fetch).await? {
+        Some(round) => Ok(Json(round)),
+        None => Err(ApiError::NotFound(format!("round {id}"))),
+    }
+}
@@ -0,0 +1,143 @@
+//! JSON shapes of the responses. Keys are base58 strings and amounts are
+//! base units of the auction mint.
+
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+use sovra_client::{AuctionState, Bid, RoundResult};
+
+#[derive(Serialize)]
+pub struct AuctionView {
+    pub address: String,
+    pub auction_id: u64,
+    pub agent: String,
+    pub usdc_mint: String,
+    pub treasury: String,
+    pub minimum_bid: u64,
+    pub bid_increment: u64,
+    pub active_bid_count: u64,
+    pub total_escrowed: u64,
+    pub leader: Option<String>,
+    pub leading_amount: u64,
+    pub start_time: i64,
+    /// Zero for an open-ended auction.
+    pub end_time: i64,
+    /// Status as of the request, so an open round past `end_time` reads as
+    /// closing before anyone has called `close_auction`.
+    pub status: String,
+    pub current_round: u64,
+    pub rounds_settled: u64,
+    pub total_bids_placed: u64,
+    pub total_volume: u128,
+}
+
+#[derive(Serialize)]
+pub struct LeaderboardEntryView {
+    /// One-based.
+    pub rank: usize,
+    pub bidder: String,
+    pub bid_index: u16,
+    pub amount: u64,
+    pub updated_at: i64,
+}
+
+#[derive(Serialize)]
+pub struct BidView {
+    pub address: String,
+    pub bidder: String,
+    pub amount: u64,
+    pub max_amount: u64,
+    pub proxy: bool,
+    pub active: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Serialize)]
+pub struct BidPage {
+    /// Bids matching the query, across all pages.
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub bids: Vec<BidView>,
+}
+
+#[derive(Serialize)]
+pub struct RoundView {
+    pub round: u64,
+    pub winner: String,
+    pub winner_count: u8,
+    pub amount: u64,
+    pub settled_at: i64,
+    /// Hex of the committed content hash; absent when none was committed.
+    pub item_hash: Option<String>,
+}
+
+impl AuctionView {
+    pub fn new(address: Pubkey, state: &AuctionState, now: i64) -> Self {
+        Self {
+            address: address.to_string(),
+            auction_id: state.auction_id,
+            agent: state.agent.to_string(),
+            usdc_mint: state.usdc_mint.to_string(),
+            treasury: state.treasury.to_string(),
+            minimum_bid: state.minimum_bid,
+            bid_increment: state.bid_increment,
+            active_bid_count: state.active_bid_count,
+            total_escrowed: state.total_escrowed,
+            leader: (state.leader != Pubkey::default()).then(|| state.leader.to_string()),
+            leading_amount: state.leading_amount,
+            start_time: state.start_time,
+            end_time: state.end_time,
+            status: format!("{:?}", state.status_at(now)),
+            current_round: state.current_round(),
+            rounds_settled: state.total_rounds_settled,
+            total_bids_placed: state.total_bids_placed,
+            total_volume: state.total_volume,
+        }
+    }
+}
+
+pub fn leaderboard(state: &AuctionState) -> Vec<LeaderboardEntryView> {
+    state
+        .leaderboard
+        .entries()
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| LeaderboardEntryView {
+            rank: index + 1,
+            bidder: entry.bidder.to_string(),
+            bid_index: entry.bid_index,
+            amount: entry.amount,
+            updated_at: entry.updated_at,
+        })
+        .collect()
+}
+
+impl BidView {
+    pub fn new(address: &Pubkey, bid: &Bid) -> Self {
+        Self {
+            address: address.to_string(),
+            bidder: bid.bidder.to_string(),
+            amount: bid.amount,
+            max_amount: bid.max_amount,
+            proxy: bid.proxy,
+            active: bid.active,
+            created_at: bid.created_at,
+            updated_at: bid.updated_at,
+        }
+    }
+}
+
+impl From<&RoundResult> for RoundView {
+    fn from(result: &RoundResult) -> Self {
+        Self {
+            round: result.round,
+            winner: result.winner.to_string(),
+            winner_count: result.winner_count,
+            amount: result.amount,
+            settled_at: result.settled_at,
+            item_hash: (result.item_hash != [0; 32])
+                .then(|| result.item_hash.iter().map(|b| format!("{b:02x}")).collect()),
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! `sovra export`: every program account read in one `getProgramAccounts`
+//! call, so all rows share the slot it was served at, and written out as
+//! three tables: `auctions`, `bids` and `leaderboard`. Each row carries that
+//! slot. Amounts are base units of the auction's mint.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use parquet::column::writer::ColumnWriter;
+use parquet::data_type::ByteArray;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{OptionalContext, RpcKeyedAccount};
+use sovra_client::{decode, AuctionState, Bid, SovraClient, PROGRAM_ID};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Json,
+    Csv,
+    Parquet,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+enum Values {
+    U64(Vec<u64>),
+    I64(Vec<i64>),
+    Bool(Vec<bool>),
+    Str(Vec<String>),
+}
+
+impl Values {
+    fn json(&self, row: usize) -> Value {
+        match self {
+            Self::U64(values) => values[row].into(),
+            Self::I64(values) => values[row].into(),
+            Self::Bool(values) => values[row].into(),
+            Self::Str(values) => values[row].clone().into(),
+        }
+    }
+
+    fn text(&self, row: usize) -> String {
+        match self {
+            Self::U64(values) => values[row].to_string(),
+            Self::I64(values) => values[row].to_string(),
+            Self::Bool(values) => values[row].to_string(),
+            Self::Str(values) => values[row].clone(),
+        }
+    }
+
+    /// Field of a Parquet message type.
+    fn parquet_field(&self, name: &str) -> String {
+        match self {
+            Self::U64(_) => format!("required INT64 {name} (INTEGER(64, false));"),
+            Self::I64(_) => format!("required INT64 {name};"),
+            Self::Bool(_) => format!("required BOOLEAN {name};"),
+            Self::Str(_) => format!("required BYTE_ARRAY {name} (STRING);"),
+        }
+    }
+}
+
+/// Named columns of equal length.
+struct Table {
+    name: &'static str,
+    rows: usize,
+    columns: Vec<(&'static str, Values)>,
+}
+
+impl Table {
+    fn new<T>(name: &'static str, items: &[T], columns: Vec<(&'static str, Values)>) -> Self {
+        Self {
+            name,
+            rows: items.len(),
+            columns,
+        }
+    }
+}
+
+/// One row as a JSON object, keyed in column order.
+struct JsonRow<'a> {
+    table: &'a Table,
+    slot: u64,
+    row: usize,
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.table.columns.len() + 1))?;
+        map.serialize_entry("slot", &self.slot)?;
+        for (name, values) in &self.table.columns {
+            map.serialize_entry(name, &values.json(self.row))?;
+        }
+        map.end()
+    }
+}
+
+macro_rules! column {
+    ($variant:ident, $items:expr, |$item:pat_param| $value:expr) => {
+        Values::$variant($items.iter().map(|$item| $value).collect())
+    };
+}
+
+/// Reads every program account and writes the tables into `out`, returning
+/// the slot they were read at.
+pub fn run(client: &SovraClient, out: &Path, format: Format) -> Result<u64> {
+    let config = RpcProgramAccountsConfig {
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(client.rpc().commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: Some(true),
+        ..RpcProgramAccountsConfig::default()
+    };
+    let response: OptionalContext<Vec<RpcKeyedAccount>> = client.rpc().send(
+        RpcRequest::GetProgramAccounts,
+        serde_json::json!([PROGRAM_ID.to_string(), config]),
+    )?;
+    let (slot, accounts) = match response {
+        OptionalContext::Context(response) => (response.context.slot, response.value),
+        OptionalContext::NoContext(accounts) => (client.rpc().get_slot()?, accounts),
+    };
+
+    let mut states = Vec::new();
+    let mut bids = Vec::new();
+    for keyed in accounts {
+        let Some(data) = keyed.account.data.decode() else {
+            continue;
+        };
+        let address: Pubkey = keyed.pubkey.parse()?;
+        if let Ok(bid) = decode::<Bid>(&data) {
+            bids.push((address, bid));
+        } else if let Ok(state) = decode::<AuctionState>(&data) {
+            states.push((address, state));
+        }
+    }
+    states.sort_by_key(|(_, state)| state.auction_id);
+    bids.sort_by_key(|(_, bid)| (bid.auction_id, std::cmp::Reverse(bid.amount)));
+
+    let now = match client.rpc().get_block_time(slot) {
+        Ok(time) => time,
+        Err(_) => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64,
+    };
+    std::fs::create_dir_all(out).with_context(|| format!("creating {}", out.display()))?;
+    for table in [auctions(&states, now), self::bids(&bids), leaderboard(&states)] {
+        let path = out.join(format!("{}.{}", table.name, format.extension()));
+        write(&table, slot, &path, format)
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(slot)
+}
+
+fn auctions(states: &[(Pubkey, AuctionState)], now: i64) -> Table {
+    Table::new(
+        "auctions",
+        states,
+        vec![
+            ("auction_id", column!(U64, states, |(_, s)| s.auction_id)),
+            ("address", column!(Str, states, |(address, _)| address.to_string())),
+            ("agent", column!(Str, states, |(_, s)| s.agent.to_string())),
+            ("mint", column!(Str, states, |(_, s)| s.usdc_mint.to_string())),
+            ("treasury", column!(Str, states, |(_, s)| s.treasury.to_string())),
+            ("status", column!(Str, states, |(_, s)| format!("{:?}", s.status_at(now)))),
+            ("minimum_bid", column!(U64, states, |(_, s)| s.minimum_bid)),
+            ("bid_increment", column!(U64, states, |(_, s)| s.bid_increment)),
+            ("active_bid_count", column!(U64, states, |(_, s)| s.active_bid_count)),
+            ("total_escrowed", column!(U64, states, |(_, s)| s.total_escrowed)),
+            ("leader", column!(Str, states, |(_, s)| s.leader.to_string())),
+            ("leading_amount", column!(U64, states, |(_, s)| s.leading_amount)),
+            ("start_time", column!(I64, states, |(_, s)| s.start_time)),
+            ("end_time", column!(I64, states, |(_, s)| s.end_time)),
+            ("current_round", column!(U64, states, |(_, s)| s.current_round())),
+            ("rounds_settled", column!(U64, states, |(_, s)| s.total_rounds_settled)),
+            ("total_bids_placed", column!(U64, states, |(_, s)| s.total_bids_placed)),
+        ],
+    )
+}
+
+fn bids(bids: &[(Pubkey, Bid)]) -> Table {
+    Table::new(
+        "bids",
+        bids,
+        vec![
+            ("auction_id", column!(U64, bids, |(_, b)| b.auction_id)),
+            ("address", column!(Str, bids, |(address, _)| address.to_string())),
+            ("bidder", column!(Str, bids, |(_, b)| b.bidder.to_string())),
+            ("bid_index", column!(U64, bids, |(_, b)| u64::from(b.bid_index))),
+            ("mint", column!(Str, bids, |(_, b)| b.mint.to_string())),
+            ("amount", column!(U64, bids, |(_, b)| b.amount)),
+            ("max_amount", column!(U64, bids, |(_, b)| b.max_amount)),
+            ("escrowed", column!(U64, bids, |(_, b)| b.escrowed())),
+            ("proxy", column!(Bool, bids, |(_, b)| b.proxy)),
+            ("active", column!(Bool, bids, |(_, b)| b.active)),
+            ("referrer", column!(Str, bids, |(_, b)| b.referrer.to_string())),
+            ("created_at", column!(I64, bids, |(_, b)| b.created_at)),
+            ("updated_at", column!(I64, bids, |(_, b)| b.updated_at)),
+        ],
+    )
+}
+
+fn leaderboard(states: &[(Pubkey, AuctionState)]) -> Table {
+    let entries: Vec<_> = states
+        .iter()
+        .flat_map(|(_, state)| {
+            state
+                .leaderboard
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| (state.auction_id, index as u64 + 1, *entry))
+        })
+        .collect();
+    Table::new(
+        "leaderboard",
+        &entries,
+        vec![
+            ("auction_id", column!(U64, entries, |(id, _, _)| *id)),
+            ("rank", column!(U64, entries, |(_, rank, _)| *rank)),
+            ("bidder", column!(Str, entries, |(_, _, e)| e.bidder.to_string())),
+            ("bid_index", column!(U64, entries, |(_, _, e)| u64::from(e.bid_index))),
+            ("amount", column!(U64, entries, |(_, _, e)| e.amount)),
+            ("updated_at", column!(I64, entries, |(_, _, e)| e.updated_at)),
+        ],
+    )
+}
+
+fn write(table: &Table, slot: u64, path: &Path, format: Format) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    match format {
+        Format::Json => {
+            let rows: Vec<_> = (0..table.rows).map(|row| JsonRow { table, slot, row }).collect();
+            serde_json::to_writer_pretty(&mut file, &rows)?;
+            writeln!(file)?;
+        }
+        // No value contains a comma, quote or newline, so none is quoted.
+        Format::Csv => {
+            let header: Vec<_> = table.columns.iter().map(|(name, _)| *name).collect();
+            writeln!(file, "slot,{}", header.join(","))?;
+            for row in 0..table.rows {
+                let fields: Vec<_> = table.columns.iter().map(|(_, v)| v.text(row)).collect();
+                writeln!(file, "{slot},{}", fields.join(","))?;
+            }
+        }
+        Format::Parquet => write_parquet(table, slot, file)?,
+    }
+    Ok(())
+}
+
+fn write_parquet(table: &Table, slot: u64, file: BufWriter<File>) -> Result<()> {
+    let slots = Values::U64(vec![slot; table.rows]);
+    let columns: Vec<_> = std::iter::once(("slot", &slots))
+        .chain(table.columns.iter().map(|(name, values)| (*name, values)))
+        .collect();
+    let fields: Vec<_> = columns.iter().map(|(name, values)| values.parquet_field(name)).collect();
+    let message = format!("message {} {{ {} }}", table.name, fields.join(" "));
+    let schema = Arc::new(parquet::schema::parser::parse_message_type(&message)?);
+    let mut writer = SerializedFileWriter::new(file, schema, Arc::new(WriterProperties::new()))?;
+    let mut row_group = writer.next_row_group()?;
+    let mut columns = columns.into_iter().map(|(_, values)| values);
+    while let Some(mut column) = row_group.next_column()? {
+        let values = columns.next().expect("a column per schema field");
+        match (column.untyped(), values) {
+            (ColumnWriter::Int64ColumnWriter(writer), Values::U64(values)) => {
+                let values: Vec<i64> = values.iter().map(|v| *v as i64).collect();
+                writer.write_batch(&values, None, None)?;
+            }
+            (ColumnWriter::Int64ColumnWriter(writer), Values::I64(values)) => {
+                writer.write_batch(values, None, None)?;
+            }
+            (ColumnWriter::BoolColumnWriter(writer), Values::Bool(values)) => {
+                writer.write_batch(values, None, None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(writer), Values::Str(values)) => {
+                let values: Vec<ByteArray> = values.iter().map(|v| v.as_str().into()).collect();
+                writer.write_batch(&values, None, None)?;
+            }
+            _ => unreachable!("column types follow the schema"),
+        }
+        column.close()?;
+    }
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AnchorDeserialize;
+    use anchor_lang::Space;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    use super::*;
+
+    /// A fresh directory under the system temp dir, removed when dropped.
+    struct TempDir(std::path::PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("sovra-export-{}-{name}", std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn table() -> Table {
+        let items = [(), ()];
+        Table::new(
+            "things",
+            &items,
+            vec![
+                ("count", Values::U64(vec![1, u64::MAX])),
+                ("time", Values::I64(vec![-5, 7])),
+                ("live", Values::Bool(vec![true, false])),
+                ("name", Values::Str(vec!["a".into(), "b".into()])),
+            ],
+        )
+    }
+
+    fn written(format: Format) -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new(format.extension());
+        let path = dir.0.join(format!("things.{}", format.extension()));
+        write(&table(), 42, &path, format).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn csv_has_a_header_and_a_line_per_row_with_the_slot() {
+        let (_dir, path) = written(Format::Csv);
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "slot,count,time,live,name\n\
+             42,1,-5,true,a\n\
+             42,18446744073709551615,7,false,b\n"
+        );
+    }
+
+    #[test]
+    fn json_is_an_array_of_objects_in_column_order() {
+        let (_dir, path) = written(Format::Json);
+        let text = std::fs::read_to_string(path).unwrap();
+        let first_row = &text[..text.find('}').unwrap()];
+        let positions: Vec<_> = ["slot", "count", "time", "live", "name"]
+            .iter()
+            .map(|key| first_row.find(&format!("\"{key}\"")).unwrap())
+            .collect();
+        assert!(positions.is_sorted());
+
+        let rows: Vec<Value> = serde_json::from_str(&text).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1],
+            serde_json::json!({
+                "slot": 42,
+                "count": u64::MAX,
+                "time": 7,
+                "live": false,
+                "name": "b",
+            })
+        );
+    }
+
+    #[test]
+    fn parquet_keeps_the_column_types() {
+        let (_dir, path) = written(Format::Parquet);
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let names: Vec<_> = schema
+            .columns()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        assert_eq!(names, ["slot", "count", "time", "live", "name"]);
+
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_ulong(0).unwrap(), 42);
+        assert_eq!(rows[1].get_ulong(1).unwrap(), u64::MAX);
+        assert_eq!(rows[0].get_long(2).unwrap(), -5);
+        assert!(rows[0].get_bool(3).unwrap());
+        assert_eq!(rows[1].get_string(4).unwrap(), "b");
+    }
+
+    #[test]
+    fn bid_rows_report_the_escrowed_amount() {
+        let mut bid = Bid::deserialize(&mut &vec![0; Bid::INIT_SPACE][..]).unwrap();
+        bid.auction_id = 3;
+        bid.amount = 40;
+        bid.max_amount = 50;
+        bid.balance_due = 45;
+        bid.active = true;
+        let table = bids(&[(Pubkey::new_unique(), bid)]);
+
+        assert_eq!(table.name, "bids");
+        assert_eq!(table.rows, 1);
+        let column = |name: &str| {
+            let (_, values) = table.columns.iter().find(|(n, _)| *n == name).unwrap();
+            values.text(0)
+        };
+        assert_eq!(column("auction_id"), "3");
+        assert_eq!(column("amount"), "40");
+        assert_eq!(column("escrowed"), "5");
+        assert_eq!(column("active"), "true");
+    }
+}
@@ -0,0 +1,756 @@
+mod export;
+mod replay;
+mod report;
+mod watch;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::TokenAccount;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::signer::null_signer::NullSigner;
+use solana_sdk::transaction::Transaction;
+use sovra_client::{
+    instructions, mint_safety, nonce, pda, AuctionKeys, ContentReveal, PriorityFees, ShareKeys,
+    SovraClient, USDC_DECIMALS,
+};
+
+#[derive(Parser)]
+#[command(name = "sovra", about = "Bid on and operate the cartoonist auction")]
+struct Cli {
+    /// RPC endpoint.
+    #[arg(long, short = 'u', env = "SOVRA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Signer keypair file; defaults to ~/.config/solana/id.json.
+    #[arg(long, short = 'k', env = "SOVRA_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    /// Which of the bidder's bids to act on; zero is their first.
+    #[arg(long, global = true, default_value_t = 0)]
+    bid_index: u16,
+    /// Pay priority fees, at most this many micro-lamports per compute unit,
+    /// and re-send at rising prices until the transaction lands.
+    #[arg(long, global = true, env = "SOVRA_MAX_PRIORITY_FEE")]
+    max_priority_fee: Option<u64>,
+    /// Build the transaction on this durable nonce account and print it
+    /// instead of sending it, to be finished with `sign` and `broadcast`.
+    #[arg(long, global = true)]
+    nonce: Option<Pubkey>,
+    /// Act as this key without its keypair, e.g. an agent key in cold
+    /// storage; its signature is left for `sign`. Needs `--nonce`.
+    #[arg(long, global = true, requires = "nonce")]
+    signer: Option<Pubkey>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the auction, with the signer as agent.
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Token account that receives winning bids.
+        #[arg(long)]
+        treasury: Pubkey,
+        /// Minimum bid in USDC, e.g. `1.5`.
+        #[arg(long)]
+        minimum_bid: String,
+        /// Token program owning the mint.
+        #[arg(long, default_value_t = anchor_spl::token::ID)]
+        token_program: Pubkey,
+        /// Accept a Token-2022 mint with a permanent delegate.
+        #[arg(long)]
+        allow_permanent_delegate: bool,
+        /// Accept a Token-2022 mint whose accounts start frozen.
+        #[arg(long)]
+        allow_default_frozen: bool,
+        /// Accept a pausable Token-2022 mint.
+        #[arg(long)]
+        allow_pausable: bool,
+    },
+    /// Place a bid, in USDC.
+    Bid {
+        amount: String,
+        /// Escrow `amount` as a ceiling and only bid what is needed to lead.
+        #[arg(long)]
+        proxy: bool,
+        /// Source token account; defaults to the signer's associated account.
+        #[arg(long)]
+        usdc: Option<Pubkey>,
+        /// Bid in an accepted stablecoin instead of the auction mint.
+        #[arg(long)]
+        mint: Option<Pubkey>,
+        /// Wallet that referred you, paid a share if the bid wins.
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+        /// Token account to receive a receipt token for the bid.
+        #[arg(long)]
+        receipt: Option<Pubkey>,
+    },
+    /// Add to an existing bid, in USDC.
+    Increase {
+        amount: String,
+        #[arg(long)]
+        usdc: Option<Pubkey>,
+    },
+    /// Pull part of an existing bid back, in USDC.
+    Decrease {
+        amount: String,
+        #[arg(long)]
+        usdc: Option<Pubkey>,
+    },
+    /// Pay the rest of a winning bid placed on a deposit.
+    Pay {
+        #[arg(long)]
+        usdc: Option<Pubkey>,
+    },
+    /// Withdraw the signer's bid, less any early-withdrawal penalty.
+    Withdraw {
+        #[arg(long)]
+        usdc: Option<Pubkey>,
+        /// Account holding the bid's receipt; defaults to the signer's associated account.
+        #[arg(long)]
+        receipt: Option<Pubkey>,
+    },
+    /// Keep the signer's bid for the next round if it loses, instead of
+    /// having it refunded.
+    Standing {
+        /// Stop carrying the bid over.
+        #[arg(long)]
+        off: bool,
+    },
+    /// Hand the signer's bid to another wallet, which co-signs.
+    TransferBid {
+        /// Keypair file of the new owner.
+        new_owner: PathBuf,
+    },
+    /// Refund a spam bid and send its bond to the treasury (moderator only).
+    SlashBid { bidder: Pubkey },
+    /// Settle the auction to the current leader (agent or settler).
+    Settle {
+        /// Winner to settle instead of the tracked leader.
+        #[arg(long)]
+        winner: Option<Pubkey>,
+    },
+    /// Release vested settled funds to the treasury.
+    ClaimFunds,
+    /// Commit the round to a deliverable by the hash of its URI (agent only).
+    CommitContent { uri: String },
+    /// Reveal the committed deliverable's URI once bidding closes (agent only).
+    RevealContent { uri: String },
+    /// Show the auction state.
+    Status,
+    /// List bids, highest first.
+    ListBids {
+        /// Include inactive bids.
+        #[arg(long)]
+        all: bool,
+    },
+    /// List auctions opened through `create_auction`, from the registry.
+    Auctions,
+    /// Watch the live round: leaderboard, deadline, escrow and events.
+    Watch {
+        /// Keeper wallet, to show its balance and last activity.
+        #[arg(long, env = "SOVRA_KEEPER")]
+        keeper: Option<Pubkey>,
+        /// Seconds between refreshes.
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+    },
+    /// Snapshot every auction, bid and leaderboard at one slot, for audits,
+    /// analytics or archiving a round.
+    Export {
+        /// Directory for `auctions`, `bids` and `leaderboard` files.
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+        #[arg(long, value_enum, default_value = "json")]
+        format: export::Format,
+    },
+    /// CSV of every escrow deposit, refund, settlement and fee between two
+    /// dates (UTC, both included), for accounting. Totals go to stderr.
+    Report {
+        /// First day, e.g. `2026-01-01`.
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day.
+        #[arg(long)]
+        to: NaiveDate,
+        /// Write the CSV here instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Rebuild the auction's bids from its transaction history and diff them
+    /// against the chain; fails if they differ. Needs an RPC node with the
+    /// full history.
+    Replay {
+        /// Also write every replayed transaction's logs here, one JSON object
+        /// per line, for `sovra-indexer --backfill`.
+        #[arg(long)]
+        transactions: Option<PathBuf>,
+    },
+    /// Create a durable nonce account for `--nonce`, paid for by the signer.
+    CreateNonce {
+        /// Keypair file for the new account's address.
+        nonce_keypair: PathBuf,
+        /// Key that advances the nonce and so must sign its transactions;
+        /// defaults to the signer.
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Add the signer's signature to a transaction printed by `--nonce`.
+    /// Needs no network, so it can run on an offline machine.
+    Sign { transaction: String },
+    /// Send a fully signed transaction printed by `--nonce` or `sign`.
+    Broadcast { transaction: String },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut client = SovraClient::new(&cli.url);
+    if let Some(max_price) = cli.max_priority_fee {
+        client = client.with_priority_fees(PriorityFees {
+            max_price,
+            ..PriorityFees::default()
+        });
+    }
+
+    match cli.command {
+        Command::Init {
+            mint,
+            treasury,
+            minimum_bid,
+            token_program,
+            allow_permanent_delegate,
+            allow_default_frozen,
+            allow_pausable,
+        } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let overrides = [
+                (allow_permanent_delegate, mint_safety::ALLOW_PERMANENT_DELEGATE),
+                (allow_default_frozen, mint_safety::ALLOW_DEFAULT_FROZEN),
+                (allow_pausable, mint_safety::ALLOW_PAUSABLE),
+            ];
+            let mint_overrides = overrides
+                .into_iter()
+                .filter(|(allowed, _)| *allowed)
+                .fold(0, |flags, (_, flag)| flags | flag);
+            let ix = instructions::initialize(
+                signer.pubkey(),
+                mint,
+                treasury,
+                token_program,
+                parse_usdc(&minimum_bid)?,
+                mint_overrides,
+            );
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("initialized {} ({sig})", pda::auction_state());
+        }
+        Command::Bid {
+            amount,
+            proxy,
+            usdc,
+            mint,
+            referrer,
+            receipt,
+        } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let bidder = signer.pubkey();
+            let state = client.auction_state()?;
+            let keys = match mint {
+                Some(mint) => client.keys_for_mint(&mint)?,
+                None => client.keys()?,
+            }
+            .with_bid_index(cli.bid_index);
+            let usdc = usdc.unwrap_or_else(|| token_account(&keys, &bidder));
+            let leader_bid = pda::leader_bid_at(&state, &bidder, cli.bid_index);
+            // English mode refunds the leader this bid displaces.
+            let leader_usdc = leader_bid
+                .filter(|_| state.english_auction)
+                .map(|_| token_account(&keys, &state.leader));
+            let amount = parse_usdc(&amount)?;
+            let ix = if proxy {
+                instructions::place_proxy_bid(
+                    &keys,
+                    bidder,
+                    usdc,
+                    amount,
+                    leader_bid,
+                    leader_usdc,
+                    referrer,
+                    receipt,
+                )
+            } else {
+                instructions::place_bid(
+                    &keys,
+                    bidder,
+                    usdc,
+                    amount,
+                    leader_bid,
+                    leader_usdc,
+                    referrer,
+                    receipt,
+                )
+            };
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("bid {} USDC ({sig})", format_usdc(amount));
+        }
+        Command::Increase { amount, usdc } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let bidder = signer.pubkey();
+            let state = client.auction_state()?;
+            let keys = bid_keys(&client, &bidder, cli.bid_index)?;
+            let usdc = usdc.unwrap_or_else(|| token_account(&keys, &bidder));
+            let amount = parse_usdc(&amount)?;
+            let ix = instructions::increase_bid(
+                &keys,
+                bidder,
+                usdc,
+                amount,
+                pda::leader_bid_at(&state, &bidder, cli.bid_index),
+            );
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("increased bid by {} USDC ({sig})", format_usdc(amount));
+        }
+        Command::Decrease { amount, usdc } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let bidder = signer.pubkey();
+            let state = client.auction_state()?;
+            let keys = bid_keys(&client, &bidder, cli.bid_index)?;
+            let usdc = usdc.unwrap_or_else(|| token_account(&keys, &bidder));
+            let amount = parse_usdc(&amount)?;
+            let ix = instructions::decrease_bid(
+                &keys,
+                bidder,
+                usdc,
+                amount,
+                pda::leader_bid_at(&state, &bidder, cli.bid_index),
+            );
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("decreased bid by {} USDC ({sig})", format_usdc(amount));
+        }
+        Command::Pay { usdc } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let bidder = signer.pubkey();
+            let keys = client.keys()?.with_bid_index(cli.bid_index);
+            let usdc = usdc.unwrap_or_else(|| token_account(&keys, &bidder));
+            let bid = client
+                .bid_at(&bidder, cli.bid_index)?
+                .ok_or_else(|| anyhow!("{bidder} has no bid"))?;
+            let ix = instructions::complete_payment(&keys, bidder, usdc);
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("paid {} USDC ({sig})", format_usdc(bid.balance_due));
+        }
+        Command::Withdraw { usdc, receipt } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let bidder = signer.pubkey();
+            let bid = client
+                .bid_at(&bidder, cli.bid_index)?
+                .ok_or_else(|| anyhow!("{bidder} has no bid"))?;
+            let keys = client.keys_for_mint(&bid.mint)?.with_bid_index(cli.bid_index);
+            let usdc = usdc.unwrap_or_else(|| token_account(&keys, &bidder));
+            let receipt = bid.receipt.then(|| {
+                receipt.unwrap_or_else(|| {
+                    get_associated_token_address_with_program_id(
+                        &bidder,
+                        &pda::receipt_mint_in(keys.auction_id),
+                        &keys.token_program,
+                    )
+                })
+            });
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs() as i64;
+            let state = client.auction_state()?;
+            // A bid holding points cannot close, so they are claimed first.
+            let ixs: Vec<_> = instructions::claim_points_due(&keys, bidder, &state, &bid, now)
+                .into_iter()
+                .chain([instructions::withdraw_bid(&keys, bidder, usdc, receipt)])
+                .collect();
+            let Some(sig) = send(&client, cli.nonce, &ixs, &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("withdrew bid ({sig})");
+        }
+        Command::Standing { off } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let keys = client.keys()?.with_bid_index(cli.bid_index);
+            let ix = instructions::set_standing(&keys, signer.pubkey(), !off);
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("bid {} standing ({sig})", if off { "no longer" } else { "now" });
+        }
+        Command::TransferBid { new_owner } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let new_owner = load_keypair(Some(new_owner))?;
+            let keys = client.keys()?.with_bid_index(cli.bid_index);
+            let ix = instructions::transfer_bid(&keys, signer.pubkey(), new_owner.pubkey());
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[&new_owner])? else {
+                return Ok(());
+            };
+            println!("transferred bid to {} ({sig})", new_owner.pubkey());
+        }
+        Command::SlashBid { bidder } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let state = client.auction_state()?;
+            let keys = bid_keys(&client, &bidder, cli.bid_index)?;
+            let treasury = client.rpc().get_account(&state.treasury)?;
+            let treasury_owner = TokenAccount::try_deserialize(&mut &treasury.data[..])?.owner;
+            let ix = instructions::slash_bid(
+                &keys,
+                signer.pubkey(),
+                bidder,
+                token_account(&keys, &bidder),
+                state.treasury,
+                treasury_owner,
+            );
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("slashed {bidder}'s bid ({sig})");
+        }
+        Command::Settle { winner } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let state = client.auction_state()?;
+            let (winner, bid_index) = match winner {
+                Some(winner) => (winner, cli.bid_index),
+                None => (state.leader, state.leader_index),
+            };
+            if winner == Pubkey::default() {
+                bail!("no bids to settle");
+            }
+            let bid = client
+                .bid_at(&winner, bid_index)?
+                .ok_or_else(|| anyhow!("{winner} has no bid"))?;
+            let keys = client.keys_for_mint(&bid.mint)?.with_bid_index(bid_index);
+            let winner_usdc = (bid.max_amount > bid.amount || bid.fee_discount_bps > 0)
+                .then(|| token_account(&keys, &winner));
+            let shares = ShareKeys::new(&keys, &state, &bid);
+            let prize = client.prize_keys(&state, &winner)?;
+            let winning_bid = pda::bid_at(keys.auction_id, &winner, bid_index);
+            let rivals: Vec<_> = client
+                .bids()?
+                .into_iter()
+                .filter(|(address, bid)| bid.active && *address != winning_bid)
+                .map(|(address, _)| address)
+                .collect();
+            let ix = instructions::settle(
+                &keys,
+                signer.pubkey(),
+                winner,
+                winner_usdc,
+                shares,
+                prize,
+                &rivals,
+            );
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("settled to {winner} for {} USDC ({sig})", format_usdc(bid.amount));
+        }
+        Command::ClaimFunds => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let keys = client.keys()?;
+            let ix = instructions::claim_settled_funds(&keys, signer.pubkey());
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("claimed vested funds ({sig})");
+        }
+        Command::CommitContent { uri } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let content_hash = ContentReveal::hash_uri(&uri);
+            let ix = instructions::commit_content(signer.pubkey(), content_hash);
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("committed {} ({sig})", hex(&content_hash));
+        }
+        Command::RevealContent { uri } => {
+            let signer = load_signer(&cli.keypair, cli.signer)?;
+            let state = client.auction_state()?;
+            if state.content_hash != ContentReveal::hash_uri(&uri) {
+                bail!("{uri} does not match the committed content hash");
+            }
+            let ix = instructions::reveal_content(signer.pubkey(), &state.content_hash, uri);
+            let Some(sig) = send(&client, cli.nonce, &[ix], &*signer, &[])? else {
+                return Ok(());
+            };
+            println!("revealed {} ({sig})", pda::content_reveal(&state.content_hash));
+        }
+        Command::Status => {
+            let state = client.auction_state()?;
+            println!("auction          {}", pda::auction_state());
+            println!("agent            {}", state.agent);
+            println!("mint             {}", state.usdc_mint);
+            println!("treasury         {}", state.treasury);
+            if state.curator_bps > 0 {
+                println!("curator          {} ({} bps)", state.curator, state.curator_bps);
+            }
+            println!("minimum bid      {}", format_usdc(state.minimum_bid));
+            println!("bid increment    {}", format_usdc(state.bid_increment));
+            println!("active bids      {}", state.active_bid_count);
+            println!("total escrowed   {}", format_usdc(state.total_escrowed));
+            for entry in client.accepted_mints()?.iter().flat_map(|a| a.entries()) {
+                println!(
+                    "accepted mint    {} ({} escrowed)",
+                    entry.mint,
+                    format_usdc(entry.total_escrowed)
+                );
+            }
+            if state.leader != Pubkey::default() {
+                println!(
+                    "leader           {} ({})",
+                    state.leader,
+                    format_usdc(state.leading_amount)
+                );
+            }
+            if state.start_time != 0 {
+                println!("start time       {}", state.start_time);
+            }
+            match state.end_time {
+                0 => println!("end time         open-ended"),
+                t => println!("end time         {t}"),
+            }
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs() as i64;
+            let status = state.status_at(now);
+            if status.is_called_off() {
+                println!("status           {status:?} (bids withdraw in full)");
+            } else {
+                println!("status           {status:?}");
+            }
+            if state.vesting_total > state.vesting_claimed {
+                println!(
+                    "vesting          {} of {} claimed",
+                    format_usdc(state.vesting_claimed),
+                    format_usdc(state.vesting_total)
+                );
+            }
+            if state.content_hash != [0; 32] {
+                println!("content hash     {}", hex(&state.content_hash));
+            }
+            println!("rounds settled   {}", state.total_rounds_settled);
+            println!("bids placed      {}", state.total_bids_placed);
+            println!("lifetime volume  {}", format_usdc(state.total_volume));
+            println!("highest bid      {}", format_usdc(state.highest_ever_bid));
+        }
+        Command::ListBids { all } => {
+            let mut bids: Vec<_> = client
+                .bids()?
+                .into_iter()
+                .filter(|(_, bid)| all || bid.active)
+                .collect();
+            bids.sort_by_key(|(_, bid)| std::cmp::Reverse(bid.amount));
+            for (_, bid) in bids {
+                let index = match bid.bid_index {
+                    0 => String::new(),
+                    index => format!(" #{index}"),
+                };
+                println!(
+                    "{:<44} {:>14} {:>14}{}{}{}",
+                    bid.bidder,
+                    format_usdc(bid.amount),
+                    format_usdc(bid.max_amount),
+                    index,
+                    if bid.proxy { " proxy" } else { "" },
+                    if bid.active { "" } else { " inactive" },
+                );
+            }
+        }
+        Command::Auctions => {
+            for entry in client.auctions()? {
+                println!(
+                    "{:>20} {:<10} {}",
+                    entry.auction_id,
+                    format!("{:?}", entry.status),
+                    pda::auction_state_in(entry.auction_id),
+                );
+            }
+        }
+        Command::Watch { keeper, interval } => {
+            watch::run(cli.url, keeper, Duration::from_secs(interval))?;
+        }
+        Command::Export { out, format } => {
+            let slot = export::run(&client, &out, format)?;
+            println!("exported slot {slot} to {}", out.display());
+        }
+        Command::Report { from, to, out } => match out {
+            Some(path) => {
+                let mut file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                report::run(&client, from, to, &mut file)?;
+                std::io::Write::flush(&mut file)?;
+            }
+            None => report::run(&client, from, to, &mut std::io::stdout().lock())?,
+        },
+        Command::Replay { transactions } => match transactions {
+            Some(path) => {
+                let mut file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                replay::run(&client, Some(&mut file))?;
+                std::io::Write::flush(&mut file)?;
+            }
+            None => replay::run(&client, None)?,
+        },
+        Command::CreateNonce {
+            nonce_keypair,
+            authority,
+        } => {
+            let signer = load_keypair(cli.keypair)?;
+            let nonce_account = load_keypair(Some(nonce_keypair))?;
+            let authority = authority.unwrap_or_else(|| signer.pubkey());
+            let rent = client
+                .rpc()
+                .get_minimum_balance_for_rent_exemption(nonce::NONCE_ACCOUNT_LEN)?;
+            let ixs = nonce::create_nonce_account(
+                &signer.pubkey(),
+                &nonce_account.pubkey(),
+                &authority,
+                rent,
+            );
+            let sig = client.send(&ixs, &signer, &[&nonce_account])?;
+            println!("created nonce account {} ({sig})", nonce_account.pubkey());
+        }
+        Command::Sign { transaction } => {
+            let signer = load_keypair(cli.keypair)?;
+            let mut tx = nonce::decode(&transaction)?;
+            let blockhash = tx.message.recent_blockhash;
+            tx.try_partial_sign(&[&signer], blockhash)
+                .with_context(|| format!("{} cannot sign this transaction", signer.pubkey()))?;
+            print_transaction(&tx);
+        }
+        Command::Broadcast { transaction } => {
+            let tx = nonce::decode(&transaction)?;
+            let missing = missing_signers(&tx);
+            if !missing.is_empty() {
+                bail!("not yet signed by {}", missing.join(", "));
+            }
+            let sig = client.rpc().send_and_confirm_transaction(&tx)?;
+            println!("sent {sig}");
+        }
+    }
+
+    Ok(())
+}
+
+fn load_keypair(path: Option<PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME").context("HOME not set")?)
+            .join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path).map_err(|err| anyhow!("reading {}: {err}", path.display()))
+}
+
+/// The `--keypair` signer, or a stand-in for `--signer` that leaves its
+/// signature blank.
+fn load_signer(keypair: &Option<PathBuf>, signer: Option<Pubkey>) -> Result<Box<dyn Signer>> {
+    Ok(match signer {
+        Some(pubkey) => Box::new(NullSigner::new(&pubkey)),
+        None => Box::new(load_keypair(keypair.clone())?),
+    })
+}
+
+/// Sends `instructions`, or with `--nonce` prints them as a transaction on
+/// the nonce, signed by whichever keys are at hand, and returns `None`.
+fn send(
+    client: &SovraClient,
+    nonce_account: Option<Pubkey>,
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+) -> Result<Option<Signature>> {
+    let Some(nonce_account) = nonce_account else {
+        return Ok(Some(client.send(instructions, payer, signers)?));
+    };
+    let mut tx = client.nonce_transaction(instructions, &payer.pubkey(), &nonce_account)?;
+    let mut all = vec![payer];
+    all.extend_from_slice(signers);
+    let blockhash = tx.message.recent_blockhash;
+    tx.try_partial_sign(&all, blockhash)?;
+    print_transaction(&tx);
+    Ok(None)
+}
+
+/// Prints `tx` for the next machine, noting whose signatures it still needs.
+fn print_transaction(tx: &Transaction) {
+    println!("{}", nonce::encode(tx));
+    let missing = missing_signers(tx);
+    if missing.is_empty() {
+        eprintln!("fully signed; send it with `sovra broadcast`");
+    } else {
+        eprintln!("still needs signatures from {}", missing.join(", "));
+    }
+}
+
+fn missing_signers(tx: &Transaction) -> Vec<String> {
+    let signer_keys = &tx.message.account_keys[..tx.signatures.len()];
+    signer_keys
+        .iter()
+        .zip(&tx.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| key.to_string())
+        .collect()
+}
+
+/// Keys for the mint `bidder`'s bid is escrowed in.
+fn bid_keys(client: &SovraClient, bidder: &Pubkey, bid_index: u16) -> Result<AuctionKeys> {
+    let bid = client
+        .bid_at(bidder, bid_index)?
+        .ok_or_else(|| anyhow!("{bidder} has no bid"))?;
+    Ok(client.keys_for_mint(&bid.mint)?.with_bid_index(bid_index))
+}
+
+fn token_account(keys: &AuctionKeys, owner: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, &keys.usdc_mint, &keys.token_program)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Parses a decimal USDC amount into base units.
+fn parse_usdc(amount: &str) -> Result<u64> {
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    if !(whole.chars().chain(frac.chars())).all(|c| c.is_ascii_digit()) {
+        bail!("{amount}: not a USDC amount");
+    }
+    if frac.len() > USDC_DECIMALS as usize {
+        bail!("{amount}: at most {USDC_DECIMALS} decimal places");
+    }
+    let scale = 10u64.pow(u32::from(USDC_DECIMALS));
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse()? };
+    let frac: u64 = format!("{frac:0<width$}", width = USDC_DECIMALS as usize).parse()?;
+    whole
+        .checked_mul(scale)
+        .and_then(|w| w.checked_add(frac))
+        .ok_or_else(|| anyhow!("{amount}: too large"))
+}
+
+fn format_usdc(amount: impl Into<u128>) -> String {
+    let amount = amount.into();
+    let scale = 10u128.pow(u32::from(USDC_DECIMALS));
+    format!(
+        "{}.{:0width$}",
+        amount / scale,
+        amount % scale,
+        width = USDC_DECIMALS as usize
+    )
+}
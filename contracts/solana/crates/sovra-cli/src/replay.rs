@@ -0,0 +1,431 @@
+//! `sovra replay`: rebuilds the auction's bids from its transaction history
+//! and diffs them against the accounts on chain.
+//!
+//! The history is every successful transaction that touched the auction's
+//! state account, oldest first, which every instruction on the auction
+//! does. Their events are applied in order, and only what the events fully
+//! determine is compared: which bids are active, at what amount, and the
+//! auction's `active_bid_count`. Events before the oldest signature the
+//! RPC node still serves cannot be replayed, so point it at a node with the
+//! full history. A bid that changes while the accounts are read can show up
+//! as a difference; running again tells the two apart.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, Context, Result};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::signature::Signature;
+use sovra_client::{parse_logs_counting_failures, pda, AuctionEvent, AuctionState, Bid, SovraClient};
+
+use crate::format_usdc;
+use crate::report::{account_keys, fetch};
+
+const SIGNATURE_PAGE: usize = 1_000;
+
+/// Replays the history and prints every difference, failing if there is
+/// one. With `transactions`, also writes each replayed transaction there as
+/// a JSON line of its signature, slot and logs.
+pub fn run(client: &SovraClient, mut transactions: Option<&mut dyn Write>) -> Result<()> {
+    let state = client.auction_state()?;
+    let on_chain: BTreeMap<Pubkey, Bid> =
+        client.bids()?.into_iter().filter(|(_, bid)| bid.active).collect();
+    let slot = client.rpc().get_slot()?;
+
+    let signatures = history(client, &pda::auction_state_in(state.auction_id), slot)?;
+    let mut replay = Replay {
+        auction_id: state.auction_id,
+        bids: BTreeMap::new(),
+    };
+    for signature in &signatures {
+        let tx = fetch(client, signature)?;
+        let meta = tx.transaction.meta.as_ref().context("transaction without status meta")?;
+        let logs: Option<Vec<String>> = meta.log_messages.clone().into();
+        let logs = logs.unwrap_or_default();
+        let (events, failures) = parse_logs_counting_failures(&logs);
+        if failures > 0 {
+            eprintln!("{signature}: {failures} undecodable events, skipped");
+        }
+        let keys = account_keys(&tx)?;
+        for event in &events {
+            replay.apply(event, &keys);
+        }
+        if let Some(out) = transactions.as_deref_mut() {
+            let line = serde_json::json!({
+                "signature": signature.to_string(),
+                "slot": tx.slot,
+                "logs": logs,
+            });
+            writeln!(out, "{line}")?;
+        }
+    }
+
+    println!("replayed {} transactions through slot {slot}", signatures.len());
+    let differences = replay.diff(&state, &on_chain);
+    for difference in &differences {
+        println!("{difference}");
+    }
+    if !differences.is_empty() {
+        bail!("{} differences between the replay and the chain", differences.len());
+    }
+    println!("{} active bids match the chain", on_chain.len());
+    Ok(())
+}
+
+/// Successful transactions touching `address` up to `max_slot`, oldest
+/// first.
+fn history(client: &SovraClient, address: &Pubkey, max_slot: u64) -> Result<Vec<Signature>> {
+    let mut found = Vec::new();
+    let mut before = None;
+    loop {
+        let page = client.rpc().get_signatures_for_address_with_config(
+            address,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                limit: Some(SIGNATURE_PAGE),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )?;
+        let Some(last) = page.last() else { break };
+        before = Some(last.signature.parse()?);
+        for status in &page {
+            if status.slot <= max_slot && status.err.is_none() {
+                found.push(status.signature.parse()?);
+            }
+        }
+        if page.len() < SIGNATURE_PAGE {
+            break;
+        }
+    }
+    found.reverse();
+    Ok(found)
+}
+
+struct Replayed {
+    bidder: Pubkey,
+    bid_index: u16,
+    amount: u64,
+    active: bool,
+}
+
+/// Bids as the events left them, keyed by account address.
+struct Replay {
+    auction_id: u64,
+    bids: BTreeMap<Pubkey, Replayed>,
+}
+
+impl Replay {
+    /// The bid `bidder` acted through in a transaction referencing `keys`:
+    /// the one at `bid_index` when the event names it, otherwise the
+    /// bidder's replayed bid among the transaction's accounts.
+    fn locate(&self, bidder: &Pubkey, bid_index: Option<u16>, keys: &[Pubkey]) -> Pubkey {
+        if let Some(bid_index) = bid_index {
+            return pda::bid_at(self.auction_id, bidder, bid_index);
+        }
+        keys.iter()
+            .copied()
+            .find(|key| self.bids.get(key).is_some_and(|bid| bid.bidder == *bidder))
+            .unwrap_or_else(|| pda::bid_at(self.auction_id, bidder, 0))
+    }
+
+    fn place(&mut self, bidder: Pubkey, bid_index: u16, amount: u64) {
+        let address = pda::bid_at(self.auction_id, &bidder, bid_index);
+        let bid = Replayed {
+            bidder,
+            bid_index,
+            amount,
+            active: true,
+        };
+        self.bids.insert(address, bid);
+    }
+
+    fn set_amount(
+        &mut self,
+        bidder: &Pubkey,
+        bid_index: Option<u16>,
+        amount: u64,
+        keys: &[Pubkey],
+    ) {
+        let address = self.locate(bidder, bid_index, keys);
+        if let Some(bid) = self.bids.get_mut(&address) {
+            bid.amount = amount;
+        }
+    }
+
+    fn deactivate(&mut self, bidder: &Pubkey, bid_index: Option<u16>, keys: &[Pubkey]) {
+        let address = self.locate(bidder, bid_index, keys);
+        if let Some(bid) = self.bids.get_mut(&address) {
+            bid.active = false;
+        }
+    }
+
+    fn apply(&mut self, event: &AuctionEvent, keys: &[Pubkey]) {
+        match event {
+            AuctionEvent::BidPlaced(e) => self.place(e.bidder, e.bid_index, e.amount),
+            AuctionEvent::BidRevealed(e) => {
+                self.set_amount(&e.bidder, Some(e.bid_index), e.amount, keys)
+            }
+            AuctionEvent::BidUpdated(e) => self.set_amount(&e.bidder, None, e.new_amount, keys),
+            AuctionEvent::BidIncreased(e) => self.set_amount(&e.bidder, None, e.new_amount, keys),
+            AuctionEvent::BidDecreased(e) => self.set_amount(&e.bidder, None, e.new_amount, keys),
+            // The bid moves to the new owner's seeds at the same index.
+            AuctionEvent::BidTransferred(e) => {
+                let from = self.locate(&e.from, None, keys);
+                let bid_index = self.bids.get(&from).map_or(0, |bid| bid.bid_index);
+                self.bids.remove(&from);
+                self.place(e.to, bid_index, e.amount);
+            }
+            AuctionEvent::BidWithdrawn(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::BidRefunded(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::BidSlashed(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::BidExpired(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::AllPayBidCollected(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::DepositForfeited(e) => self.deactivate(&e.bidder, None, keys),
+            AuctionEvent::BidSettled(e) => self.deactivate(&e.winner, None, keys),
+            AuctionEvent::TopKWinnerSettled(e) => {
+                self.deactivate(&e.bidder, Some(e.bid_index), keys)
+            }
+            AuctionEvent::BidForceRefunded(e) => {
+                self.deactivate(&e.bidder, Some(e.bid_index), keys)
+            }
+            AuctionEvent::WinnerDefaulted(e) => {
+                self.deactivate(&e.bidder, Some(e.bid_index), keys)
+            }
+            AuctionEvent::BidAccountClosed(e) => {
+                self.bids.remove(&pda::bid_at(self.auction_id, &e.bidder, e.bid_index));
+            }
+            _ => {}
+        }
+    }
+
+    fn diff(&self, state: &AuctionState, on_chain: &BTreeMap<Pubkey, Bid>) -> Vec<String> {
+        let replayed: BTreeMap<_, _> = self.bids.iter().filter(|(_, bid)| bid.active).collect();
+        let mut differences = Vec::new();
+        for (address, bid) in &replayed {
+            match on_chain.get(*address) {
+                None => differences.push(format!(
+                    "{address}: {}'s bid replays as active at {}, but is not active on chain",
+                    bid.bidder,
+                    format_usdc(bid.amount),
+                )),
+                Some(actual) if actual.amount != bid.amount => differences.push(format!(
+                    "{address}: {}'s bid replays at {}, but stands at {} on chain",
+                    bid.bidder,
+                    format_usdc(bid.amount),
+                    format_usdc(actual.amount),
+                )),
+                Some(_) => {}
+            }
+        }
+        for (address, bid) in on_chain {
+            if !replayed.contains_key(address) {
+                differences.push(format!(
+                    "{address}: {}'s bid is active on chain at {}, but not in the replay",
+                    bid.bidder,
+                    format_usdc(bid.amount),
+                ));
+            }
+        }
+        if state.active_bid_count != replayed.len() as u64 {
+            differences.push(format!(
+                "active_bid_count: {} on chain, {} replayed",
+                state.active_bid_count,
+                replayed.len(),
+            ));
+        }
+        differences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorDeserialize, Space};
+    use cartoonist_auction::event::*;
+
+    const AUCTION: u64 = 7;
+
+    fn replay(events: &[(AuctionEvent, Vec<Pubkey>)]) -> Replay {
+        let mut replay = Replay {
+            auction_id: AUCTION,
+            bids: BTreeMap::new(),
+        };
+        for (event, keys) in events {
+            replay.apply(event, keys);
+        }
+        replay
+    }
+
+    fn placed(bidder: Pubkey, bid_index: u16, amount: u64) -> (AuctionEvent, Vec<Pubkey>) {
+        let event = AuctionEvent::BidPlaced(BidPlaced {
+            bidder,
+            amount,
+            bid_index,
+        });
+        (event, vec![bid_at(&bidder, bid_index)])
+    }
+
+    fn bid_at(bidder: &Pubkey, bid_index: u16) -> Pubkey {
+        pda::bid_at(AUCTION, bidder, bid_index)
+    }
+
+    /// Active replayed bids as (address, bidder, amount).
+    fn active(replay: &Replay) -> Vec<(Pubkey, Pubkey, u64)> {
+        replay
+            .bids
+            .iter()
+            .filter(|(_, bid)| bid.active)
+            .map(|(address, bid)| (*address, bid.bidder, bid.amount))
+            .collect()
+    }
+
+    fn state(active_bid_count: u64) -> AuctionState {
+        let mut state =
+            AuctionState::deserialize(&mut &vec![0; AuctionState::INIT_SPACE][..]).unwrap();
+        state.auction_id = AUCTION;
+        state.active_bid_count = active_bid_count;
+        state
+    }
+
+    fn on_chain(bids: &[(Pubkey, u16, u64)]) -> BTreeMap<Pubkey, Bid> {
+        bids.iter()
+            .map(|&(bidder, bid_index, amount)| {
+                let mut bid = Bid::deserialize(&mut &vec![0; Bid::INIT_SPACE][..]).unwrap();
+                bid.auction_id = AUCTION;
+                bid.bidder = bidder;
+                bid.bid_index = bid_index;
+                bid.amount = amount;
+                bid.active = true;
+                (bid_at(&bidder, bid_index), bid)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn amounts_follow_the_bids_and_withdrawals_drop_them() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let increased = AuctionEvent::BidIncreased(BidIncreased {
+            bidder: alice,
+            amount: 100,
+            new_amount: 150,
+            max_amount: 150,
+        });
+        let withdrawn = AuctionEvent::BidWithdrawn(BidWithdrawn {
+            bidder: bob,
+            amount: 200,
+            penalty: 0,
+        });
+        let replay = replay(&[
+            placed(alice, 0, 100),
+            placed(bob, 0, 200),
+            (increased, vec![bid_at(&alice, 0)]),
+            (withdrawn, vec![bid_at(&bob, 0)]),
+        ]);
+
+        assert_eq!(active(&replay), [(bid_at(&alice, 0), alice, 150)]);
+        assert!(replay
+            .diff(&state(1), &on_chain(&[(alice, 0, 150)]))
+            .is_empty());
+    }
+
+    #[test]
+    fn an_event_without_an_index_acts_on_the_bid_in_the_transaction() {
+        let alice = Pubkey::new_unique();
+        let increased = AuctionEvent::BidIncreased(BidIncreased {
+            bidder: alice,
+            amount: 20,
+            new_amount: 25,
+            max_amount: 25,
+        });
+        let replay = replay(&[
+            placed(alice, 0, 10),
+            placed(alice, 1, 20),
+            (increased, vec![Pubkey::new_unique(), bid_at(&alice, 1)]),
+        ]);
+
+        let mut expected = vec![
+            (bid_at(&alice, 0), alice, 10),
+            (bid_at(&alice, 1), alice, 25),
+        ];
+        expected.sort();
+        assert_eq!(active(&replay), expected);
+    }
+
+    #[test]
+    fn a_transfer_moves_the_bid_to_the_new_owner_at_the_same_index() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transferred = AuctionEvent::BidTransferred(BidTransferred {
+            from: alice,
+            to: bob,
+            amount: 30,
+            max_amount: 30,
+        });
+        let replay = replay(&[
+            placed(alice, 0, 10),
+            placed(alice, 1, 30),
+            (transferred, vec![bid_at(&alice, 1), bid_at(&bob, 1)]),
+        ]);
+
+        let mut expected = vec![(bid_at(&alice, 0), alice, 10), (bid_at(&bob, 1), bob, 30)];
+        expected.sort();
+        assert_eq!(active(&replay), expected);
+    }
+
+    #[test]
+    fn settled_bids_stay_replayed_until_their_account_closes() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let settled = AuctionEvent::TopKWinnerSettled(TopKWinnerSettled {
+            auction_id: AUCTION,
+            rank: 0,
+            bidder: alice,
+            bid_index: 1,
+            price: 40,
+        });
+        let closed = AuctionEvent::BidAccountClosed(BidAccountClosed {
+            auction_id: AUCTION,
+            bidder: alice,
+            bid_index: 1,
+            lamports: 0,
+        });
+        let mut replay = replay(&[
+            placed(alice, 0, 10),
+            placed(alice, 1, 40),
+            placed(bob, 0, 20),
+            // Names the bid by index, whatever the transaction references.
+            (settled, vec![bid_at(&alice, 0)]),
+        ]);
+        assert!(!replay.bids[&bid_at(&alice, 1)].active);
+        assert!(replay.bids[&bid_at(&alice, 0)].active);
+
+        replay.apply(&closed, &[]);
+        assert!(!replay.bids.contains_key(&bid_at(&alice, 1)));
+        assert_eq!(replay.bids.len(), 2);
+    }
+
+    #[test]
+    fn diff_reports_every_disagreement_with_the_chain() {
+        let (alice, bob, carol) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let replay = replay(&[placed(alice, 0, 100), placed(bob, 0, 200)]);
+        let chain = on_chain(&[(alice, 0, 150), (carol, 0, 300)]);
+
+        let differences = replay.diff(&state(3), &chain);
+        assert_eq!(differences.len(), 4, "{differences:#?}");
+        let reported = |address: Pubkey, text: &str| {
+            differences
+                .iter()
+                .any(|d| d.starts_with(&address.to_string()) && d.contains(text))
+        };
+        assert!(reported(
+            bid_at(&alice, 0),
+            "replays at 0.000100, but stands at 0.000150"
+        ));
+        assert!(reported(bid_at(&bob, 0), "not active on chain"));
+        assert!(reported(bid_at(&carol, 0), "not in the replay"));
+        assert!(differences.contains(&"active_bid_count: 3 on chain, 2 replayed".to_string()));
+    }
+}
@@ -0,0 +1,539 @@
+//! `sovra report`: every token movement into or out of the auction's
+//! escrows over a date range, one CSV row per counterparty per transaction.
+//!
+//! Amounts come from the transactions' token balances, not from events, so
+//! the rows of a transaction always sum to the escrow's own change; a
+//! transaction where they do not (say, a Token-2022 transfer fee withheld
+//! in the escrow) is flagged on stderr. `escrow_balance` is the escrow's
+//! balance after the transaction, to tie the report to a statement.
+//!
+//! Inflows are deposits. Outflows are classified by where they went: an
+//! auction treasury is paid a settlement, or a fee when the transaction
+//! swept penalties, surplus or bonds instead; the charity, the curator and
+//! referrers are paid fees; anything else is a refund to a bidder.
+//! Treasuries are matched against the current ones, so payouts to a
+//! treasury since replaced show up as refunds.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiLoadedAddresses, UiTransactionEncoding,
+};
+use sovra_client::{parse_logs, AuctionEvent, SovraClient};
+
+use crate::format_usdc;
+
+const SIGNATURE_PAGE: usize = 1_000;
+
+/// Writes the report for `[from, to]`, both days included.
+pub fn run(
+    client: &SovraClient,
+    from: NaiveDate,
+    to: NaiveDate,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp();
+    let start = midnight(from);
+    let end = midnight(to.succ_opt().context("date out of range")?);
+
+    let state = client.auction_state()?;
+    let mut escrows = vec![(state.escrow, state.usdc_mint)];
+    let mut treasuries = HashSet::from([state.treasury]);
+    for entry in client.accepted_mints()?.iter().flat_map(|a| a.entries()) {
+        escrows.push((entry.escrow, entry.mint));
+        treasuries.insert(entry.treasury);
+    }
+
+    let mut rows = Vec::new();
+    for (escrow, mint) in escrows {
+        for (signature, time) in signatures(client, &escrow, start, end)? {
+            let tx = fetch(client, &signature)?;
+            let payees = Payees {
+                treasuries: &treasuries,
+                charity: state.charity,
+                curator: state.curator,
+            };
+            rows.extend(movements(&tx, &signature, time, &escrow, &mint, &payees)?);
+        }
+    }
+    rows.sort_by(|a, b| (a.slot, &a.signature).cmp(&(b.slot, &b.signature)));
+
+    writeln!(
+        out,
+        "time,slot,signature,mint,classification,counterparty,token_account,amount,escrow_balance"
+    )?;
+    let mut totals: BTreeMap<&str, u128> = BTreeMap::new();
+    for row in &rows {
+        *totals.entry(row.classification).or_default() += u128::from(row.amount);
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            DateTime::from_timestamp(row.time, 0).map(|t| t.to_rfc3339()).unwrap_or_default(),
+            row.slot,
+            row.signature,
+            row.mint,
+            row.classification,
+            row.counterparty,
+            row.token_account,
+            format_usdc(row.amount),
+            format_usdc(row.escrow_balance),
+        )?;
+    }
+    for (classification, total) in totals {
+        eprintln!("{classification:<12} {}", format_usdc(total));
+    }
+    Ok(())
+}
+
+/// Successful transactions touching `address` with a block time in
+/// `[start, end)`, newest first.
+fn signatures(
+    client: &SovraClient,
+    address: &Pubkey,
+    start: i64,
+    end: i64,
+) -> Result<Vec<(Signature, i64)>> {
+    let mut found = Vec::new();
+    let mut before = None;
+    loop {
+        let page = client.rpc().get_signatures_for_address_with_config(
+            address,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                limit: Some(SIGNATURE_PAGE),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )?;
+        let Some(last) = page.last() else {
+            return Ok(found);
+        };
+        before = Some(last.signature.parse()?);
+        for status in &page {
+            let Some(time) = status.block_time else { continue };
+            if time < start {
+                return Ok(found);
+            }
+            if time < end && status.err.is_none() {
+                found.push((status.signature.parse()?, time));
+            }
+        }
+        if page.len() < SIGNATURE_PAGE {
+            return Ok(found);
+        }
+    }
+}
+
+/// `signature` with its status meta and logs.
+pub fn fetch(
+    client: &SovraClient,
+    signature: &Signature,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    client
+        .rpc()
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(client.rpc().commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .with_context(|| format!("fetching {signature}"))
+}
+
+/// Every account `tx` references, indexed as its balances and instructions
+/// index them: the static keys, then those loaded from lookup tables.
+pub fn account_keys(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Result<Vec<Pubkey>> {
+    let meta = tx.transaction.meta.as_ref().context("transaction without status meta")?;
+    let decoded = tx.transaction.transaction.decode().context("undecodable transaction")?;
+    let mut keys: Vec<Pubkey> = decoded.message.static_account_keys().to_vec();
+    if let Some(loaded) = Option::<UiLoadedAddresses>::from(meta.loaded_addresses.clone()) {
+        for key in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(key.parse()?);
+        }
+    }
+    Ok(keys)
+}
+
+struct Payees<'a> {
+    treasuries: &'a HashSet<Pubkey>,
+    charity: Pubkey,
+    curator: Pubkey,
+}
+
+struct Row {
+    time: i64,
+    slot: u64,
+    signature: Signature,
+    mint: Pubkey,
+    classification: &'static str,
+    /// Owner of `token_account`.
+    counterparty: String,
+    token_account: Pubkey,
+    amount: u64,
+    escrow_balance: u64,
+}
+
+/// One row per token account whose balance in `mint` moved against the
+/// escrow's in `tx`.
+fn movements(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    signature: &Signature,
+    time: i64,
+    escrow: &Pubkey,
+    mint: &Pubkey,
+    payees: &Payees,
+) -> Result<Vec<Row>> {
+    let meta = tx.transaction.meta.as_ref().context("transaction without status meta")?;
+    let keys = account_keys(tx)?;
+
+    // Balance change and owner of every account holding `mint`.
+    let mut changes: BTreeMap<u8, (i128, String, u64)> = BTreeMap::new();
+    let pre = Option::<Vec<_>>::from(meta.pre_token_balances.clone()).unwrap_or_default();
+    let post = Option::<Vec<_>>::from(meta.post_token_balances.clone()).unwrap_or_default();
+    for (balances, sign) in [(pre, -1), (post, 1)] {
+        for balance in balances.into_iter().filter(|b| b.mint == mint.to_string()) {
+            let amount: u64 = balance.ui_token_amount.amount.parse()?;
+            let owner: Option<String> = balance.owner.into();
+            let change = changes.entry(balance.account_index).or_default();
+            change.0 += sign * i128::from(amount);
+            change.1 = owner.unwrap_or_default();
+            if sign > 0 {
+                change.2 = amount;
+            }
+        }
+    }
+    let key = |index: u8| keys.get(usize::from(index)).copied().unwrap_or_default();
+    let Some((&escrow_index, &(escrow_change, _, escrow_balance))) =
+        changes.iter().find(|(index, _)| key(**index) == *escrow)
+    else {
+        return Ok(Vec::new());
+    };
+
+    let logs: Option<Vec<String>> = meta.log_messages.clone().into();
+    let events = parse_logs(&logs.unwrap_or_default());
+    let swept = events.iter().any(|event| {
+        matches!(
+            event,
+            AuctionEvent::PenaltiesSwept(_)
+                | AuctionEvent::SurplusSwept(_)
+                | AuctionEvent::CommitBondsSwept(_)
+        )
+    });
+    let referrers: HashSet<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            AuctionEvent::ReferralPaid(paid) => Some(paid.referrer.to_string()),
+            _ => None,
+        })
+        .collect();
+    let curator = payees.curator.to_string();
+
+    let mut rows = Vec::new();
+    let mut total = 0i128;
+    for (index, (change, owner, _)) in &changes {
+        if *index == escrow_index || *change == 0 {
+            continue;
+        }
+        total += change;
+        let token_account = key(*index);
+        let classification = if *change < 0 {
+            "deposit"
+        } else if payees.treasuries.contains(&token_account) {
+            if swept {
+                "fee"
+            } else {
+                "settlement"
+            }
+        } else if token_account == payees.charity || *owner == curator || referrers.contains(owner)
+        {
+            "fee"
+        } else {
+            "refund"
+        };
+        rows.push(Row {
+            time,
+            slot: tx.slot,
+            signature: *signature,
+            mint: *mint,
+            classification,
+            counterparty: owner.clone(),
+            token_account,
+            amount: change.unsigned_abs() as u64,
+            escrow_balance,
+        });
+    }
+    if total != -escrow_change {
+        eprintln!(
+            "{signature}: counterparties moved {total} but the escrow moved {escrow_change}; \
+             check it by hand"
+        );
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde_json::json;
+    use solana_sdk::hash::{hash, Hash};
+    use solana_sdk::message::Message;
+    use solana_sdk::transaction::Transaction;
+    use sovra_client::PROGRAM_ID;
+
+    /// The accounts of a made-up transaction: the fee payer, the escrow,
+    /// then one token account per kind of counterparty.
+    struct Fixture {
+        mint: Pubkey,
+        treasuries: HashSet<Pubkey>,
+        charity: Pubkey,
+        curator: Pubkey,
+        referrer: Pubkey,
+        bidder: Pubkey,
+        keys: Vec<Pubkey>,
+    }
+
+    const ESCROW: u8 = 1;
+    const TREASURY: u8 = 2;
+    const CHARITY: u8 = 3;
+    const CURATOR: u8 = 4;
+    const REFERRER: u8 = 5;
+    const BIDDER: u8 = 6;
+
+    impl Fixture {
+        fn new() -> Self {
+            let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+            Self {
+                mint: Pubkey::new_unique(),
+                treasuries: HashSet::from([keys[usize::from(TREASURY)]]),
+                charity: keys[usize::from(CHARITY)],
+                curator: Pubkey::new_unique(),
+                referrer: Pubkey::new_unique(),
+                bidder: Pubkey::new_unique(),
+                keys,
+            }
+        }
+
+        fn owner(&self, index: u8) -> Pubkey {
+            match index {
+                CURATOR => self.curator,
+                REFERRER => self.referrer,
+                BIDDER => self.bidder,
+                _ => Pubkey::default(),
+            }
+        }
+
+        /// A transaction, as the RPC returns it, moving each account of
+        /// `balances` from its first amount to its second.
+        fn transaction(
+            &self,
+            balances: &[(u8, u64, u64)],
+            events: &[Vec<u8>],
+        ) -> EncodedConfirmedTransactionWithStatusMeta {
+            let message = Message::new_with_compiled_instructions(
+                1,
+                0,
+                0,
+                self.keys.clone(),
+                Hash::default(),
+                vec![],
+            );
+            let tx = Transaction {
+                signatures: vec![Signature::default()],
+                message,
+            };
+            let token_balances = |pick: fn(&(u8, u64, u64)) -> u64| {
+                balances
+                    .iter()
+                    .map(|balance| {
+                        json!({
+                            "accountIndex": balance.0,
+                            "mint": self.mint.to_string(),
+                            "uiTokenAmount": {
+                                "uiAmount": null,
+                                "decimals": 6,
+                                "amount": pick(balance).to_string(),
+                                "uiAmountString": "",
+                            },
+                            "owner": self.owner(balance.0).to_string(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let program = PROGRAM_ID.to_string();
+            let mut logs = vec![format!("Program {program} invoke [1]")];
+            logs.extend(
+                events
+                    .iter()
+                    .map(|event| format!("Program data: {}", STANDARD.encode(event))),
+            );
+            logs.push(format!("Program {program} success"));
+            serde_json::from_value(json!({
+                "slot": 42,
+                "blockTime": 1_700_000_000,
+                "transaction": [STANDARD.encode(bincode::serialize(&tx).unwrap()), "base64"],
+                "meta": {
+                    "err": null,
+                    "status": { "Ok": null },
+                    "fee": 5_000,
+                    "preBalances": [],
+                    "postBalances": [],
+                    "logMessages": logs,
+                    "preTokenBalances": token_balances(|b| b.1),
+                    "postTokenBalances": token_balances(|b| b.2),
+                },
+            }))
+            .unwrap()
+        }
+
+        fn movements(&self, tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<Row> {
+            let payees = Payees {
+                treasuries: &self.treasuries,
+                charity: self.charity,
+                curator: self.curator,
+            };
+            let escrow = self.keys[usize::from(ESCROW)];
+            movements(
+                tx,
+                &Signature::default(),
+                1_700_000_000,
+                &escrow,
+                &self.mint,
+                &payees,
+            )
+            .unwrap()
+        }
+    }
+
+    /// An event as the program logs it: its discriminator, then its fields.
+    fn event(name: &str, fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = hash(format!("event:{name}").as_bytes()).to_bytes()[..8].to_vec();
+        for field in fields {
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    fn classified(rows: &[Row]) -> Vec<(&'static str, Pubkey, u64)> {
+        rows.iter()
+            .map(|row| (row.classification, row.token_account, row.amount))
+            .collect()
+    }
+
+    #[test]
+    fn a_bid_is_a_deposit() {
+        let fixture = Fixture::new();
+        let tx = fixture.transaction(&[(ESCROW, 0, 100), (BIDDER, 250, 150)], &[]);
+        let rows = fixture.movements(&tx);
+
+        assert_eq!(classified(&rows), [("deposit", fixture.keys[6], 100)]);
+        assert_eq!(rows[0].counterparty, fixture.bidder.to_string());
+        assert_eq!((rows[0].slot, rows[0].escrow_balance), (42, 100));
+    }
+
+    #[test]
+    fn settling_pays_the_treasury_and_fees() {
+        let fixture = Fixture::new();
+        let tx = fixture.transaction(
+            &[
+                (ESCROW, 1_000, 0),
+                (TREASURY, 0, 850),
+                (CHARITY, 0, 50),
+                (CURATOR, 0, 60),
+                (REFERRER, 0, 40),
+            ],
+            &[event(
+                "ReferralPaid",
+                &[
+                    fixture.referrer.as_ref(),
+                    fixture.bidder.as_ref(),
+                    &40u64.to_le_bytes(),
+                ],
+            )],
+        );
+        let rows = fixture.movements(&tx);
+
+        let keys = &fixture.keys;
+        assert_eq!(
+            classified(&rows),
+            [
+                ("settlement", keys[2], 850),
+                ("fee", keys[3], 50),
+                ("fee", keys[4], 60),
+                ("fee", keys[5], 40),
+            ]
+        );
+        assert!(rows.iter().all(|row| row.escrow_balance == 0));
+        // The rows reconcile with the escrow.
+        assert_eq!(rows.iter().map(|row| row.amount).sum::<u64>(), 1_000);
+    }
+
+    #[test]
+    fn a_sweep_to_the_treasury_is_a_fee() {
+        let fixture = Fixture::new();
+        for name in ["PenaltiesSwept", "SurplusSwept", "CommitBondsSwept"] {
+            let tx = fixture.transaction(
+                &[(ESCROW, 500, 200), (TREASURY, 0, 300)],
+                &[event(name, &[&300u64.to_le_bytes()])],
+            );
+            assert_eq!(
+                classified(&fixture.movements(&tx)),
+                [("fee", fixture.keys[2], 300)],
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn anything_else_is_a_refund() {
+        let fixture = Fixture::new();
+        // Without its ReferralPaid event, the referrer is just another bidder.
+        let tx = fixture.transaction(
+            &[(ESCROW, 500, 200), (REFERRER, 0, 100), (BIDDER, 0, 200)],
+            &[],
+        );
+
+        assert_eq!(
+            classified(&fixture.movements(&tx)),
+            [
+                ("refund", fixture.keys[5], 100),
+                ("refund", fixture.keys[6], 200)
+            ]
+        );
+    }
+
+    #[test]
+    fn other_mints_and_untouched_escrows_are_skipped() {
+        let fixture = Fixture::new();
+        let tx = fixture.transaction(&[(ESCROW, 500, 500), (BIDDER, 0, 0)], &[]);
+        assert!(fixture.movements(&tx).is_empty());
+
+        // The escrow moved, but in a mint other than the one reported on.
+        let tx = fixture.transaction(&[(ESCROW, 500, 200), (BIDDER, 0, 300)], &[]);
+        let other = Fixture {
+            mint: Pubkey::new_unique(),
+            ..fixture
+        };
+        assert!(other.movements(&tx).is_empty());
+    }
+
+    #[test]
+    fn rows_still_come_when_the_escrow_does_not_reconcile() {
+        let fixture = Fixture::new();
+        // A transfer fee withheld in the escrow: it gains less than was sent.
+        let tx = fixture.transaction(&[(ESCROW, 0, 99), (BIDDER, 100, 0)], &[]);
+        let rows = fixture.movements(&tx);
+
+        assert_eq!(classified(&rows), [("deposit", fixture.keys[6], 100)]);
+        assert_eq!(rows[0].escrow_balance, 99);
+    }
+}
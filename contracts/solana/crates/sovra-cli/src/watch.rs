@@ -0,0 +1,384 @@
+//! `sovra watch`: a live terminal view of the auction for operators.
+//!
+//! A worker thread polls RPC and hands each [`Snapshot`] to the UI thread,
+//! so a slow endpoint never freezes the screen or the quit key.
+
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use sovra_client::{parse_logs, pda, AuctionEvent, AuctionState, AuctionStatus, SovraClient};
+
+use crate::format_usdc;
+
+/// Events kept on screen.
+const RECENT_EVENTS: usize = 50;
+/// Signatures fetched per poll; a busier auction shows only the latest.
+const SIGNATURE_PAGE: usize = 25;
+/// How long a closing auction may wait for settlement before it is flagged.
+const SETTLE_GRACE: i64 = 120;
+/// Keeper balance below which it may soon fail to pay fees.
+const LOW_KEEPER_LAMPORTS: u64 = LAMPORTS_PER_SOL / 20;
+
+struct Snapshot {
+    state: AuctionState,
+    /// Balance of the auction mint's escrow account.
+    escrow_balance: u64,
+    /// What the active bids in the auction mint have escrowed.
+    bid_escrow: u64,
+    keeper: Option<KeeperHealth>,
+    /// Events since the previous snapshot, oldest first.
+    events: Vec<(u64, AuctionEvent)>,
+}
+
+struct KeeperHealth {
+    address: Pubkey,
+    lamports: u64,
+    /// Block time of its latest transaction.
+    last_active: Option<i64>,
+}
+
+pub fn run(url: String, keeper: Option<Pubkey>, interval: Duration) -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || poll(SovraClient::new(&url), keeper, interval, sender));
+
+    let mut terminal = ratatui::init();
+    let result = ui(&mut terminal, receiver);
+    ratatui::restore();
+    result
+}
+
+fn poll(
+    client: SovraClient,
+    keeper: Option<Pubkey>,
+    interval: Duration,
+    sender: mpsc::Sender<Result<Snapshot>>,
+) {
+    let mut last = None;
+    loop {
+        if sender.send(snapshot(&client, keeper, &mut last)).is_err() {
+            return;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+fn snapshot(
+    client: &SovraClient,
+    keeper: Option<Pubkey>,
+    last: &mut Option<Signature>,
+) -> Result<Snapshot> {
+    let state = client.auction_state()?;
+    let escrow_balance = client
+        .rpc()
+        .get_token_account_balance(&state.escrow)?
+        .amount
+        .parse()?;
+    let bid_escrow = client
+        .bids()?
+        .iter()
+        .filter(|(_, bid)| bid.active && bid.mint == state.usdc_mint)
+        .map(|(_, bid)| bid.max_amount.saturating_sub(bid.balance_due))
+        .sum();
+    let keeper = keeper
+        .map(|address| -> Result<KeeperHealth> {
+            let lamports = client.rpc().get_balance(&address)?;
+            let latest = client.rpc().get_signatures_for_address_with_config(
+                &address,
+                GetConfirmedSignaturesForAddress2Config {
+                    limit: Some(1),
+                    ..GetConfirmedSignaturesForAddress2Config::default()
+                },
+            )?;
+            Ok(KeeperHealth {
+                address,
+                lamports,
+                last_active: latest.first().and_then(|status| status.block_time),
+            })
+        })
+        .transpose()?;
+    let events = new_events(client, state.auction_id, last)?;
+    Ok(Snapshot {
+        state,
+        escrow_balance,
+        bid_escrow,
+        keeper,
+        events,
+    })
+}
+
+/// Events from transactions on the auction since `last`, which is advanced.
+fn new_events(
+    client: &SovraClient,
+    auction_id: u64,
+    last: &mut Option<Signature>,
+) -> Result<Vec<(u64, AuctionEvent)>> {
+    let statuses = client.rpc().get_signatures_for_address_with_config(
+        &pda::auction_state_in(auction_id),
+        GetConfirmedSignaturesForAddress2Config {
+            until: *last,
+            limit: Some(SIGNATURE_PAGE),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        },
+    )?;
+    if let Some(newest) = statuses.first() {
+        *last = Some(newest.signature.parse()?);
+    }
+    let mut events = Vec::new();
+    for status in statuses.iter().rev().filter(|status| status.err.is_none()) {
+        let tx = client.rpc().get_transaction_with_config(
+            &status.signature.parse()?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(client.rpc().commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let logs: Option<Vec<String>> = tx
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages.into());
+        events.extend(
+            parse_logs(&logs.unwrap_or_default())
+                .into_iter()
+                .map(|event| (tx.slot, event)),
+        );
+    }
+    Ok(events)
+}
+
+fn ui(terminal: &mut DefaultTerminal, receiver: mpsc::Receiver<Result<Snapshot>>) -> Result<()> {
+    let mut latest: Option<Snapshot> = None;
+    let mut events = VecDeque::new();
+    let mut error = None;
+    loop {
+        while let Ok(update) = receiver.try_recv() {
+            match update {
+                Ok(mut snapshot) => {
+                    for event in snapshot.events.drain(..) {
+                        events.push_front(event);
+                    }
+                    events.truncate(RECENT_EVENTS);
+                    latest = Some(snapshot);
+                    error = None;
+                }
+                Err(err) => error = Some(format!("{err:#}")),
+            }
+        }
+        terminal.draw(|frame| draw(frame, latest.as_ref(), &events, error.as_deref()))?;
+
+        if event::poll(Duration::from_millis(250))? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                let quit = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) || ctrl_c;
+                if key.kind == KeyEventKind::Press && quit {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn draw(
+    frame: &mut Frame,
+    snapshot: Option<&Snapshot>,
+    events: &VecDeque<(u64, AuctionEvent)>,
+    error: Option<&str>,
+) {
+    let [header, body, recent, footer] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(8),
+        Constraint::Percentage(40),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [board, health] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(body);
+
+    let footer_line = match error {
+        Some(error) => Line::from(error.to_string()).red(),
+        None => Line::from("q to quit").dim(),
+    };
+    frame.render_widget(footer_line, footer);
+
+    let Some(snapshot) = snapshot else {
+        frame.render_widget(Paragraph::new("loading…").block(Block::bordered()), header);
+        return;
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64);
+    let state = &snapshot.state;
+
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw(format!("{:?}", state.status_at(now))).bold(),
+                Span::raw(format!("  round {}  ", state.current_round())),
+                deadline(state, now),
+            ]),
+            Line::from(format!(
+                "leader {}  {} USDC  ·  {} active bids",
+                if state.leader == Pubkey::default() {
+                    "none".to_string()
+                } else {
+                    state.leader.to_string()
+                },
+                format_usdc(state.leading_amount),
+                state.active_bid_count
+            )),
+        ])
+        .block(Block::bordered().title(format!(" auction {} ", state.auction_id))),
+        header,
+    );
+
+    let rows = state.leaderboard.entries().iter().enumerate().map(|(index, entry)| {
+        Row::new(vec![
+            format!("{}", index + 1),
+            entry.bidder.to_string(),
+            format!("{}", entry.bid_index),
+            format_usdc(entry.amount),
+        ])
+    });
+    frame.render_widget(
+        Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Min(44),
+                Constraint::Length(5),
+                Constraint::Length(16),
+            ],
+        )
+        .header(Row::new(["#", "bidder", "idx", "amount"]).add_modifier(Modifier::BOLD))
+        .block(Block::bordered().title(" leaderboard ")),
+        board,
+    );
+
+    frame.render_widget(
+        Paragraph::new(health_lines(snapshot, now)).block(Block::bordered().title(" health ")),
+        health,
+    );
+
+    let items: Vec<ListItem> = events
+        .iter()
+        .map(|(slot, event)| ListItem::new(format!("{slot:>11}  {}", describe(event))))
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title(" recent events ")),
+        recent,
+    );
+}
+
+fn deadline(state: &AuctionState, now: i64) -> Span<'static> {
+    if state.end_time == 0 {
+        return Span::raw("open-ended");
+    }
+    let left = state.end_time - now;
+    if left > 0 {
+        let style = if left < 60 {
+            Style::new().fg(Color::Yellow)
+        } else {
+            Style::new()
+        };
+        Span::styled(format!("ends in {}", duration(left)), style)
+    } else {
+        Span::raw(format!("ended {} ago", duration(-left)))
+    }
+}
+
+fn health_lines(snapshot: &Snapshot, now: i64) -> Vec<Line<'static>> {
+    let state = &snapshot.state;
+    let mut lines = Vec::new();
+
+    let escrow = Line::from(format!("escrow      {}", format_usdc(snapshot.escrow_balance)));
+    lines.push(if snapshot.escrow_balance < state.total_escrowed {
+        escrow.red()
+    } else {
+        escrow
+    });
+    lines.push(Line::from(format!("recorded    {}", format_usdc(state.total_escrowed))));
+    let bids = Line::from(format!("active bids {}", format_usdc(snapshot.bid_escrow)));
+    lines.push(if snapshot.bid_escrow > snapshot.escrow_balance {
+        bids.red()
+    } else {
+        bids
+    });
+
+    let overdue = now - state.end_time;
+    if state.status_at(now) == AuctionStatus::Closing && overdue > SETTLE_GRACE {
+        lines.push(Line::from(format!("settlement overdue by {}", duration(overdue))).red());
+    }
+
+    lines.push(Line::from(""));
+    match &snapshot.keeper {
+        None => lines.push(Line::from("keeper      pass --keeper to track").dim()),
+        Some(keeper) => {
+            let address = keeper.address.to_string();
+            lines.push(Line::from(format!("keeper      {}…", &address[..8])));
+            let balance = Line::from(format!(
+                "balance     {:.4} SOL",
+                keeper.lamports as f64 / LAMPORTS_PER_SOL as f64
+            ));
+            lines.push(if keeper.lamports < LOW_KEEPER_LAMPORTS {
+                balance.red()
+            } else {
+                balance
+            });
+            lines.push(Line::from(match keeper.last_active {
+                Some(time) => format!("last tx     {} ago", duration(now - time)),
+                None => "last tx     never".to_string(),
+            }));
+        }
+    }
+    lines
+}
+
+fn describe(event: &AuctionEvent) -> String {
+    match event {
+        AuctionEvent::BidPlaced(e) => format!("bid      {} {}", e.bidder, format_usdc(e.amount)),
+        AuctionEvent::BidIncreased(e) => {
+            format!("raised   {} {}", e.bidder, format_usdc(e.new_amount))
+        }
+        AuctionEvent::BidDecreased(e) => {
+            format!("lowered  {} {}", e.bidder, format_usdc(e.new_amount))
+        }
+        AuctionEvent::Outbid(e) => {
+            format!("outbid   {} by {}", e.previous_leader, e.new_leader)
+        }
+        AuctionEvent::BidWithdrawn(e) => {
+            format!("withdrew {} {}", e.bidder, format_usdc(e.amount))
+        }
+        AuctionEvent::BidRefunded(e) => {
+            format!("refunded {} {}", e.bidder, format_usdc(e.amount))
+        }
+        AuctionEvent::BidSettled(e) => {
+            format!("settled  {} {}", e.winner, format_usdc(e.amount))
+        }
+        AuctionEvent::StatusChanged(e) => format!("status   {:?} → {:?}", e.from, e.to),
+        other => other.name().to_string(),
+    }
+}
+
+fn duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
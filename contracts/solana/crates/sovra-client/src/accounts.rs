@@ -0,0 +1,227 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use cartoonist_auction::state::{AcceptedMint, AuctionState, Bid};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+use crate::pda;
+use crate::Result;
+
+/// Decodes an Anchor account, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// `getProgramAccounts` filters matching every `Bid` account.
+pub fn bid_filters() -> Vec<RpcFilterType> {
+    vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        0,
+        Bid::DISCRIMINATOR,
+    ))]
+}
+
+/// Accounts shared by most instructions. Read them once from the auction
+/// state and pass them to the builders in [`crate::instructions`].
+#[derive(Clone, Copy, Debug)]
+pub struct AuctionKeys {
+    /// `AuctionState::auction_id`; bid PDAs are derived from it.
+    pub auction_id: u64,
+    pub auction_state: Pubkey,
+    pub escrow: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub treasury: Pubkey,
+    /// Owner of `usdc_mint` — SPL Token or Token-2022.
+    pub token_program: Pubkey,
+    /// Accepted-mints registry; set when `usdc_mint` is not the auction mint.
+    pub accepted_mints: Option<Pubkey>,
+    /// Round the next settlement closes, counting from one.
+    pub round: u64,
+    /// `RoundResult` the next settlement writes.
+    pub round_result: Pubkey,
+    /// The auction's `AuditLog`, once `initialize_audit_log` has created it.
+    pub audit_log: Option<Pubkey>,
+    /// The auction's `Denylist`, once `set_denylisted` has created it.
+    pub denylist: Option<Pubkey>,
+    /// The SPL Memo program, once `set_memo` has given the auction a memo.
+    pub memo_program: Option<Pubkey>,
+    /// `Bid::bid_index` of the bids addressed; zero unless set with
+    /// [`AuctionKeys::with_bid_index`].
+    pub bid_index: u16,
+    /// `Config::discount_mint`, when bids should claim a fee discount; set
+    /// with [`AuctionKeys::with_discount_mint`].
+    pub discount_mint: Option<Pubkey>,
+}
+
+impl AuctionKeys {
+    pub fn new(state: &AuctionState, token_program: Pubkey) -> Self {
+        Self {
+            auction_id: state.auction_id,
+            auction_state: pda::auction_state_in(state.auction_id),
+            escrow: state.escrow,
+            usdc_mint: state.usdc_mint,
+            treasury: state.treasury,
+            token_program,
+            accepted_mints: None,
+            round: state.current_round(),
+            round_result: pda::round_result(state.auction_id, state.current_round()),
+            audit_log: state
+                .audit_log_enabled
+                .then(|| pda::audit_log(state.auction_id)),
+            denylist: state
+                .denylist_enabled
+                .then(|| pda::denylist(state.auction_id)),
+            memo_program: (state.memo_len > 0).then_some(anchor_spl::memo::ID),
+            bid_index: 0,
+            discount_mint: None,
+        }
+    }
+
+    /// Keys addressing each bidder's bid `bid_index` rather than their first.
+    pub fn with_bid_index(&self, bid_index: u16) -> Self {
+        Self { bid_index, ..*self }
+    }
+
+    /// Keys placing bids with the bidder's associated token account for
+    /// `discount_mint`, held under `token_program`, so each bid snapshots
+    /// the fee tier its balance reaches.
+    pub fn with_discount_mint(&self, discount_mint: Pubkey) -> Self {
+        Self {
+            discount_mint: Some(discount_mint),
+            ..*self
+        }
+    }
+
+    /// Keys for bidding and settling in an accepted mint instead of the
+    /// auction mint.
+    pub fn for_accepted_mint(&self, entry: &AcceptedMint, token_program: Pubkey) -> Self {
+        Self {
+            escrow: entry.escrow,
+            usdc_mint: entry.mint,
+            treasury: entry.treasury,
+            token_program,
+            accepted_mints: Some(pda::accepted_mints_in(self.auction_id)),
+            ..*self
+        }
+    }
+}
+
+/// Accounts that hand an escrowed prize to the winner on settlement.
+#[derive(Clone, Copy, Debug)]
+pub struct PrizeKeys {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    /// The winner's associated token account for the prize mint.
+    pub winner_prize: Pubkey,
+    /// Owner of the prize mint — SPL Token or Token-2022.
+    pub token_program: Pubkey,
+}
+
+impl PrizeKeys {
+    /// `None` when the auction has no prize escrowed.
+    pub fn new(state: &AuctionState, winner: &Pubkey, token_program: Pubkey) -> Option<Self> {
+        (state.prize_amount > 0).then(|| Self {
+            vault: pda::prize_vault_in(state.auction_id, &state.prize_mint),
+            mint: state.prize_mint,
+            winner_prize: get_associated_token_address_with_program_id(
+                winner,
+                &state.prize_mint,
+                &token_program,
+            ),
+            token_program,
+        })
+    }
+}
+
+/// Recipients of the charity, curator and referral shares of a winning bid.
+/// Each is `None` unless a share is actually owed to it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShareKeys {
+    pub charity: Option<Pubkey>,
+    /// The curator's associated token account for the bid mint.
+    pub curator_usdc: Option<Pubkey>,
+    /// The referrer's associated token account for the bid mint.
+    pub referrer_usdc: Option<Pubkey>,
+}
+
+impl ShareKeys {
+    /// `keys` must be the keys for `bid`'s mint.
+    pub fn new(keys: &AuctionKeys, state: &AuctionState, bid: &Bid) -> Self {
+        Self::for_referrer(keys, state, &bid.referrer)
+    }
+
+    /// Recipients for releasing the settlement `state` is holding.
+    pub fn held(keys: &AuctionKeys, state: &AuctionState) -> Self {
+        Self::for_referrer(keys, state, &state.held_referrer)
+    }
+
+    fn for_referrer(keys: &AuctionKeys, state: &AuctionState, referrer: &Pubkey) -> Self {
+        let associated = |owner: &Pubkey| {
+            get_associated_token_address_with_program_id(owner, &keys.usdc_mint, &keys.token_program)
+        };
+        Self {
+            charity: (state.charity_bps > 0).then_some(state.charity),
+            curator_usdc: (state.curator_bps > 0).then(|| associated(&state.curator)),
+            referrer_usdc: (state.referral_bps > 0 && *referrer != Pubkey::default())
+                .then(|| associated(referrer)),
+        }
+    }
+}
+
+/// This round's ranked bids in list order, highest first, read from `bids`
+/// as returned by `bids()`. Stops early if a linked bid is missing.
+pub fn ranked_bids(state: &AuctionState, bids: &[(Pubkey, Bid)]) -> Vec<(Pubkey, Bid)> {
+    let mut ranked = Vec::new();
+    let mut cursor = state.ranked_head;
+    while let Some((key, bid)) = bids
+        .iter()
+        .find(|(key, bid)| *key == cursor && state.is_ranked(bid))
+    {
+        ranked.push((*key, bid.clone()));
+        cursor = bid.next_bid;
+    }
+    ranked
+}
+
+/// Neighbours to name when linking or unlinking a bid in the ranked list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RankHints {
+    pub prev_bid: Option<Pubkey>,
+    pub next_bid: Option<Pubkey>,
+}
+
+impl RankHints {
+    /// Where `link_bid` will accept `bid`: after every ranked bid at or above
+    /// its amount, or at the head if it leads. `bids` are the auction's bids,
+    /// as returned by `bids()`.
+    pub fn link(state: &AuctionState, bids: &[(Pubkey, Bid)], bid: &Bid) -> Self {
+        let ranked = |key: &Pubkey| {
+            bids.iter()
+                .find(|(address, candidate)| address == key && state.is_ranked(candidate))
+                .map(|(_, candidate)| candidate)
+        };
+        let mut prev_bid = None;
+        let mut cursor = state.ranked_head;
+        if !state.is_leader(bid.bidder, bid.bid_index) {
+            while let Some(current) = ranked(&cursor) {
+                if current.amount < bid.amount {
+                    break;
+                }
+                prev_bid = Some(cursor);
+                cursor = current.next_bid;
+            }
+        }
+        Self {
+            prev_bid,
+            next_bid: (cursor != Pubkey::default()).then_some(cursor),
+        }
+    }
+
+    /// The neighbours a ranked `bid` points at, as `unlink_bid` expects.
+    pub fn unlink(bid: &Bid) -> Self {
+        let some = |key: Pubkey| (key != Pubkey::default()).then_some(key);
+        Self {
+            prev_bid: some(bid.prev_bid),
+            next_bid: some(bid.next_bid),
+        }
+    }
+}
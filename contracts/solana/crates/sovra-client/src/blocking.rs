@@ -0,0 +1,416 @@
+use std::time::Instant;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use cartoonist_auction::state::{
+    AcceptedMints, AuctionState, AuditLog, Bid, BundleBid, Config, Denylist, Lot, LotBid, Operator,
+    PriceFeed, RegistryEntry, RegistryPage, RoundResult, WinReceipt, REGISTRY_PAGE_LEN,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::fees::{landed, writable_accounts, POLL_INTERVAL};
+use crate::{
+    bid_filters, decode, hooks, nonce, pda, AuctionKeys, ClientError, PriorityFees, PrizeKeys,
+    Result, PROGRAM_ID,
+};
+
+/// Blocking RPC client for the auction program.
+pub struct SovraClient {
+    rpc: RpcClient,
+    auction_id: u64,
+    priority_fees: Option<PriorityFees>,
+}
+
+impl SovraClient {
+    pub fn new(url: impl ToString) -> Self {
+        Self::from_rpc(RpcClient::new_with_commitment(
+            url.to_string(),
+            CommitmentConfig::confirmed(),
+        ))
+    }
+
+    pub fn from_rpc(rpc: RpcClient) -> Self {
+        Self {
+            rpc,
+            auction_id: 0,
+            priority_fees: None,
+        }
+    }
+
+    /// Points the client at an auction opened by `create_auction` instead
+    /// of auction zero.
+    pub fn with_auction_id(mut self, auction_id: u64) -> Self {
+        self.auction_id = auction_id;
+        self
+    }
+
+    /// Makes [`Self::send`] pay priority fees and re-send at rising prices
+    /// until confirmed; see [`PriorityFees`].
+    pub fn with_priority_fees(mut self, fees: PriorityFees) -> Self {
+        self.priority_fees = Some(fees);
+        self
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn auction_state(&self) -> Result<AuctionState> {
+        let address = pda::auction_state_in(self.auction_id);
+        let account = self
+            .rpc
+            .get_account_with_commitment(&address, self.rpc.commitment())?
+            .value
+            .ok_or(ClientError::AccountNotFound(address))?;
+        decode(&account.data)
+    }
+
+    /// Resolves the shared instruction accounts, including which token
+    /// program owns the configured mint.
+    pub fn keys(&self) -> Result<AuctionKeys> {
+        let state = self.auction_state()?;
+        let mint = self.rpc.get_account(&state.usdc_mint)?;
+        Ok(AuctionKeys::new(&state, mint.owner))
+    }
+
+    /// Like [`Self::keys`], but for bids in `mint`, which may be the auction
+    /// mint or one of its accepted mints.
+    pub fn keys_for_mint(&self, mint: &Pubkey) -> Result<AuctionKeys> {
+        let keys = self.keys()?;
+        if *mint == keys.usdc_mint {
+            return Ok(keys);
+        }
+        let accepted = self
+            .accepted_mints()?
+            .ok_or(ClientError::MintNotAccepted(*mint))?;
+        let entry = accepted.get(mint).ok_or(ClientError::MintNotAccepted(*mint))?;
+        let owner = self.rpc.get_account(mint)?.owner;
+        Ok(keys.for_accepted_mint(entry, owner))
+    }
+
+    /// The accepted-mints registry, or `None` before the agent adds a mint.
+    pub fn accepted_mints(&self) -> Result<Option<AcceptedMints>> {
+        self.rpc
+            .get_account_with_commitment(&pda::accepted_mints_in(self.auction_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// The config account, or `None` before the first `update_config`.
+    pub fn config(&self) -> Result<Option<Config>> {
+        self.rpc
+            .get_account_with_commitment(&pda::config_in(self.auction_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// The operator account, or `None` before `initialize_operator`.
+    pub fn operator(&self) -> Result<Option<Operator>> {
+        self.rpc
+            .get_account_with_commitment(&pda::operator(), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Every auction opened through `create_auction`, in creation order, read
+    /// from the registry pages; entry `i` sits on page
+    /// `i / REGISTRY_PAGE_LEN`.
+    pub fn auctions(&self) -> Result<Vec<RegistryEntry>> {
+        let Some(operator) = self.operator()? else {
+            return Ok(Vec::new());
+        };
+        let pages = registry_pages(&operator);
+        let accounts = self.rpc.get_multiple_accounts(&pages)?;
+        registry_entries(&pages, accounts)
+    }
+
+    /// Accounts delivering the escrowed prize to `winner`, if there is one.
+    pub fn prize_keys(&self, state: &AuctionState, winner: &Pubkey) -> Result<Option<PrizeKeys>> {
+        if state.prize_amount == 0 {
+            return Ok(None);
+        }
+        let mint = self.rpc.get_account(&state.prize_mint)?;
+        Ok(PrizeKeys::new(state, winner, mint.owner))
+    }
+
+    /// The bidder's first bid account, or `None` if they have never bid or
+    /// closed it.
+    pub fn bid(&self, bidder: &Pubkey) -> Result<Option<Bid>> {
+        self.bid_at(bidder, 0)
+    }
+
+    /// The bidder's bid `bid_index`, as for [`SovraClient::bid`].
+    pub fn bid_at(&self, bidder: &Pubkey, bid_index: u16) -> Result<Option<Bid>> {
+        let address = pda::bid_at(self.auction_id, bidder, bid_index);
+        self.rpc
+            .get_account_with_commitment(&address, self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// The archived result of settled `round` (1-based), or `None` if the
+    /// auction has not settled that many rounds.
+    pub fn round_result(&self, round: u64) -> Result<Option<RoundResult>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::round_result(self.auction_id, round),
+                self.rpc.commitment(),
+            )?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// `winner`'s receipt for `round`, or `None` if they did not win it.
+    pub fn win_receipt(&self, round: u64, winner: &Pubkey) -> Result<Option<WinReceipt>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::win_receipt(self.auction_id, round, winner),
+                self.rpc.commitment(),
+            )?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Recent admin changes, or `None` if the auction keeps no log.
+    pub fn audit_log(&self) -> Result<Option<AuditLog>> {
+        self.rpc
+            .get_account_with_commitment(&pda::audit_log(self.auction_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Wallets barred from bidding, or `None` if the agent never set any.
+    pub fn denylist(&self) -> Result<Option<Denylist>> {
+        self.rpc
+            .get_account_with_commitment(&pda::denylist(self.auction_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Latest clearing price, or `None` before the first settlement.
+    pub fn price_feed(&self) -> Result<Option<PriceFeed>> {
+        self.rpc
+            .get_account_with_commitment(&pda::price_feed(self.auction_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Lot `lot_id`, or `None` if the auction has fewer lots.
+    pub fn lot(&self, lot_id: u16) -> Result<Option<Lot>> {
+        self.rpc
+            .get_account_with_commitment(&pda::lot(self.auction_id, lot_id), self.rpc.commitment())?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// `bidder`'s bid on lot `lot_id`, or `None` if they have none open.
+    pub fn lot_bid(&self, lot_id: u16, bidder: &Pubkey) -> Result<Option<LotBid>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::lot_bid(self.auction_id, lot_id, bidder),
+                self.rpc.commitment(),
+            )?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// `bidder`'s bundle bid, or `None` if they have none open.
+    pub fn bundle_bid(&self, bidder: &Pubkey) -> Result<Option<BundleBid>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::bundle_bid(self.auction_id, bidder),
+                self.rpc.commitment(),
+            )?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    /// Every bid account in this client's auction, active or not, keyed by
+    /// address.
+    pub fn bids(&self) -> Result<Vec<(Pubkey, Bid)>> {
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(&PROGRAM_ID, bids_config())?;
+        in_auction(accounts, self.auction_id)
+    }
+
+    /// Accounts to append to an instruction's remaining accounts so the
+    /// program can run `mint`'s transfer hook when it moves `amount` from
+    /// `source` to `destination`. Only forwarded once the agent has enabled
+    /// `set_transfer_hooks`.
+    pub fn transfer_hook_accounts(
+        &self,
+        mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Result<Vec<AccountMeta>> {
+        let mint_account = self.rpc.get_account(mint)?;
+        let fetch = |address: Pubkey| {
+            std::future::ready(
+                self.rpc
+                    .get_account_with_commitment(&address, self.rpc.commitment())
+                    .map(|response| response.value.map(|account| account.data))
+                    .map_err(Into::into),
+            )
+        };
+        hooks::ready(hooks::resolve(
+            mint,
+            &mint_account,
+            source,
+            destination,
+            authority,
+            amount,
+            fetch,
+        ))
+    }
+
+    /// The nonce and authority stored in `nonce_account`.
+    pub fn nonce(&self, nonce_account: &Pubkey) -> Result<nonce::Data> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(nonce_account, self.rpc.commitment())?
+            .value
+            .ok_or(ClientError::AccountNotFound(*nonce_account))?;
+        nonce::nonce_data(nonce_account, &account.data)
+    }
+
+    /// `instructions` as an unsigned transaction on `nonce_account`'s
+    /// current nonce, to be signed offline and broadcast whenever; see
+    /// [`crate::nonce`]. The nonce authority must sign it.
+    pub fn nonce_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+    ) -> Result<Transaction> {
+        let data = self.nonce(nonce_account)?;
+        Ok(nonce::nonce_transaction(
+            instructions,
+            payer,
+            nonce_account,
+            &data.authority,
+            data.blockhash(),
+        ))
+    }
+
+    /// Signs and sends `instructions`, waiting for confirmation. With
+    /// [`Self::with_priority_fees`], compute-budget instructions are added
+    /// and the transaction is re-sent at rising prices until it lands.
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &dyn Signer,
+        signers: &[&dyn Signer],
+    ) -> Result<Signature> {
+        let Some(fees) = self.priority_fees else {
+            let blockhash = self.rpc.get_latest_blockhash()?;
+            let tx = signed_transaction(instructions, payer, signers, blockhash);
+            return Ok(self.rpc.send_and_confirm_transaction(&tx)?);
+        };
+        let recent = self
+            .rpc
+            .get_recent_prioritization_fees(&writable_accounts(instructions))?;
+        let starting_price = fees.starting_price(&recent);
+        let mut sent = Vec::new();
+        for attempt in 0..fees.max_attempts {
+            let budgeted = fees.budgeted(instructions, fees.price(starting_price, attempt));
+            let blockhash = self.rpc.get_latest_blockhash()?;
+            let tx = signed_transaction(&budgeted, payer, signers, blockhash);
+            // A failed preflight may only mean an earlier attempt landed.
+            if let Err(err) = self.rpc.send_transaction(&tx) {
+                return self.landed(&sent)?.ok_or_else(|| err.into());
+            }
+            sent.push(tx.signatures[0]);
+            let deadline = Instant::now() + fees.confirm_timeout;
+            while Instant::now() < deadline {
+                std::thread::sleep(POLL_INTERVAL);
+                if let Some(signature) = self.landed(&sent)? {
+                    return Ok(signature);
+                }
+            }
+        }
+        Err(ClientError::NotConfirmed(fees.max_attempts))
+    }
+
+    fn landed(&self, sent: &[Signature]) -> Result<Option<Signature>> {
+        if sent.is_empty() {
+            return Ok(None);
+        }
+        let statuses = self.rpc.get_signature_statuses(sent)?.value;
+        landed(sent, &statuses, self.rpc.commitment())
+    }
+}
+
+pub(crate) fn bids_config() -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(bid_filters()),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    }
+}
+
+/// Registry page addresses holding the operator's auctions.
+pub(crate) fn registry_pages(operator: &Operator) -> Vec<Pubkey> {
+    let count = operator.auction_count.div_ceil(REGISTRY_PAGE_LEN as u64) as u32;
+    (0..count).map(pda::registry_page).collect()
+}
+
+pub(crate) fn registry_entries(
+    pages: &[Pubkey],
+    accounts: Vec<Option<solana_sdk::account::Account>>,
+) -> Result<Vec<RegistryEntry>> {
+    let mut entries = Vec::new();
+    for (address, account) in pages.iter().zip(accounts) {
+        let account = account.ok_or(ClientError::AccountNotFound(*address))?;
+        entries.extend(decode::<RegistryPage>(&account.data)?.entries);
+    }
+    Ok(entries)
+}
+
+/// Decodes fetched bid accounts, keeping those placed in `auction_id`.
+pub(crate) fn in_auction(
+    accounts: Vec<(Pubkey, solana_sdk::account::Account)>,
+    auction_id: u64,
+) -> Result<Vec<(Pubkey, Bid)>> {
+    let mut bids = Vec::new();
+    for (address, account) in accounts {
+        let bid: Bid = decode(&account.data)?;
+        if bid.auction_id == auction_id {
+            bids.push((address, bid));
+        }
+    }
+    Ok(bids)
+}
+
+pub(crate) fn signed_transaction(
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    blockhash: solana_sdk::hash::Hash,
+) -> Transaction {
+    let mut all: Vec<&dyn Signer> = vec![payer];
+    all.extend_from_slice(signers);
+    Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all, blockhash)
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] Box<solana_client::client_error::ClientError>),
+    #[error("failed to decode account: {0}")]
+    Decode(#[from] anchor_lang::error::Error),
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("mint {0} is not accepted by the auction")]
+    MintNotAccepted(Pubkey),
+    #[error("resolving transfer hook accounts: {0}")]
+    TransferHook(String),
+    #[error("transaction {0} failed: {1}")]
+    TransactionFailed(Signature, TransactionError),
+    #[error("transaction not confirmed after {0} attempts")]
+    NotConfirmed(u32),
+    #[error("block engine: {0}")]
+    Jito(String),
+    #[error("{0} is not an initialized nonce account")]
+    InvalidNonce(Pubkey),
+    #[error("malformed transaction: {0}")]
+    MalformedTransaction(String),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
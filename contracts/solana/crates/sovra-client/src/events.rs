@@ -0,0 +1,178 @@
+//! Decoding of the program's `emit!` events from transaction logs.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cartoonist_auction::event::*;
+use cartoonist_auction::ID;
+
+macro_rules! auction_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the auction program.
+        #[derive(Clone, Debug)]
+        pub enum AuctionEvent {
+            $($name($name),)*
+        }
+
+        impl AuctionEvent {
+            /// Decodes one event from its raw `Program data:` bytes.
+            /// Returns `None` for unknown discriminators.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(mut body) = data.strip_prefix($name::DISCRIMINATOR) {
+                        return $name::deserialize(&mut body).ok().map(Self::$name);
+                    }
+                )*
+                None
+            }
+
+            /// Event name as it appears in the IDL.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+auction_events!(
+    BidPlaced,
+    BidUpdated,
+    BidIncreased,
+    BidDecreased,
+    BidWithdrawn,
+    BidSettled,
+    PenaltiesSwept,
+    TreasuryChanged,
+    SurplusSwept,
+    BidRefunded,
+    TieBreakRequested,
+    TieResolved,
+    RaffleDrawRequested,
+    RaffleDrawn,
+    CrossChainBidReceived,
+    CrossChainRefund,
+    RefundRootPosted,
+    RefundClaimed,
+    ContentCommitted,
+    ContentRevealed,
+    PrizeDeposited,
+    PrizeDelivered,
+    PrizeWithdrawn,
+    SettledFundsStreamed,
+    SettledFundsClaimed,
+    PaymentCompleted,
+    DepositForfeited,
+    AcceptedMintAdded,
+    AcceptedMintRemoved,
+    WithheldFeesHarvested,
+    SettlerChanged,
+    AuctionAbandoned,
+    CuratorChanged,
+    ReferralPaid,
+    BidTransferred,
+    ModeratorChanged,
+    BidSlashed,
+    BidExpired,
+    StatusChanged,
+    AuctionCancelled,
+    Outbid,
+    CandleEndRequested,
+    CandleEndDetermined,
+    AllPayChanged,
+    AllPayBidCollected,
+    AuctionCreated,
+    AuctionClosed,
+    LotCreated,
+    LotBidPlaced,
+    LotBidWithdrawn,
+    LotSettled,
+    BundleBidPlaced,
+    BundleSettled,
+    AuctionScheduled,
+    StandingChanged,
+    MinimumBidRecalculated,
+    AgentChanged,
+    MinimumBidChanged,
+    EscrowMigrated,
+    TokensRescued,
+    BidLinked,
+    BidUnlinked,
+    TopKWinnerSettled,
+    BidAccountClosed,
+    DenylistChanged,
+    BidForceRefunded,
+    DisputeTermsChanged,
+    SettlementHeld,
+    DisputeOpened,
+    SettlementResolved,
+    WinClaimed,
+    WinnerDefaulted,
+    PointsClaimed,
+    RebatePaid,
+    FeeDiscounted,
+    SessionKeySet,
+    BidRevealed,
+    CommitBondsSwept,
+    RaffleModeChanged,
+    UniformPriceChanged,
+    BuyNowPriceChanged,
+    CandleWindowChanged,
+    BidTtlChanged,
+    TransferHooksChanged,
+    EnglishAuctionChanged,
+    NumWinnersChanged,
+    VestingDurationChanged,
+    Paused,
+    Unpaused,
+);
+
+/// Extracts the auction program's events from a transaction's log messages,
+/// in emission order. Invocation depth is tracked so `Program data:` lines
+/// logged by other programs in the same transaction are ignored.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<AuctionEvent> {
+    parse_logs_counting_failures(logs).0
+}
+
+/// [`parse_logs`], plus the number of the program's `Program data:` lines
+/// that did not decode, e.g. events added by a program upgrade this client
+/// predates.
+pub fn parse_logs_counting_failures<S: AsRef<str>>(logs: &[S]) -> (Vec<AuctionEvent>, usize) {
+    let program_id = ID.to_string();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+    let mut failures = 0;
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(rest) = line.strip_prefix("Program ") {
+            if let Some(data) = rest.strip_prefix("data: ") {
+                if stack.last() == Some(&true) {
+                    match STANDARD
+                        .decode(data)
+                        .ok()
+                        .and_then(|bytes| AuctionEvent::decode(&bytes))
+                    {
+                        Some(event) => events.push(event),
+                        None => failures += 1,
+                    }
+                }
+                continue;
+            }
+            let mut words = rest.split_whitespace();
+            let (Some(program), Some(action)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => stack.push(program == program_id),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    (events, failures)
+}
@@ -0,0 +1,242 @@
+//! Priority fees for transactions sent by the clients.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_client::rpc_response::RpcPrioritizationFee;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::TransactionStatus;
+
+use crate::{ClientError, Result};
+
+/// How often an attempt polls for its signatures.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How `send` prices and retries a transaction once set through
+/// `with_priority_fees`. The first attempt pays `percentile` of the fees
+/// recently paid to write the transaction's writable accounts; each
+/// attempt not confirmed within `confirm_timeout` is re-signed at twice the
+/// price, within `min_price..=max_price`. Earlier attempts stay valid until
+/// their blockhash expires, so whichever lands first is returned.
+#[derive(Clone, Copy, Debug)]
+pub struct PriorityFees {
+    pub percentile: u8,
+    /// Micro-lamports per compute unit.
+    pub min_price: u64,
+    pub max_price: u64,
+    pub compute_unit_limit: u32,
+    pub max_attempts: u32,
+    pub confirm_timeout: Duration,
+}
+
+impl Default for PriorityFees {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            min_price: 1_000,
+            max_price: 1_000_000,
+            compute_unit_limit: 400_000,
+            max_attempts: 5,
+            confirm_timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+impl PriorityFees {
+    /// Price of the first attempt, from `getRecentPrioritizationFees`.
+    pub fn starting_price(&self, recent: &[RpcPrioritizationFee]) -> u64 {
+        let mut fees: Vec<u64> = recent.iter().map(|fee| fee.prioritization_fee).collect();
+        fees.sort_unstable();
+        let rank = fees.len() * usize::from(self.percentile.min(100)) / 100;
+        let fee = fees.get(rank.min(fees.len().saturating_sub(1))).copied();
+        fee.unwrap_or(0).max(self.min_price).min(self.max_price)
+    }
+
+    /// Price of attempt `attempt`, counting from zero.
+    pub fn price(&self, starting_price: u64, attempt: u32) -> u64 {
+        starting_price
+            .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX))
+            .max(self.min_price)
+            .min(self.max_price)
+    }
+
+    /// `instructions` behind the compute-budget instructions for `price`.
+    pub(crate) fn budgeted(&self, instructions: &[Instruction], price: u64) -> Vec<Instruction> {
+        let mut budgeted = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(self.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(price),
+        ];
+        budgeted.extend_from_slice(instructions);
+        budgeted
+    }
+}
+
+/// Accounts the instructions write, which is what priority fees are
+/// contended on.
+pub(crate) fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let writable: BTreeSet<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect();
+    writable.into_iter().collect()
+}
+
+/// The first of `sent` to reach `commitment`, or its error if it failed.
+pub(crate) fn landed(
+    sent: &[Signature],
+    statuses: &[Option<TransactionStatus>],
+    commitment: CommitmentConfig,
+) -> Result<Option<Signature>> {
+    for (signature, status) in sent.iter().zip(statuses) {
+        let Some(status) = status else { continue };
+        if !status.satisfies_commitment(commitment) {
+            continue;
+        }
+        return match &status.err {
+            Some(err) => Err(ClientError::TransactionFailed(*signature, err.clone())),
+            None => Ok(Some(*signature)),
+        };
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::transaction::TransactionError;
+    use solana_transaction_status_client_types::TransactionConfirmationStatus;
+
+    use super::*;
+
+    fn recent(fees: &[u64]) -> Vec<RpcPrioritizationFee> {
+        fees.iter()
+            .enumerate()
+            .map(|(slot, &prioritization_fee)| RpcPrioritizationFee {
+                slot: slot as u64,
+                prioritization_fee,
+            })
+            .collect()
+    }
+
+    fn status(
+        confirmation_status: TransactionConfirmationStatus,
+        err: Option<TransactionError>,
+    ) -> Option<TransactionStatus> {
+        Some(TransactionStatus {
+            slot: 1,
+            confirmations: Some(1),
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: Some(confirmation_status),
+        })
+    }
+
+    #[test]
+    fn starting_price_takes_the_percentile_of_recent_fees() {
+        let fees = PriorityFees::default();
+        // Unsorted on purpose: 1_000 up to 10_000.
+        let recent = recent(&[
+            5_000, 1_000, 9_000, 2_000, 10_000, 3_000, 8_000, 4_000, 7_000, 6_000,
+        ]);
+        assert_eq!(fees.starting_price(&recent), 8_000);
+        assert_eq!(
+            PriorityFees {
+                percentile: 0,
+                ..fees
+            }
+            .starting_price(&recent),
+            1_000
+        );
+        assert_eq!(
+            PriorityFees {
+                percentile: 50,
+                ..fees
+            }
+            .starting_price(&recent),
+            6_000
+        );
+        assert_eq!(
+            PriorityFees {
+                percentile: 100,
+                ..fees
+            }
+            .starting_price(&recent),
+            10_000
+        );
+        assert_eq!(
+            PriorityFees {
+                percentile: 255,
+                ..fees
+            }
+            .starting_price(&recent),
+            10_000
+        );
+    }
+
+    #[test]
+    fn starting_price_stays_within_the_bounds() {
+        let fees = PriorityFees::default();
+        assert_eq!(fees.starting_price(&[]), fees.min_price);
+        assert_eq!(fees.starting_price(&recent(&[0, 10, 20])), fees.min_price);
+        assert_eq!(
+            fees.starting_price(&recent(&[5_000_000; 4])),
+            fees.max_price
+        );
+    }
+
+    #[test]
+    fn price_doubles_each_attempt_up_to_the_cap() {
+        let fees = PriorityFees::default();
+        let prices: Vec<u64> = (0..4).map(|attempt| fees.price(2_000, attempt)).collect();
+        assert_eq!(prices, [2_000, 4_000, 8_000, 16_000]);
+        assert_eq!(fees.price(2_000, 20), fees.max_price);
+        assert_eq!(fees.price(2_000, 64), fees.max_price);
+        assert_eq!(fees.price(u64::MAX, 1), fees.max_price);
+        assert_eq!(fees.price(0, 3), fees.min_price);
+    }
+
+    #[test]
+    fn landed_returns_the_first_attempt_to_reach_the_commitment() {
+        let sent = [
+            Signature::new_unique(),
+            Signature::new_unique(),
+            Signature::new_unique(),
+        ];
+        let statuses = [
+            None,
+            status(TransactionConfirmationStatus::Processed, None),
+            status(TransactionConfirmationStatus::Confirmed, None),
+        ];
+        let confirmed = CommitmentConfig::confirmed();
+        assert_eq!(landed(&sent, &statuses, confirmed).unwrap(), Some(sent[2]));
+        assert_eq!(
+            landed(&sent, &statuses, CommitmentConfig::processed()).unwrap(),
+            Some(sent[1])
+        );
+        assert_eq!(landed(&sent, &statuses[..2], confirmed).unwrap(), None);
+        assert_eq!(landed(&sent, &[None, None, None], confirmed).unwrap(), None);
+    }
+
+    #[test]
+    fn landed_reports_an_attempt_that_failed() {
+        let sent = [Signature::new_unique(), Signature::new_unique()];
+        let statuses = [
+            status(
+                TransactionConfirmationStatus::Confirmed,
+                Some(TransactionError::AccountInUse),
+            ),
+            status(TransactionConfirmationStatus::Confirmed, None),
+        ];
+        let result = landed(&sent, &statuses, CommitmentConfig::confirmed());
+        assert!(matches!(
+            result,
+            Err(ClientError::TransactionFailed(signature, TransactionError::AccountInUse))
+                if signature == sent[0]
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+//! Bidding from an SPL Governance DAO.
+//!
+//! A governance's native treasury is a system-owned PDA that SPL Governance
+//! signs for, through `invoke_signed`, when it executes a passed proposal.
+//! The auction program only asks that the bidder sign, so the treasury bids
+//! like any wallet:
+//!
+//! 1. Fund [`native_treasury`] with SOL for rent, then create and fund its
+//!    associated token account for the auction mint, [`treasury_usdc`].
+//! 2. Build the bid with [`place_bid`]. The treasury is both bidder and
+//!    payer, so every lamport and token it moves is its own.
+//! 3. Wrap the instruction with spl-governance's `InstructionData::from`,
+//!    add it to a proposal with `insert_transaction`, vote, and run it with
+//!    `execute_transaction` once the hold-up time has passed.
+//!
+//! Raising or withdrawing the bid takes another proposal built the same
+//! way; [`withdraw_bid`] covers the latter. Refunds after settlement need
+//! none, since `crank_refund` is permissionless and pays the treasury back.
+
+use anchor_lang::prelude::{pubkey, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use solana_sdk::instruction::Instruction;
+
+use crate::{instructions, AuctionKeys};
+
+/// The canonical SPL Governance deployment. DAOs running their own instance
+/// pass its address instead.
+pub const GOVERNANCE_PROGRAM_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+/// The SOL treasury of `governance` under `governance_program`.
+pub fn native_treasury(governance_program: &Pubkey, governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"native-treasury", governance.as_ref()],
+        governance_program,
+    )
+    .0
+}
+
+/// The native treasury's associated token account for `keys.usdc_mint`.
+pub fn treasury_usdc(
+    keys: &AuctionKeys,
+    governance_program: &Pubkey,
+    governance: &Pubkey,
+) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        &native_treasury(governance_program, governance),
+        &keys.usdc_mint,
+        &keys.token_program,
+    )
+}
+
+/// `place_bid` for the governance's native treasury, ready to be added to
+/// a proposal. `leader_bid` and `leader_usdc` are as in
+/// [`instructions::place_bid`], read when the proposal is drafted; should
+/// the lead change before it executes, the bid fails and needs a new one.
+pub fn place_bid(
+    keys: &AuctionKeys,
+    governance_program: &Pubkey,
+    governance: &Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+) -> Instruction {
+    instructions::place_bid(
+        keys,
+        native_treasury(governance_program, governance),
+        treasury_usdc(keys, governance_program, governance),
+        amount,
+        leader_bid,
+        leader_usdc,
+        None,
+        None,
+    )
+}
+
+/// `withdraw_bid` for the governance's native treasury.
+pub fn withdraw_bid(
+    keys: &AuctionKeys,
+    governance_program: &Pubkey,
+    governance: &Pubkey,
+) -> Instruction {
+    instructions::withdraw_bid(
+        keys,
+        native_treasury(governance_program, governance),
+        treasury_usdc(keys, governance_program, governance),
+        None,
+    )
+}
@@ -0,0 +1,76 @@
+//! Extra accounts for mints with the Token-2022 TransferHook extension.
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::instruction::transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use solana_sdk::account::Account;
+use spl_transfer_hook_interface::offchain::{
+    add_extra_account_metas_for_execute, AccountDataResult,
+};
+
+use crate::{ClientError, Result};
+
+/// Accounts the program forwards to the hook when it moves `amount` from
+/// `source` to `destination`: whatever the mint's extra-account-metas PDA
+/// lists, then the hook program and the PDA itself. Empty for mints without
+/// a hook.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn resolve<F, Fut>(
+    mint: &Pubkey,
+    mint_account: &Account,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    fetch: F,
+) -> Result<Vec<AccountMeta>>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let state = StateWithExtensions::<Mint>::unpack(&mint_account.data)
+        .map_err(|err| ClientError::TransferHook(err.to_string()))?;
+    let Some(program_id) = transfer_hook::get_program_id(&state) else {
+        return Ok(Vec::new());
+    };
+
+    let mut instruction = transfer_checked(
+        &mint_account.owner,
+        source,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        state.base.decimals,
+    )
+    .map_err(|err| ClientError::TransferHook(err.to_string()))?;
+    add_extra_account_metas_for_execute(
+        &mut instruction,
+        &program_id,
+        source,
+        mint,
+        destination,
+        authority,
+        amount,
+        fetch,
+    )
+    .await
+    .map_err(|err| ClientError::TransferHook(err.to_string()))?;
+    Ok(instruction.accounts.split_off(4))
+}
+
+/// Polls `future` once. The blocking client fetches accounts synchronously
+/// inside `std::future::ready`, so resolution never suspends.
+pub(crate) fn ready<T>(future: impl Future<Output = T>) -> T {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(value) => value,
+        Poll::Pending => unreachable!("blocking fetches never suspend"),
+    }
+}
@@ -0,0 +1,1823 @@
+//! Typed builders for the auction program's instructions.
+//!
+//! Bid-side builders take the bidder's wallet; the bid PDA is derived here,
+//! at [`AuctionKeys::bid_index`]. `leader_bid` is the current leader's bid
+//! account when another bid is leading — see [`crate::pda::leader_bid_at`].
+//! Builders that take no [`AuctionKeys`] address auction zero and each
+//! bidder's first bid.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use cartoonist_auction::{accounts, instruction, scheduler, ID};
+
+use crate::{
+    pda, AuctionConfig, AuctionKeys, AuctionState, Bid, ConfigUpdate, PrizeKeys, RankHints,
+    RegistryPage, ShareKeys,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(
+    agent: Pubkey,
+    usdc_mint: Pubkey,
+    treasury: Pubkey,
+    token_program: Pubkey,
+    minimum_bid: u64,
+    mint_overrides: u8,
+) -> Instruction {
+    build(
+        accounts::Initialize {
+            auction_state: pda::auction_state(),
+            usdc_mint,
+            treasury,
+            escrow: pda::escrow(),
+            agent,
+            token_program,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {
+            minimum_bid,
+            mint_overrides,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn place_bid_accounts(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    payer: Pubkey,
+    bidder_usdc: Pubkey,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+    bidder_receipt: Option<Pubkey>,
+) -> accounts::PlaceBid {
+    accounts::PlaceBid {
+        auction_state: keys.auction_state,
+        bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+        bidder_usdc,
+        escrow: keys.escrow,
+        usdc_mint: keys.usdc_mint,
+        bidder,
+        payer,
+        token_program: keys.token_program,
+        system_program: system_program::ID,
+        leader_bid,
+        accepted_mints: keys.accepted_mints,
+        receipt_mint: bidder_receipt.map(|_| pda::receipt_mint_in(keys.auction_id)),
+        bidder_receipt,
+        leader_usdc,
+        denylist: keys.denylist,
+        config: keys.discount_mint.map(|_| pda::config_in(keys.auction_id)),
+        bidder_discount: keys.discount_mint.map(|mint| {
+            get_associated_token_address_with_program_id(&bidder, &mint, &keys.token_program)
+        }),
+    }
+}
+
+/// `bidder_receipt`, the bidder's token account for [`pda::receipt_mint`],
+/// takes a receipt for the bid; withdrawing it will then burn the receipt.
+/// In English mode `leader_usdc`, the current leader's token account, is
+/// required to refund them when this bid takes the lead.
+#[allow(clippy::too_many_arguments)]
+pub fn place_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+    referrer: Option<Pubkey>,
+    bidder_receipt: Option<Pubkey>,
+) -> Instruction {
+    build(
+        place_bid_accounts(
+            keys,
+            bidder,
+            bidder,
+            bidder_usdc,
+            leader_bid,
+            leader_usdc,
+            bidder_receipt,
+        ),
+        instruction::PlaceBid {
+            bid_index: keys.bid_index,
+            amount,
+            referrer,
+        },
+    )
+}
+
+/// [`place_bid`] with `payer`, such as an app onboarding the bidder, paying
+/// the new bid's rent. Both sign; the bidder's tokens are still escrowed.
+#[allow(clippy::too_many_arguments)]
+pub fn sponsored_place_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    payer: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+) -> Instruction {
+    build(
+        place_bid_accounts(keys, bidder, payer, bidder_usdc, leader_bid, leader_usdc, None),
+        instruction::PlaceBid {
+            bid_index: keys.bid_index,
+            amount,
+            referrer: None,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn place_proxy_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    max_amount: u64,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+    referrer: Option<Pubkey>,
+    bidder_receipt: Option<Pubkey>,
+) -> Instruction {
+    build(
+        place_bid_accounts(
+            keys,
+            bidder,
+            bidder,
+            bidder_usdc,
+            leader_bid,
+            leader_usdc,
+            bidder_receipt,
+        ),
+        instruction::PlaceProxyBid {
+            bid_index: keys.bid_index,
+            max_amount,
+            referrer,
+        },
+    )
+}
+
+/// Places a bid of `amount`, or raises the bidder's existing bid to it,
+/// without first checking which applies. `leader_usdc` is as for
+/// [`place_bid`].
+pub fn upsert_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+    leader_usdc: Option<Pubkey>,
+) -> Instruction {
+    build(
+        place_bid_accounts(keys, bidder, bidder, bidder_usdc, leader_bid, leader_usdc, None),
+        instruction::UpsertBid {
+            bid_index: keys.bid_index,
+            amount,
+        },
+    )
+}
+
+fn update_bid_accounts(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    leader_bid: Option<Pubkey>,
+) -> accounts::UpdateBid {
+    accounts::UpdateBid {
+        auction_state: keys.auction_state,
+        bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+        bidder_usdc,
+        escrow: keys.escrow,
+        usdc_mint: keys.usdc_mint,
+        bidder,
+        token_program: keys.token_program,
+        leader_bid,
+        accepted_mints: keys.accepted_mints,
+        denylist: keys.denylist,
+        session: None,
+    }
+}
+
+/// Marks `signer` as signing `instruction`, for an account the program
+/// checks for a signature in the handler rather than its accounts struct.
+fn signed_by(mut instruction: Instruction, signer: Pubkey) -> Instruction {
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == signer {
+            meta.is_signer = true;
+        }
+    }
+    instruction
+}
+
+#[deprecated(note = "use `increase_bid` or `decrease_bid`")]
+pub fn update_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount_change: i64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = build(
+        update_bid_accounts(keys, bidder, bidder_usdc, leader_bid),
+        instruction::UpdateBid { amount_change },
+    );
+    signed_by(instruction, bidder)
+}
+
+pub fn increase_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = build(
+        update_bid_accounts(keys, bidder, bidder_usdc, leader_bid),
+        instruction::IncreaseBid { amount },
+    );
+    signed_by(instruction, bidder)
+}
+
+pub fn decrease_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = build(
+        update_bid_accounts(keys, bidder, bidder_usdc, leader_bid),
+        instruction::DecreaseBid { amount },
+    );
+    signed_by(instruction, bidder)
+}
+
+/// `bidder_receipt` is required when the bid was placed with a receipt.
+pub fn withdraw_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    bidder_receipt: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::WithdrawBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            payer: bidder,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            receipt_mint: bidder_receipt.map(|_| pda::receipt_mint_in(keys.auction_id)),
+            bidder_receipt,
+            memo_program: keys.memo_program,
+        },
+        instruction::WithdrawBid {},
+    )
+}
+
+/// Moves `bidder`'s bid to `new_owner`, at the same index; both must sign.
+pub fn transfer_bid(keys: &AuctionKeys, bidder: Pubkey, new_owner: Pubkey) -> Instruction {
+    build(
+        accounts::TransferBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            new_bid: pda::bid_at(keys.auction_id, &new_owner, keys.bid_index),
+            bidder,
+            payer: bidder,
+            new_owner,
+            system_program: system_program::ID,
+            denylist: keys.denylist,
+        },
+        instruction::TransferBid {},
+    )
+}
+
+/// `session_usdc` is the token account a session on the bid drew from,
+/// required while the bid has a session key so its allowance is revoked.
+pub fn close_bid(bidder: Pubkey, session_usdc: Option<Pubkey>, token_program: Pubkey) -> Instruction {
+    build(
+        accounts::CloseBid {
+            bid: pda::bid(&bidder),
+            bidder,
+            session_usdc,
+            token_program: session_usdc.map(|_| token_program),
+        },
+        instruction::CloseBid {},
+    )
+}
+
+pub fn buy_now(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    shares: ShareKeys,
+    prize: Option<PrizeKeys>,
+) -> Instruction {
+    build(
+        accounts::BuyNow {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            payer: bidder,
+            token_program: keys.token_program,
+            charity: shares.charity,
+            curator_usdc: shares.curator_usdc,
+            referrer_usdc: shares.referrer_usdc,
+            prize_vault: prize.map(|p| p.vault),
+            prize_mint: prize.map(|p| p.mint),
+            winner_prize: prize.map(|p| p.winner_prize),
+            prize_token_program: prize.map(|p| p.token_program),
+            round_result: keys.round_result,
+            win_receipt: pda::win_receipt(keys.auction_id, keys.round, &bidder),
+            price_feed: pda::price_feed(keys.auction_id),
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::BuyNow {},
+    )
+}
+
+/// `authority` is the agent or its settler. `winner_usdc` receives any
+/// escrow above the winning amount; it is required when the winner escrowed
+/// more than they pay. `shares` names whoever is owed part of the price, and
+/// `prize` is required when the agent has escrowed a prize. `rival_bids` must
+/// hold every other active bid account so the program can check none is
+/// higher; they are sorted here.
+pub fn settle(
+    keys: &AuctionKeys,
+    authority: Pubkey,
+    winner: Pubkey,
+    winner_usdc: Option<Pubkey>,
+    shares: ShareKeys,
+    prize: Option<PrizeKeys>,
+    rival_bids: &[Pubkey],
+) -> Instruction {
+    let mut rival_bids = rival_bids.to_vec();
+    rival_bids.sort();
+    let mut ix = build(
+        accounts::Settle {
+            auction_state: keys.auction_state,
+            winning_bid: pda::bid_at(keys.auction_id, &winner, keys.bid_index),
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            authority,
+            token_program: keys.token_program,
+            winner_usdc,
+            charity: shares.charity,
+            curator_usdc: shares.curator_usdc,
+            referrer_usdc: shares.referrer_usdc,
+            prize_vault: prize.map(|p| p.vault),
+            prize_mint: prize.map(|p| p.mint),
+            winner_prize: prize.map(|p| p.winner_prize),
+            prize_token_program: prize.map(|p| p.token_program),
+            accepted_mints: keys.accepted_mints,
+            round_result: keys.round_result,
+            win_receipt: pda::win_receipt(keys.auction_id, keys.round, &winner),
+            price_feed: pda::price_feed(keys.auction_id),
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::Settle {},
+    );
+    ix.accounts
+        .extend(rival_bids.into_iter().map(|bid| AccountMeta::new_readonly(bid, false)));
+    ix
+}
+
+/// Like [`settle`], but anyone may send it and `winner` must head the
+/// leaderboard. `rival_bids` must hold every other active bid account, or
+/// be empty once a raffle has been drawn; they are sorted here.
+pub fn crank_settle(
+    keys: &AuctionKeys,
+    cranker: Pubkey,
+    winner: Pubkey,
+    winner_usdc: Option<Pubkey>,
+    shares: ShareKeys,
+    prize: Option<PrizeKeys>,
+    rival_bids: &[Pubkey],
+) -> Instruction {
+    let mut rival_bids = rival_bids.to_vec();
+    rival_bids.sort();
+    let mut ix = build(
+        accounts::CrankSettle {
+            auction_state: keys.auction_state,
+            winning_bid: pda::bid_at(keys.auction_id, &winner, keys.bid_index),
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            winner_usdc,
+            charity: shares.charity,
+            curator_usdc: shares.curator_usdc,
+            referrer_usdc: shares.referrer_usdc,
+            prize_vault: prize.map(|p| p.vault),
+            prize_mint: prize.map(|p| p.mint),
+            winner_prize: prize.map(|p| p.winner_prize),
+            prize_token_program: prize.map(|p| p.token_program),
+            accepted_mints: keys.accepted_mints,
+            round_result: keys.round_result,
+            win_receipt: pda::win_receipt(keys.auction_id, keys.round, &winner),
+            price_feed: pda::price_feed(keys.auction_id),
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::CrankSettle {},
+    );
+    ix.accounts
+        .extend(rival_bids.into_iter().map(|bid| AccountMeta::new_readonly(bid, false)));
+    ix
+}
+
+pub fn crank_refund(
+    keys: &AuctionKeys,
+    cranker: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    build(
+        accounts::CrankRefund {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::CrankRefund {},
+    )
+}
+
+/// Has a Clockwork thread crank settlement of `keys.round` at the auction's
+/// end time. `amount` lamports from `agent` fund the thread's fees.
+pub fn schedule_settlement(keys: &AuctionKeys, agent: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::ScheduleSettlement {
+            auction_state: keys.auction_state,
+            agent,
+            thread: pda::settle_thread(keys.auction_id, keys.round),
+            thread_program: scheduler::THREAD_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::ScheduleSettlement { amount },
+    )
+}
+
+pub fn post_refund_root(authority: Pubkey, root: [u8; 32], leaf_count: u32) -> Instruction {
+    build(
+        accounts::PostRefundRoot {
+            auction_state: pda::auction_state(),
+            refund_claims: pda::refund_claims(&root),
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::PostRefundRoot { root, leaf_count },
+    )
+}
+
+/// Claims `bidder`'s leaf of the posted refunds tree; see [`crate::merkle::RefundTree`].
+pub fn claim_refund(
+    keys: &AuctionKeys,
+    root: &[u8; 32],
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::ClaimRefund {
+            auction_state: keys.auction_state,
+            refund_claims: pda::refund_claims_in(keys.auction_id, root),
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            payer: bidder,
+            token_program: keys.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::ClaimRefund {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+/// `audit_log` is required once the auction keeps one; see
+/// [`AuctionKeys::audit_log`].
+pub fn set_minimum_bid(agent: Pubkey, minimum_bid: u64, audit_log: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::SetMinimumBid {
+            auction_state: pda::auction_state(),
+            agent,
+            audit_log,
+        },
+        instruction::SetMinimumBid { minimum_bid },
+    )
+}
+
+pub fn set_end_time(agent: Pubkey, end_time: i64) -> Instruction {
+    build(
+        accounts::SetEndTime {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetEndTime { end_time },
+    )
+}
+
+pub fn set_deposit_terms(agent: Pubkey, deposit_bps: u16, payment_window: i64) -> Instruction {
+    build(
+        accounts::SetDepositTerms {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetDepositTerms {
+            deposit_bps,
+            payment_window,
+        },
+    )
+}
+
+pub fn set_vesting_duration(agent: Pubkey, vesting_duration: i64) -> Instruction {
+    build(
+        accounts::SetVestingDuration {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetVestingDuration { vesting_duration },
+    )
+}
+
+pub fn sweep_penalties(keys: &AuctionKeys, agent: Pubkey) -> Instruction {
+    build(
+        accounts::SweepPenalties {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            agent,
+            token_program: keys.token_program,
+        },
+        instruction::SweepPenalties {},
+    )
+}
+
+pub fn assert_solvent(keys: &AuctionKeys) -> Instruction {
+    build(
+        accounts::AssertSolvent {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            accepted_mints: keys.accepted_mints,
+        },
+        instruction::AssertSolvent {},
+    )
+}
+
+/// Commits the round to a deliverable; hash its URI with
+/// [`ContentReveal::hash_uri`](cartoonist_auction::state::ContentReveal::hash_uri).
+pub fn commit_content(agent: Pubkey, content_hash: [u8; 32]) -> Instruction {
+    build(
+        accounts::CommitContent {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::CommitContent { content_hash },
+    )
+}
+
+pub fn reveal_content(agent: Pubkey, content_hash: &[u8; 32], uri: String) -> Instruction {
+    build(
+        accounts::RevealContent {
+            auction_state: pda::auction_state(),
+            content_reveal: pda::content_reveal(content_hash),
+            agent,
+            system_program: system_program::ID,
+        },
+        instruction::RevealContent { uri },
+    )
+}
+
+/// Escrows `amount` of `prize_mint` from the agent's `agent_prize` account.
+/// `token_program` owns the prize mint.
+pub fn deposit_prize(
+    agent: Pubkey,
+    prize_mint: Pubkey,
+    agent_prize: Pubkey,
+    token_program: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::DepositPrize {
+            auction_state: pda::auction_state(),
+            prize_mint,
+            prize_vault: pda::prize_vault(&prize_mint),
+            agent_prize,
+            agent,
+            token_program,
+            system_program: system_program::ID,
+        },
+        instruction::DepositPrize { amount },
+    )
+}
+
+pub fn withdraw_prize(
+    agent: Pubkey,
+    prize_mint: Pubkey,
+    agent_prize: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawPrize {
+            auction_state: pda::auction_state(),
+            prize_mint,
+            prize_vault: pda::prize_vault(&prize_mint),
+            agent_prize,
+            agent,
+            token_program,
+        },
+        instruction::WithdrawPrize {},
+    )
+}
+
+/// Releases vested settled funds to the treasury. Anyone may send it.
+pub fn claim_settled_funds(keys: &AuctionKeys, cranker: Pubkey) -> Instruction {
+    build(
+        accounts::ClaimSettledFunds {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+        },
+        instruction::ClaimSettledFunds {},
+    )
+}
+
+/// Pays the winning bid's balance due when the auction takes deposits.
+pub fn complete_payment(keys: &AuctionKeys, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    build(
+        accounts::CompletePayment {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            token_program: keys.token_program,
+        },
+        instruction::CompletePayment {},
+    )
+}
+
+/// Forfeits an unpaid top bid's deposit after its payment window, refunding
+/// what the default penalty leaves to `bidder_usdc`, the bidder's associated
+/// token account.
+pub fn forfeit_deposit(
+    keys: &AuctionKeys,
+    cranker: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ForfeitDeposit {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ForfeitDeposit {},
+    )
+}
+
+/// Accepts bids in `mint`, paying winning bids in it to `treasury`.
+pub fn add_accepted_mint(
+    agent: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    build(
+        accounts::AddAcceptedMint {
+            auction_state: pda::auction_state(),
+            accepted_mints: pda::accepted_mints(),
+            mint,
+            escrow: pda::mint_escrow(&mint),
+            treasury,
+            agent,
+            token_program,
+            system_program: system_program::ID,
+        },
+        instruction::AddAcceptedMint {},
+    )
+}
+
+pub fn remove_accepted_mint(agent: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveAcceptedMint {
+            auction_state: pda::auction_state(),
+            accepted_mints: pda::accepted_mints(),
+            agent,
+        },
+        instruction::RemoveAcceptedMint { mint },
+    )
+}
+
+/// Harvests Token-2022 transfer fees withheld in the escrow for `keys.usdc_mint`
+/// into the mint. With `withdraw`, they continue on to the treasury, which only
+/// works when the auction is the mint's withdraw-withheld authority.
+pub fn harvest_withheld_fees(keys: &AuctionKeys, agent: Pubkey, withdraw: bool) -> Instruction {
+    build(
+        accounts::HarvestWithheldFees {
+            auction_state: keys.auction_state,
+            mint: keys.usdc_mint,
+            escrow: keys.escrow,
+            agent,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            treasury: withdraw.then_some(keys.treasury),
+        },
+        instruction::HarvestWithheldFees {},
+    )
+}
+
+/// Turns forwarding of remaining accounts to transfer hooks on or off. While
+/// on, append [`crate::SovraClient::transfer_hook_accounts`] to any
+/// instruction that moves tokens in a hooked mint.
+pub fn set_transfer_hooks(agent: Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetTransferHooks {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetTransferHooks { enabled },
+    )
+}
+
+/// Applies every `Some` field of `update` in one instruction, creating the
+/// config account on first use. `audit_log` is required to change the
+/// minimum bid once the auction keeps one.
+pub fn update_config(agent: Pubkey, update: ConfigUpdate, audit_log: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            auction_state: pda::auction_state(),
+            config: pda::config(),
+            agent,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::UpdateConfig { update },
+    )
+}
+
+/// Delegates settlement to `settler`; `Pubkey::default()` revokes it.
+pub fn set_settler(agent: Pubkey, settler: Pubkey) -> Instruction {
+    build(
+        accounts::SetSettler {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetSettler { settler },
+    )
+}
+
+/// Opens the round to `mark_abandoned` `abandonment_window` seconds past its
+/// deadline; zero disables it.
+pub fn set_abandonment_window(agent: Pubkey, abandonment_window: i64) -> Instruction {
+    build(
+        accounts::SetAbandonmentWindow {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetAbandonmentWindow { abandonment_window },
+    )
+}
+
+/// Marks an unsettled round abandoned once its abandonment window has passed.
+pub fn mark_abandoned(cranker: Pubkey) -> Instruction {
+    build(
+        accounts::MarkAbandoned {
+            auction_state: pda::auction_state(),
+            cranker,
+        },
+        instruction::MarkAbandoned {},
+    )
+}
+
+/// Pays `curator` `curator_bps` of each settlement; `Pubkey::default()`
+/// removes the curator.
+pub fn set_curator(agent: Pubkey, curator: Pubkey, curator_bps: u16) -> Instruction {
+    build(
+        accounts::SetCurator {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetCurator {
+            curator,
+            curator_bps,
+        },
+    )
+}
+
+/// Pays a winning bid's referrer `referral_bps` of the price.
+pub fn set_referral_bps(agent: Pubkey, referral_bps: u16) -> Instruction {
+    build(
+        accounts::SetReferralBps {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetReferralBps { referral_bps },
+    )
+}
+
+pub fn create_receipt_mint(keys: &AuctionKeys, agent: Pubkey) -> Instruction {
+    build(
+        accounts::CreateReceiptMint {
+            auction_state: keys.auction_state,
+            usdc_mint: keys.usdc_mint,
+            receipt_mint: pda::receipt_mint_in(keys.auction_id),
+            agent,
+            token_program: keys.token_program,
+            system_program: system_program::ID,
+        },
+        instruction::CreateReceiptMint {},
+    )
+}
+
+/// Lets `moderator` slash spam bids and has new bids lock `bid_bond`
+/// lamports; `Pubkey::default()` disables slashing.
+pub fn set_moderator(agent: Pubkey, moderator: Pubkey, bid_bond: u64) -> Instruction {
+    build(
+        accounts::SetModerator {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetModerator {
+            moderator,
+            bid_bond,
+        },
+    )
+}
+
+/// Refunds a spam bid and sends its bond to `treasury_owner`, the owner of
+/// the auction's `treasury` token account.
+pub fn slash_bid(
+    keys: &AuctionKeys,
+    moderator: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    treasury: Pubkey,
+    treasury_owner: Pubkey,
+) -> Instruction {
+    build(
+        accounts::SlashBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            treasury,
+            treasury_owner,
+            moderator,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SlashBid {},
+    )
+}
+
+/// Expires bids left untouched for `bid_ttl` seconds; zero turns expiry off.
+pub fn set_bid_ttl(agent: Pubkey, bid_ttl: i64) -> Instruction {
+    build(
+        accounts::SetBidTtl {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetBidTtl { bid_ttl },
+    )
+}
+
+/// Refunds and closes a bid that outlived the auction's `bid_ttl`.
+pub fn expire_bid(
+    keys: &AuctionKeys,
+    cranker: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExpireBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExpireBid {},
+    )
+}
+
+/// Calls off the round; bidders then withdraw in full.
+pub fn cancel_auction(agent: Pubkey) -> Instruction {
+    build(
+        accounts::CancelAuction {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::CancelAuction {},
+    )
+}
+
+/// Escrows only the leading bid; each bid that takes the lead refunds the
+/// one it displaces. Can only change while at most one bid is active.
+pub fn set_english_auction(agent: Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetEnglishAuction {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetEnglishAuction { enabled },
+    )
+}
+
+/// Makes the round a candle auction closing at a random moment in its last
+/// `candle_window` seconds; zero turns the mode off.
+pub fn set_candle_window(agent: Pubkey, candle_window: i64) -> Instruction {
+    build(
+        accounts::SetCandleWindow {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetCandleWindow { candle_window },
+    )
+}
+
+/// Switches all-pay mode, where no bid is refunded and losing bids go to
+/// the treasury after settlement. Only allowed with no active bids.
+pub fn set_all_pay(agent: Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetAllPay {
+            auction_state: pda::auction_state(),
+            agent,
+        },
+        instruction::SetAllPay { enabled },
+    )
+}
+
+/// Sends a losing all-pay bid's escrow to the treasury once the round has
+/// settled; `keys` must be the keys for the bid's mint.
+pub fn collect_all_pay_bid(keys: &AuctionKeys, cranker: Pubkey, bidder: Pubkey) -> Instruction {
+    build(
+        accounts::CollectAllPayBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+        },
+        instruction::CollectAllPayBid {},
+    )
+}
+
+pub fn initialize_operator(agent: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeOperator {
+            operator: pda::operator(),
+            agent,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeOperator {},
+    )
+}
+
+/// `auction_count` is the operator's count before this auction, which picks
+/// the registry page it is listed on.
+pub fn create_auction(
+    agent: Pubkey,
+    auction_count: u64,
+    auction_id: u64,
+    usdc_mint: Pubkey,
+    treasury: Pubkey,
+    token_program: Pubkey,
+    config: AuctionConfig,
+) -> Instruction {
+    build(
+        accounts::CreateAuction {
+            operator: pda::operator(),
+            auction_state: pda::auction_state_in(auction_id),
+            usdc_mint,
+            treasury,
+            escrow: pda::escrow_in(auction_id),
+            registry_page: pda::registry_page(RegistryPage::index_for(auction_count)),
+            agent,
+            token_program,
+            system_program: system_program::ID,
+        },
+        instruction::CreateAuction { auction_id, config },
+    )
+}
+
+/// `page` is the registry page listing the auction.
+pub fn sync_registry(auction_id: u64, page: u32) -> Instruction {
+    build(
+        accounts::SyncRegistry {
+            auction_state: pda::auction_state_in(auction_id),
+            registry_page: pda::registry_page(page),
+        },
+        instruction::SyncRegistry {},
+    )
+}
+
+/// Winds up a settled, cancelled or abandoned auction once no bid is left.
+pub fn close_auction(keys: &AuctionKeys, agent: Pubkey) -> Instruction {
+    build(
+        accounts::CloseAuction {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::CloseAuction {},
+    )
+}
+
+/// Moves the auction onto a fresh escrow for `new_mint`, which may be the
+/// current mint. `escrow_generation` is the auction's current
+/// `AuctionState::escrow_generation`; `new_token_program` owns `new_mint`.
+pub fn migrate_escrow(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    escrow_generation: u8,
+    new_mint: Pubkey,
+    new_token_program: Pubkey,
+    new_treasury: Pubkey,
+    mint_overrides: u8,
+) -> Instruction {
+    build(
+        accounts::MigrateEscrow {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            token_program: keys.token_program,
+            new_mint,
+            new_escrow: pda::migrated_escrow(
+                keys.auction_id,
+                &new_mint,
+                escrow_generation.saturating_add(1),
+            ),
+            new_treasury,
+            agent,
+            new_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateEscrow { mint_overrides },
+    )
+}
+
+/// Moves `amount` of a stray `mint` out of `source`, a token account the
+/// auction state controls, to `destination`.
+pub fn rescue_tokens(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::RescueTokens {
+            auction_state: keys.auction_state,
+            source,
+            destination,
+            mint,
+            accepted_mints: pda::accepted_mints_in(keys.auction_id),
+            agent,
+            token_program,
+        },
+        instruction::RescueTokens { amount },
+    )
+}
+
+/// Starts logging agent, minimum-bid and treasury changes.
+pub fn initialize_audit_log(keys: &AuctionKeys, agent: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAuditLog {
+            auction_state: keys.auction_state,
+            audit_log: pda::audit_log(keys.auction_id),
+            agent,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAuditLog {},
+    )
+}
+
+/// Adds lot `lot_id` to the round, which must be one more than the auction's
+/// current `lot_count`.
+pub fn create_lot(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    lot_id: u16,
+    minimum_bid: u64,
+    reserve_price: u64,
+    item_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::CreateLot {
+            auction_state: keys.auction_state,
+            lot: pda::lot(keys.auction_id, lot_id),
+            agent,
+            system_program: system_program::ID,
+        },
+        instruction::CreateLot {
+            minimum_bid,
+            reserve_price,
+            item_hash,
+        },
+    )
+}
+
+/// Bids `amount` on a lot, or raises the bidder's bid on it to `amount`.
+pub fn place_lot_bid(
+    keys: &AuctionKeys,
+    lot_id: u16,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::PlaceLotBid {
+            auction_state: keys.auction_state,
+            lot: pda::lot(keys.auction_id, lot_id),
+            lot_bid: pda::lot_bid(keys.auction_id, lot_id, &bidder),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            payer: bidder,
+            token_program: keys.token_program,
+            system_program: system_program::ID,
+            denylist: keys.denylist,
+        },
+        instruction::PlaceLotBid { amount },
+    )
+}
+
+/// Refunds and closes a lot bid that is not leading its lot.
+pub fn withdraw_lot_bid(
+    keys: &AuctionKeys,
+    lot_id: u16,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawLotBid {
+            auction_state: keys.auction_state,
+            lot: pda::lot(keys.auction_id, lot_id),
+            lot_bid: pda::lot_bid(keys.auction_id, lot_id, &bidder),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            token_program: keys.token_program,
+        },
+        instruction::WithdrawLotBid {},
+    )
+}
+
+/// Sells a lot to `leader`, its leading bidder, or closes it unsold when
+/// `leader` is `None` or below the reserve. `authority` is the agent or its
+/// settler.
+pub fn settle_lot(
+    keys: &AuctionKeys,
+    authority: Pubkey,
+    lot_id: u16,
+    leader: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::SettleLot {
+            auction_state: keys.auction_state,
+            lot: pda::lot(keys.auction_id, lot_id),
+            winning_bid: leader.map(|leader| pda::lot_bid(keys.auction_id, lot_id, &leader)),
+            winner: leader,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            authority,
+            token_program: keys.token_program,
+        },
+        instruction::SettleLot {},
+    )
+}
+
+/// Offers `amount` for every lot in `lots` together. The lots are sorted
+/// here and passed after the named accounts.
+pub fn place_bundle_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    lots: &[u16],
+    amount: u64,
+) -> Instruction {
+    let mut lots = lots.to_vec();
+    lots.sort_unstable();
+    let mut ix = build(
+        accounts::PlaceBundleBid {
+            auction_state: keys.auction_state,
+            bundle_bid: pda::bundle_bid(keys.auction_id, &bidder),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            payer: bidder,
+            token_program: keys.token_program,
+            system_program: system_program::ID,
+            denylist: keys.denylist,
+        },
+        instruction::PlaceBundleBid {
+            lots: lots.clone(),
+            amount,
+        },
+    );
+    ix.accounts.extend(
+        lots.iter()
+            .map(|lot_id| AccountMeta::new(pda::lot(keys.auction_id, *lot_id), false)),
+    );
+    ix
+}
+
+/// Sells `bidder`'s bundle its lots or refunds it to `bidder_usdc`; `lots`
+/// are the bundle's lots as stored on its `BundleBid`. `authority` is the
+/// agent or its settler.
+pub fn settle_bundle(
+    keys: &AuctionKeys,
+    authority: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    lots: &[u16],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleBundle {
+            auction_state: keys.auction_state,
+            bundle_bid: pda::bundle_bid(keys.auction_id, &bidder),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            authority,
+            token_program: keys.token_program,
+        },
+        instruction::SettleBundle {},
+    );
+    ix.accounts.extend(
+        lots.iter()
+            .map(|lot_id| AccountMeta::new(pda::lot(keys.auction_id, *lot_id), false)),
+    );
+    ix
+}
+
+/// Opts `bidder`'s bid in or out of carrying into the next round if it loses.
+pub fn set_standing(keys: &AuctionKeys, bidder: Pubkey, standing: bool) -> Instruction {
+    build(
+        accounts::SetStanding {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+        },
+        instruction::SetStanding { standing },
+    )
+}
+
+/// Inserts the bid at `bid` into the ranked list between `hints`; see
+/// [`RankHints::link`]. Anyone may send it.
+pub fn link_bid(keys: &AuctionKeys, bid: Pubkey, hints: RankHints) -> Instruction {
+    build(
+        accounts::LinkBid {
+            auction_state: keys.auction_state,
+            bid,
+            prev_bid: hints.prev_bid,
+            next_bid: hints.next_bid,
+        },
+        instruction::LinkBid {},
+    )
+}
+
+/// Takes `bidder`'s bid out of the ranked list; `hints` come from
+/// [`RankHints::unlink`].
+pub fn unlink_bid(keys: &AuctionKeys, bidder: Pubkey, hints: RankHints) -> Instruction {
+    build(
+        accounts::UnlinkBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            prev_bid: hints.prev_bid,
+            next_bid: hints.next_bid,
+            bidder,
+        },
+        instruction::UnlinkBid {},
+    )
+}
+
+/// Pays the next `winners.len()` bids off the head of the ranked list, each
+/// `(bid, winner_usdc)` in list order; see [`crate::ranked_bids`]. Send
+/// pages until `num_winners` are paid or the list runs out, which settles
+/// the round. `authority` is the agent or its settler.
+pub fn settle_top_k(
+    keys: &AuctionKeys,
+    authority: Pubkey,
+    shares: ShareKeys,
+    winners: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleTopK {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            authority,
+            token_program: keys.token_program,
+            charity: shares.charity,
+            curator_usdc: shares.curator_usdc,
+            referrer_usdc: shares.referrer_usdc,
+            round_result: keys.round_result,
+            price_feed: pda::price_feed(keys.auction_id),
+            system_program: system_program::ID,
+            memo_program: keys.memo_program,
+        },
+        instruction::SettleTopK {
+            count: winners.len() as u8,
+        },
+    );
+    ix.accounts.extend(winners.iter().flat_map(|(bid, winner_usdc)| {
+        [AccountMeta::new(*bid, false), AccountMeta::new(*winner_usdc, false)]
+    }));
+    ix
+}
+
+/// Closes each inactive bid in `bids`, `(bid, bidder)` pairs from any
+/// auction, returning its rent to the bidder. Anyone may send it.
+pub fn gc_bid(cranker: Pubkey, bids: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut ix = build(accounts::GcBid { cranker }, instruction::GcBid {});
+    ix.accounts.extend(bids.iter().flat_map(|(bid, bidder)| {
+        [AccountMeta::new(*bid, false), AccountMeta::new(*bidder, false)]
+    }));
+    ix
+}
+
+/// Bars `wallet` from placing or raising bids, or lifts the bar when
+/// `denied` is false. The first call creates the auction's denylist.
+pub fn set_denylisted(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    wallet: Pubkey,
+    denied: bool,
+) -> Instruction {
+    build(
+        accounts::SetDenylisted {
+            auction_state: keys.auction_state,
+            denylist: pda::denylist(keys.auction_id),
+            agent,
+            system_program: system_program::ID,
+        },
+        instruction::SetDenylisted { wallet, denied },
+    )
+}
+
+/// Refunds a denylisted `bidder`'s bid in full to `bidder_usdc`, their
+/// associated token account, and closes it. `hints` are needed when the bid
+/// is ranked; see [`RankHints::unlink`].
+pub fn force_refund(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    hints: RankHints,
+) -> Instruction {
+    build(
+        accounts::ForceRefund {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            denylist: pda::denylist(keys.auction_id),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            agent,
+            token_program: keys.token_program,
+            accepted_mints: keys.accepted_mints,
+            prev_bid: hints.prev_bid,
+            next_bid: hints.next_bid,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ForceRefund {},
+    )
+}
+
+/// Holds future settlements in escrow for `dispute_window` seconds, open to
+/// `arbiter`'s ruling; zero pays them out immediately.
+pub fn set_dispute_terms(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    arbiter: Pubkey,
+    dispute_window: i64,
+) -> Instruction {
+    build(
+        accounts::SetDisputeTerms {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetDisputeTerms {
+            arbiter,
+            dispute_window,
+        },
+    )
+}
+
+/// The held settlement's winner disputes it, leaving it to the arbiter.
+pub fn open_dispute(keys: &AuctionKeys, winner: Pubkey) -> Instruction {
+    build(
+        accounts::OpenDispute {
+            auction_state: keys.auction_state,
+            winner,
+        },
+        instruction::OpenDispute {},
+    )
+}
+
+/// Ends the held settlement: refunds it to `winner_usdc` if `refund`,
+/// otherwise pays it out to `shares` and the treasury. `authority` is the
+/// arbiter, or any signer releasing an undisputed settlement after its
+/// window; see [`ShareKeys::held`].
+pub fn resolve_settlement(
+    keys: &AuctionKeys,
+    authority: Pubkey,
+    winner_usdc: Option<Pubkey>,
+    shares: ShareKeys,
+    refund: bool,
+) -> Instruction {
+    build(
+        accounts::ResolveSettlement {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            authority,
+            token_program: keys.token_program,
+            winner_usdc,
+            charity: shares.charity,
+            curator_usdc: shares.curator_usdc,
+            referrer_usdc: shares.referrer_usdc,
+            memo_program: keys.memo_program,
+        },
+        instruction::ResolveSettlement { refund },
+    )
+}
+
+/// Makes the top bidder claim the win within `claim_window` seconds of the
+/// deadline; zero settles without a claim.
+pub fn set_claim_window(keys: &AuctionKeys, agent: Pubkey, claim_window: i64) -> Instruction {
+    build(
+        accounts::SetClaimWindow {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetClaimWindow { claim_window },
+    )
+}
+
+/// The top bidder confirms their win.
+pub fn claim_win(keys: &AuctionKeys, bidder: Pubkey) -> Instruction {
+    build(
+        accounts::ClaimWin {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+        },
+        instruction::ClaimWin {},
+    )
+}
+
+/// Forfeits a top bidder who let the claim window lapse, refunding what the
+/// default penalty leaves to `bidder_usdc`, their associated token account,
+/// and offers the win to the next bidder.
+pub fn promote_next_bidder(
+    keys: &AuctionKeys,
+    cranker: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    build(
+        accounts::PromoteNextBidder {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_usdc,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::PromoteNextBidder {},
+    )
+}
+
+/// Slashes `default_penalty_bps` of a defaulting winner's escrow to the
+/// treasury; the rest is refunded to them.
+pub fn set_default_penalty(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    default_penalty_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetDefaultPenalty {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetDefaultPenalty {
+            default_penalty_bps,
+        },
+    )
+}
+
+/// Makes settlement wait `min_settle_slots` after the winning bid's last
+/// change.
+pub fn set_min_settle_slots(
+    keys: &AuctionKeys,
+    agent: Pubkey,
+    min_settle_slots: u64,
+) -> Instruction {
+    build(
+        accounts::SetMinSettleSlots {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetMinSettleSlots { min_settle_slots },
+    )
+}
+
+pub fn create_points_mint(keys: &AuctionKeys, agent: Pubkey) -> Instruction {
+    build(
+        accounts::CreatePointsMint {
+            auction_state: keys.auction_state,
+            points_mint: pda::points_mint(keys.auction_id),
+            agent,
+            token_program: keys.token_program,
+            system_program: system_program::ID,
+        },
+        instruction::CreatePointsMint {},
+    )
+}
+
+/// Has bids earn `points_rate` points per whole token escrowed for an hour.
+pub fn set_points_rate(keys: &AuctionKeys, agent: Pubkey, points_rate: u64) -> Instruction {
+    build(
+        accounts::SetPointsRate {
+            auction_state: keys.auction_state,
+            points_mint: pda::points_mint(keys.auction_id),
+            agent,
+        },
+        instruction::SetPointsRate { points_rate },
+    )
+}
+
+/// Mints the points `bidder`'s bid has earned to their associated token
+/// account for [`pda::points_mint`], which `payer` creates if needed.
+pub fn claim_points(keys: &AuctionKeys, payer: Pubkey, bidder: Pubkey) -> Instruction {
+    let points_mint = pda::points_mint(keys.auction_id);
+    build(
+        accounts::ClaimPoints {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder,
+            bidder_points: get_associated_token_address_with_program_id(
+                &bidder,
+                &points_mint,
+                &keys.token_program,
+            ),
+            points_mint,
+            payer,
+            token_program: keys.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimPoints {},
+    )
+}
+
+/// [`claim_points`] for `bid` when it has points to mint by `now`. A bid
+/// holding points cannot be closed, so this goes in the same transaction,
+/// ahead of whatever closes it.
+pub fn claim_points_due(
+    keys: &AuctionKeys,
+    payer: Pubkey,
+    state: &AuctionState,
+    bid: &Bid,
+    now: i64,
+) -> Option<Instruction> {
+    let mut bid = bid.clone();
+    state.accrue_points(&mut bid, now).ok()?;
+    (bid.points > 0).then(|| claim_points(keys, payer, bid.bidder))
+}
+
+/// Keeps `rebate_bps` of the treasury's cut back for the round's losing bids.
+pub fn set_rebate_bps(keys: &AuctionKeys, agent: Pubkey, rebate_bps: u16) -> Instruction {
+    build(
+        accounts::SetRebateBps {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetRebateBps { rebate_bps },
+    )
+}
+
+/// Lets `session_key` raise the bid by up to `spend_cap` until `expires_at`
+/// with [`session_increase_bid`]; `Pubkey::default()` revokes it.
+pub fn set_session_key(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    session_key: Pubkey,
+    expires_at: i64,
+    spend_cap: u64,
+) -> Instruction {
+    build(
+        accounts::SetSessionKey {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            token_program: keys.token_program,
+        },
+        instruction::SetSessionKey {
+            session_key,
+            expires_at,
+            spend_cap,
+        },
+    )
+}
+
+/// [`increase_bid`] signed by the bid's session key alone.
+pub fn session_increase_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    session: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::UpdateBid {
+            session: Some(session),
+            ..update_bid_accounts(keys, bidder, bidder_usdc, leader_bid)
+        },
+        instruction::IncreaseBid { amount },
+    )
+}
+
+/// Makes the round take sealed bids, revealed within `reveal_window`
+/// seconds of the deadline; zero returns to open bids.
+pub fn set_reveal_window(keys: &AuctionKeys, agent: Pubkey, reveal_window: i64) -> Instruction {
+    build(
+        accounts::SetRevealWindow {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetRevealWindow { reveal_window },
+    )
+}
+
+/// Escrows `deposit` behind a hidden amount; `commitment` is
+/// [`crate::sealed_commitment`] of it, and the salt must be kept for
+/// [`reveal_bid`].
+pub fn place_sealed_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    deposit: u64,
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        place_bid_accounts(keys, bidder, bidder, bidder_usdc, None, None, None),
+        instruction::PlaceSealedBid {
+            bid_index: keys.bid_index,
+            deposit,
+            commitment,
+        },
+    )
+}
+
+pub fn reveal_bid(
+    keys: &AuctionKeys,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    salt: [u8; 32],
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::RevealBid {
+            auction_state: keys.auction_state,
+            bid: pda::bid_at(keys.auction_id, &bidder, keys.bid_index),
+            bidder_usdc,
+            escrow: keys.escrow,
+            usdc_mint: keys.usdc_mint,
+            bidder,
+            token_program: keys.token_program,
+            leader_bid,
+        },
+        instruction::RevealBid { amount, salt },
+    )
+}
+
+/// Stops or resumes new bids and raises.
+pub fn set_paused(keys: &AuctionKeys, agent: Pubkey, paused: bool) -> Instruction {
+    build(
+        accounts::SetPaused {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+/// Sets the bond sealed bids escrow on top of their deposit.
+pub fn set_commit_bond(keys: &AuctionKeys, agent: Pubkey, commit_bond: u64) -> Instruction {
+    build(
+        accounts::SetCommitBond {
+            auction_state: keys.auction_state,
+            agent,
+        },
+        instruction::SetCommitBond { commit_bond },
+    )
+}
+
+/// Sends forfeited commit bonds to the treasury; any signer may crank it
+/// once the reveal window has closed.
+pub fn sweep_commit_bonds(keys: &AuctionKeys, cranker: Pubkey) -> Instruction {
+    build(
+        accounts::SweepCommitBonds {
+            auction_state: keys.auction_state,
+            escrow: keys.escrow,
+            treasury: keys.treasury,
+            usdc_mint: keys.usdc_mint,
+            cranker,
+            token_program: keys.token_program,
+        },
+        instruction::SweepCommitBonds {},
+    )
+}
@@ -0,0 +1,101 @@
+//! Bundle submission through a Jito block engine.
+//!
+//! A bundle's transactions land together, in order, in one slot, or none of
+//! them do. The keeper uses this to settle a round and refund its first
+//! losers at once, so no slot ever shows a settled auction whose losing
+//! escrow is still locked. Bundles pay the validator through a lamport tip
+//! in their last transaction rather than through priority fees.
+
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::transaction::Transaction;
+
+use crate::{ClientError, Result};
+
+/// Most transactions a block engine accepts in one bundle.
+pub const MAX_BUNDLE_LEN: usize = 5;
+
+/// JSON-RPC client for a block engine, e.g.
+/// `https://mainnet.block-engine.jito.wtf`.
+pub struct JitoClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl JitoClient {
+    pub fn new(url: impl ToString) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("static reqwest config"),
+            url: url.to_string().trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn call(&self, path: &str, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(format!("{}/api/v1/{path}", self.url))
+            .json(&body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| ClientError::Jito(err.to_string()))?
+            .json()
+            .await
+            .map_err(|err| ClientError::Jito(err.to_string()))?;
+        if let Some(err) = response.get("error") {
+            return Err(ClientError::Jito(err.to_string()));
+        }
+        Ok(response["result"].clone())
+    }
+
+    /// Accounts the block engine takes tips in; any one will do.
+    pub async fn tip_accounts(&self) -> Result<Vec<Pubkey>> {
+        let result = self.call("bundles", "getTipAccounts", json!([])).await?;
+        let accounts = result
+            .as_array()
+            .map(|accounts| {
+                accounts
+                    .iter()
+                    .filter_map(|account| account.as_str()?.parse().ok())
+                    .collect::<Vec<Pubkey>>()
+            })
+            .unwrap_or_default();
+        if accounts.is_empty() {
+            return Err(ClientError::Jito("no tip accounts returned".to_string()));
+        }
+        Ok(accounts)
+    }
+
+    /// Submits signed transactions as one bundle and returns its id. The
+    /// block engine only forwards it; whether it landed shows in the
+    /// transactions' signature statuses.
+    pub async fn send_bundle(&self, transactions: &[Transaction]) -> Result<String> {
+        let encoded = transactions
+            .iter()
+            .map(|tx| bincode::serialize(tx).map(|bytes| STANDARD.encode(bytes)))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| ClientError::Jito(err.to_string()))?;
+        let result = self
+            .call("bundles", "sendBundle", json!([encoded, { "encoding": "base64" }]))
+            .await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ClientError::Jito(format!("unexpected sendBundle result {result}")))
+    }
+}
+
+/// Pays `lamports` from `payer` to `tip_account`; goes last in the bundle.
+pub fn tip(payer: &Pubkey, tip_account: &Pubkey, lamports: u64) -> Instruction {
+    system_instruction::transfer(payer, tip_account, lamports)
+}
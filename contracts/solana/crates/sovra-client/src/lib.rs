@@ -0,0 +1,37 @@
+//! Rust client for the cartoonist auction program.
+//!
+//! Instruction builders wrap the program's Anchor `accounts`/`instruction`
+//! types, so callers never deal with discriminators or account ordering.
+//! [`SovraClient`] fetches and decodes program accounts over blocking RPC;
+//! [`nonblocking::SovraClient`] does the same on an async runtime.
+
+mod accounts;
+mod blocking;
+mod error;
+pub mod events;
+mod fees;
+pub mod governance;
+mod hooks;
+pub mod instructions;
+pub mod jito;
+pub mod merkle;
+pub mod nonblocking;
+pub mod nonce;
+pub mod pda;
+pub mod stream;
+
+pub use accounts::{
+    bid_filters, decode, ranked_bids, AuctionKeys, PrizeKeys, RankHints, ShareKeys,
+};
+pub use blocking::SovraClient;
+pub use cartoonist_auction::state::{
+    sealed_commitment, AcceptedMint, AcceptedMints, AuctionConfig, AuctionState, AuctionStatus,
+    AuditEntry, AuditLog,
+    Bid, BundleBid,
+    Config, ConfigUpdate, ContentReveal, Denylist, Leaderboard, LeaderboardEntry, Lot, LotBid,
+    Operator, PriceFeed, RegistryEntry, RegistryPage, RoundResult, WinReceipt,
+};
+pub use cartoonist_auction::{mint_safety, ID as PROGRAM_ID, USDC_DECIMALS};
+pub use error::{ClientError, Result};
+pub use events::{parse_logs, parse_logs_counting_failures, AuctionEvent};
+pub use fees::PriorityFees;
@@ -0,0 +1,89 @@
+//! Builds the refunds Merkle tree the agent posts with `post_refund_root`,
+//! and the per-bidder proofs `claim_refund` checks against it.
+
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::merkle::{leaf, node};
+
+/// One refund: `amount` back to `bidder`, claimable once at `index`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Refund {
+    pub index: u32,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+pub struct RefundTree {
+    refunds: Vec<Refund>,
+    /// Hash levels from the leaves up to the single root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl RefundTree {
+    /// Indexes `(bidder, amount)` pairs in the order given. An odd node at
+    /// the end of a level is carried up unchanged.
+    pub fn new(refunds: impl IntoIterator<Item = (Pubkey, u64)>) -> Self {
+        let refunds: Vec<Refund> = refunds
+            .into_iter()
+            .enumerate()
+            .map(|(index, (bidder, amount))| Refund {
+                index: index as u32,
+                bidder,
+                amount,
+            })
+            .collect();
+
+        let mut levels = vec![refunds
+            .iter()
+            .map(|r| leaf(r.index, &r.bidder, r.amount))
+            .collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { refunds, levels }
+    }
+
+    /// Zero for an empty tree, which the program rejects.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> u32 {
+        self.refunds.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.refunds.is_empty()
+    }
+
+    pub fn refund(&self, bidder: &Pubkey) -> Option<Refund> {
+        self.refunds.iter().find(|r| r.bidder == *bidder).copied()
+    }
+
+    /// Sibling hashes from the leaf at `index` up to the root.
+    pub fn proof(&self, index: u32) -> Vec<[u8; 32]> {
+        let mut position = index as usize;
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        proof
+    }
+}
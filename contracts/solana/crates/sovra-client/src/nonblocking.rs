@@ -0,0 +1,427 @@
+//! Async counterpart of [`crate::SovraClient`].
+
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use futures_util::Stream;
+use cartoonist_auction::state::{
+    AcceptedMints, AuctionState, AuditLog, Bid, BundleBid, Config, Denylist, Lot, LotBid, Operator,
+    PriceFeed, RegistryEntry, RoundResult, WinReceipt,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::blocking::{
+    bids_config, in_auction, registry_entries, registry_pages, signed_transaction,
+};
+use crate::fees::{landed, writable_accounts, POLL_INTERVAL};
+use crate::jito::{self, JitoClient, MAX_BUNDLE_LEN};
+use crate::stream::{self, EventNotification};
+use crate::{
+    decode, hooks, nonce, pda, AuctionKeys, ClientError, PriorityFees, PrizeKeys, Result,
+    PROGRAM_ID,
+};
+
+/// How long a bundle has to land; its blockhash expires not long after.
+const BUNDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Async RPC client for the auction program.
+pub struct SovraClient {
+    rpc: RpcClient,
+    auction_id: u64,
+    priority_fees: Option<PriorityFees>,
+}
+
+impl SovraClient {
+    pub fn new(url: impl ToString) -> Self {
+        Self::from_rpc(RpcClient::new_with_commitment(
+            url.to_string(),
+            CommitmentConfig::confirmed(),
+        ))
+    }
+
+    pub fn from_rpc(rpc: RpcClient) -> Self {
+        Self {
+            rpc,
+            auction_id: 0,
+            priority_fees: None,
+        }
+    }
+
+    /// Points the client at an auction opened by `create_auction` instead
+    /// of auction zero.
+    pub fn with_auction_id(mut self, auction_id: u64) -> Self {
+        self.auction_id = auction_id;
+        self
+    }
+
+    /// Makes [`Self::send`] pay priority fees and re-send at rising prices
+    /// until confirmed; see [`PriorityFees`].
+    pub fn with_priority_fees(mut self, fees: PriorityFees) -> Self {
+        self.priority_fees = Some(fees);
+        self
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// Program events over `ws_url`, backfilled through this client's RPC
+    /// endpoint after reconnects; see [`crate::stream`].
+    pub fn event_stream(
+        &self,
+        ws_url: impl ToString,
+        since: Option<Signature>,
+    ) -> impl Stream<Item = EventNotification> {
+        let rpc = RpcClient::new_with_commitment(self.rpc.url(), self.rpc.commitment());
+        stream::event_stream(rpc, ws_url.to_string(), since)
+    }
+
+    /// [`Self::event_stream`] narrowed to this client's auction.
+    pub fn auction_event_stream(
+        &self,
+        ws_url: impl ToString,
+        since: Option<Signature>,
+    ) -> impl Stream<Item = EventNotification> {
+        let rpc = RpcClient::new_with_commitment(self.rpc.url(), self.rpc.commitment());
+        stream::auction_event_stream(rpc, ws_url.to_string(), self.auction_id, since)
+    }
+
+    pub async fn auction_state(&self) -> Result<AuctionState> {
+        let address = pda::auction_state_in(self.auction_id);
+        let account = self
+            .rpc
+            .get_account_with_commitment(&address, self.rpc.commitment())
+            .await?
+            .value
+            .ok_or(ClientError::AccountNotFound(address))?;
+        decode(&account.data)
+    }
+
+    pub async fn keys(&self) -> Result<AuctionKeys> {
+        let state = self.auction_state().await?;
+        let mint = self.rpc.get_account(&state.usdc_mint).await?;
+        Ok(AuctionKeys::new(&state, mint.owner))
+    }
+
+    pub async fn keys_for_mint(&self, mint: &Pubkey) -> Result<AuctionKeys> {
+        let keys = self.keys().await?;
+        if *mint == keys.usdc_mint {
+            return Ok(keys);
+        }
+        let accepted = self
+            .accepted_mints()
+            .await?
+            .ok_or(ClientError::MintNotAccepted(*mint))?;
+        let entry = accepted.get(mint).ok_or(ClientError::MintNotAccepted(*mint))?;
+        let owner = self.rpc.get_account(mint).await?.owner;
+        Ok(keys.for_accepted_mint(entry, owner))
+    }
+
+    pub async fn accepted_mints(&self) -> Result<Option<AcceptedMints>> {
+        self.rpc
+            .get_account_with_commitment(&pda::accepted_mints_in(self.auction_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn config(&self) -> Result<Option<Config>> {
+        self.rpc
+            .get_account_with_commitment(&pda::config_in(self.auction_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn operator(&self) -> Result<Option<Operator>> {
+        self.rpc
+            .get_account_with_commitment(&pda::operator(), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn auctions(&self) -> Result<Vec<RegistryEntry>> {
+        let Some(operator) = self.operator().await? else {
+            return Ok(Vec::new());
+        };
+        let pages = registry_pages(&operator);
+        let accounts = self.rpc.get_multiple_accounts(&pages).await?;
+        registry_entries(&pages, accounts)
+    }
+
+    pub async fn prize_keys(&self, state: &AuctionState, winner: &Pubkey) -> Result<Option<PrizeKeys>> {
+        if state.prize_amount == 0 {
+            return Ok(None);
+        }
+        let mint = self.rpc.get_account(&state.prize_mint).await?;
+        Ok(PrizeKeys::new(state, winner, mint.owner))
+    }
+
+    pub async fn bid(&self, bidder: &Pubkey) -> Result<Option<Bid>> {
+        self.bid_at(bidder, 0).await
+    }
+
+    pub async fn bid_at(&self, bidder: &Pubkey, bid_index: u16) -> Result<Option<Bid>> {
+        let address = pda::bid_at(self.auction_id, bidder, bid_index);
+        self.rpc
+            .get_account_with_commitment(&address, self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn round_result(&self, round: u64) -> Result<Option<RoundResult>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::round_result(self.auction_id, round),
+                self.rpc.commitment(),
+            )
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn win_receipt(&self, round: u64, winner: &Pubkey) -> Result<Option<WinReceipt>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::win_receipt(self.auction_id, round, winner),
+                self.rpc.commitment(),
+            )
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn audit_log(&self) -> Result<Option<AuditLog>> {
+        self.rpc
+            .get_account_with_commitment(&pda::audit_log(self.auction_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn denylist(&self) -> Result<Option<Denylist>> {
+        self.rpc
+            .get_account_with_commitment(&pda::denylist(self.auction_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn price_feed(&self) -> Result<Option<PriceFeed>> {
+        self.rpc
+            .get_account_with_commitment(&pda::price_feed(self.auction_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn lot(&self, lot_id: u16) -> Result<Option<Lot>> {
+        self.rpc
+            .get_account_with_commitment(&pda::lot(self.auction_id, lot_id), self.rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn lot_bid(&self, lot_id: u16, bidder: &Pubkey) -> Result<Option<LotBid>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::lot_bid(self.auction_id, lot_id, bidder),
+                self.rpc.commitment(),
+            )
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn bundle_bid(&self, bidder: &Pubkey) -> Result<Option<BundleBid>> {
+        self.rpc
+            .get_account_with_commitment(
+                &pda::bundle_bid(self.auction_id, bidder),
+                self.rpc.commitment(),
+            )
+            .await?
+            .value
+            .map(|account| decode(&account.data))
+            .transpose()
+    }
+
+    pub async fn bids(&self) -> Result<Vec<(Pubkey, Bid)>> {
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(&PROGRAM_ID, bids_config())
+            .await?;
+        in_auction(accounts, self.auction_id)
+    }
+
+    pub async fn transfer_hook_accounts(
+        &self,
+        mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Result<Vec<AccountMeta>> {
+        let mint_account = self.rpc.get_account(mint).await?;
+        let fetch = |address: Pubkey| async move {
+            self.rpc
+                .get_account_with_commitment(&address, self.rpc.commitment())
+                .await
+                .map(|response| response.value.map(|account| account.data))
+                .map_err(Into::into)
+        };
+        hooks::resolve(
+            mint,
+            &mint_account,
+            source,
+            destination,
+            authority,
+            amount,
+            fetch,
+        )
+        .await
+    }
+
+    pub async fn nonce(&self, nonce_account: &Pubkey) -> Result<nonce::Data> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(nonce_account, self.rpc.commitment())
+            .await?
+            .value
+            .ok_or(ClientError::AccountNotFound(*nonce_account))?;
+        nonce::nonce_data(nonce_account, &account.data)
+    }
+
+    pub async fn nonce_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+    ) -> Result<Transaction> {
+        let data = self.nonce(nonce_account).await?;
+        Ok(nonce::nonce_transaction(
+            instructions,
+            payer,
+            nonce_account,
+            &data.authority,
+            data.blockhash(),
+        ))
+    }
+
+    /// Signs and sends `instructions`, waiting for confirmation. With
+    /// [`Self::with_priority_fees`], compute-budget instructions are added
+    /// and the transaction is re-sent at rising prices until it lands.
+    pub async fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &dyn Signer,
+        signers: &[&dyn Signer],
+    ) -> Result<Signature> {
+        let Some(fees) = self.priority_fees else {
+            let blockhash = self.rpc.get_latest_blockhash().await?;
+            let tx = signed_transaction(instructions, payer, signers, blockhash);
+            return Ok(self.rpc.send_and_confirm_transaction(&tx).await?);
+        };
+        let recent = self
+            .rpc
+            .get_recent_prioritization_fees(&writable_accounts(instructions))
+            .await?;
+        let starting_price = fees.starting_price(&recent);
+        let mut sent = Vec::new();
+        for attempt in 0..fees.max_attempts {
+            let budgeted = fees.budgeted(instructions, fees.price(starting_price, attempt));
+            let blockhash = self.rpc.get_latest_blockhash().await?;
+            let tx = signed_transaction(&budgeted, payer, signers, blockhash);
+            // A failed preflight may only mean an earlier attempt landed.
+            if let Err(err) = self.rpc.send_transaction(&tx).await {
+                return self.landed(&sent).await?.ok_or_else(|| err.into());
+            }
+            sent.push(tx.signatures[0]);
+            let deadline = Instant::now() + fees.confirm_timeout;
+            while Instant::now() < deadline {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                if let Some(signature) = self.landed(&sent).await? {
+                    return Ok(signature);
+                }
+            }
+        }
+        Err(ClientError::NotConfirmed(fees.max_attempts))
+    }
+
+    /// Sends each entry of `transactions` as one transaction of a Jito
+    /// bundle, with a `tip` in lamports added to the last, and waits until
+    /// all of them are confirmed. They land together or not at all; if the
+    /// bundle is dropped this returns [`ClientError::NotConfirmed`] and
+    /// nothing has changed on chain.
+    pub async fn send_bundle(
+        &self,
+        jito: &JitoClient,
+        transactions: &[Vec<Instruction>],
+        payer: &dyn Signer,
+        tip: u64,
+    ) -> Result<Vec<Signature>> {
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_LEN {
+            return Err(ClientError::Jito(format!(
+                "a bundle holds 1 to {MAX_BUNDLE_LEN} transactions, not {}",
+                transactions.len()
+            )));
+        }
+        let tip_account = jito.tip_accounts().await?[0];
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let last = transactions.len() - 1;
+        let signed: Vec<Transaction> = transactions
+            .iter()
+            .enumerate()
+            .map(|(index, instructions)| {
+                let mut instructions = instructions.clone();
+                if index == last {
+                    instructions.push(jito::tip(&payer.pubkey(), &tip_account, tip));
+                }
+                signed_transaction(&instructions, payer, &[], blockhash)
+            })
+            .collect();
+        let signatures: Vec<Signature> = signed.iter().map(|tx| tx.signatures[0]).collect();
+        jito.send_bundle(&signed).await?;
+
+        let deadline = Instant::now() + BUNDLE_TIMEOUT;
+        while Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let statuses = self.rpc.get_signature_statuses(&signatures).await?.value;
+            // Bundles are atomic, so the first transaction speaks for all.
+            if landed(&signatures[..1], &statuses[..1], self.rpc.commitment())?.is_some() {
+                for (signature, status) in signatures.iter().zip(&statuses) {
+                    if let Some(err) = status.as_ref().and_then(|status| status.err.clone()) {
+                        return Err(ClientError::TransactionFailed(*signature, err));
+                    }
+                }
+                return Ok(signatures);
+            }
+        }
+        Err(ClientError::NotConfirmed(1))
+    }
+
+    async fn landed(&self, sent: &[Signature]) -> Result<Option<Signature>> {
+        if sent.is_empty() {
+            return Ok(None);
+        }
+        let statuses = self.rpc.get_signature_statuses(sent).await?.value;
+        landed(sent, &statuses, self.rpc.commitment())
+    }
+}
@@ -0,0 +1,72 @@
+//! Durable-nonce transactions, for signers who cannot sign within a recent
+//! blockhash's lifetime, such as an agent key kept in cold storage.
+//!
+//! 1. Create a nonce account once with [`create_nonce_account`], its
+//!    authority being the key that will sign.
+//! 2. Build the transaction online with `SovraClient::nonce_transaction`,
+//!    which pins it to the account's current nonce, and [`encode`] it.
+//! 3. [`decode`] and sign it offline, with no RPC access, at leisure.
+//! 4. Broadcast the signed transaction whenever. It stays valid until the
+//!    nonce advances, which this transaction itself does when it lands.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+pub use solana_nonce::state::Data;
+use solana_nonce::state::State;
+use solana_nonce::versions::Versions;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::transaction::Transaction;
+
+use crate::{ClientError, Result};
+
+/// Bytes of a nonce account, for its rent exemption.
+pub const NONCE_ACCOUNT_LEN: usize = State::size();
+
+/// Creates `nonce_account`, which must sign, funded with `lamports`.
+pub fn create_nonce_account(
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    system_instruction::create_nonce_account(payer, nonce_account, authority, lamports)
+}
+
+/// The nonce and authority stored in a nonce account's data.
+pub fn nonce_data(address: &Pubkey, data: &[u8]) -> Result<Data> {
+    match bincode::deserialize::<Versions>(data).map(|versions| versions.state().clone()) {
+        Ok(State::Initialized(data)) => Ok(data),
+        _ => Err(ClientError::InvalidNonce(*address)),
+    }
+}
+
+/// An unsigned transaction that advances `nonce_account` before running
+/// `instructions`, using its stored `nonce` in place of a blockhash.
+pub fn nonce_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    nonce: Hash,
+) -> Transaction {
+    let mut message =
+        Message::new_with_nonce(instructions.to_vec(), Some(payer), nonce_account, authority);
+    message.recent_blockhash = nonce;
+    Transaction::new_unsigned(message)
+}
+
+/// Base64 of the transaction's wire format, for moving it between machines.
+pub fn encode(tx: &Transaction) -> String {
+    STANDARD.encode(bincode::serialize(tx).expect("transactions serialize"))
+}
+
+pub fn decode(encoded: &str) -> Result<Transaction> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| ClientError::MalformedTransaction(err.to_string()))?;
+    bincode::deserialize(&bytes).map_err(|err| ClientError::MalformedTransaction(err.to_string()))
+}
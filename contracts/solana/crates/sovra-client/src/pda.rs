@@ -0,0 +1,191 @@
+//! Program-derived addresses used by the auction program.
+//!
+//! Helpers without an `auction_id` address auction zero, the one set up by
+//! `initialize`; the `*_in` variants address auctions from `create_auction`.
+//! All of them drop the bump from [`cartoonist_auction::pda`], which owns
+//! the seeds; use it directly to keep the bump or re-derive from a cached one.
+
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::pda;
+use cartoonist_auction::scheduler;
+use cartoonist_auction::state::AuctionState;
+
+pub fn auction_state() -> Pubkey {
+    auction_state_in(0)
+}
+
+pub fn auction_state_in(auction_id: u64) -> Pubkey {
+    pda::find_auction_state(auction_id).0
+}
+
+pub fn escrow() -> Pubkey {
+    escrow_in(0)
+}
+
+pub fn escrow_in(auction_id: u64) -> Pubkey {
+    pda::find_escrow(auction_id).0
+}
+
+/// Escrow `migrate_escrow` creates for `mint` as the auction's
+/// `generation`-th replacement (1-based).
+pub fn migrated_escrow(auction_id: u64, mint: &Pubkey, generation: u8) -> Pubkey {
+    pda::find_migrated_escrow(auction_id, mint, generation).0
+}
+
+pub fn bid(bidder: &Pubkey) -> Pubkey {
+    bid_in(0, bidder)
+}
+
+pub fn bid_in(auction_id: u64, bidder: &Pubkey) -> Pubkey {
+    bid_at(auction_id, bidder, 0)
+}
+
+/// The bidder's bid `bid_index` in an auction; index zero is their first.
+pub fn bid_at(auction_id: u64, bidder: &Pubkey, bid_index: u16) -> Pubkey {
+    pda::find_bid(auction_id, bidder, bid_index).0
+}
+
+/// Bid account of the current leader, which place/update instructions need
+/// whenever a bid other than `bidder`'s first is leading.
+pub fn leader_bid(state: &AuctionState, bidder: &Pubkey) -> Option<Pubkey> {
+    leader_bid_at(state, bidder, 0)
+}
+
+/// As [`leader_bid`], for the bidder's bid `bid_index`.
+pub fn leader_bid_at(state: &AuctionState, bidder: &Pubkey, bid_index: u16) -> Option<Pubkey> {
+    (state.leader != Pubkey::default() && !state.is_leader(*bidder, bid_index))
+        .then(|| bid_at(state.auction_id, &state.leader, state.leader_index))
+}
+
+/// Program-wide record of the agent allowed to call `create_auction`.
+pub fn operator() -> Pubkey {
+    pda::find_operator().0
+}
+
+/// Page `index` of the registry of auctions from `create_auction`.
+pub fn registry_page(index: u32) -> Pubkey {
+    pda::find_registry_page(index).0
+}
+
+/// Archived result of settled `round` (1-based).
+pub fn round_result(auction_id: u64, round: u64) -> Pubkey {
+    pda::find_round_result(auction_id, round).0
+}
+
+/// Loyalty points mint, whose authority is the auction state.
+pub fn points_mint(auction_id: u64) -> Pubkey {
+    pda::find_points_mint(auction_id).0
+}
+
+/// `winner`'s proof of winning `round` (1-based).
+pub fn win_receipt(auction_id: u64, round: u64, winner: &Pubkey) -> Pubkey {
+    pda::find_win_receipt(auction_id, round, winner).0
+}
+
+/// Clockwork thread `schedule_settlement` creates to settle `round` (1-based).
+pub fn settle_thread(auction_id: u64, round: u64) -> Pubkey {
+    scheduler::thread_address(&auction_state_in(auction_id), &scheduler::settle_thread_id(round))
+}
+
+/// Latest clearing price of an auction, rewritten by every settlement.
+pub fn price_feed(auction_id: u64) -> Pubkey {
+    pda::find_price_feed(auction_id).0
+}
+
+/// Ring of an auction's recent admin changes.
+pub fn audit_log(auction_id: u64) -> Pubkey {
+    pda::find_audit_log(auction_id).0
+}
+
+/// Wallets barred from bidding in an auction.
+pub fn denylist(auction_id: u64) -> Pubkey {
+    pda::find_denylist(auction_id).0
+}
+
+/// Lot `lot_id` (1-based) of an auction.
+pub fn lot(auction_id: u64, lot_id: u16) -> Pubkey {
+    pda::find_lot(auction_id, lot_id).0
+}
+
+/// The bidder's bid on lot `lot_id`.
+pub fn lot_bid(auction_id: u64, lot_id: u16, bidder: &Pubkey) -> Pubkey {
+    pda::find_lot_bid(auction_id, lot_id, bidder).0
+}
+
+/// The bidder's bundle bid across several lots of an auction.
+pub fn bundle_bid(auction_id: u64, bidder: &Pubkey) -> Pubkey {
+    pda::find_bundle_bid(auction_id, bidder).0
+}
+
+/// Replay marker for a Wormhole message consumed by auction `auction_id`.
+pub fn received(
+    auction_id: u64,
+    emitter_chain: u16,
+    emitter_address: &[u8; 32],
+    sequence: u64,
+) -> Pubkey {
+    pda::find_received(auction_id, emitter_chain, emitter_address, sequence).0
+}
+
+/// Claim bitmap for a posted refunds Merkle root.
+pub fn refund_claims(root: &[u8; 32]) -> Pubkey {
+    refund_claims_in(0, root)
+}
+
+pub fn refund_claims_in(auction_id: u64, root: &[u8; 32]) -> Pubkey {
+    pda::find_refund_claims(auction_id, root).0
+}
+
+/// Revealed deliverable for a committed content hash.
+pub fn content_reveal(content_hash: &[u8; 32]) -> Pubkey {
+    content_reveal_in(0, content_hash)
+}
+
+pub fn content_reveal_in(auction_id: u64, content_hash: &[u8; 32]) -> Pubkey {
+    pda::find_content_reveal(auction_id, content_hash).0
+}
+
+/// Vault holding a prize escrowed by the agent.
+pub fn prize_vault(prize_mint: &Pubkey) -> Pubkey {
+    prize_vault_in(0, prize_mint)
+}
+
+pub fn prize_vault_in(auction_id: u64, prize_mint: &Pubkey) -> Pubkey {
+    pda::find_prize_vault(auction_id, prize_mint).0
+}
+
+/// Registry of extra mints the auction accepts bids in.
+pub fn accepted_mints() -> Pubkey {
+    accepted_mints_in(0)
+}
+
+pub fn accepted_mints_in(auction_id: u64) -> Pubkey {
+    pda::find_accepted_mints(auction_id).0
+}
+
+/// Escrow for bids in an accepted mint other than the auction mint.
+pub fn mint_escrow(mint: &Pubkey) -> Pubkey {
+    mint_escrow_in(0, mint)
+}
+
+pub fn mint_escrow_in(auction_id: u64, mint: &Pubkey) -> Pubkey {
+    pda::find_mint_escrow(auction_id, mint).0
+}
+
+/// Feature flags and parameters set through `update_config`.
+pub fn config() -> Pubkey {
+    config_in(0)
+}
+
+pub fn config_in(auction_id: u64) -> Pubkey {
+    pda::find_config(auction_id).0
+}
+
+/// Mint of the receipt tokens bidders may take for their bids.
+pub fn receipt_mint() -> Pubkey {
+    receipt_mint_in(0)
+}
+
+pub fn receipt_mint_in(auction_id: u64) -> Pubkey {
+    pda::find_receipt_mint(auction_id).0
+}
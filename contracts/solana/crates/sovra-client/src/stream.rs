@@ -0,0 +1,201 @@
+//! A reconnecting stream of the program's events.
+//!
+//! Events come from `logsSubscribe` on the program id, so every auction the
+//! program runs is covered, or on one auction's state account, which every
+//! transaction acting on that auction writes. When the socket drops, the
+//! stream resubscribes and then backfills, through
+//! `getSignaturesForAddress`, every transaction after the last one it
+//! yielded, so a reconnect leaves no gap.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+use solana_client::client_error::ClientError as RpcError;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{
+    RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+};
+use anchor_lang::prelude::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use tokio::sync::mpsc;
+
+use crate::{parse_logs, pda, AuctionEvent, PROGRAM_ID};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+/// Most signatures `getSignaturesForAddress` returns per page.
+const SIGNATURE_PAGE: usize = 1000;
+
+/// An event with the transaction that emitted it.
+#[derive(Clone, Debug)]
+pub struct EventNotification {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Position among the transaction's events, for deduplication downstream.
+    pub index: usize,
+    pub event: AuctionEvent,
+}
+
+/// Streams events from successful transactions, oldest first. With `since`,
+/// it starts by backfilling everything after that signature, so a bot can
+/// resume where it stopped. The connection runs on a spawned task, which
+/// ends when the stream is dropped.
+pub fn event_stream(
+    rpc: RpcClient,
+    ws_url: String,
+    since: Option<Signature>,
+) -> impl Stream<Item = EventNotification> {
+    events_mentioning(rpc, ws_url, PROGRAM_ID, since)
+}
+
+/// Like [`event_stream`], but only events from transactions on auction
+/// `auction_id`.
+pub fn auction_event_stream(
+    rpc: RpcClient,
+    ws_url: String,
+    auction_id: u64,
+    since: Option<Signature>,
+) -> impl Stream<Item = EventNotification> {
+    events_mentioning(rpc, ws_url, pda::auction_state_in(auction_id), since)
+}
+
+fn events_mentioning(
+    rpc: RpcClient,
+    ws_url: String,
+    address: Pubkey,
+    since: Option<Signature>,
+) -> impl Stream<Item = EventNotification> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut last = since;
+        while !sender.is_closed() {
+            let _ = follow(&rpc, &ws_url, &address, &mut last, &sender).await;
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+    futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx))
+}
+
+/// Subscribes, backfills from `last`, then forwards live notifications
+/// until the socket closes or the receiver is dropped.
+async fn follow(
+    rpc: &RpcClient,
+    ws_url: &str,
+    address: &Pubkey,
+    last: &mut Option<Signature>,
+    sender: &mpsc::UnboundedSender<EventNotification>,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut notifications, _unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![address.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(rpc.commitment()),
+            },
+        )
+        .await?;
+
+    // Subscribing first means a transaction landing during the backfill is
+    // seen twice rather than not at all; the set drops the repeat.
+    let mut backfilled = HashSet::new();
+    if let Some(until) = *last {
+        for (signature, slot, logs) in missed(rpc, address, until).await? {
+            backfilled.insert(signature);
+            if !forward(sender, signature, slot, &logs) {
+                return Ok(());
+            }
+            *last = Some(signature);
+        }
+    }
+
+    while let Some(notification) = notifications.next().await {
+        let tx = notification.value;
+        let signature: Signature = tx.signature.parse()?;
+        if tx.err.is_some() || backfilled.remove(&signature) {
+            continue;
+        }
+        if !forward(sender, signature, notification.context.slot, &tx.logs) {
+            return Ok(());
+        }
+        *last = Some(signature);
+    }
+    Ok(())
+}
+
+/// Sends the events in `logs`; false once the receiver is gone.
+fn forward(
+    sender: &mpsc::UnboundedSender<EventNotification>,
+    signature: Signature,
+    slot: u64,
+    logs: &[String],
+) -> bool {
+    parse_logs(logs)
+        .into_iter()
+        .enumerate()
+        .all(|(index, event)| {
+            sender
+                .send(EventNotification {
+                    signature,
+                    slot,
+                    index,
+                    event,
+                })
+                .is_ok()
+        })
+}
+
+/// Successful transactions mentioning `address` after `until`, oldest
+/// first, with their slots and logs.
+async fn missed(
+    rpc: &RpcClient,
+    address: &Pubkey,
+    until: Signature,
+) -> std::result::Result<Vec<(Signature, u64, Vec<String>)>, RpcError> {
+    let mut statuses = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: Some(until),
+                    limit: None,
+                    commitment: Some(rpc.commitment()),
+                },
+            )
+            .await?;
+        let full = page.len() == SIGNATURE_PAGE;
+        before = page.last().and_then(|status| status.signature.parse().ok());
+        statuses.extend(page);
+        if !full || before.is_none() {
+            break;
+        }
+    }
+
+    let mut missed = Vec::new();
+    for status in statuses.into_iter().rev().filter(|status| status.err.is_none()) {
+        let Ok(signature) = status.signature.parse() else {
+            continue;
+        };
+        let tx = rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(rpc.commitment()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let logs: Option<Vec<String>> = tx
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages.into());
+        missed.push((signature, tx.slot, logs.unwrap_or_default()));
+    }
+    Ok(missed)
+}
@@ -0,0 +1,179 @@
+//! Geyser plugin that filters account writes for the auction program, decodes
+//! `AuctionState` and `Bid` accounts, and publishes them as JSON lines to a
+//! configured sink.
+//!
+//! Validator config:
+//!
+//! ```json
+//! {
+//!     "libpath": "/path/to/libsovra_geyser.so",
+//!     "sink": { "type": "nats", "url": "nats://127.0.0.1:4222", "subject": "sovra.accounts" }
+//! }
+//! ```
+//!
+//! `{ "type": "stdout" }` prints each update on its own line instead.
+
+mod update;
+
+use std::io::Write;
+
+use agave_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, Result,
+};
+use serde::Deserialize;
+use sovra_client::PROGRAM_ID;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+
+use crate::update::AccountUpdate;
+
+#[derive(Deserialize)]
+struct Config {
+    sink: SinkConfig,
+    /// Also publish the accounts replayed from the snapshot at startup.
+    #[serde(default)]
+    include_startup: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SinkConfig {
+    Stdout,
+    Nats { url: String, subject: String },
+}
+
+enum Sink {
+    Stdout,
+    /// Updates are handed to a background task so a slow broker never
+    /// stalls the validator's replay thread.
+    Nats {
+        sender: mpsc::UnboundedSender<Vec<u8>>,
+        _runtime: Runtime,
+    },
+}
+
+impl Sink {
+    fn start(config: SinkConfig) -> Result<Self> {
+        match config {
+            SinkConfig::Stdout => Ok(Self::Stdout),
+            SinkConfig::Nats { url, subject } => {
+                let runtime = Runtime::new()?;
+                let (sender, mut receiver) = mpsc::unbounded_channel::<Vec<u8>>();
+                runtime.spawn(async move {
+                    let client = match async_nats::connect(&url).await {
+                        Ok(client) => client,
+                        Err(err) => {
+                            eprintln!("sovra-geyser: connecting to {url}: {err}");
+                            return;
+                        }
+                    };
+                    while let Some(payload) = receiver.recv().await {
+                        if let Err(err) = client.publish(subject.clone(), payload.into()).await {
+                            eprintln!("sovra-geyser: publish failed: {err}");
+                        }
+                    }
+                });
+                Ok(Self::Nats {
+                    sender,
+                    _runtime: runtime,
+                })
+            }
+        }
+    }
+
+    fn publish(&self, payload: Vec<u8>) -> Result<()> {
+        match self {
+            Self::Stdout => {
+                let mut out = std::io::stdout().lock();
+                out.write_all(&payload)?;
+                out.write_all(b"\n")?;
+            }
+            Self::Nats { sender, .. } => {
+                sender
+                    .send(payload)
+                    .map_err(|err| GeyserPluginError::Custom(Box::new(err)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct SovraGeyserPlugin {
+    sink: Option<Sink>,
+    include_startup: bool,
+}
+
+impl std::fmt::Debug for SovraGeyserPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SovraGeyserPlugin").finish_non_exhaustive()
+    }
+}
+
+impl GeyserPlugin for SovraGeyserPlugin {
+    fn name(&self) -> &'static str {
+        "sovra-geyser"
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config: Config = serde_json::from_str(&std::fs::read_to_string(config_file)?)
+            .map_err(|err| GeyserPluginError::ConfigFileReadError {
+                msg: err.to_string(),
+            })?;
+        self.include_startup = config.include_startup;
+        self.sink = Some(Sink::start(config.sink)?);
+        Ok(())
+    }
+
+    fn on_unload(&mut self) {
+        self.sink = None;
+    }
+
+    fn update_account(
+        &self,
+        account: ReplicaAccountInfoVersions,
+        slot: u64,
+        is_startup: bool,
+    ) -> Result<()> {
+        if is_startup && !self.include_startup {
+            return Ok(());
+        }
+        let (pubkey, owner, data, write_version) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(a) => (a.pubkey, a.owner, a.data, a.write_version),
+            ReplicaAccountInfoVersions::V0_0_2(a) => (a.pubkey, a.owner, a.data, a.write_version),
+            ReplicaAccountInfoVersions::V0_0_3(a) => (a.pubkey, a.owner, a.data, a.write_version),
+        };
+        if owner != PROGRAM_ID.as_ref() {
+            return Ok(());
+        }
+        let Some(update) = AccountUpdate::decode(pubkey, data, slot, write_version) else {
+            return Ok(());
+        };
+        let Some(sink) = &self.sink else {
+            return Ok(());
+        };
+        let payload = serde_json::to_vec(&update).map_err(|err| {
+            GeyserPluginError::AccountsUpdateError {
+                msg: err.to_string(),
+            }
+        })?;
+        sink.publish(payload)
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// # Safety
+///
+/// Called by the validator's plugin loader, which takes ownership of the box.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    Box::into_raw(Box::new(SovraGeyserPlugin::default()))
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+use sovra_client::{decode, AuctionState, Bid};
+
+/// One decoded account write, serialized as a JSON line.
+#[derive(Serialize)]
+pub struct AccountUpdate {
+    pub slot: u64,
+    /// Orders multiple writes to the same account within a slot.
+    pub write_version: u64,
+    pub pubkey: String,
+    #[serde(flatten)]
+    pub account: DecodedAccount,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DecodedAccount {
+    AuctionState(StateView),
+    Bid(BidView),
+}
+
+#[derive(Serialize)]
+pub struct LeaderboardEntryView {
+    pub bidder: String,
+    pub amount: u64,
+    pub bid_index: u16,
+}
+
+#[derive(Serialize)]
+pub struct StateView {
+    pub agent: String,
+    pub usdc_mint: String,
+    pub minimum_bid: u64,
+    pub bid_increment: u64,
+    pub active_bid_count: u64,
+    pub total_escrowed: u64,
+    pub leader: Option<String>,
+    pub leading_amount: u64,
+    pub leaderboard: Vec<LeaderboardEntryView>,
+    pub end_time: i64,
+    pub settled: bool,
+    /// Stored lifecycle status; an open round past `end_time` is closing.
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct BidView {
+    pub bidder: String,
+    pub bid_index: u16,
+    pub amount: u64,
+    pub max_amount: u64,
+    pub proxy: bool,
+    pub active: bool,
+    pub standing: bool,
+    pub updated_at: i64,
+}
+
+impl From<&AuctionState> for StateView {
+    fn from(state: &AuctionState) -> Self {
+        Self {
+            agent: state.agent.to_string(),
+            usdc_mint: state.usdc_mint.to_string(),
+            minimum_bid: state.minimum_bid,
+            bid_increment: state.bid_increment,
+            active_bid_count: state.active_bid_count,
+            total_escrowed: state.total_escrowed,
+            leader: (state.leader != Pubkey::default()).then(|| state.leader.to_string()),
+            leading_amount: state.leading_amount,
+            leaderboard: state
+                .leaderboard
+                .entries()
+                .iter()
+                .map(|entry| LeaderboardEntryView {
+                    bidder: entry.bidder.to_string(),
+                    amount: entry.amount,
+                    bid_index: entry.bid_index,
+                })
+                .collect(),
+            end_time: state.end_time,
+            settled: state.is_settled(),
+            status: format!("{:?}", state.status),
+        }
+    }
+}
+
+impl From<&Bid> for BidView {
+    fn from(bid: &Bid) -> Self {
+        Self {
+            bidder: bid.bidder.to_string(),
+            bid_index: bid.bid_index,
+            amount: bid.amount,
+            max_amount: bid.max_amount,
+            proxy: bid.proxy,
+            active: bid.active,
+            standing: bid.standing,
+            updated_at: bid.updated_at,
+        }
+    }
+}
+
+impl AccountUpdate {
+    /// Returns `None` for accounts that are neither an `AuctionState` nor a
+    /// `Bid` (e.g. Wormhole replay markers, or closed accounts).
+    pub fn decode(pubkey: &[u8], data: &[u8], slot: u64, write_version: u64) -> Option<Self> {
+        let account = if let Ok(bid) = decode::<Bid>(data) {
+            DecodedAccount::Bid((&bid).into())
+        } else if let Ok(state) = decode::<AuctionState>(data) {
+            DecodedAccount::AuctionState((&state).into())
+        } else {
+            return None;
+        };
+        Some(Self {
+            slot,
+            write_version,
+            pubkey: Pubkey::try_from(pubkey).ok()?.to_string(),
+            account,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+-- Applied on startup; every statement must be idempotent.
+
+CREATE TABLE IF NOT EXISTS auction_state (
+    id SMALLINT PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+    agent TEXT NOT NULL,
+    treasury TEXT NOT NULL,
+    minimum_bid BIGINT NOT NULL,
+    bid_increment BIGINT NOT NULL,
+    active_bid_count BIGINT NOT NULL,
+    total_escrowed BIGINT NOT NULL,
+    leader TEXT,
+    leading_amount BIGINT NOT NULL,
+    end_time BIGINT NOT NULL,
+    settled BOOLEAN NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+-- Latest known state of each bid account.
+CREATE TABLE IF NOT EXISTS bids (
+    address TEXT PRIMARY KEY,
+    bidder TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    max_amount BIGINT NOT NULL,
+    proxy BOOLEAN NOT NULL,
+    active BOOLEAN NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+-- Placements, top-ups and refunds, one row per emitted event.
+CREATE TABLE IF NOT EXISTS bid_events (
+    signature TEXT NOT NULL,
+    event_index INT NOT NULL,
+    slot BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    bidder TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS bid_events_bidder ON bid_events (bidder, slot);
+
+CREATE TABLE IF NOT EXISTS withdrawals (
+    signature TEXT NOT NULL,
+    event_index INT NOT NULL,
+    slot BIGINT NOT NULL,
+    bidder TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    penalty BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS settlements (
+    signature TEXT NOT NULL,
+    event_index INT NOT NULL,
+    slot BIGINT NOT NULL,
+    winner TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    charity_amount BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+ALTER TABLE settlements ADD COLUMN IF NOT EXISTS curator TEXT NOT NULL DEFAULT '';
+ALTER TABLE settlements ADD COLUMN IF NOT EXISTS curator_amount BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE auction_state ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'Created';
@@ -0,0 +1,222 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use sovra_client::{AuctionEvent, AuctionState, Bid};
+use tokio_postgres::{Client, NoTls};
+
+const SCHEMA: &str = include_str!("../schema.sql");
+
+/// Postgres sink. Every write is an upsert keyed by account address or by
+/// `(signature, event_index)`, so replaying a notification is harmless.
+pub struct Db {
+    client: Client,
+}
+
+fn int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+impl Db {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                tracing::error!(%err, "postgres connection closed");
+            }
+        });
+        client.batch_execute(SCHEMA).await?;
+        Ok(Self { client })
+    }
+
+    pub async fn upsert_state(&self, state: &AuctionState, slot: u64) -> Result<()> {
+        let leader = (state.leader != Pubkey::default()).then(|| state.leader.to_string());
+        self.client
+            .execute(
+                "INSERT INTO auction_state (id, agent, treasury, minimum_bid, bid_increment,
+                    active_bid_count, total_escrowed, leader, leading_amount, end_time, settled, slot,
+                    status)
+                 VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                 ON CONFLICT (id) DO UPDATE SET
+                    agent = EXCLUDED.agent, treasury = EXCLUDED.treasury,
+                    minimum_bid = EXCLUDED.minimum_bid, bid_increment = EXCLUDED.bid_increment,
+                    active_bid_count = EXCLUDED.active_bid_count,
+                    total_escrowed = EXCLUDED.total_escrowed, leader = EXCLUDED.leader,
+                    leading_amount = EXCLUDED.leading_amount, end_time = EXCLUDED.end_time,
+                    settled = EXCLUDED.settled, slot = EXCLUDED.slot,
+                    status = EXCLUDED.status
+                 WHERE auction_state.slot <= EXCLUDED.slot",
+                &[
+                    &state.agent.to_string(),
+                    &state.treasury.to_string(),
+                    &int(state.minimum_bid),
+                    &int(state.bid_increment),
+                    &int(state.active_bid_count),
+                    &int(state.total_escrowed),
+                    &leader,
+                    &int(state.leading_amount),
+                    &state.end_time,
+                    &state.is_settled(),
+                    &int(slot),
+                    &format!("{:?}", state.status),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_bid(&self, address: &Pubkey, bid: &Bid, slot: u64) -> Result<()> {
+        self.client
+            .execute(
+                "INSERT INTO bids (address, bidder, amount, max_amount, proxy, active,
+                    created_at, updated_at, slot)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (address) DO UPDATE SET
+                    bidder = EXCLUDED.bidder, amount = EXCLUDED.amount,
+                    max_amount = EXCLUDED.max_amount, proxy = EXCLUDED.proxy,
+                    active = EXCLUDED.active, created_at = EXCLUDED.created_at,
+                    updated_at = EXCLUDED.updated_at, slot = EXCLUDED.slot
+                 WHERE bids.slot <= EXCLUDED.slot",
+                &[
+                    &address.to_string(),
+                    &bid.bidder.to_string(),
+                    &int(bid.amount),
+                    &int(bid.max_amount),
+                    &bid.proxy,
+                    &bid.active,
+                    &bid.created_at,
+                    &bid.updated_at,
+                    &int(slot),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Marks a bid inactive when its account is closed in the same
+    /// transaction and no account update will follow.
+    async fn deactivate_bid(&self, bidder: &Pubkey, slot: u64) -> Result<()> {
+        self.client
+            .execute(
+                "UPDATE bids SET active = FALSE, slot = $2 WHERE bidder = $1 AND slot <= $2",
+                &[&bidder.to_string(), &int(slot)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_bid_event(
+        &self,
+        key: (&str, i32, i64),
+        kind: &str,
+        bidder: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let (signature, index, slot) = key;
+        self.client
+            .execute(
+                "INSERT INTO bid_events (signature, event_index, slot, kind, bidder, amount)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT DO NOTHING",
+                &[&signature, &index, &slot, &kind, &bidder.to_string(), &int(amount)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Records one decoded event. Returns whether it maps to a table.
+    pub async fn insert_event(
+        &self,
+        signature: &str,
+        index: usize,
+        slot: u64,
+        event: &AuctionEvent,
+    ) -> Result<bool> {
+        let key = (signature, index as i32, int(slot));
+        match event {
+            AuctionEvent::BidPlaced(e) => {
+                self.insert_bid_event(key, "placed", &e.bidder, e.amount).await?
+            }
+            AuctionEvent::BidUpdated(e) => {
+                self.insert_bid_event(key, "updated", &e.bidder, e.new_amount)
+                    .await?
+            }
+            AuctionEvent::BidIncreased(e) => {
+                self.insert_bid_event(key, "increased", &e.bidder, e.new_amount)
+                    .await?
+            }
+            AuctionEvent::BidDecreased(e) => {
+                self.insert_bid_event(key, "decreased", &e.bidder, e.new_amount)
+                    .await?
+            }
+            AuctionEvent::CrossChainBidReceived(e) => {
+                self.insert_bid_event(key, "cross_chain", &e.bidder, e.amount)
+                    .await?
+            }
+            AuctionEvent::BidRefunded(e) => {
+                self.insert_bid_event(key, "refunded", &e.bidder, e.amount)
+                    .await?;
+                self.deactivate_bid(&e.bidder, slot).await?;
+            }
+            AuctionEvent::BidTransferred(e) => {
+                // Recorded against the new owner; the old bid account closes.
+                self.insert_bid_event(key, "transferred", &e.to, e.amount)
+                    .await?;
+                self.deactivate_bid(&e.from, slot).await?;
+            }
+            AuctionEvent::BidSlashed(e) => {
+                self.insert_bid_event(key, "slashed", &e.bidder, e.refunded)
+                    .await?;
+                self.deactivate_bid(&e.bidder, slot).await?;
+            }
+            AuctionEvent::BidExpired(e) => {
+                self.insert_bid_event(key, "expired", &e.bidder, e.amount)
+                    .await?;
+                self.deactivate_bid(&e.bidder, slot).await?;
+            }
+            AuctionEvent::AllPayBidCollected(e) => {
+                self.insert_bid_event(key, "collected", &e.bidder, e.amount)
+                    .await?;
+                self.deactivate_bid(&e.bidder, slot).await?;
+            }
+            AuctionEvent::BidWithdrawn(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO withdrawals (signature, event_index, slot, bidder, amount, penalty)
+                         VALUES ($1, $2, $3, $4, $5, $6)
+                         ON CONFLICT DO NOTHING",
+                        &[
+                            &key.0,
+                            &key.1,
+                            &key.2,
+                            &e.bidder.to_string(),
+                            &int(e.amount),
+                            &int(e.penalty),
+                        ],
+                    )
+                    .await?;
+                self.deactivate_bid(&e.bidder, slot).await?;
+            }
+            AuctionEvent::BidSettled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO settlements (signature, event_index, slot, winner, amount,
+                            charity_amount, curator, curator_amount)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                         ON CONFLICT DO NOTHING",
+                        &[
+                            &key.0,
+                            &key.1,
+                            &key.2,
+                            &e.winner.to_string(),
+                            &int(e.amount),
+                            &int(e.charity_amount),
+                            &e.curator.to_string(),
+                            &int(e.curator_amount),
+                        ],
+                    )
+                    .await?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,209 @@
+//! Follows the auction program over websocket and mirrors its events and
+//! accounts into Postgres (see `schema.sql`). With `--metrics-listen`, it
+//! serves Prometheus metrics: `sovra_indexer_events_total{event}`,
+//! `sovra_indexer_decode_failures_total`,
+//! `sovra_indexer_subscription_errors_total{subscription}` and
+//! `sovra_indexer_slot{stream}`, the last slot each stream handled, which
+//! stops rising when indexing stalls.
+//!
+//! `--backfill` first indexes the events of transactions written by
+//! `sovra replay --transactions`, filling in history from before the
+//! indexer ran; rows already indexed are left as they are.
+
+mod db;
+
+use std::io::BufRead;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use clap::Parser;
+use futures_util::StreamExt;
+use metrics::{counter, gauge};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use serde::Deserialize;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsConfig,
+    RpcTransactionLogsFilter,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{decode, parse_logs_counting_failures, AuctionState, Bid, PROGRAM_ID};
+
+use crate::db::Db;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Parser)]
+struct Args {
+    /// HTTP RPC endpoint, used for the startup snapshot.
+    #[arg(long, env = "SOVRA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// Websocket endpoint for log and account subscriptions.
+    #[arg(long, env = "SOVRA_WS_URL", default_value = "ws://127.0.0.1:8900")]
+    ws_url: String,
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: String,
+    /// Serve Prometheus metrics on this address.
+    #[arg(long, env = "SOVRA_METRICS_LISTEN")]
+    metrics_listen: Option<SocketAddr>,
+    /// Index the transactions in this `sovra replay --transactions` file
+    /// before following the chain.
+    #[arg(long)]
+    backfill: Option<PathBuf>,
+}
+
+/// One line of a `sovra replay --transactions` file.
+#[derive(Deserialize)]
+struct Logged {
+    signature: String,
+    slot: u64,
+    logs: Vec<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+    if let Some(listen) = args.metrics_listen {
+        PrometheusBuilder::new()
+            .with_http_listener(listen)
+            .install()
+            .context("starting the metrics listener")?;
+    }
+    let db = Arc::new(
+        Db::connect(&args.database_url)
+            .await
+            .context("connecting to postgres")?,
+    );
+    if let Some(path) = &args.backfill {
+        backfill(&db, path).await.context("backfilling")?;
+    }
+
+    tokio::join!(
+        supervise("logs", || follow_logs(&args.ws_url, &db)),
+        supervise("accounts", || follow_accounts(&args.rpc_url, &args.ws_url, &db)),
+    );
+    Ok(())
+}
+
+/// Restarts a subscription whenever it errors or the socket closes.
+async fn supervise<F, Fut>(name: &'static str, mut run: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    loop {
+        match run().await {
+            Ok(()) => tracing::warn!(subscription = name, "stream closed, reconnecting"),
+            Err(err) => {
+                counter!("sovra_indexer_subscription_errors_total", "subscription" => name)
+                    .increment(1);
+                tracing::error!(subscription = name, ?err, "subscription failed");
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn follow_logs(ws_url: &str, db: &Db) -> Result<()> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut notifications, _unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![PROGRAM_ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+    tracing::info!("subscribed to program logs");
+
+    while let Some(notification) = notifications.next().await {
+        let slot = notification.context.slot;
+        let tx = notification.value;
+        if tx.err.is_some() {
+            continue;
+        }
+        index_logs(db, &tx.signature, slot, &tx.logs).await?;
+        gauge!("sovra_indexer_slot", "stream" => "logs").set(slot as f64);
+    }
+    Ok(())
+}
+
+async fn index_logs(db: &Db, signature: &str, slot: u64, logs: &[String]) -> Result<()> {
+    let (events, failures) = parse_logs_counting_failures(logs);
+    if failures > 0 {
+        counter!("sovra_indexer_decode_failures_total").increment(failures as u64);
+        tracing::warn!(signature, failures, "undecodable program events");
+    }
+    for (index, event) in events.iter().enumerate() {
+        if db.insert_event(signature, index, slot, event).await? {
+            counter!("sovra_indexer_events_total", "event" => event.name()).increment(1);
+            tracing::info!(signature, slot, event = event.name(), "indexed event");
+        }
+    }
+    Ok(())
+}
+
+async fn backfill(db: &Db, path: &Path) -> Result<()> {
+    let file = std::fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut count = 0;
+    for line in std::io::BufReader::new(file).lines() {
+        let tx: Logged = serde_json::from_str(&line?)?;
+        index_logs(db, &tx.signature, tx.slot, &tx.logs).await?;
+        count += 1;
+    }
+    tracing::info!(transactions = count, "backfilled");
+    Ok(())
+}
+
+async fn follow_accounts(rpc_url: &str, ws_url: &str, db: &Db) -> Result<()> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut notifications, _unsubscribe) = pubsub
+        .program_subscribe(
+            &PROGRAM_ID,
+            Some(RpcProgramAccountsConfig {
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            }),
+        )
+        .await?;
+
+    // Snapshot after subscribing so nothing between the two is missed;
+    // the slot guard in the upserts discards anything stale.
+    let client = SovraClient::new(rpc_url);
+    let slot = client.rpc().get_slot().await?;
+    db.upsert_state(&client.auction_state().await?, slot).await?;
+    let bids = client.bids().await?;
+    for (address, bid) in &bids {
+        db.upsert_bid(address, bid, slot).await?;
+    }
+    tracing::info!(slot, bids = bids.len(), "loaded account snapshot");
+
+    while let Some(notification) = notifications.next().await {
+        let slot = notification.context.slot;
+        let keyed = notification.value;
+        let Some(data) = keyed.account.data.decode() else {
+            continue;
+        };
+        let address: Pubkey = keyed.pubkey.parse()?;
+        if let Ok(bid) = decode::<Bid>(&data) {
+            db.upsert_bid(&address, &bid, slot).await?;
+        } else if let Ok(state) = decode::<AuctionState>(&data) {
+            db.upsert_state(&state, slot).await?;
+        }
+        gauge!("sovra_indexer_slot", "stream" => "accounts").set(slot as f64);
+    }
+    Ok(())
+}
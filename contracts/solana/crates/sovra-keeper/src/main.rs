@@ -0,0 +1,432 @@
+//! Keeper bot: waits for the auction deadline, settles to the verified top
+//! bid with `crank_settle`, then cranks `crank_refund` for every losing bid,
+//! or `collect_all_pay_bid` in an all-pay round. A top bid that expired
+//! before the deadline is cranked out with `expire_bid` first, so the next
+//! live bid settles. Given a Jito block engine, the settlement and the first
+//! refunds go out as one bundle.
+
+mod metrics;
+mod sender;
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use sovra_client::jito::JitoClient;
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{
+    instructions, jito, pda, AuctionKeys, AuctionState, AuctionStatus, Bid, LeaderboardEntry,
+    ShareKeys,
+};
+
+use crate::sender::Sender;
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long, short = 'u', env = "SOVRA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Fee payer and cranker. Any funded key works; no authority is needed.
+    #[arg(long, short = 'k', env = "SOVRA_KEYPAIR")]
+    keypair: PathBuf,
+    /// Seconds between polls while nothing is due.
+    #[arg(long, default_value_t = 15)]
+    interval: u64,
+    /// Compute-unit price in micro-lamports.
+    #[arg(long, default_value_t = 10_000)]
+    priority_fee: u64,
+    /// Jito block engine URL. When set, settlement and the first refunds are
+    /// sent as one bundle.
+    #[arg(long, env = "SOVRA_JITO_URL")]
+    jito_url: Option<String>,
+    /// Bundle tip in lamports.
+    #[arg(long, default_value_t = 10_000)]
+    jito_tip: u64,
+    /// Simulate transactions instead of sending them.
+    #[arg(long)]
+    dry_run: bool,
+    /// Emit logs as JSON lines.
+    #[arg(long)]
+    json: bool,
+    /// Serve Prometheus metrics on this address; see `metrics.rs`.
+    #[arg(long, env = "SOVRA_METRICS_LISTEN")]
+    metrics_listen: Option<SocketAddr>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    if args.json {
+        tracing_subscriber::fmt().json().with_env_filter(filter).init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(filter).init();
+    }
+
+    if let Some(listen) = args.metrics_listen {
+        metrics::install(listen)?;
+    }
+    let keypair = read_keypair_file(&args.keypair)
+        .map_err(|err| anyhow!("reading {}: {err}", args.keypair.display()))?;
+    let client = SovraClient::new(&args.url);
+    let jito = args.jito_url.map(|url| (JitoClient::new(url), args.jito_tip));
+    let sender = Sender::new(&client, &keypair, args.priority_fee, jito, args.dry_run);
+    let interval = Duration::from_secs(args.interval);
+
+    tracing::info!(keeper = %keypair.pubkey(), dry_run = args.dry_run, "keeper started");
+    loop {
+        let result = tick(&client, &sender, &keypair).await;
+        metrics::tick(result.is_ok());
+        let wait = match result {
+            Ok(wait) => wait.unwrap_or(interval).min(interval),
+            Err(err) => {
+                tracing::error!(?err, "keeper tick failed");
+                interval
+            }
+        };
+        if args.metrics_listen.is_some() {
+            if let Err(err) = metrics::record_escrow_drift(&client).await {
+                tracing::warn!(?err, "escrow drift check failed");
+            }
+        }
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Does whatever is due and returns how long until something might be.
+async fn tick(client: &SovraClient, sender: &Sender<'_>, keeper: &Keypair) -> Result<Option<Duration>> {
+    let state = client.auction_state().await?;
+    let keys = client.keys().await?;
+
+    if state.status == AuctionStatus::Closed {
+        tracing::debug!("auction is closed, nothing left to do");
+        return Ok(None);
+    }
+    if state.status.is_called_off() {
+        tracing::debug!(status = ?state.status, "auction was called off, bidders withdraw on their own");
+        return Ok(None);
+    }
+    if state.is_settled() {
+        if state.refund_root != [0; 32] {
+            tracing::debug!("refunds are claimed against the posted Merkle root");
+            return Ok(None);
+        }
+        if state.commit_bonds_held > 0 {
+            let ix = instructions::sweep_commit_bonds(&keys, keeper.pubkey());
+            let signature = sender.send(ix).await?;
+            let amount = state.commit_bonds_held;
+            tracing::info!(%signature, amount, "swept forfeited commit bonds");
+        }
+        refund_losers(client, sender, &keys, &state, keeper).await?;
+        return Ok(None);
+    }
+    if state.end_time == 0 {
+        tracing::debug!("auction is open-ended, nothing to settle");
+        return Ok(None);
+    }
+
+    let slot = client.rpc().get_slot().await?;
+    let now = client.rpc().get_block_time(slot).await?;
+    if !state.has_ended(now) {
+        let remaining = (state.end_time - now).max(1) as u64;
+        tracing::debug!(remaining_secs = remaining, "auction still running");
+        return Ok(Some(Duration::from_secs(remaining)));
+    }
+    if state.revealing(now) {
+        let remaining = (state.end_time + state.reveal_window - now).max(1) as u64;
+        tracing::debug!(remaining_secs = remaining, "sealed bids still being revealed");
+        return Ok(Some(Duration::from_secs(remaining)));
+    }
+
+    if state.is_abandonable(now) {
+        let signature = sender.send(instructions::mark_abandoned(keeper.pubkey())).await?;
+        tracing::warn!(%signature, "marked unsettled auction abandoned");
+        return Ok(Some(Duration::ZERO));
+    }
+    settle(client, sender, &keys, &state, keeper, now)
+        .await
+        .inspect_err(|_| metrics::settle_failed())?;
+    Ok(Some(Duration::ZERO))
+}
+
+async fn settle(
+    client: &SovraClient,
+    sender: &Sender<'_>,
+    keys: &AuctionKeys,
+    state: &AuctionState,
+    keeper: &Keypair,
+    now: i64,
+) -> Result<()> {
+    if state.raffle_mode && !state.raffle_drawn {
+        tracing::warn!("raffle not drawn yet, waiting for the agent");
+        return Ok(());
+    }
+    if state.candle_pending() {
+        tracing::warn!("candle end not determined yet, waiting for the agent");
+        return Ok(());
+    }
+    if state.num_winners > 1 {
+        tracing::warn!(num_winners = state.num_winners, "multi-winner rounds are settled by the agent");
+        return Ok(());
+    }
+    let Some(top) = state.leaderboard.entries().first().copied() else {
+        tracing::info!("auction ended without bids");
+        return Ok(());
+    };
+
+    // The leaderboard is program-maintained, but confirm the bid account
+    // agrees before paying out.
+    let bid = client
+        .bid_at(&top.bidder, top.bid_index)
+        .await?
+        .filter(|bid| bid.active && bid.amount == top.amount)
+        .ok_or_else(|| anyhow!("top bid by {} does not match the leaderboard", top.bidder))?;
+
+    if bid.balance_due > 0 {
+        if now <= state.payment_deadline() {
+            tracing::debug!(winner = %top.bidder, "waiting for the winner to complete payment");
+            return Ok(());
+        }
+        let ix = instructions::forfeit_deposit(
+            &keys.with_bid_index(top.bid_index),
+            keeper.pubkey(),
+            top.bidder,
+            token_account(keys, &top.bidder),
+        );
+        let signature = sender.send(ix).await?;
+        tracing::info!(bidder = %top.bidder, %signature, "forfeited unpaid deposit");
+        return Ok(());
+    }
+
+    if state.claim_pending(&pda::bid_at(keys.auction_id, &top.bidder, top.bid_index)) {
+        if now <= state.claim_deadline() {
+            tracing::debug!(winner = %top.bidder, "waiting for the winner to claim");
+            return Ok(());
+        }
+        let ix = instructions::promote_next_bidder(
+            &keys.with_bid_index(top.bid_index),
+            keeper.pubkey(),
+            top.bidder,
+            token_account(keys, &top.bidder),
+        );
+        let signature = sender.send(ix).await?;
+        tracing::info!(bidder = %top.bidder, %signature, "winner defaulted on the claim");
+        return Ok(());
+    }
+
+    let auction_keys = *keys;
+    let keys = &if bid.mint == keys.usdc_mint {
+        *keys
+    } else {
+        client.keys_for_mint(&bid.mint).await?
+    }
+    .with_bid_index(bid.bid_index);
+    if state.is_expired(&bid, now) {
+        if state.all_pay {
+            tracing::warn!(bidder = %top.bidder, "top all-pay bid expired, the agent settles this round");
+            return Ok(());
+        }
+        let bidder_usdc = token_account(keys, &top.bidder);
+        let mut ix = instructions::expire_bid(keys, keeper.pubkey(), top.bidder, bidder_usdc);
+        if state.transfer_hooks {
+            let hooks = client
+                .transfer_hook_accounts(
+                    &keys.usdc_mint,
+                    &keys.escrow,
+                    &bidder_usdc,
+                    &keys.auction_state,
+                    bid.escrowed(),
+                )
+                .await?;
+            ix.accounts.extend(hooks);
+        }
+        let claim = instructions::claim_points_due(keys, keeper.pubkey(), state, &bid, now);
+        let signature = sender.send_all(claim.into_iter().chain([ix]).collect()).await?;
+        tracing::info!(bidder = %top.bidder, %signature, "expired stale top bid");
+        return Ok(());
+    }
+    let winner_usdc = (bid.max_amount > bid.amount || bid.fee_discount_bps > 0)
+        .then(|| token_account(keys, &top.bidder));
+    let shares = ShareKeys::new(keys, state, &bid);
+    let prize = client.prize_keys(state, &top.bidder).await?;
+    // A drawn raffle has picked its winner; otherwise the program checks
+    // every other active bid against it.
+    let rivals: Vec<_> = if state.raffle_drawn {
+        Vec::new()
+    } else {
+        client
+            .bids()
+            .await?
+            .into_iter()
+            .filter(|(_, bid)| bid.active && !top.is(bid.bidder, bid.bid_index))
+            .map(|(address, _)| address)
+            .collect()
+    };
+    let mut ix = instructions::crank_settle(
+        keys,
+        keeper.pubkey(),
+        top.bidder,
+        winner_usdc,
+        shares,
+        prize,
+        &rivals,
+    );
+    if state.transfer_hooks {
+        let hooks = client
+            .transfer_hook_accounts(
+                &keys.usdc_mint,
+                &keys.escrow,
+                &keys.treasury,
+                &keys.auction_state,
+                bid.amount,
+            )
+            .await?;
+        ix.accounts.extend(hooks);
+    }
+    if sender.bundles() && state.points_rate == 0 {
+        return settle_with_refunds(client, sender, &auction_keys, state, keeper, &top, ix).await;
+    }
+    let signature = sender.send(ix).await?;
+    metrics::settled(state);
+    tracing::info!(winner = %top.bidder, amount = bid.amount, %signature, "settled auction");
+    Ok(())
+}
+
+/// Lands the settlement in one bundle with as many refunds as fit, so the
+/// first losers are paid in the same slot the round settles. The rest are
+/// left to `refund_losers`. Rounds paying points are not bundled, since
+/// their losers claim points first and a failed claim would sink the bundle.
+async fn settle_with_refunds(
+    client: &SovraClient,
+    sender: &Sender<'_>,
+    keys: &AuctionKeys,
+    state: &AuctionState,
+    keeper: &Keypair,
+    top: &LeaderboardEntry,
+    settle: Instruction,
+) -> Result<()> {
+    let mut transactions = vec![vec![settle]];
+    for (_, bid) in client.bids().await? {
+        if transactions.len() == jito::MAX_BUNDLE_LEN {
+            break;
+        }
+        if !bid.active || top.is(bid.bidder, bid.bid_index) {
+            continue;
+        }
+        if let Some(ix) = refund_instruction(client, keys, state, keeper, &bid).await? {
+            transactions.push(vec![ix]);
+        }
+    }
+    let refunds = transactions.len() - 1;
+    let signature = sender.send_bundle(transactions).await?[0];
+    metrics::settled(state);
+    tracing::info!(winner = %top.bidder, refunds, %signature, "settled auction in a bundle");
+    Ok(())
+}
+
+async fn refund_losers(
+    client: &SovraClient,
+    sender: &Sender<'_>,
+    keys: &AuctionKeys,
+    state: &AuctionState,
+    keeper: &Keypair,
+) -> Result<()> {
+    let slot = client.rpc().get_slot().await?;
+    let now = client.rpc().get_block_time(slot).await?;
+    for (_, bid) in client.bids().await?.into_iter().filter(|(_, bid)| bid.active) {
+        if !refundable(state, &bid) {
+            continue;
+        }
+        let Some(ix) = refund_instruction(client, keys, state, keeper, &bid).await? else {
+            continue;
+        };
+        // A bid holding points cannot close, so they are claimed with the refund.
+        let claim = instructions::claim_points_due(
+            &keys.with_bid_index(bid.bid_index),
+            keeper.pubkey(),
+            state,
+            &bid,
+            now,
+        );
+        match sender.send_all(claim.into_iter().chain([ix]).collect()).await {
+            Ok(signature) if state.all_pay => {
+                metrics::refund("collected");
+                tracing::info!(bidder = %bid.bidder, amount = bid.max_amount, %signature, "collected bid")
+            }
+            Ok(signature) => {
+                metrics::refund("refunded");
+                tracing::info!(bidder = %bid.bidder, amount = bid.max_amount, %signature, "refunded bid")
+            }
+            Err(err) => {
+                metrics::refund("failed");
+                tracing::warn!(bidder = %bid.bidder, ?err, "refund failed");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the keeper refunds `bid` once the round has settled.
+fn refundable(state: &AuctionState, bid: &Bid) -> bool {
+    if bid.foreign_chain != 0 {
+        tracing::debug!(bidder = %bid.bidder, "cross-chain bid, refunded by the relayer");
+        return false;
+    }
+    if bid.standing && !state.all_pay {
+        tracing::debug!(bidder = %bid.bidder, "standing bid, carried into the next round");
+        return false;
+    }
+    true
+}
+
+/// `crank_refund` for a losing bid, or `collect_all_pay_bid` in an all-pay
+/// round; `None` for bids the keeper leaves alone.
+async fn refund_instruction(
+    client: &SovraClient,
+    keys: &AuctionKeys,
+    state: &AuctionState,
+    keeper: &Keypair,
+    bid: &Bid,
+) -> Result<Option<Instruction>> {
+    if !refundable(state, bid) {
+        return Ok(None);
+    }
+    let keys = &if bid.mint == keys.usdc_mint {
+        *keys
+    } else {
+        client.keys_for_mint(&bid.mint).await?
+    }
+    .with_bid_index(bid.bid_index);
+    // All-pay rounds keep losing bids: they go to the treasury instead.
+    let (mut ix, destination) = if state.all_pay {
+        let ix = instructions::collect_all_pay_bid(keys, keeper.pubkey(), bid.bidder);
+        (ix, keys.treasury)
+    } else {
+        let bidder_usdc = token_account(keys, &bid.bidder);
+        let ix = instructions::crank_refund(keys, keeper.pubkey(), bid.bidder, bidder_usdc);
+        (ix, bidder_usdc)
+    };
+    if state.transfer_hooks {
+        let hooks = client
+            .transfer_hook_accounts(
+                &keys.usdc_mint,
+                &keys.escrow,
+                &destination,
+                &keys.auction_state,
+                bid.max_amount,
+            )
+            .await?;
+        ix.accounts.extend(hooks);
+    }
+    Ok(Some(ix))
+}
+
+fn token_account(keys: &AuctionKeys, owner: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, &keys.usdc_mint, &keys.token_program)
+}
@@ -0,0 +1,79 @@
+//! Prometheus metrics, served with `--metrics-listen`:
+//!
+//! - `sovra_keeper_ticks_total` and `sovra_keeper_tick_errors_total`; ticks
+//!   fail mostly on RPC errors.
+//! - `sovra_keeper_last_tick_timestamp_seconds`, which stops rising when the
+//!   keeper hangs.
+//! - `sovra_keeper_settlements_total`, `sovra_keeper_settle_failures_total`
+//!   and `sovra_keeper_settle_latency_seconds`, from the deadline to the
+//!   settlement landing.
+//! - `sovra_keeper_refunds_total{outcome}`.
+//! - `sovra_keeper_escrow_drift`: the escrow balance less what the active
+//!   bids in the auction mint escrowed. Below zero, bids are not covered.
+
+use std::net::SocketAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::AuctionState;
+
+const SETTLE_LATENCY: &str = "sovra_keeper_settle_latency_seconds";
+const SETTLE_LATENCY_BUCKETS: &[f64] = &[5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 900.0, 3600.0];
+
+pub fn install(listen: SocketAddr) -> Result<()> {
+    PrometheusBuilder::new()
+        .with_http_listener(listen)
+        .set_buckets_for_metric(Matcher::Full(SETTLE_LATENCY.to_string()), SETTLE_LATENCY_BUCKETS)?
+        .install()?;
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+pub fn tick(ok: bool) {
+    counter!("sovra_keeper_ticks_total").increment(1);
+    if ok {
+        gauge!("sovra_keeper_last_tick_timestamp_seconds").set(unix_now() as f64);
+    } else {
+        counter!("sovra_keeper_tick_errors_total").increment(1);
+    }
+}
+
+pub fn settled(state: &AuctionState) {
+    counter!("sovra_keeper_settlements_total").increment(1);
+    histogram!(SETTLE_LATENCY).record((unix_now() - state.end_time).max(0) as f64);
+}
+
+pub fn settle_failed() {
+    counter!("sovra_keeper_settle_failures_total").increment(1);
+}
+
+pub fn refund(outcome: &'static str) {
+    counter!("sovra_keeper_refunds_total", "outcome" => outcome).increment(1);
+}
+
+pub async fn record_escrow_drift(client: &SovraClient) -> Result<()> {
+    let state = client.auction_state().await?;
+    let balance: u64 = client
+        .rpc()
+        .get_token_account_balance(&state.escrow)
+        .await?
+        .amount
+        .parse()?;
+    let escrowed: u64 = client
+        .bids()
+        .await?
+        .iter()
+        .filter(|(_, bid)| bid.active && bid.mint == state.usdc_mint)
+        .map(|(_, bid)| bid.escrowed())
+        .sum();
+    gauge!("sovra_keeper_escrow_drift").set(balance as f64 - escrowed as f64);
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{bail, Result};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use sovra_client::jito::JitoClient;
+use sovra_client::nonblocking::SovraClient;
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// Signs keeper transactions with a priority fee and retries with
+/// exponential backoff. In dry-run mode transactions are only simulated.
+pub struct Sender<'a> {
+    client: &'a SovraClient,
+    payer: &'a Keypair,
+    priority_fee: u64,
+    /// Block engine and tip in lamports, when bundles are enabled.
+    jito: Option<(JitoClient, u64)>,
+    dry_run: bool,
+}
+
+impl<'a> Sender<'a> {
+    pub fn new(
+        client: &'a SovraClient,
+        payer: &'a Keypair,
+        priority_fee: u64,
+        jito: Option<(JitoClient, u64)>,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            client,
+            payer,
+            priority_fee,
+            jito,
+            dry_run,
+        }
+    }
+
+    pub fn bundles(&self) -> bool {
+        self.jito.is_some()
+    }
+
+    /// Sends `transactions` as one Jito bundle. Not retried: a dropped
+    /// bundle changes nothing, and the next tick rebuilds it from fresh
+    /// state. In dry-run mode only the first transaction is simulated, as
+    /// the rest depend on it.
+    pub async fn send_bundle(&self, transactions: Vec<Vec<Instruction>>) -> Result<Vec<Signature>> {
+        let Some((jito, tip)) = &self.jito else {
+            bail!("bundles are not enabled");
+        };
+        let transactions: Vec<Vec<Instruction>> = transactions
+            .into_iter()
+            .map(|instructions| {
+                let mut budgeted =
+                    vec![ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNIT_LIMIT)];
+                budgeted.extend(instructions);
+                budgeted
+            })
+            .collect();
+
+        if self.dry_run {
+            let rpc = self.client.rpc();
+            let blockhash = rpc.get_latest_blockhash().await?;
+            let tx = Transaction::new_signed_with_payer(
+                &transactions[0],
+                Some(&self.payer.pubkey()),
+                &[self.payer],
+                blockhash,
+            );
+            let result = rpc.simulate_transaction(&tx).await?.value;
+            if let Some(err) = result.err {
+                bail!("simulation failed: {err}: {:?}", result.logs.unwrap_or_default());
+            }
+            tracing::info!(
+                transactions = transactions.len(),
+                "dry run: first bundle transaction simulated"
+            );
+            return Ok(vec![tx.signatures[0]]);
+        }
+        Ok(self
+            .client
+            .send_bundle(jito, &transactions, self.payer, *tip)
+            .await?)
+    }
+
+    pub async fn send(&self, ix: Instruction) -> Result<Signature> {
+        self.send_all(vec![ix]).await
+    }
+
+    /// Sends `ixs` together in one transaction, like [`Sender::send`].
+    pub async fn send_all(&self, ixs: Vec<Instruction>) -> Result<Signature> {
+        let mut instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNIT_LIMIT),
+            ComputeBudgetInstruction::set_compute_unit_price(self.priority_fee),
+        ];
+        instructions.extend(ixs);
+        let rpc = self.client.rpc();
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let blockhash = rpc.get_latest_blockhash().await?;
+            let tx = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&self.payer.pubkey()),
+                &[self.payer],
+                blockhash,
+            );
+
+            if self.dry_run {
+                let result = rpc.simulate_transaction(&tx).await?.value;
+                if let Some(err) = result.err {
+                    bail!("simulation failed: {err}: {:?}", result.logs.unwrap_or_default());
+                }
+                tracing::info!(units = result.units_consumed, "dry run: simulation succeeded");
+                return Ok(tx.signatures[0]);
+            }
+
+            match rpc.send_and_confirm_transaction(&tx).await {
+                Ok(signature) => return Ok(signature),
+                Err(err) if attempt == MAX_ATTEMPTS => return Err(err.into()),
+                Err(err) => {
+                    tracing::warn!(attempt, %err, "transaction failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+        unreachable!("the last attempt returns")
+    }
+}
@@ -0,0 +1,229 @@
+//! The load itself: every bidder places a bid and then raises it, many
+//! bidders at once, each transaction sent without preflight and its fate
+//! read back from signature statuses.
+//!
+//! Bidders learn the leader the way a client on a websocket would, from a
+//! shared view of the auction polled every [`POLL_INTERVAL`]. Bids racing a
+//! change of leader fail with `WrongLeaderBid` just as real ones would, so
+//! that failure rate is part of what the run measures.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::{stream, StreamExt};
+use rand::Rng;
+use solana_client::client_error::ClientErrorKind;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcTransactionConfig};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{instructions, pda, AuctionKeys, AuctionState, ClientError};
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use sovra_types::AuctionError;
+use tokio::sync::watch;
+
+use crate::setup::Bidder;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+/// How long a sent transaction may go unconfirmed before it counts as
+/// dropped; a blockhash expires in about a minute.
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(75);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Op {
+    PlaceBid,
+    IncreaseBid,
+}
+
+pub struct Attempt {
+    pub op: Op,
+    /// From sending to confirmation or failure.
+    pub latency: Duration,
+    /// The confirmed signature, or why the transaction did not land.
+    pub result: Result<Signature, String>,
+}
+
+/// What every bidder needs to build a transaction, refreshed in the
+/// background.
+struct Tip {
+    state: AuctionState,
+    blockhash: Hash,
+}
+
+/// Runs `ops` bids per bidder, `concurrency` bidders at a time. Each bid
+/// adds a random amount of up to `step` base units, on top of the minimum
+/// for a first bid.
+pub async fn run(
+    client: &SovraClient,
+    keys: &AuctionKeys,
+    bidders: &[Bidder],
+    ops: usize,
+    step: u64,
+    concurrency: usize,
+) -> anyhow::Result<Vec<Attempt>> {
+    let (sender, tip) = watch::channel(Arc::new(Tip {
+        state: client.auction_state().await?,
+        blockhash: client.rpc().get_latest_blockhash().await?,
+    }));
+    let poller = async {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            match tokio::try_join!(client.auction_state(), async {
+                Ok::<_, ClientError>(client.rpc().get_latest_blockhash().await?)
+            }) {
+                Ok((state, blockhash)) => {
+                    sender.send_replace(Arc::new(Tip { state, blockhash }));
+                }
+                Err(err) => tracing::warn!(?err, "refreshing the auction state failed"),
+            }
+        }
+    };
+
+    let load = stream::iter(bidders)
+        .map(|bidder| {
+            let tip = tip.clone();
+            async move {
+                let mut attempts = Vec::with_capacity(ops);
+                let mut placed = false;
+                for _ in 0..ops {
+                    let current = tip.borrow().clone();
+                    let (op, instruction) = bid(keys, bidder, &current.state, placed, step);
+                    let attempt = submit(client, bidder, op, instruction, current.blockhash).await;
+                    placed |= op == Op::PlaceBid && attempt.result.is_ok();
+                    attempts.push(attempt);
+                }
+                attempts
+            }
+        })
+        .buffer_unordered(concurrency)
+        .concat();
+
+    tokio::select! {
+        attempts = load => Ok(attempts),
+        () = poller => unreachable!("the poller never returns"),
+    }
+}
+
+/// Places `bidder`'s bid, or raises it once placed.
+fn bid(
+    keys: &AuctionKeys,
+    bidder: &Bidder,
+    state: &AuctionState,
+    placed: bool,
+    step: u64,
+) -> (Op, Instruction) {
+    let wallet = bidder.keypair.pubkey();
+    let leader_bid = pda::leader_bid_at(state, &wallet, keys.bid_index);
+    let amount = rand::thread_rng().gen_range(1..=step.max(1));
+    if placed {
+        let ix = instructions::increase_bid(keys, wallet, bidder.usdc, amount, leader_bid);
+        return (Op::IncreaseBid, ix);
+    }
+    let amount = state.minimum_bid + amount;
+    let ix =
+        instructions::place_bid(keys, wallet, bidder.usdc, amount, leader_bid, None, None, None);
+    (Op::PlaceBid, ix)
+}
+
+async fn submit(
+    client: &SovraClient,
+    bidder: &Bidder,
+    op: Op,
+    instruction: Instruction,
+    blockhash: Hash,
+) -> Attempt {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&bidder.keypair.pubkey()),
+        &[&bidder.keypair],
+        blockhash,
+    );
+    let sent_at = Instant::now();
+    let result = confirm(client, &tx).await;
+    Attempt {
+        op,
+        latency: sent_at.elapsed(),
+        result,
+    }
+}
+
+async fn confirm(client: &SovraClient, tx: &Transaction) -> Result<Signature, String> {
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        ..RpcSendTransactionConfig::default()
+    };
+    let signature = client
+        .rpc()
+        .send_transaction_with_config(tx, config)
+        .await
+        .map_err(|err| rpc_cause(err.kind()))?;
+    let deadline = Instant::now() + CONFIRM_TIMEOUT;
+    while Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let status = match client.rpc().get_signature_statuses(&[signature]).await {
+            Ok(statuses) => statuses.value.into_iter().next().flatten(),
+            Err(err) => return Err(rpc_cause(err.kind())),
+        };
+        let Some(status) = status else { continue };
+        if !status.satisfies_commitment(client.rpc().commitment()) {
+            continue;
+        }
+        return match status.err {
+            Some(err) => Err(cause(&err)),
+            None => Ok(signature),
+        };
+    }
+    Err("not confirmed".into())
+}
+
+/// Program errors by name; anything else by the runtime's variant.
+fn cause(err: &TransactionError) -> String {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            AuctionError::from_code(*code)
+                .map_or_else(|| format!("Custom({code})"), |err| format!("{err:?}"))
+        }
+        TransactionError::InstructionError(_, err) => format!("{err:?}"),
+        err => format!("{err:?}"),
+    }
+}
+
+fn rpc_cause(kind: &ClientErrorKind) -> String {
+    match kind {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => "rpc unreachable".into(),
+        ClientErrorKind::RpcError(_) => "rejected by rpc".into(),
+        ClientErrorKind::TransactionError(err) => cause(err),
+        _ => "rpc error".into(),
+    }
+}
+
+/// Compute units each of `landed` consumed, fetched after the load so the
+/// lookups do not compete with it.
+pub async fn compute_units(
+    client: &SovraClient,
+    landed: &[(Op, Signature)],
+    concurrency: usize,
+) -> anyhow::Result<Vec<(Op, u64)>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(client.rpc().commitment()),
+        max_supported_transaction_version: Some(0),
+    };
+    stream::iter(landed)
+        .map(|(op, signature)| async move {
+            let tx = client.rpc().get_transaction_with_config(signature, config).await?;
+            let units: Option<u64> = tx
+                .transaction
+                .meta
+                .and_then(|meta| meta.compute_units_consumed.into());
+            Ok(units.map(|units| (*op, units)))
+        })
+        .buffer_unordered(concurrency)
+        .filter_map(|units: anyhow::Result<_>| async move { units.transpose() })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
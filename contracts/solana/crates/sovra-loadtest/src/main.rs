@@ -0,0 +1,107 @@
+//! Load test: starts a `solana-test-validator` with the built program (or
+//! uses one at `--url`), creates an auction and thousands of funded
+//! bidders, then has them place and raise bids concurrently. Reports
+//! throughput, confirmation latency, failures by cause and the compute
+//! units each instruction consumed.
+//!
+//! Build the program with `anchor build` first. A test validator runs far
+//! below mainnet capacity, so the numbers are for comparing changes and
+//! finding contention, not a prediction of mainnet TPS.
+
+mod load;
+mod report;
+mod setup;
+mod validator;
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use solana_sdk::signature::{Keypair, Signer};
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::USDC_DECIMALS;
+
+use crate::validator::Validator;
+
+#[derive(Parser)]
+struct Args {
+    /// A running validator with the program deployed and no auction zero
+    /// yet. Without it, one is started for the run.
+    #[arg(long, short = 'u')]
+    url: Option<String>,
+    /// Program to load into the started validator.
+    #[arg(long, default_value = "target/deploy/cartoonist_auction.so")]
+    program: PathBuf,
+    /// RPC port of the started validator.
+    #[arg(long, default_value_t = 18_899)]
+    rpc_port: u16,
+    #[arg(long, default_value_t = 2_000)]
+    bidders: usize,
+    /// Bidders sending at once.
+    #[arg(long, default_value_t = 256)]
+    concurrency: usize,
+    /// Raises each bidder attempts after placing their bid.
+    #[arg(long, default_value_t = 2)]
+    raises: usize,
+    /// Largest amount, in whole USDC, a bid adds over the minimum or a
+    /// raise adds to a bid.
+    #[arg(long, default_value_t = 10)]
+    step: u64,
+    /// Landed transactions to fetch compute units for.
+    #[arg(long, default_value_t = 1_000)]
+    cu_sample: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+    let args = Args::parse();
+    ensure!(args.bidders > 0 && args.concurrency > 0, "need at least one bidder");
+
+    let validator = match &args.url {
+        Some(_) => None,
+        None => {
+            tracing::info!(program = %args.program.display(), "starting solana-test-validator");
+            Some(Validator::spawn(&args.program, args.rpc_port).await?)
+        }
+    };
+    let url = args
+        .url
+        .clone()
+        .or_else(|| validator.as_ref().map(|v| v.url.clone()))
+        .expect("a url or a started validator");
+    let client = SovraClient::new(url);
+
+    let usdc = 10u64.pow(u32::from(USDC_DECIMALS));
+    let minimum_bid = usdc;
+    let step = args.step * usdc;
+    let budget = minimum_bid + step * (args.raises as u64 + 1);
+
+    let agent = Keypair::new();
+    setup::fund_agent(&client, &agent.pubkey(), args.bidders).await?;
+    let keys = setup::auction(&client, &agent, minimum_bid).await?;
+    tracing::info!(bidders = args.bidders, "funding bidders");
+    let bidders =
+        setup::bidders(&client, &agent, &keys, args.bidders, budget, args.concurrency).await?;
+
+    tracing::info!(concurrency = args.concurrency, "bidding");
+    let started = Instant::now();
+    let attempts =
+        load::run(&client, &keys, &bidders, args.raises + 1, step, args.concurrency).await?;
+    let elapsed = started.elapsed();
+
+    let landed: Vec<_> = attempts
+        .iter()
+        .filter_map(|a| a.result.as_ref().ok().map(|signature| (a.op, *signature)))
+        .take(args.cu_sample)
+        .collect();
+    let units = load::compute_units(&client, &landed, args.concurrency).await?;
+    report::print(&attempts, elapsed, &units);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::load::{Attempt, Op};
+
+pub fn print(attempts: &[Attempt], elapsed: Duration, units: &[(Op, u64)]) {
+    let landed = attempts.iter().filter(|a| a.result.is_ok()).count();
+    let seconds = elapsed.as_secs_f64();
+    println!(
+        "{} transactions in {seconds:.1}s: {landed} landed, {} failed",
+        attempts.len(),
+        attempts.len() - landed,
+    );
+    println!(
+        "throughput: {:.1} landed tx/s, {:.1} sent tx/s",
+        landed as f64 / seconds,
+        attempts.len() as f64 / seconds,
+    );
+
+    let mut latencies: Vec<u64> = attempts
+        .iter()
+        .filter(|a| a.result.is_ok())
+        .map(|a| a.latency.as_millis() as u64)
+        .collect();
+    latencies.sort_unstable();
+    if !latencies.is_empty() {
+        let [p50, p90, p99, max] = percentiles(&latencies);
+        println!("confirmation latency: p50 {p50}ms, p90 {p90}ms, p99 {p99}ms, max {max}ms");
+    }
+
+    let mut failures: BTreeMap<(&str, Op), usize> = BTreeMap::new();
+    for attempt in attempts {
+        if let Err(cause) = &attempt.result {
+            *failures.entry((cause, attempt.op)).or_default() += 1;
+        }
+    }
+    if !failures.is_empty() {
+        let mut failures: Vec<_> = failures.into_iter().collect();
+        failures.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        println!("\nfailures:");
+        for ((cause, op), count) in failures {
+            println!("  {count:>8}  {:<14} {cause}", format!("{op:?}"));
+        }
+    }
+
+    let mut by_op: BTreeMap<Op, Vec<u64>> = BTreeMap::new();
+    for (op, units) in units {
+        by_op.entry(*op).or_default().push(*units);
+    }
+    if !by_op.is_empty() {
+        println!("\ncompute units ({} sampled):", units.len());
+        println!("  {:<14} {:>8} {:>8} {:>8} {:>8} {:>8}", "", "min", "p50", "p90", "p99", "max");
+        for (op, mut units) in by_op {
+            units.sort_unstable();
+            let [p50, p90, p99, max] = percentiles(&units);
+            println!(
+                "  {:<14} {:>8} {p50:>8} {p90:>8} {p99:>8} {max:>8}",
+                format!("{op:?}"),
+                units[0],
+            );
+        }
+    }
+}
+
+/// p50, p90, p99 and max of a sorted, non-empty slice.
+fn percentiles(sorted: &[u64]) -> [u64; 4] {
+    let at = |p: usize| sorted[(sorted.len() * p / 100).min(sorted.len() - 1)];
+    [at(50), at(90), at(99), sorted[sorted.len() - 1]]
+}
@@ -0,0 +1,137 @@
+//! Creates the mint and auction, and funds the bidders, before the load.
+
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction as ata;
+use anchor_spl::token::spl_token;
+use anyhow::{bail, Context, Result};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{instructions, AuctionKeys, USDC_DECIMALS};
+
+/// SOL each bidder gets, for fees and their bid account's rent.
+const BIDDER_LAMPORTS: u64 = LAMPORTS_PER_SOL / 20;
+
+pub struct Bidder {
+    pub keypair: Keypair,
+    pub usdc: Pubkey,
+}
+
+/// Airdrops the agent enough SOL to fund `bidders`.
+pub async fn fund_agent(client: &SovraClient, agent: &Pubkey, bidders: usize) -> Result<()> {
+    let lamports = BIDDER_LAMPORTS * bidders as u64 + 10 * LAMPORTS_PER_SOL;
+    let signature = client.rpc().request_airdrop(agent, lamports).await?;
+    while !client.rpc().confirm_transaction(&signature).await? {
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    Ok(())
+}
+
+/// A fresh mint and auction zero on it, with the agent as mint authority.
+pub async fn auction(
+    client: &SovraClient,
+    agent: &Keypair,
+    minimum_bid: u64,
+) -> Result<AuctionKeys> {
+    if client.rpc().get_account(&sovra_client::pda::auction_state()).await.is_ok() {
+        bail!("auction zero already exists; point --url at a fresh validator");
+    }
+    let mint = Keypair::new();
+    let rent = client
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+        .await?;
+    let treasury = get_associated_token_address(&agent.pubkey(), &mint.pubkey());
+    client
+        .send(
+            &[
+                system_instruction::create_account(
+                    &agent.pubkey(),
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &agent.pubkey(),
+                    None,
+                    USDC_DECIMALS,
+                )?,
+                ata::create_associated_token_account_idempotent(
+                    &agent.pubkey(),
+                    &agent.pubkey(),
+                    &mint.pubkey(),
+                    &spl_token::ID,
+                ),
+                instructions::initialize(
+                    agent.pubkey(),
+                    mint.pubkey(),
+                    treasury,
+                    spl_token::ID,
+                    minimum_bid,
+                    0,
+                ),
+            ],
+            agent,
+            &[&mint],
+        )
+        .await
+        .context("creating the auction")?;
+    Ok(AuctionKeys::new(&client.auction_state().await?, spl_token::ID))
+}
+
+/// `count` new wallets, each with SOL and `budget` of the auction mint.
+pub async fn bidders(
+    client: &SovraClient,
+    agent: &Keypair,
+    keys: &AuctionKeys,
+    count: usize,
+    budget: u64,
+    concurrency: usize,
+) -> Result<Vec<Bidder>> {
+    stream::iter(0..count)
+        .map(|_| async {
+            let keypair = Keypair::new();
+            let usdc = get_associated_token_address(&keypair.pubkey(), &keys.usdc_mint);
+            client
+                .send(
+                    &[
+                        system_instruction::transfer(
+                            &agent.pubkey(),
+                            &keypair.pubkey(),
+                            BIDDER_LAMPORTS,
+                        ),
+                        ata::create_associated_token_account_idempotent(
+                            &agent.pubkey(),
+                            &keypair.pubkey(),
+                            &keys.usdc_mint,
+                            &spl_token::ID,
+                        ),
+                        spl_token::instruction::mint_to(
+                            &spl_token::ID,
+                            &keys.usdc_mint,
+                            &usdc,
+                            &agent.pubkey(),
+                            &[],
+                            budget,
+                        )?,
+                    ],
+                    agent,
+                    &[],
+                )
+                .await
+                .context("funding a bidder")?;
+            Ok(Bidder { keypair, usdc })
+        })
+        .buffer_unordered(concurrency)
+        .try_collect()
+        .await
+}
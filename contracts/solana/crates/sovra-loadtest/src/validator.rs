@@ -0,0 +1,63 @@
+//! A throwaway `solana-test-validator` with the program preloaded.
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use sovra_client::PROGRAM_ID;
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Killed, and its ledger deleted, on drop.
+pub struct Validator {
+    child: Child,
+    ledger: PathBuf,
+    pub url: String,
+}
+
+impl Validator {
+    pub async fn spawn(program: &Path, rpc_port: u16) -> Result<Self> {
+        let ledger = std::env::temp_dir().join(format!("sovra-loadtest-{}", std::process::id()));
+        let child = Command::new("solana-test-validator")
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(&ledger)
+            .arg("--rpc-port")
+            .arg(rpc_port.to_string())
+            .arg("--bpf-program")
+            .arg(PROGRAM_ID.to_string())
+            .arg(program)
+            .stdout(Stdio::null())
+            .spawn()
+            .context("starting solana-test-validator; is the Solana CLI installed?")?;
+        let mut validator = Self {
+            child,
+            ledger,
+            url: format!("http://127.0.0.1:{rpc_port}"),
+        };
+
+        let rpc = RpcClient::new(validator.url.clone());
+        let started = Instant::now();
+        while rpc.get_health().await.is_err() {
+            if let Some(status) = validator.child.try_wait()? {
+                bail!("solana-test-validator exited with {status}");
+            }
+            if started.elapsed() > STARTUP_TIMEOUT {
+                bail!("solana-test-validator not healthy after {STARTUP_TIMEOUT:?}");
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+        Ok(validator)
+    }
+}
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.ledger);
+    }
+}
@@ -0,0 +1,101 @@
+//! Tails the auction program's events and posts `bid_placed`, `outbid` and
+//! `settled` webhooks.
+
+mod webhook;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use futures_util::StreamExt;
+use serde_json::json;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use sovra_client::{parse_logs, AuctionEvent, PROGRAM_ID};
+
+use crate::webhook::Webhooks;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long, env = "SOVRA_WS_URL", default_value = "ws://127.0.0.1:8900")]
+    ws_url: String,
+    /// Endpoint to POST to; repeat for several.
+    #[arg(long = "webhook", env = "SOVRA_WEBHOOKS", value_delimiter = ',', required = true)]
+    webhooks: Vec<String>,
+    /// Shared secret for the HMAC signature header.
+    #[arg(long, env = "SOVRA_WEBHOOK_SECRET")]
+    secret: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+    let hooks = Arc::new(Webhooks::new(args.webhooks, args.secret));
+
+    loop {
+        if let Err(err) = follow(&args.ws_url, &hooks).await {
+            tracing::error!(?err, "event subscription failed");
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn follow(ws_url: &str, hooks: &Arc<Webhooks>) -> Result<()> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut notifications, _unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![PROGRAM_ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+
+    tracing::info!("following auction events");
+
+    while let Some(notification) = notifications.next().await {
+        let slot = notification.context.slot;
+        let tx = notification.value;
+        if tx.err.is_some() {
+            continue;
+        }
+        for event in parse_logs(&tx.logs) {
+            let meta = json!({ "signature": tx.signature, "slot": slot });
+            match &event {
+                AuctionEvent::BidPlaced(e) => hooks.dispatch(json!({
+                    "type": "bid_placed",
+                    "meta": meta,
+                    "bidder": e.bidder.to_string(),
+                    "amount": e.amount,
+                    "bid_index": e.bid_index,
+                })),
+                AuctionEvent::Outbid(e) => hooks.dispatch(json!({
+                    "type": "outbid",
+                    "meta": meta,
+                    "bidder": e.previous_leader.to_string(),
+                    "previous_amount": e.previous_amount,
+                    "new_leader": e.new_leader.to_string(),
+                    "amount": e.new_amount,
+                })),
+                AuctionEvent::BidSettled(e) => hooks.dispatch(json!({
+                    "type": "settled",
+                    "meta": meta,
+                    "winner": e.winner.to_string(),
+                    "amount": e.amount,
+                    "charity_amount": e.charity_amount,
+                    "curator": e.curator.to_string(),
+                    "curator_amount": e.curator_amount,
+                })),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
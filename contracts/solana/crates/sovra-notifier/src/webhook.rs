@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const MAX_ATTEMPTS: u32 = 6;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Delivers payloads to every configured URL. Each request carries
+/// `X-Sovra-Timestamp` and `X-Sovra-Signature: sha256=<hex>`, the HMAC-SHA256
+/// of `"{timestamp}.{body}"` under the shared secret.
+pub struct Webhooks {
+    http: reqwest::Client,
+    urls: Vec<String>,
+    secret: Vec<u8>,
+}
+
+impl Webhooks {
+    pub fn new(urls: Vec<String>, secret: String) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("static reqwest config"),
+            urls,
+            secret: secret.into_bytes(),
+        }
+    }
+
+    fn sign(&self, timestamp: u64, body: &[u8]) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Fans `payload` out to every URL in the background, retrying each with
+    /// exponential backoff so one slow endpoint never delays the others.
+    pub fn dispatch(self: &Arc<Self>, payload: serde_json::Value) {
+        let body = Arc::new(payload.to_string().into_bytes());
+        for url in self.urls.clone() {
+            let hooks = Arc::clone(self);
+            let body = Arc::clone(&body);
+            tokio::spawn(async move { hooks.deliver(&url, &body).await });
+        }
+    }
+
+    async fn deliver(&self, url: &str, body: &[u8]) {
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let result = self
+                .http
+                .post(url)
+                .header("content-type", "application/json")
+                .header("x-sovra-timestamp", timestamp)
+                .header("x-sovra-signature", self.sign(timestamp, body))
+                .body(body.to_vec())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => return,
+                Err(err) if attempt == MAX_ATTEMPTS => {
+                    tracing::error!(url, attempt, %err, "webhook delivery abandoned");
+                }
+                Err(err) => {
+                    tracing::warn!(url, attempt, %err, "webhook delivery failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use axum::extract::ws::Utf8Bytes;
+use futures_util::StreamExt;
+use sovra_client::nonblocking::SovraClient;
+use sovra_client::{decode, pda, AuctionState};
+use tokio::sync::broadcast;
+
+use crate::message;
+
+/// Frames a subscriber may fall behind by before it is dropped.
+const TOPIC_CAPACITY: usize = 256;
+
+/// One topic per auction. A topic's event stream starts with its first
+/// subscriber and stops at the first event after its last one leaves, so
+/// the service holds one log subscription per watched auction however many
+/// pages are open.
+pub struct Hub {
+    rpc_url: String,
+    ws_url: String,
+    client: SovraClient,
+    topics: Mutex<HashMap<u64, broadcast::Sender<Utf8Bytes>>>,
+}
+
+impl Hub {
+    pub fn new(rpc_url: String, ws_url: String) -> Self {
+        Self {
+            client: SovraClient::new(&rpc_url),
+            rpc_url,
+            ws_url,
+            topics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn subscribe(self: &Arc<Self>, auction_id: u64) -> broadcast::Receiver<Utf8Bytes> {
+        let mut topics = self.topics.lock().unwrap();
+        if let Some(topic) = topics.get(&auction_id) {
+            return topic.subscribe();
+        }
+        let (topic, receiver) = broadcast::channel(TOPIC_CAPACITY);
+        topics.insert(auction_id, topic.clone());
+        tokio::spawn(self.clone().fan_out(auction_id, topic));
+        tracing::info!(auction_id, "topic opened");
+        receiver
+    }
+
+    /// The snapshot frame for `auction_id`, read after subscribing so no
+    /// event falls between the two.
+    pub async fn snapshot(&self, auction_id: u64) -> Result<Utf8Bytes> {
+        let address = pda::auction_state_in(auction_id);
+        let response = self
+            .client
+            .rpc()
+            .get_account_with_commitment(&address, self.client.rpc().commitment())
+            .await?;
+        let account = response
+            .value
+            .with_context(|| format!("auction {auction_id} not found"))?;
+        let state: AuctionState = decode(&account.data)?;
+        let frame = message::snapshot(auction_id, response.context.slot, &state);
+        Ok(frame.to_string().into())
+    }
+
+    async fn fan_out(self: Arc<Self>, auction_id: u64, topic: broadcast::Sender<Utf8Bytes>) {
+        let client = SovraClient::new(&self.rpc_url).with_auction_id(auction_id);
+        let mut events = pin!(client.auction_event_stream(&self.ws_url, None));
+        while let Some(notification) = events.next().await {
+            if let Some(frame) = message::event(auction_id, &notification) {
+                let _ = topic.send(frame.to_string().into());
+            }
+            // Checked under the lock so a concurrent subscribe either joins
+            // this topic before it closes or opens a fresh one.
+            let mut topics = self.topics.lock().unwrap();
+            if topic.receiver_count() == 0 {
+                topics.remove(&auction_id);
+                tracing::info!(auction_id, "topic closed");
+                return;
+            }
+        }
+    }
+}
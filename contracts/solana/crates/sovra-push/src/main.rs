@@ -0,0 +1,109 @@
+//! Pushes live bid events to auction pages over WebSocket, so they can show
+//! outbids without polling.
+//!
+//! Clients connect to `/auctions/{id}`. The first frame is a `snapshot` of
+//! the auction's leader and leaderboard; every later frame is one bid event
+//! (`bid_placed`, `outbid`, `settled`, ...) tagged with its slot. A client
+//! too slow to keep up is disconnected, and reconnecting brings a fresh
+//! snapshot.
+
+mod hub;
+mod message;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use clap::Parser;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::hub::Hub;
+
+#[derive(Parser)]
+struct Args {
+    /// HTTP RPC endpoint, for snapshots and backfills after reconnects.
+    #[arg(long, env = "SOVRA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    #[arg(long, env = "SOVRA_WS_URL", default_value = "ws://127.0.0.1:8900")]
+    ws_url: String,
+    #[arg(long, env = "SOVRA_PUSH_LISTEN", default_value = "0.0.0.0:8081")]
+    listen: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+    let hub = Arc::new(Hub::new(args.rpc_url, args.ws_url));
+    let app = Router::new()
+        .route("/auctions/{id}", get(upgrade))
+        .with_state(hub);
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    tracing::info!(listen = %args.listen, "serving");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn upgrade(
+    State(hub): State<Arc<Hub>>,
+    Path(auction_id): Path<u64>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| serve(hub, auction_id, socket))
+}
+
+async fn serve(hub: Arc<Hub>, auction_id: u64, mut socket: WebSocket) {
+    let mut events = hub.subscribe(auction_id);
+    let snapshot = match hub.snapshot(auction_id).await {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            tracing::warn!(auction_id, ?err, "snapshot failed");
+            let _ = socket
+                .send(Message::Close(Some(CloseFrame {
+                    code: close_code::ERROR,
+                    reason: "auction unavailable".into(),
+                })))
+                .await;
+            return;
+        }
+    };
+    if socket.send(Message::Text(snapshot)).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(frame) => {
+                    if socket.send(Message::Text(frame)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::AGAIN,
+                            reason: "too far behind; reconnect for a snapshot".into(),
+                        })))
+                        .await;
+                    return;
+                }
+                Err(RecvError::Closed) => return,
+            },
+            incoming = socket.recv() => match incoming {
+                // Pings are answered by axum; anything else from the client
+                // is ignored.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
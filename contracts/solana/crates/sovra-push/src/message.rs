@@ -0,0 +1,117 @@
+//! JSON text frames sent to subscribers.
+
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Value};
+use sovra_client::stream::EventNotification;
+use sovra_client::{AuctionEvent, AuctionState};
+
+/// First frame on every connection: the auction as of `slot`. Events at or
+/// before `slot` are already reflected in it and can be dropped.
+pub fn snapshot(auction_id: u64, slot: u64, state: &AuctionState) -> Value {
+    let leaderboard: Vec<Value> = state
+        .leaderboard
+        .entries()
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            json!({
+                "rank": index + 1,
+                "bidder": entry.bidder.to_string(),
+                "bid_index": entry.bid_index,
+                "amount": entry.amount,
+            })
+        })
+        .collect();
+    json!({
+        "type": "snapshot",
+        "auction_id": auction_id,
+        "slot": slot,
+        "status": format!("{:?}", state.status),
+        "leader": (state.leader != Pubkey::default()).then(|| state.leader.to_string()),
+        "leading_amount": state.leading_amount,
+        "minimum_bid": state.minimum_bid,
+        "bid_increment": state.bid_increment,
+        "active_bid_count": state.active_bid_count,
+        "end_time": state.end_time,
+        "leaderboard": leaderboard,
+    })
+}
+
+/// The frame for a bid lifecycle event; `None` for other events.
+pub fn event(auction_id: u64, notification: &EventNotification) -> Option<Value> {
+    let mut body = match &notification.event {
+        AuctionEvent::BidPlaced(e) => json!({
+            "type": "bid_placed",
+            "bidder": e.bidder.to_string(),
+            "bid_index": e.bid_index,
+            "amount": e.amount,
+        }),
+        AuctionEvent::BidUpdated(e) => json!({
+            "type": "bid_updated",
+            "bidder": e.bidder.to_string(),
+            "amount": e.new_amount,
+        }),
+        AuctionEvent::BidIncreased(e) => json!({
+            "type": "bid_increased",
+            "bidder": e.bidder.to_string(),
+            "amount": e.new_amount,
+            "max_amount": e.max_amount,
+        }),
+        AuctionEvent::BidDecreased(e) => json!({
+            "type": "bid_decreased",
+            "bidder": e.bidder.to_string(),
+            "amount": e.new_amount,
+            "max_amount": e.max_amount,
+        }),
+        AuctionEvent::Outbid(e) => json!({
+            "type": "outbid",
+            "bidder": e.previous_leader.to_string(),
+            "previous_amount": e.previous_amount,
+            "new_leader": e.new_leader.to_string(),
+            "amount": e.new_amount,
+        }),
+        AuctionEvent::BidWithdrawn(e) => json!({
+            "type": "bid_withdrawn",
+            "bidder": e.bidder.to_string(),
+            "amount": e.amount,
+            "penalty": e.penalty,
+        }),
+        AuctionEvent::BidRefunded(e) => json!({
+            "type": "bid_refunded",
+            "bidder": e.bidder.to_string(),
+            "amount": e.amount,
+        }),
+        AuctionEvent::BidSlashed(e) => json!({
+            "type": "bid_slashed",
+            "bidder": e.bidder.to_string(),
+            "amount": e.refunded,
+        }),
+        AuctionEvent::BidExpired(e) => json!({
+            "type": "bid_expired",
+            "bidder": e.bidder.to_string(),
+            "amount": e.amount,
+        }),
+        AuctionEvent::BidTransferred(e) => json!({
+            "type": "bid_transferred",
+            "bidder": e.from.to_string(),
+            "to": e.to.to_string(),
+            "amount": e.amount,
+        }),
+        AuctionEvent::BidSettled(e) => json!({
+            "type": "settled",
+            "winner": e.winner.to_string(),
+            "amount": e.amount,
+        }),
+        AuctionEvent::StatusChanged(e) => json!({
+            "type": "status_changed",
+            "from": format!("{:?}", e.from),
+            "to": format!("{:?}", e.to),
+        }),
+        _ => return None,
+    };
+    body["auction_id"] = auction_id.into();
+    body["slot"] = notification.slot.into();
+    body["signature"] = notification.signature.to_string().into();
+    body["index"] = notification.index.into();
+    Some(body)
+}
@@ -0,0 +1,213 @@
+//! Plays one round between simulated bidders under the program's bidding
+//! rules: bids must meet `minimum_bid`, proxy bids escalate eBay style one
+//! `bid_increment` over the other side (see `leader.rs` in the program),
+//! and the leader at the deadline pays their bid.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::model::{Behavior, Bidder};
+
+const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub minimum_bid: u64,
+    pub bid_increment: u64,
+    /// Soft close: a bid landing within this many seconds of the deadline
+    /// pushes it back to that many seconds after the bid. Zero is a hard
+    /// close, which is all the program does today; the window is modeled so
+    /// its effect can be weighed before building it.
+    pub anti_snipe: i64,
+    /// Share of the winning price paid out before the treasury: charity,
+    /// curator and referral shares together.
+    pub fee_bps: u16,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Outcome {
+    /// Winning price; zero when nobody bid.
+    pub price: u64,
+    /// What reaches the treasury once fees are paid.
+    pub treasury: u64,
+    /// Bidders who placed at least one bid.
+    pub participants: usize,
+    /// Bid transactions that landed, counting raises.
+    pub bids: usize,
+    /// Snipes that landed after the deadline.
+    pub missed_snipes: usize,
+    pub extensions: usize,
+    /// Whether the item went to the bidder who valued it most.
+    pub efficient: bool,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Bid {
+    amount: u64,
+    max_amount: u64,
+    proxy: bool,
+}
+
+struct Round<'a> {
+    config: &'a Config,
+    bidders: &'a [Bidder],
+    bids: Vec<Option<Bid>>,
+    leader: Option<usize>,
+    leading_amount: u64,
+    end: i64,
+    outcome: Outcome,
+}
+
+pub fn run(config: &Config, bidders: &[Bidder], duration: i64) -> Outcome {
+    let mut round = Round {
+        config,
+        bidders,
+        bids: vec![None; bidders.len()],
+        leader: None,
+        leading_amount: 0,
+        end: duration,
+        outcome: Outcome::default(),
+    };
+    // Actions in time order; ties go to whoever was scheduled first.
+    let mut queue = BinaryHeap::new();
+    let mut seq = 0usize;
+    for (index, bidder) in bidders.iter().enumerate() {
+        let at = match bidder.behavior {
+            Behavior::Sniper => duration - bidder.snipe_lead + bidder.latency,
+            Behavior::Proxy | Behavior::Incremental => bidder.arrival,
+        };
+        queue.push(Reverse((at, seq, index)));
+        seq += 1;
+    }
+
+    while let Some(Reverse((now, _, index))) = queue.pop() {
+        if now >= round.end {
+            if bidders[index].behavior == Behavior::Sniper && round.bids[index].is_none() {
+                round.outcome.missed_snipes += 1;
+            }
+            continue;
+        }
+        let previous_leader = round.leader;
+        if !round.act(index) {
+            continue;
+        }
+        round.outcome.bids += 1;
+        if config.anti_snipe > 0 && now >= round.end - config.anti_snipe {
+            round.end = now + config.anti_snipe;
+            round.outcome.extensions += 1;
+        }
+        // Incremental bidders who are left behind come back for another go.
+        for loser in [Some(index), previous_leader].into_iter().flatten() {
+            if round.leader != Some(loser) && round.wants_to_raise(loser) {
+                queue.push(Reverse((now + bidders[loser].reaction.max(1), seq, loser)));
+                seq += 1;
+            }
+        }
+    }
+
+    round.settle()
+}
+
+impl Round<'_> {
+    /// Places or raises `index`'s bid, returning whether it did.
+    fn act(&mut self, index: usize) -> bool {
+        let bidder = &self.bidders[index];
+        let bid = match bidder.behavior {
+            Behavior::Proxy | Behavior::Sniper => {
+                if self.bids[index].is_some() || bidder.value < self.minimum_next() {
+                    return false;
+                }
+                Bid {
+                    amount: self.config.minimum_bid,
+                    max_amount: bidder.value,
+                    proxy: true,
+                }
+            }
+            Behavior::Incremental => {
+                let amount = self.minimum_next();
+                if self.leader == Some(index) || bidder.value < amount {
+                    return false;
+                }
+                Bid {
+                    amount,
+                    max_amount: amount,
+                    proxy: false,
+                }
+            }
+        };
+        if self.bids[index].is_none() {
+            self.outcome.participants += 1;
+        }
+        self.bids[index] = Some(bid);
+        self.escalate(index);
+        true
+    }
+
+    /// Least a bid can usefully offer: the minimum bid, or one increment
+    /// over the leader.
+    fn minimum_next(&self) -> u64 {
+        match self.leader {
+            Some(_) => self
+                .leading_amount
+                .saturating_add(self.config.bid_increment.max(1))
+                .max(self.config.minimum_bid),
+            None => self.config.minimum_bid,
+        }
+    }
+
+    fn wants_to_raise(&self, index: usize) -> bool {
+        let bidder = &self.bidders[index];
+        bidder.behavior == Behavior::Incremental && bidder.value >= self.minimum_next()
+    }
+
+    /// The program's `auto_escalate`: the higher ceiling leads, proxy bids
+    /// rise to one increment over the other side, and ties keep the leader.
+    fn escalate(&mut self, challenger: usize) {
+        let increment = self.config.bid_increment.max(1);
+        let Some(leader) = self.leader.filter(|&leader| leader != challenger) else {
+            self.leader = Some(challenger);
+            self.leading_amount = self.bids[challenger].unwrap_or_default().amount;
+            return;
+        };
+        let mut ours = self.bids[challenger].unwrap_or_default();
+        let mut theirs = self.bids[leader].unwrap_or_default();
+        if ours.max_amount > theirs.max_amount {
+            if theirs.proxy {
+                theirs.amount = theirs.max_amount;
+            }
+            if ours.proxy {
+                ours.amount = ours.amount.max(
+                    theirs.amount.saturating_add(increment).min(ours.max_amount),
+                );
+            }
+            self.leader = Some(challenger);
+            self.leading_amount = ours.amount;
+        } else {
+            if ours.proxy {
+                ours.amount = ours.max_amount;
+            }
+            if theirs.proxy {
+                theirs.amount = theirs.amount.max(
+                    ours.amount.saturating_add(increment).min(theirs.max_amount),
+                );
+            }
+            self.leading_amount = theirs.amount;
+        }
+        self.bids[challenger] = Some(ours);
+        self.bids[leader] = Some(theirs);
+    }
+
+    fn settle(mut self) -> Outcome {
+        let Some(winner) = self.leader else {
+            return self.outcome;
+        };
+        let price = self.leading_amount;
+        let fees = u128::from(price) * u128::from(self.config.fee_bps)
+            / u128::from(BPS_DENOMINATOR);
+        self.outcome.price = price;
+        self.outcome.treasury = price - fees as u64;
+        let top_value = self.bidders.iter().map(|b| b.value).max().unwrap_or(0);
+        self.outcome.efficient = self.bidders[winner].value == top_value;
+        self.outcome
+    }
+}
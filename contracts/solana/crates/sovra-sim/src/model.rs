@@ -0,0 +1,85 @@
+//! Bidder behavior models and the population a round's bidders are drawn
+//! from.
+
+use rand::Rng;
+
+/// How a bidder goes about reaching their private value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    /// Places one proxy bid with their value as the ceiling on arrival and
+    /// lets escalation do the rest.
+    Proxy,
+    /// Bids one increment over the leader, and again each time they are
+    /// outbid, until the price passes their value.
+    Incremental,
+    /// Waits until the last seconds, then places a proxy bid at their
+    /// value. A late enough snipe lands after the deadline and is lost.
+    Sniper,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Bidder {
+    pub behavior: Behavior,
+    /// Most they will pay, in base units.
+    pub value: u64,
+    /// Seconds into the round at which they first look at it.
+    pub arrival: i64,
+    /// Seconds an incremental bidder takes to respond to being outbid.
+    pub reaction: i64,
+    /// Seconds before the deadline a sniper sends their bid, and how long
+    /// it takes to land.
+    pub snipe_lead: i64,
+    pub latency: i64,
+}
+
+/// Latest a sniper sends their bid, in seconds before the deadline.
+const MAX_SNIPE_LEAD: i64 = 10;
+/// Slowest a sniper's bid takes to land.
+const MAX_LATENCY: i64 = 3;
+
+pub struct Population {
+    pub bidders: usize,
+    /// Median private value, in base units. Values are log-normal around it.
+    pub median_value: f64,
+    /// Standard deviation of the log of the value.
+    pub value_spread: f64,
+    /// Mean reaction time of incremental bidders, in seconds.
+    pub reaction: f64,
+    /// Relative weights of proxy, incremental and sniping bidders.
+    pub mix: [f64; 3],
+}
+
+impl Population {
+    /// Bidders for one round lasting `duration` seconds.
+    pub fn draw(&self, rng: &mut impl Rng, duration: i64) -> Vec<Bidder> {
+        (0..self.bidders)
+            .map(|_| Bidder {
+                behavior: self.behavior(rng),
+                value: (self.median_value * (self.value_spread * standard_normal(rng)).exp())
+                    as u64,
+                arrival: rng.gen_range(0..duration.max(1)),
+                reaction: (-self.reaction * (1.0 - rng.gen::<f64>()).ln()).round() as i64,
+                snipe_lead: rng.gen_range(0..=MAX_SNIPE_LEAD),
+                latency: rng.gen_range(0..=MAX_LATENCY),
+            })
+            .collect()
+    }
+
+    fn behavior(&self, rng: &mut impl Rng) -> Behavior {
+        let pick = rng.gen::<f64>() * self.mix.iter().sum::<f64>();
+        if pick < self.mix[0] {
+            Behavior::Proxy
+        } else if pick < self.mix[0] + self.mix[1] {
+            Behavior::Incremental
+        } else {
+            Behavior::Sniper
+        }
+    }
+}
+
+/// Box-Muller.
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
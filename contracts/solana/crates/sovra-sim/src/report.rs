@@ -0,0 +1,80 @@
+//! Aggregates a config's outcomes across trials and prints the comparison.
+
+use crate::engine::{Config, Outcome};
+
+/// USDC has 6 decimals.
+pub const SCALE: f64 = 1e6;
+
+pub struct Summary {
+    pub config: Config,
+    /// Share of rounds that drew at least one bid.
+    pub sold: f64,
+    pub mean_price: f64,
+    /// 10th, 50th and 90th percentile prices, unsold rounds counting as zero.
+    pub price_percentiles: [u64; 3],
+    pub mean_treasury: f64,
+    /// Share of the population that placed a bid.
+    pub participation: f64,
+    pub mean_bids: f64,
+    pub mean_extensions: f64,
+    pub mean_missed_snipes: f64,
+    /// Share of sold rounds won by the bidder who valued the item most.
+    pub efficiency: f64,
+}
+
+impl Summary {
+    pub fn new(config: Config, outcomes: &[Outcome], bidders: usize) -> Self {
+        let trials = outcomes.len().max(1) as f64;
+        let mean = |f: fn(&Outcome) -> f64| outcomes.iter().map(f).sum::<f64>() / trials;
+        let mut prices: Vec<u64> = outcomes.iter().map(|o| o.price).collect();
+        prices.sort_unstable();
+        let percentile = |p: usize| prices.get(prices.len() * p / 100).copied().unwrap_or(0);
+        let sold = outcomes.iter().filter(|o| o.price > 0).count();
+        Self {
+            config,
+            sold: sold as f64 / trials,
+            mean_price: mean(|o| o.price as f64),
+            price_percentiles: [percentile(10), percentile(50), percentile(90)],
+            mean_treasury: mean(|o| o.treasury as f64),
+            participation: mean(|o| o.participants as f64) / bidders.max(1) as f64,
+            mean_bids: mean(|o| o.bids as f64),
+            mean_extensions: mean(|o| o.extensions as f64),
+            mean_missed_snipes: mean(|o| o.missed_snipes as f64),
+            efficiency: outcomes.iter().filter(|o| o.efficient).count() as f64
+                / sold.max(1) as f64,
+        }
+    }
+}
+
+/// One row per config, best expected treasury revenue first.
+pub fn print(summaries: &mut [Summary]) {
+    summaries.sort_by(|a, b| b.mean_treasury.total_cmp(&a.mean_treasury));
+    println!(
+        "{:>10} {:>9} {:>6} {:>5} | {:>6} {:>10} {:>10} {:>10} {:>10} {:>10} | {:>7} {:>6} \
+         {:>6} {:>6} {:>6}",
+        "minimum", "increment", "snipe", "fee", "sold", "revenue", "p10", "p50", "p90",
+        "treasury", "partic.", "bids", "ext.", "missed", "effic.",
+    );
+    for s in summaries.iter() {
+        let c = &s.config;
+        println!(
+            "{:>10.2} {:>9.2} {:>5}s {:>5} | {:>5.1}% {:>10.2} {:>10.2} {:>10.2} {:>10.2} \
+             {:>10.2} | {:>6.1}% {:>6.1} {:>6.2} {:>6.2} {:>5.1}%",
+            c.minimum_bid as f64 / SCALE,
+            c.bid_increment as f64 / SCALE,
+            c.anti_snipe,
+            c.fee_bps,
+            s.sold * 100.0,
+            s.mean_price / SCALE,
+            s.price_percentiles[0] as f64 / SCALE,
+            s.price_percentiles[1] as f64 / SCALE,
+            s.price_percentiles[2] as f64 / SCALE,
+            s.mean_treasury / SCALE,
+            s.participation * 100.0,
+            s.mean_bids,
+            s.mean_extensions,
+            s.mean_missed_snipes,
+            s.efficiency * 100.0,
+        );
+    }
+}
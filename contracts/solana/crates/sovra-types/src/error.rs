@@ -0,0 +1,562 @@
+//! The auction program's custom error codes.
+
+/// Custom program errors, numbered from 6000 as Anchor assigns them. A
+/// failed transaction reports the code as `Custom(code)`; [`Self::from_code`]
+/// maps it back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AuctionError {
+    OnlyAgent = 6000,
+    BidTooLow,
+    BidNotActive,
+    AmountBelowMinimum,
+    InsufficientEscrow,
+    WrongBidder,
+    BidStillActive,
+    ArithmeticOverflow,
+    InvalidMintDecimals,
+    InvalidAmountChange,
+    InvalidBps,
+    AuctionEnded,
+    LeaderBidRequired,
+    WrongLeaderBid,
+    WinnerTokenAccountRequired,
+    BuyNowDisabled,
+    BelowBuyNowPrice,
+    AuctionSettled,
+    InvalidNumWinners,
+    WinnersMismatch,
+    InvalidTokenAccount,
+    CharityAccountRequired,
+    EscrowInsolvent,
+    AuctionNotEnded,
+    AuctionNotSettled,
+    NoTie,
+    InvalidRandomness,
+    RandomnessNotRevealed,
+    RaffleModeDisabled,
+    RaffleAlreadyDrawn,
+    RaffleEntrantsMismatch,
+    RaffleNotDrawn,
+    InvalidVaa,
+    UnknownEmitter,
+    OnlyRelayer,
+    InvalidRefundRoot,
+    RefundRootNotPosted,
+    MerkleRefundsActive,
+    InvalidMerkleProof,
+    RefundAlreadyClaimed,
+    RefundExceedsEscrow,
+    InvalidContentHash,
+    ContentAlreadyCommitted,
+    ContentNotCommitted,
+    ContentHashMismatch,
+    ContentUriTooLong,
+    InvalidPrizeAmount,
+    InvalidPrizeMint,
+    PrizeAlreadyDeposited,
+    PrizeLocked,
+    NoPrize,
+    PrizeAccountsRequired,
+    PrizeRequiresSingleWinner,
+    InvalidVestingDuration,
+    VestingInProgress,
+    NothingVested,
+    InvalidPaymentWindow,
+    BalanceDue,
+    NoBalanceDue,
+    PaymentWindowClosed,
+    PaymentWindowOpen,
+    MintNotAccepted,
+    MintAlreadyAccepted,
+    AcceptedMintsFull,
+    AcceptedMintInUse,
+    AuctionMintOnly,
+    UnsafeMint,
+    NonTransferableMint,
+    OnlySettler,
+    InvalidAbandonmentWindow,
+    AuctionAbandoned,
+    NotAbandonable,
+    CuratorAccountRequired,
+    SelfReferral,
+    ReferrerAccountRequired,
+    ForeignBid,
+    ReceiptAccountsRequired,
+    OnlyModerator,
+    InvalidBidTtl,
+    BidExpired,
+    BidNotExpired,
+    InvalidStatusTransition,
+    AuctionCancelled,
+    RivalBidsMismatch,
+    WinnerNotHighest,
+    EnglishModeLocked,
+    LeaderAccountRequired,
+    InvalidCandleWindow,
+    CandleModeDisabled,
+    CandleAlreadyDetermined,
+    CandleNotDetermined,
+    CandleEntrantsMismatch,
+    BidAfterCandle,
+    BidsNonRefundable,
+    AllPayLocked,
+    AllPayDisabled,
+    WrongAuction,
+    InvalidAuctionId,
+    NotInRegistry,
+    AuctionClosed,
+    BidsOutstanding,
+    BidAlreadyPlaced,
+    LotSettled,
+    LotWinnerRequired,
+    LotLeaderLocked,
+    InvalidBundle,
+    BundlePending,
+    AuctionNotStarted,
+    StandingBid,
+    InvalidTrailingRounds,
+    AuditLogRequired,
+    EscrowNotEmpty,
+    MintNotRescuable,
+    BidRanked,
+    BidNotRanked,
+    RankHintMismatch,
+    RankOrderViolated,
+    LeaderNotRankedFirst,
+    TopKSettlementPending,
+    TopKUniformPrice,
+    RankedListEmpty,
+    BidPairsMismatch,
+    BidderDenylisted,
+    BidderNotDenylisted,
+    DenylistRequired,
+    DenylistFull,
+    OnlyArbiter,
+    ArbiterRequired,
+    NoHeldSettlement,
+    SettlementAlreadyHeld,
+    DisputeWindowOpen,
+    DisputeWindowClosed,
+    DisputeRequiresSingleWinner,
+    InvalidDisputeWindow,
+    InvalidClaimWindow,
+    NoClaimWindow,
+    ClaimWindowOpen,
+    ClaimWindowClosed,
+    WinClaimed,
+    WinNotClaimed,
+    ClaimRequiresSingleWinner,
+    SettlementTooSoon,
+    MemoTooLong,
+    MemoProgramRequired,
+    NoPoints,
+    InvalidFeeTiers,
+    TieLostOnPriority,
+    BidderSignatureRequired,
+    InvalidSessionKey,
+    SessionKeyExpired,
+    SessionSpendCapExceeded,
+    SealedBidsLocked,
+    SealedBidsOnly,
+    SealedBidsDisabled,
+    BidSealed,
+    BidNotSealed,
+    NotRevealing,
+    RevealWindowOpen,
+    CommitmentMismatch,
+    InvalidRevealWindow,
+    InvalidThread,
+    SettlementNotSchedulable,
+    PointsUnclaimed,
+    TieChanged,
+    AuctionPaused,
+    BidReceipted,
+}
+
+const ALL: [AuctionError; 166] = {
+    use AuctionError::*;
+    [
+        OnlyAgent,
+        BidTooLow,
+        BidNotActive,
+        AmountBelowMinimum,
+        InsufficientEscrow,
+        WrongBidder,
+        BidStillActive,
+        ArithmeticOverflow,
+        InvalidMintDecimals,
+        InvalidAmountChange,
+        InvalidBps,
+        AuctionEnded,
+        LeaderBidRequired,
+        WrongLeaderBid,
+        WinnerTokenAccountRequired,
+        BuyNowDisabled,
+        BelowBuyNowPrice,
+        AuctionSettled,
+        InvalidNumWinners,
+        WinnersMismatch,
+        InvalidTokenAccount,
+        CharityAccountRequired,
+        EscrowInsolvent,
+        AuctionNotEnded,
+        AuctionNotSettled,
+        NoTie,
+        InvalidRandomness,
+        RandomnessNotRevealed,
+        RaffleModeDisabled,
+        RaffleAlreadyDrawn,
+        RaffleEntrantsMismatch,
+        RaffleNotDrawn,
+        InvalidVaa,
+        UnknownEmitter,
+        OnlyRelayer,
+        InvalidRefundRoot,
+        RefundRootNotPosted,
+        MerkleRefundsActive,
+        InvalidMerkleProof,
+        RefundAlreadyClaimed,
+        RefundExceedsEscrow,
+        InvalidContentHash,
+        ContentAlreadyCommitted,
+        ContentNotCommitted,
+        ContentHashMismatch,
+        ContentUriTooLong,
+        InvalidPrizeAmount,
+        InvalidPrizeMint,
+        PrizeAlreadyDeposited,
+        PrizeLocked,
+        NoPrize,
+        PrizeAccountsRequired,
+        PrizeRequiresSingleWinner,
+        InvalidVestingDuration,
+        VestingInProgress,
+        NothingVested,
+        InvalidPaymentWindow,
+        BalanceDue,
+        NoBalanceDue,
+        PaymentWindowClosed,
+        PaymentWindowOpen,
+        MintNotAccepted,
+        MintAlreadyAccepted,
+        AcceptedMintsFull,
+        AcceptedMintInUse,
+        AuctionMintOnly,
+        UnsafeMint,
+        NonTransferableMint,
+        OnlySettler,
+        InvalidAbandonmentWindow,
+        AuctionAbandoned,
+        NotAbandonable,
+        CuratorAccountRequired,
+        SelfReferral,
+        ReferrerAccountRequired,
+        ForeignBid,
+        ReceiptAccountsRequired,
+        OnlyModerator,
+        InvalidBidTtl,
+        BidExpired,
+        BidNotExpired,
+        InvalidStatusTransition,
+        AuctionCancelled,
+        RivalBidsMismatch,
+        WinnerNotHighest,
+        EnglishModeLocked,
+        LeaderAccountRequired,
+        InvalidCandleWindow,
+        CandleModeDisabled,
+        CandleAlreadyDetermined,
+        CandleNotDetermined,
+        CandleEntrantsMismatch,
+        BidAfterCandle,
+        BidsNonRefundable,
+        AllPayLocked,
+        AllPayDisabled,
+        WrongAuction,
+        InvalidAuctionId,
+        NotInRegistry,
+        AuctionClosed,
+        BidsOutstanding,
+        BidAlreadyPlaced,
+        LotSettled,
+        LotWinnerRequired,
+        LotLeaderLocked,
+        InvalidBundle,
+        BundlePending,
+        AuctionNotStarted,
+        StandingBid,
+        InvalidTrailingRounds,
+        AuditLogRequired,
+        EscrowNotEmpty,
+        MintNotRescuable,
+        BidRanked,
+        BidNotRanked,
+        RankHintMismatch,
+        RankOrderViolated,
+        LeaderNotRankedFirst,
+        TopKSettlementPending,
+        TopKUniformPrice,
+        RankedListEmpty,
+        BidPairsMismatch,
+        BidderDenylisted,
+        BidderNotDenylisted,
+        DenylistRequired,
+        DenylistFull,
+        OnlyArbiter,
+        ArbiterRequired,
+        NoHeldSettlement,
+        SettlementAlreadyHeld,
+        DisputeWindowOpen,
+        DisputeWindowClosed,
+        DisputeRequiresSingleWinner,
+        InvalidDisputeWindow,
+        InvalidClaimWindow,
+        NoClaimWindow,
+        ClaimWindowOpen,
+        ClaimWindowClosed,
+        WinClaimed,
+        WinNotClaimed,
+        ClaimRequiresSingleWinner,
+        SettlementTooSoon,
+        MemoTooLong,
+        MemoProgramRequired,
+        NoPoints,
+        InvalidFeeTiers,
+        TieLostOnPriority,
+        BidderSignatureRequired,
+        InvalidSessionKey,
+        SessionKeyExpired,
+        SessionSpendCapExceeded,
+        SealedBidsLocked,
+        SealedBidsOnly,
+        SealedBidsDisabled,
+        BidSealed,
+        BidNotSealed,
+        NotRevealing,
+        RevealWindowOpen,
+        CommitmentMismatch,
+        InvalidRevealWindow,
+        InvalidThread,
+        SettlementNotSchedulable,
+        PointsUnclaimed,
+        TieChanged,
+        AuctionPaused,
+        BidReceipted,
+    ]
+};
+
+impl AuctionError {
+    /// The `Custom` code the program fails with.
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        let index = code.checked_sub(Self::OnlyAgent.code())?;
+        ALL.get(index as usize).copied()
+    }
+
+    /// The `#[msg]` the program logs alongside the code.
+    pub const fn message(self) -> &'static str {
+        use AuctionError::*;
+        match self {
+            OnlyAgent => "Only the agent can perform this action",
+            BidTooLow => "Bid below minimum",
+            BidNotActive => "Bid is not active",
+            AmountBelowMinimum => "Bid amount would fall below minimum",
+            InsufficientEscrow => "Insufficient escrow balance",
+            WrongBidder => "Bid does not belong to this bidder",
+            BidStillActive => "Bid is still active — withdraw first or wait to win",
+            ArithmeticOverflow => "Arithmetic overflow or underflow",
+            InvalidMintDecimals => "Invalid mint decimals",
+            InvalidAmountChange => "Invalid amount change",
+            InvalidBps => "Basis points must not exceed 10000",
+            AuctionEnded => "Auction has ended",
+            LeaderBidRequired => "The current leader's bid account must be provided",
+            WrongLeaderBid => "Provided bid is not the current leader",
+            WinnerTokenAccountRequired => "Winner token account required to refund proxy surplus",
+            BuyNowDisabled => "Buy-it-now is not enabled",
+            BelowBuyNowPrice => "Escrowed amount is below the buy-it-now price",
+            AuctionSettled => "Auction is already settled",
+            InvalidNumWinners => "Number of winners must be between 1 and the leaderboard size",
+            WinnersMismatch => "Winning bids do not match the leaderboard",
+            InvalidTokenAccount => "Token account has the wrong owner or mint",
+            CharityAccountRequired => "Charity token account required for this settlement",
+            EscrowInsolvent => "Escrow balance is below total escrowed",
+            AuctionNotEnded => "Auction has not ended yet",
+            AuctionNotSettled => "Auction has not been settled yet",
+            NoTie => "The top of the leaderboard is not tied",
+            InvalidRandomness => "Randomness account is invalid or was not committed for this tie",
+            RandomnessNotRevealed => "Randomness has not been revealed yet",
+            RaffleModeDisabled => "Raffle mode is not enabled",
+            RaffleAlreadyDrawn => "Raffle winner has already been drawn",
+            RaffleEntrantsMismatch => {
+                "Raffle entrants must be every active bid, sorted by account key"
+            }
+            RaffleNotDrawn => "Raffle winner has not been drawn yet",
+            InvalidVaa => "Invalid or unexpected Wormhole VAA",
+            UnknownEmitter => "VAA was not sent by the registered emitter",
+            OnlyRelayer => "Only the relayer can perform this action",
+            InvalidRefundRoot => {
+                "Refund root must be non-zero and cover between 1 and 80000 leaves"
+            }
+            RefundRootNotPosted => "No refunds Merkle root has been posted",
+            MerkleRefundsActive => "Refunds are paid through Merkle claims for this round",
+            InvalidMerkleProof => "Merkle proof does not match the refunds root",
+            RefundAlreadyClaimed => "Refund has already been claimed",
+            RefundExceedsEscrow => "Refund exceeds the bid's escrow",
+            InvalidContentHash => "Content hash must be non-zero",
+            ContentAlreadyCommitted => "Content has already been committed for this round",
+            ContentNotCommitted => "No content has been committed for this round",
+            ContentHashMismatch => "URI does not hash to the committed content hash",
+            ContentUriTooLong => "Content URI is too long",
+            InvalidPrizeAmount => "Prize amount must be non-zero",
+            InvalidPrizeMint => "Prize mint must differ from the bid mint",
+            PrizeAlreadyDeposited => "A prize is already escrowed",
+            PrizeLocked => "The prize can only move while there are no active bids",
+            NoPrize => "No prize is escrowed",
+            PrizeAccountsRequired => {
+                "Prize vault, mint, winner token account and token program are required"
+            }
+            PrizeRequiresSingleWinner => "An escrowed prize can only go to a single winner",
+            InvalidVestingDuration => "Vesting duration must not be negative",
+            VestingInProgress => "Settled funds are still vesting",
+            NothingVested => "No settled funds have vested yet",
+            InvalidPaymentWindow => "Payment window must be positive when deposits are enabled",
+            BalanceDue => "Bid has a balance due; complete payment first",
+            NoBalanceDue => "Bid has no balance due",
+            PaymentWindowClosed => "Payment window has closed",
+            PaymentWindowOpen => "Payment window is still open",
+            MintNotAccepted => "Mint is not accepted by this auction",
+            MintAlreadyAccepted => "Mint is already accepted",
+            AcceptedMintsFull => "Accepted mint list is full",
+            AcceptedMintInUse => "Bids are still escrowed in this mint",
+            AuctionMintOnly => "Only available for bids in the auction mint",
+            UnsafeMint => {
+                "Mint has a risky Token-2022 extension; set its override flag to accept it"
+            }
+            NonTransferableMint => "Non-transferable mints cannot back an auction",
+            OnlySettler => "Only the agent or settler can call this",
+            InvalidAbandonmentWindow => "Abandonment window must not be negative",
+            AuctionAbandoned => "Auction was abandoned",
+            NotAbandonable => "Auction cannot be marked abandoned yet",
+            CuratorAccountRequired => "Curator token account required for this settlement",
+            SelfReferral => "Bidders cannot refer themselves",
+            ReferrerAccountRequired => "Referrer token account required for this settlement",
+            ForeignBid => "Cross-chain bids are managed by the relayer",
+            ReceiptAccountsRequired => "Receipt mint and token account are required",
+            OnlyModerator => "Only the moderator can call this",
+            InvalidBidTtl => "Bid time-to-live must not be negative",
+            BidExpired => "Bid has expired",
+            BidNotExpired => "Bid has not expired",
+            InvalidStatusTransition => "Auction status does not allow this",
+            AuctionCancelled => "Auction was cancelled",
+            RivalBidsMismatch => {
+                "Rival bids must list every other active bid once, sorted by address"
+            }
+            WinnerNotHighest => "A rival bid is higher than the claimed winner",
+            EnglishModeLocked => "English mode can only change while at most one bid is active",
+            LeaderAccountRequired => "Displaced leader's token account is required in English mode",
+            InvalidCandleWindow => "Candle window must not be negative",
+            CandleModeDisabled => "Candle mode is not enabled",
+            CandleAlreadyDetermined => "Candle end has already been determined",
+            CandleNotDetermined => "Candle end has not been determined yet",
+            CandleEntrantsMismatch => {
+                "Candle entrants must be every active bid, sorted by account key"
+            }
+            BidAfterCandle => "Bid was placed or updated after the candle went out",
+            BidsNonRefundable => "All-pay mode: bids are not refundable",
+            AllPayLocked => {
+                "All-pay mode can only change with no active bids, and not alongside English mode"
+            }
+            AllPayDisabled => "All-pay mode is not enabled",
+            WrongAuction => "Bid belongs to a different auction",
+            InvalidAuctionId => "Auction id zero is reserved for the auction set up by initialize",
+            NotInRegistry => "Auction is not listed on this registry page",
+            AuctionClosed => "Auction has been closed",
+            BidsOutstanding => "Auction can only close once every bid is paid out or refunded",
+            BidAlreadyPlaced => {
+                "Bidder already has a bid in this auction; use upsert_bid or increase_bid"
+            }
+            LotSettled => "Lot has already been settled",
+            LotWinnerRequired => "The lot's leading bid and its bidder are required to sell it",
+            LotLeaderLocked => "The lot's leading bid stays escrowed until the lot settles",
+            InvalidBundle => {
+                "A bundle covers 2 to 8 distinct lots, listed in ascending order with their accounts"
+            }
+            BundlePending => "Lot is covered by a bundle bid that has not been settled",
+            AuctionNotStarted => "Auction has not started yet",
+            StandingBid => {
+                "Standing bids carry into the next round; only their bidder can withdraw them"
+            }
+            InvalidTrailingRounds => "The dynamic minimum averages 1 to 8 trailing rounds",
+            AuditLogRequired => "This auction keeps an audit log; pass it to record the change",
+            EscrowNotEmpty => "Escrow must hold no bids or surplus to move to a different mint",
+            MintNotRescuable => "The auction, accepted and escrowed prize mints cannot be rescued",
+            BidRanked => "Bid is in the ranked list; unlink it first",
+            BidNotRanked => "Bid is not in the ranked list",
+            RankHintMismatch => "The given neighbours are not adjacent in the ranked list",
+            RankOrderViolated => "Bid does not belong between the given neighbours",
+            LeaderNotRankedFirst => "The leader's bid must head the ranked list",
+            TopKSettlementPending => "A ranked settlement is under way",
+            TopKUniformPrice => "Uniform-price rounds settle through settle_multi",
+            RankedListEmpty => "No ranked bids to settle",
+            BidPairsMismatch => "Expected (bid, bidder) account pairs",
+            BidderDenylisted => "Bidder is denylisted",
+            BidderNotDenylisted => "Bidder is not denylisted",
+            DenylistRequired => "The auction has a denylist; pass it",
+            DenylistFull => "Denylist is full",
+            OnlyArbiter => "Only the arbiter can do this",
+            ArbiterRequired => "A dispute window needs an arbiter",
+            NoHeldSettlement => "No settlement is held",
+            SettlementAlreadyHeld => "The previous settlement is still held",
+            DisputeWindowOpen => "The dispute window is still open",
+            DisputeWindowClosed => "The dispute window has closed",
+            DisputeRequiresSingleWinner => "Held settlements need a single winner",
+            InvalidDisputeWindow => "Dispute window must not be negative",
+            InvalidClaimWindow => "Claim window must not be negative",
+            NoClaimWindow => "The auction has no claim window",
+            ClaimWindowOpen => "The claim window is still open",
+            ClaimWindowClosed => "The claim window has closed",
+            WinClaimed => "The win has already been claimed",
+            WinNotClaimed => "The winner has not claimed the win",
+            ClaimRequiresSingleWinner => "Claimed wins need a single winner",
+            SettlementTooSoon => "The winning bid changed too recently to settle",
+            MemoTooLong => "Memo is too long",
+            MemoProgramRequired => "The auction has a memo; pass the memo program",
+            NoPoints => "The bid has no points to claim",
+            InvalidFeeTiers => "Invalid fee discount tiers",
+            TieLostOnPriority => "A rival tied with the winner and holds priority by bidding first",
+            BidderSignatureRequired => "Only the bidder can make this change",
+            InvalidSessionKey => "Signer is not the bid's session key",
+            SessionKeyExpired => "The session key has expired",
+            SessionSpendCapExceeded => "The raise exceeds what the session key may still spend",
+            SealedBidsLocked => {
+                "Sealed bids can only be switched with no active bids, and not in English mode"
+            }
+            SealedBidsOnly => "The auction takes sealed bids only",
+            SealedBidsDisabled => "The auction does not take sealed bids",
+            BidSealed => "The bid is sealed until revealed",
+            BidNotSealed => "The bid is not sealed",
+            NotRevealing => {
+                "Sealed bids are revealed between the deadline and the end of the reveal window"
+            }
+            RevealWindowOpen => "Sealed bids are still being revealed",
+            CommitmentMismatch => "The revealed amount does not match the commitment",
+            InvalidRevealWindow => "Reveal window must not be negative",
+            InvalidThread => "Not this round's settlement thread or the thread program",
+            SettlementNotSchedulable => "Settlement needs accounts only a keeper can supply",
+            PointsUnclaimed => "Claim the bid's points before closing it",
+            TieChanged => "The tied bids changed since the tie break was requested",
+            AuctionPaused => "Bidding is paused",
+            BidReceipted => "Bids holding a receipt token cannot be transferred",
+        }
+    }
+}
+
+impl core::fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.message())
+    }
+}
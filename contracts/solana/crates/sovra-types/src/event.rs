@@ -0,0 +1,782 @@
+//! Events the auction program emits through `emit!`.
+//!
+//! Each is logged as `Program data: ` followed by the base64 of its
+//! [`Discriminator`] and borsh body; see [`crate::decode_event`].
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::state::AuctionStatus;
+use crate::{Discriminator, Pubkey};
+
+macro_rules! discriminators {
+    ($($name:ident = $bytes:expr,)*) => {
+        $(
+            impl Discriminator for $name {
+                const DISCRIMINATOR: [u8; 8] = $bytes;
+            }
+        )*
+    };
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidPlaced {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// `Bid::bid_index`; zero for a wallet's first bid.
+    pub bid_index: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidUpdated {
+    pub bidder: Pubkey,
+    pub new_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidIncreased {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+    pub max_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ModeratorChanged {
+    pub moderator: Pubkey,
+    pub bid_bond: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidSlashed {
+    pub bidder: Pubkey,
+    pub moderator: Pubkey,
+    /// Escrow returned to the bidder.
+    pub refunded: u64,
+    /// Lamports of bond sent to the treasury owner.
+    pub bond: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidExpired {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub updated_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidDecreased {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+    pub max_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidWithdrawn {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidSettled {
+    pub winner: Pubkey,
+    pub amount: u64,
+    /// Portion of `amount` routed to the charity account.
+    pub charity_amount: u64,
+    /// Portion of `amount` paid to `curator`; zero when no curator is set.
+    pub curator: Pubkey,
+    pub curator_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PenaltiesSwept {
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TreasuryChanged {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SurplusSwept {
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidRefunded {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TieBreakRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+    pub tied_count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TieResolved {
+    pub randomness_account: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub tied_count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RaffleDrawRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RaffleDrawn {
+    pub randomness_account: Pubkey,
+    pub winner: Pubkey,
+    pub winner_weight: u64,
+    pub total_weight: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrossChainBidReceived {
+    pub bidder: Pubkey,
+    pub foreign_chain: u16,
+    pub sequence: u64,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrossChainRefund {
+    pub bidder: Pubkey,
+    pub foreign_chain: u16,
+    pub sequence: u64,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefundRootPosted {
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefundClaimed {
+    pub bidder: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContentCommitted {
+    pub content_hash: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContentRevealed {
+    pub content_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrizeDeposited {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrizeDelivered {
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrizeWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettledFundsStreamed {
+    pub amount: u64,
+    /// Previously vested funds paid out before the stream restarted.
+    pub released: u64,
+    pub vesting_total: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettledFundsClaimed {
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PaymentCompleted {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// Superseded by `WinnerDefaulted`; kept so older logs still decode.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DepositForfeited {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Bidder now on top of the leaderboard; default when there is none.
+    pub next_bidder: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AcceptedMintAdded {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub treasury: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AcceptedMintRemoved {
+    pub mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WithheldFeesHarvested {
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Whether the fees went on to the treasury rather than staying in the mint.
+    pub withdrawn: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettlerChanged {
+    pub old_settler: Pubkey,
+    pub new_settler: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionAbandoned {
+    pub end_time: i64,
+    pub active_bid_count: u64,
+    pub marked_by: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CuratorChanged {
+    pub curator: Pubkey,
+    pub curator_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralPaid {
+    pub referrer: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub max_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StatusChanged {
+    pub from: AuctionStatus,
+    pub to: AuctionStatus,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionCancelled {
+    pub active_bid_count: u64,
+    pub total_escrowed: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Outbid {
+    pub previous_leader: Pubkey,
+    pub previous_amount: u64,
+    pub new_leader: Pubkey,
+    pub new_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandleEndRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandleEndDetermined {
+    pub randomness_account: Pubkey,
+    pub candle_end: i64,
+    /// Default when no bid was in before the candle went out.
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// All-pay mode was switched. While on, no bid is refunded: losing bids go
+/// to the treasury once the round settles.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllPayChanged {
+    pub enabled: bool,
+}
+
+/// A losing all-pay bid's escrow went to the treasury.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllPayBidCollected {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// The operator's agent opened a new auction with `create_auction`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionCreated {
+    pub auction_id: u64,
+    pub auction_state: Pubkey,
+    pub agent: Pubkey,
+    pub usdc_mint: Pubkey,
+}
+
+/// `close_auction` wound the auction up; figures cover its last round.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionClosed {
+    pub total_bids: u64,
+    /// Bids entered this round, from `AuctionState::round_bidder_count`; a
+    /// wallet counts once per bid it entered, not once overall.
+    pub bidder_entries: u64,
+    pub winning_amount: u64,
+    /// Seconds from the start of the round to the close.
+    pub duration: i64,
+}
+
+/// The agent added a lot to the round with `create_lot`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotCreated {
+    pub lot_id: u16,
+    pub minimum_bid: u64,
+    pub reserve_price: u64,
+    pub item_hash: [u8; 32],
+}
+
+/// A lot bid was placed or raised to `amount`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotBidPlaced {
+    pub lot_id: u16,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Whether the bid now leads its lot.
+    pub leading: bool,
+}
+
+/// A lot bid was withdrawn and its escrow refunded.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotBidWithdrawn {
+    pub lot_id: u16,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// `settle_lot` sold a lot to `winner` for `amount`, or closed it unsold
+/// with a default winner and zero amount.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotSettled {
+    pub lot_id: u16,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// A bundle bid offered `amount` for `lots` together.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BundleBidPlaced {
+    pub bidder: Pubkey,
+    pub lots: Vec<u16>,
+    pub amount: u64,
+}
+
+/// `settle_bundle` sold a bundle's lots to its bidder, or refunded it when
+/// it did not beat the lots' leading bids.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BundleSettled {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub won: bool,
+}
+
+/// Bidding on an auction opens at `start_time`; emitted when it is created
+/// and whenever the agent moves the start.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionScheduled {
+    pub auction_id: u64,
+    pub start_time: i64,
+}
+
+/// A bidder opted their bid in or out of carrying into later rounds.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StandingChanged {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub standing: bool,
+}
+
+/// A new round's floor was reset from recent clearing prices.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MinimumBidRecalculated {
+    pub auction_id: u64,
+    pub trailing_average: u64,
+    pub minimum_bid: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AgentChanged {
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+}
+
+/// Emitted by `set_minimum_bid` and by `update_config` when it sets one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MinimumBidChanged {
+    pub old_minimum_bid: u64,
+    pub new_minimum_bid: u64,
+}
+
+/// The auction's escrow was replaced, moving `amount` into `new_escrow`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EscrowMigrated {
+    pub auction_id: u64,
+    pub old_escrow: Pubkey,
+    pub new_escrow: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Stray tokens were moved out of an account the auction controls.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokensRescued {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// A bid joined the ranked list between `prev_bid` and `next_bid`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidLinked {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+    pub prev_bid: Pubkey,
+    pub next_bid: Pubkey,
+}
+
+/// A bid left the ranked list.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidUnlinked {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+}
+
+/// `settle_top_k` paid the `rank`-th highest ranked bid, counting from one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TopKWinnerSettled {
+    pub auction_id: u64,
+    pub rank: u8,
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub price: u64,
+}
+
+/// `gc_bid` closed an inactive bid, returning `lamports` to its bidder.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidAccountClosed {
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub lamports: u64,
+}
+
+/// `wallet` was added to or removed from the auction's denylist.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DenylistChanged {
+    pub wallet: Pubkey,
+    pub denied: bool,
+}
+
+/// The agent refunded a denylisted bidder's bid in full and closed it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidForceRefunded {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// The agent set who rules on held settlements and how long they are held.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputeTermsChanged {
+    pub arbiter: Pubkey,
+    pub dispute_window: i64,
+}
+
+/// A settled price stays in escrow until `held_until`, open to dispute.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettlementHeld {
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub held_until: i64,
+}
+
+/// The winner disputed the held settlement; only the arbiter can resolve it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputeOpened {
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// A held settlement was resolved: paid out, or refunded to the winner.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettlementResolved {
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+}
+
+/// The top bidder confirmed their win within the claim window.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WinClaimed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// The top bidder failed to pay or claim in time: `slashed` of their escrow
+/// went to the treasury, `refunded` back to them, and `next_bidder` (default
+/// when there is none) is now on top.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WinnerDefaulted {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub slashed: u64,
+    pub refunded: u64,
+    pub next_bidder: Pubkey,
+}
+
+/// Loyalty points a bid earned were minted to its bidder.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PointsClaimed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// A refunded losing bid was paid its share of the round's rebate pool.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RebatePaid {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// The winner's fee tier gave `amount` of the treasury's cut back to them.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeDiscounted {
+    pub winner: Pubkey,
+    pub discount_bps: u16,
+    pub amount: u64,
+}
+
+/// The bidder let `session_key` raise the bid by up to `spend_cap` until
+/// `expires_at`; a default key revokes it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionKeySet {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub spend_cap: u64,
+}
+
+/// A sealed bid was opened at `amount`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidRevealed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// Forfeited commit bonds went to the treasury.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommitBondsSwept {
+    pub amount: u64,
+}
+
+/// Emitted by `set_raffle_mode` and by `update_config` when it sets it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RaffleModeChanged {
+    pub old_raffle_mode: bool,
+    pub new_raffle_mode: bool,
+}
+
+/// Emitted by `set_uniform_price` and by `update_config` when it sets it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UniformPriceChanged {
+    pub old_uniform_price: bool,
+    pub new_uniform_price: bool,
+}
+
+/// Emitted by `set_buy_now_price` and by `update_config` when it sets one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuyNowPriceChanged {
+    pub old_buy_now_price: u64,
+    pub new_buy_now_price: u64,
+}
+
+/// Emitted by `set_candle_window` and by `update_config` when it sets one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandleWindowChanged {
+    pub old_candle_window: i64,
+    pub new_candle_window: i64,
+}
+
+/// Emitted by `set_bid_ttl` and by `update_config` when it sets one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BidTtlChanged {
+    pub old_bid_ttl: i64,
+    pub new_bid_ttl: i64,
+}
+
+/// Emitted by `set_transfer_hooks` and by `update_config` when it sets it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransferHooksChanged {
+    pub old_transfer_hooks: bool,
+    pub new_transfer_hooks: bool,
+}
+
+/// Emitted by `set_english_auction` and by `update_config` when it sets it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EnglishAuctionChanged {
+    pub old_english_auction: bool,
+    pub new_english_auction: bool,
+}
+
+/// Emitted by `set_num_winners` and by `update_config` when it sets it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NumWinnersChanged {
+    pub old_num_winners: u8,
+    pub new_num_winners: u8,
+}
+
+/// Emitted by `set_vesting_duration` and by `update_config` when it sets one.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VestingDurationChanged {
+    pub old_vesting_duration: i64,
+    pub new_vesting_duration: i64,
+}
+
+/// The agent paused bidding; see `set_paused`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Paused {
+    pub auction_id: u64,
+}
+
+/// The agent resumed bidding.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Unpaused {
+    pub auction_id: u64,
+}
+
+discriminators! {
+    BidPlaced = [135, 53, 176, 83, 193, 69, 108, 61],
+    BidUpdated = [70, 153, 25, 253, 224, 94, 198, 148],
+    BidIncreased = [184, 180, 215, 94, 126, 126, 49, 102],
+    ModeratorChanged = [197, 28, 98, 253, 159, 54, 108, 168],
+    BidSlashed = [151, 115, 136, 75, 32, 56, 99, 5],
+    BidExpired = [116, 54, 204, 245, 160, 244, 77, 97],
+    BidDecreased = [123, 201, 62, 162, 221, 194, 46, 233],
+    BidWithdrawn = [145, 195, 97, 230, 166, 54, 74, 206],
+    BidSettled = [234, 32, 141, 114, 0, 102, 0, 139],
+    PenaltiesSwept = [119, 215, 249, 169, 5, 162, 125, 1],
+    TreasuryChanged = [252, 2, 55, 56, 106, 205, 227, 47],
+    SurplusSwept = [10, 228, 130, 83, 221, 240, 210, 32],
+    BidRefunded = [197, 100, 31, 186, 67, 28, 46, 103],
+    TieBreakRequested = [196, 170, 178, 141, 140, 145, 168, 60],
+    TieResolved = [199, 157, 148, 87, 4, 237, 234, 89],
+    RaffleDrawRequested = [131, 146, 161, 19, 141, 12, 1, 217],
+    RaffleDrawn = [17, 181, 213, 34, 171, 64, 191, 170],
+    CrossChainBidReceived = [146, 45, 82, 57, 160, 155, 97, 147],
+    CrossChainRefund = [239, 191, 174, 18, 192, 219, 72, 31],
+    RefundRootPosted = [202, 50, 143, 150, 77, 230, 185, 186],
+    RefundClaimed = [136, 64, 242, 99, 4, 244, 208, 130],
+    ContentCommitted = [95, 72, 40, 165, 0, 246, 138, 36],
+    ContentRevealed = [10, 102, 252, 222, 180, 225, 172, 7],
+    PrizeDeposited = [162, 100, 89, 151, 104, 179, 227, 105],
+    PrizeDelivered = [89, 164, 100, 134, 202, 76, 141, 143],
+    PrizeWithdrawn = [168, 111, 175, 222, 127, 220, 247, 155],
+    SettledFundsStreamed = [211, 227, 171, 224, 34, 82, 252, 238],
+    SettledFundsClaimed = [205, 196, 169, 193, 188, 61, 97, 226],
+    PaymentCompleted = [157, 184, 146, 198, 243, 50, 113, 174],
+    DepositForfeited = [165, 9, 162, 33, 55, 90, 73, 131],
+    AcceptedMintAdded = [186, 69, 25, 108, 217, 85, 27, 8],
+    AcceptedMintRemoved = [178, 1, 210, 96, 61, 190, 27, 235],
+    WithheldFeesHarvested = [73, 135, 172, 158, 159, 108, 23, 225],
+    SettlerChanged = [33, 54, 1, 21, 216, 157, 170, 61],
+    AuctionAbandoned = [117, 150, 179, 181, 246, 16, 83, 226],
+    CuratorChanged = [87, 59, 49, 50, 194, 244, 0, 178],
+    ReferralPaid = [70, 190, 133, 42, 145, 213, 87, 197],
+    BidTransferred = [247, 135, 2, 109, 37, 42, 131, 39],
+    StatusChanged = [146, 235, 222, 125, 145, 246, 34, 240],
+    AuctionCancelled = [22, 32, 51, 83, 215, 194, 171, 209],
+    Outbid = [205, 106, 230, 20, 225, 5, 68, 12],
+    CandleEndRequested = [190, 68, 156, 132, 54, 220, 126, 192],
+    CandleEndDetermined = [73, 22, 102, 234, 220, 48, 222, 43],
+    AllPayChanged = [105, 8, 132, 24, 95, 75, 75, 78],
+    AllPayBidCollected = [166, 41, 160, 240, 37, 109, 21, 103],
+    AuctionCreated = [133, 190, 194, 65, 172, 0, 70, 178],
+    AuctionClosed = [104, 72, 168, 177, 241, 79, 231, 167],
+    LotCreated = [52, 9, 70, 235, 197, 238, 10, 181],
+    LotBidPlaced = [46, 63, 25, 228, 32, 175, 224, 65],
+    LotBidWithdrawn = [115, 122, 62, 247, 194, 52, 190, 141],
+    LotSettled = [87, 217, 89, 245, 32, 86, 22, 175],
+    BundleBidPlaced = [141, 208, 118, 163, 43, 63, 172, 246],
+    BundleSettled = [186, 95, 253, 117, 99, 129, 155, 245],
+    AuctionScheduled = [33, 21, 133, 180, 6, 87, 242, 101],
+    StandingChanged = [124, 128, 32, 82, 138, 191, 44, 72],
+    MinimumBidRecalculated = [139, 104, 38, 24, 21, 121, 75, 0],
+    AgentChanged = [248, 6, 169, 87, 197, 255, 16, 191],
+    MinimumBidChanged = [209, 250, 152, 80, 193, 141, 11, 159],
+    EscrowMigrated = [169, 120, 241, 205, 106, 10, 45, 112],
+    TokensRescued = [182, 128, 176, 193, 145, 174, 60, 228],
+    BidLinked = [227, 80, 102, 114, 18, 164, 234, 22],
+    BidUnlinked = [32, 14, 178, 50, 64, 168, 239, 192],
+    TopKWinnerSettled = [86, 13, 249, 128, 121, 182, 24, 84],
+    BidAccountClosed = [26, 214, 231, 174, 165, 122, 102, 74],
+    DenylistChanged = [102, 92, 150, 25, 158, 128, 141, 210],
+    BidForceRefunded = [68, 218, 51, 26, 225, 61, 220, 180],
+    DisputeTermsChanged = [237, 15, 95, 215, 173, 182, 247, 18],
+    SettlementHeld = [242, 198, 138, 197, 38, 172, 13, 150],
+    DisputeOpened = [239, 222, 102, 235, 193, 85, 1, 214],
+    SettlementResolved = [104, 253, 80, 157, 29, 53, 22, 241],
+    WinClaimed = [222, 254, 147, 204, 233, 195, 37, 131],
+    WinnerDefaulted = [220, 215, 204, 200, 54, 195, 207, 41],
+    PointsClaimed = [159, 251, 55, 234, 170, 58, 220, 34],
+    RebatePaid = [240, 119, 234, 76, 110, 156, 152, 192],
+    FeeDiscounted = [69, 48, 160, 22, 206, 235, 231, 222],
+    SessionKeySet = [94, 76, 244, 82, 192, 95, 235, 199],
+    BidRevealed = [227, 144, 125, 229, 28, 109, 18, 209],
+    CommitBondsSwept = [189, 151, 147, 118, 135, 5, 192, 101],
+    RaffleModeChanged = [252, 237, 104, 130, 173, 148, 252, 152],
+    UniformPriceChanged = [247, 250, 31, 3, 183, 218, 82, 169],
+    BuyNowPriceChanged = [91, 124, 19, 129, 42, 90, 138, 169],
+    CandleWindowChanged = [99, 134, 6, 120, 159, 22, 72, 234],
+    BidTtlChanged = [2, 195, 94, 233, 40, 217, 219, 169],
+    TransferHooksChanged = [41, 233, 17, 6, 216, 129, 228, 213],
+    EnglishAuctionChanged = [192, 184, 134, 204, 134, 71, 105, 206],
+    NumWinnersChanged = [7, 54, 252, 44, 189, 159, 2, 91],
+    VestingDurationChanged = [208, 255, 194, 130, 55, 128, 149, 169],
+    Paused = [172, 248, 5, 253, 49, 255, 255, 232],
+    Unpaused = [156, 150, 47, 174, 120, 216, 93, 117],
+}
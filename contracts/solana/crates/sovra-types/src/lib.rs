@@ -0,0 +1,52 @@
+//! Borsh layouts of the cartoonist auction program's accounts, events and
+//! errors, for indexers and other programs that want to read them without
+//! pulling in Anchor or `solana-program`.
+//!
+//! Addresses are plain `[u8; 32]`; convert with `Pubkey::new_from_array`
+//! and `Pubkey::to_bytes`. The types mirror the program's field for field
+//! and must change with it.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod error;
+pub mod event;
+pub mod seeds;
+pub mod state;
+
+use borsh::io::{Error, ErrorKind, Result};
+use borsh::BorshDeserialize;
+
+pub use error::AuctionError;
+pub use state::{AuctionState, AuctionStatus, Bid, Leaderboard, LeaderboardEntry};
+
+pub type Pubkey = [u8; 32];
+
+/// `2UDUA7vCqZ87c4kCXbshF7S5uuxMXJvykwn9LJ1JnMU2`.
+pub const PROGRAM_ID: Pubkey = [
+    21, 212, 119, 117, 214, 131, 40, 72, 35, 179, 175, 50, 215, 255, 239, 85, 151, 193, 78, 148,
+    41, 88, 145, 31, 35, 11, 5, 28, 143, 75, 95, 199,
+];
+
+/// The 8-byte prefix Anchor puts before an account's or event's borsh body.
+pub trait Discriminator {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+/// Decodes account data, checking its discriminator. Trailing bytes, left by
+/// accounts sized for later fields, are ignored.
+pub fn decode_account<T: BorshDeserialize + Discriminator>(data: &[u8]) -> Result<T> {
+    let body = data
+        .strip_prefix(&T::DISCRIMINATOR)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "discriminator mismatch"))?;
+    T::deserialize(&mut &body[..])
+}
+
+/// Decodes an event from the base64-decoded `Program data:` log payload.
+pub fn decode_event<T: BorshDeserialize + Discriminator>(data: &[u8]) -> Result<T> {
+    let body = data
+        .strip_prefix(&T::DISCRIMINATOR)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "discriminator mismatch"))?;
+    T::try_from_slice(body)
+}
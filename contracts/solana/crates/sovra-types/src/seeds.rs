@@ -0,0 +1,54 @@
+//! PDA seed prefixes. Per-auction accounts follow the prefix with
+//! [`auction_seed`]; deriving the address itself is left to the caller's
+//! Solana SDK.
+
+use alloc::vec::Vec;
+
+pub const AUCTION_STATE: &[u8] = b"auction_state";
+/// `[ESCROW, auction_seed]`, or `[ESCROW, auction_seed, mint]` for an
+/// accepted mint's escrow.
+pub const ESCROW: &[u8] = b"escrow";
+/// `[BID, auction_seed, bidder, bid_index_seed]`.
+pub const BID: &[u8] = b"bid";
+pub const OPERATOR: &[u8] = b"operator";
+/// `[REGISTRY, page index as u32 LE]`.
+pub const REGISTRY: &[u8] = b"registry";
+/// `[ROUND_RESULT, auction_seed, round as u64 LE]`, rounds counting from one.
+pub const ROUND_RESULT: &[u8] = b"round_result";
+pub const WIN_RECEIPT: &[u8] = b"win_receipt";
+pub const RECEIPT_MINT: &[u8] = b"receipt_mint";
+pub const POINTS_MINT: &[u8] = b"points_mint";
+pub const PRICE_FEED: &[u8] = b"price_feed";
+pub const AUDIT_LOG: &[u8] = b"audit_log";
+pub const DENYLIST: &[u8] = b"denylist";
+pub const CONFIG: &[u8] = b"config";
+pub const ACCEPTED_MINTS: &[u8] = b"accepted_mints";
+/// `[LOT, auction_seed, lot_id as u16 LE]`.
+pub const LOT: &[u8] = b"lot";
+pub const LOT_BID: &[u8] = b"lot_bid";
+pub const BUNDLE_BID: &[u8] = b"bundle_bid";
+pub const PRIZE_VAULT: &[u8] = b"prize_vault";
+pub const CONTENT: &[u8] = b"content";
+pub const REFUND_CLAIMS: &[u8] = b"refund_claims";
+/// `[RECEIVED, auction_seed, emitter chain as u16 LE, emitter, sequence as u64 LE]`.
+pub const RECEIVED: &[u8] = b"received";
+
+/// Seed separating one auction's PDAs from another's: the id's little-endian
+/// bytes, or nothing for auction zero, which predates the factory.
+pub fn auction_seed(auction_id: u64) -> Vec<u8> {
+    if auction_id == 0 {
+        Vec::new()
+    } else {
+        auction_id.to_le_bytes().to_vec()
+    }
+}
+
+/// Last seed of a bid PDA: the index's little-endian bytes, or nothing for
+/// a wallet's first bid.
+pub fn bid_index_seed(bid_index: u16) -> Vec<u8> {
+    if bid_index == 0 {
+        Vec::new()
+    } else {
+        bid_index.to_le_bytes().to_vec()
+    }
+}
@@ -0,0 +1,369 @@
+//! Account layouts of the auction program.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{Discriminator, Pubkey};
+
+/// Highest bids `settle_multi` can pay out.
+pub const MAX_WINNERS: usize = 8;
+/// Settled rounds remembered for the dynamic minimum bid.
+pub const MAX_TRAILING_ROUNDS: usize = 8;
+pub const MAX_MEMO_LEN: usize = 64;
+
+/// Lifecycle of a round. `Closing` is recorded lazily: the first instruction
+/// to touch the state after the deadline moves an open round into it, so
+/// clients should use `AuctionState::status_at` for the current value.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AuctionStatus {
+    /// Initialized, with no bid placed yet.
+    #[default]
+    Created,
+    /// Taking bids.
+    Open,
+    /// Past the deadline and awaiting settlement.
+    Closing,
+    /// Paid out. `set_end_time` reopens it for the next round.
+    Settled,
+    /// Called off by the agent; bidders withdraw in full. Only
+    /// `close_auction` follows.
+    Cancelled,
+    /// Left unsettled past the abandonment window; bidders withdraw in full.
+    /// Only `close_auction` follows.
+    Abandoned,
+    /// Wound up by `close_auction` once every bid was paid out or refunded.
+    /// Terminal.
+    Closed,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// `Bid::bid_index` of the ranked bid.
+    pub bid_index: u16,
+    /// `Bid::updated_at` of the ranked bid, which breaks ties.
+    pub updated_at: i64,
+}
+
+/// Top bids, highest first, in `LeaderboardEntry::outranks` order.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub entries: [LeaderboardEntry; MAX_WINNERS],
+    pub len: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionState {
+    pub agent: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub escrow_bump: u8,
+    pub minimum_bid: u64,
+    pub active_bid_count: u64,
+    pub bump: u8,
+    /// Bidding deadline (unix seconds). Zero means the auction is open-ended.
+    pub end_time: i64,
+    pub withdrawal_penalty_bps: u16,
+    /// Penalties retained in escrow that have not yet been swept to the treasury.
+    pub accrued_penalties: u64,
+    /// Minimum step a proxy bid escalates by when outbid.
+    pub bid_increment: u64,
+    /// Highest bidder seen by leader tracking; default when there is no leader.
+    pub leader: Pubkey,
+    pub leading_amount: u64,
+    /// Price at which a bidder may settle instantly via `buy_now`. Zero disables it.
+    pub buy_now_price: u64,
+    /// Where the round is in its lifecycle; only changed through `transition`.
+    pub status: AuctionStatus,
+    /// Number of top bids paid out by `settle_multi`.
+    pub num_winners: u8,
+    pub leaderboard: Leaderboard,
+    /// When set, every `settle_multi` winner pays the lowest winning amount.
+    pub uniform_price: bool,
+    /// Token account receiving `charity_bps` of every settlement; default when unset.
+    pub charity: Pubkey,
+    pub charity_bps: u16,
+    /// Tokens the escrow owes: active bid escrow plus unswept penalties.
+    pub total_escrowed: u64,
+    /// Switchboard randomness account awaiting reveal; default when none is pending.
+    pub pending_randomness: Pubkey,
+    pub randomness_seed_slot: u64,
+    /// Draw the winner with probability proportional to escrow instead of by amount.
+    pub raffle_mode: bool,
+    /// Set once `draw_raffle` has picked a winner; cleared by `set_end_time`.
+    pub raffle_drawn: bool,
+    /// Wormhole emitter (chain id and universal address) trusted to send cross-chain bids.
+    pub foreign_emitter_chain: u16,
+    pub foreign_emitter: [u8; 32],
+    /// Redeems bridged USDC into escrow and receives refunds to bridge back.
+    pub relayer: Pubkey,
+    /// Escrow PDA address, so hot paths can check it without re-deriving it.
+    pub escrow: Pubkey,
+    /// Root of the posted refunds Merkle tree; zero when refunds go through
+    /// `crank_refund`. Cleared by `set_end_time`.
+    pub refund_root: [u8; 32],
+    /// SHA-256 of the deliverable's URI, committed by the agent while bidding
+    /// is open; zero when nothing is committed. Cleared by `set_end_time`
+    /// once a closed round is reopened.
+    pub content_hash: [u8; 32],
+    /// Mint of the prize the agent escrowed with `deposit_prize`; default when
+    /// there is none.
+    pub prize_mint: Pubkey,
+    /// Prize units held in the prize vault, swapped to the winner on settlement.
+    pub prize_amount: u64,
+    /// Seconds over which settled funds stream to the treasury; zero pays
+    /// them out as a lump sum.
+    pub vesting_duration: i64,
+    /// Current stream: `vesting_total` vests linearly from `vesting_start`,
+    /// and `claim_settled_funds` has released `vesting_claimed` of it.
+    pub vesting_start: i64,
+    pub vesting_total: u64,
+    pub vesting_claimed: u64,
+    /// Share of each bid escrowed up front; zero escrows bids in full. The
+    /// winner owes the rest within `payment_window` seconds of the deadline.
+    pub deposit_bps: u16,
+    pub payment_window: i64,
+    /// Deadline for the current top bidder to complete payment, once a
+    /// forfeit has passed the slot on; zero means `end_time + payment_window`.
+    pub payment_deadline: i64,
+    /// Forward `remaining_accounts` to transfer hooks on every token transfer.
+    pub transfer_hooks: bool,
+    /// Key allowed to settle and post refunds alongside the agent, without
+    /// its other powers; default when none is delegated.
+    pub settler: Pubkey,
+    /// Seconds after `end_time` an unsettled auction may be marked abandoned;
+    /// zero disables the fallback.
+    pub abandonment_window: i64,
+    /// Guest curator paid `curator_bps` of each settlement to their associated
+    /// token account; default when the round has none.
+    pub curator: Pubkey,
+    pub curator_bps: u16,
+    /// Share of a winning price paid to the winning bid's referrer, out of
+    /// what would otherwise go to the treasury.
+    pub referral_bps: u16,
+    /// Key allowed to slash spam bids with `slash_bid`; default when bids
+    /// cannot be slashed.
+    pub moderator: Pubkey,
+    /// Lamports a new bid locks in its account on top of rent, returned when
+    /// the bid account closes unless the moderator slashes it first.
+    pub bid_bond: u64,
+    /// Seconds a bid may go without being placed or updated before it
+    /// expires; zero keeps bids live indefinitely.
+    pub bid_ttl: i64,
+    /// Ascending mode: only the leading bid stays escrowed, and a bid that
+    /// takes the lead refunds the one it displaces.
+    pub english_auction: bool,
+    /// Candle mode: the round really closed at a random moment within this
+    /// many seconds before `end_time`, drawn by `determine_candle_end`. Zero
+    /// turns the mode off.
+    pub candle_window: i64,
+    /// Effective end drawn for a candle round; zero until it is determined.
+    /// Cleared by `set_end_time`.
+    pub candle_end: i64,
+    /// All-pay mode. WARNING: bids are never refunded while the round runs;
+    /// after settlement every losing bid goes to the treasury as well, and
+    /// only the top bidder receives the prize. Meant for charity rounds.
+    pub all_pay: bool,
+    /// Id given by `create_auction`; zero for the auction set up by
+    /// `initialize`. Every per-auction PDA is seeded with [`auction_seed`].
+    ///
+    /// [`auction_seed`]: crate::seeds::auction_seed
+    pub auction_id: u64,
+    /// When the current round began: at creation, then whenever
+    /// `set_end_time` reopens a settled round.
+    pub round_started_at: i64,
+    /// Bids placed or raised this round.
+    pub round_bid_count: u64,
+    /// Bids entered this round: a wallet holding several bids, or one who
+    /// withdraws and bids again, counts once per entry.
+    pub round_bidder_count: u64,
+    /// Total price paid by this round's winners.
+    pub winning_amount: u64,
+    /// Prices paid by every winner across all rounds, counting accepted
+    /// mints 1:1 with the auction mint. Like the other lifetime totals below,
+    /// never reset.
+    pub total_volume: u128,
+    /// Bids placed or raised across all rounds.
+    pub total_bids_placed: u64,
+    /// Rounds that reached `Settled`.
+    pub total_rounds_settled: u64,
+    /// Highest amount any bid has stood at.
+    pub highest_ever_bid: u64,
+    /// `Bid::bid_index` of the leader's bid.
+    pub leader_index: u16,
+    /// Lots created under the auction; the next lot's id is one more.
+    pub lot_count: u16,
+    /// Lot and bundle bids holding escrow across all lots, which keep the
+    /// auction from closing just as active bids do.
+    pub active_lot_bid_count: u64,
+    /// Unix time bidding opens; bids before it fail with `AuctionNotStarted`.
+    /// Zero when the round opened at creation.
+    pub start_time: i64,
+    /// When non-zero, each new round's `minimum_bid` is this share of the
+    /// average winning amount over the last `dynamic_minimum_rounds` rounds.
+    pub dynamic_minimum_bps: u16,
+    pub dynamic_minimum_rounds: u8,
+    /// Winning amounts of the most recent settled rounds, newest first.
+    pub recent_winning_amounts: [u64; MAX_TRAILING_ROUNDS],
+    /// How many entries of `recent_winning_amounts` are filled.
+    pub recent_round_count: u8,
+    /// Set by `initialize_audit_log`; admin changes must then be logged.
+    pub audit_log_enabled: bool,
+    /// Times `migrate_escrow` has replaced the escrow; seeds the next one.
+    pub escrow_generation: u8,
+    /// Highest bid in this round's ranked list, kept by `link_bid`; default
+    /// while empty. The list is dropped when the round settles or is called
+    /// off.
+    pub ranked_head: Pubkey,
+    /// Bids in this round's ranked list.
+    pub ranked_count: u64,
+    /// Winners `settle_top_k` has paid so far this round. Nonzero while its
+    /// pages are under way, which freezes the ranked list and every other
+    /// way to settle.
+    pub top_k_paid: u8,
+    /// What those winners paid together.
+    pub top_k_total: u64,
+    /// The first of them, recorded as the round's winner.
+    pub top_k_first: Pubkey,
+    /// Set once `set_denylisted` creates the auction's `Denylist`; bid
+    /// paths must then pass it.
+    pub denylist_enabled: bool,
+    /// Seconds a settled price stays in escrow before it can be released;
+    /// zero pays settlements out immediately.
+    pub dispute_window: i64,
+    /// Rules on held settlements: releases them early or refunds the winner.
+    pub arbiter: Pubkey,
+    /// The settlement currently held for the dispute window, if
+    /// `held_amount` is non-zero: what the winner paid, who they were, their
+    /// bid's referrer, and when anyone may release it.
+    pub held_amount: u64,
+    pub held_winner: Pubkey,
+    pub held_referrer: Pubkey,
+    pub held_until: i64,
+    /// Set by the winner through `open_dispute`; only the arbiter can then
+    /// settle the held funds.
+    pub disputed: bool,
+    /// Seconds after the deadline the top bidder has to `claim_win`; zero
+    /// lets the round settle without a claim.
+    pub claim_window: i64,
+    /// Share of a defaulting winner's escrow that `forfeit_deposit` and
+    /// `promote_next_bidder` slash to the treasury.
+    pub default_penalty_bps: u16,
+    /// Claim deadline once `promote_next_bidder` has passed the slot on;
+    /// zero means `end_time + claim_window`.
+    pub claim_deadline: i64,
+    /// The bid that claimed this round's win, or default. Cleared by
+    /// `set_end_time`.
+    pub claimed_bid: Pubkey,
+    /// Slots that must pass after the winning bid's last change before it
+    /// can be settled, so a bid cannot move in the same slot it is settled.
+    pub min_settle_slots: u64,
+    /// UTF-8 memo logged with settlement and refund transfers; the first
+    /// `memo_len` bytes are used, and none means no memo.
+    pub memo: [u8; MAX_MEMO_LEN],
+    pub memo_len: u8,
+    /// Loyalty points a bid earns per whole token it keeps in escrow for an
+    /// hour, minted from the points mint; zero earns none.
+    pub points_rate: u64,
+    /// Share of the treasury's cut of each price set aside for the bids that
+    /// lost the round, paid with their refund by `crank_refund`.
+    pub rebate_bps: u16,
+    /// Rebates set aside and not yet paid, held in escrow. Whatever one
+    /// round's losers leave behind joins the next round's pool.
+    pub rebate_pool: u64,
+    /// The pool at the last settlement and the losing escrow it is shared
+    /// over; a refund of `x` earns `rebate_total * x / rebate_base`.
+    pub rebate_total: u64,
+    pub rebate_base: u64,
+    /// `Bid::fee_discount_bps` of the held winner, applied on release.
+    pub held_discount_bps: u16,
+    /// Seconds after `end_time` for revealing sealed bids, which the round
+    /// takes instead of open ones while this is set; see `place_sealed_bid`.
+    pub reveal_window: i64,
+    /// Tokens each sealed bid escrows on top of its deposit. `reveal_bid`
+    /// returns them; a bid that leaves unrevealed forfeits them.
+    pub commit_bond: u64,
+    /// Commit bonds in escrow, both those of sealed bids still waiting and
+    /// those forfeited; `sweep_commit_bonds` sends them to the treasury once
+    /// the reveal window has closed.
+    pub commit_bonds_held: u64,
+    /// Bids tied at the top when `request_tie_break` committed its draw,
+    /// which `resolve_tie` draws among; zero when no draw is pending.
+    pub tie_count: u8,
+    /// Set by `set_paused`: no bids are placed or raised until it clears.
+    pub paused: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bid {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub active: bool,
+    pub bump: u8,
+    /// Escrowed ceiling. Equals `amount` for plain bids; proxy bids escalate `amount` up to it.
+    pub max_amount: u64,
+    pub proxy: bool,
+    /// Wormhole chain id of a cross-chain bidder; zero for native Solana bids.
+    pub foreign_chain: u16,
+    /// Part of `max_amount` not escrowed yet, owed through `complete_payment`
+    /// when the auction takes deposits. Zero for fully escrowed bids.
+    pub balance_due: u64,
+    /// Mint the bid is escrowed in: the auction mint or one of its accepted mints.
+    pub mint: Pubkey,
+    /// Wallet credited with bringing this bidder in, paid `referral_bps` if
+    /// the bid wins; default when there is none.
+    pub referrer: Pubkey,
+    /// Set when a receipt token was minted for this bid; withdrawing burns it.
+    pub receipt: bool,
+    /// Lamports of anti-spam bond held by this account beyond its rent.
+    pub bond: u64,
+    /// `AuctionState::auction_id` of the auction the bid was placed in.
+    pub auction_id: u64,
+    /// Which of the bidder's bids in the auction this is; a wallet holds one
+    /// bid per index. See [`bid_index_seed`].
+    ///
+    /// [`bid_index_seed`]: crate::seeds::bid_index_seed
+    pub bid_index: u16,
+    /// Set through `set_standing`: if the bid loses, it carries into the next
+    /// round instead of being refunded by `crank_refund`.
+    pub standing: bool,
+    /// Round the bid was linked into the auction's ranked list in, counting
+    /// from one; zero if never. See the program's
+    /// `AuctionState::is_ranked`.
+    pub ranked_round: u64,
+    /// Neighbouring bids in the ranked list, higher and lower; default at
+    /// either end and while unranked.
+    pub prev_bid: Pubkey,
+    pub next_bid: Pubkey,
+    /// Slot of the last change to `amount` or `max_amount`; settlement waits
+    /// `AuctionState::min_settle_slots` past it.
+    pub updated_slot: u64,
+    /// Loyalty points earned and not yet minted by `claim_points`.
+    pub points: u64,
+    /// When `points` last caught up with the bid's escrow.
+    pub points_accrued_at: i64,
+    /// Share of the treasury's cut given back to the bidder if this bid wins,
+    /// from the fee tier their discount-token balance reached when placing it.
+    pub fee_discount_bps: u16,
+    /// Key that may raise this bid without the bidder's signature, until
+    /// `session_expires_at`; see `set_session_key`. Default when none.
+    pub session_key: Pubkey,
+    pub session_expires_at: i64,
+    /// How much more `session_key` may still add to the bid.
+    pub session_spend_cap: u64,
+    /// `sealed_commitment` of a sealed bid's amount, cleared by `reveal_bid`;
+    /// zero for open bids. `amount` stays zero until then.
+    pub commitment: [u8; 32],
+    /// `AuctionState::commit_bond` escrowed with the sealed bid, apart from
+    /// `max_amount`; cleared when the bid is revealed.
+    pub commit_bond: u64,
+}
+impl Discriminator for AuctionState {
+    const DISCRIMINATOR: [u8; 8] = [252, 227, 205, 147, 72, 64, 250, 126];
+}
+
+impl Discriminator for Bid {
+    const DISCRIMINATOR: [u8; 8] = [143, 246, 48, 245, 42, 145, 180, 88];
+}
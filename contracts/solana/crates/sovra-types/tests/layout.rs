@@ -0,0 +1,152 @@
+//! Checks the mirrored layouts against the program's own types.
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorSerialize, Space};
+use cartoonist_auction::{error, event, state};
+use sovra_types::{decode_account, decode_event, AuctionError, AuctionStatus, Discriminator};
+
+macro_rules! same_discriminators {
+    ($($name:ident,)*) => {
+        $(assert_eq!(
+            <event::$name as anchor_lang::Discriminator>::DISCRIMINATOR,
+            sovra_types::event::$name::DISCRIMINATOR,
+            stringify!($name),
+        );)*
+    };
+}
+
+macro_rules! same_errors {
+    ($($name:ident,)*) => {
+        $(
+            assert_eq!(u32::from(error::AuctionError::$name), AuctionError::$name.code());
+            assert_eq!(error::AuctionError::$name.to_string(), AuctionError::$name.message());
+        )*
+    };
+}
+
+fn zeroed<T: AccountDeserialize + anchor_lang::Discriminator>(space: usize) -> T {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.resize(8 + space, 0);
+    T::try_deserialize(&mut &data[..]).unwrap()
+}
+
+#[test]
+fn accounts_decode_the_bytes_the_program_writes() {
+    let mut state: state::AuctionState = zeroed(state::AuctionState::INIT_SPACE);
+    state.agent = [7; 32].into();
+    state.status = state::AuctionStatus::Settled;
+    state.leaderboard.entries[3].bid_index = 9;
+    state.leaderboard.len = 4;
+    state.memo[63] = 1;
+    state.commit_bonds_held = 42;
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+
+    let mirrored: sovra_types::AuctionState = decode_account(&data).unwrap();
+    assert_eq!(mirrored.agent, [7; 32]);
+    assert_eq!(mirrored.status, AuctionStatus::Settled);
+    assert_eq!(mirrored.leaderboard.entries[3].bid_index, 9);
+    assert_eq!(mirrored.leaderboard.len, 4);
+    assert_eq!(mirrored.memo[63], 1);
+    assert_eq!(mirrored.commit_bonds_held, 42);
+    assert_eq!(borsh::to_vec(&mirrored).unwrap(), data[8..]);
+
+    let mut bid: state::Bid = zeroed(state::Bid::INIT_SPACE);
+    bid.bidder = [3; 32].into();
+    bid.commitment = [5; 32];
+    bid.commit_bond = 11;
+    let mut data = Vec::new();
+    bid.try_serialize(&mut data).unwrap();
+
+    let mirrored: sovra_types::Bid = decode_account(&data).unwrap();
+    assert_eq!(mirrored.bidder, [3; 32]);
+    assert_eq!(mirrored.commitment, [5; 32]);
+    assert_eq!(mirrored.commit_bond, 11);
+    assert_eq!(borsh::to_vec(&mirrored).unwrap(), data[8..]);
+    assert!(decode_account::<sovra_types::AuctionState>(&data).is_err());
+}
+
+#[test]
+fn events_match_the_program() {
+    same_discriminators! {
+        BidPlaced, BidUpdated, BidIncreased, ModeratorChanged, BidSlashed, BidExpired,
+        BidDecreased, BidWithdrawn, BidSettled, PenaltiesSwept, TreasuryChanged, SurplusSwept,
+        BidRefunded, TieBreakRequested, TieResolved, RaffleDrawRequested, RaffleDrawn,
+        CrossChainBidReceived, CrossChainRefund, RefundRootPosted, RefundClaimed, ContentCommitted,
+        ContentRevealed, PrizeDeposited, PrizeDelivered, PrizeWithdrawn, SettledFundsStreamed,
+        SettledFundsClaimed, PaymentCompleted, DepositForfeited, AcceptedMintAdded,
+        AcceptedMintRemoved, WithheldFeesHarvested, SettlerChanged, AuctionAbandoned,
+        CuratorChanged, ReferralPaid, BidTransferred, StatusChanged, AuctionCancelled, Outbid,
+        CandleEndRequested, CandleEndDetermined, AllPayChanged, AllPayBidCollected, AuctionCreated,
+        AuctionClosed, LotCreated, LotBidPlaced, LotBidWithdrawn, LotSettled, BundleBidPlaced,
+        BundleSettled, AuctionScheduled, StandingChanged, MinimumBidRecalculated, AgentChanged,
+        MinimumBidChanged, EscrowMigrated, TokensRescued, BidLinked, BidUnlinked,
+        TopKWinnerSettled, BidAccountClosed, DenylistChanged, BidForceRefunded,
+        DisputeTermsChanged, SettlementHeld, DisputeOpened, SettlementResolved, WinClaimed,
+        WinnerDefaulted, PointsClaimed, RebatePaid, FeeDiscounted, SessionKeySet, BidRevealed,
+        CommitBondsSwept, RaffleModeChanged, UniformPriceChanged, BuyNowPriceChanged,
+        CandleWindowChanged, BidTtlChanged, TransferHooksChanged, EnglishAuctionChanged,
+        NumWinnersChanged, VestingDurationChanged, Paused, Unpaused,
+    }
+
+    let revealed = event::BidRevealed {
+        bidder: [1; 32].into(),
+        bid_index: 2,
+        amount: 3,
+    };
+    let mut data = <event::BidRevealed as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+    revealed.serialize(&mut data).unwrap();
+    let mirrored: sovra_types::event::BidRevealed = decode_event(&data).unwrap();
+    assert_eq!(
+        mirrored,
+        sovra_types::event::BidRevealed {
+            bidder: [1; 32],
+            bid_index: 2,
+            amount: 3,
+        }
+    );
+}
+
+#[test]
+fn error_codes_match_the_program() {
+    same_errors! {
+        OnlyAgent, BidTooLow, BidNotActive, AmountBelowMinimum, InsufficientEscrow, WrongBidder,
+        BidStillActive, ArithmeticOverflow, InvalidMintDecimals, InvalidAmountChange, InvalidBps,
+        AuctionEnded, LeaderBidRequired, WrongLeaderBid, WinnerTokenAccountRequired,
+        BuyNowDisabled, BelowBuyNowPrice, AuctionSettled, InvalidNumWinners, WinnersMismatch,
+        InvalidTokenAccount, CharityAccountRequired, EscrowInsolvent, AuctionNotEnded,
+        AuctionNotSettled, NoTie, InvalidRandomness, RandomnessNotRevealed, RaffleModeDisabled,
+        RaffleAlreadyDrawn, RaffleEntrantsMismatch, RaffleNotDrawn, InvalidVaa, UnknownEmitter,
+        OnlyRelayer, InvalidRefundRoot, RefundRootNotPosted, MerkleRefundsActive,
+        InvalidMerkleProof, RefundAlreadyClaimed, RefundExceedsEscrow, InvalidContentHash,
+        ContentAlreadyCommitted, ContentNotCommitted, ContentHashMismatch, ContentUriTooLong,
+        InvalidPrizeAmount, InvalidPrizeMint, PrizeAlreadyDeposited, PrizeLocked, NoPrize,
+        PrizeAccountsRequired, PrizeRequiresSingleWinner, InvalidVestingDuration,
+        VestingInProgress, NothingVested, InvalidPaymentWindow, BalanceDue, NoBalanceDue,
+        PaymentWindowClosed, PaymentWindowOpen, MintNotAccepted, MintAlreadyAccepted,
+        AcceptedMintsFull, AcceptedMintInUse, AuctionMintOnly, UnsafeMint, NonTransferableMint,
+        OnlySettler, InvalidAbandonmentWindow, AuctionAbandoned, NotAbandonable,
+        CuratorAccountRequired, SelfReferral, ReferrerAccountRequired, ForeignBid,
+        ReceiptAccountsRequired, OnlyModerator, InvalidBidTtl, BidExpired, BidNotExpired,
+        InvalidStatusTransition, AuctionCancelled, RivalBidsMismatch, WinnerNotHighest,
+        EnglishModeLocked, LeaderAccountRequired, InvalidCandleWindow, CandleModeDisabled,
+        CandleAlreadyDetermined, CandleNotDetermined, CandleEntrantsMismatch, BidAfterCandle,
+        BidsNonRefundable, AllPayLocked, AllPayDisabled, WrongAuction, InvalidAuctionId,
+        NotInRegistry, AuctionClosed, BidsOutstanding, BidAlreadyPlaced, LotSettled,
+        LotWinnerRequired, LotLeaderLocked, InvalidBundle, BundlePending, AuctionNotStarted,
+        StandingBid, InvalidTrailingRounds, AuditLogRequired, EscrowNotEmpty, MintNotRescuable,
+        BidRanked, BidNotRanked, RankHintMismatch, RankOrderViolated, LeaderNotRankedFirst,
+        TopKSettlementPending, TopKUniformPrice, RankedListEmpty, BidPairsMismatch,
+        BidderDenylisted, BidderNotDenylisted, DenylistRequired, DenylistFull, OnlyArbiter,
+        ArbiterRequired, NoHeldSettlement, SettlementAlreadyHeld, DisputeWindowOpen,
+        DisputeWindowClosed, DisputeRequiresSingleWinner, InvalidDisputeWindow, InvalidClaimWindow,
+        NoClaimWindow, ClaimWindowOpen, ClaimWindowClosed, WinClaimed, WinNotClaimed,
+        ClaimRequiresSingleWinner, SettlementTooSoon, MemoTooLong, MemoProgramRequired, NoPoints,
+        InvalidFeeTiers, TieLostOnPriority, BidderSignatureRequired, InvalidSessionKey,
+        SessionKeyExpired, SessionSpendCapExceeded, SealedBidsLocked, SealedBidsOnly,
+        SealedBidsDisabled, BidSealed, BidNotSealed, NotRevealing, RevealWindowOpen,
+        CommitmentMismatch, InvalidRevealWindow, InvalidThread, SettlementNotSchedulable,
+        PointsUnclaimed, TieChanged, AuctionPaused, BidReceipted,
+    }
+    assert_eq!(AuctionError::from_code(6000), Some(AuctionError::OnlyAgent));
+    assert_eq!(AuctionError::from_code(6000 + 166), None);
+}
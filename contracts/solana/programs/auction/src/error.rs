@@ -22,4 +22,316 @@ pub enum AuctionError {
     InvalidMintDecimals,
     #[msg("Invalid amount change")]
     InvalidAmountChange,
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBps,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("The current leader's bid account must be provided")]
+    LeaderBidRequired,
+    #[msg("Provided bid is not the current leader")]
+    WrongLeaderBid,
+    #[msg("Winner token account required to refund proxy surplus")]
+    WinnerTokenAccountRequired,
+    #[msg("Buy-it-now is not enabled")]
+    BuyNowDisabled,
+    #[msg("Escrowed amount is below the buy-it-now price")]
+    BelowBuyNowPrice,
+    #[msg("Auction is already settled")]
+    AuctionSettled,
+    #[msg("Number of winners must be between 1 and the leaderboard size")]
+    InvalidNumWinners,
+    #[msg("Winning bids do not match the leaderboard")]
+    WinnersMismatch,
+    #[msg("Token account has the wrong owner or mint")]
+    InvalidTokenAccount,
+    #[msg("Charity token account required for this settlement")]
+    CharityAccountRequired,
+    #[msg("Escrow balance is below total escrowed")]
+    EscrowInsolvent,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Auction has not been settled yet")]
+    AuctionNotSettled,
+    #[msg("The top of the leaderboard is not tied")]
+    NoTie,
+    #[msg("Randomness account is invalid or was not committed for this tie")]
+    InvalidRandomness,
+    #[msg("Randomness has not been revealed yet")]
+    RandomnessNotRevealed,
+    #[msg("Raffle mode is not enabled")]
+    RaffleModeDisabled,
+    #[msg("Raffle winner has already been drawn")]
+    RaffleAlreadyDrawn,
+    #[msg("Raffle entrants must be every active bid, sorted by account key")]
+    RaffleEntrantsMismatch,
+    #[msg("Raffle winner has not been drawn yet")]
+    RaffleNotDrawn,
+    #[msg("Invalid or unexpected Wormhole VAA")]
+    InvalidVaa,
+    #[msg("VAA was not sent by the registered emitter")]
+    UnknownEmitter,
+    #[msg("Only the relayer can perform this action")]
+    OnlyRelayer,
+    #[msg("Refund root must be non-zero and cover between 1 and 80000 leaves")]
+    InvalidRefundRoot,
+    #[msg("No refunds Merkle root has been posted")]
+    RefundRootNotPosted,
+    #[msg("Refunds are paid through Merkle claims for this round")]
+    MerkleRefundsActive,
+    #[msg("Merkle proof does not match the refunds root")]
+    InvalidMerkleProof,
+    #[msg("Refund has already been claimed")]
+    RefundAlreadyClaimed,
+    #[msg("Refund exceeds the bid's escrow")]
+    RefundExceedsEscrow,
+    #[msg("Content hash must be non-zero")]
+    InvalidContentHash,
+    #[msg("Content has already been committed for this round")]
+    ContentAlreadyCommitted,
+    #[msg("No content has been committed for this round")]
+    ContentNotCommitted,
+    #[msg("URI does not hash to the committed content hash")]
+    ContentHashMismatch,
+    #[msg("Content URI is too long")]
+    ContentUriTooLong,
+    #[msg("Prize amount must be non-zero")]
+    InvalidPrizeAmount,
+    #[msg("Prize mint must differ from the bid mint")]
+    InvalidPrizeMint,
+    #[msg("A prize is already escrowed")]
+    PrizeAlreadyDeposited,
+    #[msg("The prize can only move while there are no active bids")]
+    PrizeLocked,
+    #[msg("No prize is escrowed")]
+    NoPrize,
+    #[msg("Prize vault, mint, winner token account and token program are required")]
+    PrizeAccountsRequired,
+    #[msg("An escrowed prize can only go to a single winner")]
+    PrizeRequiresSingleWinner,
+    #[msg("Vesting duration must not be negative")]
+    InvalidVestingDuration,
+    #[msg("Settled funds are still vesting")]
+    VestingInProgress,
+    #[msg("No settled funds have vested yet")]
+    NothingVested,
+    #[msg("Payment window must be positive when deposits are enabled")]
+    InvalidPaymentWindow,
+    #[msg("Bid has a balance due; complete payment first")]
+    BalanceDue,
+    #[msg("Bid has no balance due")]
+    NoBalanceDue,
+    #[msg("Payment window has closed")]
+    PaymentWindowClosed,
+    #[msg("Payment window is still open")]
+    PaymentWindowOpen,
+    #[msg("Mint is not accepted by this auction")]
+    MintNotAccepted,
+    #[msg("Mint is already accepted")]
+    MintAlreadyAccepted,
+    #[msg("Accepted mint list is full")]
+    AcceptedMintsFull,
+    #[msg("Bids are still escrowed in this mint")]
+    AcceptedMintInUse,
+    #[msg("Only available for bids in the auction mint")]
+    AuctionMintOnly,
+    #[msg("Mint has a risky Token-2022 extension; set its override flag to accept it")]
+    UnsafeMint,
+    #[msg("Non-transferable mints cannot back an auction")]
+    NonTransferableMint,
+    #[msg("Only the agent or settler can call this")]
+    OnlySettler,
+    #[msg("Abandonment window must not be negative")]
+    InvalidAbandonmentWindow,
+    #[msg("Auction was abandoned")]
+    AuctionAbandoned,
+    #[msg("Auction cannot be marked abandoned yet")]
+    NotAbandonable,
+    #[msg("Curator token account required for this settlement")]
+    CuratorAccountRequired,
+    #[msg("Bidders cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referrer token account required for this settlement")]
+    ReferrerAccountRequired,
+    #[msg("Cross-chain bids are managed by the relayer")]
+    ForeignBid,
+    #[msg("Receipt mint and token account are required")]
+    ReceiptAccountsRequired,
+    #[msg("Only the moderator can call this")]
+    OnlyModerator,
+    #[msg("Bid time-to-live must not be negative")]
+    InvalidBidTtl,
+    #[msg("Bid has expired")]
+    BidExpired,
+    #[msg("Bid has not expired")]
+    BidNotExpired,
+    #[msg("Auction status does not allow this")]
+    InvalidStatusTransition,
+    #[msg("Auction was cancelled")]
+    AuctionCancelled,
+    #[msg("Rival bids must list every other active bid once, sorted by address")]
+    RivalBidsMismatch,
+    #[msg("A rival bid is higher than the claimed winner")]
+    WinnerNotHighest,
+    #[msg("English mode can only change while at most one bid is active")]
+    EnglishModeLocked,
+    #[msg("Displaced leader's token account is required in English mode")]
+    LeaderAccountRequired,
+    #[msg("Candle window must not be negative")]
+    InvalidCandleWindow,
+    #[msg("Candle mode is not enabled")]
+    CandleModeDisabled,
+    #[msg("Candle end has already been determined")]
+    CandleAlreadyDetermined,
+    #[msg("Candle end has not been determined yet")]
+    CandleNotDetermined,
+    #[msg("Candle entrants must be every active bid, sorted by account key")]
+    CandleEntrantsMismatch,
+    #[msg("Bid was placed or updated after the candle went out")]
+    BidAfterCandle,
+    #[msg("All-pay mode: bids are not refundable")]
+    BidsNonRefundable,
+    #[msg("All-pay mode can only change with no active bids, and not alongside English mode")]
+    AllPayLocked,
+    #[msg("All-pay mode is not enabled")]
+    AllPayDisabled,
+    #[msg("Bid belongs to a different auction")]
+    WrongAuction,
+    #[msg("Auction id zero is reserved for the auction set up by initialize")]
+    InvalidAuctionId,
+    #[msg("Auction is not listed on this registry page")]
+    NotInRegistry,
+    #[msg("Auction has been closed")]
+    AuctionClosed,
+    #[msg("Auction can only close once every bid is paid out or refunded")]
+    BidsOutstanding,
+    #[msg("Bidder already has a bid in this auction; use upsert_bid or increase_bid")]
+    BidAlreadyPlaced,
+    #[msg("Lot has already been settled")]
+    LotSettled,
+    #[msg("The lot's leading bid and its bidder are required to sell it")]
+    LotWinnerRequired,
+    #[msg("The lot's leading bid stays escrowed until the lot settles")]
+    LotLeaderLocked,
+    #[msg("A bundle covers 2 to 8 distinct lots, listed in ascending order with their accounts")]
+    InvalidBundle,
+    #[msg("Lot is covered by a bundle bid that has not been settled")]
+    BundlePending,
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+    #[msg("Standing bids carry into the next round; only their bidder can withdraw them")]
+    StandingBid,
+    #[msg("The dynamic minimum averages 1 to 8 trailing rounds")]
+    InvalidTrailingRounds,
+    #[msg("This auction keeps an audit log; pass it to record the change")]
+    AuditLogRequired,
+    #[msg("Escrow must hold no bids or surplus to move to a different mint")]
+    EscrowNotEmpty,
+    #[msg("The auction, accepted and escrowed prize mints cannot be rescued")]
+    MintNotRescuable,
+    #[msg("Bid is in the ranked list; unlink it first")]
+    BidRanked,
+    #[msg("Bid is not in the ranked list")]
+    BidNotRanked,
+    #[msg("The given neighbours are not adjacent in the ranked list")]
+    RankHintMismatch,
+    #[msg("Bid does not belong between the given neighbours")]
+    RankOrderViolated,
+    #[msg("The leader's bid must head the ranked list")]
+    LeaderNotRankedFirst,
+    #[msg("A ranked settlement is under way")]
+    TopKSettlementPending,
+    #[msg("Uniform-price rounds settle through settle_multi")]
+    TopKUniformPrice,
+    #[msg("No ranked bids to settle")]
+    RankedListEmpty,
+    #[msg("Expected (bid, bidder) account pairs")]
+    BidPairsMismatch,
+    #[msg("Bidder is denylisted")]
+    BidderDenylisted,
+    #[msg("Bidder is not denylisted")]
+    BidderNotDenylisted,
+    #[msg("The auction has a denylist; pass it")]
+    DenylistRequired,
+    #[msg("Denylist is full")]
+    DenylistFull,
+    #[msg("Only the arbiter can do this")]
+    OnlyArbiter,
+    #[msg("A dispute window needs an arbiter")]
+    ArbiterRequired,
+    #[msg("No settlement is held")]
+    NoHeldSettlement,
+    #[msg("The previous settlement is still held")]
+    SettlementAlreadyHeld,
+    #[msg("The dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("The dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Held settlements need a single winner")]
+    DisputeRequiresSingleWinner,
+    #[msg("Dispute window must not be negative")]
+    InvalidDisputeWindow,
+    #[msg("Claim window must not be negative")]
+    InvalidClaimWindow,
+    #[msg("The auction has no claim window")]
+    NoClaimWindow,
+    #[msg("The claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("The claim window has closed")]
+    ClaimWindowClosed,
+    #[msg("The win has already been claimed")]
+    WinClaimed,
+    #[msg("The winner has not claimed the win")]
+    WinNotClaimed,
+    #[msg("Claimed wins need a single winner")]
+    ClaimRequiresSingleWinner,
+    #[msg("The winning bid changed too recently to settle")]
+    SettlementTooSoon,
+    #[msg("Memo is too long")]
+    MemoTooLong,
+    #[msg("The auction has a memo; pass the memo program")]
+    MemoProgramRequired,
+    #[msg("The bid has no points to claim")]
+    NoPoints,
+    #[msg("Invalid fee discount tiers")]
+    InvalidFeeTiers,
+    #[msg("A rival tied with the winner and holds priority by bidding first")]
+    TieLostOnPriority,
+    #[msg("Only the bidder can make this change")]
+    BidderSignatureRequired,
+    #[msg("Signer is not the bid's session key")]
+    InvalidSessionKey,
+    #[msg("The session key has expired")]
+    SessionKeyExpired,
+    #[msg("The raise exceeds what the session key may still spend")]
+    SessionSpendCapExceeded,
+    #[msg("Sealed bids can only be switched with no active bids, and not in English mode")]
+    SealedBidsLocked,
+    #[msg("The auction takes sealed bids only")]
+    SealedBidsOnly,
+    #[msg("The auction does not take sealed bids")]
+    SealedBidsDisabled,
+    #[msg("The bid is sealed until revealed")]
+    BidSealed,
+    #[msg("The bid is not sealed")]
+    BidNotSealed,
+    #[msg("Sealed bids are revealed between the deadline and the end of the reveal window")]
+    NotRevealing,
+    #[msg("Sealed bids are still being revealed")]
+    RevealWindowOpen,
+    #[msg("The revealed amount does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Reveal window must not be negative")]
+    InvalidRevealWindow,
+    #[msg("Not this round's settlement thread or the thread program")]
+    InvalidThread,
+    #[msg("Settlement needs accounts only a keeper can supply")]
+    SettlementNotSchedulable,
+    #[msg("Claim the bid's points before closing it")]
+    PointsUnclaimed,
+    #[msg("The tied bids changed since the tie break was requested")]
+    TieChanged,
+    #[msg("Bidding is paused")]
+    AuctionPaused,
+    #[msg("Bids holding a receipt token cannot be transferred")]
+    BidReceipted,
 }
@@ -1,25 +1,760 @@
 use anchor_lang::prelude::*;
 
+use crate::state::AuctionStatus;
+
 #[event]
+#[derive(Clone, Debug)]
 pub struct BidPlaced {
     pub bidder: Pubkey,
     pub amount: u64,
+    /// `Bid::bid_index`; zero for a wallet's first bid.
+    pub bid_index: u16,
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct BidUpdated {
     pub bidder: Pubkey,
     pub new_amount: u64,
 }
 
 #[event]
+#[derive(Clone, Debug)]
+pub struct BidIncreased {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+    pub max_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ModeratorChanged {
+    pub moderator: Pubkey,
+    pub bid_bond: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidSlashed {
+    pub bidder: Pubkey,
+    pub moderator: Pubkey,
+    /// Escrow returned to the bidder.
+    pub refunded: u64,
+    /// Lamports of bond sent to the treasury owner.
+    pub bond: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidExpired {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidDecreased {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+    pub max_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
 pub struct BidWithdrawn {
     pub bidder: Pubkey,
     pub amount: u64,
+    pub penalty: u64,
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct BidSettled {
     pub winner: Pubkey,
     pub amount: u64,
+    /// Portion of `amount` routed to the charity account.
+    pub charity_amount: u64,
+    /// Portion of `amount` paid to `curator`; zero when no curator is set.
+    pub curator: Pubkey,
+    pub curator_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PenaltiesSwept {
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct TreasuryChanged {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct SurplusSwept {
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidRefunded {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct TieBreakRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+    pub tied_count: u8,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct TieResolved {
+    pub randomness_account: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub tied_count: u8,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RaffleDrawRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RaffleDrawn {
+    pub randomness_account: Pubkey,
+    pub winner: Pubkey,
+    pub winner_weight: u64,
+    pub total_weight: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct CrossChainBidReceived {
+    pub bidder: Pubkey,
+    pub foreign_chain: u16,
+    pub sequence: u64,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct CrossChainRefund {
+    pub bidder: Pubkey,
+    pub foreign_chain: u16,
+    pub sequence: u64,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RefundRootPosted {
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RefundClaimed {
+    pub bidder: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ContentCommitted {
+    pub content_hash: [u8; 32],
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ContentRevealed {
+    pub content_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PrizeDeposited {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PrizeDelivered {
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PrizeWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct SettledFundsStreamed {
+    pub amount: u64,
+    /// Previously vested funds paid out before the stream restarted.
+    pub released: u64,
+    pub vesting_total: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct SettledFundsClaimed {
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PaymentCompleted {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// Superseded by `WinnerDefaulted`; kept so older logs still decode.
+#[event]
+#[derive(Clone, Debug)]
+pub struct DepositForfeited {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Bidder now on top of the leaderboard; default when there is none.
+    pub next_bidder: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct AcceptedMintAdded {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub treasury: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct AcceptedMintRemoved {
+    pub mint: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct WithheldFeesHarvested {
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Whether the fees went on to the treasury rather than staying in the mint.
+    pub withdrawn: bool,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct SettlerChanged {
+    pub old_settler: Pubkey,
+    pub new_settler: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct AuctionAbandoned {
+    pub end_time: i64,
+    pub active_bid_count: u64,
+    pub marked_by: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct CuratorChanged {
+    pub curator: Pubkey,
+    pub curator_bps: u16,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ReferralPaid {
+    pub referrer: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub max_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct StatusChanged {
+    pub from: AuctionStatus,
+    pub to: AuctionStatus,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct AuctionCancelled {
+    pub active_bid_count: u64,
+    pub total_escrowed: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct Outbid {
+    pub previous_leader: Pubkey,
+    pub previous_amount: u64,
+    pub new_leader: Pubkey,
+    pub new_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct CandleEndRequested {
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct CandleEndDetermined {
+    pub randomness_account: Pubkey,
+    pub candle_end: i64,
+    /// Default when no bid was in before the candle went out.
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// All-pay mode was switched. While on, no bid is refunded: losing bids go
+/// to the treasury once the round settles.
+#[event]
+#[derive(Clone, Debug)]
+pub struct AllPayChanged {
+    pub enabled: bool,
+}
+
+/// A losing all-pay bid's escrow went to the treasury.
+#[event]
+#[derive(Clone, Debug)]
+pub struct AllPayBidCollected {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// The operator's agent opened a new auction with `create_auction`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct AuctionCreated {
+    pub auction_id: u64,
+    pub auction_state: Pubkey,
+    pub agent: Pubkey,
+    pub usdc_mint: Pubkey,
+}
+
+/// `close_auction` wound the auction up; figures cover its last round.
+#[event]
+#[derive(Clone, Debug)]
+pub struct AuctionClosed {
+    pub total_bids: u64,
+    /// Bids entered this round, from `AuctionState::round_bidder_count`; a
+    /// wallet counts once per bid it entered, not once overall.
+    pub bidder_entries: u64,
+    pub winning_amount: u64,
+    /// Seconds from the start of the round to the close.
+    pub duration: i64,
+}
+
+/// The agent added a lot to the round with `create_lot`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct LotCreated {
+    pub lot_id: u16,
+    pub minimum_bid: u64,
+    pub reserve_price: u64,
+    pub item_hash: [u8; 32],
+}
+
+/// A lot bid was placed or raised to `amount`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct LotBidPlaced {
+    pub lot_id: u16,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Whether the bid now leads its lot.
+    pub leading: bool,
+}
+
+/// A lot bid was withdrawn and its escrow refunded.
+#[event]
+#[derive(Clone, Debug)]
+pub struct LotBidWithdrawn {
+    pub lot_id: u16,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// `settle_lot` sold a lot to `winner` for `amount`, or closed it unsold
+/// with a default winner and zero amount.
+#[event]
+#[derive(Clone, Debug)]
+pub struct LotSettled {
+    pub lot_id: u16,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// A bundle bid offered `amount` for `lots` together.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BundleBidPlaced {
+    pub bidder: Pubkey,
+    pub lots: Vec<u16>,
+    pub amount: u64,
+}
+
+/// `settle_bundle` sold a bundle's lots to its bidder, or refunded it when
+/// it did not beat the lots' leading bids.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BundleSettled {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub won: bool,
+}
+
+/// Bidding on an auction opens at `start_time`; emitted when it is created
+/// and whenever the agent moves the start.
+#[event]
+#[derive(Clone, Debug)]
+pub struct AuctionScheduled {
+    pub auction_id: u64,
+    pub start_time: i64,
+}
+
+/// A bidder opted their bid in or out of carrying into later rounds.
+#[event]
+#[derive(Clone, Debug)]
+pub struct StandingChanged {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub standing: bool,
+}
+
+/// A new round's floor was reset from recent clearing prices.
+#[event]
+#[derive(Clone, Debug)]
+pub struct MinimumBidRecalculated {
+    pub auction_id: u64,
+    pub trailing_average: u64,
+    pub minimum_bid: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct AgentChanged {
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+}
+
+/// Emitted by `set_minimum_bid` and by `update_config` when it sets one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct MinimumBidChanged {
+    pub old_minimum_bid: u64,
+    pub new_minimum_bid: u64,
+}
+
+/// The auction's escrow was replaced, moving `amount` into `new_escrow`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct EscrowMigrated {
+    pub auction_id: u64,
+    pub old_escrow: Pubkey,
+    pub new_escrow: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Stray tokens were moved out of an account the auction controls.
+#[event]
+#[derive(Clone, Debug)]
+pub struct TokensRescued {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// A bid joined the ranked list between `prev_bid` and `next_bid`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidLinked {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+    pub prev_bid: Pubkey,
+    pub next_bid: Pubkey,
+}
+
+/// A bid left the ranked list.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidUnlinked {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+}
+
+/// `settle_top_k` paid the `rank`-th highest ranked bid, counting from one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct TopKWinnerSettled {
+    pub auction_id: u64,
+    pub rank: u8,
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub price: u64,
+}
+
+/// `gc_bid` closed an inactive bid, returning `lamports` to its bidder.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidAccountClosed {
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub lamports: u64,
+}
+
+/// `wallet` was added to or removed from the auction's denylist.
+#[event]
+#[derive(Clone, Debug)]
+pub struct DenylistChanged {
+    pub wallet: Pubkey,
+    pub denied: bool,
+}
+
+/// The agent refunded a denylisted bidder's bid in full and closed it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidForceRefunded {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// The agent set who rules on held settlements and how long they are held.
+#[event]
+#[derive(Clone, Debug)]
+pub struct DisputeTermsChanged {
+    pub arbiter: Pubkey,
+    pub dispute_window: i64,
+}
+
+/// A settled price stays in escrow until `held_until`, open to dispute.
+#[event]
+#[derive(Clone, Debug)]
+pub struct SettlementHeld {
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub held_until: i64,
+}
+
+/// The winner disputed the held settlement; only the arbiter can resolve it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct DisputeOpened {
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// A held settlement was resolved: paid out, or refunded to the winner.
+#[event]
+#[derive(Clone, Debug)]
+pub struct SettlementResolved {
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+}
+
+/// The top bidder confirmed their win within the claim window.
+#[event]
+#[derive(Clone, Debug)]
+pub struct WinClaimed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// The top bidder failed to pay or claim in time: `slashed` of their escrow
+/// went to the treasury, `refunded` back to them, and `next_bidder` (default
+/// when there is none) is now on top.
+#[event]
+#[derive(Clone, Debug)]
+pub struct WinnerDefaulted {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub slashed: u64,
+    pub refunded: u64,
+    pub next_bidder: Pubkey,
+}
+
+/// Loyalty points a bid earned were minted to its bidder.
+#[event]
+#[derive(Clone, Debug)]
+pub struct PointsClaimed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// A refunded losing bid was paid its share of the round's rebate pool.
+#[event]
+#[derive(Clone, Debug)]
+pub struct RebatePaid {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+/// The winner's fee tier gave `amount` of the treasury's cut back to them.
+#[event]
+#[derive(Clone, Debug)]
+pub struct FeeDiscounted {
+    pub winner: Pubkey,
+    pub discount_bps: u16,
+    pub amount: u64,
+}
+
+/// The bidder let `session_key` raise the bid by up to `spend_cap` until
+/// `expires_at`; a default key revokes it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct SessionKeySet {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub spend_cap: u64,
+}
+
+/// A sealed bid was opened at `amount`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidRevealed {
+    pub bidder: Pubkey,
+    pub bid_index: u16,
+    pub amount: u64,
+}
+
+/// Forfeited commit bonds went to the treasury.
+#[event]
+#[derive(Clone, Debug)]
+pub struct CommitBondsSwept {
+    pub amount: u64,
+}
+
+/// Emitted by `set_raffle_mode` and by `update_config` when it sets it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct RaffleModeChanged {
+    pub old_raffle_mode: bool,
+    pub new_raffle_mode: bool,
+}
+
+/// Emitted by `set_uniform_price` and by `update_config` when it sets it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct UniformPriceChanged {
+    pub old_uniform_price: bool,
+    pub new_uniform_price: bool,
+}
+
+/// Emitted by `set_buy_now_price` and by `update_config` when it sets one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BuyNowPriceChanged {
+    pub old_buy_now_price: u64,
+    pub new_buy_now_price: u64,
+}
+
+/// Emitted by `set_candle_window` and by `update_config` when it sets one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct CandleWindowChanged {
+    pub old_candle_window: i64,
+    pub new_candle_window: i64,
+}
+
+/// Emitted by `set_bid_ttl` and by `update_config` when it sets one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct BidTtlChanged {
+    pub old_bid_ttl: i64,
+    pub new_bid_ttl: i64,
+}
+
+/// Emitted by `set_transfer_hooks` and by `update_config` when it sets it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransferHooksChanged {
+    pub old_transfer_hooks: bool,
+    pub new_transfer_hooks: bool,
+}
+
+/// Emitted by `set_english_auction` and by `update_config` when it sets it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct EnglishAuctionChanged {
+    pub old_english_auction: bool,
+    pub new_english_auction: bool,
+}
+
+/// Emitted by `set_num_winners` and by `update_config` when it sets it.
+#[event]
+#[derive(Clone, Debug)]
+pub struct NumWinnersChanged {
+    pub old_num_winners: u8,
+    pub new_num_winners: u8,
+}
+
+/// Emitted by `set_vesting_duration` and by `update_config` when it sets one.
+#[event]
+#[derive(Clone, Debug)]
+pub struct VestingDurationChanged {
+    pub old_vesting_duration: i64,
+    pub new_vesting_duration: i64,
+}
+
+/// The agent paused bidding; see `set_paused`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct Paused {
+    pub auction_id: u64,
+}
+
+/// The agent resumed bidding.
+#[event]
+#[derive(Clone, Debug)]
+pub struct Unpaused {
+    pub auction_id: u64,
 }
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::WinnerDefaulted;
+use crate::state::{AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::{BPS_DENOMINATOR, USDC_DECIMALS};
+
+/// Accounts needed to unwind a top bid whose bidder failed to pay or claim.
+pub struct Forfeiture<'a, 'info> {
+    pub auction_state: &'a mut Account<'info, AuctionState>,
+    pub escrow: &'a InterfaceAccount<'info, TokenAccount>,
+    /// Receives the slash.
+    pub treasury: &'a InterfaceAccount<'info, TokenAccount>,
+    /// Receives the rest of the bid's escrow.
+    pub bidder_usdc: &'a InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    /// Forwarded to the mint's transfer hook, if it has one.
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'info> Forfeiture<'_, 'info> {
+    /// Slashes `default_penalty_bps` of what `bid` escrowed to the treasury,
+    /// refunds the rest, and passes the lead to the next bidder on the
+    /// leaderboard. `bid` must head the leaderboard.
+    pub fn apply(self, bid: &mut Bid) -> Result<()> {
+        let top = self
+            .auction_state
+            .leaderboard
+            .entries()
+            .first()
+            .ok_or(AuctionError::WinnersMismatch)?;
+        require!(
+            top.is(bid.bidder, bid.bid_index),
+            AuctionError::WinnersMismatch
+        );
+
+        let amount = bid.escrowed();
+        let slashed = (u128::from(amount) * u128::from(self.auction_state.default_penalty_bps)
+            / u128::from(BPS_DENOMINATOR)) as u64;
+        let refunded = amount - slashed;
+        self.transfer(self.treasury, slashed)?;
+        self.transfer(self.bidder_usdc, refunded)?;
+
+        self.auction_state
+            .accrue_points(bid, Clock::get()?.unix_timestamp)?;
+        bid.active = false;
+        bid.max_amount = 0;
+        bid.balance_due = 0;
+
+        let state = &mut *self.auction_state;
+        state.active_bid_count = state
+            .active_bid_count
+            .checked_sub(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.debit_escrow(amount)?;
+        state.forget_bid(bid.bidder, bid.bid_index);
+        if let Some(next) = state.leaderboard.entries().first().copied() {
+            state.set_leader(next.bidder, next.bid_index);
+            state.leading_amount = next.amount;
+        }
+
+        emit!(WinnerDefaulted {
+            bidder: bid.bidder,
+            bid_index: bid.bid_index,
+            slashed,
+            refunded,
+            next_bidder: state.leader,
+        });
+
+        Ok(())
+    }
+
+    fn transfer(&self, to: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let state_bump = self.auction_state.bump;
+        let id_seed = self.auction_state.id_seed();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.auction_state.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            USDC_DECIMALS,
+            self.hook_accounts,
+        )
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::event::AcceptedMintAdded;
+use crate::state::{AcceptedMint, AcceptedMints, AuctionState, MAX_ACCEPTED_MINTS};
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct AddAcceptedMint<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + AcceptedMints::INIT_SPACE,
+        seeds = [b"accepted_mints", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub accepted_mints: Account<'info, AcceptedMints>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() != auction_state.usdc_mint @ AuctionError::MintAlreadyAccepted,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        token::mint = mint,
+        token::authority = auction_state,
+        token::token_program = token_program,
+        seeds = [b"escrow", auction_state.id_seed().as_slice(), mint.key().as_ref()],
+        bump,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(token::mint = mint, token::authority = agent)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    /// Owner of `mint`, which need not be the auction mint's program.
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accepts bids in another stablecoin. Amounts are compared 1:1 with the
+/// auction mint, so only mints with the same decimals are allowed.
+pub fn handler(ctx: Context<AddAcceptedMint>) -> Result<()> {
+    require!(
+        ctx.accounts.mint.decimals == USDC_DECIMALS,
+        AuctionError::InvalidMintDecimals
+    );
+
+    let accepted = &mut ctx.accounts.accepted_mints;
+    let mint = ctx.accounts.mint.key();
+    require!(accepted.get(&mint).is_none(), AuctionError::MintAlreadyAccepted);
+    require!(
+        (accepted.len as usize) < MAX_ACCEPTED_MINTS,
+        AuctionError::AcceptedMintsFull
+    );
+
+    let len = accepted.len as usize;
+    accepted.bump = ctx.bumps.accepted_mints;
+    accepted.entries[len] = AcceptedMint {
+        mint,
+        escrow: ctx.accounts.escrow.key(),
+        treasury: ctx.accounts.treasury.key(),
+        escrow_bump: ctx.bumps.escrow,
+        total_escrowed: 0,
+    };
+    accepted.len += 1;
+
+    emit!(AcceptedMintAdded {
+        mint,
+        escrow: ctx.accounts.escrow.key(),
+        treasury: ctx.accounts.treasury.key(),
+    });
+
+    Ok(())
+}
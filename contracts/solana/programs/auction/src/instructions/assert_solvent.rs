@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::error::AuctionError;
+use crate::state::{AcceptedMints, AuctionState};
+
+#[derive(Accounts)]
+pub struct AssertSolvent<'info> {
+    #[account(seeds = [b"auction_state", auction_state.id_seed().as_slice()], bump = auction_state.bump)]
+    pub auction_state: Account<'info, AuctionState>,
+    /// The auction escrow, or an accepted mint's escrow; checked in the handler.
+    #[account(token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// Required to check the escrow of a mint other than the auction mint.
+    #[account(seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+}
+
+/// Permissionless check that the escrow holds at least what the program owes
+/// out of it.
+pub fn handler(ctx: Context<AssertSolvent>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let accepted = ctx.accounts.accepted_mints.as_deref();
+    let mint = ctx.accounts.escrow.mint;
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    require!(
+        ctx.accounts.escrow.amount >= state.escrowed_in(accepted, &mint)?,
+        AuctionError::EscrowInsolvent
+    );
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::payout::Payout;
+use crate::prize::PrizeDelivery;
+use crate::state::{AuctionState, AuctionStatus, Bid, PriceFeed, RoundResult, WinReceipt};
+
+#[derive(Accounts)]
+pub struct BuyNow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = treasury,
+        has_one = usdc_mint,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.top_k_paid == 0 @ AuctionError::TopKSettlementPending,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    /// Pays the rent of the round's result, receipt and price feed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Prize vault, mint, the winner's prize token account and the prize's
+    /// token program; required when the agent has escrowed a prize.
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_state.id_seed().as_slice(), auction_state.prize_mint.as_ref()],
+        bump,
+    )]
+    pub prize_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = auction_state.prize_mint)]
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub winner_prize: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub prize_token_program: Option<Interface<'info, TokenInterface>>,
+    /// The curator's associated token account for the bid mint; required
+    /// when a curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bidder's referrer's associated token account for the bid
+    /// mint; required when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Archive of the round this purchase settles.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RoundResult::INIT_SPACE,
+        seeds = [b"round_result", auction_state.id_seed().as_slice(), &auction_state.next_round_seed()],
+        bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+    /// The winner's proof of this win.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WinReceipt::INIT_SPACE,
+        seeds = [
+            b"win_receipt",
+            auction_state.id_seed().as_slice(),
+            &auction_state.next_round_seed(),
+            bid.bidder.as_ref(),
+        ],
+        bump,
+    )]
+    pub win_receipt: Account<'info, WinReceipt>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Settles the auction to the caller at the buy-it-now price, refunding any
+/// escrow above it and closing bidding immediately.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, BuyNow<'info>>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let bid = &mut ctx.accounts.bid;
+    let clock = Clock::get()?;
+
+    let price = state.buy_now_price;
+    require!(price > 0, AuctionError::BuyNowDisabled);
+    require!(
+        !state.has_ended(clock.unix_timestamp),
+        AuctionError::AuctionEnded
+    );
+    require!(bid.max_amount >= price, AuctionError::BelowBuyNowPrice);
+
+    bid.updated_at = clock.unix_timestamp;
+    state.transition(AuctionStatus::Settled, clock.unix_timestamp)?;
+    state.end_time = clock.unix_timestamp;
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    Payout {
+        auction_state: &mut ctx.accounts.auction_state,
+        escrow: &ctx.accounts.escrow,
+        treasury: &ctx.accounts.treasury,
+        usdc_mint: &ctx.accounts.usdc_mint,
+        token_program: &ctx.accounts.token_program,
+        winner_usdc: Some(&ctx.accounts.bidder_usdc),
+        charity: ctx.accounts.charity.as_ref(),
+        curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+        referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+        memo_program: ctx.accounts.memo_program.as_ref(),
+        accepted_mints: None,
+        hook_accounts: hooks,
+    }
+    .settle(&mut ctx.accounts.bid, price)?;
+
+    let winner = ctx.accounts.bid.bidder;
+    PrizeDelivery {
+        auction_state: &mut ctx.accounts.auction_state,
+        prize_vault: ctx.accounts.prize_vault.as_ref(),
+        prize_mint: ctx.accounts.prize_mint.as_ref(),
+        winner_prize: ctx.accounts.winner_prize.as_ref(),
+        prize_token_program: ctx.accounts.prize_token_program.as_ref(),
+        hook_accounts: hooks,
+    }
+    .deliver(winner)?;
+
+    ctx.accounts.round_result.record(
+        &ctx.accounts.auction_state,
+        winner,
+        price,
+        1,
+        ctx.bumps.round_result,
+    )?;
+    ctx.accounts.win_receipt.issue(
+        &ctx.accounts.auction_state,
+        &ctx.accounts.bid,
+        price,
+        ctx.bumps.win_receipt,
+    )?;
+    ctx.accounts
+        .price_feed
+        .publish(&ctx.accounts.round_result, price, ctx.bumps.price_feed);
+    Ok(())
+}
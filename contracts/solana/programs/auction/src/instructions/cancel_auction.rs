@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::AuctionCancelled;
+use crate::state::{AuctionState, AuctionStatus};
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Calls off an unsettled round for good. Nothing is paid out: bidders
+/// withdraw their full escrow, and the round can no longer be settled or
+/// rescheduled.
+pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.auction_state;
+    state.transition(AuctionStatus::Cancelled, now)?;
+
+    emit!(AuctionCancelled {
+        active_bid_count: state.active_bid_count,
+        total_escrowed: state.total_escrowed,
+    });
+
+    Ok(())
+}
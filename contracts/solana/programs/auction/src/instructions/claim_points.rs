@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::event::PointsClaimed;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct ClaimPoints<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bid.bidder.as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: only used as the authority of `bidder_points`.
+    #[account(address = bid.bidder @ AuctionError::WrongBidder)]
+    pub bidder: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = points_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_points: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        seeds = [b"points_mint", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub points_mint: InterfaceAccount<'info, Mint>,
+    /// The bidder, or a keeper claiming for them before a refund closes the bid.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints the points the bid has earned so far to its bidder. Anyone may
+/// call it, and whoever closes a bid holding points has to, first.
+pub fn handler(ctx: Context<ClaimPoints>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let bid = &mut ctx.accounts.bid;
+    state.accrue_points(bid, Clock::get()?.unix_timestamp)?;
+    let amount = bid.points;
+    require!(amount > 0, AuctionError::NoPoints);
+    bid.points = 0;
+
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.points_mint.to_account_info(),
+                to: ctx.accounts.bidder_points.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    emit!(PointsClaimed {
+        bidder: bid.bidder,
+        bid_index: bid.bid_index,
+        amount,
+    });
+
+    Ok(())
+}
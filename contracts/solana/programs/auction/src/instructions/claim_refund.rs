@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::RefundClaimed;
+use crate::memo::write_memo;
+use crate::merkle;
+use crate::state::{AuctionState, Bid, RefundClaims};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        constraint = auction_state.refund_root != [0; 32] @ AuctionError::RefundRootNotPosted,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [b"refund_claims", auction_state.id_seed().as_slice(), auction_state.refund_root.as_ref()],
+        bump = refund_claims.bump,
+    )]
+    pub refund_claims: Account<'info, RefundClaims>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.mint == auction_state.usdc_mint @ AuctionError::AuctionMintOnly,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// Recreated if the bidder has since closed it.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    /// Pays to recreate `bidder_usdc`, as in `withdraw_bid`.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Pays out the bidder's leaf of the refunds tree and closes their bid. Any
+/// escrow above the leaf amount is released from `total_escrowed` and left
+/// for `sweep_surplus`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRefund<'info>>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, Clock::get()?.unix_timestamp)?;
+    let bidder = ctx.accounts.bidder.key();
+    let bid_index = ctx.accounts.bid.bid_index;
+    let claims = &mut ctx.accounts.refund_claims;
+
+    require!(
+        index < claims.leaf_count && !claims.is_claimed(index),
+        AuctionError::RefundAlreadyClaimed
+    );
+    require!(
+        merkle::verify(&proof, &claims.root, merkle::leaf(index, &bidder, amount)),
+        AuctionError::InvalidMerkleProof
+    );
+    let escrowed = ctx.accounts.bid.escrowed();
+    require!(amount <= escrowed, AuctionError::RefundExceedsEscrow);
+    claims.set_claimed(index);
+
+    let state_bump = ctx.accounts.auction_state.bump;
+
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    write_memo(&ctx.accounts.auction_state, ctx.accounts.memo_program.as_ref())?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    state.debit_escrow(escrowed)?;
+
+    emit!(RefundClaimed {
+        bidder,
+        index,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::SettledFundsClaimed;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct ClaimSettledFunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = treasury,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Any signer: funds can only go to the treasury.
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Releases the vested part of the settled-funds stream to the treasury.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimSettledFunds<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.auction_state.claimable(now);
+    require!(amount > 0, AuctionError::NothingVested);
+
+    let state_bump = ctx.accounts.auction_state.bump;
+
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.vesting_claimed += amount;
+    state.debit_escrow(amount)?;
+
+    emit!(SettledFundsClaimed {
+        amount,
+        remaining: state.unclaimed(),
+    });
+
+    Ok(())
+}
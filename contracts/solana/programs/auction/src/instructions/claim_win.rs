@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::WinClaimed;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct ClaimWin<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.claim_window > 0 @ AuctionError::NoClaimWindow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.active @ AuctionError::BidNotActive,
+    )]
+    pub bid: Account<'info, Bid>,
+    pub bidder: Signer<'info>,
+}
+
+/// The top bidder confirms they still want the win, which the round needs
+/// before it can settle to them.
+pub fn handler(ctx: Context<ClaimWin>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let bid = &ctx.accounts.bid;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        state.end_time != 0 && state.has_ended(now),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        now <= state.claim_deadline(),
+        AuctionError::ClaimWindowClosed
+    );
+    let top = state
+        .leaderboard
+        .entries()
+        .first()
+        .ok_or(AuctionError::WinnersMismatch)?;
+    require!(
+        top.is(bid.bidder, bid.bid_index),
+        AuctionError::WinnersMismatch
+    );
+    state.claimed_bid = bid.key();
+
+    emit!(WinClaimed {
+        bidder: bid.bidder,
+        bid_index: bid.bid_index,
+        amount: bid.amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::AuctionClosed;
+use crate::state::{AuctionState, AuctionStatus};
+
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = auction_state.active_bid_count == 0 @ AuctionError::BidsOutstanding,
+        constraint = auction_state.active_lot_bid_count == 0 @ AuctionError::BidsOutstanding,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Winds up a settled, cancelled or abandoned auction for good once no bid
+/// is left in escrow, reporting the last round's figures in `AuctionClosed`.
+pub fn handler(ctx: Context<CloseAuction>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.auction_state;
+    state.transition(AuctionStatus::Closed, now)?;
+
+    emit!(AuctionClosed {
+        total_bids: state.round_bid_count,
+        bidder_entries: state.round_bidder_count,
+        winning_amount: state.winning_amount,
+        duration: now.saturating_sub(state.round_started_at),
+    });
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
+use super::set_session_key::revoke_session;
 use crate::error::AuctionError;
+use crate::pda;
 use crate::state::Bid;
 
 #[derive(Accounts)]
@@ -8,16 +11,42 @@ pub struct CloseBid<'info> {
     #[account(
         mut,
         close = bidder,
-        seeds = [b"bid", bidder.key().as_ref()],
+        seeds = [
+            b"bid",
+            bid.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
         bump = bid.bump,
         constraint = !bid.active @ AuctionError::BidStillActive,
         constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.points == 0 @ AuctionError::PointsUnclaimed,
     )]
     pub bid: Account<'info, Bid>,
     #[account(mut)]
     pub bidder: Signer<'info>,
+    /// The account the bid's session drew from, whose allowance is revoked;
+    /// required when the bid has a session key.
+    #[account(mut, token::authority = bidder)]
+    pub session_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn handler(_ctx: Context<CloseBid>) -> Result<()> {
-    Ok(())
+/// Closes an inactive bid, returning its rent to the bidder, and revokes the
+/// allowance its session left on `session_usdc`.
+pub fn handler(ctx: Context<CloseBid>) -> Result<()> {
+    if !ctx.accounts.bid.has_session() {
+        return Ok(());
+    }
+    let (Some(session_usdc), Some(token_program)) =
+        (&ctx.accounts.session_usdc, &ctx.accounts.token_program)
+    else {
+        return err!(AuctionError::InvalidTokenAccount);
+    };
+    revoke_session(
+        &pda::find_auction_state(ctx.accounts.bid.auction_id).0,
+        session_usdc,
+        ctx.accounts.bidder.to_account_info(),
+        token_program.to_account_info(),
+    )
 }
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::AllPayBidCollected;
+use crate::state::{AcceptedMints, AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct CollectAllPayBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.all_pay @ AuctionError::AllPayDisabled,
+        constraint = auction_state.is_settled() @ AuctionError::AuctionNotSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: Receives the bid's rent; pinned to `bid.bidder` by the bid constraints.
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+    /// Escrow and treasury for the bid's mint, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = usdc_mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Any signer: an automation thread, a keeper, or the agent.
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+}
+
+/// Permissionless collection of a losing all-pay bid after settlement: its
+/// whole escrow goes to the treasury and only the bid's rent returns to the
+/// bidder. This is the all-pay counterpart of `crank_refund`.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CollectAllPayBid<'info>>) -> Result<()> {
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, Clock::get()?.unix_timestamp)?;
+    let amount = ctx.accounts.bid.escrowed();
+    let bidder = ctx.accounts.bid.bidder;
+    let bid_index = ctx.accounts.bid.bid_index;
+    let mint = ctx.accounts.usdc_mint.key();
+    let accepted = ctx.accounts.accepted_mints.as_deref();
+    let state = &ctx.accounts.auction_state;
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    require_keys_eq!(
+        ctx.accounts.treasury.key(),
+        state.treasury_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    state.debit_escrow_in(ctx.accounts.accepted_mints.as_deref_mut(), &mint, amount)?;
+
+    emit!(AllPayBidCollected { bidder, amount });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::ContentCommitted;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct CommitContent<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Commits the round to a deliverable by the SHA-256 of its URI, while
+/// bidding is still open. The commitment cannot be replaced until the round
+/// closes and is reopened, so `reveal_content` proves the winner gets what
+/// was promised.
+pub fn handler(ctx: Context<CommitContent>, content_hash: [u8; 32]) -> Result<()> {
+    require!(content_hash != [0; 32], AuctionError::InvalidContentHash);
+
+    let state = &mut ctx.accounts.auction_state;
+    require!(
+        !state.has_ended(Clock::get()?.unix_timestamp),
+        AuctionError::AuctionEnded
+    );
+    require!(
+        state.content_hash == [0; 32],
+        AuctionError::ContentAlreadyCommitted
+    );
+    state.content_hash = content_hash;
+
+    emit!(ContentCommitted { content_hash });
+
+    Ok(())
+}
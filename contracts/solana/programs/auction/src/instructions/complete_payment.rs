@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::PaymentCompleted;
+use crate::state::{AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct CompletePayment<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.balance_due > 0 @ AuctionError::NoBalanceDue,
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays the top bid's balance due once bidding has closed, so it can be
+/// settled like a fully escrowed bid.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CompletePayment<'info>>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        state.end_time != 0 && state.has_ended(now),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        now <= state.payment_deadline(),
+        AuctionError::PaymentWindowClosed
+    );
+    let top = state
+        .leaderboard
+        .entries()
+        .first()
+        .ok_or(AuctionError::WinnersMismatch)?;
+    require!(
+        top.is(ctx.accounts.bid.bidder, ctx.accounts.bid.bid_index),
+        AuctionError::WinnersMismatch
+    );
+
+    let amount = ctx.accounts.bid.balance_due;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_usdc.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    ctx.accounts
+        .auction_state
+        .accrue_points(&mut ctx.accounts.bid, now)?;
+    ctx.accounts.bid.balance_due = 0;
+    ctx.accounts.auction_state.credit_escrow(amount)?;
+
+    emit!(PaymentCompleted {
+        bidder: ctx.accounts.bid.bidder,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::{BidRefunded, RebatePaid};
+use crate::memo::write_memo;
+use crate::state::{AcceptedMints, AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct CrankRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.is_settled() @ AuctionError::AuctionNotSettled,
+        constraint = auction_state.refund_root == [0; 32] @ AuctionError::MerkleRefundsActive,
+        constraint = !auction_state.all_pay @ AuctionError::BidsNonRefundable,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !bid.standing @ AuctionError::StandingBid,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: Receives the bid's rent; pinned to `bid.bidder` by the bid constraints.
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+    /// Recreated at the cranker's expense if the bidder closed it, so a
+    /// missing account never blocks the refund.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// Escrow for the bid's mint, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Any signer: an automation thread, a keeper, or a bidder. Pays the rent
+    /// of the bidder's token account if it has to be recreated.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Permissionless refund of a losing bid after settlement, plus its share of
+/// the round's rebate pool. Funds and rent always go back to the bidder,
+/// never to the caller.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankRefund<'info>>) -> Result<()> {
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, Clock::get()?.unix_timestamp)?;
+    let amount = ctx.accounts.bid.escrowed();
+    let bidder = ctx.accounts.bid.bidder;
+    let bid_index = ctx.accounts.bid.bid_index;
+    let mint = ctx.accounts.usdc_mint.key();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        ctx.accounts
+            .auction_state
+            .escrow_for(ctx.accounts.accepted_mints.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let rebate = if mint == ctx.accounts.auction_state.usdc_mint {
+        ctx.accounts.auction_state.rebate_for(amount)
+    } else {
+        0
+    };
+    let state_bump = ctx.accounts.auction_state.bump;
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    write_memo(&ctx.accounts.auction_state, ctx.accounts.memo_program.as_ref())?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount
+            .checked_add(rebate)
+            .ok_or(AuctionError::ArithmeticOverflow)?,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    state.debit_escrow_in(ctx.accounts.accepted_mints.as_deref_mut(), &mint, amount)?;
+
+    emit!(BidRefunded { bidder, amount });
+
+    if rebate > 0 {
+        state.debit_escrow(rebate)?;
+        state.rebate_pool -= rebate;
+        emit!(RebatePaid {
+            bidder,
+            amount: rebate,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use super::settle::{split_rivals, verify_winner};
+use crate::error::AuctionError;
+use crate::payout::Payout;
+use crate::prize::PrizeDelivery;
+use crate::state::{
+    AcceptedMints, AuctionState, AuctionStatus, Bid, PriceFeed, RoundResult, WinReceipt,
+};
+
+#[derive(Accounts)]
+pub struct CrankSettle<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.top_k_paid == 0 @ AuctionError::TopKSettlementPending,
+        constraint = !auction_state.is_abandoned() @ AuctionError::AuctionAbandoned,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            winning_bid.bidder.as_ref(),
+            winning_bid.index_seed().as_slice(),
+        ],
+        bump = winning_bid.bump,
+        constraint = winning_bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.claim_pending(&winning_bid.key()) @ AuctionError::WinNotClaimed,
+    )]
+    pub winning_bid: Account<'info, Bid>,
+    /// Escrow, treasury and mint of the winning bid; checked by `Payout`.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = usdc_mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Any signer: an automation thread, a keeper, or a bidder.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = winning_bid.bidder,
+    )]
+    pub winner_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Prize vault, mint, the winner's prize token account and the prize's
+    /// token program; required when the agent has escrowed a prize.
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_state.id_seed().as_slice(), auction_state.prize_mint.as_ref()],
+        bump,
+    )]
+    pub prize_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = auction_state.prize_mint)]
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub winner_prize: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub prize_token_program: Option<Interface<'info, TokenInterface>>,
+    /// Required when the winning bid is in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// The curator's associated token account for the bid mint; required
+    /// when a curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bidder's referrer's associated token account for the bid
+    /// mint; required when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// This round's archive entry; the cranker pays its rent.
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + RoundResult::INIT_SPACE,
+        seeds = [b"round_result", auction_state.id_seed().as_slice(), &auction_state.next_round_seed()],
+        bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+    /// The winner's proof of this win; the cranker pays its rent.
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + WinReceipt::INIT_SPACE,
+        seeds = [
+            b"win_receipt",
+            auction_state.id_seed().as_slice(),
+            &auction_state.next_round_seed(),
+            winning_bid.bidder.as_ref(),
+        ],
+        bump,
+    )]
+    pub win_receipt: Account<'info, WinReceipt>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Permissionless settlement once the deadline passes. The winner is not
+/// chosen by the caller: it must be the top of the on-chain leaderboard, so a
+/// scheduler can fire this at `end_time` without trusting an off-chain bot.
+/// The leaderboard only holds the top `MAX_WINNERS` bids and is not refilled
+/// as bids leave, so `remaining_accounts` starts with every other active bid,
+/// sorted by address, as in `settle`. A drawn raffle has already picked its
+/// winner and takes no rivals.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankSettle<'info>>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let clock = Clock::get()?;
+
+    require!(
+        state.end_time != 0 && state.has_ended(clock.unix_timestamp),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        !state.raffle_mode || state.raffle_drawn,
+        AuctionError::RaffleNotDrawn
+    );
+    require!(!state.candle_pending(), AuctionError::CandleNotDetermined);
+    let top = state
+        .leaderboard
+        .entries()
+        .first()
+        .ok_or(AuctionError::WinnersMismatch)?;
+    require!(
+        top.is(ctx.accounts.winning_bid.bidder, ctx.accounts.winning_bid.bid_index),
+        AuctionError::WinnersMismatch
+    );
+
+    state.transition(AuctionStatus::Settled, clock.unix_timestamp)?;
+
+    let price = ctx.accounts.winning_bid.amount;
+    let (rivals, extra) = split_rivals(&ctx.accounts.auction_state, ctx.remaining_accounts)?;
+    verify_winner(&ctx.accounts.auction_state, &ctx.accounts.winning_bid, rivals)?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(extra);
+    Payout {
+        auction_state: &mut ctx.accounts.auction_state,
+        escrow: &ctx.accounts.escrow,
+        treasury: &ctx.accounts.treasury,
+        usdc_mint: &ctx.accounts.usdc_mint,
+        token_program: &ctx.accounts.token_program,
+        winner_usdc: ctx.accounts.winner_usdc.as_ref(),
+        charity: ctx.accounts.charity.as_ref(),
+        curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+        referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+        memo_program: ctx.accounts.memo_program.as_ref(),
+        accepted_mints: ctx.accounts.accepted_mints.as_deref_mut(),
+        hook_accounts: hooks,
+    }
+    .settle(&mut ctx.accounts.winning_bid, price)?;
+
+    let winner = ctx.accounts.winning_bid.bidder;
+    PrizeDelivery {
+        auction_state: &mut ctx.accounts.auction_state,
+        prize_vault: ctx.accounts.prize_vault.as_ref(),
+        prize_mint: ctx.accounts.prize_mint.as_ref(),
+        winner_prize: ctx.accounts.winner_prize.as_ref(),
+        prize_token_program: ctx.accounts.prize_token_program.as_ref(),
+        hook_accounts: hooks,
+    }
+    .deliver(winner)?;
+
+    ctx.accounts.round_result.record(
+        &ctx.accounts.auction_state,
+        winner,
+        price,
+        1,
+        ctx.bumps.round_result,
+    )?;
+    ctx.accounts.win_receipt.issue(
+        &ctx.accounts.auction_state,
+        &ctx.accounts.winning_bid,
+        price,
+        ctx.bumps.win_receipt,
+    )?;
+    ctx.accounts
+        .price_feed
+        .publish(&ctx.accounts.round_result, price, ctx.bumps.price_feed);
+    Ok(())
+}
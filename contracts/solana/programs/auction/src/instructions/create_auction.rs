@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use super::initialize::open_auction;
+use crate::error::AuctionError;
+use crate::event::{AuctionCreated, AuctionScheduled};
+use crate::state::{AuctionConfig, AuctionState, Operator, RegistryEntry, RegistryPage};
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CreateAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"operator"],
+        bump = operator.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub operator: Account<'info, Operator>,
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + AuctionState::INIT_SPACE,
+        seeds = [b"auction_state", auction_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = usdc_mint, token::authority = agent)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = agent,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        seeds = [b"escrow", auction_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// Registry page the new auction is listed on, opened by its first entry.
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + RegistryPage::INIT_SPACE,
+        seeds = [b"registry", RegistryPage::index_for(operator.auction_count).to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens an auction with its own state and escrow, seeded by `auction_id`,
+/// and lists it in the registry.
+/// Ids are the agent's to choose; zero belongs to the `initialize` auction,
+/// and an id already in use fails on the existing state account.
+pub fn handler(ctx: Context<CreateAuction>, auction_id: u64, config: AuctionConfig) -> Result<()> {
+    require!(auction_id != 0, AuctionError::InvalidAuctionId);
+
+    let accounts = ctx.accounts;
+    let state = &mut accounts.auction_state;
+    open_auction(
+        state,
+        accounts.agent.key(),
+        &accounts.usdc_mint,
+        accounts.treasury.key(),
+        config,
+    )?;
+    state.auction_id = auction_id;
+    state.escrow_bump = ctx.bumps.escrow;
+    state.escrow = accounts.escrow.key();
+    state.bump = ctx.bumps.auction_state;
+
+    let operator = &mut accounts.operator;
+    let page = &mut accounts.registry_page;
+    page.index = RegistryPage::index_for(operator.auction_count);
+    page.bump = ctx.bumps.registry_page;
+    page.entries.push(RegistryEntry {
+        auction_id,
+        status: state.status,
+        item_metadata: config.item_metadata,
+    });
+    operator.auction_count = operator
+        .auction_count
+        .checked_add(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+
+    emit!(AuctionCreated {
+        auction_id,
+        auction_state: state.key(),
+        agent: state.agent,
+        usdc_mint: state.usdc_mint,
+    });
+    emit!(AuctionScheduled {
+        auction_id,
+        start_time: state.start_time,
+    });
+    Ok(())
+}
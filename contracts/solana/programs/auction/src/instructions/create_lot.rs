@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::LotCreated;
+use crate::state::{AuctionState, Lot};
+
+#[derive(Accounts)]
+pub struct CreateLot<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Lot::INIT_SPACE,
+        seeds = [b"lot", auction_state.id_seed().as_slice(), &auction_state.next_lot_seed()],
+        bump,
+    )]
+    pub lot: Account<'info, Lot>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds the next lot to the round while it still takes bids. Bids on the lot
+/// must reach `minimum_bid`, and it only sells at `reserve_price` or more.
+pub fn handler(
+    ctx: Context<CreateLot>,
+    minimum_bid: u64,
+    reserve_price: u64,
+    item_hash: [u8; 32],
+) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    require!(
+        !state.has_ended(Clock::get()?.unix_timestamp),
+        AuctionError::AuctionEnded
+    );
+    state.require_accepting_bids()?;
+    state.lot_count = state
+        .lot_count
+        .checked_add(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+
+    let lot = &mut ctx.accounts.lot;
+    lot.auction_id = state.auction_id;
+    lot.lot_id = state.lot_count;
+    lot.minimum_bid = minimum_bid;
+    lot.reserve_price = reserve_price;
+    lot.item_hash = item_hash;
+    lot.bump = ctx.bumps.lot;
+
+    emit!(LotCreated {
+        lot_id: lot.lot_id,
+        minimum_bid,
+        reserve_price,
+        item_hash,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct CreatePointsMint<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = agent,
+        mint::decimals = 0,
+        mint::authority = auction_state,
+        mint::token_program = token_program,
+        seeds = [b"points_mint", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub points_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the loyalty points mint, whose only minter is the auction state.
+/// Bids earn nothing until `set_points_rate` prices escrow in points.
+pub fn handler(_ctx: Context<CreatePointsMint>) -> Result<()> {
+    Ok(())
+}
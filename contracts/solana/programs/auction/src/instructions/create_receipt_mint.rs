@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct CreateReceiptMint<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        has_one = usdc_mint,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mint::token_program = token_program)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = agent,
+        mint::decimals = 0,
+        mint::authority = auction_state,
+        mint::token_program = token_program,
+        seeds = [b"receipt_mint", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    /// Owner of the auction mint; receipts live under the same program.
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the receipt mint, letting bidders ask for a transferable token
+/// standing for each bid they place. Receipts only gate `withdraw_bid`: bids
+/// that are refunded or win at settlement leave theirs outstanding.
+pub fn handler(_ctx: Context<CreateReceiptMint>) -> Result<()> {
+    Ok(())
+}
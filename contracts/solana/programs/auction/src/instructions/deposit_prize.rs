@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::PrizeDeposited;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+
+#[derive(Accounts)]
+pub struct DepositPrize<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.prize_amount == 0 @ AuctionError::PrizeAlreadyDeposited,
+        constraint = auction_state.active_bid_count == 0 @ AuctionError::PrizeLocked,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = prize_mint.key() != auction_state.usdc_mint @ AuctionError::InvalidPrizeMint,
+    )]
+    pub prize_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        token::mint = prize_mint,
+        token::authority = auction_state,
+        token::token_program = token_program,
+        seeds = [b"prize_vault", auction_state.id_seed().as_slice(), prize_mint.key().as_ref()],
+        bump,
+    )]
+    pub prize_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = prize_mint, token::authority = agent)]
+    pub agent_prize: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    /// Owner of `prize_mint`, which need not be the bid mint's program.
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows the prize before anyone bids, so bidders can check it on-chain.
+/// The next single-winner settlement swaps it for the winning bid.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositPrize<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, AuctionError::InvalidPrizeAmount);
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.agent_prize.to_account_info(),
+                to: ctx.accounts.prize_vault.to_account_info(),
+                authority: ctx.accounts.agent.to_account_info(),
+                mint: ctx.accounts.prize_mint.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.prize_mint.decimals,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.prize_mint = ctx.accounts.prize_mint.key();
+    state.prize_amount = amount;
+
+    emit!(PrizeDeposited {
+        mint: state.prize_mint,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::CandleEndDetermined;
+use crate::randomness;
+use crate::state::{AuctionState, Bid, LeaderboardEntry};
+
+#[derive(Accounts)]
+pub struct DetermineCandleEnd<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.candle_window > 0 @ AuctionError::CandleModeDisabled,
+        constraint = auction_state.candle_end == 0 @ AuctionError::CandleAlreadyDetermined,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Must be the committed randomness account; checked and parsed in `randomness::reveal`.
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+/// Permissionless reveal of the candle end, a uniform draw from the last
+/// `candle_window` seconds of the round. `remaining_accounts` must list every
+/// active bid exactly once, sorted by account key. The highest bid last
+/// placed or updated by the candle end becomes the sole leaderboard entry
+/// (earliest first on ties), so `crank_settle` pays it and `crank_refund`
+/// returns the rest. If none qualifies the round has no winner and can only
+/// be cancelled.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DetermineCandleEnd<'info>>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+
+    require!(
+        ctx.remaining_accounts.len() as u64 == state.active_bid_count,
+        AuctionError::CandleEntrantsMismatch
+    );
+
+    let span = u64::try_from(state.candle_window).map_err(|_| AuctionError::InvalidCandleWindow)?;
+    let draw = randomness::reveal(state, &ctx.accounts.randomness_account)? % (span + 1);
+    let candle_end = state.end_time - state.candle_window + draw as i64;
+    state.candle_end = candle_end;
+
+    let mut winner: Option<LeaderboardEntry> = None;
+    let mut previous_key: Option<Pubkey> = None;
+    for info in ctx.remaining_accounts.iter() {
+        require!(
+            previous_key.is_none_or(|key| info.key() > key),
+            AuctionError::CandleEntrantsMismatch
+        );
+        previous_key = Some(info.key());
+
+        let bid = Account::<Bid>::try_from(info)?;
+        require!(bid.active, AuctionError::BidNotActive);
+        require!(
+            bid.auction_id == state.auction_id,
+            AuctionError::CandleEntrantsMismatch
+        );
+        if !state.counts_at_candle(&bid) {
+            continue;
+        }
+        if winner.is_none_or(|leader| bid.rank().outranks(&leader)) {
+            winner = Some(bid.rank());
+        }
+    }
+
+    let (winner, bid_index, amount) = match winner {
+        Some(entry) => {
+            state.leaderboard.reset_to(entry);
+            (entry.bidder, entry.bid_index, entry.amount)
+        }
+        None => {
+            state.leaderboard = Default::default();
+            (Pubkey::default(), 0, 0)
+        }
+    };
+    state.set_leader(winner, bid_index);
+    state.leading_amount = amount;
+
+    emit!(CandleEndDetermined {
+        randomness_account: ctx.accounts.randomness_account.key(),
+        candle_end,
+        winner,
+        amount,
+    });
+
+    Ok(())
+}
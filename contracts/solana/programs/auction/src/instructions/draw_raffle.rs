@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::RaffleDrawn;
+use crate::randomness;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.raffle_mode @ AuctionError::RaffleModeDisabled,
+        constraint = !auction_state.raffle_drawn @ AuctionError::RaffleAlreadyDrawn,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Must be the committed randomness account; checked and parsed in `randomness::reveal`.
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+/// Permissionless draw weighted by escrowed amount. `remaining_accounts` must
+/// list every active bid exactly once, sorted by account key. The winner
+/// becomes the sole leaderboard entry so `crank_settle` pays them out and
+/// `crank_refund` returns everyone else's escrow in full.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DrawRaffle<'info>>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+
+    require!(
+        ctx.remaining_accounts.len() as u64 == state.active_bid_count,
+        AuctionError::RaffleEntrantsMismatch
+    );
+
+    let mut entrants = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut total_weight: u64 = 0;
+    let mut previous_key: Option<Pubkey> = None;
+    for info in ctx.remaining_accounts.iter() {
+        require!(
+            previous_key.is_none_or(|key| info.key() > key),
+            AuctionError::RaffleEntrantsMismatch
+        );
+        previous_key = Some(info.key());
+
+        let bid = Account::<Bid>::try_from(info)?;
+        require!(bid.active, AuctionError::BidNotActive);
+        require!(
+            bid.auction_id == state.auction_id,
+            AuctionError::RaffleEntrantsMismatch
+        );
+        total_weight = total_weight
+            .checked_add(bid.max_amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        entrants.push((bid.rank(), bid.max_amount));
+    }
+    require!(total_weight > 0, AuctionError::RaffleEntrantsMismatch);
+
+    let draw = randomness::reveal(state, &ctx.accounts.randomness_account)? % total_weight;
+
+    let mut cumulative = 0;
+    let (winner, winner_weight) = entrants
+        .into_iter()
+        .find(|(_, weight)| {
+            cumulative += weight;
+            draw < cumulative
+        })
+        .ok_or(AuctionError::RaffleEntrantsMismatch)?;
+
+    state.leaderboard.reset_to(winner);
+    state.set_leader(winner.bidder, winner.bid_index);
+    state.leading_amount = winner.amount;
+    state.raffle_drawn = true;
+
+    emit!(RaffleDrawn {
+        randomness_account: ctx.accounts.randomness_account.key(),
+        winner: winner.bidder,
+        winner_weight,
+        total_weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::BidExpired;
+use crate::state::{AcceptedMints, AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct ExpireBid<'info> {
+    #[account(mut, seeds = [b"auction_state", auction_state.id_seed().as_slice()], bump = auction_state.bump)]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.foreign_chain == 0 @ AuctionError::ForeignBid,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: Receives the bid's rent; pinned to `bid.bidder` by the bid constraints.
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+    /// Recreated at the cranker's expense if the bidder closed it.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// Escrow for the bid's mint, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless refund of a bid that outlived `bid_ttl`. The bidder gets
+/// the whole escrow back with no penalty, and their rent and bond with it.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireBid<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.auction_state.require_refundable()?;
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, clock.unix_timestamp)?;
+    require!(
+        ctx.accounts
+            .auction_state
+            .is_expired(&ctx.accounts.bid, clock.unix_timestamp),
+        AuctionError::BidNotExpired
+    );
+    let amount = ctx.accounts.bid.escrowed();
+    let bidder = ctx.accounts.bid.bidder;
+    let bid_index = ctx.accounts.bid.bid_index;
+    let mint = ctx.accounts.usdc_mint.key();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        ctx.accounts
+            .auction_state
+            .escrow_for(ctx.accounts.accepted_mints.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let state_bump = ctx.accounts.auction_state.bump;
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    state.debit_escrow_in(ctx.accounts.accepted_mints.as_deref_mut(), &mint, amount)?;
+
+    emit!(BidExpired {
+        bidder,
+        amount,
+        updated_at: ctx.accounts.bid.updated_at,
+    });
+
+    Ok(())
+}
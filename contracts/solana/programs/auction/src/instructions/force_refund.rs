@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::BidForceRefunded;
+use crate::ranking;
+use crate::state::{AcceptedMints, AuctionState, Bid, Denylist};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct ForceRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.foreign_chain == 0 @ AuctionError::ForeignBid,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        seeds = [b"denylist", auction_state.id_seed().as_slice()],
+        bump = denylist.bump,
+        constraint = denylist.contains(&bid.bidder) @ AuctionError::BidderNotDenylisted,
+    )]
+    pub denylist: Account<'info, Denylist>,
+    /// CHECK: Receives the bid's rent; pinned to `bid.bidder` by the bid constraints.
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+    /// Recreated at the agent's expense if the bidder closed it.
+    #[account(
+        init_if_needed,
+        payer = agent,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// Escrow for the bid's mint, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// The bid's neighbours in the ranked list, when it is ranked.
+    #[account(mut)]
+    pub prev_bid: Option<Account<'info, Bid>>,
+    #[account(mut)]
+    pub next_bid: Option<Account<'info, Bid>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Returns a denylisted bidder's escrow in full and closes their bid. No
+/// penalty is taken and nothing reaches the treasury, even in all-pay mode.
+/// A receipt minted for the bid stays with its holder but no longer
+/// redeems anything.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ForceRefund<'info>>) -> Result<()> {
+    let accounts = ctx.accounts;
+    accounts
+        .auction_state
+        .require_points_claimed(&mut accounts.bid, Clock::get()?.unix_timestamp)?;
+    let amount = accounts.bid.escrowed();
+    let bidder = accounts.bid.bidder;
+    let bid_index = accounts.bid.bid_index;
+    let mint = accounts.usdc_mint.key();
+    require_keys_eq!(
+        accounts.escrow.key(),
+        accounts
+            .auction_state
+            .escrow_for(accounts.accepted_mints.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    if accounts.auction_state.is_ranked(&accounts.bid) {
+        ranking::unlink(
+            &mut accounts.auction_state,
+            &mut accounts.bid,
+            accounts.prev_bid.as_mut(),
+            accounts.next_bid.as_mut(),
+        )?;
+    }
+
+    let state_bump = accounts.auction_state.bump;
+    let id_seed = accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+    let hooks = accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.escrow.to_account_info(),
+                to: accounts.bidder_usdc.to_account_info(),
+                authority: accounts.auction_state.to_account_info(),
+                mint: accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    if mint == state.usdc_mint {
+        state.debit_escrow(amount)?;
+    } else {
+        state.debit_escrow_in(accounts.accepted_mints.as_deref_mut(), &mint, amount)?;
+    }
+
+    emit!(BidForceRefunded {
+        bidder,
+        bid_index,
+        amount,
+    });
+    Ok(())
+}
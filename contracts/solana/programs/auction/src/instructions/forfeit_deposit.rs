@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::forfeiture::Forfeiture;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct ForfeitDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+        has_one = treasury,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bid.bidder.as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.balance_due > 0 @ AuctionError::NoBalanceDue,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::AuctionMintOnly,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: only used as the authority of `bidder_usdc`.
+    #[account(address = bid.bidder @ AuctionError::WrongBidder)]
+    pub bidder: UncheckedAccount<'info>,
+    /// The defaulting bidder's associated token account; created if needed.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Any signer: an automation thread, a keeper, or the next bidder.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Once the payment window lapses, the top bidder's deposit is forfeited,
+/// less whatever the default penalty leaves them, and the next bidder on the
+/// leaderboard gets a fresh window to pay.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ForfeitDeposit<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &ctx.accounts.auction_state;
+    require!(
+        state.end_time != 0 && state.has_ended(now),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        now > state.payment_deadline(),
+        AuctionError::PaymentWindowOpen
+    );
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    Forfeiture {
+        auction_state: &mut ctx.accounts.auction_state,
+        escrow: &ctx.accounts.escrow,
+        treasury: &ctx.accounts.treasury,
+        bidder_usdc: &ctx.accounts.bidder_usdc,
+        usdc_mint: &ctx.accounts.usdc_mint,
+        token_program: &ctx.accounts.token_program,
+        hook_accounts: hooks,
+    }
+    .apply(&mut ctx.accounts.bid)?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.payment_deadline = now.saturating_add(state.payment_window);
+    Ok(())
+}
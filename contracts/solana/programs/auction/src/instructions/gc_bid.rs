@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BidAccountClosed;
+use crate::state::Bid;
+
+#[derive(Accounts)]
+pub struct GcBid<'info> {
+    /// Anyone; the rent goes to each bid's bidder, not here.
+    pub cranker: Signer<'info>,
+}
+
+/// Closes inactive bid accounts their bidders left open, in any auction.
+/// `remaining_accounts` holds `(bid, bidder)` pairs, both writable; each
+/// bid's rent and any bond left in it go to the wallet that placed it.
+/// Foreign bids are refused, as their bidder is no Solana wallet, and so are
+/// bids holding points, which `claim_points` must mint first.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GcBid<'info>>) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2),
+        AuctionError::BidPairsMismatch
+    );
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let bid = Account::<Bid>::try_from(&pair[0])?;
+        let bidder = &pair[1];
+        require!(!bid.active, AuctionError::BidStillActive);
+        require!(bid.foreign_chain == 0, AuctionError::ForeignBid);
+        require!(bid.points == 0, AuctionError::PointsUnclaimed);
+        require_keys_eq!(bid.bidder, bidder.key(), AuctionError::WrongBidder);
+
+        emit!(BidAccountClosed {
+            auction_id: bid.auction_id,
+            bidder: bid.bidder,
+            bid_index: bid.bid_index,
+            lamports: bid.to_account_info().lamports(),
+        });
+        bid.close(bidder.clone())?;
+    }
+    Ok(())
+}
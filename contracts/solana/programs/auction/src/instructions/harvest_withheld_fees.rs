@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{
+    TransferFeeAmount, TransferFeeConfig,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenState;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::transfer_fee::{
+    self, HarvestWithheldTokensToMint, WithdrawWithheldTokensFromMint,
+};
+use anchor_spl::token_interface::{get_mint_extension_data, Mint, TokenAccount};
+
+use crate::error::AuctionError;
+use crate::event::WithheldFeesHarvested;
+use crate::state::{AcceptedMints, AuctionState};
+
+#[derive(Accounts)]
+pub struct HarvestWithheldFees<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// Escrow for `mint`, checked in the handler.
+    #[account(mut, token::mint = mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub agent: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    /// Required for the escrow of a mint other than the auction mint.
+    #[account(seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// Treasury for `mint`; receives the fees when the auction is the mint's
+    /// withdraw-withheld authority.
+    #[account(mut, token::mint = mint)]
+    pub treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Moves transfer fees withheld in an escrow into its Token-2022 mint, so the
+/// escrow only holds bidder funds. If the auction is the mint's
+/// withdraw-withheld authority and a treasury is given, the fees are then
+/// withdrawn to it; otherwise they wait in the mint for its fee authority.
+pub fn handler(ctx: Context<HarvestWithheldFees>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let accepted = ctx.accounts.accepted_mints.as_deref();
+    let mint = ctx.accounts.mint.key();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+
+    let amount = withheld(&ctx.accounts.escrow.to_account_info())?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    transfer_fee::harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        vec![ctx.accounts.escrow.to_account_info()],
+    )?;
+
+    let config =
+        get_mint_extension_data::<TransferFeeConfig>(&ctx.accounts.mint.to_account_info())?;
+    let authority: Option<Pubkey> = config.withdraw_withheld_authority.into();
+    let withdrawn = match &ctx.accounts.treasury {
+        Some(treasury) if authority == Some(state.key()) => {
+            require_keys_eq!(
+                treasury.key(),
+                state.treasury_for(accepted, &mint)?,
+                AuctionError::InvalidTokenAccount
+            );
+            let id_seed = state.id_seed();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+            transfer_fee::withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                WithdrawWithheldTokensFromMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    destination: treasury.to_account_info(),
+                    authority: state.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+            true
+        }
+        _ => false,
+    };
+
+    emit!(WithheldFeesHarvested {
+        mint,
+        amount,
+        withdrawn,
+    });
+
+    Ok(())
+}
+
+/// Transfer fees withheld in a token account; zero without the extension.
+fn withheld(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenState>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |fee| u64::from(fee.withheld_amount)))
+}
@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::error::AuctionError;
-use crate::state::AuctionState;
+use crate::mint_safety;
+use crate::state::{AuctionConfig, AuctionState};
 use crate::USDC_DECIMALS;
 
 #[derive(Accounts)]
@@ -33,19 +34,53 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Initialize>, minimum_bid: u64) -> Result<()> {
+/// Sets up auction zero, whose PDAs carry no id seed. Further auctions come
+/// from `create_auction`.
+///
+/// `mint_overrides` holds the `mint_safety::ALLOW_*` flags for risky
+/// Token-2022 extensions the agent accepts on the auction mint.
+pub fn handler(ctx: Context<Initialize>, minimum_bid: u64, mint_overrides: u8) -> Result<()> {
+    let accounts = ctx.accounts;
+    let state = &mut accounts.auction_state;
+    open_auction(
+        state,
+        accounts.agent.key(),
+        &accounts.usdc_mint,
+        accounts.treasury.key(),
+        AuctionConfig {
+            minimum_bid,
+            mint_overrides,
+            ..AuctionConfig::default()
+        },
+    )?;
+    state.escrow_bump = ctx.bumps.escrow;
+    state.escrow = accounts.escrow.key();
+    state.bump = ctx.bumps.auction_state;
+    Ok(())
+}
+
+/// Checks the auction mint and fills in a freshly created state; the caller
+/// records the id, escrow and bumps.
+pub(crate) fn open_auction(
+    state: &mut AuctionState,
+    agent: Pubkey,
+    usdc_mint: &InterfaceAccount<Mint>,
+    treasury: Pubkey,
+    config: AuctionConfig,
+) -> Result<()> {
     require!(
-        ctx.accounts.usdc_mint.decimals == USDC_DECIMALS,
+        usdc_mint.decimals == USDC_DECIMALS,
         AuctionError::InvalidMintDecimals
     );
+    mint_safety::validate(&usdc_mint.to_account_info(), config.mint_overrides)?;
 
-    let state = &mut ctx.accounts.auction_state;
-    state.agent = ctx.accounts.agent.key();
-    state.usdc_mint = ctx.accounts.usdc_mint.key();
-    state.treasury = ctx.accounts.treasury.key();
-    state.escrow_bump = ctx.bumps.escrow;
-    state.minimum_bid = minimum_bid;
+    state.agent = agent;
+    state.usdc_mint = usdc_mint.key();
+    state.treasury = treasury;
+    state.minimum_bid = config.minimum_bid;
+    state.start_time = config.start_time;
     state.active_bid_count = 0;
-    state.bump = ctx.bumps.auction_state;
+    state.num_winners = 1;
+    state.start_round(Clock::get()?.unix_timestamp);
     Ok(())
 }
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::{AuctionState, AuditLog};
+
+#[derive(Accounts)]
+pub struct InitializeAuditLog<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + std::mem::size_of::<AuditLog>(),
+        seeds = [b"audit_log", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the auction's audit log. From then on agent, minimum-bid and
+/// treasury changes fail with `AuditLogRequired` unless they pass the log to
+/// append to.
+pub fn handler(ctx: Context<InitializeAuditLog>) -> Result<()> {
+    let mut audit_log = ctx.accounts.audit_log.load_init()?;
+    audit_log.auction_id = ctx.accounts.auction_state.auction_id;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.auction_state.audit_log_enabled = true;
+    Ok(())
+}
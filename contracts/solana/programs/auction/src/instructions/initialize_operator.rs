@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Operator;
+
+#[derive(Accounts)]
+pub struct InitializeOperator<'info> {
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Operator::INIT_SPACE,
+        seeds = [b"operator"],
+        bump,
+    )]
+    pub operator: Account<'info, Operator>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Makes the signer the agent who may open auctions with `create_auction`.
+pub fn handler(ctx: Context<InitializeOperator>) -> Result<()> {
+    let operator = &mut ctx.accounts.operator;
+    operator.agent = ctx.accounts.agent.key();
+    operator.auction_count = 0;
+    operator.bump = ctx.bumps.operator;
+    Ok(())
+}
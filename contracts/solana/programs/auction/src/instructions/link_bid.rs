@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BidLinked;
+use crate::ranking;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct LinkBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        constraint = bid.auction_id == auction_state.auction_id @ AuctionError::WrongAuction,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// The ranked bid to insert after; omit to insert at the head.
+    #[account(mut)]
+    pub prev_bid: Option<Account<'info, Bid>>,
+    /// The ranked bid currently following `prev_bid`; omit at the tail.
+    #[account(mut)]
+    pub next_bid: Option<Account<'info, Bid>>,
+}
+
+/// Inserts an active bid into the auction's ranked list at the position the
+/// caller names. Permissionless: ranking a bid only pins its place, and the
+/// program rejects any position that would break the descending order.
+pub fn handler(ctx: Context<LinkBid>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let key = accounts.bid.key();
+    ranking::link(
+        &mut accounts.auction_state,
+        key,
+        &mut accounts.bid,
+        accounts.prev_bid.as_mut(),
+        accounts.next_bid.as_mut(),
+    )?;
+    emit!(BidLinked {
+        bidder: accounts.bid.bidder,
+        bid_index: accounts.bid.bid_index,
+        amount: accounts.bid.amount,
+        prev_bid: accounts.bid.prev_bid,
+        next_bid: accounts.bid.next_bid,
+    });
+    Ok(())
+}
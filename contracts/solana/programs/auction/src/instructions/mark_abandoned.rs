@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::AuctionAbandoned;
+use crate::state::{AuctionState, AuctionStatus};
+
+#[derive(Accounts)]
+pub struct MarkAbandoned<'info> {
+    #[account(mut, seeds = [b"auction_state", auction_state.id_seed().as_slice()], bump = auction_state.bump)]
+    pub auction_state: Account<'info, AuctionState>,
+    pub cranker: Signer<'info>,
+}
+
+/// Permissionless fallback for an agent that stops settling: once the round
+/// is `abandonment_window` past its deadline, anyone may close it for good.
+/// Settlement and rescheduling are refused from then on, so bidders can
+/// withdraw their full escrow without racing a late settle.
+pub fn handler(ctx: Context<MarkAbandoned>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.auction_state;
+    require!(state.is_abandonable(now), AuctionError::NotAbandonable);
+    state.transition(AuctionStatus::Abandoned, now)?;
+
+    emit!(AuctionAbandoned {
+        end_time: state.end_time,
+        active_bid_count: state.active_bid_count,
+        marked_by: ctx.accounts.cranker.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::EscrowMigrated;
+use crate::mint_safety;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        has_one = escrow,
+        has_one = usdc_mint,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// The auction mint from now on; the current one to only rotate the
+    /// escrow account.
+    #[account(mint::token_program = new_token_program)]
+    pub new_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = agent,
+        token::mint = new_mint,
+        token::authority = auction_state,
+        token::token_program = new_token_program,
+        seeds = [b"escrow", auction_state.id_seed().as_slice(), new_mint.key().as_ref(), &auction_state.next_escrow_seed()],
+        bump,
+    )]
+    pub new_escrow: InterfaceAccount<'info, TokenAccount>,
+    /// Replaces the treasury, which must hold the new mint.
+    #[account(token::mint = new_mint)]
+    pub new_treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub new_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves the auction onto a new escrow account. With the same mint the whole
+/// balance follows, bids included. A different mint, such as USDC reissued
+/// under Token-2022, cannot take the old tokens, so the old escrow must
+/// first be emptied by settling or refunding every bid and sweeping any
+/// surplus. `mint_overrides` are the `mint_safety::ALLOW_*` flags for the new
+/// mint. Transfer-hook accounts, when enabled, go in `remaining_accounts`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateEscrow<'info>>,
+    mint_overrides: u8,
+) -> Result<()> {
+    let new_mint = &ctx.accounts.new_mint;
+    let same_mint = new_mint.key() == ctx.accounts.usdc_mint.key();
+    let amount = ctx.accounts.escrow.amount;
+    if !same_mint {
+        require!(
+            new_mint.decimals == USDC_DECIMALS,
+            AuctionError::InvalidMintDecimals
+        );
+        mint_safety::validate(&new_mint.to_account_info(), mint_overrides)?;
+        require!(
+            amount == 0 && ctx.accounts.auction_state.total_escrowed == 0,
+            AuctionError::EscrowNotEmpty
+        );
+    }
+
+    if amount > 0 {
+        let id_seed = ctx.accounts.auction_state.id_seed();
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[b"auction_state", &id_seed, &[ctx.accounts.auction_state.bump]]];
+        let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.new_escrow.to_account_info(),
+                    authority: ctx.accounts.auction_state.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            USDC_DECIMALS,
+            hooks,
+        )?;
+    }
+
+    let state = &mut ctx.accounts.auction_state;
+    emit!(EscrowMigrated {
+        auction_id: state.auction_id,
+        old_escrow: state.escrow,
+        new_escrow: ctx.accounts.new_escrow.key(),
+        old_mint: state.usdc_mint,
+        new_mint: new_mint.key(),
+        amount,
+    });
+    state.escrow = ctx.accounts.new_escrow.key();
+    state.escrow_bump = ctx.bumps.new_escrow;
+    state.escrow_generation = state.escrow_generation.saturating_add(1);
+    state.usdc_mint = new_mint.key();
+    state.treasury = ctx.accounts.new_treasury.key();
+    Ok(())
+}
@@ -6,6 +6,104 @@ pub mod settle;
 pub mod close_bid;
 pub mod set_minimum_bid;
 pub mod set_agent;
+pub mod set_end_time;
+pub mod set_withdrawal_penalty;
+pub mod sweep_penalties;
+pub mod set_bid_increment;
+pub mod set_buy_now_price;
+pub mod buy_now;
+pub mod set_num_winners;
+pub mod settle_multi;
+pub mod set_uniform_price;
+pub mod set_charity;
+pub mod set_treasury;
+pub mod assert_solvent;
+pub mod sweep_surplus;
+pub mod crank_settle;
+pub mod crank_refund;
+pub mod schedule_settlement;
+pub mod plan_settlement;
+pub mod request_tie_break;
+pub mod resolve_tie;
+pub mod set_raffle_mode;
+pub mod request_raffle_draw;
+pub mod draw_raffle;
+pub mod set_wormhole_config;
+pub mod receive_cross_chain_bid;
+pub mod receive_cross_chain_withdrawal;
+pub mod post_refund_root;
+pub mod claim_refund;
+pub mod commit_content;
+pub mod reveal_content;
+pub mod deposit_prize;
+pub mod withdraw_prize;
+pub mod set_vesting_duration;
+pub mod claim_settled_funds;
+pub mod set_deposit_terms;
+pub mod complete_payment;
+pub mod forfeit_deposit;
+pub mod add_accepted_mint;
+pub mod remove_accepted_mint;
+pub mod harvest_withheld_fees;
+pub mod set_transfer_hooks;
+pub mod update_config;
+pub mod set_settler;
+pub mod set_abandonment_window;
+pub mod mark_abandoned;
+pub mod set_curator;
+pub mod set_referral_bps;
+pub mod transfer_bid;
+pub mod create_receipt_mint;
+pub mod set_moderator;
+pub mod slash_bid;
+pub mod set_bid_ttl;
+pub mod expire_bid;
+pub mod cancel_auction;
+pub mod set_english_auction;
+pub mod set_candle_window;
+pub mod request_candle_end;
+pub mod determine_candle_end;
+pub mod set_all_pay;
+pub mod collect_all_pay_bid;
+pub mod initialize_operator;
+pub mod create_auction;
+pub mod sync_registry;
+pub mod close_auction;
+pub mod create_lot;
+pub mod place_lot_bid;
+pub mod settle_lot;
+pub mod withdraw_lot_bid;
+pub mod place_bundle_bid;
+pub mod settle_bundle;
+pub mod set_standing;
+pub mod initialize_audit_log;
+pub mod migrate_escrow;
+pub mod rescue_tokens;
+pub mod link_bid;
+pub mod unlink_bid;
+pub mod settle_top_k;
+pub mod gc_bid;
+pub mod set_denylisted;
+pub mod force_refund;
+pub mod set_dispute_terms;
+pub mod open_dispute;
+pub mod resolve_settlement;
+pub mod set_claim_window;
+pub mod claim_win;
+pub mod promote_next_bidder;
+pub mod set_default_penalty;
+pub mod set_min_settle_slots;
+pub mod set_memo;
+pub mod create_points_mint;
+pub mod set_points_rate;
+pub mod claim_points;
+pub mod set_rebate_bps;
+pub mod set_session_key;
+pub mod set_reveal_window;
+pub mod reveal_bid;
+pub mod set_commit_bond;
+pub mod sweep_commit_bonds;
+pub mod set_paused;
 
 pub use initialize::*;
 pub use place_bid::*;
@@ -15,3 +113,101 @@ pub use settle::*;
 pub use close_bid::*;
 pub use set_minimum_bid::*;
 pub use set_agent::*;
+pub use set_end_time::*;
+pub use set_withdrawal_penalty::*;
+pub use sweep_penalties::*;
+pub use set_bid_increment::*;
+pub use set_buy_now_price::*;
+pub use buy_now::*;
+pub use set_num_winners::*;
+pub use settle_multi::*;
+pub use set_uniform_price::*;
+pub use set_charity::*;
+pub use set_treasury::*;
+pub use assert_solvent::*;
+pub use sweep_surplus::*;
+pub use crank_settle::*;
+pub use crank_refund::*;
+pub use schedule_settlement::*;
+pub use plan_settlement::*;
+pub use request_tie_break::*;
+pub use resolve_tie::*;
+pub use set_raffle_mode::*;
+pub use request_raffle_draw::*;
+pub use draw_raffle::*;
+pub use set_wormhole_config::*;
+pub use receive_cross_chain_bid::*;
+pub use receive_cross_chain_withdrawal::*;
+pub use post_refund_root::*;
+pub use claim_refund::*;
+pub use commit_content::*;
+pub use reveal_content::*;
+pub use deposit_prize::*;
+pub use withdraw_prize::*;
+pub use set_vesting_duration::*;
+pub use claim_settled_funds::*;
+pub use set_deposit_terms::*;
+pub use complete_payment::*;
+pub use forfeit_deposit::*;
+pub use add_accepted_mint::*;
+pub use remove_accepted_mint::*;
+pub use harvest_withheld_fees::*;
+pub use set_transfer_hooks::*;
+pub use update_config::*;
+pub use set_settler::*;
+pub use set_abandonment_window::*;
+pub use mark_abandoned::*;
+pub use set_curator::*;
+pub use set_referral_bps::*;
+pub use transfer_bid::*;
+pub use create_receipt_mint::*;
+pub use set_moderator::*;
+pub use slash_bid::*;
+pub use set_bid_ttl::*;
+pub use expire_bid::*;
+pub use cancel_auction::*;
+pub use set_english_auction::*;
+pub use set_candle_window::*;
+pub use request_candle_end::*;
+pub use determine_candle_end::*;
+pub use set_all_pay::*;
+pub use collect_all_pay_bid::*;
+pub use initialize_operator::*;
+pub use create_auction::*;
+pub use sync_registry::*;
+pub use close_auction::*;
+pub use create_lot::*;
+pub use place_lot_bid::*;
+pub use settle_lot::*;
+pub use withdraw_lot_bid::*;
+pub use place_bundle_bid::*;
+pub use settle_bundle::*;
+pub use set_standing::*;
+pub use initialize_audit_log::*;
+pub use migrate_escrow::*;
+pub use rescue_tokens::*;
+pub use link_bid::*;
+pub use unlink_bid::*;
+pub use settle_top_k::*;
+pub use gc_bid::*;
+pub use set_denylisted::*;
+pub use force_refund::*;
+pub use set_dispute_terms::*;
+pub use open_dispute::*;
+pub use resolve_settlement::*;
+pub use set_claim_window::*;
+pub use claim_win::*;
+pub use promote_next_bidder::*;
+pub use set_default_penalty::*;
+pub use set_min_settle_slots::*;
+pub use set_memo::*;
+pub use create_points_mint::*;
+pub use set_points_rate::*;
+pub use claim_points::*;
+pub use set_rebate_bps::*;
+pub use set_session_key::*;
+pub use set_reveal_window::*;
+pub use reveal_bid::*;
+pub use set_commit_bond::*;
+pub use sweep_commit_bonds::*;
+pub use set_paused::*;
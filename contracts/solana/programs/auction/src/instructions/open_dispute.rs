@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::DisputeOpened;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.held_amount > 0 @ AuctionError::NoHeldSettlement,
+        constraint = auction_state.held_winner == winner.key() @ AuctionError::WrongBidder,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub winner: Signer<'info>,
+}
+
+/// The held settlement's winner contests it before the window closes, which
+/// stops anyone but the arbiter from releasing the funds.
+pub fn handler(ctx: Context<OpenDispute>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    require!(
+        Clock::get()?.unix_timestamp < state.held_until,
+        AuctionError::DisputeWindowClosed
+    );
+    state.disputed = true;
+
+    emit!(DisputeOpened {
+        winner: state.held_winner,
+        amount: state.held_amount,
+    });
+
+    Ok(())
+}
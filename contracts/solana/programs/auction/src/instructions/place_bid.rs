@@ -1,53 +1,283 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::error::AuctionError;
-use crate::event::BidPlaced;
-use crate::state::{AuctionState, Bid};
+use crate::event::{BidIncreased, BidPlaced, BidRefunded};
+use crate::leader::auto_escalate;
+use crate::receipt::Receipt;
+use crate::state::{
+    bid_index_seed, require_not_denylisted, AcceptedMints, AuctionState, AuctionStatus, Bid,
+    Config, Denylist,
+};
+use crate::transfer::transfer_checked;
 use crate::USDC_DECIMALS;
 
 #[derive(Accounts)]
+#[instruction(bid_index: u16)]
 pub struct PlaceBid<'info> {
-    #[account(
-        mut,
-        seeds = [b"auction_state"],
-        bump = auction_state.bump,
-        has_one = usdc_mint,
-    )]
+    /// Ownership and discriminator already identify an `AuctionState`, and
+    /// the bid is derived from its id, so the seeds are not re-derived on the
+    /// bid path.
+    #[account(mut)]
     pub auction_state: Account<'info, AuctionState>,
+    /// Created here; only `upsert_bid` accepts one that already exists.
     #[account(
-        init,
-        payer = bidder,
+        init_if_needed,
+        payer = payer,
         space = 8 + Bid::INIT_SPACE,
-        seeds = [b"bid", bidder.key().as_ref()],
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid_index_seed(bid_index).as_slice(),
+        ],
         bump,
     )]
     pub bid: Account<'info, Bid>,
     #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
     pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
-    #[account(
-        mut,
-        token::mint = usdc_mint,
-        token::authority = auction_state,
-        seeds = [b"escrow"],
-        bump = auction_state.escrow_bump,
-    )]
+    /// Escrow for `usdc_mint`, checked in the handler.
+    #[account(mut)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// Auction mint, or an accepted mint listed in `accepted_mints`.
     pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Owner of the bid and authority over `bidder_usdc`. A program's PDA,
+    /// such as a DAO treasury, bids by signing through CPI.
     #[account(mut)]
     pub bidder: Signer<'info>,
+    /// Pays the new bid's rent and any bond; the bidder, an app sponsoring
+    /// them, or a wallet funding a PDA bidder. Rent and bond still go to the
+    /// bidder when the bid is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    /// Current leader's bid, required whenever someone else holds the lead.
+    #[account(mut)]
+    pub leader_bid: Option<Account<'info, Bid>>,
+    /// Required when bidding in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// Receipt mint and the bidder's token account for it, to take a
+    /// receipt for this bid.
+    #[account(mut, seeds = [b"receipt_mint", auction_state.id_seed().as_slice()], bump)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub bidder_receipt: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The displaced leader's token account, refunded in English mode when
+    /// this bid takes the lead.
+    #[account(mut)]
+    pub leader_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Required once the auction has a denylist; see `set_denylisted`.
+    #[account(seeds = [b"denylist", auction_state.id_seed().as_slice()], bump = denylist.bump)]
+    pub denylist: Option<Account<'info, Denylist>>,
+    /// The auction's config and the bidder's token account for its discount
+    /// mint, to snapshot the fee discount their balance earns.
+    #[account(seeds = [b"config", auction_state.id_seed().as_slice()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+    pub bidder_discount: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// `bid_index` picks which of the bidder's bids in the auction this is.
+/// `referrer`, if given, is recorded on the bid and paid `referral_bps` of
+/// the price should it win.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+    bid_index: u16,
+    amount: u64,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    place(ctx, bid_index, amount, amount, false, referrer, [0; 32])
+}
+
+/// Escrows `max_amount` but bids only the minimum, letting leader tracking
+/// escalate the effective amount when outbid.
+pub fn proxy_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+    bid_index: u16,
+    max_amount: u64,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    let minimum_bid = ctx.accounts.auction_state.minimum_bid;
+    place(ctx, bid_index, minimum_bid, max_amount, true, referrer, [0; 32])
+}
+
+/// Escrows `deposit` behind a bid whose amount stays hidden until the round
+/// closes: `commitment` is `sealed_commitment` of the amount, which must be
+/// at least the minimum bid and at most `deposit`. The bid is not tracked
+/// for the lead until `reveal_bid` opens it. Only the deposit is public, so
+/// bidders cover their amount generously to give less away. The auction's
+/// `commit_bond` is escrowed on top, and lost unless the bid is revealed.
+pub fn sealed_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+    bid_index: u16,
+    deposit: u64,
+    commitment: [u8; 32],
+) -> Result<()> {
+    require!(commitment != [0; 32], AuctionError::CommitmentMismatch);
+    place(ctx, bid_index, 0, deposit, false, None, commitment)
 }
 
-pub fn handler(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+/// Places a plain bid of `amount`, or raises the caller's existing bid to
+/// `amount`, so a client need not know which applies. Proxy bids raise their
+/// ceiling. Repeating the call once it has landed does nothing.
+pub fn upsert_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+    bid_index: u16,
+    amount: u64,
+) -> Result<()> {
+    if ctx.accounts.bid.bidder == Pubkey::default() {
+        return place(ctx, bid_index, amount, amount, false, None, [0; 32]);
+    }
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
     let state = &mut ctx.accounts.auction_state;
     let bid = &mut ctx.accounts.bid;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(bid.active, AuctionError::BidNotActive);
+    require!(bid.commitment == [0; 32], AuctionError::BidSealed);
+    require!(!state.is_ranked(bid), AuctionError::BidRanked);
+    require_keys_eq!(bid.mint, mint, AuctionError::InvalidTokenAccount);
+    if amount == bid.max_amount {
+        return Ok(());
+    }
+    require!(amount > bid.max_amount, AuctionError::BidTooLow);
+    require!(state.has_started(now), AuctionError::AuctionNotStarted);
+    require!(!state.has_ended(now), AuctionError::AuctionEnded);
+    state.require_accepting_bids()?;
+    require_not_denylisted(state, ctx.accounts.denylist.as_deref(), &ctx.accounts.bidder.key())?;
+    let accepted = ctx.accounts.accepted_mints.as_deref_mut();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let increase = amount - bid.max_amount;
+    let deposit = if mint == state.usdc_mint {
+        state.deposit_for(increase)
+    } else {
+        increase
+    };
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_usdc.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+        ),
+        deposit,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+    state.accrue_points(bid, now)?;
+    bid.max_amount = amount;
+    bid.balance_due = increase
+        .checked_sub(deposit)
+        .and_then(|owed| bid.balance_due.checked_add(owed))
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    if !bid.proxy {
+        bid.amount = amount;
+    }
+    bid.updated_at = now;
+    bid.updated_slot = Clock::get()?.slot;
+    state.record_bid(false);
+    state.credit_escrow_in(accepted, &mint, deposit)?;
+
+    auto_escalate(
+        state,
+        ctx.accounts.bidder.key(),
+        bid,
+        ctx.accounts.leader_bid.as_mut(),
+    )?;
+
+    emit!(BidIncreased {
+        bidder: bid.bidder,
+        amount: increase,
+        new_amount: bid.amount,
+        max_amount: bid.max_amount,
+    });
+
+    Ok(())
+}
+
+fn place<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+    bid_index: u16,
+    amount: u64,
+    max_amount: u64,
+    proxy: bool,
+    referrer: Option<Pubkey>,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let state = &mut ctx.accounts.auction_state;
+    let bid = &mut ctx.accounts.bid;
+    let accepted = ctx.accounts.accepted_mints.as_deref_mut();
+    let mint = ctx.accounts.usdc_mint.key();
     let clock = Clock::get()?;
 
-    require!(amount >= state.minimum_bid, AuctionError::BidTooLow);
+    require_keys_eq!(
+        bid.bidder,
+        Pubkey::default(),
+        AuctionError::BidAlreadyPlaced
+    );
+    require!(
+        state.has_started(clock.unix_timestamp),
+        AuctionError::AuctionNotStarted
+    );
+    require!(
+        !state.has_ended(clock.unix_timestamp),
+        AuctionError::AuctionEnded
+    );
+    state.require_accepting_bids()?;
+    require_not_denylisted(state, ctx.accounts.denylist.as_deref(), &ctx.accounts.bidder.key())?;
+    require!(
+        !state.english_auction || mint == state.usdc_mint,
+        AuctionError::AuctionMintOnly
+    );
+    let sealed = commitment != [0; 32];
+    if sealed {
+        require!(state.takes_sealed_bids(), AuctionError::SealedBidsDisabled);
+        require_keys_eq!(mint, state.usdc_mint, AuctionError::AuctionMintOnly);
+    } else {
+        require!(!state.takes_sealed_bids(), AuctionError::SealedBidsOnly);
+        require!(amount >= state.minimum_bid, AuctionError::BidTooLow);
+    }
+    require!(
+        max_amount >= amount.max(state.minimum_bid),
+        AuctionError::BidTooLow
+    );
+    let referrer = referrer.unwrap_or_default();
+    require_keys_neq!(
+        referrer,
+        ctx.accounts.bidder.key(),
+        AuctionError::SelfReferral
+    );
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    // Balances due are only collected in the auction mint, and sealed bids
+    // escrow their whole cover so reveals only ever refund.
+    let deposit = if mint == state.usdc_mint && !sealed {
+        state.deposit_for(max_amount)
+    } else {
+        max_amount
+    };
+
+    let commit_bond = if sealed { state.commit_bond } else { 0 };
+    let escrowed = deposit
+        .checked_add(commit_bond)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
 
-    token_interface::transfer_checked(
+    transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             TransferChecked {
@@ -57,26 +287,169 @@ pub fn handler(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
                 mint: ctx.accounts.usdc_mint.to_account_info(),
             },
         ),
-        amount,
+        escrowed,
         USDC_DECIMALS,
+        hooks,
     )?;
+    if state.bid_bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: bid.to_account_info(),
+                },
+            ),
+            state.bid_bond,
+        )?;
+    }
 
+    state.accrue_points(bid, clock.unix_timestamp)?;
     bid.bidder = ctx.accounts.bidder.key();
     bid.amount = amount;
     bid.created_at = clock.unix_timestamp;
     bid.updated_at = clock.unix_timestamp;
+    bid.updated_slot = clock.slot;
     bid.active = true;
     bid.bump = ctx.bumps.bid;
+    bid.max_amount = max_amount;
+    bid.proxy = proxy;
+    bid.balance_due = max_amount - deposit;
+    bid.mint = mint;
+    bid.auction_id = state.auction_id;
+    bid.bid_index = bid_index;
+    bid.referrer = referrer;
+    bid.bond = state.bid_bond;
+    bid.commitment = commitment;
+    bid.commit_bond = commit_bond;
+    bid.fee_discount_bps = fee_discount(
+        ctx.accounts.config.as_deref(),
+        ctx.accounts.bidder_discount.as_ref(),
+        &bid.bidder,
+    )?;
+
+    let receipt = Receipt {
+        receipt_mint: ctx.accounts.receipt_mint.as_ref(),
+        holder: ctx.accounts.bidder_receipt.as_ref(),
+        token_program: &ctx.accounts.token_program,
+    };
+    bid.receipt = receipt.requested();
+    if bid.receipt {
+        receipt.issue(&bid.bidder, state)?;
+    }
 
     state.active_bid_count = state
         .active_bid_count
         .checked_add(1)
         .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.record_bid(true);
+    state.credit_escrow_in(accepted, &mint, escrowed)?;
+    state.commit_bonds_held = state
+        .commit_bonds_held
+        .checked_add(commit_bond)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.transition(AuctionStatus::Open, clock.unix_timestamp)?;
+
+    let prior_leader =
+        (state.leader != Pubkey::default()).then_some((state.leader, state.leader_index));
+    if !sealed {
+        auto_escalate(
+            state,
+            ctx.accounts.bidder.key(),
+            bid,
+            ctx.accounts.leader_bid.as_mut(),
+        )?;
+    }
+
+    // English mode escrows the leader alone: a bid that does not take the
+    // lead is refused, and one that does pays the displaced leader back.
+    if state.english_auction {
+        require!(
+            state.is_leader(bid.bidder, bid_index),
+            AuctionError::BidTooLow
+        );
+        if let Some(displaced) = ctx.accounts.leader_bid.as_mut() {
+            // Only the live bid that led before this one is refunded; with
+            // no prior leader there is no one to pay back.
+            require!(
+                displaced.active
+                    && displaced.auction_id == state.auction_id
+                    && prior_leader == Some((displaced.bidder, displaced.bid_index))
+                    && !state.is_leader(displaced.bidder, displaced.bid_index),
+                AuctionError::WrongLeaderBid
+            );
+            let leader_usdc = ctx
+                .accounts
+                .leader_usdc
+                .as_ref()
+                .ok_or(AuctionError::LeaderAccountRequired)?;
+            require_keys_eq!(displaced.mint, mint, AuctionError::AuctionMintOnly);
+            require!(!state.is_ranked(displaced), AuctionError::BidRanked);
+            require_keys_eq!(
+                leader_usdc.owner,
+                displaced.bidder,
+                AuctionError::InvalidTokenAccount
+            );
+            require_keys_eq!(leader_usdc.mint, mint, AuctionError::InvalidTokenAccount);
+
+            let refund = displaced.escrowed();
+            let id_seed = state.id_seed();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: leader_usdc.to_account_info(),
+                        authority: state.to_account_info(),
+                        mint: ctx.accounts.usdc_mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund,
+                USDC_DECIMALS,
+                hooks,
+            )?;
+
+            state.accrue_points(displaced, clock.unix_timestamp)?;
+            displaced.active = false;
+            state.active_bid_count = state
+                .active_bid_count
+                .checked_sub(1)
+                .ok_or(AuctionError::ArithmeticOverflow)?;
+            state
+                .leaderboard
+                .remove(displaced.bidder, displaced.bid_index);
+            state.debit_escrow(refund)?;
+            emit!(BidRefunded {
+                bidder: displaced.bidder,
+                amount: refund,
+            });
+        }
+    }
 
     emit!(BidPlaced {
         bidder: ctx.accounts.bidder.key(),
-        amount,
+        amount: bid.amount,
+        bid_index,
     });
 
     Ok(())
 }
+
+/// Fee discount `holding`, the bidder's token account for the config's
+/// discount mint, earns them; zero when either account is left out.
+fn fee_discount(
+    config: Option<&Config>,
+    holding: Option<&InterfaceAccount<TokenAccount>>,
+    bidder: &Pubkey,
+) -> Result<u16> {
+    let (Some(config), Some(holding)) = (config, holding) else {
+        return Ok(0);
+    };
+    require!(
+        holding.mint == config.discount_mint && holding.owner == *bidder,
+        AuctionError::InvalidTokenAccount
+    );
+    Ok(config.fee_discount(holding.amount))
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::BundleBidPlaced;
+use crate::state::{require_not_denylisted, AuctionState, BundleBid, Denylist, Lot, MAX_BUNDLE_LOTS};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct PlaceBundleBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BundleBid::INIT_SPACE,
+        seeds = [b"bundle_bid", auction_state.id_seed().as_slice(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub bundle_bid: Account<'info, BundleBid>,
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    /// Pays the bundle bid's rent; the rent goes to the bidder on close.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Required once the auction has a denylist; see `set_denylisted`.
+    #[account(seeds = [b"denylist", auction_state.id_seed().as_slice()], bump = denylist.bump)]
+    pub denylist: Option<Account<'info, Denylist>>,
+}
+
+/// Escrows `amount` as one offer for every lot in `lots`, which must be
+/// ascending and at least cover the lots' minimums together.
+/// `remaining_accounts` starts with the `Lot` accounts in the same order;
+/// transfer-hook accounts, when enabled, follow them. Each lot then waits for
+/// the bundle in `settle_lot` until `settle_bundle` decides it.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBundleBid<'info>>,
+    lots: Vec<u16>,
+    amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &ctx.accounts.auction_state;
+    require!(state.has_started(now), AuctionError::AuctionNotStarted);
+    require!(!state.has_ended(now), AuctionError::AuctionEnded);
+    state.require_accepting_bids()?;
+    require_not_denylisted(state, ctx.accounts.denylist.as_deref(), &ctx.accounts.bidder.key())?;
+    require!(
+        (2..=MAX_BUNDLE_LOTS).contains(&lots.len())
+            && lots.windows(2).all(|pair| pair[0] < pair[1])
+            && ctx.remaining_accounts.len() >= lots.len(),
+        AuctionError::InvalidBundle
+    );
+    let (lot_infos, extra) = ctx.remaining_accounts.split_at(lots.len());
+
+    let mut minimums: u64 = 0;
+    for (lot_id, info) in lots.iter().zip(lot_infos) {
+        let mut lot = Account::<Lot>::try_from(info)?;
+        require!(
+            lot.auction_id == state.auction_id && lot.lot_id == *lot_id,
+            AuctionError::InvalidBundle
+        );
+        require!(!lot.settled, AuctionError::LotSettled);
+        minimums = minimums
+            .checked_add(lot.minimum_bid)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        lot.pending_bundles = lot
+            .pending_bundles
+            .checked_add(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        lot.exit(&crate::ID)?;
+    }
+    require!(amount >= minimums, AuctionError::BidTooLow);
+
+    let hooks = state.hook_accounts(extra);
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_usdc.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.credit_escrow(amount)?;
+    state.record_bid(true);
+    state.active_lot_bid_count = state
+        .active_lot_bid_count
+        .checked_add(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+
+    let bundle = &mut ctx.accounts.bundle_bid;
+    bundle.bidder = ctx.accounts.bidder.key();
+    bundle.auction_id = state.auction_id;
+    bundle.lots = lots.clone();
+    bundle.amount = amount;
+    bundle.created_at = now;
+    bundle.bump = ctx.bumps.bundle_bid;
+
+    emit!(BundleBidPlaced {
+        bidder: bundle.bidder,
+        lots,
+        amount,
+    });
+
+    Ok(())
+}
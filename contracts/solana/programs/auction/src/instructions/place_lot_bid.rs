@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::LotBidPlaced;
+use crate::state::{require_not_denylisted, AuctionState, Denylist, Lot, LotBid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct PlaceLotBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [b"lot", auction_state.id_seed().as_slice(), &lot.lot_seed()],
+        bump = lot.bump,
+        constraint = !lot.settled @ AuctionError::LotSettled,
+    )]
+    pub lot: Account<'info, Lot>,
+    /// Created on the bidder's first bid on the lot and raised after that.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + LotBid::INIT_SPACE,
+        seeds = [b"lot_bid", auction_state.id_seed().as_slice(), &lot.lot_seed(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub lot_bid: Account<'info, LotBid>,
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    /// Pays the new lot bid's rent; the rent goes to the bidder on close.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Required once the auction has a denylist; see `set_denylisted`.
+    #[account(seeds = [b"denylist", auction_state.id_seed().as_slice()], bump = denylist.bump)]
+    pub denylist: Option<Account<'info, Denylist>>,
+}
+
+/// Bids `amount` on the lot, or raises the caller's bid on it to `amount`,
+/// escrowing the difference. The bid takes the lot's lead by beating the
+/// leading amount; ties keep the existing leader.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceLotBid<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &ctx.accounts.auction_state;
+    require!(state.has_started(now), AuctionError::AuctionNotStarted);
+    require!(!state.has_ended(now), AuctionError::AuctionEnded);
+    state.require_accepting_bids()?;
+    require_not_denylisted(state, ctx.accounts.denylist.as_deref(), &ctx.accounts.bidder.key())?;
+    let lot_bid = &ctx.accounts.lot_bid;
+    require!(
+        amount >= ctx.accounts.lot.minimum_bid && amount > lot_bid.amount,
+        AuctionError::BidTooLow
+    );
+    let new_bid = lot_bid.bidder == Pubkey::default();
+    let increase = amount - lot_bid.amount;
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_usdc.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+        ),
+        increase,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    let lot = &mut ctx.accounts.lot;
+    let lot_bid = &mut ctx.accounts.lot_bid;
+    let bidder = ctx.accounts.bidder.key();
+    if new_bid {
+        lot_bid.bidder = bidder;
+        lot_bid.auction_id = state.auction_id;
+        lot_bid.lot_id = lot.lot_id;
+        lot_bid.created_at = now;
+        lot_bid.bump = ctx.bumps.lot_bid;
+        lot.active_bid_count = lot
+            .active_bid_count
+            .checked_add(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.active_lot_bid_count = state
+            .active_lot_bid_count
+            .checked_add(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+    }
+    lot_bid.amount = amount;
+    lot_bid.updated_at = now;
+    state.credit_escrow(increase)?;
+    state.record_bid(new_bid);
+
+    if lot.leader == bidder || amount > lot.leading_amount {
+        lot.leader = bidder;
+        lot.leading_amount = amount;
+        state.highest_ever_bid = state.highest_ever_bid.max(amount);
+    }
+
+    emit!(LotBidPlaced {
+        lot_id: lot.lot_id,
+        bidder,
+        amount,
+        leading: lot.leader == bidder,
+    });
+
+    Ok(())
+}
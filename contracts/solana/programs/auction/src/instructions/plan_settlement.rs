@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::error::AuctionError;
+use crate::pda;
+use crate::scheduler::{ThreadResponse, PAYER_PUBKEY};
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct PlanSettlement<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// The leaderboard top's bid. Left out on a thread's first call, which
+    /// answers with a call that names it.
+    pub winning_bid: Option<Account<'info, Bid>>,
+    /// CHECK: The auction mint, read only for its token program.
+    #[account(address = auction_state.usdc_mint)]
+    pub usdc_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: The prize mint, read only for its token program; required when
+    /// the agent has escrowed a prize.
+    #[account(address = auction_state.prize_mint)]
+    pub prize_mint: Option<UncheckedAccount<'info>>,
+}
+
+/// Read-only callback for a settlement thread; see `schedule_settlement`.
+/// Given only the auction state, it answers with a call to itself naming
+/// the winning bid and mints. Given those, it answers with the `crank_settle`
+/// for the leaderboard top, its rivals taken from the leaderboard. It fails
+/// while the crank would, and whenever the crank needs accounts only a keeper
+/// can find: rivals that fell off the leaderboard, transfer-hook accounts, or
+/// a winner in another accepted mint.
+pub fn handler(ctx: Context<PlanSettlement>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    require!(
+        state.has_ended(Clock::get()?.unix_timestamp),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        !state.raffle_mode || state.raffle_drawn,
+        AuctionError::RaffleNotDrawn
+    );
+    require!(!state.candle_pending(), AuctionError::CandleNotDetermined);
+    require!(
+        !state.transfer_hooks,
+        AuctionError::SettlementNotSchedulable
+    );
+    let top = *state
+        .leaderboard
+        .entries()
+        .first()
+        .ok_or(AuctionError::WinnersMismatch)?;
+    let winning_bid = pda::find_bid(state.auction_id, &top.bidder, top.bid_index).0;
+
+    let (Some(bid), Some(usdc_mint)) = (&ctx.accounts.winning_bid, &ctx.accounts.usdc_mint) else {
+        let plan = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::PlanSettlement {
+                auction_state: state.key(),
+                winning_bid: Some(winning_bid),
+                usdc_mint: Some(state.usdc_mint),
+                prize_mint: (state.prize_amount > 0).then_some(state.prize_mint),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::PlanSettlement {}.data(),
+        };
+        return ThreadResponse::run_next(plan).publish();
+    };
+    require_keys_eq!(bid.key(), winning_bid, AuctionError::WinnersMismatch);
+    require_keys_eq!(
+        bid.mint,
+        state.usdc_mint,
+        AuctionError::SettlementNotSchedulable
+    );
+
+    let token_program = *usdc_mint.owner;
+    let prize_token_program = if state.prize_amount > 0 {
+        let prize_mint = ctx
+            .accounts
+            .prize_mint
+            .as_ref()
+            .ok_or(AuctionError::PrizeAccountsRequired)?;
+        Some(*prize_mint.owner)
+    } else {
+        None
+    };
+    let associated = |owner: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, &state.usdc_mint, &token_program)
+    };
+    let round = state.current_round();
+    let mut crank = Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::CrankSettle {
+            auction_state: state.key(),
+            winning_bid,
+            escrow: state.escrow,
+            treasury: state.treasury,
+            usdc_mint: state.usdc_mint,
+            cranker: PAYER_PUBKEY,
+            token_program,
+            winner_usdc: (bid.max_amount > bid.amount || bid.fee_discount_bps > 0)
+                .then(|| associated(&bid.bidder)),
+            charity: (state.charity_bps > 0).then_some(state.charity),
+            curator_usdc: (state.curator_bps > 0).then(|| associated(&state.curator)),
+            referrer_usdc: (state.referral_bps > 0 && bid.referrer != Pubkey::default())
+                .then(|| associated(&bid.referrer)),
+            prize_vault: prize_token_program
+                .map(|_| pda::find_prize_vault(state.auction_id, &state.prize_mint).0),
+            prize_mint: prize_token_program.map(|_| state.prize_mint),
+            winner_prize: prize_token_program.map(|program| {
+                get_associated_token_address_with_program_id(
+                    &bid.bidder,
+                    &state.prize_mint,
+                    &program,
+                )
+            }),
+            prize_token_program,
+            accepted_mints: None,
+            round_result: pda::find_round_result(state.auction_id, round).0,
+            win_receipt: pda::find_win_receipt(state.auction_id, round, &bid.bidder).0,
+            price_feed: pda::find_price_feed(state.auction_id).0,
+            system_program: System::id(),
+            memo_program: (state.memo_len > 0).then_some(anchor_spl::memo::ID),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::CrankSettle {}.data(),
+    };
+
+    // A drawn raffle takes no rivals. Otherwise every other active bid must
+    // still be on the leaderboard for the thread to name them all.
+    if !state.raffle_drawn {
+        let entries = state.leaderboard.entries();
+        require!(
+            entries.len() as u64 == state.active_bid_count,
+            AuctionError::RivalBidsMismatch
+        );
+        let mut rivals: Vec<Pubkey> = entries[1..]
+            .iter()
+            .map(|entry| pda::find_bid(state.auction_id, &entry.bidder, entry.bid_index).0)
+            .collect();
+        rivals.sort();
+        crank.accounts.extend(
+            rivals
+                .into_iter()
+                .map(|rival| AccountMeta::new_readonly(rival, false)),
+        );
+    }
+
+    ThreadResponse::run_next(crank).publish()
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::RefundRootPosted;
+use crate::state::{AuctionState, RefundClaims, MAX_REFUND_LEAVES};
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], leaf_count: u32)]
+pub struct PostRefundRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+        constraint = auction_state.is_settled() @ AuctionError::AuctionNotSettled,
+        constraint = !auction_state.all_pay @ AuctionError::BidsNonRefundable,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = authority,
+        space = RefundClaims::space(leaf_count),
+        seeds = [b"refund_claims", auction_state.id_seed().as_slice(), root.as_ref()],
+        bump,
+    )]
+    pub refund_claims: Account<'info, RefundClaims>,
+    #[account(mut)]
+    /// The agent, or the settler it delegated settlement to.
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Switches the round's refunds to Merkle claims: each leaf commits to an
+/// index, bidder and amount, and bidders pull their own refund with
+/// `claim_refund`. Replaces any earlier root; `crank_refund` is disabled
+/// while one is posted.
+pub fn handler(ctx: Context<PostRefundRoot>, root: [u8; 32], leaf_count: u32) -> Result<()> {
+    require!(
+        root != [0; 32] && (1..=MAX_REFUND_LEAVES).contains(&leaf_count),
+        AuctionError::InvalidRefundRoot
+    );
+
+    let claims = &mut ctx.accounts.refund_claims;
+    claims.root = root;
+    claims.leaf_count = leaf_count;
+    claims.bump = ctx.bumps.refund_claims;
+    claims.claimed = vec![0; RefundClaims::bitmap_len(leaf_count)];
+
+    ctx.accounts.auction_state.refund_root = root;
+
+    emit!(RefundRootPosted { root, leaf_count });
+
+    Ok(())
+}
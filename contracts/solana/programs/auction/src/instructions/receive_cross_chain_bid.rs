@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::{BidPlaced, BidUpdated, CrossChainBidReceived};
+use crate::leader::auto_escalate;
+use crate::state::{AuctionState, AuctionStatus, Bid, ReceivedMessage};
+use crate::transfer::transfer_checked;
+use crate::wormhole::{BidMessage, PAYLOAD_BID};
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+#[instruction(sequence: u64, bidder: Pubkey)]
+pub struct ReceiveCrossChainBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = relayer @ AuctionError::OnlyRelayer,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Owner and layout are checked in `BidMessage::parse`.
+    pub posted_vaa: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + ReceivedMessage::INIT_SPACE,
+        seeds = [
+            b"received",
+            auction_state.id_seed().as_slice(),
+            auction_state.foreign_emitter_chain.to_le_bytes().as_ref(),
+            auction_state.foreign_emitter.as_ref(),
+            sequence.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub received: Account<'info, ReceivedMessage>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", auction_state.id_seed().as_slice(), bidder.as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// Holds the USDC the relayer redeemed from the bridge for this bid.
+    #[account(mut, token::mint = usdc_mint, token::authority = relayer)]
+    pub relayer_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub leader_bid: Option<Account<'info, Bid>>,
+}
+
+/// Places or tops up a bid for a foreign wallet from a verified Wormhole VAA.
+/// The bid PDA is keyed by the bidder's universal address, which no Solana
+/// key can sign for, so only further VAAs can move it.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReceiveCrossChainBid<'info>>,
+    sequence: u64,
+    bidder: Pubkey,
+) -> Result<()> {
+    let message = BidMessage::parse(&ctx.accounts.posted_vaa)?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let state = &mut ctx.accounts.auction_state;
+    let bid = &mut ctx.accounts.bid;
+    let clock = Clock::get()?;
+
+    require!(
+        message.emitter_chain == state.foreign_emitter_chain
+            && message.emitter_address == state.foreign_emitter,
+        AuctionError::UnknownEmitter
+    );
+    require!(
+        message.sequence == sequence
+            && message.auction_id == state.auction_id
+            && message.bidder == bidder
+            && message.kind == PAYLOAD_BID,
+        AuctionError::InvalidVaa
+    );
+    require!(
+        state.has_started(clock.unix_timestamp),
+        AuctionError::AuctionNotStarted
+    );
+    require!(
+        !state.has_ended(clock.unix_timestamp),
+        AuctionError::AuctionEnded
+    );
+    state.require_accepting_bids()?;
+    require!(!state.takes_sealed_bids(), AuctionError::SealedBidsOnly);
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.relayer_usdc.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.relayer.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+        ),
+        message.amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    state.accrue_points(bid, clock.unix_timestamp)?;
+    let placed = !bid.active;
+    if placed {
+        require!(message.amount >= state.minimum_bid, AuctionError::BidTooLow);
+        bid.bidder = bidder;
+        bid.amount = message.amount;
+        bid.max_amount = message.amount;
+        bid.proxy = false;
+        bid.created_at = clock.unix_timestamp;
+        bid.active = true;
+        bid.bump = ctx.bumps.bid;
+        bid.foreign_chain = message.emitter_chain;
+        bid.mint = state.usdc_mint;
+        bid.auction_id = state.auction_id;
+
+        state.active_bid_count = state
+            .active_bid_count
+            .checked_add(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.transition(AuctionStatus::Open, clock.unix_timestamp)?;
+    } else {
+        require!(
+            bid.foreign_chain == message.emitter_chain,
+            AuctionError::WrongBidder
+        );
+        bid.max_amount = bid
+            .max_amount
+            .checked_add(message.amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        bid.amount = bid.max_amount;
+    }
+    bid.updated_at = clock.unix_timestamp;
+    bid.updated_slot = clock.slot;
+    state.record_bid(placed);
+    state.credit_escrow(message.amount)?;
+
+    auto_escalate(state, bidder, bid, ctx.accounts.leader_bid.as_mut())?;
+
+    let received = &mut ctx.accounts.received;
+    received.emitter_chain = message.emitter_chain;
+    received.sequence = sequence;
+    received.bump = ctx.bumps.received;
+
+    if placed {
+        emit!(BidPlaced {
+            bidder,
+            amount: bid.amount,
+            bid_index: 0,
+        });
+    } else {
+        emit!(BidUpdated {
+            bidder,
+            new_amount: bid.amount,
+        });
+    }
+    emit!(CrossChainBidReceived {
+        bidder,
+        foreign_chain: message.emitter_chain,
+        sequence,
+        amount: message.amount,
+    });
+
+    Ok(())
+}
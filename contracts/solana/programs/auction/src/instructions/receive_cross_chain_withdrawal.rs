@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::{BidWithdrawn, CrossChainRefund};
+use crate::state::{AuctionState, Bid, ReceivedMessage};
+use crate::transfer::transfer_checked;
+use crate::wormhole::{BidMessage, PAYLOAD_WITHDRAW};
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+#[instruction(sequence: u64, bidder: Pubkey)]
+pub struct ReceiveCrossChainWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = relayer @ AuctionError::OnlyRelayer,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Owner and layout are checked in `BidMessage::parse`.
+    pub posted_vaa: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + ReceivedMessage::INIT_SPACE,
+        seeds = [
+            b"received",
+            auction_state.id_seed().as_slice(),
+            auction_state.foreign_emitter_chain.to_le_bytes().as_ref(),
+            auction_state.foreign_emitter.as_ref(),
+            sequence.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub received: Account<'info, ReceivedMessage>,
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [b"bid", auction_state.id_seed().as_slice(), bidder.as_ref()],
+        bump = bid.bump,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.foreign_chain == auction_state.foreign_emitter_chain
+            @ AuctionError::WrongBidder,
+        constraint = bid.mint == auction_state.usdc_mint @ AuctionError::AuctionMintOnly,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// Receives the refund, which the relayer bridges back to the bidder.
+    #[account(mut, token::mint = usdc_mint, token::authority = relayer)]
+    pub relayer_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Reverse path: a withdrawal VAA from the bidder's home chain releases their
+/// escrow (less any early-withdrawal penalty) to the relayer, which bridges it
+/// back. The message amount is ignored; withdrawals are always in full.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReceiveCrossChainWithdrawal<'info>>,
+    sequence: u64,
+    bidder: Pubkey,
+) -> Result<()> {
+    let message = BidMessage::parse(&ctx.accounts.posted_vaa)?;
+    let clock = Clock::get()?;
+
+    {
+        let state = &ctx.accounts.auction_state;
+        require!(
+            message.emitter_chain == state.foreign_emitter_chain
+                && message.emitter_address == state.foreign_emitter,
+            AuctionError::UnknownEmitter
+        );
+    }
+    require!(
+        message.sequence == sequence
+            && message.auction_id == ctx.accounts.auction_state.auction_id
+            && message.bidder == bidder
+            && message.kind == PAYLOAD_WITHDRAW,
+        AuctionError::InvalidVaa
+    );
+    ctx.accounts.auction_state.require_refundable()?;
+
+    let amount = ctx.accounts.bid.escrowed();
+    let penalty = ctx
+        .accounts
+        .auction_state
+        .withdrawal_penalty(amount, clock.unix_timestamp);
+    let refund = amount - penalty;
+
+    let state_bump = ctx.accounts.auction_state.bump;
+
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.relayer_usdc.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.accrued_penalties = state
+        .accrued_penalties
+        .checked_add(penalty)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, 0);
+    state.debit_escrow(refund)?;
+
+    let received = &mut ctx.accounts.received;
+    received.emitter_chain = message.emitter_chain;
+    received.sequence = sequence;
+    received.bump = ctx.bumps.received;
+
+    emit!(BidWithdrawn {
+        bidder,
+        amount,
+        penalty,
+    });
+    emit!(CrossChainRefund {
+        bidder,
+        foreign_chain: message.emitter_chain,
+        sequence,
+        amount: refund,
+    });
+
+    Ok(())
+}
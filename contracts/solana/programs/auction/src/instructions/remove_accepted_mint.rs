@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::AcceptedMintRemoved;
+use crate::state::{AcceptedMints, AuctionState};
+
+#[derive(Accounts)]
+pub struct RemoveAcceptedMint<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Account<'info, AcceptedMints>,
+    pub agent: Signer<'info>,
+}
+
+/// Stops accepting `mint`. Its escrow must not owe any bidder.
+pub fn handler(ctx: Context<RemoveAcceptedMint>, mint: Pubkey) -> Result<()> {
+    let accepted = &mut ctx.accounts.accepted_mints;
+    let entry = accepted.get(&mint).ok_or(AuctionError::MintNotAccepted)?;
+    require!(entry.total_escrowed == 0, AuctionError::AcceptedMintInUse);
+    accepted.remove(&mint);
+
+    emit!(AcceptedMintRemoved { mint });
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::get_switchboard_on_demand_program_id;
+
+use crate::error::AuctionError;
+use crate::event::CandleEndRequested;
+use crate::randomness;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct RequestCandleEnd<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = auction_state.candle_window > 0 @ AuctionError::CandleModeDisabled,
+        constraint = auction_state.candle_end == 0 @ AuctionError::CandleAlreadyDetermined,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Owner is checked here and contents are parsed by the Switchboard SDK.
+    #[account(owner = get_switchboard_on_demand_program_id() @ AuctionError::InvalidRandomness)]
+    pub randomness_account: UncheckedAccount<'info>,
+    pub agent: Signer<'info>,
+}
+
+/// Commits the randomness that will place the candle end. Only possible
+/// once the round has ended, so no bid can be timed against the draw.
+pub fn handler(ctx: Context<RequestCandleEnd>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let clock = Clock::get()?;
+
+    require!(
+        state.end_time != 0 && state.has_ended(clock.unix_timestamp),
+        AuctionError::AuctionNotEnded
+    );
+
+    randomness::commit(state, &ctx.accounts.randomness_account)?;
+
+    emit!(CandleEndRequested {
+        randomness_account: state.pending_randomness,
+        seed_slot: state.randomness_seed_slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::get_switchboard_on_demand_program_id;
+
+use crate::error::AuctionError;
+use crate::event::TieBreakRequested;
+use crate::randomness;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct RequestTieBreak<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Owner is checked here and contents are parsed by the Switchboard SDK.
+    #[account(owner = get_switchboard_on_demand_program_id() @ AuctionError::InvalidRandomness)]
+    pub randomness_account: UncheckedAccount<'info>,
+    pub agent: Signer<'info>,
+}
+
+/// Binds a freshly committed Switchboard randomness account to the current
+/// top-of-leaderboard tie, once bidding is over and the tie can no longer
+/// change.
+pub fn handler(ctx: Context<RequestTieBreak>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(state.has_ended(now), AuctionError::AuctionNotEnded);
+    require!(!state.revealing(now), AuctionError::RevealWindowOpen);
+
+    let tied_count = state.leaderboard.tied_at_top();
+    require!(tied_count > 1, AuctionError::NoTie);
+
+    randomness::commit(state, &ctx.accounts.randomness_account)?;
+    state.tie_count = tied_count as u8;
+
+    emit!(TieBreakRequested {
+        randomness_account: state.pending_randomness,
+        seed_slot: state.randomness_seed_slot,
+        tied_count: tied_count as u8,
+    });
+
+    Ok(())
+}
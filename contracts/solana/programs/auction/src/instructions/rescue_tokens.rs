@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::TokensRescued;
+use crate::state::{AcceptedMints, AuctionState};
+use crate::transfer::transfer_checked;
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// Any token account the auction state controls, such as its associated
+    /// token account for a mint someone sent it by mistake.
+    #[account(mut, token::mint = mint, token::authority = auction_state)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The accepted-mints PDA, which need not exist. When it does its
+    /// mints are refused, so it cannot be left out to slip one through.
+    #[account(seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump)]
+    pub accepted_mints: UncheckedAccount<'info>,
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Moves `amount` of a stray mint out of an account the auction controls.
+/// The auction mint, accepted mints and an escrowed prize's mint are refused,
+/// so bidder funds and the prize can never leave this way. Transfer-hook
+/// accounts for the mint, if it has a hook, go in `remaining_accounts`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RescueTokens<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let mint = ctx.accounts.mint.key();
+    require!(
+        mint != state.usdc_mint && !(mint == state.prize_mint && state.prize_amount > 0),
+        AuctionError::MintNotRescuable
+    );
+    if !ctx.accounts.accepted_mints.data_is_empty() {
+        let data = ctx.accounts.accepted_mints.try_borrow_data()?;
+        let accepted = AcceptedMints::try_deserialize(&mut &data[..])?;
+        require!(accepted.get(&mint).is_none(), AuctionError::MintNotRescuable);
+    }
+
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.source.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(TokensRescued {
+        mint,
+        source: ctx.accounts.source.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::event::SettlementResolved;
+use crate::memo::write_memo;
+use crate::payout::Payout;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct ResolveSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = escrow,
+        has_one = treasury,
+        has_one = usdc_mint,
+        constraint = auction_state.held_amount > 0 @ AuctionError::NoHeldSettlement,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The arbiter; or, to release an undisputed settlement once its window
+    /// has passed, anyone.
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Receives a refund; required when refunding.
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state.held_winner,
+    )]
+    pub winner_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The curator's associated token account; required on release when a
+    /// curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winner's referrer's associated token account; required on
+    /// release when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Ends the held settlement. A refund returns the price to the winner and is
+/// the arbiter's call; a release splits it as `settle` would have, and is
+/// open to anyone once an undisputed window has passed. Shares use the rates
+/// in force now, not at settlement.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveSettlement<'info>>,
+    refund: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.auction_state;
+    if ctx.accounts.authority.key() != state.arbiter {
+        require!(!refund && !state.disputed, AuctionError::OnlyArbiter);
+        require!(now >= state.held_until, AuctionError::DisputeWindowOpen);
+    }
+
+    let amount = state.held_amount;
+    let winner = state.held_winner;
+    let referrer = state.held_referrer;
+    let discount_bps = state.held_discount_bps;
+    state.held_amount = 0;
+    state.held_winner = Pubkey::default();
+    state.held_referrer = Pubkey::default();
+    state.held_until = 0;
+    state.held_discount_bps = 0;
+    state.disputed = false;
+    state.debit_escrow(amount)?;
+    if refund {
+        state.total_volume = state.total_volume.saturating_sub(u128::from(amount));
+    }
+
+    write_memo(&ctx.accounts.auction_state, ctx.accounts.memo_program.as_ref())?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let mut payout = Payout {
+        auction_state: &mut ctx.accounts.auction_state,
+        escrow: &ctx.accounts.escrow,
+        treasury: &ctx.accounts.treasury,
+        usdc_mint: &ctx.accounts.usdc_mint,
+        token_program: &ctx.accounts.token_program,
+        winner_usdc: ctx.accounts.winner_usdc.as_ref(),
+        charity: ctx.accounts.charity.as_ref(),
+        curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+        referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+        memo_program: ctx.accounts.memo_program.as_ref(),
+        accepted_mints: None,
+        hook_accounts: hooks,
+    };
+    if refund {
+        let winner_usdc = payout
+            .winner_usdc
+            .ok_or(AuctionError::WinnerTokenAccountRequired)?;
+        payout.transfer(winner_usdc, amount)?;
+    } else {
+        payout.distribute(winner, referrer, discount_bps, amount)?;
+    }
+
+    emit!(SettlementResolved {
+        winner,
+        amount,
+        refunded: refund,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::TieResolved;
+use crate::randomness;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct ResolveTie<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// CHECK: Must be the committed randomness account; checked and parsed in `randomness::reveal`.
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+/// Permissionless: consumes the revealed value and moves the drawn bidder to
+/// the front of the tied group, making them the leader. The draw is among
+/// the bids tied when it was requested, and fails if that tie has changed.
+pub fn handler(ctx: Context<ResolveTie>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+
+    let tied_count = state.tie_count as usize;
+    require!(tied_count > 1, AuctionError::NoTie);
+    require!(
+        state.leaderboard.tied_at_top() == tied_count,
+        AuctionError::TieChanged
+    );
+
+    let draw = randomness::reveal(state, &ctx.accounts.randomness_account)?;
+    state.tie_count = 0;
+    state.leaderboard.promote((draw % tied_count as u64) as usize);
+
+    let winner = state.leaderboard.entries()[0];
+    state.set_leader(winner.bidder, winner.bid_index);
+    state.leading_amount = winner.amount;
+
+    emit!(TieResolved {
+        randomness_account: ctx.accounts.randomness_account.key(),
+        winner: winner.bidder,
+        amount: winner.amount,
+        tied_count: tied_count as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::BidRevealed;
+use crate::leader::auto_escalate;
+use crate::state::{sealed_commitment, AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.commitment != [0; 32] @ AuctionError::BidNotSealed,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// Takes back whatever the deposit covered beyond the amount.
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Current leader's bid, required whenever someone else holds the lead.
+    #[account(mut)]
+    pub leader_bid: Option<Account<'info, Bid>>,
+}
+
+/// Opens a sealed bid at `amount` during the reveal window, refunding the
+/// rest of its deposit and its commit bond. From then on it is an ordinary
+/// bid of `amount`, ranked by the time it was placed rather than revealed.
+/// Bids left sealed never take the lead, and are refunded without their
+/// bond after settlement.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevealBid<'info>>,
+    amount: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let state = &mut ctx.accounts.auction_state;
+    let bid = &mut ctx.accounts.bid;
+    require!(state.revealing(clock.unix_timestamp), AuctionError::NotRevealing);
+    require!(
+        sealed_commitment(&bid.bidder, amount, &salt) == bid.commitment,
+        AuctionError::CommitmentMismatch
+    );
+    require!(amount >= state.minimum_bid, AuctionError::BidTooLow);
+    let excess = bid
+        .max_amount
+        .checked_sub(amount)
+        .ok_or(AuctionError::InsufficientEscrow)?;
+    let refund = excess + bid.commit_bond;
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                authority: state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+    state.accrue_points(bid, clock.unix_timestamp)?;
+    state.debit_escrow(refund)?;
+    state.commit_bonds_held = state
+        .commit_bonds_held
+        .checked_sub(bid.commit_bond)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    bid.commit_bond = 0;
+    bid.commitment = [0; 32];
+    bid.amount = amount;
+    bid.max_amount = amount;
+    bid.updated_slot = clock.slot;
+
+    auto_escalate(
+        state,
+        ctx.accounts.bidder.key(),
+        bid,
+        ctx.accounts.leader_bid.as_mut(),
+    )?;
+    emit!(BidRevealed {
+        bidder: bid.bidder,
+        bid_index: bid.bid_index,
+        amount,
+    });
+    Ok(())
+}
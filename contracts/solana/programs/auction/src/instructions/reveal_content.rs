@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::ContentRevealed;
+use crate::state::{AuctionState, ContentReveal, MAX_CONTENT_URI_LEN};
+
+#[derive(Accounts)]
+pub struct RevealContent<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = auction_state.content_hash != [0; 32] @ AuctionError::ContentNotCommitted,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + ContentReveal::INIT_SPACE,
+        seeds = [b"content", auction_state.id_seed().as_slice(), auction_state.content_hash.as_ref()],
+        bump,
+    )]
+    pub content_reveal: Account<'info, ContentReveal>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Publishes the committed deliverable's URI once bidding has closed,
+/// checking it against the commitment made while it was open.
+pub fn handler(ctx: Context<RevealContent>, uri: String) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        state.is_settled() || state.has_ended(now),
+        AuctionError::AuctionNotEnded
+    );
+    require!(
+        uri.len() <= MAX_CONTENT_URI_LEN,
+        AuctionError::ContentUriTooLong
+    );
+
+    let content_hash = state.content_hash;
+    require!(
+        ContentReveal::hash_uri(&uri) == content_hash,
+        AuctionError::ContentHashMismatch
+    );
+
+    let reveal = &mut ctx.accounts.content_reveal;
+    reveal.content_hash = content_hash;
+    reveal.uri = uri.clone();
+    reveal.revealed_at = now;
+    reveal.bump = ctx.bumps.content_reveal;
+
+    emit!(ContentRevealed { content_hash, uri });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::error::AuctionError;
+use crate::scheduler::{self, CreateThread, Trigger, THREAD_PROGRAM_ID};
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct ScheduleSettlement<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// Pays for the thread and funds its fees.
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    /// CHECK: This round's settlement thread, created by the thread program.
+    #[account(
+        mut,
+        address = scheduler::thread_address(
+            &auction_state.key(),
+            &scheduler::settle_thread_id(auction_state.current_round()),
+        ) @ AuctionError::InvalidThread,
+    )]
+    pub thread: UncheckedAccount<'info>,
+    /// CHECK: The Clockwork thread program.
+    #[account(address = THREAD_PROGRAM_ID @ AuctionError::InvalidThread)]
+    pub thread_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a thread, owned by the auction state, that settles this round at
+/// `end_time` without an off-chain bot. `amount` lamports fund the workers'
+/// fees. The thread calls `plan_settlement`, which answers with the
+/// `crank_settle` to run; the worker pays that crank's rent.
+pub fn handler(ctx: Context<ScheduleSettlement>, amount: u64) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    require!(state.end_time != 0, AuctionError::SettlementNotSchedulable);
+
+    let plan = Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::PlanSettlement {
+            auction_state: state.key(),
+            winning_bid: None,
+            usdc_mint: None,
+            prize_mint: None,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::PlanSettlement {}.data(),
+    };
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+    CreateThread {
+        thread_program: ctx.accounts.thread_program.to_account_info(),
+        authority: state.to_account_info(),
+        payer: ctx.accounts.agent.to_account_info(),
+        thread: ctx.accounts.thread.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    }
+    .create(
+        signer_seeds,
+        scheduler::settle_thread_id(state.current_round()),
+        amount,
+        plan,
+        Trigger {
+            unix_ts: state.end_time,
+        },
+    )
+}
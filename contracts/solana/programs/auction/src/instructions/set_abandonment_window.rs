@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetAbandonmentWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Lets anyone call `mark_abandoned` once the auction has gone unsettled for
+/// `abandonment_window` seconds past its deadline; zero turns that off.
+pub fn handler(ctx: Context<SetAbandonmentWindow>, abandonment_window: i64) -> Result<()> {
+    require!(
+        abandonment_window >= 0,
+        AuctionError::InvalidAbandonmentWindow
+    );
+    ctx.accounts.auction_state.abandonment_window = abandonment_window;
+    Ok(())
+}
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
 
 use crate::error::AuctionError;
-use crate::state::AuctionState;
+use crate::event::AgentChanged;
+use crate::state::{audit_action, record_admin_action, AuctionState, AuditLog};
 
 #[derive(Accounts)]
 pub struct SetAgent<'info> {
     #[account(
         mut,
-        seeds = [b"auction_state"],
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
         bump = auction_state.bump,
         has_one = agent @ AuctionError::OnlyAgent,
     )]
@@ -15,9 +16,25 @@ pub struct SetAgent<'info> {
     pub agent: Signer<'info>,
     /// CHECK: The new agent address — no constraints needed, just stored.
     pub new_agent: UncheckedAccount<'info>,
+    /// Required once `initialize_audit_log` has run.
+    #[account(mut, seeds = [b"audit_log", auction_state.id_seed().as_slice()], bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
 }
 
 pub fn handler(ctx: Context<SetAgent>) -> Result<()> {
-    ctx.accounts.auction_state.agent = ctx.accounts.new_agent.key();
-    Ok(())
+    let state = &mut ctx.accounts.auction_state;
+    let old_agent = state.agent;
+    state.agent = ctx.accounts.new_agent.key();
+    emit!(AgentChanged {
+        old_agent,
+        new_agent: state.agent,
+    });
+    record_admin_action(
+        state,
+        ctx.accounts.audit_log.as_ref(),
+        ctx.accounts.agent.key(),
+        audit_action::AGENT_CHANGED,
+        old_agent.to_bytes(),
+        state.agent.to_bytes(),
+    )
 }
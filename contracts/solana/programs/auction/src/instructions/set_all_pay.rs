@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetAllPay<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Switches all-pay mode. Only allowed while no bid is escrowed, so nobody
+/// ends up bound by rules they did not bid under.
+pub fn handler(ctx: Context<SetAllPay>, enabled: bool) -> Result<()> {
+    ctx.accounts.auction_state.set_all_pay(enabled)
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetBidIncrement<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetBidIncrement>, bid_increment: u64) -> Result<()> {
+    ctx.accounts.auction_state.bid_increment = bid_increment;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BidTtlChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetBidTtl<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Expires bids left untouched for `bid_ttl` seconds: they can no longer
+/// win, and anyone may refund them with `expire_bid`. Zero turns expiry off.
+pub fn handler(ctx: Context<SetBidTtl>, bid_ttl: i64) -> Result<()> {
+    require!(bid_ttl >= 0, AuctionError::InvalidBidTtl);
+    let state = &mut ctx.accounts.auction_state;
+    emit!(BidTtlChanged {
+        old_bid_ttl: state.bid_ttl,
+        new_bid_ttl: bid_ttl,
+    });
+    state.bid_ttl = bid_ttl;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BuyNowPriceChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetBuyNowPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetBuyNowPrice>, buy_now_price: u64) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    emit!(BuyNowPriceChanged {
+        old_buy_now_price: state.buy_now_price,
+        new_buy_now_price: buy_now_price,
+    });
+    state.buy_now_price = buy_now_price;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::CandleWindowChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetCandleWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Turns the round into a candle auction: once it ends, randomness picks
+/// when within the last `candle_window` seconds it really closed, so bidding
+/// at the last second buys nothing. Zero turns the mode off.
+pub fn handler(ctx: Context<SetCandleWindow>, candle_window: i64) -> Result<()> {
+    require!(candle_window >= 0, AuctionError::InvalidCandleWindow);
+    let state = &mut ctx.accounts.auction_state;
+    emit!(CandleWindowChanged {
+        old_candle_window: state.candle_window,
+        new_candle_window: candle_window,
+    });
+    state.candle_window = candle_window;
+    Ok(())
+}
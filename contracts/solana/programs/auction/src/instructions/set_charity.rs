@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct SetCharity<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        has_one = usdc_mint,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub agent: Signer<'info>,
+    /// Omit to disable the charity split.
+    #[account(token::mint = usdc_mint)]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<SetCharity>, charity_bps: u16) -> Result<()> {
+    require!(
+        u64::from(charity_bps) <= BPS_DENOMINATOR,
+        AuctionError::InvalidBps
+    );
+
+    let state = &mut ctx.accounts.auction_state;
+    match &ctx.accounts.charity {
+        Some(charity) => {
+            state.charity = charity.key();
+            state.charity_bps = charity_bps;
+        }
+        None => {
+            state.charity = Pubkey::default();
+            state.charity_bps = 0;
+        }
+    }
+    require!(state.shares_fit(), AuctionError::InvalidBps);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// The top bidder must `claim_win` within `claim_window` seconds of the
+/// deadline before the round settles; otherwise `promote_next_bidder` passes
+/// the slot on. Zero settles without a claim.
+pub fn handler(ctx: Context<SetClaimWindow>, claim_window: i64) -> Result<()> {
+    require!(claim_window >= 0, AuctionError::InvalidClaimWindow);
+    ctx.accounts.auction_state.claim_window = claim_window;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetCommitBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Sets the bond sealed bids escrow from now on, so flooding the round with
+/// commitments nobody means to reveal has a price. Bids already placed keep
+/// the bond they paid.
+pub fn handler(ctx: Context<SetCommitBond>, commit_bond: u64) -> Result<()> {
+    ctx.accounts.auction_state.commit_bond = commit_bond;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::CuratorChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetCurator<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Names the round's guest curator, who is paid `curator_bps` of every
+/// settlement but gets no say over the auction. `Pubkey::default()` removes
+/// the curator. Together with the other shares it may not exceed the price.
+pub fn handler(ctx: Context<SetCurator>, curator: Pubkey, curator_bps: u16) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let curator_bps = if curator == Pubkey::default() { 0 } else { curator_bps };
+    state.curator = curator;
+    state.curator_bps = curator_bps;
+    require!(state.shares_fit(), AuctionError::InvalidBps);
+
+    emit!(CuratorChanged {
+        curator,
+        curator_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct SetDefaultPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Share of a defaulting winner's escrow sent to the treasury when
+/// `forfeit_deposit` or `promote_next_bidder` passes their slot on; the rest
+/// is refunded.
+pub fn handler(ctx: Context<SetDefaultPenalty>, default_penalty_bps: u16) -> Result<()> {
+    require!(
+        u64::from(default_penalty_bps) <= BPS_DENOMINATOR,
+        AuctionError::InvalidBps
+    );
+    ctx.accounts.auction_state.default_penalty_bps = default_penalty_bps;
+    Ok(())
+}
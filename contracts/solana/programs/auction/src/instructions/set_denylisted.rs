@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::DenylistChanged;
+use crate::state::{AuctionState, Denylist};
+
+#[derive(Accounts)]
+pub struct SetDenylisted<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + Denylist::INIT_SPACE,
+        seeds = [b"denylist", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub denylist: Account<'info, Denylist>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Bars `wallet` from placing or raising bids, or lifts the bar. Bids it
+/// already holds are untouched; `force_refund` returns them.
+pub fn handler(ctx: Context<SetDenylisted>, wallet: Pubkey, denied: bool) -> Result<()> {
+    let denylist = &mut ctx.accounts.denylist;
+    denylist.bump = ctx.bumps.denylist;
+    if denied {
+        denylist.insert(wallet)?;
+    } else {
+        denylist.remove(&wallet);
+    }
+    ctx.accounts.auction_state.denylist_enabled = true;
+
+    emit!(DenylistChanged { wallet, denied });
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct SetDepositTerms<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Future bids escrow `deposit_bps` of their amount; the winner then has
+/// `payment_window` seconds after the deadline to pay the rest. Zero bps
+/// returns to full escrow. Existing bids keep the terms they were placed on.
+pub fn handler(ctx: Context<SetDepositTerms>, deposit_bps: u16, payment_window: i64) -> Result<()> {
+    require!(
+        u64::from(deposit_bps) <= BPS_DENOMINATOR,
+        AuctionError::InvalidBps
+    );
+    require!(
+        deposit_bps == 0 || payment_window > 0,
+        AuctionError::InvalidPaymentWindow
+    );
+
+    let state = &mut ctx.accounts.auction_state;
+    state.deposit_bps = deposit_bps;
+    state.payment_window = payment_window;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::DisputeTermsChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetDisputeTerms<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Holds future settlements in escrow for `dispute_window` seconds, during
+/// which `arbiter` can refund the winner. Zero pays settlements out at once;
+/// a settlement already held keeps its deadline.
+pub fn handler(ctx: Context<SetDisputeTerms>, arbiter: Pubkey, dispute_window: i64) -> Result<()> {
+    require!(dispute_window >= 0, AuctionError::InvalidDisputeWindow);
+    require!(
+        dispute_window == 0 || arbiter != Pubkey::default(),
+        AuctionError::ArbiterRequired
+    );
+
+    let state = &mut ctx.accounts.auction_state;
+    state.arbiter = arbiter;
+    state.dispute_window = dispute_window;
+
+    emit!(DisputeTermsChanged {
+        arbiter,
+        dispute_window,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetEndTime<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Scheduling a new deadline also reopens an auction closed by `buy_now`,
+/// allows a fresh raffle draw or candle end, and retires any posted refunds
+/// root. Reopening a closed round drops its content commitment so the next
+/// one can commit. An abandoned round stays closed.
+pub fn handler(ctx: Context<SetEndTime>, end_time: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.auction_state.reschedule(end_time, now)
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::EnglishAuctionChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetEnglishAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Switches English mode on or off. Locked while several bids are escrowed,
+/// since turning it on then would leave losing bids the mode never refunds.
+pub fn handler(ctx: Context<SetEnglishAuction>, enabled: bool) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    require!(state.active_bid_count <= 1, AuctionError::EnglishModeLocked);
+    require!(!(enabled && state.all_pay), AuctionError::AllPayLocked);
+    require!(
+        !(enabled && state.takes_sealed_bids()),
+        AuctionError::SealedBidsLocked
+    );
+    emit!(EnglishAuctionChanged {
+        old_english_auction: state.english_auction,
+        new_english_auction: enabled,
+    });
+    state.english_auction = enabled;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::{AuctionState, MAX_MEMO_LEN};
+
+#[derive(Accounts)]
+pub struct SetMemo<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Attaches `memo`, such as an invoice or round reference, to every
+/// settlement and refund transfer from now on. An empty memo stops it.
+pub fn handler(ctx: Context<SetMemo>, memo: String) -> Result<()> {
+    let bytes = memo.as_bytes();
+    require!(bytes.len() <= MAX_MEMO_LEN, AuctionError::MemoTooLong);
+
+    let state = &mut ctx.accounts.auction_state;
+    state.memo = [0; MAX_MEMO_LEN];
+    state.memo[..bytes.len()].copy_from_slice(bytes);
+    state.memo_len = bytes.len() as u8;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetMinSettleSlots<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Makes settlement wait `min_settle_slots` slots after the winning bid last
+/// changed, so the agent cannot settle in the slot a colluding bidder moves
+/// their bid. Zero allows it.
+pub fn handler(ctx: Context<SetMinSettleSlots>, min_settle_slots: u64) -> Result<()> {
+    ctx.accounts.auction_state.min_settle_slots = min_settle_slots;
+    Ok(())
+}
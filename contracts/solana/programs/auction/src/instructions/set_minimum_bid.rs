@@ -1,21 +1,38 @@
 use anchor_lang::prelude::*;
 
 use crate::error::AuctionError;
-use crate::state::AuctionState;
+use crate::event::MinimumBidChanged;
+use crate::state::{audit_action, record_admin_action, AuctionState, AuditEntry, AuditLog};
 
 #[derive(Accounts)]
 pub struct SetMinimumBid<'info> {
     #[account(
         mut,
-        seeds = [b"auction_state"],
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
         bump = auction_state.bump,
         has_one = agent @ AuctionError::OnlyAgent,
     )]
     pub auction_state: Account<'info, AuctionState>,
     pub agent: Signer<'info>,
+    /// Required once `initialize_audit_log` has run.
+    #[account(mut, seeds = [b"audit_log", auction_state.id_seed().as_slice()], bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
 }
 
 pub fn handler(ctx: Context<SetMinimumBid>, minimum_bid: u64) -> Result<()> {
-    ctx.accounts.auction_state.minimum_bid = minimum_bid;
-    Ok(())
+    let state = &mut ctx.accounts.auction_state;
+    let old_minimum = state.minimum_bid;
+    state.minimum_bid = minimum_bid;
+    emit!(MinimumBidChanged {
+        old_minimum_bid: old_minimum,
+        new_minimum_bid: minimum_bid,
+    });
+    record_admin_action(
+        state,
+        ctx.accounts.audit_log.as_ref(),
+        ctx.accounts.agent.key(),
+        audit_action::MINIMUM_BID_CHANGED,
+        AuditEntry::amount(old_minimum),
+        AuditEntry::amount(minimum_bid),
+    )
 }
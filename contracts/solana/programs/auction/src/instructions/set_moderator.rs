@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::ModeratorChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetModerator<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Lets `moderator` slash spam bids, and has every new bid lock `bid_bond`
+/// lamports for it to slash. Bids keep the bond they were placed with.
+pub fn handler(ctx: Context<SetModerator>, moderator: Pubkey, bid_bond: u64) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    state.moderator = moderator;
+    state.bid_bond = bid_bond;
+
+    emit!(ModeratorChanged {
+        moderator,
+        bid_bond,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::NumWinnersChanged;
+use crate::state::{AuctionState, MAX_WINNERS};
+
+#[derive(Accounts)]
+pub struct SetNumWinners<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetNumWinners>, num_winners: u8) -> Result<()> {
+    require!(
+        num_winners >= 1 && num_winners as usize <= MAX_WINNERS,
+        AuctionError::InvalidNumWinners
+    );
+    let state = &mut ctx.accounts.auction_state;
+    emit!(NumWinnersChanged {
+        old_num_winners: state.num_winners,
+        new_num_winners: num_winners,
+    });
+    state.num_winners = num_winners;
+    Ok(())
+}
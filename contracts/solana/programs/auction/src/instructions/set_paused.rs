@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::{Paused, Unpaused};
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Stops or resumes new bids and raises, announcing the switch with `Paused`
+/// or `Unpaused`. Withdrawals, refunds and settlement carry on while paused.
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    if state.paused == paused {
+        return Ok(());
+    }
+    state.paused = paused;
+    let auction_id = state.auction_id;
+    if paused {
+        emit!(Paused { auction_id });
+    } else {
+        emit!(Unpaused { auction_id });
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetPointsRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// Must already exist; see `create_points_mint`.
+    #[account(seeds = [b"points_mint", auction_state.id_seed().as_slice()], bump)]
+    pub points_mint: InterfaceAccount<'info, Mint>,
+    pub agent: Signer<'info>,
+}
+
+/// Sets how many points a bid earns per whole token escrowed for an hour.
+/// The new rate also prices time each bid has held since it last accrued;
+/// zero stops accrual without touching points already earned.
+pub fn handler(ctx: Context<SetPointsRate>, points_rate: u64) -> Result<()> {
+    ctx.accounts.auction_state.points_rate = points_rate;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::RaffleModeChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetRaffleMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRaffleMode>, enabled: bool) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    emit!(RaffleModeChanged {
+        old_raffle_mode: state.raffle_mode,
+        new_raffle_mode: enabled,
+    });
+    state.raffle_mode = enabled;
+    Ok(())
+}
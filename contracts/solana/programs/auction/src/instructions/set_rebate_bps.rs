@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct SetRebateBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Sets the share of the treasury's cut that settlement keeps back as a
+/// consolation rebate, split over losing bids by the escrow they held.
+/// Only prices in the auction mint fund it.
+pub fn handler(ctx: Context<SetRebateBps>, rebate_bps: u16) -> Result<()> {
+    require!(
+        u64::from(rebate_bps) <= BPS_DENOMINATOR,
+        AuctionError::InvalidBps
+    );
+    ctx.accounts.auction_state.rebate_bps = rebate_bps;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetReferralBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Share of a winning price paid to the winner's referrer; zero stops
+/// referral payouts without clearing referrers recorded on bids.
+pub fn handler(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    state.referral_bps = referral_bps;
+    require!(state.shares_fit(), AuctionError::InvalidBps);
+    Ok(())
+}
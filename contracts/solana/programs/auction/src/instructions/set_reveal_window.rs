@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetRevealWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// A nonzero `reveal_window` makes the round take sealed bids only, opened
+/// by `reveal_bid` within that many seconds of the deadline; settlement
+/// waits for the window to close. Zero goes back to open bids. Switching
+/// needs a round with no active bids, and English mode, which must see
+/// every amount as it arrives, rules it out.
+pub fn handler(ctx: Context<SetRevealWindow>, reveal_window: i64) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    require!(reveal_window >= 0, AuctionError::InvalidRevealWindow);
+    require!(
+        state.active_bid_count == 0 && !(reveal_window > 0 && state.english_auction),
+        AuctionError::SealedBidsLocked
+    );
+    state.reveal_window = reveal_window;
+    Ok(())
+}
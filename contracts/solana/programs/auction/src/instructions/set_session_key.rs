@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Approve, Mint, Revoke, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::event::SessionKeySet;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct SetSessionKey<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// The account session raises draw from; the auction state is made its
+    /// delegate for the spend cap.
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Lets `session_key`, typically a throwaway key held by a web app, raise
+/// the bid through `increase_bid` or `update_bid` by up to `spend_cap` in
+/// total until `expires_at`, without the bidder signing each raise. The
+/// auction state becomes the delegate of `bidder_usdc` for `spend_cap`, so
+/// the session key itself can never move the bidder's tokens. A default
+/// key revokes the session. A token account has one delegate, so a new
+/// session on any bid drawing from it replaces the last. The allowance is
+/// also revoked when the bid is withdrawn or closed, and once the session
+/// has expired, the next time the bidder signs an update.
+pub fn handler(
+    ctx: Context<SetSessionKey>,
+    session_key: Pubkey,
+    expires_at: i64,
+    spend_cap: u64,
+) -> Result<()> {
+    let revoked = session_key == Pubkey::default();
+    let (expires_at, spend_cap) = if revoked { (0, 0) } else { (expires_at, spend_cap) };
+    if !revoked {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            AuctionError::SessionKeyExpired
+        );
+    }
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                delegate: ctx.accounts.auction_state.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        ),
+        spend_cap,
+    )?;
+
+    let bid = &mut ctx.accounts.bid;
+    bid.session_key = session_key;
+    bid.session_expires_at = expires_at;
+    bid.session_spend_cap = spend_cap;
+    emit!(SessionKeySet {
+        bidder: bid.bidder,
+        bid_index: bid.bid_index,
+        session_key,
+        expires_at,
+        spend_cap,
+    });
+    Ok(())
+}
+
+/// Revokes the auction state's allowance over `token_account`, left by a
+/// session, signed by its owner `authority`. Does nothing when the account
+/// has another delegate or none.
+pub(crate) fn revoke_session<'info>(
+    auction_state: &Pubkey,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    if token_account.delegate != Some(*auction_state).into() {
+        return Ok(());
+    }
+    token_interface::revoke(CpiContext::new(
+        token_program,
+        Revoke {
+            source: token_account.to_account_info(),
+            authority,
+        },
+    ))
+}
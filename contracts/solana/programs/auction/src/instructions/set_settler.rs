@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::SettlerChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetSettler<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Delegates `settle`, `settle_multi` and `post_refund_root` to `settler`,
+/// a key that can run settlement but change nothing else. The default
+/// pubkey revokes it.
+pub fn handler(ctx: Context<SetSettler>, settler: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let old_settler = state.settler;
+    state.settler = settler;
+
+    emit!(SettlerChanged {
+        old_settler,
+        new_settler: settler,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::StandingChanged;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct SetStanding<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+    )]
+    pub bid: Account<'info, Bid>,
+    pub bidder: Signer<'info>,
+}
+
+/// Opts the bid in or out of standing across rounds. A standing bid that
+/// loses is not refunded by `crank_refund`; it stays escrowed and ranked
+/// when the agent opens the next round, until its bidder withdraws it.
+pub fn handler(ctx: Context<SetStanding>, standing: bool) -> Result<()> {
+    let bid = &mut ctx.accounts.bid;
+    bid.standing = standing;
+    emit!(StandingChanged {
+        bidder: bid.bidder,
+        bid_index: bid.bid_index,
+        standing,
+    });
+    Ok(())
+}
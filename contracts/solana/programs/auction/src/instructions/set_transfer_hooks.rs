@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::TransferHooksChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetTransferHooks<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetTransferHooks>, enabled: bool) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    emit!(TransferHooksChanged {
+        old_transfer_hooks: state.transfer_hooks,
+        new_transfer_hooks: enabled,
+    });
+    state.transfer_hooks = enabled;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::error::AuctionError;
+use crate::event::TreasuryChanged;
+use crate::state::{audit_action, record_admin_action, AuctionState, AuditLog};
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        has_one = usdc_mint,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = usdc_mint)]
+    pub new_treasury: InterfaceAccount<'info, TokenAccount>,
+    pub agent: Signer<'info>,
+    /// Required once `initialize_audit_log` has run.
+    #[account(mut, seeds = [b"audit_log", auction_state.id_seed().as_slice()], bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+pub fn handler(ctx: Context<SetTreasury>) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let old_treasury = state.treasury;
+    state.treasury = ctx.accounts.new_treasury.key();
+
+    emit!(TreasuryChanged {
+        old_treasury,
+        new_treasury: state.treasury,
+    });
+
+    record_admin_action(
+        state,
+        ctx.accounts.audit_log.as_ref(),
+        ctx.accounts.agent.key(),
+        audit_action::TREASURY_CHANGED,
+        old_treasury.to_bytes(),
+        state.treasury.to_bytes(),
+    )
+}
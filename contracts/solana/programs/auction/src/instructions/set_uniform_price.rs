@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::UniformPriceChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetUniformPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetUniformPrice>, enabled: bool) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    emit!(UniformPriceChanged {
+        old_uniform_price: state.uniform_price,
+        new_uniform_price: enabled,
+    });
+    state.uniform_price = enabled;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::VestingDurationChanged;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetVestingDuration<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = auction_state.unclaimed() == 0 @ AuctionError::VestingInProgress,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+/// Streams future settlements to the treasury over `vesting_duration`
+/// seconds; zero restores lump-sum payouts. Fixed while a stream is
+/// outstanding, so the agent cannot speed up funds already promised.
+pub fn handler(ctx: Context<SetVestingDuration>, vesting_duration: i64) -> Result<()> {
+    require!(vesting_duration >= 0, AuctionError::InvalidVestingDuration);
+    let state = &mut ctx.accounts.auction_state;
+    emit!(VestingDurationChanged {
+        old_vesting_duration: state.vesting_duration,
+        new_vesting_duration: vesting_duration,
+    });
+    state.vesting_duration = vesting_duration;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetWithdrawalPenalty>, penalty_bps: u16) -> Result<()> {
+    require!(
+        u64::from(penalty_bps) <= BPS_DENOMINATOR,
+        AuctionError::InvalidBps
+    );
+    ctx.accounts.auction_state.withdrawal_penalty_bps = penalty_bps;
+    Ok(())
+}
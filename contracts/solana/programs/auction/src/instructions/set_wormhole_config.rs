@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+#[derive(Accounts)]
+pub struct SetWormholeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    pub agent: Signer<'info>,
+    /// CHECK: The new relayer address — no constraints needed, just stored.
+    pub relayer: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetWormholeConfig>,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    state.foreign_emitter_chain = emitter_chain;
+    state.foreign_emitter = emitter_address;
+    state.relayer = ctx.accounts.relayer.key();
+    Ok(())
+}
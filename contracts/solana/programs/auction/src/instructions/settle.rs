@@ -1,75 +1,243 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::error::AuctionError;
-use crate::event::BidSettled;
-use crate::state::{AuctionState, Bid};
-use crate::USDC_DECIMALS;
+use crate::payout::Payout;
+use crate::prize::PrizeDelivery;
+use crate::state::{
+    AcceptedMints, AuctionState, AuctionStatus, Bid, PriceFeed, RoundResult, WinReceipt,
+};
 
 #[derive(Accounts)]
 pub struct Settle<'info> {
     #[account(
         mut,
-        seeds = [b"auction_state"],
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
         bump = auction_state.bump,
-        has_one = agent @ AuctionError::OnlyAgent,
-        has_one = treasury,
-        has_one = usdc_mint,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.top_k_paid == 0 @ AuctionError::TopKSettlementPending,
+        constraint = !auction_state.is_abandoned() @ AuctionError::AuctionAbandoned,
+        constraint = !auction_state.candle_pending() @ AuctionError::CandleNotDetermined,
     )]
     pub auction_state: Account<'info, AuctionState>,
     #[account(
         mut,
         constraint = winning_bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.claim_pending(&winning_bid.key()) @ AuctionError::WinNotClaimed,
+        constraint = winning_bid.auction_id == auction_state.auction_id @ AuctionError::WrongAuction,
     )]
     pub winning_bid: Account<'info, Bid>,
+    /// Escrow, treasury and mint of the winning bid; checked by `Payout`.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = usdc_mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The agent, or the settler it delegated settlement to.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Receives the unused part of a proxy bid's escrow.
     #[account(
         mut,
         token::mint = usdc_mint,
-        token::authority = auction_state,
-        seeds = [b"escrow"],
-        bump = auction_state.escrow_bump,
+        token::authority = winning_bid.bidder,
     )]
-    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub winner_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Prize vault, mint, the winner's prize token account and the prize's
+    /// token program; required when the agent has escrowed a prize.
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_state.id_seed().as_slice(), auction_state.prize_mint.as_ref()],
+        bump,
+    )]
+    pub prize_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = auction_state.prize_mint)]
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
     #[account(mut)]
-    pub treasury: InterfaceAccount<'info, TokenAccount>,
-    pub usdc_mint: InterfaceAccount<'info, Mint>,
-    pub agent: Signer<'info>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub winner_prize: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub prize_token_program: Option<Interface<'info, TokenInterface>>,
+    /// Required when the winning bid is in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// The curator's associated token account for the bid mint; required
+    /// when a curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bidder's referrer's associated token account for the bid
+    /// mint; required when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Archive of the round being settled; see `RoundResult`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoundResult::INIT_SPACE,
+        seeds = [b"round_result", auction_state.id_seed().as_slice(), &auction_state.next_round_seed()],
+        bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+    /// The winner's proof of this win; the authority pays its rent.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WinReceipt::INIT_SPACE,
+        seeds = [
+            b"win_receipt",
+            auction_state.id_seed().as_slice(),
+            &auction_state.next_round_seed(),
+            winning_bid.bidder.as_ref(),
+        ],
+        bump,
+    )]
+    pub win_receipt: Account<'info, WinReceipt>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
 }
 
-pub fn handler(ctx: Context<Settle>) -> Result<()> {
-    let state = &mut ctx.accounts.auction_state;
-    let winning_bid = &mut ctx.accounts.winning_bid;
+/// Settles to a winner the caller names. `remaining_accounts` starts with
+/// every other active bid, sorted by address, so the program can check that
+/// none outbids the claimed winner; transfer-hook accounts, when enabled,
+/// follow them. In a candle round only bids in before the candle end count.
+/// A raffle settles only once drawn, to the drawn winner, with no rivals.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Settle<'info>>) -> Result<()> {
+    require!(
+        !ctx.accounts.auction_state.raffle_mode || ctx.accounts.auction_state.raffle_drawn,
+        AuctionError::RaffleNotDrawn
+    );
+    ctx.accounts
+        .auction_state
+        .transition(AuctionStatus::Settled, Clock::get()?.unix_timestamp)?;
+    let price = ctx.accounts.winning_bid.amount;
+    let (rivals, extra) = split_rivals(&ctx.accounts.auction_state, ctx.remaining_accounts)?;
+    verify_winner(&ctx.accounts.auction_state, &ctx.accounts.winning_bid, rivals)?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(extra);
+    Payout {
+        auction_state: &mut ctx.accounts.auction_state,
+        escrow: &ctx.accounts.escrow,
+        treasury: &ctx.accounts.treasury,
+        usdc_mint: &ctx.accounts.usdc_mint,
+        token_program: &ctx.accounts.token_program,
+        winner_usdc: ctx.accounts.winner_usdc.as_ref(),
+        charity: ctx.accounts.charity.as_ref(),
+        curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+        referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+        memo_program: ctx.accounts.memo_program.as_ref(),
+        accepted_mints: ctx.accounts.accepted_mints.as_deref_mut(),
+        hook_accounts: hooks,
+    }
+    .settle(&mut ctx.accounts.winning_bid, price)?;
 
-    winning_bid.active = false;
+    let winner = ctx.accounts.winning_bid.bidder;
+    PrizeDelivery {
+        auction_state: &mut ctx.accounts.auction_state,
+        prize_vault: ctx.accounts.prize_vault.as_ref(),
+        prize_mint: ctx.accounts.prize_mint.as_ref(),
+        winner_prize: ctx.accounts.winner_prize.as_ref(),
+        prize_token_program: ctx.accounts.prize_token_program.as_ref(),
+        hook_accounts: hooks,
+    }
+    .deliver(winner)?;
 
-    state.active_bid_count = state
-        .active_bid_count
-        .checked_sub(1)
-        .ok_or(AuctionError::ArithmeticOverflow)?;
-
-    let state_bump = state.bump;
-    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &[state_bump]]];
-
-    token_interface::transfer_checked(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
-                authority: ctx.accounts.auction_state.to_account_info(),
-                mint: ctx.accounts.usdc_mint.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        winning_bid.amount,
-        USDC_DECIMALS,
+    ctx.accounts.round_result.record(
+        &ctx.accounts.auction_state,
+        winner,
+        price,
+        1,
+        ctx.bumps.round_result,
     )?;
+    ctx.accounts.win_receipt.issue(
+        &ctx.accounts.auction_state,
+        &ctx.accounts.winning_bid,
+        price,
+        ctx.bumps.win_receipt,
+    )?;
+    ctx.accounts
+        .price_feed
+        .publish(&ctx.accounts.round_result, price, ctx.bumps.price_feed);
+    Ok(())
+}
+
+/// Splits `remaining_accounts` into every active bid other than the winner,
+/// counted from `active_bid_count`, and the accounts that follow them. A
+/// drawn raffle takes no rivals.
+pub(crate) fn split_rivals<'a, 'info>(
+    state: &AuctionState,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    if state.raffle_drawn {
+        return Ok((&[], remaining_accounts));
+    }
+    let rival_count = state.active_bid_count.saturating_sub(1) as usize;
+    require!(
+        remaining_accounts.len() >= rival_count,
+        AuctionError::RivalBidsMismatch
+    );
+    Ok(remaining_accounts.split_at(rival_count))
+}
 
-    emit!(BidSettled {
-        winner: winning_bid.bidder,
-        amount: winning_bid.amount,
-    });
+/// Fails unless `rivals` are distinct active bids other than the winner, none
+/// of them higher among those in before the candle end. A rival tied with the
+/// winner has priority if it changed its bid first; see
+/// `LeaderboardEntry::outranks`. Sorting by address
+/// makes duplicates detectable, and the caller sized the slice from
+/// `active_bid_count`, so none can be left out. A drawn raffle's winner is
+/// its sole leaderboard entry, whatever the rivals bid.
+pub(crate) fn verify_winner<'info>(
+    state: &AuctionState,
+    winning_bid: &Account<'info, Bid>,
+    rivals: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(state.counts_at_candle(winning_bid), AuctionError::BidAfterCandle);
+    // The leaderboard top stands against any tie: it changed its bid first,
+    // or, among bids changed in the same second, arrived first or won a
+    // `resolve_tie` draw.
+    let heads_leaderboard = state
+        .leaderboard
+        .entries()
+        .first()
+        .is_some_and(|top| top.is(winning_bid.bidder, winning_bid.bid_index));
+    if state.raffle_drawn {
+        require!(heads_leaderboard, AuctionError::WinnersMismatch);
+        return Ok(());
+    }
+    let mut previous_key: Option<Pubkey> = None;
+    for info in rivals {
+        require!(
+            previous_key.is_none_or(|key| info.key() > key) && info.key() != winning_bid.key(),
+            AuctionError::RivalBidsMismatch
+        );
+        previous_key = Some(info.key());
 
+        let rival = Account::<Bid>::try_from(info)?;
+        require!(
+            rival.active && rival.auction_id == state.auction_id,
+            AuctionError::RivalBidsMismatch
+        );
+        if !state.counts_at_candle(&rival) {
+            continue;
+        }
+        require!(rival.amount <= winning_bid.amount, AuctionError::WinnerNotHighest);
+        require!(
+            heads_leaderboard || winning_bid.rank().outranks(&rival.rank()),
+            AuctionError::TieLostOnPriority
+        );
+    }
     Ok(())
 }
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::BundleSettled;
+use crate::state::{AuctionState, BundleBid, Lot};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct SettleBundle<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+        has_one = treasury,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bundle_bid", auction_state.id_seed().as_slice(), bidder.key().as_ref()],
+        bump = bundle_bid.bump,
+    )]
+    pub bundle_bid: Account<'info, BundleBid>,
+    /// The bundle's bidder, who gets its rent back.
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    /// Refunded the bundle's escrow if it loses.
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The agent, or the settler it delegated settlement to.
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Decides a bundle bid once bidding has closed. It wins when none of its
+/// lots has settled yet, it beats the sum of their leading bids, and it
+/// meets their reserves together: each lot is then settled to the bundle's
+/// bidder, its own bids left to withdraw, and the bundle's amount goes to the
+/// treasury. Otherwise, and whenever the round was called off, the bundle is
+/// refunded. `remaining_accounts` starts with the bundle's `Lot` accounts in
+/// its order; transfer-hook accounts, when enabled, follow them. Bundles
+/// that share a lot are decided in the order they are settled.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBundle<'info>>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let called_off = state.status.is_called_off();
+    if !called_off {
+        require!(
+            state.end_time != 0 && state.has_ended(Clock::get()?.unix_timestamp),
+            AuctionError::AuctionNotEnded
+        );
+    }
+    let bundle = &ctx.accounts.bundle_bid;
+    require!(
+        ctx.remaining_accounts.len() >= bundle.lots.len(),
+        AuctionError::InvalidBundle
+    );
+    let (lot_infos, extra) = ctx.remaining_accounts.split_at(bundle.lots.len());
+
+    let mut lots = Vec::with_capacity(lot_infos.len());
+    let (mut leading, mut reserves) = (0u64, 0u64);
+    let mut available = !called_off;
+    for (lot_id, info) in bundle.lots.iter().zip(lot_infos) {
+        let lot = Account::<Lot>::try_from(info)?;
+        require!(
+            lot.auction_id == state.auction_id && lot.lot_id == *lot_id,
+            AuctionError::InvalidBundle
+        );
+        available &= !lot.settled;
+        leading = leading
+            .checked_add(lot.leading_amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        reserves = reserves
+            .checked_add(lot.reserve_price)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        lots.push(lot);
+    }
+    let won = available && bundle.amount > leading && bundle.amount >= reserves;
+    let amount = bundle.amount;
+    let bidder = bundle.bidder;
+
+    for lot in lots.iter_mut() {
+        lot.pending_bundles = lot
+            .pending_bundles
+            .checked_sub(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        if won {
+            lot.settled = true;
+            lot.winner = bidder;
+            lot.leader = Pubkey::default();
+            lot.leading_amount = 0;
+        }
+        lot.exit(&crate::ID)?;
+    }
+
+    let to = if won {
+        ctx.accounts.treasury.to_account_info()
+    } else {
+        ctx.accounts.bidder_usdc.to_account_info()
+    };
+    let state_bump = state.bump;
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+    let hooks = state.hook_accounts(extra);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to,
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.active_lot_bid_count = state
+        .active_lot_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.debit_escrow(amount)?;
+    if won {
+        state.total_volume = state.total_volume.saturating_add(u128::from(amount));
+    }
+
+    emit!(BundleSettled {
+        bidder,
+        amount,
+        won,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::LotSettled;
+use crate::state::{AuctionState, Lot, LotBid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct SettleLot<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+        has_one = treasury,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [b"lot", auction_state.id_seed().as_slice(), &lot.lot_seed()],
+        bump = lot.bump,
+        constraint = !lot.settled @ AuctionError::LotSettled,
+        constraint = lot.pending_bundles == 0 @ AuctionError::BundlePending,
+    )]
+    pub lot: Account<'info, Lot>,
+    /// The lot's leading bid and its bidder, who gets the bid's rent back;
+    /// required unless the lot goes unsold.
+    #[account(
+        mut,
+        seeds = [b"lot_bid", auction_state.id_seed().as_slice(), &lot.lot_seed(), lot.leader.as_ref()],
+        bump = winning_bid.bump,
+    )]
+    pub winning_bid: Option<Account<'info, LotBid>>,
+    #[account(mut, address = lot.leader)]
+    pub winner: Option<SystemAccount<'info>>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The agent, or the settler it delegated settlement to.
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sells a lot once bidding has closed, independently of the round's main
+/// item and of other lots. A leader at or above the reserve pays its bid to
+/// the treasury; otherwise the lot closes unsold and all its bids can be
+/// withdrawn.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleLot<'info>>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        state.end_time != 0 && state.has_ended(now),
+        AuctionError::AuctionNotEnded
+    );
+    state.require_not_called_off()?;
+
+    let lot = &mut ctx.accounts.lot;
+    lot.settled = true;
+    if !lot.meets_reserve() {
+        lot.leader = Pubkey::default();
+        emit!(LotSettled {
+            lot_id: lot.lot_id,
+            winner: Pubkey::default(),
+            amount: 0,
+        });
+        return Ok(());
+    }
+
+    let (Some(winning_bid), Some(winner)) = (&ctx.accounts.winning_bid, &ctx.accounts.winner)
+    else {
+        return err!(AuctionError::LotWinnerRequired);
+    };
+    let amount = winning_bid.amount;
+    let state_bump = state.bump;
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+    winning_bid.close(winner.to_account_info())?;
+
+    lot.winner = lot.leader;
+    lot.active_bid_count = lot
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    let state = &mut ctx.accounts.auction_state;
+    state.active_lot_bid_count = state
+        .active_lot_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.debit_escrow(amount)?;
+    state.total_volume = state.total_volume.saturating_add(u128::from(amount));
+
+    emit!(LotSettled {
+        lot_id: lot.lot_id,
+        winner: lot.winner,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::payout::Payout;
+use crate::state::{AuctionState, AuctionStatus, Bid, PriceFeed, RoundResult};
+
+#[derive(Accounts)]
+pub struct SettleMulti<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+        has_one = treasury,
+        has_one = usdc_mint,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = auction_state.top_k_paid == 0 @ AuctionError::TopKSettlementPending,
+        constraint = !auction_state.is_abandoned() @ AuctionError::AuctionAbandoned,
+        constraint = !auction_state.candle_pending() @ AuctionError::CandleNotDetermined,
+        constraint = auction_state.prize_amount == 0 @ AuctionError::PrizeRequiresSingleWinner,
+        constraint = auction_state.dispute_window == 0 @ AuctionError::DisputeRequiresSingleWinner,
+        constraint = auction_state.claim_window == 0 @ AuctionError::ClaimRequiresSingleWinner,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The agent, or the settler it delegated settlement to.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The curator's associated token account for the bid mint; required
+    /// when a curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bidder's referrer's associated token account for the bid
+    /// mint; required when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Records the round's top winner and what all winners paid together.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoundResult::INIT_SPACE,
+        seeds = [b"round_result", auction_state.id_seed().as_slice(), &auction_state.next_round_seed()],
+        bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Settles the top `num_winners` leaderboard entries. `remaining_accounts`
+/// holds one `(bid, winner_usdc)` pair per winner, in leaderboard order; the
+/// token account receives any escrow the winner does not pay. Transfer-hook
+/// accounts, when enabled, follow the pairs. In uniform-price mode all winners
+/// pay the N-th highest amount. A raffle settles only once drawn.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleMulti<'info>>) -> Result<()> {
+    require!(
+        !ctx.accounts.auction_state.raffle_mode || ctx.accounts.auction_state.raffle_drawn,
+        AuctionError::RaffleNotDrawn
+    );
+    ctx.accounts
+        .auction_state
+        .transition(AuctionStatus::Settled, Clock::get()?.unix_timestamp)?;
+    let winners: Vec<_> = {
+        let state = &ctx.accounts.auction_state;
+        let count = (state.num_winners as usize).min(state.leaderboard.len as usize);
+        state.leaderboard.entries()[..count].to_vec()
+    };
+    require!(
+        ctx.remaining_accounts.len() >= winners.len() * 2,
+        AuctionError::WinnersMismatch
+    );
+    let (pairs, extra) = ctx.remaining_accounts.split_at(winners.len() * 2);
+    let hooks = ctx.accounts.auction_state.hook_accounts(extra);
+    require!(hooks.len() == extra.len(), AuctionError::WinnersMismatch);
+
+    let clearing_price = match winners.last() {
+        Some(lowest) if ctx.accounts.auction_state.uniform_price => Some(lowest.amount),
+        _ => None,
+    };
+
+    let mut total: u64 = 0;
+    for (entry, pair) in winners.iter().zip(pairs.chunks(2)) {
+        let mut bid = Account::<Bid>::try_from(&pair[0])?;
+        let winner_usdc = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+
+        require!(bid.active, AuctionError::BidNotActive);
+        require!(
+            entry.is(bid.bidder, bid.bid_index)
+                && bid.amount == entry.amount
+                && bid.auction_id == ctx.accounts.auction_state.auction_id,
+            AuctionError::WinnersMismatch
+        );
+        require_keys_eq!(
+            winner_usdc.owner,
+            bid.bidder,
+            AuctionError::InvalidTokenAccount
+        );
+        require_keys_eq!(
+            winner_usdc.mint,
+            ctx.accounts.usdc_mint.key(),
+            AuctionError::InvalidTokenAccount
+        );
+
+        let price = clearing_price.unwrap_or(bid.amount);
+        Payout {
+            auction_state: &mut ctx.accounts.auction_state,
+            escrow: &ctx.accounts.escrow,
+            treasury: &ctx.accounts.treasury,
+            usdc_mint: &ctx.accounts.usdc_mint,
+            token_program: &ctx.accounts.token_program,
+            winner_usdc: Some(&winner_usdc),
+            charity: ctx.accounts.charity.as_ref(),
+            curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+            referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+            memo_program: ctx.accounts.memo_program.as_ref(),
+            accepted_mints: None,
+            hook_accounts: hooks,
+        }
+        .settle(&mut bid, price)?;
+        bid.exit(&crate::ID)?;
+        total = total
+            .checked_add(price)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+    }
+
+    ctx.accounts.round_result.record(
+        &ctx.accounts.auction_state,
+        winners.first().map(|entry| entry.bidder).unwrap_or_default(),
+        total,
+        winners.len() as u8,
+        ctx.bumps.round_result,
+    )?;
+    if let Some(lowest) = winners.last() {
+        ctx.accounts.price_feed.publish(
+            &ctx.accounts.round_result,
+            clearing_price.unwrap_or(lowest.amount),
+            ctx.bumps.price_feed,
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::event::TopKWinnerSettled;
+use crate::payout::Payout;
+use crate::ranking;
+use crate::state::{AuctionState, AuctionStatus, Bid, PriceFeed, RoundResult};
+
+#[derive(Accounts)]
+pub struct SettleTopK<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = auction_state.is_settler(&authority.key()) @ AuctionError::OnlySettler,
+        has_one = treasury,
+        has_one = usdc_mint,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+        constraint = !auction_state.is_abandoned() @ AuctionError::AuctionAbandoned,
+        constraint = !auction_state.candle_pending() @ AuctionError::CandleNotDetermined,
+        constraint = auction_state.prize_amount == 0 @ AuctionError::PrizeRequiresSingleWinner,
+        constraint = auction_state.dispute_window == 0 @ AuctionError::DisputeRequiresSingleWinner,
+        constraint = auction_state.claim_window == 0 @ AuctionError::ClaimRequiresSingleWinner,
+        constraint = !auction_state.uniform_price @ AuctionError::TopKUniformPrice,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// The agent, or the settler it delegated settlement to.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        constraint = charity.key() == auction_state.charity @ AuctionError::InvalidTokenAccount,
+    )]
+    pub charity: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The curator's associated token account for the bid mint; required
+    /// when a curator share is configured.
+    #[account(mut)]
+    pub curator_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bidder's referrer's associated token account for the bid
+    /// mint; required when the winner was referred and referrals pay out.
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Created by the first page and written by the last, which settles the
+    /// round.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RoundResult::INIT_SPACE,
+        seeds = [b"round_result", auction_state.id_seed().as_slice(), &auction_state.next_round_seed()],
+        bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    pub system_program: Program<'info, System>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
+}
+
+/// Pays the next `count` bids off the head of the ranked list, so the top
+/// `num_winners` can be settled over as many transactions as they need.
+/// `remaining_accounts` holds one `(bid, winner_usdc)` pair per bid, in list
+/// order, then any transfer-hook accounts. Each winner pays their own amount.
+///
+/// The page that pays the last winner, or empties the list, settles the
+/// round. Until then the list is frozen and no other instruction can settle.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleTopK<'info>>,
+    count: u8,
+) -> Result<()> {
+    {
+        let state = &ctx.accounts.auction_state;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            state.status_at(now).can_become(AuctionStatus::Settled),
+            AuctionError::InvalidStatusTransition
+        );
+        require!(
+            state.ranked_head != Pubkey::default(),
+            AuctionError::RankedListEmpty
+        );
+        require!(
+            count > 0 && count <= state.num_winners.saturating_sub(state.top_k_paid),
+            AuctionError::WinnersMismatch
+        );
+    }
+    let pair_count = count as usize * 2;
+    require!(
+        ctx.remaining_accounts.len() >= pair_count,
+        AuctionError::WinnersMismatch
+    );
+    let (pairs, extra) = ctx.remaining_accounts.split_at(pair_count);
+    let hooks = ctx.accounts.auction_state.hook_accounts(extra);
+    require!(hooks.len() == extra.len(), AuctionError::WinnersMismatch);
+
+    let mut lowest_price = 0;
+    for pair in pairs.chunks(2) {
+        let mut bid = Account::<Bid>::try_from(&pair[0])?;
+        let winner_usdc = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+        require!(bid.active, AuctionError::BidNotActive);
+        ranking::pop_head(&mut ctx.accounts.auction_state, bid.key(), &mut bid)?;
+        require_keys_eq!(
+            winner_usdc.owner,
+            bid.bidder,
+            AuctionError::InvalidTokenAccount
+        );
+        require_keys_eq!(
+            winner_usdc.mint,
+            ctx.accounts.usdc_mint.key(),
+            AuctionError::InvalidTokenAccount
+        );
+
+        let price = bid.amount;
+        Payout {
+            auction_state: &mut ctx.accounts.auction_state,
+            escrow: &ctx.accounts.escrow,
+            treasury: &ctx.accounts.treasury,
+            usdc_mint: &ctx.accounts.usdc_mint,
+            token_program: &ctx.accounts.token_program,
+            winner_usdc: Some(&winner_usdc),
+            charity: ctx.accounts.charity.as_ref(),
+            curator_usdc: ctx.accounts.curator_usdc.as_ref(),
+            referrer_usdc: ctx.accounts.referrer_usdc.as_ref(),
+            memo_program: ctx.accounts.memo_program.as_ref(),
+            accepted_mints: None,
+            hook_accounts: hooks,
+        }
+        .settle(&mut bid, price)?;
+        bid.exit(&crate::ID)?;
+
+        let state = &mut ctx.accounts.auction_state;
+        if state.top_k_paid == 0 {
+            state.top_k_first = bid.bidder;
+        }
+        state.top_k_paid += 1;
+        state.top_k_total = state
+            .top_k_total
+            .checked_add(price)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        lowest_price = price;
+        emit!(TopKWinnerSettled {
+            auction_id: state.auction_id,
+            rank: state.top_k_paid,
+            bidder: bid.bidder,
+            bid_index: bid.bid_index,
+            price,
+        });
+    }
+
+    let state = &ctx.accounts.auction_state;
+    if state.top_k_paid < state.num_winners && state.ranked_head != Pubkey::default() {
+        return Ok(());
+    }
+    let (winner, total, winner_count) = (state.top_k_first, state.top_k_total, state.top_k_paid);
+    ctx.accounts
+        .auction_state
+        .transition(AuctionStatus::Settled, Clock::get()?.unix_timestamp)?;
+    ctx.accounts.round_result.record(
+        &ctx.accounts.auction_state,
+        winner,
+        total,
+        winner_count,
+        ctx.bumps.round_result,
+    )?;
+    ctx.accounts
+        .price_feed
+        .publish(&ctx.accounts.round_result, lowest_price, ctx.bumps.price_feed);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::CommitBondsSwept;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct SweepCommitBonds<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = treasury,
+        has_one = usdc_mint,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = auction_state,
+        address = auction_state.escrow,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// Anyone; the bonds can only go to the treasury.
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sends every commit bond still held to the treasury once the round's
+/// reveal window has closed. By then a bond not returned by `reveal_bid`
+/// belongs to a bid that was never revealed, whether it is still escrowed or
+/// was withdrawn early.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepCommitBonds<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let state = &ctx.accounts.auction_state;
+    require!(
+        state.has_ended(now) && !state.revealing(now),
+        AuctionError::RevealWindowOpen
+    );
+    let amount = state.commit_bonds_held;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    state.commit_bonds_held = 0;
+    state.debit_escrow(amount)?;
+
+    emit!(CommitBondsSwept { amount });
+
+    Ok(())
+}
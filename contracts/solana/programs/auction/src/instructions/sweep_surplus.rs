@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::SurplusSwept;
+use crate::state::{AcceptedMints, AuctionState};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    /// Escrow and treasury for `usdc_mint`, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Required to sweep the escrow of a mint other than the auction mint.
+    #[account(seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+}
+
+/// Moves tokens the escrow holds beyond `total_escrowed` (dust, airdrops,
+/// mistaken transfers) to the treasury. Bidder funds are never touched.
+/// Accepted mints are swept one escrow at a time, to their own treasury.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepSurplus<'info>>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let accepted = ctx.accounts.accepted_mints.as_deref();
+    let mint = ctx.accounts.usdc_mint.key();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        state.escrow_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    require_keys_eq!(
+        ctx.accounts.treasury.key(),
+        state.treasury_for(accepted, &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let surplus = ctx
+        .accounts
+        .escrow
+        .amount
+        .saturating_sub(state.escrowed_in(accepted, &mint)?);
+    if surplus == 0 {
+        return Ok(());
+    }
+
+    let state_bump = state.bump;
+
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        surplus,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    emit!(SurplusSwept { amount: surplus });
+
+    Ok(())
+}
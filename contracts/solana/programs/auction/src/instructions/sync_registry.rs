@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::{AuctionState, RegistryPage};
+
+#[derive(Accounts)]
+pub struct SyncRegistry<'info> {
+    #[account(
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [b"registry", registry_page.index.to_le_bytes().as_ref()],
+        bump = registry_page.bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+}
+
+/// Copies the auction's status into its registry entry. Permissionless: the
+/// entry only ever mirrors the state account.
+pub fn handler(ctx: Context<SyncRegistry>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let status = state.status_at(Clock::get()?.unix_timestamp);
+    let entry = ctx
+        .accounts
+        .registry_page
+        .entry_mut(state.auction_id)
+        .ok_or(AuctionError::NotInRegistry)?;
+    entry.status = status;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BidTransferred;
+use crate::state::{require_not_denylisted, AuctionState, Bid, Denylist};
+
+#[derive(Accounts)]
+pub struct TransferBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        constraint = !auction_state.is_settled() @ AuctionError::AuctionSettled,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.foreign_chain == 0 @ AuctionError::ForeignBid,
+        constraint = bid.commitment == [0; 32] @ AuctionError::BidSealed,
+        constraint = !bid.receipt @ AuctionError::BidReceipted,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// The same bid under the new owner's seeds, at the same index; fails if
+    /// they already hold a bid there.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            new_owner.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump,
+    )]
+    pub new_bid: Account<'info, Bid>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    /// Pays the new bid's rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub new_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Required once the auction has a denylist; see `set_denylisted`.
+    #[account(seeds = [b"denylist", auction_state.id_seed().as_slice()], bump = denylist.bump)]
+    pub denylist: Option<Account<'info, Denylist>>,
+}
+
+/// Moves a bid and its escrow claim to `new_owner`, who must co-sign. The bid
+/// keeps its amounts, placement time and leaderboard position; only the PDA
+/// and owner change, so the escrow itself never moves. A bid with a receipt
+/// token stays put, since the token would stay with the seller.
+pub fn handler(ctx: Context<TransferBid>) -> Result<()> {
+    let from = ctx.accounts.bidder.key();
+    let to = ctx.accounts.new_owner.key();
+    require_not_denylisted(&ctx.accounts.auction_state, ctx.accounts.denylist.as_deref(), &to)?;
+    let now = Clock::get()?.unix_timestamp;
+    // The seller's points are theirs to claim; the new owner's accrue from now.
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, now)?;
+    let bid = &ctx.accounts.bid;
+
+    let new_bid = &mut ctx.accounts.new_bid;
+    new_bid.set_inner(Bid {
+        bidder: to,
+        bump: ctx.bumps.new_bid,
+        // The seller's session key has no say over the new owner's bid.
+        session_key: Pubkey::default(),
+        session_expires_at: 0,
+        session_spend_cap: 0,
+        // A referrer cannot be paid for their own bid.
+        referrer: if bid.referrer == to {
+            Pubkey::default()
+        } else {
+            bid.referrer
+        },
+        ..(**bid).clone()
+    });
+
+    // The bond follows the bid rather than returning to the seller at close.
+    if bid.bond > 0 {
+        bid.sub_lamports(bid.bond)?;
+        new_bid.add_lamports(bid.bond)?;
+    }
+
+    let state = &mut ctx.accounts.auction_state;
+    state.leaderboard.rename(from, to, bid.bid_index);
+    if state.is_leader(from, bid.bid_index) {
+        state.set_leader(to, bid.bid_index);
+    }
+
+    emit!(BidTransferred {
+        from,
+        to,
+        amount: bid.amount,
+        max_amount: bid.max_amount,
+    });
+
+    Ok(())
+}
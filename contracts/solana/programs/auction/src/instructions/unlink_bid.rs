@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::BidUnlinked;
+use crate::ranking;
+use crate::state::{AuctionState, Bid};
+
+#[derive(Accounts)]
+pub struct UnlinkBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+    )]
+    pub bid: Account<'info, Bid>,
+    /// The bid's `prev_bid`; omit when it heads the list.
+    #[account(mut)]
+    pub prev_bid: Option<Account<'info, Bid>>,
+    /// The bid's `next_bid`; omit when it is last.
+    #[account(mut)]
+    pub next_bid: Option<Account<'info, Bid>>,
+    pub bidder: Signer<'info>,
+}
+
+/// Takes the bidder's bid out of the ranked list so it can be raised,
+/// lowered or withdrawn. Only the bidder may unlink, so nobody can drop a
+/// bid from the list ahead of a ranked settlement.
+pub fn handler(ctx: Context<UnlinkBid>) -> Result<()> {
+    let accounts = ctx.accounts;
+    ranking::unlink(
+        &mut accounts.auction_state,
+        &mut accounts.bid,
+        accounts.prev_bid.as_mut(),
+        accounts.next_bid.as_mut(),
+    )?;
+    emit!(BidUnlinked {
+        bidder: accounts.bid.bidder,
+        bid_index: accounts.bid.bid_index,
+    });
+    Ok(())
+}
@@ -1,100 +1,328 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
+use super::set_session_key::revoke_session;
 use crate::error::AuctionError;
-use crate::event::BidUpdated;
-use crate::state::{AuctionState, Bid};
+use crate::event::{BidDecreased, BidIncreased, BidUpdated};
+use crate::leader::auto_escalate;
+use crate::state::{require_not_denylisted, AcceptedMints, AuctionState, Bid, Denylist};
+use crate::transfer::transfer_checked;
 use crate::USDC_DECIMALS;
 
 #[derive(Accounts)]
 pub struct UpdateBid<'info> {
-    #[account(
-        seeds = [b"auction_state"],
-        bump = auction_state.bump,
-        has_one = usdc_mint,
-    )]
+    /// Ownership and discriminator already identify an `AuctionState`, and
+    /// the bid must belong to it, so the seeds are not re-derived on the bid
+    /// path.
+    #[account(mut)]
     pub auction_state: Account<'info, AuctionState>,
+    /// Bids are only ever created at `[b"bid", auction_seed, bidder,
+    /// bid_index_seed]`, so matching the bidder and the auction id pins the
+    /// bid to one of the bidder's own without re-deriving it.
     #[account(
         mut,
-        seeds = [b"bid", bidder.key().as_ref()],
-        bump = bid.bump,
         constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
+        constraint = bid.auction_id == auction_state.auction_id @ AuctionError::WrongAuction,
         constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = bid.commitment == [0; 32] @ AuctionError::BidSealed,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
     )]
     pub bid: Account<'info, Bid>,
     #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
     pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
-    #[account(
-        mut,
-        token::mint = usdc_mint,
-        token::authority = auction_state,
-        seeds = [b"escrow"],
-        bump = auction_state.escrow_bump,
-    )]
+    /// Escrow for the bid's mint, checked before it moves tokens.
+    #[account(mut)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
     pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The bid's owner, pinned by the bid constraints. Must sign
+    /// unless `session` signs for a raise; see `authorize`.
     #[account(mut)]
-    pub bidder: Signer<'info>,
+    pub bidder: UncheckedAccount<'info>,
     pub token_program: Interface<'info, TokenInterface>,
+    /// Current leader's bid, required whenever someone else holds the lead.
+    #[account(mut)]
+    pub leader_bid: Option<Account<'info, Bid>>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    /// Required once the auction has a denylist; see `set_denylisted`.
+    #[account(seeds = [b"denylist", auction_state.id_seed().as_slice()], bump = denylist.bump)]
+    pub denylist: Option<Account<'info, Denylist>>,
+    /// The bid's session key, raising it in place of the bidder; see
+    /// `set_session_key`.
+    pub session: Option<Signer<'info>>,
 }
 
-pub fn handler(ctx: Context<UpdateBid>, amount_change: i64) -> Result<()> {
-    let state = &ctx.accounts.auction_state;
-    let bid = &mut ctx.accounts.bid;
-    let clock = Clock::get()?;
+/// Deprecated signed form of `increase_bid`/`decrease_bid`, kept for
+/// existing clients. A zero change only re-runs leader tracking.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+    amount_change: i64,
+) -> Result<()> {
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let accounts = ctx.accounts;
+    let now = Clock::get()?.unix_timestamp;
 
     if amount_change > 0 {
-        let increase = amount_change as u64;
-        token_interface::transfer_checked(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.bidder_usdc.to_account_info(),
-                    to: ctx.accounts.escrow.to_account_info(),
-                    authority: ctx.accounts.bidder.to_account_info(),
-                    mint: ctx.accounts.usdc_mint.to_account_info(),
-                },
-            ),
-            increase,
-            USDC_DECIMALS,
-        )?;
-        bid.amount = bid
-            .amount
-            .checked_add(increase)
-            .ok_or(AuctionError::ArithmeticOverflow)?;
+        deposit(accounts, hooks, amount_change as u64, now)?;
     } else if amount_change < 0 {
         let decrease = amount_change
             .checked_abs()
             .ok_or(AuctionError::InvalidAmountChange)? as u64;
-        let new_amount = bid.amount.checked_sub(decrease).ok_or(AuctionError::InsufficientEscrow)?;
-        require!(new_amount >= state.minimum_bid, AuctionError::AmountBelowMinimum);
-
-        let state_bump = ctx.accounts.auction_state.bump;
-        let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &[state_bump]]];
-
-        token_interface::transfer_checked(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.bidder_usdc.to_account_info(),
-                    authority: ctx.accounts.auction_state.to_account_info(),
-                    mint: ctx.accounts.usdc_mint.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            decrease,
-            USDC_DECIMALS,
-        )?;
-        bid.amount = new_amount;
+        release(accounts, hooks, decrease, now)?;
+    } else {
+        authorize(accounts, 0, now)?;
     }
-
-    bid.updated_at = clock.unix_timestamp;
+    rebalance(accounts, now)?;
 
     emit!(BidUpdated {
-        bidder: ctx.accounts.bidder.key(),
-        new_amount: bid.amount,
+        bidder: accounts.bidder.key(),
+        new_amount: accounts.bid.amount,
+    });
+
+    Ok(())
+}
+
+/// Bids `amount` more, escrowing the auction's deposit share of it. Plain
+/// bids raise `amount` with it; proxy bids raise their ceiling.
+pub fn increase_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, AuctionError::InvalidAmountChange);
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let accounts = ctx.accounts;
+    let now = Clock::get()?.unix_timestamp;
+
+    deposit(accounts, hooks, amount, now)?;
+    rebalance(accounts, now)?;
+
+    emit!(BidIncreased {
+        bidder: accounts.bidder.key(),
+        amount,
+        new_amount: accounts.bid.amount,
+        max_amount: accounts.bid.max_amount,
+    });
+
+    Ok(())
+}
+
+/// Lowers the bid by `amount`, never below the minimum bid, forgiving any
+/// balance due before returning escrow less the withdrawal penalty. Proxy
+/// bids keep their effective amount at or below the lowered ceiling.
+pub fn decrease_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, AuctionError::InvalidAmountChange);
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    let accounts = ctx.accounts;
+    let now = Clock::get()?.unix_timestamp;
+
+    release(accounts, hooks, amount, now)?;
+    rebalance(accounts, now)?;
+
+    emit!(BidDecreased {
+        bidder: accounts.bidder.key(),
+        amount,
+        new_amount: accounts.bid.amount,
+        max_amount: accounts.bid.max_amount,
     });
 
     Ok(())
 }
+
+fn deposit<'info>(
+    accounts: &mut UpdateBid<'info>,
+    hooks: &[AccountInfo<'info>],
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(
+        !accounts.auction_state.has_ended(now),
+        AuctionError::AuctionEnded
+    );
+    accounts.auction_state.require_accepting_bids()?;
+    require_not_denylisted(
+        &accounts.auction_state,
+        accounts.denylist.as_deref(),
+        &accounts.bidder.key(),
+    )?;
+    check_escrow(accounts)?;
+    let delegated = authorize(accounts, amount, now)?;
+    let mint = accounts.usdc_mint.key();
+    let deposit = if mint == accounts.auction_state.usdc_mint {
+        accounts.auction_state.deposit_for(amount)
+    } else {
+        amount
+    };
+    let id_seed = accounts.auction_state.id_seed();
+    let state_bump = [accounts.auction_state.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &state_bump]];
+    let authority = if delegated {
+        accounts.auction_state.to_account_info()
+    } else {
+        accounts.bidder.to_account_info()
+    };
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.bidder_usdc.to_account_info(),
+                to: accounts.escrow.to_account_info(),
+                authority,
+                mint: accounts.usdc_mint.to_account_info(),
+            },
+            if delegated { signer_seeds } else { &[] },
+        ),
+        deposit,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+    accounts.auction_state.accrue_points(&mut accounts.bid, now)?;
+    let bid = &mut accounts.bid;
+    bid.max_amount = bid
+        .max_amount
+        .checked_add(amount)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    bid.balance_due = amount
+        .checked_sub(deposit)
+        .and_then(|owed| bid.balance_due.checked_add(owed))
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    accounts.auction_state.record_bid(false);
+    accounts
+        .auction_state
+        .credit_escrow_in(accounts.accepted_mints.as_deref_mut(), &mint, deposit)
+}
+
+fn release<'info>(
+    accounts: &mut UpdateBid<'info>,
+    hooks: &[AccountInfo<'info>],
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(accounts.bidder.is_signer, AuctionError::BidderSignatureRequired);
+    check_escrow(accounts)?;
+    accounts.auction_state.require_refundable()?;
+    let state = &mut accounts.auction_state;
+    let new_max = accounts
+        .bid
+        .max_amount
+        .checked_sub(amount)
+        .ok_or(AuctionError::InsufficientEscrow)?;
+    require!(new_max >= state.minimum_bid, AuctionError::AmountBelowMinimum);
+    let forgiven = amount.min(accounts.bid.balance_due);
+    let released = amount - forgiven;
+    // Charged as `withdraw_bid` would, and likewise waived once the auction
+    // ends, so stepping down before withdrawing saves nothing.
+    let penalty = state.withdrawal_penalty(released, now);
+    let refund = released - penalty;
+
+    let state_bump = state.bump;
+
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.escrow.to_account_info(),
+                to: accounts.bidder_usdc.to_account_info(),
+                authority: state.to_account_info(),
+                mint: accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+    state.accrue_points(&mut accounts.bid, now)?;
+    accounts.bid.max_amount = new_max;
+    accounts.bid.balance_due -= forgiven;
+    let mint = accounts.usdc_mint.key();
+    if mint == state.usdc_mint {
+        state.accrued_penalties = state
+            .accrued_penalties
+            .checked_add(penalty)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.debit_escrow(refund)
+    } else {
+        // Penalties in other mints stay behind as surplus for `sweep_surplus`.
+        state.debit_escrow_in(accounts.accepted_mints.as_deref_mut(), &mint, released)
+    }
+}
+
+/// Passes when the bidder signs. Otherwise the bid's session key must sign,
+/// before it expires, and `increase` comes out of its spend cap. Returns
+/// whether the session key is acting, so the deposit moves under the
+/// auction state's delegation over `bidder_usdc`.
+fn authorize(accounts: &mut UpdateBid, increase: u64, now: i64) -> Result<bool> {
+    if accounts.bidder.is_signer {
+        return Ok(false);
+    }
+    let session = accounts
+        .session
+        .as_ref()
+        .ok_or(AuctionError::BidderSignatureRequired)?;
+    let bid = &mut accounts.bid;
+    require_keys_eq!(session.key(), bid.session_key, AuctionError::InvalidSessionKey);
+    require!(now < bid.session_expires_at, AuctionError::SessionKeyExpired);
+    bid.session_spend_cap = bid
+        .session_spend_cap
+        .checked_sub(increase)
+        .ok_or(AuctionError::SessionSpendCapExceeded)?;
+    Ok(true)
+}
+
+/// Ends an expired session the next time the bidder signs, revoking the
+/// allowance it left on `bidder_usdc`.
+fn lapse_session<'info>(accounts: &mut UpdateBid<'info>, now: i64) -> Result<()> {
+    let bid = &accounts.bid;
+    if !accounts.bidder.is_signer || !bid.has_session() || now < bid.session_expires_at {
+        return Ok(());
+    }
+    revoke_session(
+        &accounts.auction_state.key(),
+        &accounts.bidder_usdc,
+        accounts.bidder.to_account_info(),
+        accounts.token_program.to_account_info(),
+    )?;
+    accounts.bid.end_session();
+    Ok(())
+}
+
+fn check_escrow(accounts: &UpdateBid) -> Result<()> {
+    let escrow = accounts
+        .auction_state
+        .escrow_for(accounts.accepted_mints.as_deref(), &accounts.usdc_mint.key())?;
+    require_keys_eq!(
+        accounts.escrow.key(),
+        escrow,
+        AuctionError::InvalidTokenAccount
+    );
+    Ok(())
+}
+
+/// Re-derives the effective amount from the new ceiling and re-ranks the bid.
+fn rebalance(accounts: &mut UpdateBid, now: i64) -> Result<()> {
+    lapse_session(accounts, now)?;
+    let bid = &mut accounts.bid;
+    bid.amount = if bid.proxy {
+        bid.amount.min(bid.max_amount)
+    } else {
+        bid.max_amount
+    };
+    bid.updated_at = now;
+    bid.updated_slot = Clock::get()?.slot;
+
+    auto_escalate(
+        &mut accounts.auction_state,
+        accounts.bidder.key(),
+        bid,
+        accounts.leader_bid.as_mut(),
+    )
+}
@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::{
+    AuctionScheduled, BidTtlChanged, BuyNowPriceChanged, CandleWindowChanged,
+    EnglishAuctionChanged, MinimumBidChanged, ModeratorChanged, NumWinnersChanged,
+    RaffleModeChanged, TransferHooksChanged, UniformPriceChanged, VestingDurationChanged,
+};
+use crate::state::{
+    audit_action, record_admin_action, AuctionState, AuditEntry, AuditLog, Config, ConfigUpdate,
+    FeeTier, MAX_FEE_TIERS, MAX_TRAILING_ROUNDS, MAX_WINNERS,
+};
+use crate::BPS_DENOMINATOR;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", auction_state.id_seed().as_slice()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Required once `initialize_audit_log` has run.
+    #[account(mut, seeds = [b"audit_log", auction_state.id_seed().as_slice()], bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// Applies every `Some` override in `update` at once, so a round can be
+/// reconfigured in one instruction. Nothing changes unless all of them pass.
+pub fn handler(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let state = &mut ctx.accounts.auction_state;
+    let config = &mut ctx.accounts.config;
+    config.bump = ctx.bumps.config;
+
+    let curator_bps = update
+        .curator
+        .map_or(state.curator_bps, |(curator, curator_bps)| {
+            if curator == Pubkey::default() { 0 } else { curator_bps }
+        });
+    let referral_bps = update.referral_bps.unwrap_or(state.referral_bps);
+    require!(
+        state.shares_fit_with(curator_bps, referral_bps),
+        AuctionError::InvalidBps
+    );
+
+    if let Some(minimum_bid) = update.minimum_bid {
+        record_admin_action(
+            state,
+            ctx.accounts.audit_log.as_ref(),
+            ctx.accounts.agent.key(),
+            audit_action::MINIMUM_BID_CHANGED,
+            AuditEntry::amount(state.minimum_bid),
+            AuditEntry::amount(minimum_bid),
+        )?;
+        emit!(MinimumBidChanged {
+            old_minimum_bid: state.minimum_bid,
+            new_minimum_bid: minimum_bid,
+        });
+        state.minimum_bid = minimum_bid;
+    }
+    if let Some(end_time) = update.end_time {
+        state.reschedule(end_time, Clock::get()?.unix_timestamp)?;
+    }
+    if let Some(penalty_bps) = update.withdrawal_penalty_bps {
+        require!(
+            u64::from(penalty_bps) <= BPS_DENOMINATOR,
+            AuctionError::InvalidBps
+        );
+        state.withdrawal_penalty_bps = penalty_bps;
+    }
+    if let Some(bid_increment) = update.bid_increment {
+        state.bid_increment = bid_increment;
+    }
+    if let Some(buy_now_price) = update.buy_now_price {
+        emit!(BuyNowPriceChanged {
+            old_buy_now_price: state.buy_now_price,
+            new_buy_now_price: buy_now_price,
+        });
+        state.buy_now_price = buy_now_price;
+    }
+    if let Some(num_winners) = update.num_winners {
+        require!(
+            num_winners >= 1 && num_winners as usize <= MAX_WINNERS,
+            AuctionError::InvalidNumWinners
+        );
+        emit!(NumWinnersChanged {
+            old_num_winners: state.num_winners,
+            new_num_winners: num_winners,
+        });
+        state.num_winners = num_winners;
+    }
+    if let Some(uniform_price) = update.uniform_price {
+        emit!(UniformPriceChanged {
+            old_uniform_price: state.uniform_price,
+            new_uniform_price: uniform_price,
+        });
+        state.uniform_price = uniform_price;
+    }
+    if let Some(raffle_mode) = update.raffle_mode {
+        emit!(RaffleModeChanged {
+            old_raffle_mode: state.raffle_mode,
+            new_raffle_mode: raffle_mode,
+        });
+        state.raffle_mode = raffle_mode;
+    }
+    if let Some(vesting_duration) = update.vesting_duration {
+        require!(vesting_duration >= 0, AuctionError::InvalidVestingDuration);
+        emit!(VestingDurationChanged {
+            old_vesting_duration: state.vesting_duration,
+            new_vesting_duration: vesting_duration,
+        });
+        state.vesting_duration = vesting_duration;
+    }
+    if let Some((deposit_bps, payment_window)) = update.deposit_terms {
+        require!(
+            u64::from(deposit_bps) <= BPS_DENOMINATOR,
+            AuctionError::InvalidBps
+        );
+        require!(
+            deposit_bps == 0 || payment_window > 0,
+            AuctionError::InvalidPaymentWindow
+        );
+        state.deposit_bps = deposit_bps;
+        state.payment_window = payment_window;
+    }
+    if let Some(transfer_hooks) = update.transfer_hooks {
+        emit!(TransferHooksChanged {
+            old_transfer_hooks: state.transfer_hooks,
+            new_transfer_hooks: transfer_hooks,
+        });
+        state.transfer_hooks = transfer_hooks;
+    }
+    if let Some(abandonment_window) = update.abandonment_window {
+        require!(
+            abandonment_window >= 0,
+            AuctionError::InvalidAbandonmentWindow
+        );
+        state.abandonment_window = abandonment_window;
+    }
+    if let Some((curator, _)) = update.curator {
+        state.curator = curator;
+    }
+    state.curator_bps = curator_bps;
+    state.referral_bps = referral_bps;
+    if let Some((moderator, bid_bond)) = update.moderator {
+        state.moderator = moderator;
+        state.bid_bond = bid_bond;
+        emit!(ModeratorChanged {
+            moderator,
+            bid_bond,
+        });
+    }
+    if let Some(bid_ttl) = update.bid_ttl {
+        require!(bid_ttl >= 0, AuctionError::InvalidBidTtl);
+        emit!(BidTtlChanged {
+            old_bid_ttl: state.bid_ttl,
+            new_bid_ttl: bid_ttl,
+        });
+        state.bid_ttl = bid_ttl;
+    }
+    if let Some(english_auction) = update.english_auction {
+        require!(state.active_bid_count <= 1, AuctionError::EnglishModeLocked);
+        require!(!(english_auction && state.all_pay), AuctionError::AllPayLocked);
+        require!(
+            !(english_auction && state.takes_sealed_bids()),
+            AuctionError::SealedBidsLocked
+        );
+        emit!(EnglishAuctionChanged {
+            old_english_auction: state.english_auction,
+            new_english_auction: english_auction,
+        });
+        state.english_auction = english_auction;
+    }
+    if let Some(candle_window) = update.candle_window {
+        require!(candle_window >= 0, AuctionError::InvalidCandleWindow);
+        emit!(CandleWindowChanged {
+            old_candle_window: state.candle_window,
+            new_candle_window: candle_window,
+        });
+        state.candle_window = candle_window;
+    }
+    if let Some(all_pay) = update.all_pay {
+        state.set_all_pay(all_pay)?;
+    }
+    if let Some(start_time) = update.start_time {
+        state.start_time = start_time;
+        emit!(AuctionScheduled {
+            auction_id: state.auction_id,
+            start_time,
+        });
+    }
+    if let Some((bps, rounds)) = update.dynamic_minimum {
+        require!(
+            u64::from(bps) <= BPS_DENOMINATOR,
+            AuctionError::InvalidBps
+        );
+        require!(
+            bps == 0 || (1..=MAX_TRAILING_ROUNDS).contains(&(rounds as usize)),
+            AuctionError::InvalidTrailingRounds
+        );
+        state.dynamic_minimum_bps = bps;
+        state.dynamic_minimum_rounds = rounds;
+    }
+    if let Some((discount_mint, tiers)) = update.fee_discounts {
+        require!(tiers.len() <= MAX_FEE_TIERS, AuctionError::InvalidFeeTiers);
+        require!(
+            tiers.windows(2).all(|pair| pair[0].min_balance < pair[1].min_balance)
+                && tiers
+                    .iter()
+                    .all(|tier| u64::from(tier.discount_bps) <= BPS_DENOMINATOR),
+            AuctionError::InvalidFeeTiers
+        );
+        config.discount_mint = if tiers.is_empty() {
+            Pubkey::default()
+        } else {
+            discount_mint
+        };
+        config.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        config.fee_tiers[..tiers.len()].copy_from_slice(&tiers);
+    }
+    Ok(())
+}
@@ -1,52 +1,119 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
+use super::set_session_key::revoke_session;
 use crate::error::AuctionError;
 use crate::event::BidWithdrawn;
-use crate::state::{AuctionState, Bid};
+use crate::memo::write_memo;
+use crate::receipt::Receipt;
+use crate::state::{AcceptedMints, AuctionState, Bid};
+use crate::transfer::transfer_checked;
 use crate::USDC_DECIMALS;
 
 #[derive(Accounts)]
 pub struct WithdrawBid<'info> {
     #[account(
         mut,
-        seeds = [b"auction_state"],
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
         bump = auction_state.bump,
-        has_one = usdc_mint,
     )]
     pub auction_state: Account<'info, AuctionState>,
     #[account(
         mut,
         close = bidder,
-        seeds = [b"bid", bidder.key().as_ref()],
+        seeds = [
+            b"bid",
+            auction_state.id_seed().as_slice(),
+            bidder.key().as_ref(),
+            bid.index_seed().as_slice(),
+        ],
         bump = bid.bump,
         constraint = bid.bidder == bidder.key() @ AuctionError::WrongBidder,
         constraint = bid.active @ AuctionError::BidNotActive,
+        constraint = !auction_state.is_ranked(&bid) @ AuctionError::BidRanked,
+        constraint = bid.mint == usdc_mint.key() @ AuctionError::InvalidTokenAccount,
     )]
     pub bid: Account<'info, Bid>,
-    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
-    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// The bidder's associated token account; recreated if they closed it.
     #[account(
-        mut,
-        token::mint = usdc_mint,
-        token::authority = auction_state,
-        seeds = [b"escrow"],
-        bump = auction_state.escrow_bump,
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
     )]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    /// Escrow for the bid's mint, checked in the handler.
+    #[account(mut, token::mint = usdc_mint, token::authority = auction_state)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The bid's mint.
     pub usdc_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub bidder: Signer<'info>,
+    /// Pays to recreate `bidder_usdc`; the bidder itself, or any other
+    /// signer when the bidder is a PDA that cannot fund accounts.
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
+    /// Required for bids in a mint other than the auction mint.
+    #[account(mut, seeds = [b"accepted_mints", auction_state.id_seed().as_slice()], bump = accepted_mints.bump)]
+    pub accepted_mints: Option<Account<'info, AcceptedMints>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the bid holds a receipt, which is burned.
+    #[account(mut, seeds = [b"receipt_mint", auction_state.id_seed().as_slice()], bump)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub bidder_receipt: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The SPL Memo program; required once the agent has set a memo.
+    pub memo_program: Option<Program<'info, Memo>>,
 }
 
-pub fn handler(ctx: Context<WithdrawBid>) -> Result<()> {
-    let amount = ctx.accounts.bid.amount;
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawBid<'info>>) -> Result<()> {
+    ctx.accounts.auction_state.require_refundable()?;
+    ctx.accounts
+        .auction_state
+        .require_points_claimed(&mut ctx.accounts.bid, Clock::get()?.unix_timestamp)?;
+    let amount = ctx.accounts.bid.escrowed();
     let bidder = ctx.accounts.bid.bidder;
+    let bid_index = ctx.accounts.bid.bid_index;
+    let mint = ctx.accounts.usdc_mint.key();
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        ctx.accounts
+            .auction_state
+            .escrow_for(ctx.accounts.accepted_mints.as_deref(), &mint)?,
+        AuctionError::InvalidTokenAccount
+    );
+    let clock = Clock::get()?;
+
+    if ctx.accounts.bid.receipt {
+        Receipt {
+            receipt_mint: ctx.accounts.receipt_mint.as_ref(),
+            holder: ctx.accounts.bidder_receipt.as_ref(),
+            token_program: &ctx.accounts.token_program,
+        }
+        .redeem(&ctx.accounts.bidder)?;
+    }
+
+    let penalty = ctx
+        .accounts
+        .auction_state
+        .withdrawal_penalty(amount, clock.unix_timestamp);
+    let refund = amount
+        .checked_sub(penalty)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+
     let state_bump = ctx.accounts.auction_state.bump;
-    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &[state_bump]]];
 
-    token_interface::transfer_checked(
+    let id_seed = ctx.accounts.auction_state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    write_memo(&ctx.accounts.auction_state, ctx.accounts.memo_program.as_ref())?;
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             TransferChecked {
@@ -57,18 +124,41 @@ pub fn handler(ctx: Context<WithdrawBid>) -> Result<()> {
             },
             signer_seeds,
         ),
-        amount,
+        refund,
         USDC_DECIMALS,
+        hooks,
     )?;
+    if ctx.accounts.bid.has_session() {
+        revoke_session(
+            &ctx.accounts.auction_state.key(),
+            &ctx.accounts.bidder_usdc,
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+    }
 
-    ctx.accounts.auction_state.active_bid_count = ctx
-        .accounts
-        .auction_state
+    let state = &mut ctx.accounts.auction_state;
+    state.active_bid_count = state
         .active_bid_count
         .checked_sub(1)
         .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.forget_bid(bidder, bid_index);
+    if mint == state.usdc_mint {
+        state.accrued_penalties = state
+            .accrued_penalties
+            .checked_add(penalty)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.debit_escrow(refund)?;
+    } else {
+        // Penalties in other mints stay behind as surplus for `sweep_surplus`.
+        state.debit_escrow_in(ctx.accounts.accepted_mints.as_deref_mut(), &mint, amount)?;
+    }
 
-    emit!(BidWithdrawn { bidder, amount });
+    emit!(BidWithdrawn {
+        bidder,
+        amount,
+        penalty,
+    });
 
     Ok(())
 }
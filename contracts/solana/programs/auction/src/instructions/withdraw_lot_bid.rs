@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::LotBidWithdrawn;
+use crate::state::{AuctionState, Lot, LotBid};
+use crate::transfer::transfer_checked;
+use crate::USDC_DECIMALS;
+
+#[derive(Accounts)]
+pub struct WithdrawLotBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = usdc_mint,
+        has_one = escrow,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(
+        mut,
+        seeds = [b"lot", auction_state.id_seed().as_slice(), &lot.lot_seed()],
+        bump = lot.bump,
+    )]
+    pub lot: Account<'info, Lot>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"lot_bid", auction_state.id_seed().as_slice(), &lot.lot_seed(), bidder.key().as_ref()],
+        bump = lot_bid.bump,
+    )]
+    pub lot_bid: Account<'info, LotBid>,
+    #[account(mut, token::mint = usdc_mint, token::authority = bidder)]
+    pub bidder_usdc: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refunds a lot bid in full and closes it. Any bid but the lot's leader can
+/// leave at any time; the leader only once the round is called off.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawLotBid<'info>>) -> Result<()> {
+    let bidder = ctx.accounts.bidder.key();
+    let state = &ctx.accounts.auction_state;
+    let lot = &mut ctx.accounts.lot;
+    if lot.leader == bidder {
+        require!(state.status.is_called_off(), AuctionError::LotLeaderLocked);
+        lot.leader = Pubkey::default();
+        lot.leading_amount = 0;
+    }
+    let amount = ctx.accounts.lot_bid.amount;
+    let state_bump = state.bump;
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+    let hooks = state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.bidder_usdc.to_account_info(),
+                authority: ctx.accounts.auction_state.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        USDC_DECIMALS,
+        hooks,
+    )?;
+
+    lot.active_bid_count = lot
+        .active_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    let state = &mut ctx.accounts.auction_state;
+    state.active_lot_bid_count = state
+        .active_lot_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::ArithmeticOverflow)?;
+    state.debit_escrow(amount)?;
+
+    emit!(LotBidWithdrawn {
+        lot_id: lot.lot_id,
+        bidder,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::PrizeWithdrawn;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+
+#[derive(Accounts)]
+pub struct WithdrawPrize<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_state", auction_state.id_seed().as_slice()],
+        bump = auction_state.bump,
+        has_one = agent @ AuctionError::OnlyAgent,
+        constraint = auction_state.prize_amount > 0 @ AuctionError::NoPrize,
+        constraint = auction_state.active_bid_count == 0 @ AuctionError::PrizeLocked,
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+    #[account(address = auction_state.prize_mint)]
+    pub prize_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_state.id_seed().as_slice(), prize_mint.key().as_ref()],
+        bump,
+    )]
+    pub prize_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = prize_mint, token::authority = agent)]
+    pub agent_prize: InterfaceAccount<'info, TokenAccount>,
+    pub agent: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Returns the escrowed prize to the agent. Only possible while nobody has an
+/// active bid, so no bidder can lose the prize they bid for.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawPrize<'info>>) -> Result<()> {
+    let state = &ctx.accounts.auction_state;
+    let amount = state.prize_amount;
+    let id_seed = state.id_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+
+    let hooks = ctx.accounts.auction_state.hook_accounts(ctx.remaining_accounts);
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.prize_vault.to_account_info(),
+                to: ctx.accounts.agent_prize.to_account_info(),
+                authority: state.to_account_info(),
+                mint: ctx.accounts.prize_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.prize_mint.decimals,
+        hooks,
+    )?;
+
+    let state = &mut ctx.accounts.auction_state;
+    emit!(PrizeWithdrawn {
+        mint: state.prize_mint,
+        amount,
+    });
+    state.prize_mint = Pubkey::default();
+    state.prize_amount = 0;
+
+    Ok(())
+}
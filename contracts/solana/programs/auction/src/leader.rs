@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::event::{BidUpdated, Outbid};
+use crate::state::{AuctionState, Bid, LeaderboardEntry};
+
+/// Raises a proxy bid towards `target`, never above its escrowed maximum and never down.
+fn escalate(bid: &mut Bid, target: u64) {
+    bid.amount = bid.amount.max(target.min(bid.max_amount));
+}
+
+/// Resolves `challenger` against the tracked leader, eBay style: whichever side
+/// has the higher ceiling leads, and proxy bids escalate to one increment above
+/// the other side (capped at their maximum). Ties keep the existing leader,
+/// which changed its bid first. A leader changing its own bid is re-ranked
+/// and the lead follows the leaderboard, so a decrease can hand it on.
+/// Both sides are then re-ranked on the leaderboard, and a change of leader
+/// is announced with `Outbid`.
+pub fn auto_escalate(
+    state: &mut AuctionState,
+    challenger_key: Pubkey,
+    challenger: &mut Bid,
+    leader_bid: Option<&mut Account<Bid>>,
+) -> Result<()> {
+    let challenger_index = challenger.bid_index;
+    if state.leader == Pubkey::default() || state.is_leader(challenger_key, challenger_index) {
+        state.highest_ever_bid = state.highest_ever_bid.max(challenger.amount);
+        state.leaderboard.record(LeaderboardEntry {
+            bidder: challenger_key,
+            ..challenger.rank()
+        });
+        state.follow_leaderboard();
+        return Ok(());
+    }
+
+    let leader = leader_bid.ok_or(AuctionError::LeaderBidRequired)?;
+    require!(
+        state.is_leader(leader.bidder, leader.bid_index) && leader.auction_id == state.auction_id,
+        AuctionError::WrongLeaderBid
+    );
+    // Escalation only raises the leader, which keeps the ranked list sorted
+    // as long as the leader heads it.
+    require!(
+        !state.is_ranked(leader) || state.ranked_head == leader.key(),
+        AuctionError::LeaderNotRankedFirst
+    );
+
+    let increment = state.bid_increment.max(1);
+    let leader_amount_before = leader.amount;
+    let takes_lead = challenger.max_amount > leader.max_amount;
+
+    if takes_lead {
+        if leader.proxy {
+            leader.amount = leader.max_amount;
+        }
+        if challenger.proxy {
+            escalate(challenger, leader.amount.saturating_add(increment));
+        }
+        state.set_leader(challenger_key, challenger_index);
+        state.leading_amount = challenger.amount;
+    } else {
+        if challenger.proxy {
+            challenger.amount = challenger.max_amount;
+        }
+        if leader.proxy {
+            escalate(leader, challenger.amount.saturating_add(increment));
+        }
+        state.leading_amount = leader.amount;
+    }
+
+    state.highest_ever_bid = state.highest_ever_bid.max(state.leading_amount);
+    state.leaderboard.record(leader.rank());
+    state.leaderboard.record(LeaderboardEntry {
+        bidder: challenger_key,
+        ..challenger.rank()
+    });
+
+    if leader.amount != leader_amount_before {
+        leader.updated_slot = Clock::get()?.slot;
+        emit!(BidUpdated {
+            bidder: leader.bidder,
+            new_amount: leader.amount,
+        });
+    }
+    if takes_lead {
+        emit!(Outbid {
+            previous_leader: leader.bidder,
+            previous_amount: leader.amount,
+            new_leader: challenger_key,
+            new_amount: challenger.amount,
+        });
+    }
+
+    Ok(())
+}
@@ -1,37 +1,91 @@
 use anchor_lang::prelude::*;
 
-mod error;
-mod event;
+pub mod error;
+pub mod event;
+mod forfeiture;
 mod instructions;
-mod state;
+mod leader;
+mod memo;
+pub mod merkle;
+pub mod mint_safety;
+mod payout;
+pub mod pda;
+mod prize;
+mod randomness;
+mod ranking;
+mod receipt;
+pub mod scheduler;
+mod transfer;
+mod wormhole;
+pub mod state;
 
 use instructions::*;
+use state::{AuctionConfig, ConfigUpdate};
 
 declare_id!("2UDUA7vCqZ87c4kCXbshF7S5uuxMXJvykwn9LJ1JnMU2");
 
 pub const USDC_DECIMALS: u8 = 6;
+pub const BPS_DENOMINATOR: u64 = 10_000;
 
 #[program]
 pub mod cartoonist_auction {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, minimum_bid: u64) -> Result<()> {
-        instructions::initialize::handler(ctx, minimum_bid)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        minimum_bid: u64,
+        mint_overrides: u8,
+    ) -> Result<()> {
+        instructions::initialize::handler(ctx, minimum_bid, mint_overrides)
     }
 
-    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
-        instructions::place_bid::handler(ctx, amount)
+    pub fn place_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+        bid_index: u16,
+        amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::place_bid::handler(ctx, bid_index, amount, referrer)
     }
 
-    pub fn update_bid(ctx: Context<UpdateBid>, amount_change: i64) -> Result<()> {
+    pub fn place_proxy_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+        bid_index: u16,
+        max_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::place_bid::proxy_handler(ctx, bid_index, max_amount, referrer)
+    }
+
+    /// Deprecated: use `increase_bid` or `decrease_bid`.
+    pub fn update_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+        amount_change: i64,
+    ) -> Result<()> {
         instructions::update_bid::handler(ctx, amount_change)
     }
 
-    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+    pub fn increase_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::update_bid::increase_handler(ctx, amount)
+    }
+
+    pub fn decrease_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateBid<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::update_bid::decrease_handler(ctx, amount)
+    }
+
+    pub fn withdraw_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawBid<'info>>,
+    ) -> Result<()> {
         instructions::withdraw_bid::handler(ctx)
     }
 
-    pub fn settle(ctx: Context<Settle>) -> Result<()> {
+    pub fn settle<'info>(ctx: Context<'_, '_, 'info, 'info, Settle<'info>>) -> Result<()> {
         instructions::settle::handler(ctx)
     }
 
@@ -46,4 +100,537 @@ pub mod cartoonist_auction {
     pub fn set_agent(ctx: Context<SetAgent>) -> Result<()> {
         instructions::set_agent::handler(ctx)
     }
+
+    pub fn set_end_time(ctx: Context<SetEndTime>, end_time: i64) -> Result<()> {
+        instructions::set_end_time::handler(ctx, end_time)
+    }
+
+    pub fn set_withdrawal_penalty(
+        ctx: Context<SetWithdrawalPenalty>,
+        penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::set_withdrawal_penalty::handler(ctx, penalty_bps)
+    }
+
+    pub fn sweep_penalties<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepPenalties<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_penalties::handler(ctx)
+    }
+
+    pub fn set_bid_increment(ctx: Context<SetBidIncrement>, bid_increment: u64) -> Result<()> {
+        instructions::set_bid_increment::handler(ctx, bid_increment)
+    }
+
+    pub fn set_buy_now_price(ctx: Context<SetBuyNowPrice>, buy_now_price: u64) -> Result<()> {
+        instructions::set_buy_now_price::handler(ctx, buy_now_price)
+    }
+
+    pub fn buy_now<'info>(ctx: Context<'_, '_, 'info, 'info, BuyNow<'info>>) -> Result<()> {
+        instructions::buy_now::handler(ctx)
+    }
+
+    pub fn set_num_winners(ctx: Context<SetNumWinners>, num_winners: u8) -> Result<()> {
+        instructions::set_num_winners::handler(ctx, num_winners)
+    }
+
+    pub fn settle_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleMulti<'info>>,
+    ) -> Result<()> {
+        instructions::settle_multi::handler(ctx)
+    }
+
+    pub fn set_uniform_price(ctx: Context<SetUniformPrice>, enabled: bool) -> Result<()> {
+        instructions::set_uniform_price::handler(ctx, enabled)
+    }
+
+    pub fn set_charity(ctx: Context<SetCharity>, charity_bps: u16) -> Result<()> {
+        instructions::set_charity::handler(ctx, charity_bps)
+    }
+
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        instructions::set_treasury::handler(ctx)
+    }
+
+    pub fn assert_solvent(ctx: Context<AssertSolvent>) -> Result<()> {
+        instructions::assert_solvent::handler(ctx)
+    }
+
+    pub fn sweep_surplus<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepSurplus<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_surplus::handler(ctx)
+    }
+
+    pub fn crank_settle<'info>(ctx: Context<'_, '_, 'info, 'info, CrankSettle<'info>>) -> Result<()> {
+        instructions::crank_settle::handler(ctx)
+    }
+
+    pub fn crank_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankRefund<'info>>,
+    ) -> Result<()> {
+        instructions::crank_refund::handler(ctx)
+    }
+
+    pub fn schedule_settlement(ctx: Context<ScheduleSettlement>, amount: u64) -> Result<()> {
+        instructions::schedule_settlement::handler(ctx, amount)
+    }
+
+    pub fn plan_settlement(ctx: Context<PlanSettlement>) -> Result<()> {
+        instructions::plan_settlement::handler(ctx)
+    }
+
+    pub fn request_tie_break(ctx: Context<RequestTieBreak>) -> Result<()> {
+        instructions::request_tie_break::handler(ctx)
+    }
+
+    pub fn resolve_tie(ctx: Context<ResolveTie>) -> Result<()> {
+        instructions::resolve_tie::handler(ctx)
+    }
+
+    pub fn set_raffle_mode(ctx: Context<SetRaffleMode>, enabled: bool) -> Result<()> {
+        instructions::set_raffle_mode::handler(ctx, enabled)
+    }
+
+    pub fn request_raffle_draw(ctx: Context<RequestRaffleDraw>) -> Result<()> {
+        instructions::request_raffle_draw::handler(ctx)
+    }
+
+    pub fn draw_raffle<'info>(ctx: Context<'_, '_, 'info, 'info, DrawRaffle<'info>>) -> Result<()> {
+        instructions::draw_raffle::handler(ctx)
+    }
+
+    pub fn set_wormhole_config(
+        ctx: Context<SetWormholeConfig>,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_wormhole_config::handler(ctx, emitter_chain, emitter_address)
+    }
+
+    pub fn receive_cross_chain_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReceiveCrossChainBid<'info>>,
+        sequence: u64,
+        bidder: Pubkey,
+    ) -> Result<()> {
+        instructions::receive_cross_chain_bid::handler(ctx, sequence, bidder)
+    }
+
+    pub fn receive_cross_chain_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReceiveCrossChainWithdrawal<'info>>,
+        sequence: u64,
+        bidder: Pubkey,
+    ) -> Result<()> {
+        instructions::receive_cross_chain_withdrawal::handler(ctx, sequence, bidder)
+    }
+
+    pub fn post_refund_root(
+        ctx: Context<PostRefundRoot>,
+        root: [u8; 32],
+        leaf_count: u32,
+    ) -> Result<()> {
+        instructions::post_refund_root::handler(ctx, root, leaf_count)
+    }
+
+    pub fn claim_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRefund<'info>>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_refund::handler(ctx, index, amount, proof)
+    }
+
+    pub fn commit_content(ctx: Context<CommitContent>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::commit_content::handler(ctx, content_hash)
+    }
+
+    pub fn reveal_content(ctx: Context<RevealContent>, uri: String) -> Result<()> {
+        instructions::reveal_content::handler(ctx, uri)
+    }
+
+    pub fn deposit_prize<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositPrize<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_prize::handler(ctx, amount)
+    }
+
+    pub fn withdraw_prize<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawPrize<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_prize::handler(ctx)
+    }
+
+    pub fn set_vesting_duration(
+        ctx: Context<SetVestingDuration>,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        instructions::set_vesting_duration::handler(ctx, vesting_duration)
+    }
+
+    pub fn claim_settled_funds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimSettledFunds<'info>>,
+    ) -> Result<()> {
+        instructions::claim_settled_funds::handler(ctx)
+    }
+
+    pub fn set_deposit_terms(
+        ctx: Context<SetDepositTerms>,
+        deposit_bps: u16,
+        payment_window: i64,
+    ) -> Result<()> {
+        instructions::set_deposit_terms::handler(ctx, deposit_bps, payment_window)
+    }
+
+    pub fn complete_payment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompletePayment<'info>>,
+    ) -> Result<()> {
+        instructions::complete_payment::handler(ctx)
+    }
+
+    pub fn forfeit_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForfeitDeposit<'info>>,
+    ) -> Result<()> {
+        instructions::forfeit_deposit::handler(ctx)
+    }
+
+    pub fn add_accepted_mint(ctx: Context<AddAcceptedMint>) -> Result<()> {
+        instructions::add_accepted_mint::handler(ctx)
+    }
+
+    pub fn remove_accepted_mint(ctx: Context<RemoveAcceptedMint>, mint: Pubkey) -> Result<()> {
+        instructions::remove_accepted_mint::handler(ctx, mint)
+    }
+
+    pub fn harvest_withheld_fees(ctx: Context<HarvestWithheldFees>) -> Result<()> {
+        instructions::harvest_withheld_fees::handler(ctx)
+    }
+
+    pub fn set_transfer_hooks(ctx: Context<SetTransferHooks>, enabled: bool) -> Result<()> {
+        instructions::set_transfer_hooks::handler(ctx, enabled)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        instructions::update_config::handler(ctx, update)
+    }
+
+    pub fn set_settler(ctx: Context<SetSettler>, settler: Pubkey) -> Result<()> {
+        instructions::set_settler::handler(ctx, settler)
+    }
+
+    pub fn set_abandonment_window(
+        ctx: Context<SetAbandonmentWindow>,
+        abandonment_window: i64,
+    ) -> Result<()> {
+        instructions::set_abandonment_window::handler(ctx, abandonment_window)
+    }
+
+    pub fn mark_abandoned(ctx: Context<MarkAbandoned>) -> Result<()> {
+        instructions::mark_abandoned::handler(ctx)
+    }
+
+    pub fn set_curator(ctx: Context<SetCurator>, curator: Pubkey, curator_bps: u16) -> Result<()> {
+        instructions::set_curator::handler(ctx, curator, curator_bps)
+    }
+
+    pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+        instructions::set_referral_bps::handler(ctx, referral_bps)
+    }
+
+    pub fn transfer_bid(ctx: Context<TransferBid>) -> Result<()> {
+        instructions::transfer_bid::handler(ctx)
+    }
+
+    pub fn create_receipt_mint(ctx: Context<CreateReceiptMint>) -> Result<()> {
+        instructions::create_receipt_mint::handler(ctx)
+    }
+
+    pub fn set_moderator(ctx: Context<SetModerator>, moderator: Pubkey, bid_bond: u64) -> Result<()> {
+        instructions::set_moderator::handler(ctx, moderator, bid_bond)
+    }
+
+    pub fn slash_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SlashBid<'info>>,
+    ) -> Result<()> {
+        instructions::slash_bid::handler(ctx)
+    }
+
+    pub fn set_bid_ttl(ctx: Context<SetBidTtl>, bid_ttl: i64) -> Result<()> {
+        instructions::set_bid_ttl::handler(ctx, bid_ttl)
+    }
+
+    pub fn expire_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireBid<'info>>,
+    ) -> Result<()> {
+        instructions::expire_bid::handler(ctx)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        instructions::cancel_auction::handler(ctx)
+    }
+
+    pub fn set_english_auction(ctx: Context<SetEnglishAuction>, enabled: bool) -> Result<()> {
+        instructions::set_english_auction::handler(ctx, enabled)
+    }
+
+    pub fn set_candle_window(ctx: Context<SetCandleWindow>, candle_window: i64) -> Result<()> {
+        instructions::set_candle_window::handler(ctx, candle_window)
+    }
+
+    pub fn request_candle_end(ctx: Context<RequestCandleEnd>) -> Result<()> {
+        instructions::request_candle_end::handler(ctx)
+    }
+
+    pub fn determine_candle_end<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DetermineCandleEnd<'info>>,
+    ) -> Result<()> {
+        instructions::determine_candle_end::handler(ctx)
+    }
+
+    pub fn set_all_pay(ctx: Context<SetAllPay>, enabled: bool) -> Result<()> {
+        instructions::set_all_pay::handler(ctx, enabled)
+    }
+
+    pub fn collect_all_pay_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectAllPayBid<'info>>,
+    ) -> Result<()> {
+        instructions::collect_all_pay_bid::handler(ctx)
+    }
+
+    pub fn initialize_operator(ctx: Context<InitializeOperator>) -> Result<()> {
+        instructions::initialize_operator::handler(ctx)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        auction_id: u64,
+        config: AuctionConfig,
+    ) -> Result<()> {
+        instructions::create_auction::handler(ctx, auction_id, config)
+    }
+
+    pub fn sync_registry(ctx: Context<SyncRegistry>) -> Result<()> {
+        instructions::sync_registry::handler(ctx)
+    }
+
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        instructions::close_auction::handler(ctx)
+    }
+
+    pub fn upsert_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+        bid_index: u16,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::place_bid::upsert_handler(ctx, bid_index, amount)
+    }
+
+    pub fn create_lot(
+        ctx: Context<CreateLot>,
+        minimum_bid: u64,
+        reserve_price: u64,
+        item_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::create_lot::handler(ctx, minimum_bid, reserve_price, item_hash)
+    }
+
+    pub fn place_lot_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceLotBid<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::place_lot_bid::handler(ctx, amount)
+    }
+
+    pub fn withdraw_lot_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawLotBid<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_lot_bid::handler(ctx)
+    }
+
+    pub fn settle_lot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleLot<'info>>,
+    ) -> Result<()> {
+        instructions::settle_lot::handler(ctx)
+    }
+
+    pub fn place_bundle_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBundleBid<'info>>,
+        lots: Vec<u16>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::place_bundle_bid::handler(ctx, lots, amount)
+    }
+
+    pub fn settle_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBundle<'info>>,
+    ) -> Result<()> {
+        instructions::settle_bundle::handler(ctx)
+    }
+
+    pub fn set_standing(ctx: Context<SetStanding>, standing: bool) -> Result<()> {
+        instructions::set_standing::handler(ctx, standing)
+    }
+
+    pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        instructions::initialize_audit_log::handler(ctx)
+    }
+
+    pub fn migrate_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateEscrow<'info>>,
+        mint_overrides: u8,
+    ) -> Result<()> {
+        instructions::migrate_escrow::handler(ctx, mint_overrides)
+    }
+
+    pub fn rescue_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RescueTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::rescue_tokens::handler(ctx, amount)
+    }
+
+    pub fn link_bid(ctx: Context<LinkBid>) -> Result<()> {
+        instructions::link_bid::handler(ctx)
+    }
+
+    pub fn unlink_bid(ctx: Context<UnlinkBid>) -> Result<()> {
+        instructions::unlink_bid::handler(ctx)
+    }
+
+    pub fn settle_top_k<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleTopK<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::settle_top_k::handler(ctx, count)
+    }
+
+    pub fn gc_bid<'info>(ctx: Context<'_, '_, 'info, 'info, GcBid<'info>>) -> Result<()> {
+        instructions::gc_bid::handler(ctx)
+    }
+
+    pub fn set_denylisted(
+        ctx: Context<SetDenylisted>,
+        wallet: Pubkey,
+        denied: bool,
+    ) -> Result<()> {
+        instructions::set_denylisted::handler(ctx, wallet, denied)
+    }
+
+    pub fn force_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceRefund<'info>>,
+    ) -> Result<()> {
+        instructions::force_refund::handler(ctx)
+    }
+
+    pub fn set_dispute_terms(
+        ctx: Context<SetDisputeTerms>,
+        arbiter: Pubkey,
+        dispute_window: i64,
+    ) -> Result<()> {
+        instructions::set_dispute_terms::handler(ctx, arbiter, dispute_window)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        instructions::open_dispute::handler(ctx)
+    }
+
+    pub fn resolve_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveSettlement<'info>>,
+        refund: bool,
+    ) -> Result<()> {
+        instructions::resolve_settlement::handler(ctx, refund)
+    }
+
+    pub fn set_claim_window(ctx: Context<SetClaimWindow>, claim_window: i64) -> Result<()> {
+        instructions::set_claim_window::handler(ctx, claim_window)
+    }
+
+    pub fn claim_win(ctx: Context<ClaimWin>) -> Result<()> {
+        instructions::claim_win::handler(ctx)
+    }
+
+    pub fn promote_next_bidder<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PromoteNextBidder<'info>>,
+    ) -> Result<()> {
+        instructions::promote_next_bidder::handler(ctx)
+    }
+
+    pub fn set_default_penalty(
+        ctx: Context<SetDefaultPenalty>,
+        default_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::set_default_penalty::handler(ctx, default_penalty_bps)
+    }
+
+    pub fn set_min_settle_slots(
+        ctx: Context<SetMinSettleSlots>,
+        min_settle_slots: u64,
+    ) -> Result<()> {
+        instructions::set_min_settle_slots::handler(ctx, min_settle_slots)
+    }
+
+    pub fn set_memo(ctx: Context<SetMemo>, memo: String) -> Result<()> {
+        instructions::set_memo::handler(ctx, memo)
+    }
+
+    pub fn create_points_mint(ctx: Context<CreatePointsMint>) -> Result<()> {
+        instructions::create_points_mint::handler(ctx)
+    }
+
+    pub fn set_points_rate(ctx: Context<SetPointsRate>, points_rate: u64) -> Result<()> {
+        instructions::set_points_rate::handler(ctx, points_rate)
+    }
+
+    pub fn claim_points(ctx: Context<ClaimPoints>) -> Result<()> {
+        instructions::claim_points::handler(ctx)
+    }
+
+    pub fn set_rebate_bps(ctx: Context<SetRebateBps>, rebate_bps: u16) -> Result<()> {
+        instructions::set_rebate_bps::handler(ctx, rebate_bps)
+    }
+
+    pub fn set_session_key(
+        ctx: Context<SetSessionKey>,
+        session_key: Pubkey,
+        expires_at: i64,
+        spend_cap: u64,
+    ) -> Result<()> {
+        instructions::set_session_key::handler(ctx, session_key, expires_at, spend_cap)
+    }
+
+    pub fn set_reveal_window(ctx: Context<SetRevealWindow>, reveal_window: i64) -> Result<()> {
+        instructions::set_reveal_window::handler(ctx, reveal_window)
+    }
+
+    pub fn place_sealed_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+        bid_index: u16,
+        deposit: u64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::place_bid::sealed_handler(ctx, bid_index, deposit, commitment)
+    }
+
+    pub fn reveal_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealBid<'info>>,
+        amount: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_bid::handler(ctx, amount, salt)
+    }
+
+    pub fn set_commit_bond(ctx: Context<SetCommitBond>, commit_bond: u64) -> Result<()> {
+        instructions::set_commit_bond::handler(ctx, commit_bond)
+    }
+
+    pub fn sweep_commit_bonds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepCommitBonds<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_commit_bonds::handler(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
 }
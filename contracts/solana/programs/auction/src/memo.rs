@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::{build_memo, BuildMemo, Memo};
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+/// Logs the auction's memo through the SPL Memo program, so custodians can
+/// match the transfer in the same transaction to an invoice or round. Once a
+/// memo is set every settlement and refund must pass the program; without
+/// one this does nothing.
+pub fn write_memo<'info>(
+    state: &AuctionState,
+    memo_program: Option<&Program<'info, Memo>>,
+) -> Result<()> {
+    let memo = state.memo();
+    if memo.is_empty() {
+        return Ok(());
+    }
+    let memo_program = memo_program.ok_or(AuctionError::MemoProgramRequired)?;
+    build_memo(CpiContext::new(memo_program.to_account_info(), BuildMemo {}), memo)
+}
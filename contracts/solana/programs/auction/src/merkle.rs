@@ -0,0 +1,32 @@
+//! Refund Merkle tree hashing. Leaves and inner nodes are domain-separated,
+//! and inner nodes hash their children in sorted order, so a proof is just
+//! the list of sibling hashes from leaf to root.
+
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf committing to refunding `amount` to `bidder`, at bitmap slot `index`.
+pub fn leaf(index: u32, bidder: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        bidder.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |hash, sibling| node(&hash, sibling))
+        == *root
+}
@@ -0,0 +1,57 @@
+//! Token-2022 extensions that could trap or seize escrowed bids, checked
+//! before a mint backs the auction.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::non_transferable::NonTransferable;
+use anchor_spl::token_2022::spl_token_2022::extension::pausable::PausableConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{AccountState, Mint};
+
+use crate::error::AuctionError;
+
+/// Accept a mint whose permanent delegate can move tokens out of the escrow.
+pub const ALLOW_PERMANENT_DELEGATE: u8 = 1 << 0;
+/// Accept a mint whose new token accounts, the escrow included, start frozen.
+pub const ALLOW_DEFAULT_FROZEN: u8 = 1 << 1;
+/// Accept a mint whose pause authority can halt every transfer.
+pub const ALLOW_PAUSABLE: u8 = 1 << 2;
+
+/// Rejects a Token-2022 mint with a risky extension unless `overrides` sets
+/// that extension's `ALLOW_*` flag. Non-transferable mints are always
+/// rejected, since escrow could never be paid out. SPL Token mints have no
+/// extensions and always pass.
+pub fn validate(mint: &AccountInfo, overrides: u8) -> Result<()> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    require!(
+        state.get_extension::<NonTransferable>().is_err(),
+        AuctionError::NonTransferableMint
+    );
+
+    let permanent_delegate = state
+        .get_extension::<PermanentDelegate>()
+        .is_ok_and(|extension| Option::<Pubkey>::from(extension.delegate).is_some());
+    let default_frozen = state
+        .get_extension::<DefaultAccountState>()
+        .is_ok_and(|extension| extension.state == AccountState::Frozen as u8);
+    let pausable = state.get_extension::<PausableConfig>().is_ok();
+
+    for (present, flag, risk) in [
+        (permanent_delegate, ALLOW_PERMANENT_DELEGATE, "a permanent delegate"),
+        (default_frozen, ALLOW_DEFAULT_FROZEN, "default-frozen accounts"),
+        (pausable, ALLOW_PAUSABLE, "a pause authority"),
+    ] {
+        if present && overrides & flag == 0 {
+            msg!("Mint has {}", risk);
+            return err!(AuctionError::UnsafeMint);
+        }
+    }
+    Ok(())
+}
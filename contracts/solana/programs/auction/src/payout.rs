@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::{
+    BidSettled, FeeDiscounted, ReferralPaid, SettledFundsStreamed, SettlementHeld,
+};
+use crate::memo::write_memo;
+use crate::state::{AcceptedMints, AuctionState, Bid};
+use crate::transfer::transfer_checked;
+use crate::{BPS_DENOMINATOR, USDC_DECIMALS};
+
+/// Accounts needed to pay a winning bid out of escrow.
+pub struct Payout<'a, 'info> {
+    pub auction_state: &'a mut Account<'info, AuctionState>,
+    pub escrow: &'a InterfaceAccount<'info, TokenAccount>,
+    pub treasury: &'a InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    /// Receives any escrow above the price. Only required when there is some.
+    pub winner_usdc: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub charity: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// The curator's associated token account, checked when a share is owed.
+    pub curator_usdc: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// The winning bid's referrer's associated token account, checked likewise.
+    pub referrer_usdc: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// Required when the bid was placed in a mint other than the auction mint.
+    pub accepted_mints: Option<&'a mut AcceptedMints>,
+    /// Forwarded to the mint's transfer hook, if it has one.
+    pub hook_accounts: &'a [AccountInfo<'info>],
+    /// Logs the auction's memo alongside the payout; see [`write_memo`].
+    pub memo_program: Option<&'a Program<'info, Memo>>,
+}
+
+impl<'info> Payout<'_, 'info> {
+    /// Marks `bid` won at `price`: the treasury, charity, curator and referrer
+    /// split the price, the winner gets back any escrow above it, and the bid
+    /// leaves leader tracking. With a dispute window set, the price instead
+    /// stays in escrow until `resolve_settlement` rules on it.
+    /// Bids in an accepted mint are paid from that mint's escrow to its treasury.
+    pub fn settle(mut self, bid: &mut Bid, price: u64) -> Result<()> {
+        require!(bid.balance_due == 0, AuctionError::BalanceDue);
+        let clock = Clock::get()?;
+        require!(
+            !self.auction_state.is_expired(bid, clock.unix_timestamp),
+            AuctionError::BidExpired
+        );
+        require!(
+            !self.auction_state.settles_too_soon(bid, clock.slot),
+            AuctionError::SettlementTooSoon
+        );
+        write_memo(self.auction_state, self.memo_program)?;
+        let mint = self.usdc_mint.key();
+        require_keys_eq!(bid.mint, mint, AuctionError::InvalidTokenAccount);
+        let accepted = self.accepted_mints.as_deref();
+        require_keys_eq!(
+            self.escrow.key(),
+            self.auction_state.escrow_for(accepted, &mint)?,
+            AuctionError::InvalidTokenAccount
+        );
+        require_keys_eq!(
+            self.treasury.key(),
+            self.auction_state.treasury_for(accepted, &mint)?,
+            AuctionError::InvalidTokenAccount
+        );
+        let auction_mint = mint == self.auction_state.usdc_mint;
+        let surplus = bid
+            .max_amount
+            .checked_sub(price)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+
+        let state = &mut *self.auction_state;
+        state.accrue_points(bid, clock.unix_timestamp)?;
+        bid.amount = price;
+        bid.active = false;
+        state.winning_amount = state.winning_amount.saturating_add(price);
+        state.total_volume = state.total_volume.saturating_add(u128::from(price));
+
+        state.active_bid_count = state
+            .active_bid_count
+            .checked_sub(1)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.forget_bid(bid.bidder, bid.bid_index);
+        state.debit_escrow_in(self.accepted_mints.as_deref_mut(), &mint, bid.max_amount)?;
+
+        if state.dispute_window > 0 {
+            require!(auction_mint, AuctionError::AuctionMintOnly);
+            require!(state.held_amount == 0, AuctionError::SettlementAlreadyHeld);
+            let held_until = Clock::get()?
+                .unix_timestamp
+                .checked_add(state.dispute_window)
+                .ok_or(AuctionError::ArithmeticOverflow)?;
+            state.credit_escrow(price)?;
+            state.held_amount = price;
+            state.held_winner = bid.bidder;
+            state.held_referrer = bid.referrer;
+            state.held_discount_bps = bid.fee_discount_bps;
+            state.held_until = held_until;
+            state.disputed = false;
+            emit!(SettlementHeld {
+                winner: bid.bidder,
+                amount: price,
+                held_until,
+            });
+        } else {
+            self.distribute(bid.bidder, bid.referrer, bid.fee_discount_bps, price)?;
+        }
+
+        if surplus > 0 {
+            let winner_usdc = self
+                .winner_usdc
+                .ok_or(AuctionError::WinnerTokenAccountRequired)?;
+            self.transfer(winner_usdc, surplus)?;
+        }
+
+        Ok(())
+    }
+
+    /// Splits `price`, already out of escrow accounting, between the
+    /// treasury, charity, curator and `referrer`, handing `discount_bps` of
+    /// the treasury's cut back to the winner.
+    pub fn distribute(
+        &mut self,
+        winner: Pubkey,
+        referrer: Pubkey,
+        discount_bps: u16,
+        price: u64,
+    ) -> Result<()> {
+        let auction_mint = self.usdc_mint.key() == self.auction_state.usdc_mint;
+        let state = &*self.auction_state;
+        let charity_amount = state.charity_share(price);
+        let curator_amount = state.curator_share(price);
+        let referral_amount = if referrer == Pubkey::default() {
+            0
+        } else {
+            state.referral_share(price)
+        };
+        // Vesting and the charity account are denominated in the auction mint.
+        require!(
+            auction_mint || (state.vesting_duration == 0 && charity_amount == 0),
+            AuctionError::AuctionMintOnly
+        );
+
+        let treasury_amount = price - charity_amount - curator_amount - referral_amount;
+        let discount = (u128::from(treasury_amount) * u128::from(discount_bps)
+            / u128::from(BPS_DENOMINATOR)) as u64;
+        let treasury_amount = treasury_amount - discount;
+        let rebate = if auction_mint {
+            self.auction_state.set_aside_rebate(treasury_amount)?
+        } else {
+            0
+        };
+        let treasury_amount = treasury_amount - rebate;
+        if self.auction_state.vesting_duration > 0 {
+            self.stream(treasury_amount)?;
+        } else {
+            self.transfer(self.treasury, treasury_amount)?;
+        }
+
+        if discount > 0 {
+            let winner_usdc = self
+                .winner_usdc
+                .ok_or(AuctionError::WinnerTokenAccountRequired)?;
+            self.transfer(winner_usdc, discount)?;
+            emit!(FeeDiscounted {
+                winner,
+                discount_bps,
+                amount: discount,
+            });
+        }
+
+        if charity_amount > 0 {
+            let charity = self.charity.ok_or(AuctionError::CharityAccountRequired)?;
+            self.transfer(charity, charity_amount)?;
+        }
+
+        if curator_amount > 0 {
+            let curator_usdc = self
+                .curator_usdc
+                .ok_or(AuctionError::CuratorAccountRequired)?;
+            self.require_associated(curator_usdc, &self.auction_state.curator)?;
+            self.transfer(curator_usdc, curator_amount)?;
+        }
+
+        if referral_amount > 0 {
+            let referrer_usdc = self
+                .referrer_usdc
+                .ok_or(AuctionError::ReferrerAccountRequired)?;
+            self.require_associated(referrer_usdc, &referrer)?;
+            self.transfer(referrer_usdc, referral_amount)?;
+            emit!(ReferralPaid {
+                referrer,
+                winner,
+                amount: referral_amount,
+            });
+        }
+
+        emit!(BidSettled {
+            winner,
+            amount: price,
+            charity_amount,
+            curator: self.auction_state.curator,
+            curator_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Keeps `amount` in escrow and adds it to the treasury's stream. Whatever
+    /// has already vested is paid out first; the rest restarts alongside
+    /// `amount` over a full `vesting_duration` from now.
+    fn stream(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let released = self.auction_state.claimable(now);
+        if released > 0 {
+            self.transfer(self.treasury, released)?;
+        }
+
+        let state = &mut *self.auction_state;
+        let remaining = state.unclaimed() - released;
+        state.debit_escrow(released)?;
+        state.credit_escrow(amount)?;
+        state.vesting_start = now;
+        state.vesting_total = remaining
+            .checked_add(amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        state.vesting_claimed = 0;
+
+        emit!(SettledFundsStreamed {
+            amount,
+            released,
+            vesting_total: state.vesting_total,
+        });
+
+        Ok(())
+    }
+
+    /// Checks `account` is `owner`'s associated token account for the bid mint.
+    fn require_associated(
+        &self,
+        account: &InterfaceAccount<'info, TokenAccount>,
+        owner: &Pubkey,
+    ) -> Result<()> {
+        let expected = get_associated_token_address_with_program_id(
+            owner,
+            &self.usdc_mint.key(),
+            &self.token_program.key(),
+        );
+        require_keys_eq!(account.key(), expected, AuctionError::InvalidTokenAccount);
+        Ok(())
+    }
+
+    pub fn transfer(
+        &self,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let state_bump = self.auction_state.bump;
+        let id_seed = self.auction_state.id_seed();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state_bump]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.auction_state.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            USDC_DECIMALS,
+            self.hook_accounts,
+        )
+    }
+}
@@ -0,0 +1,187 @@
+//! Addresses of the program's PDAs, from the same seeds its accounts
+//! constraints check.
+//!
+//! Each `find_*` searches for the canonical bump and returns it with the
+//! address. Accounts that store their bump (`AuctionState::bump`,
+//! `AuctionState::escrow_bump`, `Bid::bump`) can be re-derived from it with
+//! the matching `*_address`, one hash instead of a search.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::PubkeyError;
+
+use crate::state::{auction_seed, bid_index_seed};
+use crate::ID;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &ID)
+}
+
+fn create(seeds: &[&[u8]], bump: u8) -> std::result::Result<Pubkey, PubkeyError> {
+    let mut seeds = seeds.to_vec();
+    let bump = [bump];
+    seeds.push(&bump);
+    Pubkey::create_program_address(&seeds, &ID)
+}
+
+pub fn find_auction_state(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"auction_state", &auction_seed(auction_id)])
+}
+
+pub fn auction_state_address(
+    auction_id: u64,
+    bump: u8,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create(&[b"auction_state", &auction_seed(auction_id)], bump)
+}
+
+/// The escrow the auction was created with; see [`find_migrated_escrow`]
+/// for its replacements.
+pub fn find_escrow(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"escrow", &auction_seed(auction_id)])
+}
+
+pub fn escrow_address(auction_id: u64, bump: u8) -> std::result::Result<Pubkey, PubkeyError> {
+    create(&[b"escrow", &auction_seed(auction_id)], bump)
+}
+
+/// Escrow `migrate_escrow` creates for `mint` as the auction's
+/// `generation`-th replacement (1-based).
+pub fn find_migrated_escrow(auction_id: u64, mint: &Pubkey, generation: u8) -> (Pubkey, u8) {
+    find(&[b"escrow", &auction_seed(auction_id), mint.as_ref(), &[generation]])
+}
+
+/// Escrow for bids in an accepted mint other than the auction mint.
+pub fn find_mint_escrow(auction_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"escrow", &auction_seed(auction_id), mint.as_ref()])
+}
+
+/// The bidder's bid `bid_index` in an auction; index zero is their first.
+pub fn find_bid(auction_id: u64, bidder: &Pubkey, bid_index: u16) -> (Pubkey, u8) {
+    find(&[
+        b"bid",
+        &auction_seed(auction_id),
+        bidder.as_ref(),
+        &bid_index_seed(bid_index),
+    ])
+}
+
+pub fn bid_address(
+    auction_id: u64,
+    bidder: &Pubkey,
+    bid_index: u16,
+    bump: u8,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create(
+        &[
+            b"bid",
+            &auction_seed(auction_id),
+            bidder.as_ref(),
+            &bid_index_seed(bid_index),
+        ],
+        bump,
+    )
+}
+
+/// Program-wide record of the agent allowed to call `create_auction`.
+pub fn find_operator() -> (Pubkey, u8) {
+    find(&[b"operator"])
+}
+
+/// Page `index` of the registry of auctions from `create_auction`.
+pub fn find_registry_page(index: u32) -> (Pubkey, u8) {
+    find(&[b"registry", &index.to_le_bytes()])
+}
+
+/// Archived result of settled `round` (1-based).
+pub fn find_round_result(auction_id: u64, round: u64) -> (Pubkey, u8) {
+    find(&[b"round_result", &auction_seed(auction_id), &round.to_le_bytes()])
+}
+
+/// `winner`'s proof of winning `round` (1-based).
+pub fn find_win_receipt(auction_id: u64, round: u64, winner: &Pubkey) -> (Pubkey, u8) {
+    find(&[
+        b"win_receipt",
+        &auction_seed(auction_id),
+        &round.to_le_bytes(),
+        winner.as_ref(),
+    ])
+}
+
+/// Loyalty points mint, whose authority is the auction state.
+pub fn find_points_mint(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"points_mint", &auction_seed(auction_id)])
+}
+
+/// Mint of the receipt tokens bidders may take for their bids.
+pub fn find_receipt_mint(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"receipt_mint", &auction_seed(auction_id)])
+}
+
+pub fn find_price_feed(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"price_feed", &auction_seed(auction_id)])
+}
+
+pub fn find_audit_log(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"audit_log", &auction_seed(auction_id)])
+}
+
+pub fn find_denylist(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"denylist", &auction_seed(auction_id)])
+}
+
+pub fn find_config(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"config", &auction_seed(auction_id)])
+}
+
+pub fn find_accepted_mints(auction_id: u64) -> (Pubkey, u8) {
+    find(&[b"accepted_mints", &auction_seed(auction_id)])
+}
+
+/// Lot `lot_id` (1-based) of an auction.
+pub fn find_lot(auction_id: u64, lot_id: u16) -> (Pubkey, u8) {
+    find(&[b"lot", &auction_seed(auction_id), &lot_id.to_le_bytes()])
+}
+
+pub fn find_lot_bid(auction_id: u64, lot_id: u16, bidder: &Pubkey) -> (Pubkey, u8) {
+    find(&[
+        b"lot_bid",
+        &auction_seed(auction_id),
+        &lot_id.to_le_bytes(),
+        bidder.as_ref(),
+    ])
+}
+
+pub fn find_bundle_bid(auction_id: u64, bidder: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"bundle_bid", &auction_seed(auction_id), bidder.as_ref()])
+}
+
+/// Replay marker for a Wormhole message consumed by auction `auction_id`.
+pub fn find_received(
+    auction_id: u64,
+    emitter_chain: u16,
+    emitter_address: &[u8; 32],
+    sequence: u64,
+) -> (Pubkey, u8) {
+    find(&[
+        b"received",
+        &auction_seed(auction_id),
+        &emitter_chain.to_le_bytes(),
+        emitter_address,
+        &sequence.to_le_bytes(),
+    ])
+}
+
+/// Claim bitmap for a posted refunds Merkle root.
+pub fn find_refund_claims(auction_id: u64, root: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[b"refund_claims", &auction_seed(auction_id), root])
+}
+
+/// Revealed deliverable for a committed content hash.
+pub fn find_content_reveal(auction_id: u64, content_hash: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[b"content", &auction_seed(auction_id), content_hash])
+}
+
+/// Vault holding a prize escrowed by the agent.
+pub fn find_prize_vault(auction_id: u64, prize_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"prize_vault", &auction_seed(auction_id), prize_mint.as_ref()])
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::AuctionError;
+use crate::event::PrizeDelivered;
+use crate::state::AuctionState;
+use crate::transfer::transfer_checked;
+
+/// Optional accounts a settlement needs to hand over an escrowed prize.
+pub struct PrizeDelivery<'a, 'info> {
+    pub auction_state: &'a mut Account<'info, AuctionState>,
+    pub prize_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub prize_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub winner_prize: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub prize_token_program: Option<&'a Interface<'info, TokenInterface>>,
+    /// Forwarded to the prize mint's transfer hook, if it has one.
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'info> PrizeDelivery<'_, 'info> {
+    /// Sends the escrowed prize to `winner`, completing the swap started by
+    /// paying their bid to the treasury. A no-op when no prize is escrowed.
+    pub fn deliver(self, winner: Pubkey) -> Result<()> {
+        let state = &mut *self.auction_state;
+        let amount = state.prize_amount;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let (Some(vault), Some(mint), Some(to), Some(token_program)) = (
+            self.prize_vault,
+            self.prize_mint,
+            self.winner_prize,
+            self.prize_token_program,
+        ) else {
+            return err!(AuctionError::PrizeAccountsRequired);
+        };
+        require!(
+            to.owner == winner && to.mint == state.prize_mint,
+            AuctionError::InvalidTokenAccount
+        );
+
+        let id_seed = state.id_seed();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[state.bump]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: state.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+            self.hook_accounts,
+        )?;
+
+        emit!(PrizeDelivered {
+            winner,
+            mint: state.prize_mint,
+            amount,
+        });
+
+        state.prize_mint = Pubkey::default();
+        state.prize_amount = 0;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+/// Binds a freshly committed Switchboard randomness account to the auction.
+/// Its value is unknown to everyone until the oracle reveals it.
+pub fn commit(state: &mut AuctionState, randomness_account: &AccountInfo) -> Result<()> {
+    let clock = Clock::get()?;
+    let randomness = RandomnessAccountData::parse(randomness_account.data.borrow())
+        .map_err(|_| AuctionError::InvalidRandomness)?;
+    require!(
+        randomness.seed_slot == clock.slot.saturating_sub(1),
+        AuctionError::InvalidRandomness
+    );
+
+    state.pending_randomness = randomness_account.key();
+    state.randomness_seed_slot = randomness.seed_slot;
+    Ok(())
+}
+
+/// Consumes the revealed value of the committed randomness account as a `u64`.
+pub fn reveal(state: &mut AuctionState, randomness_account: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(
+        randomness_account.key(),
+        state.pending_randomness,
+        AuctionError::InvalidRandomness
+    );
+
+    let clock = Clock::get()?;
+    let randomness = RandomnessAccountData::parse(randomness_account.data.borrow())
+        .map_err(|_| AuctionError::InvalidRandomness)?;
+    require!(
+        randomness.seed_slot == state.randomness_seed_slot,
+        AuctionError::InvalidRandomness
+    );
+    let value = randomness
+        .get_value(clock.slot)
+        .map_err(|_| AuctionError::RandomnessNotRevealed)?;
+
+    state.pending_randomness = Pubkey::default();
+    state.randomness_seed_slot = 0;
+
+    Ok(u64::from_le_bytes(value[..8].try_into().unwrap()))
+}
@@ -0,0 +1,125 @@
+//! Doubly linked list of a round's bids, highest amount first, headed by
+//! `AuctionState::ranked_head`. Callers name a bid's would-be neighbours and
+//! the program only checks them, so inserting costs the same wherever the
+//! bid lands.
+//!
+//! A ranked bid's amount cannot change in place: paths that raise, lower,
+//! move or refund a bid while the round is open refuse ranked ones with
+//! `BidRanked`, so clients unlink first and relink after, in the same
+//! transaction. The one exception is proxy escalation of the leader, which
+//! only raises it; a ranked leader must head the list, so the order holds.
+
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+use crate::state::{AuctionState, Bid};
+
+/// Links the active, unranked `bid` between `prev` and `next`, which must be
+/// adjacent in the list (`None` past either end).
+pub fn link(
+    state: &mut AuctionState,
+    key: Pubkey,
+    bid: &mut Bid,
+    prev: Option<&mut Account<Bid>>,
+    next: Option<&mut Account<Bid>>,
+) -> Result<()> {
+    require!(!state.is_settled(), AuctionError::AuctionSettled);
+    state.require_not_called_off()?;
+    require!(state.top_k_paid == 0, AuctionError::TopKSettlementPending);
+    require!(bid.active, AuctionError::BidNotActive);
+    require!(!state.is_ranked(bid), AuctionError::BidRanked);
+    // A foreign bidder cannot sign `unlink_bid`, so once ranked their bid
+    // could never be topped up or withdrawn again.
+    require!(bid.foreign_chain == 0, AuctionError::ForeignBid);
+
+    let prev_key = prev.as_ref().map(|p| p.key()).unwrap_or_default();
+    let next_key = next.as_ref().map(|n| n.key()).unwrap_or_default();
+    let after_prev = match &prev {
+        Some(prev) => {
+            require!(
+                prev.auction_id == state.auction_id && state.is_ranked(prev),
+                AuctionError::RankHintMismatch
+            );
+            prev.next_bid
+        }
+        None => state.ranked_head,
+    };
+    require_keys_eq!(after_prev, next_key, AuctionError::RankHintMismatch);
+
+    if let Some(prev) = &prev {
+        require!(prev.amount >= bid.amount, AuctionError::RankOrderViolated);
+    }
+    if let Some(next) = &next {
+        require!(
+            bid.amount >= next.amount && !state.is_leader(next.bidder, next.bid_index),
+            AuctionError::RankOrderViolated
+        );
+    }
+    require!(
+        prev.is_none() || !state.is_leader(bid.bidder, bid.bid_index),
+        AuctionError::LeaderNotRankedFirst
+    );
+
+    match prev {
+        Some(prev) => prev.next_bid = key,
+        None => state.ranked_head = key,
+    }
+    if let Some(next) = next {
+        next.prev_bid = key;
+    }
+    bid.prev_bid = prev_key;
+    bid.next_bid = next_key;
+    bid.ranked_round = state.current_round();
+    state.ranked_count = state.ranked_count.saturating_add(1);
+    Ok(())
+}
+
+/// Removes the ranked `bid`, given the neighbours it points at.
+pub fn unlink(
+    state: &mut AuctionState,
+    bid: &mut Bid,
+    prev: Option<&mut Account<Bid>>,
+    next: Option<&mut Account<Bid>>,
+) -> Result<()> {
+    require!(state.is_ranked(bid), AuctionError::BidNotRanked);
+    require!(state.top_k_paid == 0, AuctionError::TopKSettlementPending);
+    require_keys_eq!(
+        prev.as_ref().map(|p| p.key()).unwrap_or_default(),
+        bid.prev_bid,
+        AuctionError::RankHintMismatch
+    );
+    require_keys_eq!(
+        next.as_ref().map(|n| n.key()).unwrap_or_default(),
+        bid.next_bid,
+        AuctionError::RankHintMismatch
+    );
+
+    match prev {
+        Some(prev) => prev.next_bid = bid.next_bid,
+        None => state.ranked_head = bid.next_bid,
+    }
+    if let Some(next) = next {
+        next.prev_bid = bid.prev_bid;
+    }
+    bid.prev_bid = Pubkey::default();
+    bid.next_bid = Pubkey::default();
+    bid.ranked_round = 0;
+    state.ranked_count = state.ranked_count.saturating_sub(1);
+    Ok(())
+}
+
+/// Removes `bid`, at `key`, from the head of the list for `settle_top_k`.
+/// The new head keeps pointing back at it; nothing reads that pointer while
+/// the settlement's pages freeze the list, and settling drops the list.
+pub fn pop_head(state: &mut AuctionState, key: Pubkey, bid: &mut Bid) -> Result<()> {
+    require!(
+        state.is_ranked(bid) && state.ranked_head == key,
+        AuctionError::WinnersMismatch
+    );
+    state.ranked_head = bid.next_bid;
+    bid.prev_bid = Pubkey::default();
+    bid.next_bid = Pubkey::default();
+    bid.ranked_round = 0;
+    state.ranked_count = state.ranked_count.saturating_sub(1);
+    Ok(())
+}
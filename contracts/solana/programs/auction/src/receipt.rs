@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::error::AuctionError;
+use crate::state::AuctionState;
+
+/// Optional accounts for the receipt token standing for a bid. A bid placed
+/// with them gets one receipt, and can only be withdrawn by burning one.
+pub struct Receipt<'a, 'info> {
+    pub receipt_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub holder: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'info> Receipt<'_, 'info> {
+    /// Whether the caller passed receipt accounts at all.
+    pub fn requested(&self) -> bool {
+        self.receipt_mint.is_some() || self.holder.is_some()
+    }
+
+    /// Mints one receipt to `owner`'s token account, signed by the auction state.
+    pub fn issue(&self, owner: &Pubkey, auction_state: &Account<'info, AuctionState>) -> Result<()> {
+        let (mint, holder) = self.accounts(owner)?;
+        let id_seed = auction_state.id_seed();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"auction_state", &id_seed, &[auction_state.bump]]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: mint.to_account_info(),
+                    to: holder.to_account_info(),
+                    authority: auction_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )
+    }
+
+    /// Burns one receipt from `owner`'s token account.
+    pub fn redeem(&self, owner: &Signer<'info>) -> Result<()> {
+        let (mint, holder) = self.accounts(&owner.key())?;
+        token_interface::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: holder.to_account_info(),
+                    authority: owner.to_account_info(),
+                },
+            ),
+            1,
+        )
+    }
+
+    fn accounts(
+        &self,
+        owner: &Pubkey,
+    ) -> Result<(&InterfaceAccount<'info, Mint>, &InterfaceAccount<'info, TokenAccount>)> {
+        let (Some(mint), Some(holder)) = (self.receipt_mint, self.holder) else {
+            return err!(AuctionError::ReceiptAccountsRequired);
+        };
+        require!(
+            holder.mint == mint.key() && holder.owner == *owner,
+            AuctionError::InvalidTokenAccount
+        );
+        Ok((mint, holder))
+    }
+}
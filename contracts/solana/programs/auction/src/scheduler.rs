@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke_signed, set_return_data};
+
+/// The Clockwork thread program. Its workers run a thread's instruction once
+/// the thread's trigger fires.
+pub const THREAD_PROGRAM_ID: Pubkey = pubkey!("CLoCKyJ6DXBJqqu2VWx9RLbgnwwR6BMHHuyasVmfMzBh");
+
+/// Stands in for the worker in a thread instruction's accounts. The thread
+/// program substitutes the worker's signer, which then pays for any account
+/// the instruction creates.
+pub const PAYER_PUBKEY: Pubkey = pubkey!("C1ockworkPayer11111111111111111111111111111");
+
+/// Anchor discriminator of the thread program's `thread_create`.
+const THREAD_CREATE: [u8; 8] = [54, 1, 238, 224, 71, 244, 252, 173];
+
+/// Variant of the thread program's `Trigger` enum that fires at a unix time.
+const TRIGGER_TIMESTAMP: u8 = 5;
+
+/// An instruction as the thread program stores and returns it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SerializableInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<SerializableAccount>,
+    pub data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SerializableAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<Instruction> for SerializableInstruction {
+    fn from(instruction: Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|meta| SerializableAccount {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+impl From<SerializableInstruction> for Instruction {
+    fn from(instruction: SerializableInstruction) -> Self {
+        Self {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+/// A thread's `Timestamp` trigger, the only one this program schedules.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trigger {
+    pub unix_ts: i64,
+}
+
+impl AnchorSerialize for Trigger {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        TRIGGER_TIMESTAMP.serialize(writer)?;
+        self.unix_ts.serialize(writer)
+    }
+}
+
+impl AnchorDeserialize for Trigger {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        if u8::deserialize_reader(reader)? != TRIGGER_TIMESTAMP {
+            return Err(std::io::ErrorKind::InvalidData.into());
+        }
+        Ok(Self {
+            unix_ts: i64::deserialize_reader(reader)?,
+        })
+    }
+}
+
+/// What a thread instruction hands back to the thread program through
+/// return data. This program only ever names the next instruction to run.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ThreadResponse {
+    pub close_to: Option<Pubkey>,
+    pub dynamic_instruction: Option<SerializableInstruction>,
+    pub trigger: Option<Trigger>,
+}
+
+impl ThreadResponse {
+    /// Has the thread run `instruction` next.
+    pub fn run_next(instruction: Instruction) -> Self {
+        Self {
+            close_to: None,
+            dynamic_instruction: Some(instruction.into()),
+            trigger: None,
+        }
+    }
+
+    pub fn publish(&self) -> Result<()> {
+        set_return_data(&self.try_to_vec()?);
+        Ok(())
+    }
+}
+
+/// Thread `id` of `authority`, as the thread program derives it.
+pub fn thread_address(authority: &Pubkey, id: &[u8]) -> Pubkey {
+    Pubkey::find_program_address(&[b"thread", authority.as_ref(), id], &THREAD_PROGRAM_ID).0
+}
+
+/// Accounts for creating a thread; `authority` signs with the seeds given
+/// to [`CreateThread::create`].
+pub struct CreateThread<'info> {
+    pub thread_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub thread: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl CreateThread<'_> {
+    /// Creates thread `id`, funded with `amount` lamports for its workers'
+    /// fees, that runs `instruction` once `trigger` fires.
+    pub fn create(
+        self,
+        signer_seeds: &[&[&[u8]]],
+        id: Vec<u8>,
+        amount: u64,
+        instruction: Instruction,
+        trigger: Trigger,
+    ) -> Result<()> {
+        let mut data = THREAD_CREATE.to_vec();
+        (
+            amount,
+            id,
+            vec![SerializableInstruction::from(instruction)],
+            trigger,
+        )
+            .serialize(&mut data)?;
+        let create = Instruction {
+            program_id: THREAD_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.authority.key(), true),
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+                AccountMeta::new(self.thread.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &create,
+            &[
+                self.authority,
+                self.payer,
+                self.system_program,
+                self.thread,
+                self.thread_program,
+            ],
+            signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
+
+/// Id of the thread that settles round `round`, so each round gets its own.
+pub fn settle_thread_id(round: u64) -> Vec<u8> {
+    [b"settle".as_slice(), &round.to_le_bytes()].concat()
+}
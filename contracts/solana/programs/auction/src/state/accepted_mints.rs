@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_ACCEPTED_MINTS: usize = 4;
+
+/// A stablecoin the auction takes besides its own mint, valued 1:1 with it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,
+    /// Escrow PDA at `[b"escrow", auction_seed, mint]`.
+    pub escrow: Pubkey,
+    /// Token account of `mint` that receives winning bids placed in it.
+    pub treasury: Pubkey,
+    pub escrow_bump: u8,
+    /// Tokens of `mint` owed to bidders, tracked apart from the auction mint.
+    pub total_escrowed: u64,
+}
+
+/// Agent-managed list of extra mints bids may be placed in.
+#[account]
+#[derive(InitSpace)]
+pub struct AcceptedMints {
+    pub bump: u8,
+    pub entries: [AcceptedMint; MAX_ACCEPTED_MINTS],
+    pub len: u8,
+}
+
+impl AcceptedMints {
+    pub fn entries(&self) -> &[AcceptedMint] {
+        &self.entries[..self.len as usize]
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<&AcceptedMint> {
+        self.entries().iter().find(|e| e.mint == *mint)
+    }
+
+    pub fn get_mut(&mut self, mint: &Pubkey) -> Option<&mut AcceptedMint> {
+        let len = self.len as usize;
+        self.entries[..len].iter_mut().find(|e| e.mint == *mint)
+    }
+
+    pub fn remove(&mut self, mint: &Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self.entries().iter().position(|e| e.mint == *mint) {
+            self.entries.copy_within(index + 1..len, index);
+            self.entries[len - 1] = AcceptedMint::default();
+            self.len -= 1;
+        }
+    }
+}
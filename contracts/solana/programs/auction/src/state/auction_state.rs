@@ -1,5 +1,14 @@
 use anchor_lang::prelude::*;
 
+use super::{AcceptedMints, AuctionStatus, Bid, Leaderboard};
+use crate::error::AuctionError;
+use crate::event::{AllPayChanged, MinimumBidRecalculated, StatusChanged};
+use crate::{BPS_DENOMINATOR, USDC_DECIMALS};
+
+/// Settled rounds remembered for the dynamic minimum bid.
+pub const MAX_TRAILING_ROUNDS: usize = 8;
+pub const MAX_MEMO_LEN: usize = 64;
+
 #[account]
 #[derive(InitSpace)]
 pub struct AuctionState {
@@ -10,4 +19,811 @@ pub struct AuctionState {
     pub minimum_bid: u64,
     pub active_bid_count: u64,
     pub bump: u8,
+    /// Bidding deadline (unix seconds). Zero means the auction is open-ended.
+    pub end_time: i64,
+    pub withdrawal_penalty_bps: u16,
+    /// Penalties retained in escrow that have not yet been swept to the treasury.
+    pub accrued_penalties: u64,
+    /// Minimum step a proxy bid escalates by when outbid.
+    pub bid_increment: u64,
+    /// Highest bidder seen by leader tracking; default when there is no leader.
+    pub leader: Pubkey,
+    pub leading_amount: u64,
+    /// Price at which a bidder may settle instantly via `buy_now`. Zero disables it.
+    pub buy_now_price: u64,
+    /// Where the round is in its lifecycle; only changed through `transition`.
+    pub status: AuctionStatus,
+    /// Number of top bids paid out by `settle_multi`.
+    pub num_winners: u8,
+    pub leaderboard: Leaderboard,
+    /// When set, every `settle_multi` winner pays the lowest winning amount.
+    pub uniform_price: bool,
+    /// Token account receiving `charity_bps` of every settlement; default when unset.
+    pub charity: Pubkey,
+    pub charity_bps: u16,
+    /// Tokens the escrow owes: active bid escrow plus unswept penalties.
+    pub total_escrowed: u64,
+    /// Switchboard randomness account awaiting reveal; default when none is pending.
+    pub pending_randomness: Pubkey,
+    pub randomness_seed_slot: u64,
+    /// Draw the winner with probability proportional to escrow instead of by amount.
+    pub raffle_mode: bool,
+    /// Set once `draw_raffle` has picked a winner; cleared by `set_end_time`.
+    pub raffle_drawn: bool,
+    /// Wormhole emitter (chain id and universal address) trusted to send cross-chain bids.
+    pub foreign_emitter_chain: u16,
+    pub foreign_emitter: [u8; 32],
+    /// Redeems bridged USDC into escrow and receives refunds to bridge back.
+    pub relayer: Pubkey,
+    /// Escrow PDA address, so hot paths can check it without re-deriving it.
+    pub escrow: Pubkey,
+    /// Root of the posted refunds Merkle tree; zero when refunds go through
+    /// `crank_refund`. Cleared by `set_end_time`.
+    pub refund_root: [u8; 32],
+    /// SHA-256 of the deliverable's URI, committed by the agent while bidding
+    /// is open; zero when nothing is committed. Cleared by `set_end_time`
+    /// once a closed round is reopened.
+    pub content_hash: [u8; 32],
+    /// Mint of the prize the agent escrowed with `deposit_prize`; default when
+    /// there is none.
+    pub prize_mint: Pubkey,
+    /// Prize units held in the prize vault, swapped to the winner on settlement.
+    pub prize_amount: u64,
+    /// Seconds over which settled funds stream to the treasury; zero pays
+    /// them out as a lump sum.
+    pub vesting_duration: i64,
+    /// Current stream: `vesting_total` vests linearly from `vesting_start`,
+    /// and `claim_settled_funds` has released `vesting_claimed` of it.
+    pub vesting_start: i64,
+    pub vesting_total: u64,
+    pub vesting_claimed: u64,
+    /// Share of each bid escrowed up front; zero escrows bids in full. The
+    /// winner owes the rest within `payment_window` seconds of the deadline.
+    pub deposit_bps: u16,
+    pub payment_window: i64,
+    /// Deadline for the current top bidder to complete payment, once a
+    /// forfeit has passed the slot on; zero means `end_time + payment_window`.
+    pub payment_deadline: i64,
+    /// Forward `remaining_accounts` to transfer hooks on every token transfer.
+    pub transfer_hooks: bool,
+    /// Key allowed to settle and post refunds alongside the agent, without
+    /// its other powers; default when none is delegated.
+    pub settler: Pubkey,
+    /// Seconds after `end_time` an unsettled auction may be marked abandoned;
+    /// zero disables the fallback.
+    pub abandonment_window: i64,
+    /// Guest curator paid `curator_bps` of each settlement to their associated
+    /// token account; default when the round has none.
+    pub curator: Pubkey,
+    pub curator_bps: u16,
+    /// Share of a winning price paid to the winning bid's referrer, out of
+    /// what would otherwise go to the treasury.
+    pub referral_bps: u16,
+    /// Key allowed to slash spam bids with `slash_bid`; default when bids
+    /// cannot be slashed.
+    pub moderator: Pubkey,
+    /// Lamports a new bid locks in its account on top of rent, returned when
+    /// the bid account closes unless the moderator slashes it first.
+    pub bid_bond: u64,
+    /// Seconds a bid may go without being placed or updated before it
+    /// expires; zero keeps bids live indefinitely.
+    pub bid_ttl: i64,
+    /// Ascending mode: only the leading bid stays escrowed, and a bid that
+    /// takes the lead refunds the one it displaces.
+    pub english_auction: bool,
+    /// Candle mode: the round really closed at a random moment within this
+    /// many seconds before `end_time`, drawn by `determine_candle_end`. Zero
+    /// turns the mode off.
+    pub candle_window: i64,
+    /// Effective end drawn for a candle round; zero until it is determined.
+    /// Cleared by `set_end_time`.
+    pub candle_end: i64,
+    /// All-pay mode. WARNING: bids are never refunded while the round runs;
+    /// after settlement every losing bid goes to the treasury as well, and
+    /// only the top bidder receives the prize. Meant for charity rounds.
+    pub all_pay: bool,
+    /// Id given by `create_auction`; zero for the auction set up by
+    /// `initialize`. Every per-auction PDA is seeded with [`auction_seed`].
+    pub auction_id: u64,
+    /// When the current round began: at creation, then whenever
+    /// `set_end_time` reopens a settled round.
+    pub round_started_at: i64,
+    /// Bids placed or raised this round.
+    pub round_bid_count: u64,
+    /// Bids entered this round: a wallet holding several bids, or one who
+    /// withdraws and bids again, counts once per entry.
+    pub round_bidder_count: u64,
+    /// Total price paid by this round's winners.
+    pub winning_amount: u64,
+    /// Prices paid by every winner across all rounds, counting accepted
+    /// mints 1:1 with the auction mint. Like the other lifetime totals below,
+    /// never reset.
+    pub total_volume: u128,
+    /// Bids placed or raised across all rounds.
+    pub total_bids_placed: u64,
+    /// Rounds that reached `Settled`.
+    pub total_rounds_settled: u64,
+    /// Highest amount any bid has stood at.
+    pub highest_ever_bid: u64,
+    /// `Bid::bid_index` of the leader's bid.
+    pub leader_index: u16,
+    /// Lots created under the auction; the next lot's id is one more.
+    pub lot_count: u16,
+    /// Lot and bundle bids holding escrow across all lots, which keep the
+    /// auction from closing just as active bids do.
+    pub active_lot_bid_count: u64,
+    /// Unix time bidding opens; bids before it fail with `AuctionNotStarted`.
+    /// Zero when the round opened at creation.
+    pub start_time: i64,
+    /// When non-zero, each new round's `minimum_bid` is this share of the
+    /// average winning amount over the last `dynamic_minimum_rounds` rounds.
+    pub dynamic_minimum_bps: u16,
+    pub dynamic_minimum_rounds: u8,
+    /// Winning amounts of the most recent settled rounds, newest first.
+    pub recent_winning_amounts: [u64; MAX_TRAILING_ROUNDS],
+    /// How many entries of `recent_winning_amounts` are filled.
+    pub recent_round_count: u8,
+    /// Set by `initialize_audit_log`; admin changes must then be logged.
+    pub audit_log_enabled: bool,
+    /// Times `migrate_escrow` has replaced the escrow; seeds the next one.
+    pub escrow_generation: u8,
+    /// Highest bid in this round's ranked list, kept by `link_bid`; default
+    /// while empty. The list is dropped when the round settles or is called
+    /// off.
+    pub ranked_head: Pubkey,
+    /// Bids in this round's ranked list.
+    pub ranked_count: u64,
+    /// Winners `settle_top_k` has paid so far this round. Nonzero while its
+    /// pages are under way, which freezes the ranked list and every other
+    /// way to settle.
+    pub top_k_paid: u8,
+    /// What those winners paid together.
+    pub top_k_total: u64,
+    /// The first of them, recorded as the round's winner.
+    pub top_k_first: Pubkey,
+    /// Set once `set_denylisted` creates the auction's `Denylist`; bid
+    /// paths must then pass it.
+    pub denylist_enabled: bool,
+    /// Seconds a settled price stays in escrow before it can be released;
+    /// zero pays settlements out immediately.
+    pub dispute_window: i64,
+    /// Rules on held settlements: releases them early or refunds the winner.
+    pub arbiter: Pubkey,
+    /// The settlement currently held for the dispute window, if
+    /// `held_amount` is non-zero: what the winner paid, who they were, their
+    /// bid's referrer, and when anyone may release it.
+    pub held_amount: u64,
+    pub held_winner: Pubkey,
+    pub held_referrer: Pubkey,
+    pub held_until: i64,
+    /// Set by the winner through `open_dispute`; only the arbiter can then
+    /// settle the held funds.
+    pub disputed: bool,
+    /// Seconds after the deadline the top bidder has to `claim_win`; zero
+    /// lets the round settle without a claim.
+    pub claim_window: i64,
+    /// Share of a defaulting winner's escrow that `forfeit_deposit` and
+    /// `promote_next_bidder` slash to the treasury.
+    pub default_penalty_bps: u16,
+    /// Claim deadline once `promote_next_bidder` has passed the slot on;
+    /// zero means `end_time + claim_window`.
+    pub claim_deadline: i64,
+    /// The bid that claimed this round's win, or default. Cleared by
+    /// `set_end_time`.
+    pub claimed_bid: Pubkey,
+    /// Slots that must pass after the winning bid's last change before it
+    /// can be settled, so a bid cannot move in the same slot it is settled.
+    pub min_settle_slots: u64,
+    /// UTF-8 memo logged with settlement and refund transfers; the first
+    /// `memo_len` bytes are used, and none means no memo.
+    pub memo: [u8; MAX_MEMO_LEN],
+    pub memo_len: u8,
+    /// Loyalty points a bid earns per whole token it keeps in escrow for an
+    /// hour, minted from the points mint; zero earns none.
+    pub points_rate: u64,
+    /// Share of the treasury's cut of each price set aside for the bids that
+    /// lost the round, paid with their refund by `crank_refund`.
+    pub rebate_bps: u16,
+    /// Rebates set aside and not yet paid, held in escrow. Whatever one
+    /// round's losers leave behind joins the next round's pool.
+    pub rebate_pool: u64,
+    /// The pool at the last settlement and the losing escrow it is shared
+    /// over; a refund of `x` earns `rebate_total * x / rebate_base`.
+    pub rebate_total: u64,
+    pub rebate_base: u64,
+    /// `Bid::fee_discount_bps` of the held winner, applied on release.
+    pub held_discount_bps: u16,
+    /// Seconds after `end_time` for revealing sealed bids, which the round
+    /// takes instead of open ones while this is set; see `place_sealed_bid`.
+    pub reveal_window: i64,
+    /// Tokens each sealed bid escrows on top of its deposit. `reveal_bid`
+    /// returns them; a bid that leaves unrevealed forfeits them.
+    pub commit_bond: u64,
+    /// Commit bonds in escrow, both those of sealed bids still waiting and
+    /// those forfeited; `sweep_commit_bonds` sends them to the treasury once
+    /// the reveal window has closed.
+    pub commit_bonds_held: u64,
+    /// Bids tied at the top when `request_tie_break` committed its draw,
+    /// which `resolve_tie` draws among; zero when no draw is pending.
+    pub tie_count: u8,
+    /// Set by `set_paused`: no bids are placed or raised until it clears.
+    pub paused: bool,
+}
+
+/// Seed separating one auction's PDAs from another's: the id's little-endian
+/// bytes, or nothing for auction zero so the original singleton keeps the
+/// addresses it had before the factory existed. An empty seed adds nothing to
+/// the derivation hash.
+pub fn auction_seed(auction_id: u64) -> Vec<u8> {
+    if auction_id == 0 {
+        Vec::new()
+    } else {
+        auction_id.to_le_bytes().to_vec()
+    }
+}
+
+impl AuctionState {
+    pub fn id_seed(&self) -> Vec<u8> {
+        auction_seed(self.auction_id)
+    }
+
+    /// Round-number seed of the `RoundResult` the next settlement writes.
+    pub fn next_round_seed(&self) -> [u8; 8] {
+        self.total_rounds_settled.saturating_add(1).to_le_bytes()
+    }
+
+    /// Number of the round in progress, counting from one.
+    pub fn current_round(&self) -> u64 {
+        self.total_rounds_settled.saturating_add(1)
+    }
+
+    /// Whether `bid` is in this round's ranked list. Bids linked in earlier
+    /// rounds are not, so settling leaves nothing to unlink.
+    pub fn is_ranked(&self, bid: &Bid) -> bool {
+        bid.ranked_round == self.current_round() && !self.status.is_called_off()
+    }
+
+    /// Last seed of the `Lot` PDA `create_lot` creates next.
+    pub fn next_lot_seed(&self) -> [u8; 2] {
+        self.lot_count.saturating_add(1).to_le_bytes()
+    }
+
+    /// Last seed of the escrow `migrate_escrow` creates next.
+    pub fn next_escrow_seed(&self) -> [u8; 1] {
+        [self.escrow_generation.saturating_add(1)]
+    }
+
+    pub fn has_started(&self, now: i64) -> bool {
+        now >= self.start_time
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.end_time != 0 && now >= self.end_time
+    }
+
+    pub fn takes_sealed_bids(&self) -> bool {
+        self.reveal_window > 0
+    }
+
+    /// Whether sealed bids may still be revealed at `now`.
+    pub fn revealing(&self, now: i64) -> bool {
+        self.takes_sealed_bids()
+            && self.has_ended(now)
+            && now < self.end_time.saturating_add(self.reveal_window)
+    }
+
+    /// Share of `amount` retained when a bid is withdrawn at `now`.
+    pub fn withdrawal_penalty(&self, amount: u64, now: i64) -> u64 {
+        if self.status.is_called_off() || self.has_ended(now) {
+            return 0;
+        }
+        (u128::from(amount) * u128::from(self.withdrawal_penalty_bps) / u128::from(BPS_DENOMINATOR))
+            as u64
+    }
+
+    pub fn credit_escrow(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_add(amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn debit_escrow(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_sub(amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Escrow holding bids in `mint`.
+    pub fn escrow_for(&self, accepted: Option<&AcceptedMints>, mint: &Pubkey) -> Result<Pubkey> {
+        if *mint == self.usdc_mint {
+            return Ok(self.escrow);
+        }
+        accepted
+            .and_then(|a| a.get(mint))
+            .map(|e| e.escrow)
+            .ok_or_else(|| error!(AuctionError::MintNotAccepted))
+    }
+
+    /// Treasury receiving winning bids in `mint`.
+    pub fn treasury_for(&self, accepted: Option<&AcceptedMints>, mint: &Pubkey) -> Result<Pubkey> {
+        if *mint == self.usdc_mint {
+            return Ok(self.treasury);
+        }
+        accepted
+            .and_then(|a| a.get(mint))
+            .map(|e| e.treasury)
+            .ok_or_else(|| error!(AuctionError::MintNotAccepted))
+    }
+
+    /// Like `credit_escrow`, but against the ledger of `mint`.
+    pub fn credit_escrow_in(
+        &mut self,
+        accepted: Option<&mut AcceptedMints>,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        if *mint == self.usdc_mint {
+            return self.credit_escrow(amount);
+        }
+        let entry = accepted
+            .and_then(|a| a.get_mut(mint))
+            .ok_or(AuctionError::MintNotAccepted)?;
+        entry.total_escrowed = entry
+            .total_escrowed
+            .checked_add(amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Like `debit_escrow`, but against the ledger of `mint`.
+    pub fn debit_escrow_in(
+        &mut self,
+        accepted: Option<&mut AcceptedMints>,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        if *mint == self.usdc_mint {
+            return self.debit_escrow(amount);
+        }
+        let entry = accepted
+            .and_then(|a| a.get_mut(mint))
+            .ok_or(AuctionError::MintNotAccepted)?;
+        entry.total_escrowed = entry
+            .total_escrowed
+            .checked_sub(amount)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Tokens owed to bidders out of the escrow for `mint`.
+    pub fn escrowed_in(&self, accepted: Option<&AcceptedMints>, mint: &Pubkey) -> Result<u64> {
+        if *mint == self.usdc_mint {
+            return Ok(self.total_escrowed);
+        }
+        accepted
+            .and_then(|a| a.get(mint))
+            .map(|e| e.total_escrowed)
+            .ok_or_else(|| error!(AuctionError::MintNotAccepted))
+    }
+
+    pub fn charity_share(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.charity_bps) / u128::from(BPS_DENOMINATOR)) as u64
+    }
+
+    pub fn curator_share(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.curator_bps) / u128::from(BPS_DENOMINATOR)) as u64
+    }
+
+    pub fn referral_share(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.referral_bps) / u128::from(BPS_DENOMINATOR)) as u64
+    }
+
+    /// Whether the charity, curator and referral shares together leave the
+    /// treasury a non-negative remainder.
+    pub fn shares_fit(&self) -> bool {
+        self.shares_fit_with(self.curator_bps, self.referral_bps)
+    }
+
+    /// `shares_fit` with the given curator and referral shares in place of
+    /// the current ones.
+    pub fn shares_fit_with(&self, curator_bps: u16, referral_bps: u16) -> bool {
+        u64::from(self.charity_bps) + u64::from(curator_bps) + u64::from(referral_bps)
+            <= BPS_DENOMINATOR
+    }
+
+    /// Tokens a bid of `amount` must escrow up front, rounded up.
+    pub fn deposit_for(&self, amount: u64) -> u64 {
+        if self.deposit_bps == 0 {
+            return amount;
+        }
+        (u128::from(amount) * u128::from(self.deposit_bps)).div_ceil(u128::from(BPS_DENOMINATOR))
+            as u64
+    }
+
+    /// Last moment the top bidder may pay their balance due.
+    pub fn payment_deadline(&self) -> i64 {
+        match self.payment_deadline {
+            0 => self.end_time.saturating_add(self.payment_window),
+            deadline => deadline,
+        }
+    }
+
+    /// Last moment the top bidder may claim the win.
+    pub fn claim_deadline(&self) -> i64 {
+        match self.claim_deadline {
+            0 => self.end_time.saturating_add(self.claim_window),
+            deadline => deadline,
+        }
+    }
+
+    /// Whether a claim window is set and `winning_bid` has not claimed it.
+    pub fn claim_pending(&self, winning_bid: &Pubkey) -> bool {
+        self.claim_window > 0 && self.claimed_bid != *winning_bid
+    }
+
+    /// Settled funds vested by `now` that have not been claimed yet.
+    pub fn claimable(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.vesting_start);
+        let vested = if self.vesting_duration == 0 || elapsed >= self.vesting_duration {
+            self.vesting_total
+        } else {
+            (u128::from(self.vesting_total) * elapsed.max(0) as u128
+                / self.vesting_duration as u128) as u64
+        };
+        vested.saturating_sub(self.vesting_claimed)
+    }
+
+    /// Settled funds still held in escrow for the treasury.
+    pub fn unclaimed(&self) -> u64 {
+        self.vesting_total - self.vesting_claimed
+    }
+
+    /// Drops a bid from leader tracking once it stops being active. A
+    /// forgotten leader hands the lead to the next leaderboard entry.
+    pub fn forget_bid(&mut self, bidder: Pubkey, bid_index: u16) {
+        self.leaderboard.remove(bidder, bid_index);
+        if self.is_leader(bidder, bid_index) {
+            self.follow_leaderboard();
+        }
+    }
+
+    /// Makes the top of the leaderboard the tracked leader, or clears the
+    /// leader when the leaderboard is empty.
+    pub fn follow_leaderboard(&mut self) {
+        let top = self.leaderboard.entries().first().copied().unwrap_or_default();
+        self.set_leader(top.bidder, top.bid_index);
+        self.leading_amount = top.amount;
+    }
+
+    /// Whether `bidder`'s bid `bid_index` is the tracked leader.
+    pub fn is_leader(&self, bidder: Pubkey, bid_index: u16) -> bool {
+        self.leader == bidder && self.leader_index == bid_index
+    }
+
+    pub fn set_leader(&mut self, bidder: Pubkey, bid_index: u16) {
+        self.leader = bidder;
+        self.leader_index = bid_index;
+    }
+
+    /// Whether `key` may run settlement: the agent, or the settler if set.
+    pub fn is_settler(&self, key: &Pubkey) -> bool {
+        *key == self.agent || (self.settler != Pubkey::default() && *key == self.settler)
+    }
+
+    /// Whether the round went unsettled for `abandonment_window` past its
+    /// deadline, so that anyone may mark it abandoned.
+    pub fn is_abandonable(&self, now: i64) -> bool {
+        self.abandonment_window > 0
+            && !self.is_settled()
+            && !self.status.is_called_off()
+            && self.end_time != 0
+            && now >= self.end_time.saturating_add(self.abandonment_window)
+    }
+
+    /// The memo set with `set_memo`; empty when there is none.
+    pub fn memo(&self) -> &[u8] {
+        &self.memo[..usize::from(self.memo_len)]
+    }
+
+    /// Credits `bid` with points for what it held in escrow since it last
+    /// accrued, at the current `points_rate`, and restarts its clock at
+    /// `now`. Must run before anything changes the bid's escrow; an inactive
+    /// bid earns nothing, so a fresh one just starts its clock.
+    pub fn accrue_points(&self, bid: &mut Bid, now: i64) -> Result<()> {
+        if bid.active && self.points_rate > 0 {
+            let held = u64::try_from(now.saturating_sub(bid.points_accrued_at)).unwrap_or(0);
+            let earned = u128::from(bid.escrowed()) * u128::from(held)
+                * u128::from(self.points_rate)
+                / (10u128.pow(u32::from(USDC_DECIMALS)) * 3_600);
+            bid.points = u64::try_from(earned)
+                .ok()
+                .and_then(|earned| bid.points.checked_add(earned))
+                .ok_or(AuctionError::ArithmeticOverflow)?;
+        }
+        bid.points_accrued_at = now;
+        Ok(())
+    }
+
+    /// Accrues `bid`'s points and refuses to go on while any are left
+    /// unclaimed, for every path that closes the bid: `claim_points` has to
+    /// mint them first, in the same transaction.
+    pub fn require_points_claimed(&self, bid: &mut Bid, now: i64) -> Result<()> {
+        self.accrue_points(bid, now)?;
+        require!(bid.points == 0, AuctionError::PointsUnclaimed);
+        Ok(())
+    }
+
+    /// Moves `rebate_bps` of `fee` into the rebate pool and shares the pool
+    /// over the escrow other bids still hold. Returns the amount set aside:
+    /// nothing when no bid is left to share it.
+    pub fn set_aside_rebate(&mut self, fee: u64) -> Result<u64> {
+        let losing_escrow = self
+            .total_escrowed
+            .saturating_sub(self.accrued_penalties)
+            .saturating_sub(self.commit_bonds_held)
+            .saturating_sub(self.unclaimed())
+            .saturating_sub(self.held_amount)
+            .saturating_sub(self.rebate_pool);
+        if self.rebate_bps == 0 || losing_escrow == 0 {
+            return Ok(0);
+        }
+        let rebate = (u128::from(fee) * u128::from(self.rebate_bps)
+            / u128::from(BPS_DENOMINATOR)) as u64;
+        self.credit_escrow(rebate)?;
+        self.rebate_pool = self
+            .rebate_pool
+            .checked_add(rebate)
+            .ok_or(AuctionError::ArithmeticOverflow)?;
+        self.rebate_total = self.rebate_pool;
+        self.rebate_base = losing_escrow;
+        Ok(rebate)
+    }
+
+    /// Rebate owed to a losing bid refunded `escrowed`.
+    pub fn rebate_for(&self, escrowed: u64) -> u64 {
+        if self.rebate_base == 0 {
+            return 0;
+        }
+        let share = u128::from(self.rebate_total) * u128::from(escrowed)
+            / u128::from(self.rebate_base);
+        u64::try_from(share).map_or(self.rebate_pool, |share| share.min(self.rebate_pool))
+    }
+
+    /// Whether `bid` changed too recently to settle at `slot`.
+    pub fn settles_too_soon(&self, bid: &Bid, slot: u64) -> bool {
+        slot < bid.updated_slot.saturating_add(self.min_settle_slots)
+    }
+
+    /// Whether `bid` went `bid_ttl` seconds without an update. Once bidding
+    /// has closed, staleness is measured at the deadline, so a bid that was
+    /// live when the round ended cannot expire while awaiting settlement.
+    pub fn is_expired(&self, bid: &Bid, now: i64) -> bool {
+        if self.bid_ttl == 0 {
+            return false;
+        }
+        let at = if self.has_ended(now) { self.end_time } else { now };
+        at >= bid.updated_at.saturating_add(self.bid_ttl)
+    }
+
+    /// Moves the deadline to `end_time` and reopens the round, as described
+    /// on `set_end_time`.
+    pub fn reschedule(&mut self, end_time: i64, now: i64) -> Result<()> {
+        self.require_not_called_off()?;
+        if self.is_settled() || self.has_ended(now) {
+            self.content_hash = [0; 32];
+        }
+        if self.is_settled() {
+            self.start_round(now);
+        }
+        self.end_time = end_time;
+        self.raffle_drawn = false;
+        self.candle_end = 0;
+        self.refund_root = [0; 32];
+        self.payment_deadline = 0;
+        self.claim_deadline = 0;
+        self.claimed_bid = Pubkey::default();
+        let reopened = if self.has_ended(now) {
+            AuctionStatus::Closing
+        } else if self.active_bid_count > 0 {
+            AuctionStatus::Open
+        } else {
+            AuctionStatus::Created
+        };
+        self.set_status(reopened);
+        Ok(())
+    }
+
+    /// Resets the per-round statistics reported by `close_auction`, first
+    /// folding a settled round's winning amount into the dynamic minimum. Bids
+    /// still ranked from the last round, such as standing bids, compete
+    /// again, led by the highest of them.
+    pub fn start_round(&mut self, now: i64) {
+        if self.is_settled() {
+            self.record_winning_amount();
+            self.apply_dynamic_minimum();
+        }
+        self.round_started_at = now;
+        self.round_bid_count = 0;
+        self.round_bidder_count = 0;
+        self.winning_amount = 0;
+        if !self.leaderboard.entries().is_empty() {
+            self.follow_leaderboard();
+        }
+    }
+
+    fn record_winning_amount(&mut self) {
+        self.recent_winning_amounts.copy_within(..MAX_TRAILING_ROUNDS - 1, 1);
+        self.recent_winning_amounts[0] = self.winning_amount;
+        self.recent_round_count = (self.recent_round_count + 1).min(MAX_TRAILING_ROUNDS as u8);
+    }
+
+    /// Average winning amount over the trailing `dynamic_minimum_rounds`
+    /// settled rounds, or fewer while the history is still short.
+    pub fn trailing_average(&self) -> Option<u64> {
+        let count = self.dynamic_minimum_rounds.min(self.recent_round_count) as usize;
+        if count == 0 {
+            return None;
+        }
+        let total: u128 = self.recent_winning_amounts[..count]
+            .iter()
+            .map(|&amount| u128::from(amount))
+            .sum();
+        Some((total / count as u128) as u64)
+    }
+
+    fn apply_dynamic_minimum(&mut self) {
+        if self.dynamic_minimum_bps == 0 {
+            return;
+        }
+        let Some(average) = self.trailing_average() else {
+            return;
+        };
+        self.minimum_bid = (u128::from(average) * u128::from(self.dynamic_minimum_bps)
+            / u128::from(BPS_DENOMINATOR)) as u64;
+        emit!(MinimumBidRecalculated {
+            auction_id: self.auction_id,
+            trailing_average: average,
+            minimum_bid: self.minimum_bid,
+        });
+    }
+
+    /// Counts a bid placed or raised; `new_bidder` when it entered the round.
+    pub fn record_bid(&mut self, new_bidder: bool) {
+        self.round_bid_count = self.round_bid_count.saturating_add(1);
+        self.total_bids_placed = self.total_bids_placed.saturating_add(1);
+        if new_bidder {
+            self.round_bidder_count = self.round_bidder_count.saturating_add(1);
+        }
+    }
+
+    /// Whether a candle round still waits for `determine_candle_end`.
+    pub fn candle_pending(&self) -> bool {
+        self.candle_window > 0 && self.candle_end == 0
+    }
+
+    /// Whether `bid` was last placed or updated before the candle went out.
+    /// Later bids cannot win a candle round; outside candle mode all count.
+    pub fn counts_at_candle(&self, bid: &Bid) -> bool {
+        self.candle_window == 0 || bid.updated_at <= self.candle_end
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.status == AuctionStatus::Settled
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        self.status == AuctionStatus::Abandoned
+    }
+
+    /// Status as of `now`, counting a deadline that has passed since the
+    /// stored status was last written.
+    pub fn status_at(&self, now: i64) -> AuctionStatus {
+        match self.status {
+            AuctionStatus::Created | AuctionStatus::Open if self.has_ended(now) => {
+                AuctionStatus::Closing
+            }
+            status => status,
+        }
+    }
+
+    /// Moves the round to `to`, recording `Closing` on the way if the
+    /// deadline has passed. Fails if the lifecycle does not allow the step.
+    pub fn transition(&mut self, to: AuctionStatus, now: i64) -> Result<()> {
+        let current = self.status_at(now);
+        if current != self.status {
+            self.set_status(current);
+        }
+        require!(current.can_become(to), AuctionError::InvalidStatusTransition);
+        // Settling mid-reveal would rank bids not yet opened at nothing.
+        require!(
+            to != AuctionStatus::Settled
+                || !self.takes_sealed_bids()
+                || (self.has_ended(now) && !self.revealing(now)),
+            AuctionError::RevealWindowOpen
+        );
+        self.set_status(to);
+        Ok(())
+    }
+
+    /// Fails with the matching error unless the round can still take bids.
+    pub fn require_accepting_bids(&self) -> Result<()> {
+        self.require_not_called_off()?;
+        require!(!self.is_settled(), AuctionError::AuctionSettled);
+        require!(!self.paused, AuctionError::AuctionPaused);
+        Ok(())
+    }
+
+    /// Switches all-pay mode, announcing it with `AllPayChanged`. Locked while
+    /// bids are escrowed, and exclusive with English mode's refunds.
+    pub fn set_all_pay(&mut self, enabled: bool) -> Result<()> {
+        require!(
+            self.active_bid_count == 0 && !(enabled && self.english_auction),
+            AuctionError::AllPayLocked
+        );
+        if self.all_pay != enabled {
+            self.all_pay = enabled;
+            emit!(AllPayChanged { enabled });
+        }
+        Ok(())
+    }
+
+    /// Fails in all-pay mode, where escrow only ever leaves for the treasury
+    /// unless the round is called off.
+    pub fn require_refundable(&self) -> Result<()> {
+        require!(
+            !self.all_pay || self.status.is_called_off(),
+            AuctionError::BidsNonRefundable
+        );
+        Ok(())
+    }
+
+    /// Fails with the matching error once the round is closed, abandoned or
+    /// cancelled.
+    pub fn require_not_called_off(&self) -> Result<()> {
+        require!(self.status != AuctionStatus::Closed, AuctionError::AuctionClosed);
+        require!(!self.is_abandoned(), AuctionError::AuctionAbandoned);
+        require!(
+            self.status != AuctionStatus::Cancelled,
+            AuctionError::AuctionCancelled
+        );
+        Ok(())
+    }
+
+    fn set_status(&mut self, to: AuctionStatus) {
+        if self.status == to {
+            return;
+        }
+        emit!(StatusChanged {
+            from: self.status,
+            to,
+        });
+        if to == AuctionStatus::Settled {
+            self.total_rounds_settled = self.total_rounds_settled.saturating_add(1);
+        }
+        if to == AuctionStatus::Settled || to.is_called_off() {
+            self.ranked_head = Pubkey::default();
+            self.ranked_count = 0;
+            self.top_k_paid = 0;
+            self.top_k_total = 0;
+            self.top_k_first = Pubkey::default();
+        }
+        self.status = to;
+    }
+
+    /// Accounts to forward to a mint's transfer hook: the instruction's
+    /// remaining accounts while hook support is on, otherwise none.
+    pub fn hook_accounts<'a, 'info>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> &'a [AccountInfo<'info>] {
+        if self.transfer_hooks {
+            remaining_accounts
+        } else {
+            &[]
+        }
+    }
 }
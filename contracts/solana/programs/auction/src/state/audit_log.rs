@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use super::AuctionState;
+use crate::error::AuctionError;
+
+/// Entries kept before the oldest is overwritten.
+pub const AUDIT_LOG_LEN: usize = 64;
+
+/// `AuditEntry::action` values.
+pub mod audit_action {
+    pub const AGENT_CHANGED: u8 = 1;
+    pub const MINIMUM_BID_CHANGED: u8 = 2;
+    pub const TREASURY_CHANGED: u8 = 3;
+}
+
+/// One admin change. Addresses are stored as their bytes and amounts as
+/// little-endian `u64`s zero-padded to 32 bytes; see [`AuditEntry::amount`].
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub slot: u64,
+    pub actor: Pubkey,
+    pub old_value: [u8; 32],
+    pub new_value: [u8; 32],
+    /// One of [`audit_action`].
+    pub action: u8,
+    pub _padding: [u8; 7],
+}
+
+impl AuditEntry {
+    pub fn amount(value: u64) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..8].copy_from_slice(&value.to_le_bytes());
+        bytes
+    }
+}
+
+/// Ring of the auction's most recent admin changes, created by
+/// `initialize_audit_log`. Zero-copy so appending does not deserialize the
+/// whole history.
+#[account(zero_copy)]
+pub struct AuditLog {
+    pub auction_id: u64,
+    /// Entries ever appended. Entry `n` lives at `entries[n % AUDIT_LOG_LEN]`,
+    /// so once this passes `AUDIT_LOG_LEN` the oldest have been overwritten.
+    pub total: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub entries: [AuditEntry; AUDIT_LOG_LEN],
+}
+
+impl AuditLog {
+    pub fn append(&mut self, entry: AuditEntry) {
+        self.entries[(self.total % AUDIT_LOG_LEN as u64) as usize] = entry;
+        self.total = self.total.saturating_add(1);
+    }
+
+    /// Entries oldest first.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        let len = (self.total as usize).min(AUDIT_LOG_LEN);
+        let start = self.total.saturating_sub(len as u64);
+        (start..self.total)
+            .map(|n| self.entries[(n % AUDIT_LOG_LEN as u64) as usize])
+            .collect()
+    }
+}
+
+/// Appends an admin change to `audit_log`. Once the auction has a log every
+/// audited instruction must pass it, so no change can be left out.
+pub fn record_admin_action(
+    state: &AuctionState,
+    audit_log: Option<&AccountLoader<AuditLog>>,
+    actor: Pubkey,
+    action: u8,
+    old_value: [u8; 32],
+    new_value: [u8; 32],
+) -> Result<()> {
+    let Some(audit_log) = audit_log else {
+        require!(!state.audit_log_enabled, AuctionError::AuditLogRequired);
+        return Ok(());
+    };
+    audit_log.load_mut()?.append(AuditEntry {
+        slot: Clock::get()?.slot,
+        actor,
+        old_value,
+        new_value,
+        action,
+        _padding: [0; 7],
+    });
+    Ok(())
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use super::LeaderboardEntry;
 
 #[account]
 #[derive(InitSpace)]
@@ -9,4 +12,112 @@ pub struct Bid {
     pub updated_at: i64,
     pub active: bool,
     pub bump: u8,
+    /// Escrowed ceiling. Equals `amount` for plain bids; proxy bids escalate `amount` up to it.
+    pub max_amount: u64,
+    pub proxy: bool,
+    /// Wormhole chain id of a cross-chain bidder; zero for native Solana bids.
+    pub foreign_chain: u16,
+    /// Part of `max_amount` not escrowed yet, owed through `complete_payment`
+    /// when the auction takes deposits. Zero for fully escrowed bids.
+    pub balance_due: u64,
+    /// Mint the bid is escrowed in: the auction mint or one of its accepted mints.
+    pub mint: Pubkey,
+    /// Wallet credited with bringing this bidder in, paid `referral_bps` if
+    /// the bid wins; default when there is none.
+    pub referrer: Pubkey,
+    /// Set when a receipt token was minted for this bid; withdrawing burns it.
+    pub receipt: bool,
+    /// Lamports of anti-spam bond held by this account beyond its rent.
+    pub bond: u64,
+    /// `AuctionState::auction_id` of the auction the bid was placed in.
+    pub auction_id: u64,
+    /// Which of the bidder's bids in the auction this is; a wallet holds one
+    /// bid per index. See [`bid_index_seed`].
+    pub bid_index: u16,
+    /// Set through `set_standing`: if the bid loses, it carries into the next
+    /// round instead of being refunded by `crank_refund`.
+    pub standing: bool,
+    /// Round the bid was linked into the auction's ranked list in, counting
+    /// from one; zero if never. See [`AuctionState::is_ranked`].
+    ///
+    /// [`AuctionState::is_ranked`]: super::AuctionState::is_ranked
+    pub ranked_round: u64,
+    /// Neighbouring bids in the ranked list, higher and lower; default at
+    /// either end and while unranked.
+    pub prev_bid: Pubkey,
+    pub next_bid: Pubkey,
+    /// Slot of the last change to `amount` or `max_amount`; settlement waits
+    /// `AuctionState::min_settle_slots` past it.
+    pub updated_slot: u64,
+    /// Loyalty points earned and not yet minted by `claim_points`.
+    pub points: u64,
+    /// When `points` last caught up with the bid's escrow.
+    pub points_accrued_at: i64,
+    /// Share of the treasury's cut given back to the bidder if this bid wins,
+    /// from the fee tier their discount-token balance reached when placing it.
+    pub fee_discount_bps: u16,
+    /// Key that may raise this bid without the bidder's signature, until
+    /// `session_expires_at`; see `set_session_key`. Default when none.
+    pub session_key: Pubkey,
+    pub session_expires_at: i64,
+    /// How much more `session_key` may still add to the bid.
+    pub session_spend_cap: u64,
+    /// `sealed_commitment` of a sealed bid's amount, cleared by `reveal_bid`;
+    /// zero for open bids. `amount` stays zero until then.
+    pub commitment: [u8; 32],
+    /// `AuctionState::commit_bond` escrowed with the sealed bid, apart from
+    /// `max_amount`; cleared when the bid is revealed.
+    pub commit_bond: u64,
+}
+
+/// Last seed of a bid PDA: the index's little-endian bytes, or nothing for
+/// index zero so a wallet's first bid keeps the address it had before
+/// indices existed.
+pub fn bid_index_seed(bid_index: u16) -> Vec<u8> {
+    if bid_index == 0 {
+        Vec::new()
+    } else {
+        bid_index.to_le_bytes().to_vec()
+    }
+}
+
+/// What a sealed bid of `amount` by `bidder` commits to; `salt` is a secret
+/// the bidder keeps until `reveal_bid`, so the amount cannot be guessed.
+pub fn sealed_commitment(bidder: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[bidder.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+}
+
+impl Bid {
+    pub fn id_seed(&self) -> Vec<u8> {
+        super::auction_seed(self.auction_id)
+    }
+
+    pub fn index_seed(&self) -> Vec<u8> {
+        bid_index_seed(self.bid_index)
+    }
+
+    /// The bid's place in ranking; see [`LeaderboardEntry::outranks`].
+    pub fn rank(&self) -> LeaderboardEntry {
+        LeaderboardEntry {
+            bidder: self.bidder,
+            amount: self.amount,
+            bid_index: self.bid_index,
+            updated_at: self.updated_at,
+        }
+    }
+
+    /// Tokens this bid holds in escrow.
+    pub fn escrowed(&self) -> u64 {
+        self.max_amount - self.balance_due
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.session_key != Pubkey::default()
+    }
+
+    pub fn end_session(&mut self) {
+        self.session_key = Pubkey::default();
+        self.session_expires_at = 0;
+        self.session_spend_cap = 0;
+    }
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Most lots one bundle bid can cover.
+pub const MAX_BUNDLE_LOTS: usize = 8;
+
+/// A conditional bid for several lots together, seeded by auction and
+/// bidder. `settle_bundle` sells it every lot only if `amount` beats the sum
+/// of those lots' leading bids; otherwise it is refunded and the lots settle
+/// one by one.
+#[account]
+#[derive(InitSpace)]
+pub struct BundleBid {
+    pub bidder: Pubkey,
+    pub auction_id: u64,
+    /// Lot ids, strictly ascending.
+    #[max_len(MAX_BUNDLE_LOTS)]
+    pub lots: Vec<u16>,
+    /// Offered for all of `lots` together; escrowed in full.
+    pub amount: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl BundleBid {
+    pub fn id_seed(&self) -> Vec<u8> {
+        super::auction_seed(self.auction_id)
+    }
+}
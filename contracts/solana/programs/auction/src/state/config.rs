@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+/// Most fee discount tiers a `Config` holds.
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Holding at least `min_balance` of the discount token when bidding takes
+/// `discount_bps` off the treasury's cut should the bid win.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace, PartialEq)]
+pub struct FeeTier {
+    pub min_balance: u64,
+    pub discount_bps: u16,
+}
+
+/// Parameters for features that are off by default. Created by the first
+/// `update_config`; instructions that read it take it as an optional account
+/// and fall back to `Config::default()` when it is absent.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Config {
+    pub bump: u8,
+    /// Token whose balance at bid time earns a fee discount; default when
+    /// there are none.
+    pub discount_mint: Pubkey,
+    /// Discount tiers by ascending `min_balance`; unused entries are zeroed.
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+}
+
+impl Config {
+    /// Discount earned by holding `balance` of the discount token: that of
+    /// the highest tier it reaches, or zero.
+    pub fn fee_discount(&self, balance: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.discount_bps > 0 && balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+}
+
+/// Overrides applied by `update_config`; `None` leaves a setting unchanged.
+/// Each field is validated as its dedicated setter would.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ConfigUpdate {
+    pub minimum_bid: Option<u64>,
+    /// Reopens a settled or ended round, as `set_end_time` does.
+    pub end_time: Option<i64>,
+    pub withdrawal_penalty_bps: Option<u16>,
+    pub bid_increment: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub num_winners: Option<u8>,
+    pub uniform_price: Option<bool>,
+    pub raffle_mode: Option<bool>,
+    pub vesting_duration: Option<i64>,
+    /// `(deposit_bps, payment_window)`, set together.
+    pub deposit_terms: Option<(u16, i64)>,
+    pub transfer_hooks: Option<bool>,
+    pub abandonment_window: Option<i64>,
+    /// `(curator, curator_bps)`, set together.
+    pub curator: Option<(Pubkey, u16)>,
+    pub referral_bps: Option<u16>,
+    /// `(moderator, bid_bond)`, set together.
+    pub moderator: Option<(Pubkey, u64)>,
+    pub bid_ttl: Option<i64>,
+    pub english_auction: Option<bool>,
+    pub candle_window: Option<i64>,
+    /// WARNING: in all-pay mode no bid is ever refunded; losing bids go to
+    /// the treasury at settlement. Only settable with no active bids.
+    pub all_pay: Option<bool>,
+    /// Moves the time bidding opens, to schedule the next round ahead.
+    pub start_time: Option<i64>,
+    /// `(dynamic_minimum_bps, dynamic_minimum_rounds)`, set together; zero
+    /// bps turns the rule off and leaves `minimum_bid` where it is.
+    pub dynamic_minimum: Option<(u16, u8)>,
+    /// `(discount_mint, tiers)`, set together: at most `MAX_FEE_TIERS` tiers
+    /// by strictly ascending balance. No tiers turns discounts off.
+    pub fee_discounts: Option<(Pubkey, Vec<FeeTier>)>,
+}
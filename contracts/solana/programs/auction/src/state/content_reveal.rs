@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+pub const MAX_CONTENT_URI_LEN: usize = 200;
+
+/// Revealed deliverable for a committed content hash, seeded by the hash so
+/// it outlives the round it was committed in.
+#[account]
+#[derive(InitSpace)]
+pub struct ContentReveal {
+    pub content_hash: [u8; 32],
+    #[max_len(MAX_CONTENT_URI_LEN)]
+    pub uri: String,
+    pub revealed_at: i64,
+    pub bump: u8,
+}
+
+impl ContentReveal {
+    /// Commitment `commit_content` expects for a deliverable at `uri`.
+    pub fn hash_uri(uri: &str) -> [u8; 32] {
+        hashv(&[uri.as_bytes()]).to_bytes()
+    }
+}
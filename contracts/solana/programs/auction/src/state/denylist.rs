@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use super::AuctionState;
+use crate::error::AuctionError;
+
+pub const MAX_DENYLISTED: usize = 32;
+
+/// Agent-managed list of wallets barred from placing or raising bids,
+/// created by the first `set_denylisted`.
+#[account]
+#[derive(InitSpace)]
+pub struct Denylist {
+    pub bump: u8,
+    pub wallets: [Pubkey; MAX_DENYLISTED],
+    pub len: u8,
+}
+
+impl Denylist {
+    pub fn wallets(&self) -> &[Pubkey] {
+        &self.wallets[..self.len as usize]
+    }
+
+    pub fn contains(&self, wallet: &Pubkey) -> bool {
+        self.wallets().contains(wallet)
+    }
+
+    pub fn insert(&mut self, wallet: Pubkey) -> Result<()> {
+        if self.contains(&wallet) {
+            return Ok(());
+        }
+        let len = self.len as usize;
+        require!(len < MAX_DENYLISTED, AuctionError::DenylistFull);
+        self.wallets[len] = wallet;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, wallet: &Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self.wallets().iter().position(|w| w == wallet) {
+            self.wallets.copy_within(index + 1..len, index);
+            self.wallets[len - 1] = Pubkey::default();
+            self.len -= 1;
+        }
+    }
+}
+
+/// Fails if `wallet` is denylisted. Once the auction has a denylist every
+/// bid path must pass it, so it cannot be skipped by leaving it out.
+pub fn require_not_denylisted(
+    state: &AuctionState,
+    denylist: Option<&Denylist>,
+    wallet: &Pubkey,
+) -> Result<()> {
+    match denylist {
+        Some(denylist) => require!(!denylist.contains(wallet), AuctionError::BidderDenylisted),
+        None => require!(!state.denylist_enabled, AuctionError::DenylistRequired),
+    }
+    Ok(())
+}
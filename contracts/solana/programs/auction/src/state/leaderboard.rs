@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_WINNERS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct LeaderboardEntry {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// `Bid::bid_index` of the ranked bid.
+    pub bid_index: u16,
+    /// `Bid::updated_at` of the ranked bid, which breaks ties.
+    pub updated_at: i64,
+}
+
+impl LeaderboardEntry {
+    pub fn is(&self, bidder: Pubkey, bid_index: u16) -> bool {
+        self.bidder == bidder && self.bid_index == bid_index
+    }
+
+    /// Whether this bid ranks ahead of `other`. The higher amount wins; equal
+    /// amounts go to the bid left unchanged longest, so raising a bid gives
+    /// up its place among equals. Bids changed in the same second rank
+    /// neither way, and keep their arrival order on the leaderboard.
+    pub fn outranks(&self, other: &LeaderboardEntry) -> bool {
+        (other.amount, self.updated_at) < (self.amount, other.updated_at)
+    }
+}
+
+/// Top bids, highest first, in `LeaderboardEntry::outranks` order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct Leaderboard {
+    pub entries: [LeaderboardEntry; MAX_WINNERS],
+    pub len: u8,
+}
+
+impl Leaderboard {
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.len as usize]
+    }
+
+    /// Number of entries the top one does not outrank, itself included:
+    /// those with the top amount changed in the same second.
+    pub fn tied_at_top(&self) -> usize {
+        match self.entries().first() {
+            Some(top) => self
+                .entries()
+                .iter()
+                .take_while(|e| !top.outranks(e))
+                .count(),
+            None => 0,
+        }
+    }
+
+    /// Moves the entry at `index` to the front, keeping the others in order.
+    pub fn promote(&mut self, index: usize) {
+        self.entries[..=index].rotate_right(1);
+    }
+
+    pub fn reset_to(&mut self, entry: LeaderboardEntry) {
+        *self = Self::default();
+        self.entries[0] = entry;
+        self.len = 1;
+    }
+
+    /// Hands `from`'s entry for bid `bid_index` to `to`, keeping its amount
+    /// and position.
+    pub fn rename(&mut self, from: Pubkey, to: Pubkey, bid_index: u16) {
+        let len = self.len as usize;
+        if let Some(entry) = self.entries[..len].iter_mut().find(|e| e.is(from, bid_index)) {
+            entry.bidder = to;
+        }
+    }
+
+    pub fn remove(&mut self, bidder: Pubkey, bid_index: u16) {
+        let len = self.len as usize;
+        if let Some(index) = self.entries().iter().position(|e| e.is(bidder, bid_index)) {
+            self.entries.copy_within(index + 1..len, index);
+            self.entries[len - 1] = LeaderboardEntry::default();
+            self.len -= 1;
+        }
+    }
+
+    pub fn record(&mut self, entry: LeaderboardEntry) {
+        self.remove(entry.bidder, entry.bid_index);
+
+        let len = self.len as usize;
+        let index = self
+            .entries()
+            .iter()
+            .position(|e| entry.outranks(e))
+            .unwrap_or(len);
+        if index >= MAX_WINNERS {
+            return;
+        }
+
+        let end = len.min(MAX_WINNERS - 1);
+        self.entries.copy_within(index..end, index + 1);
+        self.entries[index] = entry;
+        self.len = (len + 1).min(MAX_WINNERS) as u8;
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+/// One item of a multi-item round, seeded by auction and lot id. A lot takes
+/// its own `LotBid`s against its own minimum, tracks its own leader, and is
+/// sold on its own by `settle_lot`, apart from the round's main item.
+#[account]
+#[derive(InitSpace)]
+pub struct Lot {
+    pub auction_id: u64,
+    /// 1-based, in creation order; see `AuctionState::lot_count`.
+    pub lot_id: u16,
+    pub minimum_bid: u64,
+    /// Lowest leading amount `settle_lot` sells at; below it the lot goes
+    /// unsold and every bid is refunded.
+    pub reserve_price: u64,
+    /// Commitment to the lot's item, as `AuctionState::content_hash` is for
+    /// the main item; zero when none was given.
+    pub item_hash: [u8; 32],
+    /// Bidder of the highest lot bid; default while the lot has none.
+    pub leader: Pubkey,
+    pub leading_amount: u64,
+    /// Lot bids holding escrow.
+    pub active_bid_count: u64,
+    pub settled: bool,
+    /// Set by `settle_lot`; default when the lot went unsold.
+    pub winner: Pubkey,
+    pub bump: u8,
+    /// Unsettled `BundleBid`s covering the lot; `settle_lot` waits for them.
+    pub pending_bundles: u32,
+}
+
+impl Lot {
+    pub fn id_seed(&self) -> Vec<u8> {
+        super::auction_seed(self.auction_id)
+    }
+
+    pub fn lot_seed(&self) -> [u8; 2] {
+        self.lot_id.to_le_bytes()
+    }
+
+    /// Whether settling now would sell the lot.
+    pub fn meets_reserve(&self) -> bool {
+        self.leader != Pubkey::default() && self.leading_amount >= self.reserve_price
+    }
+}
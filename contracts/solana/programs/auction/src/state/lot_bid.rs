@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// A wallet's bid on one `Lot`, seeded by auction, lot and bidder. Always
+/// escrowed in full in the auction mint; raising it tops the escrow up.
+#[account]
+#[derive(InitSpace)]
+pub struct LotBid {
+    pub bidder: Pubkey,
+    pub auction_id: u64,
+    pub lot_id: u16,
+    pub amount: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl LotBid {
+    pub fn id_seed(&self) -> Vec<u8> {
+        super::auction_seed(self.auction_id)
+    }
+
+    pub fn lot_seed(&self) -> [u8; 2] {
+        self.lot_id.to_le_bytes()
+    }
+}
@@ -1,5 +1,39 @@
+mod accepted_mints;
 mod auction_state;
+mod audit_log;
 mod bid;
+mod bundle_bid;
+mod config;
+mod content_reveal;
+mod denylist;
+mod leaderboard;
+mod lot;
+mod lot_bid;
+mod operator;
+mod price_feed;
+mod received_message;
+mod refund_claims;
+mod registry;
+mod round_result;
+mod status;
+mod win_receipt;
 
+pub use accepted_mints::*;
 pub use auction_state::*;
+pub use audit_log::*;
 pub use bid::*;
+pub use bundle_bid::*;
+pub use config::*;
+pub use content_reveal::*;
+pub use denylist::*;
+pub use leaderboard::*;
+pub use lot::*;
+pub use lot_bid::*;
+pub use operator::*;
+pub use price_feed::*;
+pub use received_message::*;
+pub use refund_claims::*;
+pub use registry::*;
+pub use round_result::*;
+pub use status::*;
+pub use win_receipt::*;
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Program-wide account of the agent allowed to open auctions through
+/// `create_auction`. Created once by `initialize_operator`.
+#[account]
+#[derive(InitSpace)]
+pub struct Operator {
+    pub agent: Pubkey,
+    /// Auctions opened through `create_auction` so far, and so the number
+    /// of `RegistryPage` entries.
+    pub auction_count: u64,
+    pub bump: u8,
+}
+
+/// Starting parameters of a new auction; everything else is set afterwards
+/// through the agent's setters, as for the auction from `initialize`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct AuctionConfig {
+    pub minimum_bid: u64,
+    /// `mint_safety::ALLOW_*` flags for risky Token-2022 extensions the agent
+    /// accepts on the auction mint.
+    pub mint_overrides: u8,
+    /// Listed in the registry as `RegistryEntry::item_metadata`.
+    pub item_metadata: Pubkey,
+    /// Unix time bidding opens; zero opens it at once.
+    pub start_time: i64,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use super::RoundResult;
+
+/// Latest clearing price of an auction at a fixed address, so other programs
+/// can read it without parsing events. Created by the first settlement and
+/// overwritten by every one after it.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeed {
+    pub auction_id: u64,
+    /// What one winner paid; the lowest winning price in a multi-winner round.
+    pub price: u64,
+    /// `RoundResult::round` of the round that set `price`.
+    pub round: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl PriceFeed {
+    pub fn publish(&mut self, result: &RoundResult, price: u64, bump: u8) {
+        self.auction_id = result.auction_id;
+        self.price = price;
+        self.round = result.round;
+        self.updated_at = result.settled_at;
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Marks a Wormhole message as consumed. Seeded by emitter chain and
+/// sequence, so a VAA can never be redeemed twice.
+#[account]
+#[derive(InitSpace)]
+pub struct ReceivedMessage {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Bitmap slots a single account can hold within the 10 KiB limit on
+/// accounts created through CPI.
+pub const MAX_REFUND_LEAVES: u32 = 80_000;
+
+/// Claim bitmap for one refunds Merkle root, seeded by the root. A set bit
+/// means the leaf at that index has been paid out.
+#[account]
+pub struct RefundClaims {
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub bump: u8,
+    pub claimed: Vec<u8>,
+}
+
+impl RefundClaims {
+    pub fn space(leaf_count: u32) -> usize {
+        8 + 32 + 4 + 1 + 4 + Self::bitmap_len(leaf_count)
+    }
+
+    pub fn bitmap_len(leaf_count: u32) -> usize {
+        leaf_count.div_ceil(8) as usize
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
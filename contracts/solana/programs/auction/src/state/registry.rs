@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use super::AuctionStatus;
+
+/// Auctions listed per registry page; page `n` holds the auctions created
+/// `n * REGISTRY_PAGE_LEN` onwards, in creation order.
+pub const REGISTRY_PAGE_LEN: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct RegistryEntry {
+    pub auction_id: u64,
+    /// Status as of the last `sync_registry`; the auction state itself is
+    /// authoritative.
+    pub status: AuctionStatus,
+    /// Account or 32-byte content id describing the item on sale, e.g. its
+    /// Metaplex metadata account. Default when there is none.
+    pub item_metadata: Pubkey,
+}
+
+/// One page of the list of auctions opened through `create_auction`, seeded
+/// by its index. `Operator::auction_count` tells how many pages exist.
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryPage {
+    pub index: u32,
+    pub bump: u8,
+    #[max_len(REGISTRY_PAGE_LEN)]
+    pub entries: Vec<RegistryEntry>,
+}
+
+impl RegistryPage {
+    /// Page that lists the auction created after `auction_count` others.
+    pub fn index_for(auction_count: u64) -> u32 {
+        (auction_count / REGISTRY_PAGE_LEN as u64) as u32
+    }
+
+    pub fn entry_mut(&mut self, auction_id: u64) -> Option<&mut RegistryEntry> {
+        self.entries.iter_mut().find(|e| e.auction_id == auction_id)
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use super::AuctionState;
+
+/// Permanent record of one settled round, seeded by auction and round
+/// number. Written at settlement and never closed, so past winners stay
+/// readable after their bids are refunded and closed.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundResult {
+    pub auction_id: u64,
+    /// 1-based; equal to `AuctionState::total_rounds_settled` once written.
+    pub round: u64,
+    /// The top winner of a multi-winner round.
+    pub winner: Pubkey,
+    /// Paid by every winner together.
+    pub amount: u64,
+    pub winner_count: u8,
+    pub settled_at: i64,
+    /// `AuctionState::content_hash` at settlement; zero when no content was
+    /// committed.
+    pub item_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl RoundResult {
+    /// Fills the record for the round `state` has just settled.
+    pub fn record(
+        &mut self,
+        state: &AuctionState,
+        winner: Pubkey,
+        amount: u64,
+        winner_count: u8,
+        bump: u8,
+    ) -> Result<()> {
+        self.auction_id = state.auction_id;
+        self.round = state.total_rounds_settled;
+        self.winner = winner;
+        self.amount = amount;
+        self.winner_count = winner_count;
+        self.settled_at = Clock::get()?.unix_timestamp;
+        self.item_hash = state.content_hash;
+        self.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+/// Lifecycle of a round. `Closing` is recorded lazily: the first instruction
+/// to touch the state after the deadline moves an open round into it, so
+/// clients should use `AuctionState::status_at` for the current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum AuctionStatus {
+    /// Initialized, with no bid placed yet.
+    #[default]
+    Created,
+    /// Taking bids.
+    Open,
+    /// Past the deadline and awaiting settlement.
+    Closing,
+    /// Paid out. `set_end_time` reopens it for the next round.
+    Settled,
+    /// Called off by the agent; bidders withdraw in full. Only
+    /// `close_auction` follows.
+    Cancelled,
+    /// Left unsettled past the abandonment window; bidders withdraw in full.
+    /// Only `close_auction` follows.
+    Abandoned,
+    /// Wound up by `close_auction` once every bid was paid out or refunded.
+    /// Terminal.
+    Closed,
+}
+
+impl AuctionStatus {
+    /// Whether `transition` may move a round from `self` to `to`. Reopening
+    /// a settled round for the next one is `set_end_time`'s, not a step here.
+    pub fn can_become(self, to: Self) -> bool {
+        use AuctionStatus::*;
+        // Every legal step is listed; anything else is refused.
+        matches!(
+            (self, to),
+            (Created | Open, Open)
+                | (Created | Open | Closing, Settled | Cancelled)
+                | (Closing, Abandoned)
+                | (Settled | Cancelled | Abandoned, Closed)
+        )
+    }
+
+    /// Whether the round has ended for good without a winner, so that
+    /// withdrawals carry no penalty.
+    pub fn is_called_off(self) -> bool {
+        matches!(self, Self::Cancelled | Self::Abandoned)
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use super::{AuctionState, Bid};
+
+/// Proof that `winner` won a round, seeded by auction, round and winner. It
+/// outlives the winning bid, which is closed once paid, so other programs
+/// and the delivery flow can check a win against it. Issued by the
+/// single-winner settlements; multi-winner rounds are recorded only in their
+/// `RoundResult`.
+#[account]
+#[derive(InitSpace)]
+pub struct WinReceipt {
+    pub auction_id: u64,
+    pub round: u64,
+    pub winner: Pubkey,
+    pub bid_index: u16,
+    /// Price the winner paid.
+    pub amount: u64,
+    /// `AuctionState::content_hash` at settlement; zero when no content was
+    /// committed.
+    pub item_hash: [u8; 32],
+    pub settled_at: i64,
+    pub bump: u8,
+}
+
+impl WinReceipt {
+    /// Fills the receipt for `bid`, which has just won the round `state`
+    /// settled at `amount`.
+    pub fn issue(
+        &mut self,
+        state: &AuctionState,
+        bid: &Bid,
+        amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.auction_id = state.auction_id;
+        self.round = state.total_rounds_settled;
+        self.winner = bid.bidder;
+        self.bid_index = bid.bid_index;
+        self.amount = amount;
+        self.item_hash = state.content_hash;
+        self.settled_at = Clock::get()?.unix_timestamp;
+        self.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::onchain;
+use anchor_spl::token_interface::{self, TransferChecked};
+
+/// `token_interface::transfer_checked` that also forwards `hook_accounts`, so
+/// a mint with the TransferHook extension can find its extra-account-metas
+/// PDA, the hook program, and the accounts the PDA lists. Without hook
+/// accounts this is the plain CPI.
+pub fn transfer_checked<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if hook_accounts.is_empty() {
+        return token_interface::transfer_checked(ctx, amount, decimals);
+    }
+    let TransferChecked {
+        from,
+        mint,
+        to,
+        authority,
+    } = ctx.accounts;
+    onchain::invoke_transfer_checked(
+        ctx.program.key,
+        from,
+        mint,
+        to,
+        authority,
+        hook_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AuctionError;
+
+#[cfg(not(feature = "devnet"))]
+pub const CORE_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+#[cfg(feature = "devnet")]
+pub const CORE_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+pub const PAYLOAD_BID: u8 = 1;
+pub const PAYLOAD_WITHDRAW: u8 = 2;
+
+/// Body of a core bridge `PostedVAA` account, after its `vaa` magic prefix.
+/// Only the core bridge can create these, and only after guardian signatures
+/// have been verified.
+#[derive(AnchorDeserialize)]
+struct PostedVaaData {
+    _vaa_version: u8,
+    _consistency_level: u8,
+    _vaa_time: u32,
+    _signature_set: Pubkey,
+    _submission_time: u32,
+    _nonce: u32,
+    sequence: u64,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    payload: Vec<u8>,
+}
+
+/// A cross-chain bid instruction: `[kind: u8][auction_id: u64 big-endian]
+/// [bidder: 32 bytes][amount: u64 big-endian]`. `bidder` is the Wormhole
+/// universal address of the foreign wallet; `auction_id` names the one
+/// auction the message is for, as one emitter may serve several.
+pub struct BidMessage {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub kind: u8,
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+impl BidMessage {
+    pub fn parse(posted_vaa: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *posted_vaa.owner,
+            CORE_BRIDGE_PROGRAM_ID,
+            AuctionError::InvalidVaa
+        );
+        let data = posted_vaa.try_borrow_data()?;
+        require!(data.starts_with(b"vaa"), AuctionError::InvalidVaa);
+        let vaa = PostedVaaData::deserialize(&mut &data[3..])
+            .map_err(|_| AuctionError::InvalidVaa)?;
+
+        let payload = vaa.payload.as_slice();
+        require!(payload.len() == 49, AuctionError::InvalidVaa);
+        let auction_id = u64::from_be_bytes(payload[1..9].try_into().unwrap());
+        let bidder = Pubkey::new_from_array(payload[9..41].try_into().unwrap());
+        let amount = u64::from_be_bytes(payload[41..49].try_into().unwrap());
+
+        Ok(Self {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            kind: payload[0],
+            auction_id,
+            bidder,
+            amount,
+        })
+    }
+}
@@ -0,0 +1,486 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::event::{
+    BuyNowPriceChanged, ModeratorChanged, NumWinnersChanged, Paused, RaffleModeChanged, Unpaused,
+};
+use cartoonist_auction::state::{audit_action, AuctionStatus, AuditEntry, ConfigUpdate};
+use cartoonist_auction::{accounts, instruction};
+use solana_sdk::signature::Signer;
+
+use crate::harness::*;
+use crate::ixs::{self, admin, place_bid_accounts};
+
+#[test]
+#[ignore = "needs anchor build"]
+fn setters_are_agent_only() {
+    let mut h = Harness::new();
+    let mallory = h.bidder(0);
+    let key = mallory.pubkey();
+    let charity = h.token_account(&key, 0);
+    let attempts = [
+        admin::set_minimum_bid(key, 1),
+        admin::set_end_time(key, 1),
+        admin::set_withdrawal_penalty(key, 1),
+        admin::set_bid_increment(key, 1),
+        admin::set_buy_now_price(key, 1),
+        admin::set_num_winners(key, 2),
+        admin::set_uniform_price(key, true),
+        admin::set_raffle_mode(key, true),
+        admin::set_transfer_hooks(key, true),
+        admin::set_agent(key, key),
+        admin::set_charity(key, h.mint, Some(charity), 1),
+        admin::set_treasury(key, h.mint, charity),
+        admin::update_config(key, ConfigUpdate::default()),
+        admin::set_settler(key, key),
+        admin::set_abandonment_window(key, 1),
+        admin::set_curator(key, key, 1),
+        admin::set_referral_bps(key, 1),
+        admin::set_moderator(key, key, 1),
+        admin::set_bid_ttl(key, 1),
+        admin::cancel_auction(key),
+        admin::set_english_auction(key, true),
+        admin::set_candle_window(key, 1),
+        admin::set_all_pay(key, true),
+        admin::close_auction(key),
+        admin::set_paused(key, true),
+    ];
+    for instruction in attempts {
+        assert_auction_error(h.send(instruction, &[&mallory]), AuctionError::OnlyAgent);
+    }
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn setters_update_the_state() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    for instruction in [
+        admin::set_minimum_bid(agent, 5 * USDC),
+        admin::set_end_time(agent, 2_000),
+        admin::set_withdrawal_penalty(agent, 250),
+        admin::set_bid_increment(agent, USDC),
+        admin::set_buy_now_price(agent, 90 * USDC),
+        admin::set_num_winners(agent, 3),
+        admin::set_uniform_price(agent, true),
+        admin::set_raffle_mode(agent, true),
+        admin::set_transfer_hooks(agent, true),
+    ] {
+        h.as_agent(instruction).unwrap();
+    }
+
+    let state = h.state();
+    assert_eq!(state.minimum_bid, 5 * USDC);
+    assert_eq!(state.end_time, 2_000);
+    assert_eq!(state.withdrawal_penalty_bps, 250);
+    assert_eq!(state.bid_increment, USDC);
+    assert_eq!(state.buy_now_price, 90 * USDC);
+    assert_eq!(state.num_winners, 3);
+    assert!(state.uniform_price);
+    assert!(state.raffle_mode);
+    assert!(state.transfer_hooks);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn setters_announce_old_and_new_values() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+
+    let logs = h.as_agent(admin::set_raffle_mode(agent, true)).unwrap().logs;
+    let [changed] = &events::<RaffleModeChanged>(&logs)[..] else {
+        panic!("expected one RaffleModeChanged in {logs:#?}");
+    };
+    assert!(!changed.old_raffle_mode && changed.new_raffle_mode);
+
+    let logs = h.as_agent(admin::set_num_winners(agent, 3)).unwrap().logs;
+    let [changed] = &events::<NumWinnersChanged>(&logs)[..] else {
+        panic!("expected one NumWinnersChanged in {logs:#?}");
+    };
+    assert_eq!((changed.old_num_winners, changed.new_num_winners), (1, 3));
+
+    let moderator = Pubkey::new_unique();
+    let update = ConfigUpdate {
+        buy_now_price: Some(90 * USDC),
+        moderator: Some((moderator, USDC)),
+        ..ConfigUpdate::default()
+    };
+    let logs = h.as_agent(admin::update_config(agent, update)).unwrap().logs;
+    let [changed] = &events::<BuyNowPriceChanged>(&logs)[..] else {
+        panic!("expected one BuyNowPriceChanged in {logs:#?}");
+    };
+    assert_eq!((changed.old_buy_now_price, changed.new_buy_now_price), (0, 90 * USDC));
+    let [changed] = &events::<ModeratorChanged>(&logs)[..] else {
+        panic!("expected one ModeratorChanged in {logs:#?}");
+    };
+    assert_eq!((changed.moderator, changed.bid_bond), (moderator, USDC));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn pausing_holds_new_bids_until_unpaused() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let logs = h.as_agent(admin::set_paused(agent, true)).unwrap().logs;
+    assert_eq!(events::<Paused>(&logs).len(), 1);
+    assert!(h.state().paused);
+    let bob = h.bidder(100 * USDC);
+    assert_auction_error(h.place_bid(&bob, 30 * USDC), AuctionError::AuctionPaused);
+    assert_auction_error(
+        h.update_bid(&alice, 10 * USDC as i64),
+        AuctionError::AuctionPaused,
+    );
+    // Pausing again changes nothing and announces nothing.
+    let logs = h.as_agent(admin::set_paused(agent, true)).unwrap().logs;
+    assert!(events::<Paused>(&logs).is_empty());
+
+    let logs = h.as_agent(admin::set_paused(agent, false)).unwrap().logs;
+    assert_eq!(events::<Unpaused>(&logs).len(), 1);
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    assert_eq!(h.state().leader, bob.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_config_applies_only_the_given_overrides() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(admin::set_bid_increment(agent, USDC)).unwrap();
+
+    let update = ConfigUpdate {
+        minimum_bid: Some(5 * USDC),
+        end_time: Some(2_000),
+        uniform_price: Some(true),
+        deposit_terms: Some((2_000, 3_600)),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::update_config(agent, update)).unwrap();
+
+    let state = h.state();
+    assert_eq!(state.minimum_bid, 5 * USDC);
+    assert_eq!(state.end_time, 2_000);
+    assert!(state.uniform_price);
+    assert_eq!((state.deposit_bps, state.payment_window), (2_000, 3_600));
+    assert_eq!(state.bid_increment, USDC);
+    assert!(!state.raffle_mode);
+    assert!(h.config().is_some());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_config_validates_every_override() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let update = ConfigUpdate {
+        minimum_bid: Some(5 * USDC),
+        withdrawal_penalty_bps: Some(10_001),
+        ..ConfigUpdate::default()
+    };
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidBps,
+    );
+    assert_eq!(h.state().minimum_bid, MINIMUM_BID);
+
+    let update = ConfigUpdate {
+        num_winners: Some(0),
+        ..ConfigUpdate::default()
+    };
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidNumWinners,
+    );
+
+    let update = ConfigUpdate {
+        abandonment_window: Some(-1),
+        ..ConfigUpdate::default()
+    };
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidAbandonmentWindow,
+    );
+
+    let update = ConfigUpdate {
+        bid_ttl: Some(-1),
+        ..ConfigUpdate::default()
+    };
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidBidTtl,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn basis_points_are_capped() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let charity = h.token_account(&Pubkey::new_unique(), 0);
+    assert_auction_error(
+        h.as_agent(admin::set_withdrawal_penalty(agent, 10_001)),
+        AuctionError::InvalidBps,
+    );
+    assert_auction_error(
+        h.as_agent(admin::set_charity(agent, h.mint, Some(charity), 10_001)),
+        AuctionError::InvalidBps,
+    );
+    h.as_agent(admin::set_withdrawal_penalty(agent, 10_000)).unwrap();
+
+    // Charity and curator shares are capped together.
+    h.as_agent(admin::set_charity(agent, h.mint, Some(charity), 6_000))
+        .unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_curator(agent, Pubkey::new_unique(), 4_001)),
+        AuctionError::InvalidBps,
+    );
+    h.as_agent(admin::set_curator(agent, Pubkey::new_unique(), 3_000))
+        .unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_referral_bps(agent, 1_001)),
+        AuctionError::InvalidBps,
+    );
+    h.as_agent(admin::set_referral_bps(agent, 1_000)).unwrap();
+
+    // update_config checks the shares it sets together.
+    let update = ConfigUpdate {
+        curator: Some((Pubkey::default(), 0)),
+        referral_bps: Some(4_000),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::update_config(agent, update)).unwrap();
+    let state = h.state();
+    assert_eq!((state.curator_bps, state.referral_bps), (0, 4_000));
+    let update = ConfigUpdate {
+        curator: Some((Pubkey::new_unique(), 1)),
+        referral_bps: Some(4_000),
+        ..ConfigUpdate::default()
+    };
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidBps,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn num_winners_is_bounded_by_the_leaderboard() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    assert_auction_error(h.as_agent(admin::set_num_winners(agent, 0)), AuctionError::InvalidNumWinners);
+    assert_auction_error(h.as_agent(admin::set_num_winners(agent, 9)), AuctionError::InvalidNumWinners);
+    h.as_agent(admin::set_num_winners(agent, 8)).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_agent_hands_over_control() {
+    let mut h = Harness::new();
+    let new_agent = h.bidder(0);
+    h.as_agent(admin::set_agent(h.agent.pubkey(), new_agent.pubkey()))
+        .unwrap();
+    assert_eq!(h.state().agent, new_agent.pubkey());
+
+    assert_auction_error(
+        h.as_agent(admin::set_minimum_bid(h.agent.pubkey(), 1)),
+        AuctionError::OnlyAgent,
+    );
+    h.send(admin::set_minimum_bid(new_agent.pubkey(), 1), &[&new_agent])
+        .unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn admin_changes_are_audited_once_the_log_exists() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(admin::set_minimum_bid(agent, 2 * USDC)).unwrap();
+    h.as_agent(admin::initialize_audit_log(agent)).unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_minimum_bid(agent, 3 * USDC)),
+        AuctionError::AuditLogRequired,
+    );
+
+    h.as_agent(admin::audited(admin::set_minimum_bid(agent, 3 * USDC))).unwrap();
+    let update = ConfigUpdate {
+        minimum_bid: Some(4 * USDC),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::audited(admin::update_config(agent, update))).unwrap();
+    let new_treasury = h.token_account(&Pubkey::new_unique(), 0);
+    let old_treasury = h.treasury;
+    h.as_agent(admin::audited(admin::set_treasury(agent, h.mint, new_treasury))).unwrap();
+    let new_agent = Pubkey::new_unique();
+    h.as_agent(admin::audited(admin::set_agent(agent, new_agent))).unwrap();
+
+    let log = h.audit_log().unwrap();
+    assert_eq!(log.total, 4);
+    let entries = log.entries();
+    let changes: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.action, entry.old_value, entry.new_value))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (
+                audit_action::MINIMUM_BID_CHANGED,
+                AuditEntry::amount(2 * USDC),
+                AuditEntry::amount(3 * USDC)
+            ),
+            (
+                audit_action::MINIMUM_BID_CHANGED,
+                AuditEntry::amount(3 * USDC),
+                AuditEntry::amount(4 * USDC)
+            ),
+            (audit_action::TREASURY_CHANGED, old_treasury.to_bytes(), new_treasury.to_bytes()),
+            (audit_action::AGENT_CHANGED, agent.to_bytes(), new_agent.to_bytes()),
+        ]
+    );
+    assert!(entries.iter().all(|entry| entry.actor == agent));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_treasury_requires_the_auction_mint() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let other_mint = Pubkey::new_unique();
+    h.set_mint(other_mint, 6);
+    let wrong = Pubkey::new_unique();
+    h.set_token_account(wrong, other_mint, &agent, 0);
+    assert_anchor_error(
+        h.as_agent(admin::set_treasury(agent, h.mint, wrong)),
+        ErrorCode::ConstraintTokenMint,
+    );
+
+    let new_treasury = h.token_account(&Pubkey::new_unique(), 0);
+    h.as_agent(admin::set_treasury(agent, h.mint, new_treasury))
+        .unwrap();
+    assert_eq!(h.state().treasury, new_treasury);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn migrate_escrow_moves_bids_only_within_a_mint() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let other_mint = Pubkey::new_unique();
+    h.set_mint(other_mint, 6);
+    let other_treasury = Pubkey::new_unique();
+    h.set_token_account(other_treasury, other_mint, &agent, 0);
+    let switch = ixs::migrate_escrow(agent, escrow_pda(), h.mint, other_mint, other_treasury, 1);
+    assert_auction_error(h.as_agent(switch), AuctionError::EscrowNotEmpty);
+
+    let rotate = ixs::migrate_escrow(agent, escrow_pda(), h.mint, h.mint, h.treasury, 1);
+    h.as_agent(rotate).unwrap();
+    let new_escrow = ixs::migrated_escrow_pda(&h.mint, 1);
+    assert_eq!(h.balance(&new_escrow), 20 * USDC);
+    assert_eq!(h.escrow_balance(), 0);
+    let state = h.state();
+    assert_eq!((state.escrow, state.escrow_generation), (new_escrow, 1));
+    assert_eq!(state.total_escrowed, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn rescue_tokens_moves_only_stray_mints() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let bidder_funds = ixs::rescue_tokens(agent, escrow_pda(), h.treasury, h.mint, 20 * USDC);
+    assert_auction_error(h.as_agent(bidder_funds), AuctionError::MintNotRescuable);
+
+    let stray_mint = Pubkey::new_unique();
+    h.set_mint(stray_mint, 9);
+    let stuck = Pubkey::new_unique();
+    h.set_token_account(stuck, stray_mint, &auction_state_pda(), 5);
+    let destination = Pubkey::new_unique();
+    h.set_token_account(destination, stray_mint, &agent, 0);
+    h.as_agent(ixs::rescue_tokens(agent, stuck, destination, stray_mint, 5))
+        .unwrap();
+    assert_eq!(h.balance(&destination), 5);
+    assert_eq!(h.escrow_balance(), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn denylisted_wallets_cannot_bid_and_can_be_refunded() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.as_agent(ixs::set_denylisted(agent, alice.pubkey(), true))
+        .unwrap();
+    assert!(h.state().denylist_enabled);
+
+    // Once the list exists, bids must be checked against it.
+    assert_auction_error(h.place_bid(&carol, 40 * USDC), AuctionError::DenylistRequired);
+    let leader_bid = h.leader_bid_for(&carol.pubkey());
+    let accounts = accounts::PlaceBid {
+        denylist: Some(ixs::denylist_pda()),
+        ..place_bid_accounts(h.mint, carol.pubkey(), h.usdc(&carol.pubkey()), leader_bid)
+    };
+    let place = instruction::PlaceBid { bid_index: 0, amount: 40 * USDC, referrer: None };
+    h.send(ix(accounts, place), &[&carol]).unwrap();
+
+    let leader_bid = h.leader_bid_for(&alice.pubkey());
+    let accounts = accounts::UpdateBid {
+        denylist: Some(ixs::denylist_pda()),
+        ..ixs::update_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), leader_bid)
+    };
+    assert_auction_error(
+        h.send(ix(accounts, instruction::IncreaseBid { amount: 50 * USDC }), &[&alice]),
+        AuctionError::BidderDenylisted,
+    );
+
+    let refund_bob = ixs::force_refund(agent, h.mint, bob.pubkey(), h.usdc(&bob.pubkey()));
+    assert_auction_error(h.as_agent(refund_bob), AuctionError::BidderNotDenylisted);
+    h.as_agent(ixs::force_refund(agent, h.mint, alice.pubkey(), h.usdc(&alice.pubkey())))
+        .unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    assert_eq!(h.balance(&h.treasury), 0);
+    assert_eq!(h.state().active_bid_count, 2);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_charity_without_an_account_clears_it() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let charity = h.token_account(&Pubkey::new_unique(), 0);
+    h.as_agent(admin::set_charity(agent, h.mint, Some(charity), 500))
+        .unwrap();
+    assert_eq!(h.state().charity, charity);
+
+    h.as_agent(admin::set_charity(agent, h.mint, None, 500)).unwrap();
+    let state = h.state();
+    assert_eq!(state.charity, Pubkey::default());
+    assert_eq!(state.charity_bps, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_end_time_reopens_a_settled_auction() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    let instruction = crate::ixs::crank_settle(cranker.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+    assert!(h.state().is_settled());
+
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    assert_eq!(h.state().status, AuctionStatus::Created);
+}
@@ -0,0 +1,1370 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType};
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::event::Outbid;
+use cartoonist_auction::state::{AuctionStatus, ConfigUpdate, RegistryPage};
+use cartoonist_auction::{accounts, instruction, mint_safety, pda};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin, place_bid_accounts};
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_sets_defaults() {
+    let h = Harness::new();
+    let state = h.state();
+    assert_eq!(state.agent, h.agent.pubkey());
+    assert_eq!(state.usdc_mint, h.mint);
+    assert_eq!(state.treasury, h.treasury);
+    assert_eq!(state.minimum_bid, MINIMUM_BID);
+    assert_eq!(state.num_winners, 1);
+    assert_eq!(state.end_time, 0);
+    assert_eq!(h.escrow_balance(), 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn pda_helpers_match_the_accounts_and_their_cached_bumps() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 25 * USDC).unwrap();
+    let state = h.state();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+
+    assert_eq!(pda::find_auction_state(0), (auction_state_pda(), state.bump));
+    assert_eq!(pda::find_escrow(0), (escrow_pda(), state.escrow_bump));
+    assert_eq!(pda::find_bid(0, &alice.pubkey(), 0), (bid_pda(&alice.pubkey()), bid.bump));
+    assert_eq!(pda::auction_state_address(0, state.bump), Ok(auction_state_pda()));
+    assert_eq!(pda::escrow_address(0, state.escrow_bump), Ok(escrow_pda()));
+    assert_eq!(
+        pda::bid_address(0, &alice.pubkey(), 0, bid.bump),
+        Ok(bid_pda(&alice.pubkey()))
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_rejects_non_usdc_decimals() {
+    let mut h = Harness::bare();
+    let mint = Pubkey::new_unique();
+    h.set_mint(mint, 9);
+    let treasury = Pubkey::new_unique();
+    h.set_token_account(treasury, mint, &h.agent.pubkey(), 0);
+    let result = h.as_agent(h.initialize_ix(mint, treasury, MINIMUM_BID));
+    assert_auction_error(result, AuctionError::InvalidMintDecimals);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_rejects_treasury_owned_by_someone_else() {
+    let mut h = Harness::bare();
+    let treasury = Pubkey::new_unique();
+    h.set_token_account(treasury, h.mint, &Pubkey::new_unique(), 0);
+    let result = h.as_agent(h.initialize_ix(h.mint, treasury, MINIMUM_BID));
+    assert_anchor_error(result, ErrorCode::ConstraintTokenOwner);
+}
+
+/// Token-2022 mint with a permanent delegate, and an agent treasury for it.
+fn permanent_delegate_mint(h: &mut Harness) -> (Pubkey, Pubkey) {
+    let mint = Pubkey::new_unique();
+    h.set_mint_2022(mint, &[ExtensionType::PermanentDelegate], |state| {
+        let extension = state.init_extension::<PermanentDelegate>(true).unwrap();
+        extension.delegate = Some(Pubkey::new_unique()).try_into().unwrap();
+    });
+    let treasury = Pubkey::new_unique();
+    let agent = h.agent.pubkey();
+    h.set_token_2022_account(treasury, mint, &agent);
+    (mint, treasury)
+}
+
+fn initialize_2022(h: &Harness, mint: Pubkey, treasury: Pubkey, mint_overrides: u8) -> Instruction {
+    ix(
+        accounts::Initialize {
+            auction_state: auction_state_pda(),
+            usdc_mint: mint,
+            treasury,
+            escrow: escrow_pda(),
+            agent: h.agent.pubkey(),
+            token_program: anchor_spl::token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::Initialize {
+            minimum_bid: MINIMUM_BID,
+            mint_overrides,
+        },
+    )
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_rejects_a_permanent_delegate_mint() {
+    let mut h = Harness::bare();
+    let (mint, treasury) = permanent_delegate_mint(&mut h);
+    let result = h.as_agent(initialize_2022(&h, mint, treasury, 0));
+    assert_auction_error(result, AuctionError::UnsafeMint);
+
+    let result = h.as_agent(initialize_2022(&h, mint, treasury, mint_safety::ALLOW_PAUSABLE));
+    assert_auction_error(result, AuctionError::UnsafeMint);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_accepts_a_permanent_delegate_mint_with_its_override() {
+    let mut h = Harness::bare();
+    let (mint, treasury) = permanent_delegate_mint(&mut h);
+    let overrides = mint_safety::ALLOW_PERMANENT_DELEGATE;
+    h.as_agent(initialize_2022(&h, mint, treasury, overrides))
+        .unwrap();
+    assert_eq!(h.state().usdc_mint, mint);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn initialize_only_once() {
+    let mut h = Harness::new();
+    let result = h.as_agent(h.initialize_ix(h.mint, h.treasury, MINIMUM_BID));
+    assert!(result.is_err());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn create_auction_opens_an_independent_auction() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::initialize_operator(agent)).unwrap();
+    h.as_agent(ixs::create_auction(agent, 7, 0, h.mint, h.treasury))
+        .unwrap();
+
+    let (state_key, escrow) = ixs::auction_pdas(7);
+    let state: cartoonist_auction::state::AuctionState = h.anchor_account(&state_key).unwrap();
+    assert_eq!(state.auction_id, 7);
+    assert_eq!(state.agent, agent);
+    assert_eq!(state.escrow, escrow);
+    assert_eq!(state.minimum_bid, 2 * MINIMUM_BID);
+    let operator: cartoonist_auction::state::Operator = h.anchor_account(&ixs::operator_pda()).unwrap();
+    assert_eq!(operator.auction_count, 1);
+
+    // The same wallet bids in both auctions through separate bid accounts.
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 25 * USDC).unwrap();
+    let alice_usdc = h.usdc(&alice.pubkey());
+    let bid = Pubkey::find_program_address(
+        &[b"bid", &7u64.to_le_bytes(), alice.pubkey().as_ref()],
+        &cartoonist_auction::ID,
+    )
+    .0;
+    let accounts = accounts::PlaceBid {
+        auction_state: state_key,
+        bid,
+        escrow,
+        ..place_bid_accounts(h.mint, alice.pubkey(), alice_usdc, None)
+    };
+    let place = ix(
+        accounts,
+        instruction::PlaceBid { bid_index: 0, amount: 30 * USDC, referrer: None },
+    );
+    h.send(place, &[&alice]).unwrap();
+
+    assert_eq!(h.balance(&escrow), 30 * USDC);
+    assert_eq!(h.escrow_balance(), 25 * USDC);
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 25 * USDC);
+    let bid: cartoonist_auction::state::Bid = h.anchor_account(&bid).unwrap();
+    assert_eq!(bid.auction_id, 7);
+    assert_eq!(h.state().active_bid_count, 1);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn create_auction_is_agent_only_and_reserves_id_zero() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::initialize_operator(agent)).unwrap();
+
+    let result = h.as_agent(ixs::create_auction(agent, 0, 0, h.mint, h.treasury));
+    assert_auction_error(result, AuctionError::InvalidAuctionId);
+
+    let mallory = h.bidder(0);
+    let treasury = h.token_account(&mallory.pubkey(), 0);
+    let result = h.send(
+        ixs::create_auction(mallory.pubkey(), 1, 0, h.mint, treasury),
+        &[&mallory],
+    );
+    assert_auction_error(result, AuctionError::OnlyAgent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn registry_lists_auctions_in_creation_order() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::initialize_operator(agent)).unwrap();
+    for id in [9, 4] {
+        h.as_agent(ixs::create_auction(agent, id, 0, h.mint, h.treasury))
+            .unwrap();
+    }
+    let page: RegistryPage = h.anchor_account(&ixs::registry_page_pda(0)).unwrap();
+    let ids: Vec<u64> = page.entries.iter().map(|e| e.auction_id).collect();
+    assert_eq!(ids, [9, 4]);
+    assert_eq!(page.entries[0].status, AuctionStatus::Created);
+
+    // A page other than the operator's current one is rejected by its seeds.
+    let result = h.as_agent(ixs::create_auction(agent, 5, 1, h.mint, h.treasury));
+    assert_anchor_error(result, ErrorCode::ConstraintSeeds);
+
+    let alice = h.bidder(100 * USDC);
+    let (state_key, escrow) = ixs::auction_pdas(4);
+    let bid = Pubkey::find_program_address(
+        &[b"bid", &4u64.to_le_bytes(), alice.pubkey().as_ref()],
+        &cartoonist_auction::ID,
+    )
+    .0;
+    let alice_usdc = h.usdc(&alice.pubkey());
+    let accounts = accounts::PlaceBid {
+        auction_state: state_key,
+        bid,
+        escrow,
+        ..place_bid_accounts(h.mint, alice.pubkey(), alice_usdc, None)
+    };
+    h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 30 * USDC, referrer: None }),
+        &[&alice],
+    )
+        .unwrap();
+    h.send(ixs::sync_registry(4, 0), &[&alice]).unwrap();
+    let page: RegistryPage = h.anchor_account(&ixs::registry_page_pda(0)).unwrap();
+    assert_eq!(page.entries[1].status, AuctionStatus::Open);
+    assert_eq!(page.entries[0].status, AuctionStatus::Created);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_escrows_and_takes_the_lead() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 25 * USDC).unwrap();
+
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert_eq!(bid.amount, 25 * USDC);
+    assert_eq!(bid.max_amount, 25 * USDC);
+    assert!(bid.active && !bid.proxy);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 75 * USDC);
+    assert_eq!(h.escrow_balance(), 25 * USDC);
+
+    let state = h.state();
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leading_amount, 25 * USDC);
+    assert_eq!(state.active_bid_count, 1);
+    assert_eq!(state.total_escrowed, 25 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_below_minimum_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    assert_auction_error(h.place_bid(&alice, MINIMUM_BID - 1), AuctionError::BidTooLow);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_after_end_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.end_auction();
+    assert_auction_error(h.place_bid(&alice, 20 * USDC), AuctionError::AuctionEnded);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_before_start_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let start_time = h.now() + 100;
+    let update = ConfigUpdate {
+        start_time: Some(start_time),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::update_config(h.agent.pubkey(), update)).unwrap();
+    assert_auction_error(h.place_bid(&alice, 20 * USDC), AuctionError::AuctionNotStarted);
+
+    h.warp(start_time);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_sponsor_can_pay_a_new_bids_rent() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let sponsor = h.bidder(0);
+    let lamports = h.svm.get_balance(&alice.pubkey()).unwrap();
+    let accounts = accounts::PlaceBid {
+        payer: sponsor.pubkey(),
+        ..place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), None)
+    };
+    let place = ix(
+        accounts,
+        instruction::PlaceBid { bid_index: 0, amount: 20 * USDC, referrer: None },
+    );
+    h.send(place, &[&sponsor, &alice]).unwrap();
+
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().bidder, alice.pubkey());
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 80 * USDC);
+    assert_eq!(h.svm.get_balance(&alice.pubkey()).unwrap(), lamports);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_sponsor_funds_the_bond_so_the_bidder_needs_only_tokens() {
+    const BOND: u64 = 10_000_000;
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(admin::set_moderator(agent, agent, BOND)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let sponsor = h.bidder(0);
+    let lamports = h.svm.get_balance(&alice.pubkey()).unwrap();
+    let accounts = accounts::PlaceBid {
+        payer: sponsor.pubkey(),
+        ..place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), None)
+    };
+    let place = ix(
+        accounts,
+        instruction::PlaceBid { bid_index: 0, amount: 20 * USDC, referrer: None },
+    );
+    h.send(place, &[&sponsor, &alice]).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().bond, BOND);
+    assert_eq!(h.svm.get_balance(&alice.pubkey()).unwrap(), lamports);
+
+    let bid_lamports = h.svm.get_balance(&bid_pda(&alice.pubkey())).unwrap();
+    let withdraw = accounts::WithdrawBid {
+        auction_state: auction_state_pda(),
+        bid: bid_pda(&alice.pubkey()),
+        bidder_usdc: h.usdc(&alice.pubkey()),
+        escrow: escrow_pda(),
+        usdc_mint: h.mint,
+        bidder: alice.pubkey(),
+        payer: sponsor.pubkey(),
+        token_program: anchor_spl::token::ID,
+        accepted_mints: None,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: anchor_lang::system_program::ID,
+        receipt_mint: None,
+        bidder_receipt: None,
+        memo_program: None,
+    };
+    h.send(ix(withdraw, instruction::WithdrawBid {}), &[&sponsor, &alice])
+        .unwrap();
+    assert_eq!(h.svm.get_balance(&alice.pubkey()).unwrap(), lamports + bid_lamports);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_session_key_raises_the_bid_within_its_cap_and_expiry() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let session = h.bidder(0);
+    let alice_usdc = h.usdc(&alice.pubkey());
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let expires_at = h.now() + 60;
+    let authorize = ixs::set_session_key(
+        h.mint,
+        alice.pubkey(),
+        alice_usdc,
+        session.pubkey(),
+        expires_at,
+        15 * USDC,
+    );
+    h.send(authorize, &[&alice]).unwrap();
+
+    let (mint, key) = (h.mint, alice.pubkey());
+    let raise = |change: i64| {
+        ixs::session_update_bid(mint, key, session.pubkey(), alice_usdc, change, None)
+    };
+    h.send(raise(10 * USDC as i64), &[&session]).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 30 * USDC);
+    assert_eq!(h.balance(&alice_usdc), 70 * USDC);
+    assert_auction_error(
+        h.send(raise(10 * USDC as i64), &[&session]),
+        AuctionError::SessionSpendCapExceeded,
+    );
+    assert_auction_error(
+        h.send(raise(-(5 * USDC as i64)), &[&session]),
+        AuctionError::BidderSignatureRequired,
+    );
+    let stranger = h.bidder(0);
+    let instruction = ixs::session_update_bid(mint, key, stranger.pubkey(), alice_usdc, 1, None);
+    assert_auction_error(h.send(instruction, &[&stranger]), AuctionError::InvalidSessionKey);
+
+    h.warp(expires_at);
+    assert_auction_error(
+        h.send(raise(5 * USDC as i64), &[&session]),
+        AuctionError::SessionKeyExpired,
+    );
+    // The bidder's next signed update ends the lapsed session.
+    assert_eq!(h.delegate(&alice_usdc), Some(auction_state_pda()));
+    h.increase_bid(&alice, 5 * USDC).unwrap();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert_eq!(bid.amount, 35 * USDC);
+    assert_eq!(bid.session_key, Pubkey::default());
+    assert_eq!(h.delegate(&alice_usdc), None);
+}
+
+/// Alice bids 20 USDC and gives `session` a 15 USDC allowance for a minute.
+fn bid_with_session(h: &mut Harness) -> (Keypair, Pubkey) {
+    let alice = h.bidder(100 * USDC);
+    let session = h.bidder(0);
+    let alice_usdc = h.usdc(&alice.pubkey());
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let expires_at = h.now() + 60;
+    let authorize =
+        ixs::set_session_key(h.mint, alice.pubkey(), alice_usdc, session.pubkey(), expires_at, 15 * USDC);
+    h.send(authorize, &[&alice]).unwrap();
+    assert_eq!(h.delegate(&alice_usdc), Some(auction_state_pda()));
+    (alice, alice_usdc)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdrawing_a_bid_revokes_its_session_allowance() {
+    let mut h = Harness::new();
+    let (alice, alice_usdc) = bid_with_session(&mut h);
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.delegate(&alice_usdc), None);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn closing_a_bid_revokes_its_session_allowance() {
+    let mut h = Harness::new();
+    let (alice, alice_usdc) = bid_with_session(&mut h);
+    h.settle(&alice.pubkey()).unwrap();
+
+    assert_auction_error(
+        h.send(ixs::close_bid(alice.pubkey(), None), &[&alice]),
+        AuctionError::InvalidTokenAccount,
+    );
+    h.send(ixs::close_bid(alice.pubkey(), Some(alice_usdc)), &[&alice]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.delegate(&alice_usdc), None);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn the_ranked_list_only_accepts_verified_positions() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 30 * USDC).unwrap();
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.place_bid(&carol, 10 * USDC).unwrap();
+    let [a, b, c] = [&alice, &bob, &carol].map(|k| bid_pda(&k.pubkey()));
+    let cranker = h.bidder(0);
+
+    h.send(ixs::link_bid(a, None, None), &[&cranker]).unwrap();
+    assert_auction_error(
+        h.send(ixs::link_bid(c, None, Some(a)), &[&cranker]),
+        AuctionError::RankOrderViolated,
+    );
+    h.send(ixs::link_bid(c, Some(a), None), &[&cranker]).unwrap();
+    assert_auction_error(
+        h.send(ixs::link_bid(b, None, Some(c)), &[&cranker]),
+        AuctionError::RankHintMismatch,
+    );
+    assert_auction_error(
+        h.send(ixs::link_bid(b, Some(c), None), &[&cranker]),
+        AuctionError::RankOrderViolated,
+    );
+    h.send(ixs::link_bid(b, Some(a), Some(c)), &[&cranker]).unwrap();
+
+    let state = h.state();
+    assert_eq!((state.ranked_head, state.ranked_count), (a, 3));
+    let bob_bid = h.bid(&bob.pubkey()).unwrap();
+    assert_eq!((bob_bid.prev_bid, bob_bid.next_bid), (a, c));
+    assert_eq!(h.bid(&carol.pubkey()).unwrap().prev_bid, b);
+
+    // A ranked bid has to leave the list before it can change.
+    assert_auction_error(h.withdraw_bid(&bob), AuctionError::BidRanked);
+    h.send(ixs::unlink_bid(bob.pubkey(), Some(a), Some(c)), &[&bob]).unwrap();
+    h.withdraw_bid(&bob).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().next_bid, c);
+    assert_eq!(h.bid(&carol.pubkey()).unwrap().prev_bid, a);
+    assert_eq!(h.state().ranked_count, 2);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_twice_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(h.place_bid(&alice, 30 * USDC), AuctionError::BidAlreadyPlaced);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn one_wallet_can_hold_bids_at_several_indices() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    // Alice's first bid leads, so her second must bring it along.
+    let leader_bid = Some(bid_pda(&alice.pubkey()));
+    let accounts = accounts::PlaceBid {
+        bid: bid_pda_at(&alice.pubkey(), 1),
+        ..place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), leader_bid)
+    };
+    h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 1, amount: 30 * USDC, referrer: None }),
+        &[&alice],
+    )
+    .unwrap();
+
+    let second = h.bid_at(&alice.pubkey(), 1).unwrap();
+    assert_eq!((second.bid_index, second.amount), (1, 30 * USDC));
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 20 * USDC);
+    let state = h.state();
+    assert_eq!((state.leader, state.leader_index), (alice.pubkey(), 1));
+    assert_eq!(state.active_bid_count, 2);
+    assert_eq!(h.escrow_balance(), 50 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn upsert_bid_places_then_tops_up_to_the_target() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.upsert_bid(&alice, 20 * USDC).unwrap();
+    h.upsert_bid(&bob, 30 * USDC).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 20 * USDC);
+
+    h.upsert_bid(&alice, 40 * USDC).unwrap();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert_eq!(bid.amount, 40 * USDC);
+    assert_eq!(bid.max_amount, 40 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 60 * USDC);
+    assert_eq!(h.state().leader, alice.pubkey());
+
+    // A retry of a call that already landed changes nothing.
+    h.upsert_bid(&alice, 40 * USDC).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 60 * USDC);
+    assert_eq!(h.escrow_balance(), 70 * USDC);
+    assert_auction_error(h.upsert_bid(&alice, 35 * USDC), AuctionError::BidTooLow);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_requires_the_leader_bid() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let accounts = place_bid_accounts(h.mint, bob.pubkey(), h.usdc(&bob.pubkey()), None);
+    let result = h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 30 * USDC, referrer: None }),
+        &[&bob],
+    );
+    assert_auction_error(result, AuctionError::LeaderBidRequired);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_rejects_a_bid_that_is_not_the_leader() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 30 * USDC).unwrap();
+    h.place_bid(&bob, 20 * USDC).unwrap();
+
+    let accounts = place_bid_accounts(
+        h.mint,
+        carol.pubkey(),
+        h.usdc(&carol.pubkey()),
+        Some(bid_pda(&bob.pubkey())),
+    );
+    let result = h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 40 * USDC, referrer: None }),
+        &[&carol],
+    );
+    assert_auction_error(result, AuctionError::WrongLeaderBid);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_rejects_a_token_account_of_another_mint() {
+    let mut h = Harness::new();
+    let alice = h.bidder(0);
+    let other_mint = Pubkey::new_unique();
+    h.set_mint(other_mint, 6);
+    let wrong = Pubkey::new_unique();
+    h.set_token_account(wrong, other_mint, &alice.pubkey(), 100 * USDC);
+
+    let accounts = place_bid_accounts(h.mint, alice.pubkey(), wrong, None);
+    let result = h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 20 * USDC, referrer: None }),
+        &[&alice],
+    );
+    assert_anchor_error(result, ErrorCode::ConstraintTokenMint);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn place_bid_rejects_a_fake_escrow() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let fake = Pubkey::new_unique();
+    h.set_token_account(fake, h.mint, &auction_state_pda(), 0);
+
+    let mut accounts = place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), None);
+    accounts.escrow = fake;
+    let result = h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 20 * USDC, referrer: None }),
+        &[&alice],
+    );
+    assert_auction_error(result, AuctionError::InvalidTokenAccount);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn higher_bid_takes_the_lead_and_ties_keep_it() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    assert_eq!(h.state().leader, bob.pubkey());
+
+    h.place_bid(&carol, 30 * USDC).unwrap();
+    let state = h.state();
+    assert_eq!(state.leader, bob.pubkey());
+    let order: Vec<_> = state.leaderboard.entries().iter().map(|e| e.bidder).collect();
+    assert_eq!(order, [bob.pubkey(), carol.pubkey(), alice.pubkey()]);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn proxy_bid_escalates_one_increment_above_the_challenger() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_bid_increment(h.agent.pubkey(), USDC))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+
+    h.place_proxy_bid(&alice, 50 * USDC).unwrap();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert!(bid.proxy);
+    assert_eq!(bid.amount, MINIMUM_BID);
+    assert_eq!(bid.max_amount, 50 * USDC);
+    assert_eq!(h.escrow_balance(), 50 * USDC);
+
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 21 * USDC);
+    let state = h.state();
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leading_amount, 21 * USDC);
+
+    // Outbidding the ceiling pushes the proxy to its maximum.
+    h.update_bid(&bob, 40 * USDC as i64).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 50 * USDC);
+    assert_eq!(h.state().leader, bob.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn taking_the_lead_announces_who_was_outbid() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_bid_increment(h.agent.pubkey(), USDC))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+
+    let logs = h.place_proxy_bid(&alice, 50 * USDC).unwrap().logs;
+    assert!(events::<Outbid>(&logs).is_empty());
+    // Bob falls short of Alice's ceiling, so she keeps the lead.
+    let logs = h.place_bid(&bob, 20 * USDC).unwrap().logs;
+    assert!(events::<Outbid>(&logs).is_empty());
+
+    let logs = h.update_bid(&bob, 40 * USDC as i64).unwrap().logs;
+    let [outbid] = &events::<Outbid>(&logs)[..] else {
+        panic!("expected one Outbid in {logs:#?}");
+    };
+    assert_eq!(
+        (outbid.previous_leader, outbid.previous_amount),
+        (alice.pubkey(), 50 * USDC)
+    );
+    assert_eq!((outbid.new_leader, outbid.new_amount), (bob.pubkey(), 60 * USDC));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_moves_escrow_both_ways() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    h.update_bid(&alice, 15 * USDC as i64).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 35 * USDC);
+    assert_eq!(h.escrow_balance(), 35 * USDC);
+
+    h.update_bid(&alice, -(20 * USDC as i64)).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 15 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 85 * USDC);
+    assert_eq!(h.state().total_escrowed, 15 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_cannot_drop_below_minimum() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(
+        h.update_bid(&alice, -(15 * USDC as i64)),
+        AuctionError::AmountBelowMinimum,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_cannot_withdraw_more_than_escrowed() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(
+        h.update_bid(&alice, -(21 * USDC as i64)),
+        AuctionError::InsufficientEscrow,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_rejects_i64_min() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(h.update_bid(&alice, i64::MIN), AuctionError::InvalidAmountChange);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_only_decreases_after_end() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    assert_auction_error(h.update_bid(&alice, USDC as i64), AuctionError::AuctionEnded);
+    h.update_bid(&alice, -(USDC as i64)).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn update_bid_rejects_someone_elses_bid() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let mallory = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let mut instruction = ixs::update_bid(
+        h.mint,
+        mallory.pubkey(),
+        h.usdc(&mallory.pubkey()),
+        -(5 * USDC as i64),
+        None,
+    );
+    instruction.accounts[1].pubkey = bid_pda(&alice.pubkey());
+    assert_auction_error(h.send(instruction, &[&mallory]), AuctionError::WrongBidder);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn increase_and_decrease_bid_move_escrow() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    h.increase_bid(&alice, 15 * USDC).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 35 * USDC);
+    assert_eq!(h.escrow_balance(), 35 * USDC);
+
+    h.decrease_bid(&alice, 20 * USDC).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().amount, 15 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 85 * USDC);
+    assert_eq!(h.state().total_escrowed, 15 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn increase_and_decrease_bid_reject_zero() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(h.increase_bid(&alice, 0), AuctionError::InvalidAmountChange);
+    assert_auction_error(h.decrease_bid(&alice, 0), AuctionError::InvalidAmountChange);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn decrease_bid_keeps_the_minimum_and_the_escrow() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(
+        h.decrease_bid(&alice, 15 * USDC),
+        AuctionError::AmountBelowMinimum,
+    );
+    assert_auction_error(
+        h.decrease_bid(&alice, 21 * USDC),
+        AuctionError::InsufficientEscrow,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn increase_bid_fails_after_end() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    assert_auction_error(h.increase_bid(&alice, USDC), AuctionError::AuctionEnded);
+    h.decrease_bid(&alice, USDC).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_bid_refunds_and_closes() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.withdraw_bid(&alice).unwrap();
+
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    let state = h.state();
+    assert_eq!(state.active_bid_count, 0);
+    assert_eq!(state.leader, Pubkey::default());
+    assert_eq!(state.total_escrowed, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdrawing_the_leader_hands_the_lead_to_the_runner_up() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.withdraw_bid(&bob).unwrap();
+
+    let state = h.state();
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leading_amount, 20 * USDC);
+    // A lower newcomer no longer takes over the vacated lead.
+    h.place_bid(&carol, 15 * USDC).unwrap();
+    assert_eq!(h.state().leader, alice.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn decreasing_below_the_runner_up_hands_over_the_lead() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.decrease_bid(&bob, 15 * USDC).unwrap();
+
+    let state = h.state();
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leading_amount, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_bid_recreates_a_closed_token_account() {
+    let mut h = Harness::new();
+    let alice = h.bidder(20 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.close_account(&h.usdc(&alice.pubkey()));
+
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 20 * USDC);
+    assert!(h.bid(&alice.pubkey()).is_none());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_bid_charges_the_penalty_only_before_the_end() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_withdrawal_penalty(h.agent.pubkey(), 1_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 98 * USDC);
+    assert_eq!(h.state().accrued_penalties, 2 * USDC);
+
+    h.end_auction();
+    h.withdraw_bid(&bob).unwrap();
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 100 * USDC);
+    assert_eq!(h.state().total_escrowed, 2 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn decreasing_before_withdrawing_costs_the_same_penalty() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_withdrawal_penalty(h.agent.pubkey(), 1_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 40 * USDC).unwrap();
+    h.place_bid(&bob, 40 * USDC).unwrap();
+
+    h.withdraw_bid(&alice).unwrap();
+    h.decrease_bid(&bob, 30 * USDC).unwrap();
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 87 * USDC);
+    h.withdraw_bid(&bob).unwrap();
+
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 96 * USDC);
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 96 * USDC);
+    let state = h.state();
+    assert_eq!(state.accrued_penalties, 8 * USDC);
+    assert_eq!(state.total_escrowed, 8 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_bid_after_winning_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.settle(&alice.pubkey()).unwrap();
+    assert_auction_error(h.withdraw_bid(&alice), AuctionError::BidNotActive);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn gc_bid_returns_rent_to_the_bidder() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.settle(&bob.pubkey()).unwrap();
+    let cranker = h.bidder(0);
+    let (a, b) = (bid_pda(&alice.pubkey()), bid_pda(&bob.pubkey()));
+
+    assert_auction_error(
+        h.send(ixs::gc_bid(cranker.pubkey(), &[(a, alice.pubkey())]), &[&cranker]),
+        AuctionError::BidStillActive,
+    );
+    assert_auction_error(
+        h.send(ixs::gc_bid(cranker.pubkey(), &[(b, cranker.pubkey())]), &[&cranker]),
+        AuctionError::WrongBidder,
+    );
+
+    let rent = h.svm.get_balance(&b).unwrap();
+    let lamports = h.svm.get_balance(&bob.pubkey()).unwrap();
+    h.send(ixs::gc_bid(cranker.pubkey(), &[(b, bob.pubkey())]), &[&cranker]).unwrap();
+    assert!(h.bid(&bob.pubkey()).is_none());
+    assert_eq!(h.svm.get_balance(&bob.pubkey()).unwrap(), lamports + rent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn gc_bid_leaves_unclaimed_points_alone() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::create_points_mint(agent)).unwrap();
+    h.as_agent(ixs::set_points_rate(agent, 10)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.warp(h.now() + 3_600);
+    h.settle(&alice.pubkey()).unwrap();
+    let cranker = h.bidder(0);
+    let bid = bid_pda(&alice.pubkey());
+    assert!(h.bid(&alice.pubkey()).unwrap().points > 0);
+
+    assert_auction_error(
+        h.send(ixs::gc_bid(cranker.pubkey(), &[(bid, alice.pubkey())]), &[&cranker]),
+        AuctionError::PointsUnclaimed,
+    );
+
+    h.send(ixs::claim_points(cranker.pubkey(), alice.pubkey()), &[&cranker]).unwrap();
+    h.send(ixs::gc_bid(cranker.pubkey(), &[(bid, alice.pubkey())]), &[&cranker]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.balance(&get_associated_token_address(&alice.pubkey(), &ixs::points_mint_pda())), 200);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn closing_a_bid_waits_for_its_points_to_be_claimed() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::create_points_mint(agent)).unwrap();
+    h.as_agent(ixs::set_points_rate(agent, 10)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.warp(h.now() + 3_600);
+
+    assert_auction_error(h.withdraw_bid(&alice), AuctionError::PointsUnclaimed);
+    let withdraw = ixs::withdraw_bid(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()));
+    let claim = ixs::claim_points(alice.pubkey(), alice.pubkey());
+    h.send_all(&[claim, withdraw], &[&alice]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    let points = |bidder: &Pubkey| get_associated_token_address(bidder, &ixs::points_mint_pda());
+    assert_eq!(h.balance(&points(&alice.pubkey())), 200);
+
+    // Nor can anyone crank a refund out from under them.
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&carol, 40 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&carol.pubkey()).unwrap();
+    let refund = ixs::crank_refund(h.mint, cranker.pubkey(), bob.pubkey(), h.usdc(&bob.pubkey()));
+    assert_auction_error(h.send(refund.clone(), &[&cranker]), AuctionError::PointsUnclaimed);
+    let claim = ixs::claim_points(cranker.pubkey(), bob.pubkey());
+    h.send_all(&[claim, refund], &[&cranker]).unwrap();
+    assert!(h.balance(&points(&bob.pubkey())) >= 300);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn close_bid_only_after_it_is_inactive() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_auction_error(
+        h.send(ixs::close_bid(alice.pubkey(), None), &[&alice]),
+        AuctionError::BidStillActive,
+    );
+
+    h.settle(&alice.pubkey()).unwrap();
+    h.send(ixs::close_bid(alice.pubkey(), None), &[&alice]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+
+    // The bidder can come back once the next round opens.
+    assert_auction_error(h.place_bid(&alice, 20 * USDC), AuctionError::AuctionSettled);
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    h.place_bid(&alice, 20 * USDC).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn buy_now_settles_immediately() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_buy_now_price(h.agent.pubkey(), 40 * USDC))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 50 * USDC).unwrap();
+
+    let alice_usdc = h.usdc(&alice.pubkey());
+    h.send(
+        ixs::buy_now(h.mint, h.treasury, alice.pubkey(), alice_usdc, None),
+        &[&alice],
+    )
+    .unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 40 * USDC);
+    assert_eq!(h.balance(&alice_usdc), 60 * USDC);
+    let state = h.state();
+    assert!(state.is_settled());
+    assert_eq!(state.end_time, h.now());
+    assert!(!h.bid(&alice.pubkey()).unwrap().active);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn buy_now_error_paths() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let buy = |h: &Harness, alice: &Keypair| {
+        ixs::buy_now(h.mint, h.treasury, alice.pubkey(), h.usdc(&alice.pubkey()), None)
+    };
+
+    let instruction = buy(&h, &alice);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::BuyNowDisabled);
+
+    h.as_agent(admin::set_buy_now_price(h.agent.pubkey(), 40 * USDC))
+        .unwrap();
+    let instruction = buy(&h, &alice);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::BelowBuyNowPrice);
+
+    h.end_auction();
+    let instruction = buy(&h, &alice);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::AuctionEnded);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn buy_now_after_settlement_fails() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_buy_now_price(h.agent.pubkey(), 20 * USDC))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    let bob_usdc = h.usdc(&bob.pubkey());
+    h.send(ixs::buy_now(h.mint, h.treasury, bob.pubkey(), bob_usdc, None), &[&bob])
+        .unwrap();
+
+    let alice_usdc = h.usdc(&alice.pubkey());
+    let result = h.send(
+        ixs::buy_now(h.mint, h.treasury, alice.pubkey(), alice_usdc, None),
+        &[&alice],
+    );
+    assert_auction_error(result, AuctionError::AuctionSettled);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn bidders_cannot_refer_themselves() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let result = h.place_referred_bid(&alice, 20 * USDC, Some(alice.pubkey()));
+    assert_auction_error(result, AuctionError::SelfReferral);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn transfer_bid_moves_the_bid_and_its_lead() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let company = h.bidder(0);
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.place_bid(&alice, 30 * USDC).unwrap();
+
+    let instruction = ixs::transfer_bid(alice.pubkey(), company.pubkey());
+    let placed_at = h.bid(&alice.pubkey()).unwrap().updated_at;
+    h.warp(h.now() + 60);
+    h.send(instruction, &[&alice, &company]).unwrap();
+
+    assert!(h.bid(&alice.pubkey()).is_none());
+    let bid = h.bid(&company.pubkey()).unwrap();
+    assert_eq!(bid.bidder, company.pubkey());
+    assert_eq!((bid.amount, bid.max_amount), (30 * USDC, 30 * USDC));
+    assert_eq!(bid.updated_at, placed_at);
+    let state = h.state();
+    assert_eq!(state.leader, company.pubkey());
+    assert_eq!(state.leaderboard.entries()[0].bidder, company.pubkey());
+    assert_eq!(state.active_bid_count, 2);
+    assert_eq!(h.escrow_balance(), 50 * USDC);
+
+    // The new owner now controls the bid.
+    h.withdraw_bid(&company).unwrap();
+    assert_eq!(h.balance(&h.usdc(&company.pubkey())), 30 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn transfer_bid_needs_a_fresh_co_signing_owner() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    // Bob already has a bid account.
+    let instruction = ixs::transfer_bid(alice.pubkey(), bob.pubkey());
+    assert!(h.send(instruction, &[&alice, &bob]).is_err());
+
+    // Nobody can take a bid without its owner signing.
+    let mallory = h.bidder(0);
+    let mut instruction = ixs::transfer_bid(alice.pubkey(), mallory.pubkey());
+    instruction.accounts[3].is_signer = false;
+    assert!(h.send(instruction, &[&mallory]).is_err());
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().bidder, alice.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receipted_bids_burn_their_receipt_on_withdrawal() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::create_receipt_mint(agent, h.mint)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let receipt = Pubkey::new_unique();
+    h.set_token_account(receipt, ixs::receipt_mint_pda(), &alice.pubkey(), 0);
+
+    let accounts = accounts::PlaceBid {
+        receipt_mint: Some(ixs::receipt_mint_pda()),
+        bidder_receipt: Some(receipt),
+        ..place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), None)
+    };
+    let data = instruction::PlaceBid {
+        bid_index: 0,
+        amount: 20 * USDC,
+        referrer: None,
+    };
+    h.send(ix(accounts, data), &[&alice]).unwrap();
+    assert!(h.bid(&alice.pubkey()).unwrap().receipt);
+    assert_eq!(h.balance(&receipt), 1);
+
+    // The receipt cannot follow the bid to a new owner.
+    let company = h.bidder(0);
+    let instruction = ixs::transfer_bid(alice.pubkey(), company.pubkey());
+    let result = h.send(instruction, &[&alice, &company]);
+    assert_auction_error(result, AuctionError::BidReceipted);
+
+    assert_auction_error(h.withdraw_bid(&alice), AuctionError::ReceiptAccountsRequired);
+    let usdc = h.usdc(&alice.pubkey());
+    let instruction = ixs::withdraw_receipted_bid(h.mint, alice.pubkey(), usdc, Some(receipt));
+    h.send(instruction, &[&alice]).unwrap();
+    assert_eq!(h.balance(&receipt), 0);
+    assert_eq!(h.balance(&usdc), 100 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn bids_earn_points_for_the_time_they_hold_escrow() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::create_points_mint(agent)).unwrap();
+    h.as_agent(ixs::set_points_rate(agent, 10)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    // An hour at 20 USDC, then an hour at 50 once alice raises her bid.
+    let start = h.now();
+    h.warp(start + 3_600);
+    h.increase_bid(&alice, 30 * USDC).unwrap();
+    h.warp(start + 7_200);
+
+    let points = get_associated_token_address(&alice.pubkey(), &ixs::points_mint_pda());
+    h.send(ixs::claim_points(bob.pubkey(), alice.pubkey()), &[&bob]).unwrap();
+    assert_eq!(h.balance(&points), 700);
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().points, 0);
+    assert_auction_error(
+        h.send(ixs::claim_points(bob.pubkey(), alice.pubkey()), &[&bob]),
+        AuctionError::NoPoints,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn moderator_slashes_a_spam_bids_bond_to_the_treasury() {
+    const BOND: u64 = 10_000_000;
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let moderator = h.bidder(0);
+    h.as_agent(admin::set_moderator(agent, moderator.pubkey(), BOND))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let spammer = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&spammer, 10 * USDC).unwrap();
+    assert_eq!(h.bid(&spammer.pubkey()).unwrap().bond, BOND);
+
+    let spammer_usdc = h.usdc(&spammer.pubkey());
+    let (mint, treasury) = (h.mint, h.treasury);
+    let slash = |by: Pubkey| {
+        ixs::slash_bid(mint, by, spammer.pubkey(), spammer_usdc, treasury, agent)
+    };
+    let instruction = slash(alice.pubkey());
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlyModerator);
+
+    let treasury_lamports = h.svm.get_balance(&agent).unwrap();
+    let instruction = slash(moderator.pubkey());
+    h.send(instruction, &[&moderator]).unwrap();
+    assert_eq!(h.svm.get_balance(&agent).unwrap(), treasury_lamports + BOND);
+    assert!(h.bid(&spammer.pubkey()).is_none());
+    assert_eq!(h.balance(&spammer_usdc), 100 * USDC);
+    assert_eq!(h.state().active_bid_count, 1);
+
+    // Everyone else gets the bond back with the bid's rent.
+    let lamports = h.svm.get_balance(&alice.pubkey()).unwrap();
+    h.withdraw_bid(&alice).unwrap();
+    assert!(h.svm.get_balance(&alice.pubkey()).unwrap() > lamports + BOND);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn english_mode_escrows_only_the_leader() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_english_auction(h.agent.pubkey(), true))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    assert!(!h.bid(&alice.pubkey()).unwrap().active);
+    let state = h.state();
+    assert_eq!(state.leader, bob.pubkey());
+    assert_eq!(state.active_bid_count, 1);
+    assert_eq!(state.total_escrowed, 30 * USDC);
+    assert_eq!(h.escrow_balance(), 30 * USDC);
+
+    assert_auction_error(h.place_bid(&carol, 25 * USDC), AuctionError::BidTooLow);
+    h.send(ixs::close_bid(alice.pubkey(), None), &[&alice]).unwrap();
+    h.place_bid(&alice, 40 * USDC).unwrap();
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 100 * USDC);
+    assert_eq!(h.escrow_balance(), 40 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn english_mode_refunds_only_the_displaced_leader() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_english_auction(h.agent.pubkey(), true))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.withdraw_bid(&bob).unwrap();
+    assert_eq!(h.state().leader, Pubkey::default());
+
+    // With no leader, Alice's refunded bid is no one to pay back again.
+    let mut accounts = place_bid_accounts(
+        h.mint,
+        carol.pubkey(),
+        h.usdc(&carol.pubkey()),
+        Some(bid_pda(&alice.pubkey())),
+    );
+    accounts.leader_usdc = Some(h.usdc(&alice.pubkey()));
+    let place = ix(accounts, instruction::PlaceBid { bid_index: 0, amount: 40 * USDC, referrer: None });
+    assert_auction_error(h.send(place, &[&carol]), AuctionError::WrongLeaderBid);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+
+    h.place_bid(&carol, 40 * USDC).unwrap();
+    assert_eq!(h.escrow_balance(), 40 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn english_mode_is_locked_while_several_bids_are_escrowed() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_english_auction(h.agent.pubkey(), true)),
+        AuctionError::EnglishModeLocked,
+    );
+}
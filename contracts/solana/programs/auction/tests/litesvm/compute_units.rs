@@ -0,0 +1,85 @@
+//! Compute units of the hot-path instructions against the baseline in
+//! `tests/compute_units.json`. An instruction costing more than its baseline
+//! plus `threshold_bps` fails the test, so CU creep shows up in review.
+//!
+//! After an intended change, re-record with
+//! `UPDATE_CU_BASELINE=1 cargo test -p cartoonist-auction --test litesvm
+//! compute_units -- --ignored` and commit the JSON. Instructions missing from the
+//! baseline are reported and left for the next recording.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use litesvm::types::TransactionResult;
+use serde_json::{json, Value};
+use solana_sdk::signature::Signer;
+
+use crate::harness::*;
+use crate::ixs;
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compute_units.json")
+}
+
+fn units(result: TransactionResult) -> u64 {
+    result.unwrap().compute_units_consumed
+}
+
+/// One bidding round end to end, recording what each step costs.
+fn measure() -> BTreeMap<&'static str, u64> {
+    let mut h = Harness::bare();
+    let mut measured = BTreeMap::new();
+    h.warp(1_000);
+    let initialize = h.initialize_ix(h.mint, h.treasury, MINIMUM_BID);
+    measured.insert("initialize", units(h.as_agent(initialize)));
+
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    let dave = h.bidder(100 * USDC);
+    measured.insert("place_bid", units(h.place_bid(&alice, 20 * USDC)));
+    measured.insert("place_bid_taking_lead", units(h.place_bid(&bob, 30 * USDC)));
+    measured.insert("place_proxy_bid_taking_lead", units(h.place_proxy_bid(&carol, 50 * USDC)));
+    // Carol's proxy escalates against the raise.
+    measured.insert("increase_bid_escalating", units(h.increase_bid(&alice, 15 * USDC)));
+    measured.insert("decrease_bid", units(h.decrease_bid(&alice, 5 * USDC)));
+    h.place_bid(&dave, 20 * USDC).unwrap();
+    measured.insert("withdraw_bid", units(h.withdraw_bid(&dave)));
+
+    h.end_auction();
+    measured.insert("settle", units(h.settle(&carol.pubkey())));
+    let cranker = h.bidder(0);
+    let refund = ixs::crank_refund(h.mint, cranker.pubkey(), bob.pubkey(), h.usdc(&bob.pubkey()));
+    measured.insert("crank_refund", units(h.send(refund, &[&cranker])));
+    measured
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn compute_units_stay_within_the_baseline() {
+    let measured = measure();
+    let path = baseline_path();
+    let baseline: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let threshold_bps = baseline["threshold_bps"].as_u64().unwrap();
+
+    if std::env::var_os("UPDATE_CU_BASELINE").is_some() {
+        let updated = json!({ "threshold_bps": threshold_bps, "instructions": measured });
+        std::fs::write(&path, serde_json::to_string_pretty(&updated).unwrap() + "\n").unwrap();
+        return;
+    }
+
+    let mut regressions = Vec::new();
+    for (name, units) in &measured {
+        let Some(recorded) = baseline["instructions"][name].as_u64() else {
+            eprintln!("{name}: {units} CU, not in the baseline yet");
+            continue;
+        };
+        let allowed = recorded + recorded * threshold_bps / 10_000;
+        if *units > allowed {
+            regressions.push(format!("{name}: {units} CU, baseline {recorded} (allowed {allowed})"));
+        } else if *units < recorded {
+            eprintln!("{name}: {units} CU, down from {recorded}; consider re-recording");
+        }
+    }
+    assert!(regressions.is_empty(), "compute unit regressions:\n{}", regressions.join("\n"));
+}
@@ -0,0 +1,115 @@
+//! Content-hash commitment and its post-settlement reveal.
+
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::state::ContentReveal;
+use solana_sdk::signature::Signer;
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+const URI: &str = "ar://cartoon-round-1";
+
+/// Commits to `URI`, then settles to a single bidder.
+fn settled_round() -> (Harness, [u8; 32]) {
+    let mut h = Harness::new();
+    let content_hash = ContentReveal::hash_uri(URI);
+    h.as_agent(admin::commit_content(h.agent.pubkey(), content_hash))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    (h, content_hash)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn reveal_content_stores_the_committed_uri() {
+    let (mut h, content_hash) = settled_round();
+    h.as_agent(ixs::reveal_content(h.agent.pubkey(), &content_hash, URI))
+        .unwrap();
+
+    let reveal = h.content_reveal(&content_hash).unwrap();
+    assert_eq!(reveal.content_hash, content_hash);
+    assert_eq!(reveal.uri, URI);
+    assert_eq!(reveal.revealed_at, h.now());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn reveal_content_rejects_another_uri() {
+    let (mut h, content_hash) = settled_round();
+    let instruction = ixs::reveal_content(h.agent.pubkey(), &content_hash, "ar://swapped");
+    assert_auction_error(h.as_agent(instruction), AuctionError::ContentHashMismatch);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn reveal_content_only_after_bidding_closes() {
+    let mut h = Harness::new();
+    let content_hash = ContentReveal::hash_uri(URI);
+    let instruction = ixs::reveal_content(h.agent.pubkey(), &content_hash, URI);
+    assert_auction_error(h.as_agent(instruction.clone()), AuctionError::ContentNotCommitted);
+
+    h.as_agent(admin::commit_content(h.agent.pubkey(), content_hash))
+        .unwrap();
+    assert_auction_error(h.as_agent(instruction.clone()), AuctionError::AuctionNotEnded);
+
+    h.end_auction();
+    h.as_agent(instruction).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn commit_content_fails_after_end() {
+    let mut h = Harness::new();
+    h.end_auction();
+    let instruction = admin::commit_content(h.agent.pubkey(), ContentReveal::hash_uri(URI));
+    assert_auction_error(h.as_agent(instruction), AuctionError::AuctionEnded);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn commit_content_cannot_be_replaced() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(admin::commit_content(agent, [0; 32])),
+        AuctionError::InvalidContentHash,
+    );
+    h.as_agent(admin::commit_content(agent, ContentReveal::hash_uri(URI)))
+        .unwrap();
+    assert_auction_error(
+        h.as_agent(admin::commit_content(agent, ContentReveal::hash_uri("ar://other"))),
+        AuctionError::ContentAlreadyCommitted,
+    );
+
+    // Moving the deadline of an unsettled round keeps the commitment.
+    h.as_agent(admin::set_end_time(agent, 0)).unwrap();
+    assert_eq!(h.state().content_hash, ContentReveal::hash_uri(URI));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn commit_content_is_agent_only() {
+    let mut h = Harness::new();
+    let mallory = h.bidder(0);
+    let instruction = admin::commit_content(mallory.pubkey(), ContentReveal::hash_uri(URI));
+    assert_auction_error(h.send(instruction, &[&mallory]), AuctionError::OnlyAgent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn reopening_a_settled_round_clears_the_commitment() {
+    let (mut h, _) = settled_round();
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(admin::commit_content(agent, ContentReveal::hash_uri("ar://next"))),
+        AuctionError::AuctionSettled,
+    );
+
+    h.as_agent(admin::set_end_time(agent, 0)).unwrap();
+    assert_eq!(h.state().content_hash, [0; 32]);
+    h.as_agent(admin::commit_content(agent, ContentReveal::hash_uri("ar://next")))
+        .unwrap();
+}
@@ -0,0 +1,329 @@
+//! Wormhole bids and withdrawals against crafted core bridge `PostedVAA`
+//! accounts, relayed by a dedicated relayer wallet.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use anchor_spl::token::spl_token;
+use cartoonist_auction::error::AuctionError;
+use litesvm::types::TransactionResult;
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+
+const CORE_BRIDGE: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+const EMITTER_CHAIN: u16 = 2;
+const EMITTER: [u8; 32] = [7; 32];
+const PAYLOAD_BID: u8 = 1;
+const PAYLOAD_WITHDRAW: u8 = 2;
+
+struct Vaa {
+    owner: Pubkey,
+    emitter_chain: u16,
+    emitter: [u8; 32],
+    sequence: u64,
+    kind: u8,
+    auction_id: u64,
+    bidder: Pubkey,
+    amount: u64,
+}
+
+impl Vaa {
+    fn new(sequence: u64, kind: u8, bidder: Pubkey, amount: u64) -> Self {
+        Self {
+            owner: CORE_BRIDGE,
+            emitter_chain: EMITTER_CHAIN,
+            emitter: EMITTER,
+            sequence,
+            kind,
+            auction_id: 0,
+            bidder,
+            amount,
+        }
+    }
+
+    /// Writes the VAA as a `PostedVAA` account and returns its address.
+    fn post(&self, h: &mut Harness) -> Pubkey {
+        let mut payload = vec![self.kind];
+        payload.extend_from_slice(&self.auction_id.to_be_bytes());
+        payload.extend_from_slice(self.bidder.as_ref());
+        payload.extend_from_slice(&self.amount.to_be_bytes());
+
+        let mut data = b"vaa".to_vec();
+        (
+            1u8,
+            1u8,
+            0u32,
+            Pubkey::default(),
+            0u32,
+            0u32,
+            self.sequence,
+            self.emitter_chain,
+            self.emitter,
+            payload,
+        )
+            .serialize(&mut data)
+            .unwrap();
+
+        let address = Pubkey::new_unique();
+        let lamports = h.svm.minimum_balance_for_rent_exemption(data.len());
+        h.svm
+            .set_account(
+                address,
+                Account {
+                    lamports,
+                    data,
+                    owner: self.owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+        address
+    }
+}
+
+/// Replay marker of auction zero, whose seed is empty.
+fn received_pda(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"received",
+            &[],
+            &EMITTER_CHAIN.to_le_bytes(),
+            &EMITTER,
+            &sequence.to_le_bytes(),
+        ],
+        &cartoonist_auction::ID,
+    )
+    .0
+}
+
+fn set_wormhole_config(agent: Pubkey, relayer: Pubkey) -> Instruction {
+    ix(
+        cartoonist_auction::accounts::SetWormholeConfig {
+            auction_state: auction_state_pda(),
+            agent,
+            relayer,
+        },
+        cartoonist_auction::instruction::SetWormholeConfig {
+            emitter_chain: EMITTER_CHAIN,
+            emitter_address: EMITTER,
+        },
+    )
+}
+
+/// An auction trusting `EMITTER` with a relayer holding 1_000 USDC.
+fn bridged_auction() -> (Harness, Keypair) {
+    let mut h = Harness::new();
+    let relayer = h.bidder(1_000 * USDC);
+    h.as_agent(set_wormhole_config(h.agent.pubkey(), relayer.pubkey()))
+        .unwrap();
+    (h, relayer)
+}
+
+fn receive_bid(h: &mut Harness, relayer: &Keypair, posted_vaa: Pubkey, sequence: u64, bidder: Pubkey) -> TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::ReceiveCrossChainBid {
+            auction_state: auction_state_pda(),
+            posted_vaa,
+            received: received_pda(sequence),
+            bid: bid_pda(&bidder),
+            relayer_usdc: h.usdc(&relayer.pubkey()),
+            escrow: escrow_pda(),
+            usdc_mint: h.mint,
+            relayer: relayer.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            leader_bid: h.leader_bid_for(&bidder),
+        },
+        cartoonist_auction::instruction::ReceiveCrossChainBid { sequence, bidder },
+    );
+    h.send(instruction, &[relayer])
+}
+
+fn receive_withdrawal(h: &mut Harness, relayer: &Keypair, posted_vaa: Pubkey, sequence: u64, bidder: Pubkey) -> TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::ReceiveCrossChainWithdrawal {
+            auction_state: auction_state_pda(),
+            posted_vaa,
+            received: received_pda(sequence),
+            bid: bid_pda(&bidder),
+            relayer_usdc: h.usdc(&relayer.pubkey()),
+            escrow: escrow_pda(),
+            usdc_mint: h.mint,
+            relayer: relayer.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        cartoonist_auction::instruction::ReceiveCrossChainWithdrawal { sequence, bidder },
+    );
+    h.send(instruction, &[relayer])
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_wormhole_config_is_agent_only() {
+    let mut h = Harness::new();
+    let mallory = h.bidder(0);
+    assert_auction_error(
+        h.send(set_wormhole_config(mallory.pubkey(), mallory.pubkey()), &[&mallory]),
+        AuctionError::OnlyAgent,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_bid_escrows_for_the_foreign_bidder() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+    let vaa = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    receive_bid(&mut h, &relayer, vaa, 1, bidder).unwrap();
+
+    let bid = h.bid(&bidder).unwrap();
+    assert!(bid.active);
+    assert_eq!(bid.amount, 25 * USDC);
+    assert_eq!(bid.foreign_chain, EMITTER_CHAIN);
+    assert_eq!(h.escrow_balance(), 25 * USDC);
+    assert_eq!(h.balance(&h.usdc(&relayer.pubkey())), 975 * USDC);
+    assert_eq!(h.state().leader, bidder);
+
+    // A second VAA tops the bid up.
+    let top_up = Vaa::new(2, PAYLOAD_BID, bidder, 5 * USDC).post(&mut h);
+    receive_bid(&mut h, &relayer, top_up, 2, bidder).unwrap();
+    assert_eq!(h.bid(&bidder).unwrap().amount, 30 * USDC);
+    assert_eq!(h.state().active_bid_count, 1);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_bid_rejects_replays() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+    let vaa = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    receive_bid(&mut h, &relayer, vaa, 1, bidder).unwrap();
+    assert!(receive_bid(&mut h, &relayer, vaa, 1, bidder).is_err());
+    assert_eq!(h.escrow_balance(), 25 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_bid_is_relayer_only() {
+    let (mut h, _) = bridged_auction();
+    let impostor = h.bidder(100 * USDC);
+    let bidder = Pubkey::new_unique();
+    let vaa = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &impostor, vaa, 1, bidder),
+        AuctionError::OnlyRelayer,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_bid_rejects_unknown_emitters() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+    let vaa = Vaa {
+        emitter: [8; 32],
+        ..Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC)
+    }
+    .post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &relayer, vaa, 1, bidder),
+        AuctionError::UnknownEmitter,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_bid_rejects_forged_vaas() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+
+    let forged = Vaa {
+        owner: Pubkey::new_unique(),
+        ..Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC)
+    }
+    .post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &relayer, forged, 1, bidder),
+        AuctionError::InvalidVaa,
+    );
+
+    let vaa = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &relayer, vaa, 2, bidder),
+        AuctionError::InvalidVaa,
+    );
+
+    let withdrawal = Vaa::new(3, PAYLOAD_WITHDRAW, bidder, 0).post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &relayer, withdrawal, 3, bidder),
+        AuctionError::InvalidVaa,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn cross_chain_messages_are_for_one_auction_only() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+    let elsewhere = Vaa {
+        auction_id: 1,
+        ..Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC)
+    }
+    .post(&mut h);
+    assert_auction_error(
+        receive_bid(&mut h, &relayer, elsewhere, 1, bidder),
+        AuctionError::InvalidVaa,
+    );
+
+    let vaa = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    receive_bid(&mut h, &relayer, vaa, 1, bidder).unwrap();
+    let withdrawal = Vaa {
+        auction_id: 1,
+        ..Vaa::new(2, PAYLOAD_WITHDRAW, bidder, 0)
+    }
+    .post(&mut h);
+    assert_auction_error(
+        receive_withdrawal(&mut h, &relayer, withdrawal, 2, bidder),
+        AuctionError::InvalidVaa,
+    );
+    assert!(h.bid(&bidder).unwrap().active);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_withdrawal_refunds_the_relayer() {
+    let (mut h, relayer) = bridged_auction();
+    let bidder = Pubkey::new_unique();
+    let bid = Vaa::new(1, PAYLOAD_BID, bidder, 25 * USDC).post(&mut h);
+    receive_bid(&mut h, &relayer, bid, 1, bidder).unwrap();
+
+    let withdrawal = Vaa::new(2, PAYLOAD_WITHDRAW, bidder, 0).post(&mut h);
+    receive_withdrawal(&mut h, &relayer, withdrawal, 2, bidder).unwrap();
+
+    assert!(h.bid(&bidder).is_none());
+    assert_eq!(h.balance(&h.usdc(&relayer.pubkey())), 1_000 * USDC);
+    assert_eq!(h.escrow_balance(), 0);
+    let state = h.state();
+    assert_eq!(state.active_bid_count, 0);
+    assert_eq!(state.total_escrowed, 0);
+    assert_eq!(state.leader, Pubkey::default());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn receive_cross_chain_withdrawal_ignores_local_bids() {
+    let (mut h, relayer) = bridged_auction();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let withdrawal = Vaa::new(1, PAYLOAD_WITHDRAW, alice.pubkey(), 0).post(&mut h);
+    assert_auction_error(
+        receive_withdrawal(&mut h, &relayer, withdrawal, 1, alice.pubkey()),
+        AuctionError::WrongBidder,
+    );
+}
@@ -0,0 +1,177 @@
+//! Deposit-based bids: a 20% deposit up front, the rest due after the deadline.
+
+use cartoonist_auction::error::AuctionError;
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+const DAY: i64 = 86_400;
+
+fn pay(h: &mut Harness, bidder: &Keypair) -> TransactionResult {
+    let instruction = ixs::complete_payment(h.mint, bidder.pubkey(), h.usdc(&bidder.pubkey()));
+    h.send(instruction, &[bidder])
+}
+
+fn forfeit(h: &mut Harness, bidder: &Keypair) -> TransactionResult {
+    let cranker = h.bidder(0);
+    let bidder = bidder.pubkey();
+    let instruction =
+        ixs::forfeit_deposit(cranker.pubkey(), h.mint, h.treasury, bidder, h.usdc(&bidder));
+    h.send(instruction, &[&cranker])
+}
+
+/// 20% deposits with a one-day window, forfeited in full on default; Alice
+/// bids 100 and Bob 50.
+fn deposit_round() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_deposit_terms(h.agent.pubkey(), 2_000, DAY))
+        .unwrap();
+    h.as_agent(admin::set_default_penalty(h.agent.pubkey(), 10_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 100 * USDC).unwrap();
+    h.place_bid(&bob, 50 * USDC).unwrap();
+    (h, alice, bob)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn bids_escrow_only_the_deposit() {
+    let (h, alice, _) = deposit_round();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert_eq!(bid.amount, 100 * USDC);
+    assert_eq!(bid.balance_due, 80 * USDC);
+    assert_eq!(h.escrow_balance(), 30 * USDC);
+    assert_eq!(h.state().total_escrowed, 30 * USDC);
+    assert_eq!(h.state().leader, alice.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn winner_pays_the_balance_then_settles() {
+    let (mut h, alice, bob) = deposit_round();
+    assert_auction_error(pay(&mut h, &alice), AuctionError::AuctionNotEnded);
+    h.end_auction();
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::BalanceDue);
+    assert_auction_error(pay(&mut h, &bob), AuctionError::WinnersMismatch);
+
+    pay(&mut h, &alice).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().balance_due, 0);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 0);
+    assert_auction_error(pay(&mut h, &alice), AuctionError::NoBalanceDue);
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 100 * USDC);
+    assert_eq!(h.escrow_balance(), 10 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn unpaid_deposit_is_forfeited_and_the_next_bidder_gets_the_slot() {
+    let (mut h, alice, bob) = deposit_round();
+    h.end_auction();
+    assert_auction_error(forfeit(&mut h, &alice), AuctionError::PaymentWindowOpen);
+
+    let deadline = h.state().payment_deadline();
+    h.warp(deadline + 1);
+    assert_auction_error(pay(&mut h, &alice), AuctionError::PaymentWindowClosed);
+    forfeit(&mut h, &alice).unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+    let state = h.state();
+    assert_eq!(state.leader, bob.pubkey());
+    assert_eq!(state.payment_deadline(), deadline + 1 + DAY);
+    assert!(!h.bid(&alice.pubkey()).unwrap().active);
+
+    pay(&mut h, &bob).unwrap();
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 70 * USDC);
+    h.as_agent(ixs::assert_solvent()).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn decrease_forgives_the_balance_before_returning_escrow() {
+    let (mut h, alice, _) = deposit_round();
+    h.decrease_bid(&alice, 30 * USDC).unwrap();
+    let bid = h.bid(&alice.pubkey()).unwrap();
+    assert_eq!(bid.amount, 70 * USDC);
+    assert_eq!(bid.balance_due, 50 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 80 * USDC);
+
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn increase_escrows_the_deposit_share() {
+    let (mut h, _, bob) = deposit_round();
+    h.increase_bid(&bob, 100 * USDC).unwrap();
+    let bid = h.bid(&bob.pubkey()).unwrap();
+    assert_eq!(bid.amount, 150 * USDC);
+    assert_eq!(bid.balance_due, 120 * USDC);
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 70 * USDC);
+    assert_eq!(h.state().leader, bob.pubkey());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_deposit_terms_validates() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(admin::set_deposit_terms(agent, 10_001, DAY)),
+        AuctionError::InvalidBps,
+    );
+    assert_auction_error(
+        h.as_agent(admin::set_deposit_terms(agent, 2_000, 0)),
+        AuctionError::InvalidPaymentWindow,
+    );
+    h.as_agent(admin::set_deposit_terms(agent, 0, 0)).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_winner_who_never_claims_is_refunded_less_the_penalty() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_claim_window(h.agent.pubkey(), DAY))
+        .unwrap();
+    h.as_agent(admin::set_default_penalty(h.agent.pubkey(), 1_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 50 * USDC).unwrap();
+    h.place_bid(&bob, 40 * USDC).unwrap();
+    h.end_auction();
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::WinNotClaimed);
+    let result = h.send(ixs::claim_win(bob.pubkey()), &[&bob]);
+    assert_auction_error(result, AuctionError::WinnersMismatch);
+
+    let promote = |h: &Harness, bidder: &Keypair| {
+        let bidder = bidder.pubkey();
+        ixs::promote_next_bidder(cranker.pubkey(), h.mint, h.treasury, bidder, h.usdc(&bidder))
+    };
+    let result = h.send(promote(&h, &alice), &[&cranker]);
+    assert_auction_error(result, AuctionError::ClaimWindowOpen);
+
+    let deadline = h.state().claim_deadline();
+    h.warp(deadline + 1);
+    let result = h.send(ixs::claim_win(alice.pubkey()), &[&alice]);
+    assert_auction_error(result, AuctionError::ClaimWindowClosed);
+    h.send(promote(&h, &alice), &[&cranker]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 95 * USDC);
+    assert_eq!(h.balance(&h.treasury), 5 * USDC);
+    let state = h.state();
+    assert_eq!(state.leader, bob.pubkey());
+    assert_eq!(state.claim_deadline(), deadline + 1 + DAY);
+
+    h.send(ixs::claim_win(bob.pubkey()), &[&bob]).unwrap();
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 45 * USDC);
+    h.as_agent(ixs::assert_solvent()).unwrap();
+}
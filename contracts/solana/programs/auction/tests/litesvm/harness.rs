@@ -0,0 +1,551 @@
+//! Test environment: the compiled program loaded into LiteSVM, a 6-decimal
+//! mint, and an initialized auction. Token accounts are written directly as
+//! packed SPL state rather than created through instructions.
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Mint2022;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::state::{
+    bid_index_seed, AcceptedMints, AuctionState, AuditLog, Bid, Config, ContentReveal, Lot, PriceFeed,
+    RoundResult, WinReceipt,
+};
+use cartoonist_auction::USDC_DECIMALS;
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use crate::ixs::{self, admin, place_bid_accounts, PayoutAccounts, PrizeAccounts};
+
+pub const USDC: u64 = 1_000_000;
+pub const MINIMUM_BID: u64 = 10 * USDC;
+
+pub fn auction_state_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"auction_state"], &cartoonist_auction::ID).0
+}
+
+pub fn escrow_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow"], &cartoonist_auction::ID).0
+}
+
+pub fn bid_pda(bidder: &Pubkey) -> Pubkey {
+    bid_pda_at(bidder, 0)
+}
+
+pub fn bid_pda_at(bidder: &Pubkey, bid_index: u16) -> Pubkey {
+    let index = bid_index_seed(bid_index);
+    Pubkey::find_program_address(&[b"bid", bidder.as_ref(), &index], &cartoonist_auction::ID).0
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: cartoonist_auction::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn program_path() -> PathBuf {
+    std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"))
+        .join("cartoonist_auction.so")
+}
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub agent: Keypair,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    /// Wallets made by [`Harness::bidder`], for finding every active bid.
+    pub bidders: Vec<Pubkey>,
+}
+
+impl Harness {
+    /// Loads the program and creates the mint and treasury, without
+    /// initializing the auction. Panics when the program has not been built.
+    pub fn bare() -> Self {
+        let path = program_path();
+        let program = std::fs::read(&path).unwrap_or_else(|_| {
+            panic!("{} not found: run `anchor build` first", path.display())
+        });
+        let mut svm = LiteSVM::new();
+        svm.add_program(cartoonist_auction::ID, &program).unwrap();
+
+        let agent = Keypair::new();
+        svm.airdrop(&agent.pubkey(), 100_000_000_000).unwrap();
+        let mut harness = Self {
+            svm,
+            agent,
+            mint: Pubkey::new_unique(),
+            treasury: Pubkey::default(),
+            bidders: Vec::new(),
+        };
+        harness.set_mint(harness.mint, USDC_DECIMALS);
+        harness.treasury = harness.token_account(&harness.agent.pubkey(), 0);
+        harness
+    }
+
+    /// A fresh auction with `MINIMUM_BID`, open-ended, at unix time 1_000.
+    pub fn new() -> Self {
+        let mut harness = Self::bare();
+        harness.warp(1_000);
+        let initialize = harness.initialize_ix(harness.mint, harness.treasury, MINIMUM_BID);
+        harness.as_agent(initialize).unwrap();
+        harness
+    }
+
+    pub fn initialize_ix(&self, mint: Pubkey, treasury: Pubkey, minimum_bid: u64) -> Instruction {
+        ix(
+            cartoonist_auction::accounts::Initialize {
+                auction_state: auction_state_pda(),
+                usdc_mint: mint,
+                treasury,
+                escrow: escrow_pda(),
+                agent: self.agent.pubkey(),
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            cartoonist_auction::instruction::Initialize {
+                minimum_bid,
+                mint_overrides: 0,
+            },
+        )
+    }
+
+    // ---- transactions ----
+
+    /// Sends `instruction` paid for by the first signer. The blockhash is
+    /// expired first so identical retries are not rejected as duplicates.
+    pub fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> TransactionResult {
+        self.send_all(&[instruction], signers)
+    }
+
+    pub fn send_all(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TransactionResult {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx)
+    }
+
+    pub fn as_agent(&mut self, instruction: Instruction) -> TransactionResult {
+        let agent = self.agent.insecure_clone();
+        self.send(instruction, &[&agent])
+    }
+
+    // ---- common flows ----
+
+    /// The leader's bid account, if someone other than `bidder` leads.
+    pub fn leader_bid_for(&self, bidder: &Pubkey) -> Option<Pubkey> {
+        let state = self.state();
+        (state.leader != Pubkey::default() && state.leader != *bidder).then(|| bid_pda_at(&state.leader, state.leader_index))
+    }
+
+    /// The leader's token account, which English mode refunds when `bidder`
+    /// takes the lead.
+    pub fn leader_usdc_for(&self, bidder: &Pubkey) -> Option<Pubkey> {
+        let state = self.state();
+        self.leader_bid_for(bidder)
+            .filter(|_| state.english_auction)
+            .map(|_| self.usdc(&state.leader))
+    }
+
+    pub fn place_bid(&mut self, bidder: &Keypair, amount: u64) -> TransactionResult {
+        self.place_referred_bid(bidder, amount, None)
+    }
+
+    pub fn place_referred_bid(
+        &mut self,
+        bidder: &Keypair,
+        amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> TransactionResult {
+        let mut accounts = place_bid_accounts(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        accounts.leader_usdc = self.leader_usdc_for(&bidder.pubkey());
+        self.send(
+            ix(accounts, cartoonist_auction::instruction::PlaceBid { bid_index: 0, amount, referrer }),
+            &[bidder],
+        )
+    }
+
+    pub fn upsert_bid(&mut self, bidder: &Keypair, amount: u64) -> TransactionResult {
+        let mut accounts = place_bid_accounts(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        accounts.leader_usdc = self.leader_usdc_for(&bidder.pubkey());
+        self.send(
+            ix(accounts, cartoonist_auction::instruction::UpsertBid { bid_index: 0, amount }),
+            &[bidder],
+        )
+    }
+
+    pub fn place_proxy_bid(&mut self, bidder: &Keypair, max_amount: u64) -> TransactionResult {
+        let mut accounts = place_bid_accounts(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        accounts.leader_usdc = self.leader_usdc_for(&bidder.pubkey());
+        self.send(
+            ix(
+                accounts,
+                cartoonist_auction::instruction::PlaceProxyBid {
+                    bid_index: 0,
+                    max_amount,
+                    referrer: None,
+                },
+            ),
+            &[bidder],
+        )
+    }
+
+    pub fn update_bid(&mut self, bidder: &Keypair, amount_change: i64) -> TransactionResult {
+        let instruction = ixs::update_bid(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            amount_change,
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        self.send(instruction, &[bidder])
+    }
+
+    pub fn increase_bid(&mut self, bidder: &Keypair, amount: u64) -> TransactionResult {
+        let instruction = ixs::increase_bid(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            amount,
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        self.send(instruction, &[bidder])
+    }
+
+    pub fn decrease_bid(&mut self, bidder: &Keypair, amount: u64) -> TransactionResult {
+        let instruction = ixs::decrease_bid(
+            self.mint,
+            bidder.pubkey(),
+            self.usdc(&bidder.pubkey()),
+            amount,
+            self.leader_bid_for(&bidder.pubkey()),
+        );
+        self.send(instruction, &[bidder])
+    }
+
+    pub fn withdraw_bid(&mut self, bidder: &Keypair) -> TransactionResult {
+        let instruction = ixs::withdraw_bid(self.mint, bidder.pubkey(), self.usdc(&bidder.pubkey()));
+        self.send(instruction, &[bidder])
+    }
+
+    /// Payout accounts for `winner`: their token account only when there is
+    /// surplus to refund, the charity, curator and referrer only when a share is owed, and
+    /// their associated prize account only when a prize is escrowed. Bids in
+    /// an accepted mint are paid from that mint's escrow to its treasury.
+    pub fn payout(&self, winner: &Pubkey) -> PayoutAccounts {
+        let state = self.state();
+        let bid = self.bid(winner).expect("winner has a bid");
+        let (escrow, treasury, accepted_mints) = match self.accepted_mints() {
+            Some(accepted) if bid.mint != self.mint => {
+                let entry = accepted.get(&bid.mint).expect("accepted mint");
+                (entry.escrow, entry.treasury, Some(ixs::accepted_mints_pda()))
+            }
+            _ => (escrow_pda(), state.treasury, None),
+        };
+        PayoutAccounts {
+            mint: bid.mint,
+            escrow,
+            treasury,
+            winner_usdc: (bid.max_amount > bid.amount || bid.fee_discount_bps > 0)
+                .then(|| get_associated_token_address(winner, &bid.mint)),
+            charity: (state.charity_bps > 0).then_some(state.charity),
+            curator_usdc: (state.curator_bps > 0)
+                .then(|| get_associated_token_address(&state.curator, &bid.mint)),
+            referrer_usdc: (state.referral_bps > 0 && bid.referrer != Pubkey::default())
+                .then(|| get_associated_token_address(&bid.referrer, &bid.mint)),
+            prize: (state.prize_amount > 0).then(|| PrizeAccounts {
+                mint: state.prize_mint,
+                winner_prize: get_associated_token_address(winner, &state.prize_mint),
+            }),
+            accepted_mints,
+            rival_bids: if state.raffle_drawn { Vec::new() } else { self.rival_bids(winner) },
+            round_result: ixs::round_result_pda(state.total_rounds_settled + 1),
+            win_receipt: ixs::win_receipt_pda(state.total_rounds_settled + 1, winner),
+            memo_program: (state.memo_len > 0).then_some(anchor_spl::memo::ID),
+        }
+    }
+
+    /// Active bids of every harness bidder other than `winner`.
+    pub fn rival_bids(&self, winner: &Pubkey) -> Vec<Pubkey> {
+        self.bidders
+            .iter()
+            .filter(|bidder| *bidder != winner && self.bid(bidder).is_some_and(|bid| bid.active))
+            .map(bid_pda)
+            .collect()
+    }
+
+    pub fn settle(&mut self, winner: &Pubkey) -> TransactionResult {
+        let instruction = ixs::settle(self.agent.pubkey(), bid_pda(winner), &self.payout(winner));
+        self.as_agent(instruction)
+    }
+
+    /// Sets the end time to now, closing bidding.
+    pub fn end_auction(&mut self) {
+        let end_time = self.now();
+        let instruction = admin::set_end_time(self.agent.pubkey(), end_time);
+        self.as_agent(instruction).unwrap();
+    }
+
+    // ---- accounts ----
+
+    pub fn state(&self) -> AuctionState {
+        self.anchor_account(&auction_state_pda()).expect("auction not initialized")
+    }
+
+    pub fn bid(&self, bidder: &Pubkey) -> Option<Bid> {
+        self.bid_at(bidder, 0)
+    }
+
+    pub fn bid_at(&self, bidder: &Pubkey, bid_index: u16) -> Option<Bid> {
+        self.anchor_account(&bid_pda_at(bidder, bid_index))
+    }
+
+    pub fn content_reveal(&self, content_hash: &[u8; 32]) -> Option<ContentReveal> {
+        self.anchor_account(&ixs::content_reveal_pda(content_hash))
+    }
+
+    pub fn accepted_mints(&self) -> Option<AcceptedMints> {
+        self.anchor_account(&ixs::accepted_mints_pda())
+    }
+
+    pub fn lot(&self, lot_id: u16) -> Option<Lot> {
+        self.anchor_account(&ixs::lot_pda(lot_id))
+    }
+
+    pub fn round_result(&self, round: u64) -> Option<RoundResult> {
+        self.anchor_account(&ixs::round_result_pda(round))
+    }
+
+    pub fn win_receipt(&self, round: u64, winner: &Pubkey) -> Option<WinReceipt> {
+        self.anchor_account(&ixs::win_receipt_pda(round, winner))
+    }
+
+    pub fn audit_log(&self) -> Option<AuditLog> {
+        self.anchor_account(&ixs::admin::audit_log_pda())
+    }
+
+    pub fn price_feed(&self) -> Option<PriceFeed> {
+        self.anchor_account(&ixs::price_feed_pda())
+    }
+
+    pub fn config(&self) -> Option<Config> {
+        self.anchor_account(&ixs::config_pda())
+    }
+
+    pub fn anchor_account<T: AccountDeserialize>(&self, address: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(address).filter(|a| !a.data.is_empty())?;
+        T::try_deserialize(&mut account.data.as_slice()).ok()
+    }
+
+    pub fn balance(&self, token_account: &Pubkey) -> u64 {
+        let account = self.svm.get_account(token_account).expect("token account");
+        TokenAccount::unpack(&account.data).unwrap().amount
+    }
+
+    /// The token account's delegate, if it has one.
+    pub fn delegate(&self, token_account: &Pubkey) -> Option<Pubkey> {
+        let account = self.svm.get_account(token_account).expect("token account");
+        TokenAccount::unpack(&account.data).unwrap().delegate.into()
+    }
+
+    pub fn escrow_balance(&self) -> u64 {
+        self.balance(&escrow_pda())
+    }
+
+    pub fn set_mint(&mut self, address: Pubkey, decimals: u8) {
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            mint_authority: COption::Some(self.agent.pubkey()),
+            supply: u64::MAX / 2,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        self.set_program_account(address, data, spl_token::ID);
+    }
+
+    /// Writes a 6-decimal Token-2022 mint at `address` with room for
+    /// `extensions`, which `init` initializes.
+    pub fn set_mint_2022(
+        &mut self,
+        address: Pubkey,
+        extensions: &[ExtensionType],
+        init: impl FnOnce(&mut StateWithExtensionsMut<Mint2022>),
+    ) {
+        let len = ExtensionType::try_calculate_account_len::<Mint2022>(extensions).unwrap();
+        let mut data = vec![0; len];
+        {
+            let mut state = StateWithExtensionsMut::<Mint2022>::unpack_uninitialized(&mut data)
+                .unwrap();
+            init(&mut state);
+            state.base = Mint2022 {
+                mint_authority: COption::Some(self.agent.pubkey()),
+                supply: u64::MAX / 2,
+                decimals: USDC_DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            };
+            state.pack_base();
+            state.init_account_type().unwrap();
+        }
+        self.set_program_account(address, data, token_2022::ID);
+    }
+
+    /// Writes a Token-2022 account without extensions for `owner` at `address`.
+    pub fn set_token_2022_account(&mut self, address: Pubkey, mint: Pubkey, owner: &Pubkey) {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint,
+            owner: *owner,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_program_account(address, data, token_2022::ID);
+    }
+
+    /// Writes an SPL token account for `owner` at `address`.
+    pub fn set_token_account(&mut self, address: Pubkey, mint: Pubkey, owner: &Pubkey, amount: u64) {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_program_account(address, data, spl_token::ID);
+    }
+
+    /// The owner's associated token account for the auction mint, holding `amount`.
+    pub fn token_account(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let address = get_associated_token_address(owner, &self.mint);
+        self.set_token_account(address, self.mint, owner, amount);
+        address
+    }
+
+    pub fn usdc(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+
+    /// Credits `amount` to an existing token account without touching the
+    /// program's bookkeeping, like a direct transfer would.
+    pub fn deposit(&mut self, address: &Pubkey, amount: u64) {
+        let mut account = self.svm.get_account(address).unwrap();
+        let mut token = TokenAccount::unpack(&account.data).unwrap();
+        token.amount += amount;
+        token.pack_into_slice(&mut account.data);
+        self.svm.set_account(*address, account).unwrap();
+    }
+
+    /// Removes an account, as if its owner had closed it.
+    pub fn close_account(&mut self, address: &Pubkey) {
+        self.svm.set_account(*address, Account::default()).unwrap();
+    }
+
+    fn set_program_account(&mut self, address: Pubkey, data: Vec<u8>, owner: Pubkey) {
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                address,
+                Account {
+                    lamports,
+                    data,
+                    owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    /// A funded wallet with `usdc` in its associated token account.
+    pub fn bidder(&mut self, usdc: u64) -> Keypair {
+        let bidder = Keypair::new();
+        self.svm.airdrop(&bidder.pubkey(), 10_000_000_000).unwrap();
+        self.token_account(&bidder.pubkey(), usdc);
+        self.bidders.push(bidder.pubkey());
+        bidder
+    }
+
+    // ---- clock ----
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        self.svm.warp_to_slot(slot);
+    }
+}
+
+fn custom_code(result: TransactionResult) -> u32 {
+    match result.expect_err("transaction should have failed").err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("expected a custom program error, got {other:?}"),
+    }
+}
+
+pub fn assert_auction_error(result: TransactionResult, expected: AuctionError) {
+    assert_eq!(custom_code(result), u32::from(expected), "expected {expected:?}");
+}
+
+pub fn assert_anchor_error(result: TransactionResult, expected: anchor_lang::error::ErrorCode) {
+    assert_eq!(custom_code(result), u32::from(expected), "expected {expected:?}");
+}
+
+/// Every `T` the program emitted in a transaction's `logs`.
+pub fn events<T: Discriminator + AnchorDeserialize>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|bytes| T::deserialize(&mut bytes.strip_prefix(T::DISCRIMINATOR)?).ok())
+        .collect()
+}
@@ -0,0 +1,1486 @@
+//! Instruction builders with the usual accounts filled in. Adversarial tests
+//! build the Anchor `accounts` structs directly instead.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use cartoonist_auction::state::{AuctionConfig, ConfigUpdate};
+use cartoonist_auction::{accounts, instruction, scheduler};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+use crate::harness::{auction_state_pda, bid_pda, escrow_pda, ix};
+
+pub fn place_bid_accounts(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    leader_bid: Option<Pubkey>,
+) -> accounts::PlaceBid {
+    accounts::PlaceBid {
+        auction_state: auction_state_pda(),
+        bid: bid_pda(&bidder),
+        bidder_usdc,
+        escrow: escrow_pda(),
+        usdc_mint: mint,
+        bidder,
+        payer: bidder,
+        token_program: spl_token::ID,
+        system_program: anchor_lang::system_program::ID,
+        leader_bid,
+        accepted_mints: None,
+        receipt_mint: None,
+        bidder_receipt: None,
+        leader_usdc: None,
+        denylist: None,
+        config: None,
+        bidder_discount: None,
+    }
+}
+
+pub fn update_bid_accounts(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    leader_bid: Option<Pubkey>,
+) -> accounts::UpdateBid {
+    accounts::UpdateBid {
+        auction_state: auction_state_pda(),
+        bid: bid_pda(&bidder),
+        bidder_usdc,
+        escrow: escrow_pda(),
+        usdc_mint: mint,
+        bidder,
+        token_program: spl_token::ID,
+        leader_bid,
+        accepted_mints: None,
+        denylist: None,
+        session: None,
+    }
+}
+
+/// `update_bid_accounts` checks the bidder's signature in the handler, so
+/// the generated metas leave it unsigned.
+pub fn signed_by(mut instruction: Instruction, signer: Pubkey) -> Instruction {
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == signer {
+            meta.is_signer = true;
+        }
+    }
+    instruction
+}
+
+pub fn update_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount_change: i64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = ix(
+        update_bid_accounts(mint, bidder, bidder_usdc, leader_bid),
+        instruction::UpdateBid { amount_change },
+    );
+    signed_by(instruction, bidder)
+}
+
+pub fn increase_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = ix(
+        update_bid_accounts(mint, bidder, bidder_usdc, leader_bid),
+        instruction::IncreaseBid { amount },
+    );
+    signed_by(instruction, bidder)
+}
+
+pub fn decrease_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let instruction = ix(
+        update_bid_accounts(mint, bidder, bidder_usdc, leader_bid),
+        instruction::DecreaseBid { amount },
+    );
+    signed_by(instruction, bidder)
+}
+
+pub fn set_session_key(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    session_key: Pubkey,
+    expires_at: i64,
+    spend_cap: u64,
+) -> Instruction {
+    ix(
+        accounts::SetSessionKey {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            usdc_mint: mint,
+            bidder,
+            token_program: spl_token::ID,
+        },
+        instruction::SetSessionKey { session_key, expires_at, spend_cap },
+    )
+}
+
+pub fn place_sealed_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    deposit: u64,
+    commitment: [u8; 32],
+) -> Instruction {
+    ix(
+        place_bid_accounts(mint, bidder, bidder_usdc, None),
+        instruction::PlaceSealedBid { bid_index: 0, deposit, commitment },
+    )
+}
+
+pub fn reveal_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    amount: u64,
+    salt: [u8; 32],
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    ix(
+        accounts::RevealBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            token_program: spl_token::ID,
+            leader_bid,
+        },
+        instruction::RevealBid { amount, salt },
+    )
+}
+
+pub fn sweep_commit_bonds(mint: Pubkey, treasury: Pubkey, cranker: Pubkey) -> Instruction {
+    ix(
+        accounts::SweepCommitBonds {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+        },
+        instruction::SweepCommitBonds {},
+    )
+}
+
+/// `increase_bid` (or, with a negative change, `update_bid`) signed by
+/// `session` in place of the bidder.
+pub fn session_update_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    session: Pubkey,
+    bidder_usdc: Pubkey,
+    amount_change: i64,
+    leader_bid: Option<Pubkey>,
+) -> Instruction {
+    let accounts = accounts::UpdateBid {
+        session: Some(session),
+        ..update_bid_accounts(mint, bidder, bidder_usdc, leader_bid)
+    };
+    ix(accounts, instruction::UpdateBid { amount_change })
+}
+
+pub fn withdraw_bid(mint: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    withdraw_receipted_bid(mint, bidder, bidder_usdc, None)
+}
+
+pub fn withdraw_receipted_bid(
+    mint: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    bidder_receipt: Option<Pubkey>,
+) -> Instruction {
+    ix(
+        accounts::WithdrawBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            payer: bidder,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            receipt_mint: bidder_receipt.map(|_| receipt_mint_pda()),
+            bidder_receipt,
+            memo_program: None,
+        },
+        instruction::WithdrawBid {},
+    )
+}
+
+pub fn complete_payment(mint: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    ix(
+        accounts::CompletePayment {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            token_program: spl_token::ID,
+        },
+        instruction::CompletePayment {},
+    )
+}
+
+pub fn forfeit_deposit(
+    cranker: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    ix(
+        accounts::ForfeitDeposit {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ForfeitDeposit {},
+    )
+}
+
+pub fn mark_abandoned(cranker: Pubkey) -> Instruction {
+    ix(
+        accounts::MarkAbandoned {
+            auction_state: auction_state_pda(),
+            cranker,
+        },
+        instruction::MarkAbandoned {},
+    )
+}
+
+pub fn transfer_bid(bidder: Pubkey, new_owner: Pubkey) -> Instruction {
+    ix(
+        accounts::TransferBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            new_bid: bid_pda(&new_owner),
+            bidder,
+            payer: bidder,
+            new_owner,
+            system_program: anchor_lang::system_program::ID,
+            denylist: None,
+        },
+        instruction::TransferBid {},
+    )
+}
+
+pub fn close_bid(bidder: Pubkey, session_usdc: Option<Pubkey>) -> Instruction {
+    ix(
+        accounts::CloseBid {
+            bid: bid_pda(&bidder),
+            bidder,
+            session_usdc,
+            token_program: session_usdc.map(|_| spl_token::ID),
+        },
+        instruction::CloseBid {},
+    )
+}
+
+pub fn gc_bid(cranker: Pubkey, bids: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut instruction = ix(accounts::GcBid { cranker }, instruction::GcBid {});
+    for (bid, bidder) in bids {
+        instruction.accounts.push(AccountMeta::new(*bid, false));
+        instruction.accounts.push(AccountMeta::new(*bidder, false));
+    }
+    instruction
+}
+
+pub struct PayoutAccounts {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub treasury: Pubkey,
+    pub winner_usdc: Option<Pubkey>,
+    pub charity: Option<Pubkey>,
+    /// The curator's associated token account, when a share is configured.
+    pub curator_usdc: Option<Pubkey>,
+    /// The winning bid's referrer's associated token account, when one is owed.
+    pub referrer_usdc: Option<Pubkey>,
+    pub prize: Option<PrizeAccounts>,
+    /// Set when the winning bid is in an accepted mint.
+    pub accepted_mints: Option<Pubkey>,
+    /// Every other active bid; `settle` checks none outbids the winner.
+    pub rival_bids: Vec<Pubkey>,
+    /// Archive of the round being settled.
+    pub round_result: Pubkey,
+    /// The single winner's receipt; multi-winner settlement ignores it.
+    pub win_receipt: Pubkey,
+    /// Set once the auction has a memo.
+    pub memo_program: Option<Pubkey>,
+}
+
+/// Escrowed prize and the winner's token account for it, under SPL Token.
+#[derive(Clone, Copy)]
+pub struct PrizeAccounts {
+    pub mint: Pubkey,
+    pub winner_prize: Pubkey,
+}
+
+/// `RoundResult` of auction zero's `round`.
+pub fn round_result_pda(round: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"round_result", &round.to_le_bytes()], &cartoonist_auction::ID).0
+}
+
+/// `winner`'s `WinReceipt` for auction zero's `round`.
+pub fn win_receipt_pda(round: u64, winner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"win_receipt", &round.to_le_bytes(), winner.as_ref()],
+        &cartoonist_auction::ID,
+    )
+    .0
+}
+
+/// Auction zero's `PriceFeed`.
+pub fn price_feed_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"price_feed"], &cartoonist_auction::ID).0
+}
+
+pub fn prize_vault_pda(prize_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"prize_vault", prize_mint.as_ref()], &cartoonist_auction::ID).0
+}
+
+pub fn settle(agent: Pubkey, winning_bid: Pubkey, payout: &PayoutAccounts) -> Instruction {
+    let mut instruction = ix(
+        accounts::Settle {
+            auction_state: auction_state_pda(),
+            winning_bid,
+            escrow: payout.escrow,
+            treasury: payout.treasury,
+            usdc_mint: payout.mint,
+            authority: agent,
+            token_program: spl_token::ID,
+            winner_usdc: payout.winner_usdc,
+            charity: payout.charity,
+            curator_usdc: payout.curator_usdc,
+            referrer_usdc: payout.referrer_usdc,
+            prize_vault: payout.prize.map(|p| prize_vault_pda(&p.mint)),
+            prize_mint: payout.prize.map(|p| p.mint),
+            winner_prize: payout.prize.map(|p| p.winner_prize),
+            prize_token_program: payout.prize.map(|_| spl_token::ID),
+            accepted_mints: payout.accepted_mints,
+            round_result: payout.round_result,
+            win_receipt: payout.win_receipt,
+            price_feed: price_feed_pda(),
+            system_program: anchor_lang::system_program::ID,
+            memo_program: payout.memo_program,
+        },
+        instruction::Settle {},
+    );
+    let mut rivals = payout.rival_bids.clone();
+    rivals.sort();
+    instruction
+        .accounts
+        .extend(rivals.into_iter().map(|bid| AccountMeta::new_readonly(bid, false)));
+    instruction
+}
+
+pub fn crank_settle(cranker: Pubkey, winning_bid: Pubkey, payout: &PayoutAccounts) -> Instruction {
+    let mut instruction = ix(
+        accounts::CrankSettle {
+            auction_state: auction_state_pda(),
+            winning_bid,
+            escrow: payout.escrow,
+            treasury: payout.treasury,
+            usdc_mint: payout.mint,
+            cranker,
+            token_program: spl_token::ID,
+            winner_usdc: payout.winner_usdc,
+            charity: payout.charity,
+            curator_usdc: payout.curator_usdc,
+            referrer_usdc: payout.referrer_usdc,
+            prize_vault: payout.prize.map(|p| prize_vault_pda(&p.mint)),
+            prize_mint: payout.prize.map(|p| p.mint),
+            winner_prize: payout.prize.map(|p| p.winner_prize),
+            prize_token_program: payout.prize.map(|_| spl_token::ID),
+            accepted_mints: payout.accepted_mints,
+            round_result: payout.round_result,
+            win_receipt: payout.win_receipt,
+            price_feed: price_feed_pda(),
+            system_program: anchor_lang::system_program::ID,
+            memo_program: payout.memo_program,
+        },
+        instruction::CrankSettle {},
+    );
+    let mut rivals = payout.rival_bids.clone();
+    rivals.sort();
+    instruction
+        .accounts
+        .extend(rivals.into_iter().map(|bid| AccountMeta::new_readonly(bid, false)));
+    instruction
+}
+
+pub fn plan_settlement(winning_bid: Option<Pubkey>, usdc_mint: Option<Pubkey>, prize_mint: Option<Pubkey>) -> Instruction {
+    ix(
+        accounts::PlanSettlement { auction_state: auction_state_pda(), winning_bid, usdc_mint, prize_mint },
+        instruction::PlanSettlement {},
+    )
+}
+
+pub fn schedule_settlement(agent: Pubkey, thread: Pubkey, amount: u64) -> Instruction {
+    ix(
+        accounts::ScheduleSettlement {
+            auction_state: auction_state_pda(),
+            agent,
+            thread,
+            thread_program: scheduler::THREAD_PROGRAM_ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ScheduleSettlement { amount },
+    )
+}
+
+pub fn settle_multi(
+    agent: Pubkey,
+    payout: &PayoutAccounts,
+    winners: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut instruction = ix(
+        accounts::SettleMulti {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury: payout.treasury,
+            usdc_mint: payout.mint,
+            authority: agent,
+            token_program: spl_token::ID,
+            charity: payout.charity,
+            curator_usdc: payout.curator_usdc,
+            referrer_usdc: payout.referrer_usdc,
+            round_result: payout.round_result,
+            price_feed: price_feed_pda(),
+            system_program: anchor_lang::system_program::ID,
+            memo_program: payout.memo_program,
+        },
+        instruction::SettleMulti {},
+    );
+    for (bid, winner_usdc) in winners {
+        instruction
+            .accounts
+            .push(anchor_lang::solana_program::instruction::AccountMeta::new(*bid, false));
+        instruction
+            .accounts
+            .push(anchor_lang::solana_program::instruction::AccountMeta::new(*winner_usdc, false));
+    }
+    instruction
+}
+
+pub fn settle_top_k(agent: Pubkey, payout: &PayoutAccounts, winners: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut instruction = ix(
+        accounts::SettleTopK {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury: payout.treasury,
+            usdc_mint: payout.mint,
+            authority: agent,
+            token_program: spl_token::ID,
+            charity: payout.charity,
+            curator_usdc: payout.curator_usdc,
+            referrer_usdc: payout.referrer_usdc,
+            round_result: payout.round_result,
+            price_feed: price_feed_pda(),
+            system_program: anchor_lang::system_program::ID,
+            memo_program: payout.memo_program,
+        },
+        instruction::SettleTopK { count: winners.len() as u8 },
+    );
+    for (bid, winner_usdc) in winners {
+        instruction.accounts.push(AccountMeta::new(*bid, false));
+        instruction.accounts.push(AccountMeta::new(*winner_usdc, false));
+    }
+    instruction
+}
+
+/// Buys out the first round.
+pub fn buy_now(
+    mint: Pubkey,
+    treasury: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    charity: Option<Pubkey>,
+) -> Instruction {
+    ix(
+        accounts::BuyNow {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            bidder,
+            payer: bidder,
+            token_program: spl_token::ID,
+            charity,
+            prize_vault: None,
+            prize_mint: None,
+            winner_prize: None,
+            prize_token_program: None,
+            curator_usdc: None,
+            referrer_usdc: None,
+            round_result: round_result_pda(1),
+            win_receipt: win_receipt_pda(1, &bidder),
+            price_feed: price_feed_pda(),
+            system_program: anchor_lang::system_program::ID,
+            memo_program: None,
+        },
+        instruction::BuyNow {},
+    )
+}
+
+pub fn crank_refund(mint: Pubkey, cranker: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    ix(
+        accounts::CrankRefund {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            memo_program: None,
+        },
+        instruction::CrankRefund {},
+    )
+}
+
+pub fn set_standing(bidder: Pubkey, standing: bool) -> Instruction {
+    ix(
+        accounts::SetStanding {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+        },
+        instruction::SetStanding { standing },
+    )
+}
+
+pub fn link_bid(bid: Pubkey, prev_bid: Option<Pubkey>, next_bid: Option<Pubkey>) -> Instruction {
+    ix(
+        accounts::LinkBid {
+            auction_state: auction_state_pda(),
+            bid,
+            prev_bid,
+            next_bid,
+        },
+        instruction::LinkBid {},
+    )
+}
+
+pub fn unlink_bid(bidder: Pubkey, prev_bid: Option<Pubkey>, next_bid: Option<Pubkey>) -> Instruction {
+    ix(
+        accounts::UnlinkBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            prev_bid,
+            next_bid,
+            bidder,
+        },
+        instruction::UnlinkBid {},
+    )
+}
+
+pub fn collect_all_pay_bid(mint: Pubkey, treasury: Pubkey, cranker: Pubkey, bidder: Pubkey) -> Instruction {
+    ix(
+        accounts::CollectAllPayBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+        },
+        instruction::CollectAllPayBid {},
+    )
+}
+
+pub fn slash_bid(
+    mint: Pubkey,
+    moderator: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    treasury: Pubkey,
+    treasury_owner: Pubkey,
+) -> Instruction {
+    ix(
+        accounts::SlashBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            treasury,
+            treasury_owner,
+            moderator,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::SlashBid {},
+    )
+}
+
+pub fn expire_bid(mint: Pubkey, cranker: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    ix(
+        accounts::ExpireBid {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ExpireBid {},
+    )
+}
+
+pub fn refund_claims_pda(root: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"refund_claims", root], &cartoonist_auction::ID).0
+}
+
+pub fn post_refund_root(agent: Pubkey, root: [u8; 32], leaf_count: u32) -> Instruction {
+    ix(
+        accounts::PostRefundRoot {
+            auction_state: auction_state_pda(),
+            refund_claims: refund_claims_pda(&root),
+            authority: agent,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::PostRefundRoot { root, leaf_count },
+    )
+}
+
+pub fn deposit_prize(agent: Pubkey, prize_mint: Pubkey, agent_prize: Pubkey, amount: u64) -> Instruction {
+    ix(
+        accounts::DepositPrize {
+            auction_state: auction_state_pda(),
+            prize_mint,
+            prize_vault: prize_vault_pda(&prize_mint),
+            agent_prize,
+            agent,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::DepositPrize { amount },
+    )
+}
+
+pub fn withdraw_prize(agent: Pubkey, prize_mint: Pubkey, agent_prize: Pubkey) -> Instruction {
+    ix(
+        accounts::WithdrawPrize {
+            auction_state: auction_state_pda(),
+            prize_mint,
+            prize_vault: prize_vault_pda(&prize_mint),
+            agent_prize,
+            agent,
+            token_program: spl_token::ID,
+        },
+        instruction::WithdrawPrize {},
+    )
+}
+
+pub fn content_reveal_pda(content_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"content", content_hash], &cartoonist_auction::ID).0
+}
+
+pub fn reveal_content(agent: Pubkey, content_hash: &[u8; 32], uri: &str) -> Instruction {
+    ix(
+        accounts::RevealContent {
+            auction_state: auction_state_pda(),
+            content_reveal: content_reveal_pda(content_hash),
+            agent,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::RevealContent { uri: uri.to_string() },
+    )
+}
+
+pub fn claim_refund(
+    mint: Pubkey,
+    root: &[u8; 32],
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    ix(
+        accounts::ClaimRefund {
+            auction_state: auction_state_pda(),
+            refund_claims: refund_claims_pda(root),
+            bid: bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            payer: bidder,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            memo_program: None,
+        },
+        instruction::ClaimRefund {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+pub fn sweep_penalties(agent: Pubkey, mint: Pubkey, treasury: Pubkey) -> Instruction {
+    ix(
+        accounts::SweepPenalties {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            agent,
+            token_program: spl_token::ID,
+        },
+        instruction::SweepPenalties {},
+    )
+}
+
+pub fn sweep_surplus(agent: Pubkey, mint: Pubkey, treasury: Pubkey) -> Instruction {
+    ix(
+        accounts::SweepSurplus {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            agent,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+        },
+        instruction::SweepSurplus {},
+    )
+}
+
+/// Escrow the `generation`-th `migrate_escrow` of auction zero creates.
+pub fn migrated_escrow_pda(mint: &Pubkey, generation: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", mint.as_ref(), &[generation]], &cartoonist_auction::ID).0
+}
+
+pub fn migrate_escrow(
+    agent: Pubkey,
+    escrow: Pubkey,
+    mint: Pubkey,
+    new_mint: Pubkey,
+    new_treasury: Pubkey,
+    generation: u8,
+) -> Instruction {
+    ix(
+        accounts::MigrateEscrow {
+            auction_state: auction_state_pda(),
+            escrow,
+            usdc_mint: mint,
+            token_program: spl_token::ID,
+            new_mint,
+            new_escrow: migrated_escrow_pda(&new_mint, generation),
+            new_treasury,
+            agent,
+            new_token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::MigrateEscrow { mint_overrides: 0 },
+    )
+}
+
+pub fn rescue_tokens(agent: Pubkey, source: Pubkey, destination: Pubkey, mint: Pubkey, amount: u64) -> Instruction {
+    ix(
+        accounts::RescueTokens {
+            auction_state: auction_state_pda(),
+            source,
+            destination,
+            mint,
+            accepted_mints: accepted_mints_pda(),
+            agent,
+            token_program: spl_token::ID,
+        },
+        instruction::RescueTokens { amount },
+    )
+}
+
+pub fn denylist_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"denylist"], &cartoonist_auction::ID).0
+}
+
+pub fn set_denylisted(agent: Pubkey, wallet: Pubkey, denied: bool) -> Instruction {
+    ix(
+        accounts::SetDenylisted {
+            auction_state: auction_state_pda(),
+            denylist: denylist_pda(),
+            agent,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::SetDenylisted { wallet, denied },
+    )
+}
+
+pub fn force_refund(agent: Pubkey, mint: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    ix(
+        accounts::ForceRefund {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            denylist: denylist_pda(),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            agent,
+            token_program: spl_token::ID,
+            accepted_mints: None,
+            prev_bid: None,
+            next_bid: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ForceRefund {},
+    )
+}
+
+pub fn claim_settled_funds(cranker: Pubkey, mint: Pubkey, treasury: Pubkey) -> Instruction {
+    ix(
+        accounts::ClaimSettledFunds {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+        },
+        instruction::ClaimSettledFunds {},
+    )
+}
+
+pub fn open_dispute(winner: Pubkey) -> Instruction {
+    ix(
+        accounts::OpenDispute {
+            auction_state: auction_state_pda(),
+            winner,
+        },
+        instruction::OpenDispute {},
+    )
+}
+
+pub fn resolve_settlement(
+    authority: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    winner_usdc: Option<Pubkey>,
+    refund: bool,
+) -> Instruction {
+    ix(
+        accounts::ResolveSettlement {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            authority,
+            token_program: spl_token::ID,
+            winner_usdc,
+            charity: None,
+            curator_usdc: None,
+            referrer_usdc: None,
+            memo_program: None,
+        },
+        instruction::ResolveSettlement { refund },
+    )
+}
+
+pub fn claim_win(bidder: Pubkey) -> Instruction {
+    ix(
+        accounts::ClaimWin {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+        },
+        instruction::ClaimWin {},
+    )
+}
+
+pub fn promote_next_bidder(
+    cranker: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+) -> Instruction {
+    ix(
+        accounts::PromoteNextBidder {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            cranker,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::PromoteNextBidder {},
+    )
+}
+
+pub fn harvest_withheld_fees(agent: Pubkey, mint: Pubkey, token_program: Pubkey) -> Instruction {
+    ix(
+        accounts::HarvestWithheldFees {
+            auction_state: auction_state_pda(),
+            mint,
+            escrow: escrow_pda(),
+            agent,
+            token_program,
+            accepted_mints: None,
+            treasury: None,
+        },
+        instruction::HarvestWithheldFees {},
+    )
+}
+
+pub fn assert_solvent() -> Instruction {
+    ix(
+        accounts::AssertSolvent {
+            auction_state: auction_state_pda(),
+            escrow: escrow_pda(),
+            accepted_mints: None,
+        },
+        instruction::AssertSolvent {},
+    )
+}
+
+pub fn accepted_mints_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"accepted_mints"], &cartoonist_auction::ID).0
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &cartoonist_auction::ID).0
+}
+
+pub fn mint_escrow_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", mint.as_ref()], &cartoonist_auction::ID).0
+}
+
+pub fn add_accepted_mint(agent: Pubkey, mint: Pubkey, treasury: Pubkey) -> Instruction {
+    ix(
+        accounts::AddAcceptedMint {
+            auction_state: auction_state_pda(),
+            accepted_mints: accepted_mints_pda(),
+            mint,
+            escrow: mint_escrow_pda(&mint),
+            treasury,
+            agent,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::AddAcceptedMint {},
+    )
+}
+
+pub fn remove_accepted_mint(agent: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        accounts::RemoveAcceptedMint {
+            auction_state: auction_state_pda(),
+            accepted_mints: accepted_mints_pda(),
+            agent,
+        },
+        instruction::RemoveAcceptedMint { mint },
+    )
+}
+
+pub fn receipt_mint_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"receipt_mint"], &cartoonist_auction::ID).0
+}
+
+pub fn create_receipt_mint(agent: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        accounts::CreateReceiptMint {
+            auction_state: auction_state_pda(),
+            usdc_mint: mint,
+            receipt_mint: receipt_mint_pda(),
+            agent,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::CreateReceiptMint {},
+    )
+}
+
+pub fn points_mint_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"points_mint"], &cartoonist_auction::ID).0
+}
+
+pub fn create_points_mint(agent: Pubkey) -> Instruction {
+    ix(
+        accounts::CreatePointsMint {
+            auction_state: auction_state_pda(),
+            points_mint: points_mint_pda(),
+            agent,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::CreatePointsMint {},
+    )
+}
+
+pub fn set_points_rate(agent: Pubkey, points_rate: u64) -> Instruction {
+    ix(
+        accounts::SetPointsRate {
+            auction_state: auction_state_pda(),
+            points_mint: points_mint_pda(),
+            agent,
+        },
+        instruction::SetPointsRate { points_rate },
+    )
+}
+
+/// Mints `bidder`'s points to their associated token account, created by `payer`.
+pub fn claim_points(payer: Pubkey, bidder: Pubkey) -> Instruction {
+    ix(
+        accounts::ClaimPoints {
+            auction_state: auction_state_pda(),
+            bid: bid_pda(&bidder),
+            bidder,
+            bidder_points: get_associated_token_address(&bidder, &points_mint_pda()),
+            points_mint: points_mint_pda(),
+            payer,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ClaimPoints {},
+    )
+}
+
+pub fn operator_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"operator"], &cartoonist_auction::ID).0
+}
+
+/// `(auction_state, escrow)` of an auction from `create_auction`.
+pub fn auction_pdas(auction_id: u64) -> (Pubkey, Pubkey) {
+    let seed = auction_id.to_le_bytes();
+    let find = |prefix: &[u8]| Pubkey::find_program_address(&[prefix, &seed], &cartoonist_auction::ID).0;
+    (find(b"auction_state"), find(b"escrow"))
+}
+
+pub fn initialize_operator(agent: Pubkey) -> Instruction {
+    ix(
+        accounts::InitializeOperator {
+            operator: operator_pda(),
+            agent,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::InitializeOperator {},
+    )
+}
+
+pub fn registry_page_pda(index: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"registry", &index.to_le_bytes()], &cartoonist_auction::ID).0
+}
+
+/// Creates auction `auction_id`, listed on registry page `page`.
+pub fn create_auction(agent: Pubkey, auction_id: u64, page: u32, mint: Pubkey, treasury: Pubkey) -> Instruction {
+    let (auction_state, escrow) = auction_pdas(auction_id);
+    ix(
+        accounts::CreateAuction {
+            operator: operator_pda(),
+            auction_state,
+            usdc_mint: mint,
+            treasury,
+            escrow,
+            registry_page: registry_page_pda(page),
+            agent,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::CreateAuction {
+            auction_id,
+            config: AuctionConfig {
+                minimum_bid: 2 * crate::harness::MINIMUM_BID,
+                mint_overrides: 0,
+                item_metadata: Pubkey::default(),
+                start_time: 0,
+            },
+        },
+    )
+}
+
+pub fn sync_registry(auction_id: u64, page: u32) -> Instruction {
+    ix(
+        accounts::SyncRegistry {
+            auction_state: auction_pdas(auction_id).0,
+            registry_page: registry_page_pda(page),
+        },
+        instruction::SyncRegistry {},
+    )
+}
+
+pub fn lot_pda(lot_id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[b"lot", &lot_id.to_le_bytes()], &cartoonist_auction::ID).0
+}
+
+pub fn lot_bid_pda(lot_id: u16, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"lot_bid", &lot_id.to_le_bytes(), bidder.as_ref()],
+        &cartoonist_auction::ID,
+    )
+    .0
+}
+
+pub fn create_lot(agent: Pubkey, lot_id: u16, minimum_bid: u64, reserve_price: u64) -> Instruction {
+    ix(
+        accounts::CreateLot {
+            auction_state: auction_state_pda(),
+            lot: lot_pda(lot_id),
+            agent,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::CreateLot {
+            minimum_bid,
+            reserve_price,
+            item_hash: [lot_id as u8; 32],
+        },
+    )
+}
+
+pub fn place_lot_bid(mint: Pubkey, lot_id: u16, bidder: Pubkey, bidder_usdc: Pubkey, amount: u64) -> Instruction {
+    ix(
+        accounts::PlaceLotBid {
+            auction_state: auction_state_pda(),
+            lot: lot_pda(lot_id),
+            lot_bid: lot_bid_pda(lot_id, &bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            payer: bidder,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            denylist: None,
+        },
+        instruction::PlaceLotBid { amount },
+    )
+}
+
+pub fn withdraw_lot_bid(mint: Pubkey, lot_id: u16, bidder: Pubkey, bidder_usdc: Pubkey) -> Instruction {
+    ix(
+        accounts::WithdrawLotBid {
+            auction_state: auction_state_pda(),
+            lot: lot_pda(lot_id),
+            lot_bid: lot_bid_pda(lot_id, &bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            token_program: spl_token::ID,
+        },
+        instruction::WithdrawLotBid {},
+    )
+}
+
+pub fn settle_lot(
+    authority: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    lot_id: u16,
+    leader: Option<Pubkey>,
+) -> Instruction {
+    ix(
+        accounts::SettleLot {
+            auction_state: auction_state_pda(),
+            lot: lot_pda(lot_id),
+            winning_bid: leader.map(|leader| lot_bid_pda(lot_id, &leader)),
+            winner: leader,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            authority,
+            token_program: spl_token::ID,
+        },
+        instruction::SettleLot {},
+    )
+}
+
+pub fn bundle_bid_pda(bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bundle_bid", bidder.as_ref()], &cartoonist_auction::ID).0
+}
+
+fn lot_metas(lots: &[u16]) -> impl Iterator<Item = AccountMeta> + '_ {
+    lots.iter().map(|lot_id| AccountMeta::new(lot_pda(*lot_id), false))
+}
+
+pub fn place_bundle_bid(mint: Pubkey, bidder: Pubkey, bidder_usdc: Pubkey, lots: &[u16], amount: u64) -> Instruction {
+    let mut instruction = ix(
+        accounts::PlaceBundleBid {
+            auction_state: auction_state_pda(),
+            bundle_bid: bundle_bid_pda(&bidder),
+            bidder_usdc,
+            escrow: escrow_pda(),
+            usdc_mint: mint,
+            bidder,
+            payer: bidder,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            denylist: None,
+        },
+        instruction::PlaceBundleBid {
+            lots: lots.to_vec(),
+            amount,
+        },
+    );
+    instruction.accounts.extend(lot_metas(lots));
+    instruction
+}
+
+pub fn settle_bundle(
+    authority: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    bidder: Pubkey,
+    bidder_usdc: Pubkey,
+    lots: &[u16],
+) -> Instruction {
+    let mut instruction = ix(
+        accounts::SettleBundle {
+            auction_state: auction_state_pda(),
+            bundle_bid: bundle_bid_pda(&bidder),
+            bidder,
+            bidder_usdc,
+            escrow: escrow_pda(),
+            treasury,
+            usdc_mint: mint,
+            authority,
+            token_program: spl_token::ID,
+        },
+        instruction::SettleBundle {},
+    );
+    instruction.accounts.extend(lot_metas(lots));
+    instruction
+}
+
+/// Agent-only setters that take no accounts besides the state and signer.
+pub mod admin {
+    use super::*;
+
+    macro_rules! setter {
+        ($name:ident, $accounts:ident, $ix:ident { $($arg:ident: $ty:ty),* }) => {
+            pub fn $name(agent: Pubkey $(, $arg: $ty)*) -> Instruction {
+                ix(
+                    accounts::$accounts {
+                        auction_state: auction_state_pda(),
+                        agent,
+                    },
+                    instruction::$ix { $($arg),* },
+                )
+            }
+        };
+    }
+
+    setter!(set_end_time, SetEndTime, SetEndTime { end_time: i64 });
+    setter!(set_withdrawal_penalty, SetWithdrawalPenalty, SetWithdrawalPenalty { penalty_bps: u16 });
+    setter!(set_bid_increment, SetBidIncrement, SetBidIncrement { bid_increment: u64 });
+    setter!(set_buy_now_price, SetBuyNowPrice, SetBuyNowPrice { buy_now_price: u64 });
+    setter!(set_num_winners, SetNumWinners, SetNumWinners { num_winners: u8 });
+    setter!(set_uniform_price, SetUniformPrice, SetUniformPrice { enabled: bool });
+    setter!(set_raffle_mode, SetRaffleMode, SetRaffleMode { enabled: bool });
+    setter!(commit_content, CommitContent, CommitContent { content_hash: [u8; 32] });
+    setter!(set_vesting_duration, SetVestingDuration, SetVestingDuration { vesting_duration: i64 });
+    setter!(set_transfer_hooks, SetTransferHooks, SetTransferHooks { enabled: bool });
+    setter!(set_settler, SetSettler, SetSettler { settler: Pubkey });
+    setter!(set_referral_bps, SetReferralBps, SetReferralBps { referral_bps: u16 });
+    setter!(set_bid_ttl, SetBidTtl, SetBidTtl { bid_ttl: i64 });
+    setter!(cancel_auction, CancelAuction, CancelAuction {});
+    setter!(close_auction, CloseAuction, CloseAuction {});
+    setter!(set_english_auction, SetEnglishAuction, SetEnglishAuction { enabled: bool });
+    setter!(set_candle_window, SetCandleWindow, SetCandleWindow { candle_window: i64 });
+    setter!(set_all_pay, SetAllPay, SetAllPay { enabled: bool });
+    setter!(
+        set_abandonment_window,
+        SetAbandonmentWindow,
+        SetAbandonmentWindow { abandonment_window: i64 }
+    );
+    setter!(
+        set_deposit_terms,
+        SetDepositTerms,
+        SetDepositTerms { deposit_bps: u16, payment_window: i64 }
+    );
+    setter!(
+        set_dispute_terms,
+        SetDisputeTerms,
+        SetDisputeTerms { arbiter: Pubkey, dispute_window: i64 }
+    );
+    setter!(set_claim_window, SetClaimWindow, SetClaimWindow { claim_window: i64 });
+    setter!(set_memo, SetMemo, SetMemo { memo: String });
+    setter!(
+        set_min_settle_slots,
+        SetMinSettleSlots,
+        SetMinSettleSlots { min_settle_slots: u64 }
+    );
+    setter!(
+        set_default_penalty,
+        SetDefaultPenalty,
+        SetDefaultPenalty { default_penalty_bps: u16 }
+    );
+    setter!(set_rebate_bps, SetRebateBps, SetRebateBps { rebate_bps: u16 });
+    setter!(set_reveal_window, SetRevealWindow, SetRevealWindow { reveal_window: i64 });
+    setter!(set_commit_bond, SetCommitBond, SetCommitBond { commit_bond: u64 });
+    setter!(set_paused, SetPaused, SetPaused { paused: bool });
+
+    /// Auction zero's `AuditLog`.
+    pub fn audit_log_pda() -> Pubkey {
+        Pubkey::find_program_address(&[b"audit_log"], &cartoonist_auction::ID).0
+    }
+
+    pub fn initialize_audit_log(agent: Pubkey) -> Instruction {
+        ix(
+            accounts::InitializeAuditLog {
+                auction_state: auction_state_pda(),
+                audit_log: audit_log_pda(),
+                agent,
+                system_program: anchor_lang::system_program::ID,
+            },
+            instruction::InitializeAuditLog {},
+        )
+    }
+
+    /// Passes the audit log to an audited instruction built without it.
+    pub fn audited(mut instruction: Instruction) -> Instruction {
+        let placeholder = instruction
+            .accounts
+            .iter_mut()
+            .find(|meta| meta.pubkey == cartoonist_auction::ID)
+            .expect("instruction takes an optional audit log");
+        *placeholder = AccountMeta::new(audit_log_pda(), false);
+        instruction
+    }
+
+    pub fn set_minimum_bid(agent: Pubkey, minimum_bid: u64) -> Instruction {
+        ix(
+            accounts::SetMinimumBid {
+                auction_state: auction_state_pda(),
+                agent,
+                audit_log: None,
+            },
+            instruction::SetMinimumBid { minimum_bid },
+        )
+    }
+
+    pub fn set_agent(agent: Pubkey, new_agent: Pubkey) -> Instruction {
+        ix(
+            accounts::SetAgent {
+                auction_state: auction_state_pda(),
+                agent,
+                new_agent,
+                audit_log: None,
+            },
+            instruction::SetAgent {},
+        )
+    }
+
+    pub fn set_curator(agent: Pubkey, curator: Pubkey, curator_bps: u16) -> Instruction {
+        ix(
+            accounts::SetCurator {
+                auction_state: auction_state_pda(),
+                agent,
+            },
+            instruction::SetCurator {
+                curator,
+                curator_bps,
+            },
+        )
+    }
+
+    pub fn set_moderator(agent: Pubkey, moderator: Pubkey, bid_bond: u64) -> Instruction {
+        ix(
+            accounts::SetModerator {
+                auction_state: auction_state_pda(),
+                agent,
+            },
+            instruction::SetModerator {
+                moderator,
+                bid_bond,
+            },
+        )
+    }
+
+    pub fn set_charity(agent: Pubkey, mint: Pubkey, charity: Option<Pubkey>, charity_bps: u16) -> Instruction {
+        ix(
+            accounts::SetCharity {
+                auction_state: auction_state_pda(),
+                usdc_mint: mint,
+                agent,
+                charity,
+            },
+            instruction::SetCharity { charity_bps },
+        )
+    }
+
+    pub fn set_treasury(agent: Pubkey, mint: Pubkey, new_treasury: Pubkey) -> Instruction {
+        ix(
+            accounts::SetTreasury {
+                auction_state: auction_state_pda(),
+                usdc_mint: mint,
+                new_treasury,
+                agent,
+                audit_log: None,
+            },
+            instruction::SetTreasury {},
+        )
+    }
+
+    pub fn update_config(agent: Pubkey, update: ConfigUpdate) -> Instruction {
+        ix(
+            accounts::UpdateConfig {
+                auction_state: auction_state_pda(),
+                config: config_pda(),
+                agent,
+                system_program: anchor_lang::system_program::ID,
+                audit_log: None,
+            },
+            instruction::UpdateConfig { update },
+        )
+    }
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::error::AuctionError;
+use solana_sdk::signature::Signer;
+
+use crate::harness::*;
+use crate::ixs;
+
+#[test]
+#[ignore = "needs anchor build"]
+fn lots_take_bids_and_settle_independently() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(ixs::create_lot(agent, 1, 10 * USDC, 0)).unwrap();
+    h.as_agent(ixs::create_lot(agent, 2, 5 * USDC, 50 * USDC)).unwrap();
+    assert_eq!(h.state().lot_count, 2);
+
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    let bid = |h: &Harness, lot_id, bidder: &Pubkey, amount| {
+        ixs::place_lot_bid(h.mint, lot_id, *bidder, h.usdc(bidder), amount)
+    };
+    let below_minimum = bid(&h, 1, &alice.pubkey(), 9 * USDC);
+    assert_auction_error(h.send(below_minimum, &[&alice]), AuctionError::BidTooLow);
+    let alice_bid = bid(&h, 1, &alice.pubkey(), 20 * USDC);
+    h.send(alice_bid, &[&alice]).unwrap();
+    let bob_bid = bid(&h, 1, &bob.pubkey(), 30 * USDC);
+    h.send(bob_bid, &[&bob]).unwrap();
+    let carol_bid = bid(&h, 2, &carol.pubkey(), 40 * USDC);
+    h.send(carol_bid, &[&carol]).unwrap();
+
+    let lot = h.lot(1).unwrap();
+    assert_eq!((lot.leader, lot.leading_amount), (bob.pubkey(), 30 * USDC));
+    // Lot bids stay out of the main item's ranking.
+    assert_eq!(h.state().active_bid_count, 0);
+    assert_eq!(h.escrow_balance(), 90 * USDC);
+
+    let withdraw = |h: &Harness, lot_id, bidder: &Pubkey| {
+        ixs::withdraw_lot_bid(h.mint, lot_id, *bidder, h.usdc(bidder))
+    };
+    let bob_withdraw = withdraw(&h, 1, &bob.pubkey());
+    assert_auction_error(h.send(bob_withdraw, &[&bob]), AuctionError::LotLeaderLocked);
+    let alice_withdraw = withdraw(&h, 1, &alice.pubkey());
+    h.send(alice_withdraw, &[&alice]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+
+    let early = ixs::settle_lot(agent, h.mint, h.treasury, 1, Some(bob.pubkey()));
+    assert_auction_error(h.as_agent(early), AuctionError::AuctionNotEnded);
+    h.end_auction();
+
+    let settle = ixs::settle_lot(agent, h.mint, h.treasury, 1, Some(bob.pubkey()));
+    h.as_agent(settle).unwrap();
+    let lot = h.lot(1).unwrap();
+    assert!(lot.settled);
+    assert_eq!(lot.winner, bob.pubkey());
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+    assert!(h.svm.get_account(&ixs::lot_bid_pda(1, &bob.pubkey())).is_none_or(|a| a.data.is_empty()));
+
+    // Lot 2 misses its reserve, so it closes unsold and its leader is freed.
+    let settle = ixs::settle_lot(agent, h.mint, h.treasury, 2, Some(carol.pubkey()));
+    h.as_agent(settle).unwrap();
+    let lot = h.lot(2).unwrap();
+    assert!(lot.settled && lot.winner == Pubkey::default());
+    let carol_withdraw = withdraw(&h, 2, &carol.pubkey());
+    h.send(carol_withdraw, &[&carol]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&carol.pubkey())), 100 * USDC);
+
+    let again = ixs::settle_lot(agent, h.mint, h.treasury, 1, None);
+    assert_auction_error(h.as_agent(again), AuctionError::LotSettled);
+    assert_eq!(h.escrow_balance(), 0);
+    assert_eq!(h.state().active_lot_bid_count, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_bundle_wins_only_by_beating_its_lot_leaders_combined() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    for lot_id in 1..=3 {
+        h.as_agent(ixs::create_lot(agent, lot_id, 10 * USDC, 0)).unwrap();
+    }
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let dave = h.bidder(100 * USDC);
+    let erin = h.bidder(100 * USDC);
+    for (lot_id, bidder, amount) in [(1, &alice, 30 * USDC), (2, &bob, 40 * USDC)] {
+        let bid = ixs::place_lot_bid(h.mint, lot_id, bidder.pubkey(), h.usdc(&bidder.pubkey()), amount);
+        h.send(bid, &[bidder]).unwrap();
+    }
+
+    let bundle = |h: &Harness, bidder: &Pubkey, lots: &[u16], amount| {
+        ixs::place_bundle_bid(h.mint, *bidder, h.usdc(bidder), lots, amount)
+    };
+    let unsorted = bundle(&h, &dave.pubkey(), &[2, 1], 70 * USDC);
+    assert_auction_error(h.send(unsorted, &[&dave]), AuctionError::InvalidBundle);
+    let too_low = bundle(&h, &dave.pubkey(), &[1, 2, 3], 25 * USDC);
+    assert_auction_error(h.send(too_low, &[&dave]), AuctionError::BidTooLow);
+    // A tie with the lot leaders' 70 is not enough; 80 is.
+    let dave_bundle = bundle(&h, &dave.pubkey(), &[1, 2], 70 * USDC);
+    h.send(dave_bundle, &[&dave]).unwrap();
+    let erin_bundle = bundle(&h, &erin.pubkey(), &[1, 2], 80 * USDC);
+    h.send(erin_bundle, &[&erin]).unwrap();
+    h.end_auction();
+
+    let blocked = ixs::settle_lot(agent, h.mint, h.treasury, 1, Some(alice.pubkey()));
+    assert_auction_error(h.as_agent(blocked), AuctionError::BundlePending);
+    let settle = |h: &Harness, bidder: &Pubkey| {
+        ixs::settle_bundle(agent, h.mint, h.treasury, *bidder, h.usdc(bidder), &[1, 2])
+    };
+    let settle_dave = settle(&h, &dave.pubkey());
+    h.as_agent(settle_dave).unwrap();
+    assert_eq!(h.balance(&h.usdc(&dave.pubkey())), 100 * USDC);
+    assert_eq!(h.balance(&h.treasury), 0);
+
+    let settle_erin = settle(&h, &erin.pubkey());
+    h.as_agent(settle_erin).unwrap();
+    assert_eq!(h.balance(&h.treasury), 80 * USDC);
+    for lot_id in [1, 2] {
+        let lot = h.lot(lot_id).unwrap();
+        assert!(lot.settled && lot.pending_bundles == 0);
+        assert_eq!(lot.winner, erin.pubkey());
+    }
+    // The displaced lot leaders get their escrow back.
+    for (lot_id, bidder) in [(1, &alice), (2, &bob)] {
+        let withdraw = ixs::withdraw_lot_bid(h.mint, lot_id, bidder.pubkey(), h.usdc(&bidder.pubkey()));
+        h.send(withdraw, &[bidder]).unwrap();
+        assert_eq!(h.balance(&h.usdc(&bidder.pubkey())), 100 * USDC);
+    }
+    assert_eq!(h.escrow_balance(), 0);
+
+    // Lot 3 was outside the bundle and settles on its own, unsold.
+    h.as_agent(ixs::settle_lot(agent, h.mint, h.treasury, 3, None)).unwrap();
+}
@@ -0,0 +1,24 @@
+//! LiteSVM tests for every instruction, success and error paths. They run
+//! against `target/deploy/cartoonist_auction.so` (or `$SBF_OUT_DIR`), so they
+//! are ignored by default: run `anchor build`, then
+//! `cargo test -p cartoonist-auction --test litesvm -- --ignored`.
+
+// `TransactionResult` carries the full failed-transaction metadata.
+#![allow(clippy::result_large_err)]
+
+mod harness;
+mod ixs;
+
+mod admin;
+mod bidding;
+mod compute_units;
+mod content;
+mod cross_chain;
+mod deposits;
+mod lots;
+mod mints;
+mod prize;
+mod randomness;
+mod refunds;
+mod settlement;
+mod vesting;
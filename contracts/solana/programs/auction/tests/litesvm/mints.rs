@@ -0,0 +1,211 @@
+//! Bidding in accepted stablecoins besides the auction mint.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::{accounts, instruction, USDC_DECIMALS};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs;
+
+/// Harness accepting a second stablecoin, paid out to its own treasury.
+fn with_second_mint() -> (Harness, Pubkey, Pubkey) {
+    let mut h = Harness::new();
+    let usdt = Pubkey::new_unique();
+    h.set_mint(usdt, USDC_DECIMALS);
+    let agent = h.agent.pubkey();
+    let treasury = get_associated_token_address(&agent, &usdt);
+    h.set_token_account(treasury, usdt, &agent, 0);
+    h.as_agent(ixs::add_accepted_mint(agent, usdt, treasury)).unwrap();
+    (h, usdt, treasury)
+}
+
+/// A wallet holding `amount` of `mint` in its associated token account.
+fn holder(h: &mut Harness, mint: Pubkey, amount: u64) -> Keypair {
+    let bidder = h.bidder(0);
+    let source = get_associated_token_address(&bidder.pubkey(), &mint);
+    h.set_token_account(source, mint, &bidder.pubkey(), amount);
+    bidder
+}
+
+fn bid_in(h: &mut Harness, mint: Pubkey, bidder: &Keypair, amount: u64) -> TransactionResult {
+    let accounts = accounts::PlaceBid {
+        escrow: ixs::mint_escrow_pda(&mint),
+        accepted_mints: Some(ixs::accepted_mints_pda()),
+        ..ixs::place_bid_accounts(
+            mint,
+            bidder.pubkey(),
+            get_associated_token_address(&bidder.pubkey(), &mint),
+            h.leader_bid_for(&bidder.pubkey()),
+        )
+    };
+    h.send(
+        ix(accounts, instruction::PlaceBid { bid_index: 0, amount, referrer: None }),
+        &[bidder],
+    )
+}
+
+fn withdraw_in(h: &mut Harness, mint: Pubkey, bidder: &Keypair) -> TransactionResult {
+    let accounts = accounts::WithdrawBid {
+        auction_state: auction_state_pda(),
+        bid: bid_pda(&bidder.pubkey()),
+        bidder_usdc: get_associated_token_address(&bidder.pubkey(), &mint),
+        escrow: ixs::mint_escrow_pda(&mint),
+        usdc_mint: mint,
+        bidder: bidder.pubkey(),
+        payer: bidder.pubkey(),
+        token_program: anchor_spl::token::ID,
+        accepted_mints: Some(ixs::accepted_mints_pda()),
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: anchor_lang::system_program::ID,
+        receipt_mint: None,
+        bidder_receipt: None,
+        memo_program: None,
+    };
+    h.send(ix(accounts, instruction::WithdrawBid {}), &[bidder])
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn add_accepted_mint_creates_its_escrow() {
+    let (h, usdt, treasury) = with_second_mint();
+    let accepted = h.accepted_mints().unwrap();
+    let entry = accepted.get(&usdt).unwrap();
+    assert_eq!(entry.escrow, ixs::mint_escrow_pda(&usdt));
+    assert_eq!(entry.treasury, treasury);
+    assert_eq!(entry.total_escrowed, 0);
+    assert_eq!(h.balance(&entry.escrow), 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn bids_in_an_accepted_mint_compete_one_to_one() {
+    let (mut h, usdt, _) = with_second_mint();
+    let alice = holder(&mut h, usdt, 100 * USDC);
+    let bob = h.bidder(100 * USDC);
+
+    bid_in(&mut h, usdt, &alice, 30 * USDC).unwrap();
+    h.place_bid(&bob, 20 * USDC).unwrap();
+
+    let state = h.state();
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.total_escrowed, 20 * USDC);
+    assert_eq!(h.escrow_balance(), 20 * USDC);
+    assert_eq!(h.balance(&ixs::mint_escrow_pda(&usdt)), 30 * USDC);
+    assert_eq!(
+        h.accepted_mints().unwrap().get(&usdt).unwrap().total_escrowed,
+        30 * USDC
+    );
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().mint, usdt);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_pays_the_accepted_mint_treasury() {
+    let (mut h, usdt, treasury) = with_second_mint();
+    let alice = holder(&mut h, usdt, 100 * USDC);
+    bid_in(&mut h, usdt, &alice, 30 * USDC).unwrap();
+    h.end_auction();
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&treasury), 30 * USDC);
+    assert_eq!(h.balance(&h.treasury), 0);
+    assert_eq!(
+        h.accepted_mints().unwrap().get(&usdt).unwrap().total_escrowed,
+        0
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_rejects_the_auction_escrow_for_an_accepted_mint_bid() {
+    let (mut h, usdt, _) = with_second_mint();
+    let alice = holder(&mut h, usdt, 100 * USDC);
+    bid_in(&mut h, usdt, &alice, 30 * USDC).unwrap();
+    h.end_auction();
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.escrow = escrow_pda();
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_anchor_error(
+        h.as_agent(instruction),
+        anchor_lang::error::ErrorCode::ConstraintTokenMint,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn bid_in_an_unaccepted_mint_fails() {
+    let mut h = Harness::new();
+    let other = Pubkey::new_unique();
+    h.set_mint(other, USDC_DECIMALS);
+    let alice = holder(&mut h, other, 100 * USDC);
+
+    let accounts = ixs::place_bid_accounts(
+        other,
+        alice.pubkey(),
+        get_associated_token_address(&alice.pubkey(), &other),
+        None,
+    );
+    let place = instruction::PlaceBid {
+        bid_index: 0,
+        amount: 20 * USDC,
+        referrer: None,
+    };
+    let result = h.send(ix(accounts, place), &[&alice]);
+    assert_auction_error(result, AuctionError::MintNotAccepted);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn add_accepted_mint_rejects_other_decimals() {
+    let mut h = Harness::new();
+    let other = Pubkey::new_unique();
+    h.set_mint(other, 9);
+    let agent = h.agent.pubkey();
+    let treasury = get_associated_token_address(&agent, &other);
+    h.set_token_account(treasury, other, &agent, 0);
+
+    let result = h.as_agent(ixs::add_accepted_mint(agent, other, treasury));
+    assert_auction_error(result, AuctionError::InvalidMintDecimals);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn add_accepted_mint_rejects_duplicates() {
+    let (mut h, usdt, treasury) = with_second_mint();
+    let result = h.as_agent(ixs::add_accepted_mint(h.agent.pubkey(), usdt, treasury));
+    assert_auction_error(result, AuctionError::MintAlreadyAccepted);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn add_accepted_mint_requires_the_agent() {
+    let mut h = Harness::new();
+    let usdt = Pubkey::new_unique();
+    h.set_mint(usdt, USDC_DECIMALS);
+    let mallory = h.bidder(0);
+    let treasury = get_associated_token_address(&mallory.pubkey(), &usdt);
+    h.set_token_account(treasury, usdt, &mallory.pubkey(), 0);
+
+    let instruction = ixs::add_accepted_mint(mallory.pubkey(), usdt, treasury);
+    assert_auction_error(h.send(instruction, &[&mallory]), AuctionError::OnlyAgent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn remove_accepted_mint_waits_for_its_escrow_to_empty() {
+    let (mut h, usdt, _) = with_second_mint();
+    let alice = holder(&mut h, usdt, 100 * USDC);
+    bid_in(&mut h, usdt, &alice, 30 * USDC).unwrap();
+
+    let result = h.as_agent(ixs::remove_accepted_mint(h.agent.pubkey(), usdt));
+    assert_auction_error(result, AuctionError::AcceptedMintInUse);
+
+    withdraw_in(&mut h, usdt, &alice).unwrap();
+    h.as_agent(ixs::remove_accepted_mint(h.agent.pubkey(), usdt))
+        .unwrap();
+    assert!(h.accepted_mints().unwrap().get(&usdt).is_none());
+}
@@ -0,0 +1,190 @@
+//! Prize escrow and the atomic swap at settlement.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use cartoonist_auction::error::AuctionError;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, round_result_pda, PayoutAccounts};
+
+/// Harness with a one-of-one prize mint and the agent holding the token.
+fn with_prize() -> (Harness, Pubkey, Pubkey) {
+    let mut h = Harness::new();
+    let prize_mint = Pubkey::new_unique();
+    h.set_mint(prize_mint, 0);
+    let agent = h.agent.pubkey();
+    let agent_prize = get_associated_token_address(&agent, &prize_mint);
+    h.set_token_account(agent_prize, prize_mint, &agent, 1);
+    (h, prize_mint, agent_prize)
+}
+
+fn deposit(h: &mut Harness, prize_mint: Pubkey, agent_prize: Pubkey) {
+    let instruction = ixs::deposit_prize(h.agent.pubkey(), prize_mint, agent_prize, 1);
+    h.as_agent(instruction).unwrap();
+}
+
+/// Alice bids 20 and the auction closes; her prize account starts empty.
+fn bid_and_end(h: &mut Harness, prize_mint: Pubkey) -> Keypair {
+    let alice = h.bidder(100 * USDC);
+    let alice_prize = get_associated_token_address(&alice.pubkey(), &prize_mint);
+    h.set_token_account(alice_prize, prize_mint, &alice.pubkey(), 0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    alice
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_swaps_the_bid_for_the_prize() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    assert_eq!(h.balance(&ixs::prize_vault_pda(&prize_mint)), 1);
+    assert_eq!(h.state().prize_mint, prize_mint);
+
+    let alice = bid_and_end(&mut h, prize_mint);
+    h.settle(&alice.pubkey()).unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+    assert_eq!(
+        h.balance(&get_associated_token_address(&alice.pubkey(), &prize_mint)),
+        1
+    );
+    assert_eq!(h.balance(&ixs::prize_vault_pda(&prize_mint)), 0);
+    let state = h.state();
+    assert_eq!(state.prize_amount, 0);
+    assert_eq!(state.prize_mint, Pubkey::default());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_settle_delivers_the_prize() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let alice = bid_and_end(&mut h, prize_mint);
+
+    let cranker = h.bidder(0);
+    let instruction = ixs::crank_settle(
+        cranker.pubkey(),
+        bid_pda(&alice.pubkey()),
+        &h.payout(&alice.pubkey()),
+    );
+    h.send(instruction, &[&cranker]).unwrap();
+    assert_eq!(
+        h.balance(&get_associated_token_address(&alice.pubkey(), &prize_mint)),
+        1
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_requires_the_prize_accounts() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let alice = bid_and_end(&mut h, prize_mint);
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.prize = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::PrizeAccountsRequired);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_rejects_a_prize_account_of_someone_else() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let alice = bid_and_end(&mut h, prize_mint);
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.prize.as_mut().unwrap().winner_prize = agent_prize;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidTokenAccount);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn deposit_prize_error_paths() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(ixs::deposit_prize(agent, prize_mint, agent_prize, 0)),
+        AuctionError::InvalidPrizeAmount,
+    );
+    let agent_usdc = h.token_account(&agent, 10 * USDC);
+    assert_auction_error(
+        h.as_agent(ixs::deposit_prize(agent, h.mint, agent_usdc, USDC)),
+        AuctionError::InvalidPrizeMint,
+    );
+
+    deposit(&mut h, prize_mint, agent_prize);
+    assert_auction_error(
+        h.as_agent(ixs::deposit_prize(agent, prize_mint, agent_prize, 1)),
+        AuctionError::PrizeAlreadyDeposited,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn deposit_prize_must_precede_bids() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let instruction = ixs::deposit_prize(h.agent.pubkey(), prize_mint, agent_prize, 1);
+    assert_auction_error(h.as_agent(instruction), AuctionError::PrizeLocked);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_prize_only_without_active_bids() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+
+    let instruction = ixs::withdraw_prize(h.agent.pubkey(), prize_mint, agent_prize);
+    assert_auction_error(h.as_agent(instruction.clone()), AuctionError::PrizeLocked);
+
+    h.withdraw_bid(&alice).unwrap();
+    h.as_agent(instruction.clone()).unwrap();
+    assert_eq!(h.balance(&agent_prize), 1);
+    assert_eq!(h.state().prize_amount, 0);
+    assert_auction_error(h.as_agent(instruction), AuctionError::NoPrize);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_multi_refuses_an_escrowed_prize() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let alice = bid_and_end(&mut h, prize_mint);
+
+    let payout = PayoutAccounts {
+        mint: h.mint,
+        escrow: escrow_pda(),
+        treasury: h.treasury,
+        winner_usdc: None,
+        charity: None,
+        curator_usdc: None,
+        referrer_usdc: None,
+        prize: None,
+        accepted_mints: None,
+        rival_bids: Vec::new(),
+        round_result: round_result_pda(1),
+        win_receipt: Pubkey::default(),
+        memo_program: None,
+    };
+    let winners = [(bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey()))];
+    let instruction = ixs::settle_multi(h.agent.pubkey(), &payout, &winners);
+    assert_auction_error(h.as_agent(instruction), AuctionError::PrizeRequiresSingleWinner);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn withdraw_prize_is_agent_only() {
+    let (mut h, prize_mint, agent_prize) = with_prize();
+    deposit(&mut h, prize_mint, agent_prize);
+    let mallory = h.bidder(0);
+    let instruction = ixs::withdraw_prize(mallory.pubkey(), prize_mint, agent_prize);
+    assert_auction_error(h.send(instruction, &[&mallory]), AuctionError::OnlyAgent);
+}
@@ -0,0 +1,482 @@
+//! Tie breaks and raffle draws against crafted Switchboard randomness
+//! accounts: committed one slot after their seed slot and revealed at
+//! `reveal_slot`.
+
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::error::AuctionError;
+use solana_sdk::account::Account;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::signature::{Keypair, Signer};
+use switchboard_on_demand::get_switchboard_on_demand_program_id;
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+const RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+const SEED_SLOT: u64 = 99;
+const COMMIT_SLOT: u64 = SEED_SLOT + 1;
+const REVEAL_SLOT: u64 = COMMIT_SLOT + 1;
+
+/// Writes a `RandomnessAccountData` seeded at `seed_slot` and revealing
+/// `value` (little-endian in the first 8 bytes) at `REVEAL_SLOT`.
+fn set_randomness(h: &mut Harness, owner: Pubkey, seed_slot: u64, value: u64) -> Pubkey {
+    let mut data = vec![0; 408];
+    data[..8].copy_from_slice(&RANDOMNESS_DISCRIMINATOR);
+    data[104..112].copy_from_slice(&seed_slot.to_le_bytes());
+    data[144..152].copy_from_slice(&REVEAL_SLOT.to_le_bytes());
+    data[152..160].copy_from_slice(&value.to_le_bytes());
+
+    let address = Pubkey::new_unique();
+    let lamports = h.svm.minimum_balance_for_rent_exemption(data.len());
+    h.svm
+        .set_account(
+            address,
+            Account {
+                lamports,
+                data,
+                owner,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+    address
+}
+
+fn randomness(h: &mut Harness, value: u64) -> Pubkey {
+    set_randomness(h, get_switchboard_on_demand_program_id(), SEED_SLOT, value)
+}
+
+fn request_tie_break(h: &mut Harness, randomness_account: Pubkey) -> litesvm::types::TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::RequestTieBreak {
+            auction_state: auction_state_pda(),
+            randomness_account,
+            agent: h.agent.pubkey(),
+        },
+        cartoonist_auction::instruction::RequestTieBreak {},
+    );
+    h.as_agent(instruction)
+}
+
+fn resolve_tie(h: &mut Harness, payer: &Keypair, randomness_account: Pubkey) -> litesvm::types::TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::ResolveTie {
+            auction_state: auction_state_pda(),
+            randomness_account,
+        },
+        cartoonist_auction::instruction::ResolveTie {},
+    );
+    h.send(instruction, &[payer])
+}
+
+fn request_raffle_draw(h: &mut Harness, randomness_account: Pubkey) -> litesvm::types::TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::RequestRaffleDraw {
+            auction_state: auction_state_pda(),
+            randomness_account,
+            agent: h.agent.pubkey(),
+        },
+        cartoonist_auction::instruction::RequestRaffleDraw {},
+    );
+    h.as_agent(instruction)
+}
+
+fn draw_raffle(
+    h: &mut Harness,
+    payer: &Keypair,
+    randomness_account: Pubkey,
+    entrants: &[Pubkey],
+) -> litesvm::types::TransactionResult {
+    let mut instruction = ix(
+        cartoonist_auction::accounts::DrawRaffle {
+            auction_state: auction_state_pda(),
+            randomness_account,
+        },
+        cartoonist_auction::instruction::DrawRaffle {},
+    );
+    instruction
+        .accounts
+        .extend(entrants.iter().map(|bid| AccountMeta::new_readonly(*bid, false)));
+    h.send(instruction, &[payer])
+}
+
+fn request_candle_end(h: &mut Harness, randomness_account: Pubkey) -> litesvm::types::TransactionResult {
+    let instruction = ix(
+        cartoonist_auction::accounts::RequestCandleEnd {
+            auction_state: auction_state_pda(),
+            randomness_account,
+            agent: h.agent.pubkey(),
+        },
+        cartoonist_auction::instruction::RequestCandleEnd {},
+    );
+    h.as_agent(instruction)
+}
+
+fn determine_candle_end(
+    h: &mut Harness,
+    payer: &Keypair,
+    randomness_account: Pubkey,
+    entrants: &[Pubkey],
+) -> litesvm::types::TransactionResult {
+    let mut instruction = ix(
+        cartoonist_auction::accounts::DetermineCandleEnd {
+            auction_state: auction_state_pda(),
+            randomness_account,
+        },
+        cartoonist_auction::instruction::DetermineCandleEnd {},
+    );
+    instruction
+        .accounts
+        .extend(entrants.iter().map(|bid| AccountMeta::new_readonly(*bid, false)));
+    h.send(instruction, &[payer])
+}
+
+/// Alice and Bob tied at 20 USDC, Alice ahead by arrival, once bidding
+/// has ended.
+fn tied_auction() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    (h, alice, bob)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_requires_a_tie() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(request_tie_break(&mut h, account), AuctionError::NoTie);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_ignores_equal_bids_placed_apart() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.warp(h.now() + 5);
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(request_tie_break(&mut h, account), AuctionError::NoTie);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_waits_for_bidding_to_end() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(request_tie_break(&mut h, account), AuctionError::AuctionNotEnded);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_rejects_foreign_randomness() {
+    let (mut h, _, _) = tied_auction();
+    let account = set_randomness(&mut h, Pubkey::new_unique(), SEED_SLOT, 0);
+    assert_auction_error(request_tie_break(&mut h, account), AuctionError::InvalidRandomness);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_rejects_stale_randomness() {
+    let (mut h, _, _) = tied_auction();
+    let account = set_randomness(
+        &mut h,
+        get_switchboard_on_demand_program_id(),
+        SEED_SLOT - 1,
+        0,
+    );
+    assert_auction_error(request_tie_break(&mut h, account), AuctionError::InvalidRandomness);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_tie_break_is_agent_only() {
+    let (mut h, alice, _) = tied_auction();
+    let account = randomness(&mut h, 0);
+    let instruction = ix(
+        cartoonist_auction::accounts::RequestTieBreak {
+            auction_state: auction_state_pda(),
+            randomness_account: account,
+            agent: alice.pubkey(),
+        },
+        cartoonist_auction::instruction::RequestTieBreak {},
+    );
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlyAgent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn resolve_tie_promotes_the_drawn_bidder() {
+    let (mut h, alice, bob) = tied_auction();
+    assert_eq!(h.state().leader, alice.pubkey());
+
+    // 1 % 2 picks the second tied entry.
+    let account = randomness(&mut h, 1);
+    request_tie_break(&mut h, account).unwrap();
+    let state = h.state();
+    assert_eq!(state.pending_randomness, account);
+    assert_eq!(state.randomness_seed_slot, SEED_SLOT);
+
+    h.warp_to_slot(REVEAL_SLOT);
+    resolve_tie(&mut h, &alice, account).unwrap();
+    let state = h.state();
+    assert_eq!(state.leader, bob.pubkey());
+    assert_eq!(state.leading_amount, 20 * USDC);
+    assert_eq!(state.leaderboard.entries()[1].bidder, alice.pubkey());
+    assert_eq!(state.pending_randomness, Pubkey::default());
+    assert_eq!(state.tie_count, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn resolve_tie_draws_only_among_the_bids_tied_at_the_request() {
+    let (mut h, alice, bob) = tied_auction();
+    let account = randomness(&mut h, 1);
+    request_tie_break(&mut h, account).unwrap();
+    assert_eq!(h.state().tie_count, 2);
+
+    h.withdraw_bid(&bob).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+    assert_auction_error(resolve_tie(&mut h, &alice, account), AuctionError::TieChanged);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn resolve_tie_waits_for_the_reveal() {
+    let (mut h, alice, _) = tied_auction();
+    let account = randomness(&mut h, 1);
+    request_tie_break(&mut h, account).unwrap();
+    assert_auction_error(
+        resolve_tie(&mut h, &alice, account),
+        AuctionError::RandomnessNotRevealed,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn resolve_tie_rejects_uncommitted_randomness() {
+    let (mut h, alice, _) = tied_auction();
+    let committed = randomness(&mut h, 1);
+    let other = randomness(&mut h, 1);
+    request_tie_break(&mut h, committed).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+    assert_auction_error(
+        resolve_tie(&mut h, &alice, other),
+        AuctionError::InvalidRandomness,
+    );
+}
+
+/// Alice escrows 20 USDC and Bob 30 USDC in a raffle that has ended.
+fn ended_raffle() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_raffle_mode(h.agent.pubkey(), true)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    (h, alice, bob)
+}
+
+fn sorted_bids(bidders: &[&Keypair]) -> Vec<Pubkey> {
+    let mut bids: Vec<_> = bidders.iter().map(|b| bid_pda(&b.pubkey())).collect();
+    bids.sort();
+    bids
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_raffle_draw_requires_raffle_mode() {
+    let mut h = Harness::new();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(
+        request_raffle_draw(&mut h, account),
+        AuctionError::RaffleModeDisabled,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_raffle_draw_waits_for_the_deadline() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_raffle_mode(h.agent.pubkey(), true)).unwrap();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(request_raffle_draw(&mut h, account), AuctionError::AuctionNotEnded);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_settle_waits_for_the_raffle() {
+    let (mut h, _, bob) = ended_raffle();
+    let cranker = h.bidder(0);
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::RaffleNotDrawn);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_waits_for_the_raffle() {
+    let (mut h, _, bob) = ended_raffle();
+    assert_auction_error(h.settle(&bob.pubkey()), AuctionError::RaffleNotDrawn);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_pays_only_the_drawn_winner_whatever_the_rivals_bid() {
+    let (mut h, alice, bob) = ended_raffle();
+    let account = randomness(&mut h, 0);
+    request_raffle_draw(&mut h, account).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+    let entrants = sorted_bids(&[&alice, &bob]);
+    draw_raffle(&mut h, &alice, account, &entrants).unwrap();
+    let (winner, loser) = if entrants[0] == bid_pda(&alice.pubkey()) {
+        (&alice, &bob)
+    } else {
+        (&bob, &alice)
+    };
+
+    assert_auction_error(h.settle(&loser.pubkey()), AuctionError::WinnersMismatch);
+    let price = h.bid(&winner.pubkey()).unwrap().amount;
+    h.settle(&winner.pubkey()).unwrap();
+    assert!(h.state().is_settled());
+    assert_eq!(h.balance(&h.treasury), price);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn draw_raffle_requires_every_entrant_in_order() {
+    let (mut h, alice, bob) = ended_raffle();
+    let account = randomness(&mut h, 0);
+    request_raffle_draw(&mut h, account).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+
+    assert_auction_error(
+        draw_raffle(&mut h, &alice, account, &[bid_pda(&alice.pubkey())]),
+        AuctionError::RaffleEntrantsMismatch,
+    );
+    let mut reversed = sorted_bids(&[&alice, &bob]);
+    reversed.reverse();
+    assert_auction_error(
+        draw_raffle(&mut h, &alice, account, &reversed),
+        AuctionError::RaffleEntrantsMismatch,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn draw_raffle_picks_by_escrow_weight_and_settles() {
+    let (mut h, alice, bob) = ended_raffle();
+    let account = randomness(&mut h, 0);
+    request_raffle_draw(&mut h, account).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+
+    // A draw of 0 lands in the first entrant's weight range.
+    let entrants = sorted_bids(&[&alice, &bob]);
+    draw_raffle(&mut h, &alice, account, &entrants).unwrap();
+    let winner = if entrants[0] == bid_pda(&alice.pubkey()) { &alice } else { &bob };
+    let state = h.state();
+    assert!(state.raffle_drawn);
+    assert_eq!(state.leader, winner.pubkey());
+    assert_eq!(state.leaderboard.entries().len(), 1);
+
+    let again = randomness(&mut h, 0);
+    assert_auction_error(
+        request_raffle_draw(&mut h, again),
+        AuctionError::RaffleAlreadyDrawn,
+    );
+
+    let cranker = h.bidder(0);
+    let instruction = ixs::crank_settle(
+        cranker.pubkey(),
+        bid_pda(&winner.pubkey()),
+        &h.payout(&winner.pubkey()),
+    );
+    h.send(instruction, &[&cranker]).unwrap();
+    assert!(h.state().is_settled());
+}
+
+/// A candle round over the last 100 seconds before 2_000: Alice bids 20
+/// USDC early, Bob snipes 30 USDC ten seconds before the deadline.
+fn ended_candle() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_candle_window(h.agent.pubkey(), 100))
+        .unwrap();
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 2_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.warp(1_990);
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.warp(2_000);
+    h.warp_to_slot(COMMIT_SLOT);
+    (h, alice, bob)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn candle_end_disqualifies_bids_after_it() {
+    let (mut h, alice, bob) = ended_candle();
+    let cranker = h.bidder(0);
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::CandleNotDetermined);
+
+    // A draw of 0 puts the candle end at the start of the window, 1_900.
+    let account = randomness(&mut h, 0);
+    request_candle_end(&mut h, account).unwrap();
+    h.warp_to_slot(REVEAL_SLOT);
+    let entrants = sorted_bids(&[&alice, &bob]);
+    assert_auction_error(
+        determine_candle_end(&mut h, &cranker, account, &entrants[..1]),
+        AuctionError::CandleEntrantsMismatch,
+    );
+    determine_candle_end(&mut h, &cranker, account, &entrants).unwrap();
+    let state = h.state();
+    assert_eq!(state.candle_end, 1_900);
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leaderboard.entries().len(), 1);
+
+    assert_auction_error(h.settle(&bob.pubkey()), AuctionError::BidAfterCandle);
+    let instruction = ixs::crank_settle(
+        cranker.pubkey(),
+        bid_pda(&alice.pubkey()),
+        &h.payout(&alice.pubkey()),
+    );
+    h.send(instruction, &[&cranker]).unwrap();
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn request_candle_end_requires_candle_mode() {
+    let mut h = Harness::new();
+    h.end_auction();
+    h.warp_to_slot(COMMIT_SLOT);
+    let account = randomness(&mut h, 0);
+    assert_auction_error(
+        request_candle_end(&mut h, account),
+        AuctionError::CandleModeDisabled,
+    );
+}
@@ -0,0 +1,160 @@
+//! Merkle refund claims over a hand-built two-leaf tree.
+
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::merkle::{leaf, node};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+/// Two refund leaves and the root over them.
+struct Tree {
+    refunds: [(Pubkey, u64); 2],
+    root: [u8; 32],
+}
+
+impl Tree {
+    fn new(refunds: [(Pubkey, u64); 2]) -> Self {
+        let leaves = [0, 1].map(|i| leaf(i, &refunds[i as usize].0, refunds[i as usize].1));
+        Self {
+            refunds,
+            root: node(&leaves[0], &leaves[1]),
+        }
+    }
+
+    fn proof(&self, index: u32) -> Vec<[u8; 32]> {
+        let other = 1 - index as usize;
+        vec![leaf(other as u32, &self.refunds[other].0, self.refunds[other].1)]
+    }
+}
+
+fn claim(h: &mut Harness, tree: &Tree, bidder: &Keypair, index: u32, amount: u64) -> TransactionResult {
+    let instruction = ixs::claim_refund(
+        h.mint,
+        &tree.root,
+        bidder.pubkey(),
+        h.usdc(&bidder.pubkey()),
+        index,
+        amount,
+        tree.proof(index),
+    );
+    h.send(instruction, &[bidder])
+}
+
+/// Alice bids 20, Carol 25 and Bob wins with 30 through `crank_settle`.
+fn settled_round() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.place_bid(&carol, 25 * USDC).unwrap();
+    h.end_auction();
+    let cranker = h.bidder(0);
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+    (h, alice, carol)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn post_refund_root_requires_a_settled_auction() {
+    let mut h = Harness::new();
+    let tree = Tree::new([(Pubkey::new_unique(), USDC), (Pubkey::new_unique(), USDC)]);
+    assert_auction_error(
+        h.as_agent(ixs::post_refund_root(h.agent.pubkey(), tree.root, 2)),
+        AuctionError::AuctionNotSettled,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn post_refund_root_is_settler_only_and_validated() {
+    let (mut h, alice, _) = settled_round();
+    let tree = Tree::new([(Pubkey::new_unique(), USDC), (Pubkey::new_unique(), USDC)]);
+    assert_auction_error(
+        h.send(ixs::post_refund_root(alice.pubkey(), tree.root, 2), &[&alice]),
+        AuctionError::OnlySettler,
+    );
+    assert_auction_error(
+        h.as_agent(ixs::post_refund_root(h.agent.pubkey(), tree.root, 0)),
+        AuctionError::InvalidRefundRoot,
+    );
+    assert_auction_error(
+        h.as_agent(ixs::post_refund_root(h.agent.pubkey(), [0; 32], 2)),
+        AuctionError::InvalidRefundRoot,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn claim_refund_pays_each_leaf_once() {
+    let (mut h, alice, carol) = settled_round();
+    // Carol is refunded 15 of her 25; the rest becomes sweepable surplus.
+    let tree = Tree::new([(alice.pubkey(), 20 * USDC), (carol.pubkey(), 15 * USDC)]);
+    h.as_agent(ixs::post_refund_root(h.agent.pubkey(), tree.root, 2))
+        .unwrap();
+    assert_eq!(h.state().refund_root, tree.root);
+
+    let cranker = h.bidder(0);
+    let refund = ixs::crank_refund(h.mint, cranker.pubkey(), alice.pubkey(), h.usdc(&alice.pubkey()));
+    assert_auction_error(h.send(refund, &[&cranker]), AuctionError::MerkleRefundsActive);
+
+    assert_auction_error(
+        claim(&mut h, &tree, &alice, 0, 25 * USDC),
+        AuctionError::InvalidMerkleProof,
+    );
+    assert_auction_error(
+        claim(&mut h, &tree, &carol, 0, 20 * USDC),
+        AuctionError::InvalidMerkleProof,
+    );
+
+    claim(&mut h, &tree, &alice, 0, 20 * USDC).unwrap();
+    claim(&mut h, &tree, &carol, 1, 15 * USDC).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert!(h.bid(&carol.pubkey()).is_none());
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    assert_eq!(h.balance(&h.usdc(&carol.pubkey())), 90 * USDC);
+
+    let state = h.state();
+    assert_eq!(state.active_bid_count, 0);
+    assert_eq!(state.total_escrowed, 0);
+    assert_eq!(h.escrow_balance(), 10 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn claim_refund_cannot_be_replayed_by_rebidding() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_buy_now_price(h.agent.pubkey(), 40 * USDC))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 50 * USDC).unwrap();
+    let buy = ixs::buy_now(h.mint, h.treasury, bob.pubkey(), h.usdc(&bob.pubkey()), None);
+    h.send(buy, &[&bob]).unwrap();
+
+    let tree = Tree::new([(alice.pubkey(), 20 * USDC), (Pubkey::new_unique(), USDC)]);
+    h.as_agent(ixs::post_refund_root(h.agent.pubkey(), tree.root, 2))
+        .unwrap();
+    claim(&mut h, &tree, &alice, 0, 20 * USDC).unwrap();
+
+    // Buy-now settles the round, so alice cannot bid again to replay her leaf.
+    assert!(h.place_bid(&alice, 20 * USDC).is_err());
+    assert!(claim(&mut h, &tree, &alice, 0, 20 * USDC).is_err());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_end_time_retires_the_refund_root() {
+    let (mut h, _, _) = settled_round();
+    let tree = Tree::new([(Pubkey::new_unique(), USDC), (Pubkey::new_unique(), USDC)]);
+    h.as_agent(ixs::post_refund_root(h.agent.pubkey(), tree.root, 2))
+        .unwrap();
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    assert_eq!(h.state().refund_root, [0; 32]);
+}
@@ -0,0 +1,1335 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::AnchorDeserialize;
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::event::AuctionClosed;
+use cartoonist_auction::scheduler::{self, ThreadResponse};
+use cartoonist_auction::state::{sealed_commitment, AuctionStatus, ConfigUpdate, FeeTier, MAX_WINNERS};
+use litesvm::types::TransactionResult;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin, round_result_pda, PayoutAccounts};
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_pays_the_treasury() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+    assert_eq!(h.escrow_balance(), 20 * USDC);
+    assert!(!h.bid(&bob.pubkey()).unwrap().active);
+    let state = h.state();
+    assert_eq!(state.active_bid_count, 1);
+    assert_eq!(state.total_escrowed, 20 * USDC);
+    assert_eq!(state.leader, alice.pubkey());
+    assert_eq!(state.leading_amount, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_is_agent_or_settler_only() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let instruction = ixs::settle(alice.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlySettler);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settler_can_settle_but_not_reconfigure() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let bot = h.bidder(0);
+    h.as_agent(admin::set_settler(h.agent.pubkey(), bot.pubkey()))
+        .unwrap();
+    assert_eq!(h.state().settler, bot.pubkey());
+
+    assert_auction_error(
+        h.send(admin::set_minimum_bid(bot.pubkey(), 1), &[&bot]),
+        AuctionError::OnlyAgent,
+    );
+    let instruction = ixs::settle(bot.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    h.send(instruction, &[&bot]).unwrap();
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn revoked_settler_cannot_settle() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let bot = h.bidder(0);
+    let agent = h.agent.pubkey();
+    h.as_agent(admin::set_settler(agent, bot.pubkey())).unwrap();
+    h.as_agent(admin::set_settler(agent, Pubkey::default())).unwrap();
+
+    let instruction = ixs::settle(bot.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    assert_auction_error(h.send(instruction, &[&bot]), AuctionError::OnlySettler);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_rejects_a_foreign_treasury() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let mut payout = h.payout(&alice.pubkey());
+    payout.treasury = h.token_account(&alice.pubkey(), 0);
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidTokenAccount);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_rejects_a_fake_escrow() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let fake = Pubkey::new_unique();
+    h.set_token_account(fake, h.mint, &auction_state_pda(), 1_000 * USDC);
+    let mut instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    instruction.accounts[2].pubkey = fake;
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidTokenAccount);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_refunds_proxy_surplus_to_the_winner() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_proxy_bid(&alice, 60 * USDC).unwrap();
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.winner_usdc = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::WinnerTokenAccountRequired);
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), MINIMUM_BID);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC - MINIMUM_BID);
+    assert_eq!(h.escrow_balance(), 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_splits_the_charity_share() {
+    let mut h = Harness::new();
+    let charity = h.token_account(&Pubkey::new_unique(), 0);
+    h.as_agent(admin::set_charity(h.agent.pubkey(), h.mint, Some(charity), 2_500))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 40 * USDC).unwrap();
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.charity = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::CharityAccountRequired);
+
+    payout.charity = Some(h.token_account(&Pubkey::new_unique(), 0));
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidTokenAccount);
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&charity), 10 * USDC);
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_pays_the_curator_share_to_their_token_account() {
+    let mut h = Harness::new();
+    let curator = Pubkey::new_unique();
+    let curator_usdc = h.token_account(&curator, 0);
+    let charity = h.token_account(&Pubkey::new_unique(), 0);
+    h.as_agent(admin::set_charity(h.agent.pubkey(), h.mint, Some(charity), 1_000))
+        .unwrap();
+    h.as_agent(admin::set_curator(h.agent.pubkey(), curator, 500))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 40 * USDC).unwrap();
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.curator_usdc = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::CuratorAccountRequired);
+
+    payout.curator_usdc = Some(charity);
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidTokenAccount);
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&curator_usdc), 2 * USDC);
+    assert_eq!(h.balance(&charity), 4 * USDC);
+    assert_eq!(h.balance(&h.treasury), 34 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_pays_the_winners_referrer_out_of_the_treasury_share() {
+    let mut h = Harness::new();
+    let referrer = Pubkey::new_unique();
+    let referrer_usdc = h.token_account(&referrer, 0);
+    h.as_agent(admin::set_referral_bps(h.agent.pubkey(), 1_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_referred_bid(&alice, 40 * USDC, Some(referrer))
+        .unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().referrer, referrer);
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.referrer_usdc = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::ReferrerAccountRequired);
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&referrer_usdc), 4 * USDC);
+    assert_eq!(h.balance(&h.treasury), 36 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn unreferred_winners_pay_no_referral() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_referral_bps(h.agent.pubkey(), 1_000))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 40 * USDC).unwrap();
+
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 40 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_an_inactive_bid_fails() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.settle(&alice.pubkey()).unwrap();
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::AuctionSettled);
+
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::BidNotActive);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_checks_the_winner_against_every_rival_bid() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 35 * USDC).unwrap();
+    h.place_bid(&carol, 30 * USDC).unwrap();
+    h.end_auction();
+
+    assert_auction_error(h.settle(&carol.pubkey()), AuctionError::WinnerNotHighest);
+
+    let mut payout = h.payout(&bob.pubkey());
+    payout.rival_bids.pop();
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&bob.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::RivalBidsMismatch);
+
+    let mut payout = h.payout(&bob.pubkey());
+    payout.rival_bids[1] = payout.rival_bids[0];
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&bob.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::RivalBidsMismatch);
+
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 35 * USDC);
+}
+
+fn two_winners(h: &mut Harness) -> (Keypair, Keypair, Keypair) {
+    h.as_agent(admin::set_num_winners(h.agent.pubkey(), 2)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.place_bid(&carol, 15 * USDC).unwrap();
+    (alice, bob, carol)
+}
+
+fn multi_payout(h: &Harness) -> PayoutAccounts {
+    PayoutAccounts {
+        mint: h.mint,
+        escrow: escrow_pda(),
+        treasury: h.treasury,
+        winner_usdc: None,
+        charity: None,
+        curator_usdc: None,
+        referrer_usdc: None,
+        prize: None,
+        accepted_mints: None,
+        rival_bids: Vec::new(),
+        round_result: round_result_pda(1),
+        win_receipt: Pubkey::default(),
+        memo_program: None,
+    }
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_multi_pays_every_winner_at_their_bid() {
+    let mut h = Harness::new();
+    let (alice, bob, carol) = two_winners(&mut h);
+    let winners = [
+        (bid_pda(&bob.pubkey()), h.usdc(&bob.pubkey())),
+        (bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey())),
+    ];
+    h.as_agent(ixs::settle_multi(h.agent.pubkey(), &multi_payout(&h), &winners))
+        .unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 50 * USDC);
+    assert!(h.bid(&carol.pubkey()).unwrap().active);
+    assert_eq!(h.state().active_bid_count, 1);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_multi_uniform_price_charges_the_lowest_winning_bid() {
+    let mut h = Harness::new();
+    let (alice, bob, _) = two_winners(&mut h);
+    h.as_agent(admin::set_uniform_price(h.agent.pubkey(), true))
+        .unwrap();
+    let winners = [
+        (bid_pda(&bob.pubkey()), h.usdc(&bob.pubkey())),
+        (bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey())),
+    ];
+    h.as_agent(ixs::settle_multi(h.agent.pubkey(), &multi_payout(&h), &winners))
+        .unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 40 * USDC);
+    assert_eq!(h.balance(&h.usdc(&bob.pubkey())), 80 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_multi_takes_trailing_accounts_only_for_transfer_hooks() {
+    let mut h = Harness::new();
+    let (alice, bob, _) = two_winners(&mut h);
+    let winners = [
+        (bid_pda(&bob.pubkey()), h.usdc(&bob.pubkey())),
+        (bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey())),
+    ];
+    let mut instruction = ixs::settle_multi(h.agent.pubkey(), &multi_payout(&h), &winners);
+    instruction.accounts.push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+    assert_auction_error(h.as_agent(instruction.clone()), AuctionError::WinnersMismatch);
+
+    // A mint without a hook ignores whatever is forwarded.
+    h.as_agent(admin::set_transfer_hooks(h.agent.pubkey(), true))
+        .unwrap();
+    h.as_agent(instruction).unwrap();
+    assert_eq!(h.balance(&h.treasury), 50 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_top_k_pays_the_ranked_list_page_by_page() {
+    let mut h = Harness::new();
+    let (alice, bob, carol) = two_winners(&mut h);
+    let [a, b, c] = [&alice, &bob, &carol].map(|k| bid_pda(&k.pubkey()));
+    h.send(ixs::link_bid(b, None, None), &[&alice]).unwrap();
+    h.send(ixs::link_bid(a, Some(b), None), &[&alice]).unwrap();
+    h.send(ixs::link_bid(c, Some(a), None), &[&alice]).unwrap();
+    let payout = multi_payout(&h);
+    let agent = h.agent.pubkey();
+
+    assert_auction_error(
+        h.as_agent(ixs::settle_top_k(agent, &payout, &[(a, h.usdc(&alice.pubkey()))])),
+        AuctionError::WinnersMismatch,
+    );
+    h.as_agent(ixs::settle_top_k(agent, &payout, &[(b, h.usdc(&bob.pubkey()))]))
+        .unwrap();
+    let state = h.state();
+    assert_eq!((state.top_k_paid, state.ranked_head), (1, a));
+    assert_ne!(state.status, AuctionStatus::Settled);
+
+    // Mid-settlement the list and every other settlement path are frozen.
+    assert_auction_error(
+        h.send(ixs::unlink_bid(carol.pubkey(), Some(a), None), &[&carol]),
+        AuctionError::TopKSettlementPending,
+    );
+    let everyone = [(a, h.usdc(&alice.pubkey()))];
+    assert_auction_error(
+        h.as_agent(ixs::settle_multi(agent, &payout, &everyone)),
+        AuctionError::TopKSettlementPending,
+    );
+
+    h.as_agent(ixs::settle_top_k(agent, &payout, &everyone)).unwrap();
+    let state = h.state();
+    assert_eq!(state.status, AuctionStatus::Settled);
+    assert_eq!((state.top_k_paid, state.ranked_count), (0, 0));
+    assert_eq!(h.balance(&h.treasury), 50 * USDC);
+    assert!(h.bid(&carol.pubkey()).unwrap().active);
+    let result = h.round_result(1).unwrap();
+    assert_eq!((result.winner, result.amount, result.winner_count), (bob.pubkey(), 50 * USDC, 2));
+    assert_eq!(h.price_feed().unwrap().price, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settle_multi_rejects_mismatched_winners() {
+    let mut h = Harness::new();
+    let (alice, bob, carol) = two_winners(&mut h);
+    let payout = multi_payout(&h);
+    let agent = h.agent.pubkey();
+
+    let too_few = [(bid_pda(&bob.pubkey()), h.usdc(&bob.pubkey()))];
+    assert_auction_error(
+        h.as_agent(ixs::settle_multi(agent, &payout, &too_few)),
+        AuctionError::WinnersMismatch,
+    );
+
+    let out_of_order = [
+        (bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey())),
+        (bid_pda(&bob.pubkey()), h.usdc(&bob.pubkey())),
+    ];
+    assert_auction_error(
+        h.as_agent(ixs::settle_multi(agent, &payout, &out_of_order)),
+        AuctionError::WinnersMismatch,
+    );
+
+    let wrong_payee = [
+        (bid_pda(&bob.pubkey()), h.usdc(&carol.pubkey())),
+        (bid_pda(&alice.pubkey()), h.usdc(&alice.pubkey())),
+    ];
+    assert_auction_error(
+        h.as_agent(ixs::settle_multi(agent, &payout, &wrong_payee)),
+        AuctionError::InvalidTokenAccount,
+    );
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_settle_waits_for_the_deadline() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    let crank = |h: &Harness| ixs::crank_settle(cranker.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+
+    // Open-ended auctions never crank.
+    let instruction = crank(&h);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::AuctionNotEnded);
+
+    let end_time = h.now() + 60;
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), end_time)).unwrap();
+    let instruction = crank(&h);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::AuctionNotEnded);
+
+    h.warp(end_time);
+    let instruction = crank(&h);
+    h.send(instruction, &[&cranker]).unwrap();
+    assert!(h.state().is_settled());
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+
+    let instruction = crank(&h);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::AuctionSettled);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_settle_only_pays_the_top_bid() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::WinnersMismatch);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_settle_checks_the_winner_against_every_rival_bid() {
+    let mut h = Harness::new();
+    // The early bid falls off the full leaderboard, then the board drops
+    // below it, leaving a top entry that is not the highest bid.
+    let early = h.bidder(100 * USDC);
+    h.place_bid(&early, 12 * USDC).unwrap();
+    let board: Vec<Keypair> = (0..MAX_WINNERS as u64)
+        .map(|i| {
+            let bidder = h.bidder(100 * USDC);
+            h.place_bid(&bidder, (13 + i) * USDC).unwrap();
+            bidder
+        })
+        .collect();
+    for (i, bidder) in board.iter().enumerate() {
+        h.decrease_bid(bidder, (3 + i as u64) * USDC).unwrap();
+    }
+    h.end_auction();
+    let cranker = h.bidder(0);
+    let top = h.state().leaderboard.entries()[0].bidder;
+    assert_ne!(top, early.pubkey());
+
+    let mut payout = h.payout(&top);
+    payout.rival_bids.pop();
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&top), &payout);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::RivalBidsMismatch);
+
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&top), &h.payout(&top));
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::WinnerNotHighest);
+    assert!(!h.state().is_settled());
+}
+
+/// Runs a thread's planning calls the way a worker would, signing the final
+/// crank as `cranker`.
+fn run_settlement_thread(h: &mut Harness, cranker: &Keypair) -> TransactionResult {
+    let mut next = ixs::plan_settlement(None, None, None);
+    loop {
+        let meta = h.send(next.clone(), &[cranker])?;
+        let response = ThreadResponse::try_from_slice(&meta.return_data.data).unwrap();
+        let mut instruction: Instruction = response.dynamic_instruction.expect("a next instruction").into();
+        for account in &mut instruction.accounts {
+            if account.pubkey == scheduler::PAYER_PUBKEY {
+                account.pubkey = cranker.pubkey();
+            }
+        }
+        if instruction.data == next.data {
+            next = instruction;
+            continue;
+        }
+        return h.send(instruction, &[cranker]);
+    }
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_settlement_thread_cranks_the_top_bid() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    let instruction = ixs::plan_settlement(None, None, None);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::AuctionNotEnded);
+
+    h.end_auction();
+    run_settlement_thread(&mut h, &cranker).unwrap();
+    assert!(h.state().is_settled());
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_settlement_thread_needs_every_rival_on_the_leaderboard() {
+    let mut h = Harness::new();
+    let cranker = h.bidder(0);
+    for i in 0..=MAX_WINNERS as u64 {
+        let bidder = h.bidder(100 * USDC);
+        h.place_bid(&bidder, (10 + i) * USDC).unwrap();
+    }
+    h.end_auction();
+
+    assert_auction_error(run_settlement_thread(&mut h, &cranker), AuctionError::RivalBidsMismatch);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn schedule_settlement_is_for_this_rounds_thread_and_a_deadline() {
+    let mut h = Harness::new();
+    let thread = scheduler::thread_address(&auction_state_pda(), &scheduler::settle_thread_id(1));
+
+    let instruction = ixs::schedule_settlement(h.agent.pubkey(), thread, LAMPORTS_PER_SOL);
+    assert_auction_error(h.as_agent(instruction), AuctionError::SettlementNotSchedulable);
+
+    let end_time = h.now() + 60;
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), end_time)).unwrap();
+    let wrong = scheduler::thread_address(&auction_state_pda(), &scheduler::settle_thread_id(2));
+    let instruction = ixs::schedule_settlement(h.agent.pubkey(), wrong, LAMPORTS_PER_SOL);
+    assert_auction_error(h.as_agent(instruction), AuctionError::InvalidThread);
+
+    let outsider = h.bidder(0);
+    let instruction = ixs::schedule_settlement(outsider.pubkey(), thread, LAMPORTS_PER_SOL);
+    assert_auction_error(h.send(instruction, &[&outsider]), AuctionError::OnlyAgent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_refund_returns_losing_bids_after_settlement() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+
+    let refund = |h: &Harness| ixs::crank_refund(h.mint, cranker.pubkey(), alice.pubkey(), h.usdc(&alice.pubkey()));
+    let instruction = refund(&h);
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::AuctionNotSettled);
+
+    h.end_auction();
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+
+    let instruction = refund(&h);
+    h.send(instruction, &[&cranker]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.state().total_escrowed, 0);
+
+    // The winner's bid is inactive and cannot be "refunded".
+    let instruction = ixs::crank_refund(h.mint, cranker.pubkey(), bob.pubkey(), h.usdc(&bob.pubkey()));
+    assert_auction_error(h.send(instruction, &[&cranker]), AuctionError::BidNotActive);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn losing_bids_share_the_rebate_pro_rata() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_rebate_bps(h.agent.pubkey(), 5_000)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let carol = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.place_bid(&carol, 10 * USDC).unwrap();
+
+    // Half the 30 USDC fee is shared over the 30 USDC alice and carol lost with.
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 15 * USDC);
+    assert_eq!(h.state().rebate_pool, 15 * USDC);
+
+    for (loser, paid) in [(&alice, 110 * USDC), (&carol, 105 * USDC)] {
+        let usdc = h.usdc(&loser.pubkey());
+        let instruction = ixs::crank_refund(h.mint, cranker.pubkey(), loser.pubkey(), usdc);
+        h.send(instruction, &[&cranker]).unwrap();
+        assert_eq!(h.balance(&usdc), paid);
+    }
+    let state = h.state();
+    assert_eq!((state.rebate_pool, state.total_escrowed), (0, 0));
+    assert_eq!(h.escrow_balance(), 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_standing_bid_carries_into_the_next_round() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.send(ixs::set_standing(alice.pubkey(), true), &[&alice]).unwrap();
+    assert!(h.bid(&alice.pubkey()).unwrap().standing);
+
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    let refund = ixs::crank_refund(h.mint, cranker.pubkey(), alice.pubkey(), h.usdc(&alice.pubkey()));
+    assert_auction_error(h.send(refund, &[&cranker]), AuctionError::StandingBid);
+
+    let end_time = h.now() + 3_600;
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), end_time)).unwrap();
+    let state = h.state();
+    assert_eq!(state.status, AuctionStatus::Open);
+    assert_eq!((state.leader, state.leading_amount), (alice.pubkey(), 20 * USDC));
+    assert_eq!(state.total_escrowed, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn the_dynamic_minimum_tracks_recent_winning_amounts() {
+    let mut h = Harness::new();
+    let update = ConfigUpdate {
+        dynamic_minimum: Some((5_000, 2)),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::update_config(h.agent.pubkey(), update)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let next_round = |h: &mut Harness| {
+        let end_time = h.now() + 3_600;
+        h.as_agent(admin::set_end_time(h.agent.pubkey(), end_time)).unwrap();
+    };
+
+    h.place_bid(&alice, 30 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    next_round(&mut h);
+    assert_eq!(h.state().minimum_bid, 15 * USDC);
+
+    assert_auction_error(h.place_bid(&bob, 14 * USDC), AuctionError::BidTooLow);
+    h.place_bid(&bob, 50 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    next_round(&mut h);
+    // Half the average of the last two winning amounts, 30 and 50.
+    assert_eq!(h.state().minimum_bid, 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_refund_recreates_a_closed_token_account() {
+    let mut h = Harness::new();
+    let alice = h.bidder(20 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+    h.close_account(&h.usdc(&alice.pubkey()));
+
+    let instruction = ixs::crank_refund(h.mint, cranker.pubkey(), alice.pubkey(), h.usdc(&alice.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn crank_refund_pays_only_the_bidder() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let mallory = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+    let instruction = ixs::crank_settle(mallory.pubkey(), bid_pda(&bob.pubkey()), &h.payout(&bob.pubkey()));
+    h.send(instruction, &[&mallory]).unwrap();
+
+    let instruction = ixs::crank_refund(h.mint, mallory.pubkey(), alice.pubkey(), h.usdc(&mallory.pubkey()));
+    assert_anchor_error(h.send(instruction, &[&mallory]), ErrorCode::ConstraintTokenOwner);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn sweep_penalties_moves_accrued_penalties_to_the_treasury() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_withdrawal_penalty(h.agent.pubkey(), 500))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 40 * USDC).unwrap();
+    h.withdraw_bid(&alice).unwrap();
+
+    let instruction = ixs::sweep_penalties(alice.pubkey(), h.mint, h.treasury);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlyAgent);
+
+    h.as_agent(ixs::sweep_penalties(h.agent.pubkey(), h.mint, h.treasury))
+        .unwrap();
+    assert_eq!(h.balance(&h.treasury), 2 * USDC);
+    let state = h.state();
+    assert_eq!(state.accrued_penalties, 0);
+    assert_eq!(state.total_escrowed, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn sweep_surplus_only_takes_untracked_tokens() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.deposit(&escrow_pda(), 7 * USDC);
+
+    let instruction = ixs::sweep_surplus(alice.pubkey(), h.mint, h.treasury);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlyAgent);
+
+    h.as_agent(ixs::sweep_surplus(h.agent.pubkey(), h.mint, h.treasury))
+        .unwrap();
+    assert_eq!(h.balance(&h.treasury), 7 * USDC);
+    assert_eq!(h.escrow_balance(), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn harvest_withheld_fees_is_agent_only_and_needs_token_2022() {
+    let mut h = Harness::new();
+    let alice = h.bidder(0);
+    let token_2022 = anchor_spl::token_2022::ID;
+
+    let instruction = ixs::harvest_withheld_fees(alice.pubkey(), h.mint, token_2022);
+    assert_auction_error(h.send(instruction, &[&alice]), AuctionError::OnlyAgent);
+
+    let instruction = ixs::harvest_withheld_fees(h.agent.pubkey(), h.mint, anchor_spl::token::ID);
+    assert_anchor_error(h.as_agent(instruction), ErrorCode::InvalidProgramId);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn assert_solvent_detects_a_short_escrow() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.send(ixs::assert_solvent(), &[&alice]).unwrap();
+
+    let escrow = escrow_pda();
+    h.set_token_account(escrow, h.mint, &auction_state_pda(), 19 * USDC);
+    assert_auction_error(h.send(ixs::assert_solvent(), &[&alice]), AuctionError::EscrowInsolvent);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn mark_abandoned_waits_for_the_abandonment_window() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    let end_time = h.state().end_time;
+
+    // Disabled until the agent opts in.
+    let result = h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]);
+    assert_auction_error(result, AuctionError::NotAbandonable);
+
+    h.as_agent(admin::set_abandonment_window(h.agent.pubkey(), 3_600))
+        .unwrap();
+    h.warp(end_time + 3_599);
+    let result = h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]);
+    assert_auction_error(result, AuctionError::NotAbandonable);
+
+    h.warp(end_time + 3_600);
+    h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]).unwrap();
+    assert!(h.state().is_abandoned());
+    let result = h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]);
+    assert_auction_error(result, AuctionError::NotAbandonable);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn abandoned_auction_refunds_bidders_and_never_settles() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.as_agent(admin::set_withdrawal_penalty(h.agent.pubkey(), 1_000))
+        .unwrap();
+    h.as_agent(admin::set_abandonment_window(h.agent.pubkey(), 60))
+        .unwrap();
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    h.warp(h.state().end_time + 60);
+    h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]).unwrap();
+
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::AuctionAbandoned);
+    let result = h.as_agent(admin::set_end_time(h.agent.pubkey(), h.now() + 60));
+    assert_auction_error(result, AuctionError::AuctionAbandoned);
+
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+    assert_eq!(h.state().total_escrowed, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settled_auction_cannot_be_abandoned() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.as_agent(admin::set_abandonment_window(h.agent.pubkey(), 60))
+        .unwrap();
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+    let end_time = h.state().end_time;
+    let instruction = ixs::crank_settle(cranker.pubkey(), bid_pda(&alice.pubkey()), &h.payout(&alice.pubkey()));
+    h.send(instruction, &[&cranker]).unwrap();
+
+    h.warp(end_time + 60);
+    let result = h.send(ixs::mark_abandoned(cranker.pubkey()), &[&cranker]);
+    assert_auction_error(result, AuctionError::NotAbandonable);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn expired_bids_cannot_win_and_anyone_can_refund_them() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let cranker = h.bidder(0);
+    h.as_agent(admin::set_bid_ttl(h.agent.pubkey(), 3_600))
+        .unwrap();
+    h.place_bid(&alice, 30 * USDC).unwrap();
+    let alice_usdc = h.usdc(&alice.pubkey());
+    let expire = ixs::expire_bid(h.mint, cranker.pubkey(), alice.pubkey(), alice_usdc);
+    let result = h.send(expire.clone(), &[&cranker]);
+    assert_auction_error(result, AuctionError::BidNotExpired);
+
+    h.warp(h.now() + 3_000);
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    h.warp(h.now() + 600);
+    h.end_auction();
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::BidExpired);
+
+    h.send(expire, &[&cranker]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.balance(&alice_usdc), 100 * USDC);
+
+    // Bob's bid was live at the deadline, however late settlement runs.
+    h.warp(h.now() + 7_200);
+    h.settle(&bob.pubkey()).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn status_follows_the_round_through_settlement_and_reopening() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    assert_eq!(h.state().status, AuctionStatus::Created);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    assert_eq!(h.state().status, AuctionStatus::Open);
+
+    h.end_auction();
+    assert_eq!(h.state().status, AuctionStatus::Closing);
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.state().status, AuctionStatus::Settled);
+
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    assert_eq!(h.state().status, AuctionStatus::Created);
+}
+
+#[test]
+fn status_allows_only_the_lifecycle_steps() {
+    use AuctionStatus::*;
+    let all = [Created, Open, Closing, Settled, Cancelled, Abandoned, Closed];
+    let legal = [
+        (Created, Open),
+        (Created, Settled),
+        (Created, Cancelled),
+        (Open, Open),
+        (Open, Settled),
+        (Open, Cancelled),
+        (Closing, Settled),
+        (Closing, Cancelled),
+        (Closing, Abandoned),
+        (Settled, Closed),
+        (Cancelled, Closed),
+        (Abandoned, Closed),
+    ];
+    for from in all {
+        for to in all {
+            assert_eq!(
+                from.can_become(to),
+                legal.contains(&(from, to)),
+                "{from:?} -> {to:?}"
+            );
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn cancelled_auction_refunds_in_full_and_is_final() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.as_agent(admin::set_withdrawal_penalty(agent, 1_000)).unwrap();
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.as_agent(admin::cancel_auction(agent)).unwrap();
+    assert_eq!(h.state().status, AuctionStatus::Cancelled);
+
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::InvalidStatusTransition);
+    assert_auction_error(h.place_bid(&bob, 20 * USDC), AuctionError::AuctionCancelled);
+    let result = h.as_agent(admin::set_end_time(agent, 0));
+    assert_auction_error(result, AuctionError::AuctionCancelled);
+    let result = h.as_agent(admin::cancel_auction(agent));
+    assert_auction_error(result, AuctionError::InvalidStatusTransition);
+
+    h.withdraw_bid(&alice).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 100 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn close_auction_waits_for_every_bid_and_is_final() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.increase_bid(&alice, 15 * USDC).unwrap();
+    let result = h.as_agent(admin::close_auction(agent));
+    assert_auction_error(result, AuctionError::BidsOutstanding);
+
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    let result = h.as_agent(admin::close_auction(agent));
+    assert_auction_error(result, AuctionError::BidsOutstanding);
+    h.withdraw_bid(&bob).unwrap();
+    let logs = h.as_agent(admin::close_auction(agent)).unwrap().logs;
+    let [closed] = &events::<AuctionClosed>(&logs)[..] else {
+        panic!("expected one AuctionClosed in {logs:#?}");
+    };
+    assert_eq!((closed.total_bids, closed.bidder_entries), (3, 2));
+
+    let state = h.state();
+    assert_eq!(state.status, AuctionStatus::Closed);
+    assert_eq!(state.round_bid_count, 3);
+    assert_eq!(state.round_bidder_count, 2);
+    assert_eq!(state.winning_amount, 35 * USDC);
+    let result = h.as_agent(admin::set_end_time(agent, 0));
+    assert_auction_error(result, AuctionError::AuctionClosed);
+    let result = h.as_agent(admin::close_auction(agent));
+    assert_auction_error(result, AuctionError::InvalidStatusTransition);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn lifetime_statistics_carry_across_rounds() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 45 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    h.withdraw_bid(&alice).unwrap();
+
+    h.as_agent(admin::set_end_time(agent, 0)).unwrap();
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&carol, 25 * USDC).unwrap();
+    h.increase_bid(&carol, 5 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&carol.pubkey()).unwrap();
+
+    let state = h.state();
+    assert_eq!(state.total_volume, 75 * USDC as u128);
+    assert_eq!(state.total_bids_placed, 4);
+    assert_eq!(state.total_rounds_settled, 2);
+    assert_eq!(state.highest_ever_bid, 45 * USDC);
+    assert_eq!(state.round_bid_count, 2);
+    assert_eq!(state.winning_amount, 30 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn each_settled_round_is_archived() {
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    let hash = [7; 32];
+    h.as_agent(admin::commit_content(agent, hash)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 45 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    h.withdraw_bid(&alice).unwrap();
+    assert!(h.round_result(2).is_none());
+    let feed = h.price_feed().expect("price published");
+    assert_eq!((feed.price, feed.round), (45 * USDC, 1));
+
+    h.as_agent(admin::set_end_time(agent, 0)).unwrap();
+    let carol = h.bidder(100 * USDC);
+    h.place_bid(&carol, 30 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&carol.pubkey()).unwrap();
+
+    let first = h.round_result(1).expect("first round archived");
+    assert_eq!(first.round, 1);
+    assert_eq!(first.winner, bob.pubkey());
+    assert_eq!(first.amount, 45 * USDC);
+    assert_eq!(first.winner_count, 1);
+    assert_eq!(first.item_hash, hash);
+    let second = h.round_result(2).expect("second round archived");
+    assert_eq!(second.round, 2);
+    assert_eq!(second.winner, carol.pubkey());
+    assert_eq!(second.amount, 30 * USDC);
+    assert!(second.settled_at >= first.settled_at);
+    let feed = h.price_feed().unwrap();
+    assert_eq!((feed.price, feed.round, feed.updated_at), (30 * USDC, 2, second.settled_at));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn all_pay_round_keeps_every_bid() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_all_pay(h.agent.pubkey(), true)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_all_pay(h.agent.pubkey(), false)),
+        AuctionError::AllPayLocked,
+    );
+    assert_auction_error(h.withdraw_bid(&alice), AuctionError::BidsNonRefundable);
+
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    let cranker = h.bidder(0);
+    let refund = ixs::crank_refund(h.mint, cranker.pubkey(), alice.pubkey(), h.usdc(&alice.pubkey()));
+    assert_auction_error(h.send(refund, &[&cranker]), AuctionError::BidsNonRefundable);
+
+    let collect = ixs::collect_all_pay_bid(h.mint, h.treasury, cranker.pubkey(), alice.pubkey());
+    h.send(collect, &[&cranker]).unwrap();
+    assert!(h.bid(&alice.pubkey()).is_none());
+    assert_eq!(h.balance(&h.treasury), 50 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 80 * USDC);
+    assert_eq!(h.escrow_balance(), 0);
+    assert_eq!(h.state().active_bid_count, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn held_settlements_wait_out_the_dispute_window() {
+    let mut h = Harness::new();
+    let arbiter = h.bidder(0);
+    let cranker = h.bidder(0);
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    let instruction = admin::set_dispute_terms(h.agent.pubkey(), Pubkey::default(), 3_600);
+    assert_auction_error(h.as_agent(instruction), AuctionError::ArbiterRequired);
+    h.as_agent(admin::set_dispute_terms(h.agent.pubkey(), arbiter.pubkey(), 3_600))
+        .unwrap();
+
+    h.place_bid(&alice, 40 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    let held_until = h.now() + 3_600;
+    assert_eq!(h.balance(&h.treasury), 0);
+    assert_eq!(h.escrow_balance(), 40 * USDC);
+    assert_eq!(h.state().held_amount, 40 * USDC);
+
+    // Anyone may release an undisputed settlement, once its window is over.
+    let release = ixs::resolve_settlement(cranker.pubkey(), h.mint, h.treasury, None, false);
+    let result = h.send(release.clone(), &[&cranker]);
+    assert_auction_error(result, AuctionError::DisputeWindowOpen);
+    h.warp(held_until);
+    h.send(release, &[&cranker]).unwrap();
+    assert_eq!(h.balance(&h.treasury), 40 * USDC);
+    assert_eq!(h.state().held_amount, 0);
+
+    // A disputed one waits for the arbiter, who can refund it.
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+    h.send(ixs::open_dispute(bob.pubkey()), &[&bob]).unwrap();
+    h.warp(h.now() + 3_600);
+    let release = ixs::resolve_settlement(cranker.pubkey(), h.mint, h.treasury, None, false);
+    assert_auction_error(h.send(release, &[&cranker]), AuctionError::OnlyArbiter);
+
+    let bob_usdc = h.usdc(&bob.pubkey());
+    let refund =
+        ixs::resolve_settlement(arbiter.pubkey(), h.mint, h.treasury, Some(bob_usdc), true);
+    h.send(refund, &[&arbiter]).unwrap();
+    assert_eq!(h.balance(&bob_usdc), 100 * USDC);
+    assert_eq!(h.escrow_balance(), 0);
+    assert_eq!(h.state().total_escrowed, 0);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settlement_waits_out_the_slot_gap_after_a_bid_change() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_min_settle_slots(h.agent.pubkey(), 10))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+
+    let slot = h.bid(&alice.pubkey()).unwrap().updated_slot;
+    h.warp_to_slot(slot + 9);
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::SettlementTooSoon);
+    h.warp_to_slot(slot + 10);
+    h.settle(&alice.pubkey()).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settlement_logs_the_auction_memo() {
+    let mut h = Harness::new();
+    let instruction = admin::set_memo(h.agent.pubkey(), "x".repeat(65));
+    assert_auction_error(h.as_agent(instruction), AuctionError::MemoTooLong);
+    h.as_agent(admin::set_memo(h.agent.pubkey(), "INV-42".into()))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.end_auction();
+
+    let mut payout = h.payout(&alice.pubkey());
+    payout.memo_program = None;
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(h.as_agent(instruction), AuctionError::MemoProgramRequired);
+
+    let logs = h.settle(&alice.pubkey()).unwrap().logs;
+    assert!(logs.iter().any(|log| log.contains("INV-42")));
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settlement_issues_the_winner_a_receipt() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.place_bid(&bob, 30 * USDC).unwrap();
+    h.end_auction();
+
+    let mut payout = h.payout(&bob.pubkey());
+    payout.win_receipt = ixs::win_receipt_pda(1, &alice.pubkey());
+    let instruction = ixs::settle(h.agent.pubkey(), bid_pda(&bob.pubkey()), &payout);
+    assert_anchor_error(h.as_agent(instruction), ErrorCode::ConstraintSeeds);
+
+    h.settle(&bob.pubkey()).unwrap();
+    let receipt = h.win_receipt(1, &bob.pubkey()).expect("winner holds a receipt");
+    assert_eq!((receipt.round, receipt.winner, receipt.amount), (1, bob.pubkey(), 30 * USDC));
+    assert_eq!(receipt.item_hash, h.state().content_hash);
+    assert!(h.win_receipt(1, &alice.pubkey()).is_none());
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_tie_goes_to_the_bid_left_unchanged_longest() {
+    let mut h = Harness::new();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&alice, 20 * USDC).unwrap();
+    h.warp(h.now() + 5);
+    h.place_bid(&bob, 20 * USDC).unwrap();
+    assert_eq!(h.state().leader, alice.pubkey());
+    h.end_auction();
+
+    assert_auction_error(h.settle(&bob.pubkey()), AuctionError::TieLostOnPriority);
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 20 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_discount_tier_hands_part_of_the_fee_back_to_the_winner() {
+    let mut h = Harness::new();
+    let gov_mint = Pubkey::new_unique();
+    let tiers = vec![
+        FeeTier { min_balance: 100, discount_bps: 2_000 },
+        FeeTier { min_balance: 1_000, discount_bps: 5_000 },
+    ];
+    let update = ConfigUpdate {
+        fee_discounts: Some((gov_mint, tiers.iter().rev().copied().collect())),
+        ..ConfigUpdate::default()
+    };
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(admin::update_config(agent, update)),
+        AuctionError::InvalidFeeTiers,
+    );
+    let update = ConfigUpdate {
+        fee_discounts: Some((gov_mint, tiers)),
+        ..ConfigUpdate::default()
+    };
+    h.as_agent(admin::update_config(agent, update)).unwrap();
+
+    let alice = h.bidder(100 * USDC);
+    let holding = Pubkey::new_unique();
+    h.set_token_account(holding, gov_mint, &alice.pubkey(), 1_500);
+    let accounts = cartoonist_auction::accounts::PlaceBid {
+        config: Some(ixs::config_pda()),
+        bidder_discount: Some(holding),
+        ..ixs::place_bid_accounts(h.mint, alice.pubkey(), h.usdc(&alice.pubkey()), None)
+    };
+    let data = cartoonist_auction::instruction::PlaceBid {
+        bid_index: 0,
+        amount: 20 * USDC,
+        referrer: None,
+    };
+    h.send(ix(accounts, data), &[&alice]).unwrap();
+    assert_eq!(h.bid(&alice.pubkey()).unwrap().fee_discount_bps, 5_000);
+
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 10 * USDC);
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 90 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn sealed_bids_are_revealed_before_settlement() {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_reveal_window(h.agent.pubkey(), 60))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    let bob = h.bidder(100 * USDC);
+    assert_auction_error(h.place_bid(&alice, 20 * USDC), AuctionError::SealedBidsOnly);
+
+    let salt = [7; 32];
+    for (bidder, amount) in [(&alice, 20 * USDC), (&bob, 30 * USDC)] {
+        let commitment = sealed_commitment(&bidder.pubkey(), amount, &salt);
+        let usdc = h.usdc(&bidder.pubkey());
+        let place = ixs::place_sealed_bid(h.mint, bidder.pubkey(), usdc, 50 * USDC, commitment);
+        h.send(place, &[bidder]).unwrap();
+    }
+    assert_eq!(h.bid(&bob.pubkey()).unwrap().amount, 0);
+    assert_eq!(h.state().leader, Pubkey::default());
+
+    let mint = h.mint;
+    let reveal = |h: &Harness, bidder: &Keypair, amount: u64| {
+        let leader_bid = h.leader_bid_for(&bidder.pubkey());
+        ixs::reveal_bid(mint, bidder.pubkey(), h.usdc(&bidder.pubkey()), amount, salt, leader_bid)
+    };
+    let early = reveal(&h, &alice, 20 * USDC);
+    assert_auction_error(h.send(early, &[&alice]), AuctionError::NotRevealing);
+    h.end_auction();
+    let wrong = reveal(&h, &alice, 25 * USDC);
+    assert_auction_error(h.send(wrong, &[&alice]), AuctionError::CommitmentMismatch);
+    h.send(reveal(&h, &alice, 20 * USDC), &[&alice]).unwrap();
+    assert_eq!(h.balance(&h.usdc(&alice.pubkey())), 80 * USDC);
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::RevealWindowOpen);
+
+    h.send(reveal(&h, &bob, 30 * USDC), &[&bob]).unwrap();
+    h.warp(h.state().end_time + 60);
+    assert_auction_error(h.settle(&alice.pubkey()), AuctionError::WinnerNotHighest);
+    h.settle(&bob.pubkey()).unwrap();
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn unrevealed_commitments_forfeit_their_bond() {
+    const BOND: u64 = 2 * USDC;
+    let mut h = Harness::new();
+    let agent = h.agent.pubkey();
+    h.as_agent(admin::set_reveal_window(agent, 60)).unwrap();
+    h.as_agent(admin::set_commit_bond(agent, BOND)).unwrap();
+    let alice = h.bidder(100 * USDC);
+    let phantom = h.bidder(100 * USDC);
+    let salt = [3; 32];
+    for bidder in [&alice, &phantom] {
+        let commitment = sealed_commitment(&bidder.pubkey(), 20 * USDC, &salt);
+        let usdc = h.usdc(&bidder.pubkey());
+        let place = ixs::place_sealed_bid(h.mint, bidder.pubkey(), usdc, 30 * USDC, commitment);
+        h.send(place, &[bidder]).unwrap();
+        assert_eq!(h.balance(&usdc), 68 * USDC);
+    }
+    assert_eq!(h.state().commit_bonds_held, 2 * BOND);
+
+    h.end_auction();
+    let (mint, treasury) = (h.mint, h.treasury);
+    let sweep = ixs::sweep_commit_bonds(mint, treasury, alice.pubkey());
+    assert_auction_error(h.send(sweep.clone(), &[&alice]), AuctionError::RevealWindowOpen);
+    let alice_usdc = h.usdc(&alice.pubkey());
+    let reveal = ixs::reveal_bid(mint, alice.pubkey(), alice_usdc, 20 * USDC, salt, None);
+    h.send(reveal, &[&alice]).unwrap();
+    assert_eq!(h.balance(&alice_usdc), 80 * USDC);
+
+    h.warp(h.state().end_time + 60);
+    h.settle(&alice.pubkey()).unwrap();
+    h.send(sweep, &[&alice]).unwrap();
+    assert_eq!(h.balance(&treasury), 20 * USDC + BOND);
+    assert_eq!(h.state().commit_bonds_held, 0);
+    let phantom_usdc = h.usdc(&phantom.pubkey());
+    let refund = ixs::crank_refund(mint, alice.pubkey(), phantom.pubkey(), phantom_usdc);
+    h.send(refund, &[&alice]).unwrap();
+    assert_eq!(h.balance(&phantom_usdc), 100 * USDC - BOND);
+}
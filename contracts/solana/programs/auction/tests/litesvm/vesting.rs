@@ -0,0 +1,98 @@
+//! Streaming settled funds to the treasury.
+
+use cartoonist_auction::error::AuctionError;
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+const DAY: i64 = 86_400;
+
+fn claim(h: &mut Harness, cranker: &Keypair) -> TransactionResult {
+    let instruction = ixs::claim_settled_funds(cranker.pubkey(), h.mint, h.treasury);
+    h.send(instruction, &[cranker])
+}
+
+/// Ten-day vesting, then Alice wins with 100 at `start`.
+fn vesting_round() -> (Harness, i64) {
+    let mut h = Harness::new();
+    h.as_agent(admin::set_vesting_duration(h.agent.pubkey(), 10 * DAY))
+        .unwrap();
+    let alice = h.bidder(100 * USDC);
+    h.place_bid(&alice, 100 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&alice.pubkey()).unwrap();
+    let start = h.now();
+    (h, start)
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn settlement_streams_to_the_treasury() {
+    let (mut h, start) = vesting_round();
+    assert_eq!(h.balance(&h.treasury), 0);
+    assert_eq!(h.escrow_balance(), 100 * USDC);
+    let state = h.state();
+    assert_eq!(state.vesting_total, 100 * USDC);
+    assert_eq!(state.total_escrowed, 100 * USDC);
+
+    let cranker = h.bidder(0);
+    h.warp(start + 3 * DAY);
+    claim(&mut h, &cranker).unwrap();
+    assert_eq!(h.balance(&h.treasury), 30 * USDC);
+
+    h.warp(start + 20 * DAY);
+    claim(&mut h, &cranker).unwrap();
+    assert_eq!(h.balance(&h.treasury), 100 * USDC);
+    assert_eq!(h.escrow_balance(), 0);
+    assert_eq!(h.state().total_escrowed, 0);
+    assert_auction_error(claim(&mut h, &cranker), AuctionError::NothingVested);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn claim_fails_before_anything_vests() {
+    let (mut h, _) = vesting_round();
+    let cranker = h.bidder(0);
+    assert_auction_error(claim(&mut h, &cranker), AuctionError::NothingVested);
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn a_new_settlement_releases_vested_funds_and_restarts_the_stream() {
+    let (mut h, start) = vesting_round();
+    h.warp(start + 5 * DAY);
+    h.as_agent(admin::set_end_time(h.agent.pubkey(), 0)).unwrap();
+    let bob = h.bidder(100 * USDC);
+    h.place_bid(&bob, 40 * USDC).unwrap();
+    h.end_auction();
+    h.settle(&bob.pubkey()).unwrap();
+
+    assert_eq!(h.balance(&h.treasury), 50 * USDC);
+    let state = h.state();
+    assert_eq!(state.vesting_total, 90 * USDC);
+    assert_eq!(state.vesting_claimed, 0);
+    assert_eq!(state.vesting_start, start + 5 * DAY);
+    h.as_agent(ixs::assert_solvent()).unwrap();
+}
+
+#[test]
+#[ignore = "needs anchor build"]
+fn set_vesting_duration_is_fixed_while_funds_vest() {
+    let (mut h, start) = vesting_round();
+    let agent = h.agent.pubkey();
+    assert_auction_error(
+        h.as_agent(admin::set_vesting_duration(agent, 0)),
+        AuctionError::VestingInProgress,
+    );
+
+    h.warp(start + 10 * DAY);
+    let cranker = h.bidder(0);
+    claim(&mut h, &cranker).unwrap();
+    h.as_agent(admin::set_vesting_duration(agent, 0)).unwrap();
+    assert_auction_error(
+        h.as_agent(admin::set_vesting_duration(agent, -1)),
+        AuctionError::InvalidVestingDuration,
+    );
+}
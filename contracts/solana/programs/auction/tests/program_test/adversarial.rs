@@ -0,0 +1,133 @@
+//! Substituted accounts: every one must be caught by an account constraint
+//! before any tokens move.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use cartoonist_auction::error::AuctionError;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::ixs::{self, admin, place_bid_accounts};
+
+/// Alice leads with 20 USDC of 100.
+async fn one_bid() -> (Env, Keypair) {
+    let mut env = Env::new().await;
+    let alice = env.bidder(100 * USDC);
+    env.place_bid(&alice, 20 * USDC).await.unwrap();
+    (env, alice)
+}
+
+/// A mint other than the auction's, with `attacker` holding 1_000 of it.
+fn other_mint(env: &mut Env, attacker: &Pubkey) -> (Pubkey, Pubkey) {
+    let mint = Pubkey::new_unique();
+    env.set_mint(mint, 6);
+    let account = Pubkey::new_unique();
+    env.set_token_account(account, mint, attacker, 1_000 * USDC);
+    (mint, account)
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn settle_to_a_treasury_other_than_the_configured_one_fails() {
+    let (mut env, alice) = one_bid().await;
+    let mallory = env.bidder(0);
+    let mut payout = env.payout(&alice.pubkey()).await;
+    payout.treasury = env.usdc(&mallory.pubkey());
+    let settle = ixs::settle(env.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(env.as_agent(settle).await, AuctionError::InvalidTokenAccount);
+
+    let crank = ixs::crank_settle(mallory.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(env.send(crank, &[&mallory]).await, AuctionError::InvalidTokenAccount);
+    assert_eq!(env.escrow_balance().await, 20 * USDC);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn set_treasury_to_another_mint_fails() {
+    let mut env = Env::new().await;
+    let agent = env.agent.pubkey();
+    let (_, wrong) = other_mint(&mut env, &agent);
+    assert_anchor_error(
+        env.as_agent(admin::set_treasury(agent, env.mint, wrong)).await,
+        ErrorCode::ConstraintTokenMint,
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn bidding_with_another_mint_fails() {
+    let mut env = Env::new().await;
+    let mallory = env.bidder(0);
+    let (mint, account) = other_mint(&mut env, &mallory.pubkey());
+
+    // Worthless tokens from a look-alike mint.
+    let accounts = place_bid_accounts(env.mint, mallory.pubkey(), account, None);
+    let data = cartoonist_auction::instruction::PlaceBid {
+        bid_index: 0,
+        amount: 20 * USDC,
+        referrer: None,
+    };
+    let place = ix(accounts, data);
+    assert_anchor_error(env.send(place, &[&mallory]).await, ErrorCode::ConstraintTokenMint);
+
+    // Naming the look-alike as the bid mint.
+    let accounts = place_bid_accounts(mint, mallory.pubkey(), account, None);
+    let data = cartoonist_auction::instruction::PlaceBid {
+        bid_index: 0,
+        amount: 20 * USDC,
+        referrer: None,
+    };
+    let place = ix(accounts, data);
+    assert_auction_error(env.send(place, &[&mallory]).await, AuctionError::MintNotAccepted);
+    assert!(env.bid(&mallory.pubkey()).await.is_none());
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn settle_from_a_fake_escrow_fails() {
+    let (mut env, alice) = one_bid().await;
+    let fake = Pubkey::new_unique();
+    env.set_token_account(fake, env.mint, &auction_state_pda(), 1_000 * USDC);
+    let payout = env.payout(&alice.pubkey()).await;
+    let mut settle = ixs::settle(env.agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    settle.accounts[2].pubkey = fake;
+    assert_auction_error(env.as_agent(settle).await, AuctionError::InvalidTokenAccount);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn withdraw_into_a_fake_escrow_fails() {
+    let (mut env, alice) = one_bid().await;
+    let fake = Pubkey::new_unique();
+    env.set_token_account(fake, env.mint, &auction_state_pda(), 0);
+    let mut withdraw = ixs::withdraw_bid(env.mint, alice.pubkey(), env.usdc(&alice.pubkey()));
+    withdraw.accounts[3].pubkey = fake;
+    assert_auction_error(env.send(withdraw, &[&alice]).await, AuctionError::InvalidTokenAccount);
+    assert_eq!(env.escrow_balance().await, 20 * USDC);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn refunding_to_someone_elses_account_fails() {
+    let (mut env, alice) = one_bid().await;
+    let bob = env.bidder(100 * USDC);
+    env.place_bid(&bob, 30 * USDC).await.unwrap();
+    env.end_auction().await;
+    let mallory = env.bidder(0);
+    let payout = env.payout(&bob.pubkey()).await;
+    let crank = ixs::crank_settle(mallory.pubkey(), bid_pda(&bob.pubkey()), &payout);
+    env.send(crank, &[&mallory]).await.unwrap();
+
+    let refund = ixs::crank_refund(env.mint, mallory.pubkey(), alice.pubkey(), env.usdc(&mallory.pubkey()));
+    assert_anchor_error(env.send(refund, &[&mallory]).await, ErrorCode::ConstraintTokenOwner);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn settling_someone_elses_bid_as_a_bidder_fails() {
+    let (mut env, alice) = one_bid().await;
+    let mallory = env.bidder(100 * USDC);
+    let payout = env.payout(&alice.pubkey()).await;
+    let settle = ixs::settle(mallory.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    assert_auction_error(env.send(settle, &[&mallory]).await, AuctionError::OnlySettler);
+}
@@ -0,0 +1,128 @@
+use cartoonist_auction::error::AuctionError;
+use solana_sdk::signature::Signer;
+
+use crate::harness::*;
+use crate::ixs::{self, admin};
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn multi_bidder_auction_pays_the_highest_and_refunds_the_rest() {
+    let mut env = Env::new().await;
+    let alice = env.bidder(100 * USDC);
+    let bob = env.bidder(100 * USDC);
+    let carol = env.bidder(100 * USDC);
+    env.place_bid(&alice, 20 * USDC).await.unwrap();
+    env.place_bid(&bob, 35 * USDC).await.unwrap();
+    env.place_bid(&carol, 30 * USDC).await.unwrap();
+
+    let state = env.state().await;
+    assert_eq!(state.leader, bob.pubkey());
+    assert_eq!(state.active_bid_count, 3);
+    assert_eq!(state.total_escrowed, 85 * USDC);
+    let ranked: Vec<_> = state.leaderboard.entries().iter().map(|e| e.bidder).collect();
+    assert_eq!(ranked, [bob.pubkey(), carol.pubkey(), alice.pubkey()]);
+
+    env.end_auction().await;
+    env.settle(&bob.pubkey()).await.unwrap();
+    assert_eq!(env.balance(env.treasury).await, 35 * USDC);
+
+    let cranker = env.bidder(0);
+    for loser in [&alice, &carol] {
+        env.crank_refund(&cranker, &loser.pubkey()).await.unwrap();
+        assert!(env.bid(&loser.pubkey()).await.is_none());
+    }
+    assert_eq!(env.balance(env.usdc(&alice.pubkey())).await, 100 * USDC);
+    assert_eq!(env.balance(env.usdc(&carol.pubkey())).await, 100 * USDC);
+    assert_eq!(env.balance(env.usdc(&bob.pubkey())).await, 65 * USDC);
+    assert_eq!(env.escrow_balance().await, 0);
+
+    let state = env.state().await;
+    assert_eq!(state.active_bid_count, 0);
+    assert_eq!(state.total_escrowed, 0);
+    env.send(ixs::assert_solvent(), &[&cranker]).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn withdrawn_bidder_loses_the_lead() {
+    let mut env = Env::new().await;
+    let alice = env.bidder(100 * USDC);
+    let bob = env.bidder(100 * USDC);
+    env.place_bid(&alice, 20 * USDC).await.unwrap();
+    env.place_bid(&bob, 30 * USDC).await.unwrap();
+
+    env.withdraw_bid(&bob).await.unwrap();
+    let state = env.state().await;
+    assert_ne!(state.leader, bob.pubkey());
+    assert_eq!(state.leaderboard.entries().len(), 1);
+    assert_eq!(env.balance(env.usdc(&bob.pubkey())).await, 100 * USDC);
+
+    env.settle(&alice.pubkey()).await.unwrap();
+    assert_eq!(env.balance(env.treasury).await, 20 * USDC);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn losing_bidder_can_bid_again_after_close_bid() {
+    let mut env = Env::new().await;
+    let alice = env.bidder(100 * USDC);
+    let bob = env.bidder(100 * USDC);
+    env.place_bid(&alice, 20 * USDC).await.unwrap();
+    env.place_bid(&bob, 30 * USDC).await.unwrap();
+    env.settle(&bob.pubkey()).await.unwrap();
+
+    // The settled bid stays behind, inactive, until its owner closes it.
+    assert!(env.place_bid(&bob, 40 * USDC).await.is_err());
+    assert!(!env.bid(&bob.pubkey()).await.unwrap().active);
+    env.close_bid(&bob).await.unwrap();
+    assert!(env.bid(&bob.pubkey()).await.is_none());
+
+    // Settlement closes the round until the agent opens the next one.
+    assert!(env.place_bid(&bob, 40 * USDC).await.is_err());
+    env.as_agent(admin::set_end_time(env.agent.pubkey(), 0))
+        .await
+        .unwrap();
+    env.place_bid(&bob, 40 * USDC).await.unwrap();
+    let bid = env.bid(&bob.pubkey()).await.unwrap();
+    assert!(bid.active);
+    assert_eq!(bid.amount, 40 * USDC);
+    assert_eq!(env.state().await.leader, bob.pubkey());
+    assert_eq!(env.escrow_balance().await, 60 * USDC);
+}
+
+#[tokio::test]
+#[ignore = "needs anchor build"]
+async fn rotated_agent_takes_over_every_privilege() {
+    let mut env = Env::new().await;
+    let alice = env.bidder(100 * USDC);
+    env.place_bid(&alice, 20 * USDC).await.unwrap();
+
+    let new_agent = env.bidder(0);
+    env.as_agent(admin::set_agent(env.agent.pubkey(), new_agent.pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(env.state().await.agent, new_agent.pubkey());
+
+    assert_auction_error(env.settle(&alice.pubkey()).await, AuctionError::OnlySettler);
+    assert_auction_error(
+        env.as_agent(admin::set_agent(env.agent.pubkey(), env.agent.pubkey())).await,
+        AuctionError::OnlyAgent,
+    );
+
+    env.send(admin::set_minimum_bid(new_agent.pubkey(), 5 * USDC), &[&new_agent])
+        .await
+        .unwrap();
+    let payout = env.payout(&alice.pubkey()).await;
+    let settle = ixs::settle(new_agent.pubkey(), bid_pda(&alice.pubkey()), &payout);
+    env.send(settle, &[&new_agent]).await.unwrap();
+    assert_eq!(env.balance(env.treasury).await, 20 * USDC);
+
+    // Handing control back restores the original agent.
+    env.send(admin::set_agent(new_agent.pubkey(), env.agent.pubkey()), &[&new_agent])
+        .await
+        .unwrap();
+    env.as_agent(admin::set_minimum_bid(env.agent.pubkey(), MINIMUM_BID))
+        .await
+        .unwrap();
+    assert_eq!(env.state().await.minimum_bid, MINIMUM_BID);
+}
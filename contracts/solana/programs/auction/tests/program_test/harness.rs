@@ -0,0 +1,355 @@
+//! Test environment: the compiled program loaded into a `ProgramTestContext`, a
+//! 6-decimal mint, and an initialized auction. Token accounts are written
+//! directly as packed SPL state.
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use cartoonist_auction::error::AuctionError;
+use cartoonist_auction::state::{AuctionState, Bid};
+use cartoonist_auction::USDC_DECIMALS;
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::bpf_loader;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use crate::ixs::{self, admin, place_bid_accounts, PayoutAccounts};
+
+pub const USDC: u64 = 1_000_000;
+pub const MINIMUM_BID: u64 = 10 * USDC;
+
+pub type TxResult = Result<(), BanksClientError>;
+
+pub fn auction_state_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"auction_state"], &cartoonist_auction::ID).0
+}
+
+pub fn escrow_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow"], &cartoonist_auction::ID).0
+}
+
+pub fn bid_pda(bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bid", bidder.as_ref()], &cartoonist_auction::ID).0
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: cartoonist_auction::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn program_path() -> PathBuf {
+    std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"))
+        .join("cartoonist_auction.so")
+}
+
+fn mint_account(authority: Pubkey, decimals: u8) -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(authority),
+        supply: u64::MAX / 2,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    token_program_account(data)
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    }
+    .pack_into_slice(&mut data);
+    token_program_account(data)
+}
+
+fn token_program_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub struct Env {
+    pub ctx: ProgramTestContext,
+    pub agent: Keypair,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    /// Wallets made by [`Env::bidder`], for finding every active bid.
+    pub bidders: Vec<Pubkey>,
+}
+
+impl Env {
+    /// Starts a bank with the program, mint and treasury, without
+    /// initializing the auction. Panics when the program has not been built.
+    pub async fn bare() -> Self {
+        let path = program_path();
+        let program = std::fs::read(&path).unwrap_or_else(|_| {
+            panic!("{} not found: run `anchor build` first", path.display())
+        });
+        let mut program_test = ProgramTest::default();
+        program_test.add_account(
+            cartoonist_auction::ID,
+            Account {
+                lamports: Rent::default().minimum_balance(program.len()),
+                data: program,
+                owner: bpf_loader::ID,
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+        let agent = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let treasury = get_associated_token_address(&agent.pubkey(), &mint);
+        program_test.add_account(mint, mint_account(agent.pubkey(), USDC_DECIMALS));
+        program_test.add_account(treasury, token_account(mint, agent.pubkey(), 0));
+        program_test.add_account(
+            agent.pubkey(),
+            Account::new(100_000_000_000, 0, &anchor_lang::system_program::ID),
+        );
+
+        Self {
+            ctx: program_test.start_with_context().await,
+            agent,
+            mint,
+            treasury,
+            bidders: Vec::new(),
+        }
+    }
+
+    /// A fresh auction with `MINIMUM_BID`, open-ended, at unix time 1_000.
+    pub async fn new() -> Self {
+        let mut env = Self::bare().await;
+        env.warp(1_000).await;
+        let initialize = env.initialize_ix(env.mint, env.treasury, MINIMUM_BID);
+        env.as_agent(initialize).await.unwrap();
+        env
+    }
+
+    pub fn initialize_ix(&self, mint: Pubkey, treasury: Pubkey, minimum_bid: u64) -> Instruction {
+        ix(
+            cartoonist_auction::accounts::Initialize {
+                auction_state: auction_state_pda(),
+                usdc_mint: mint,
+                treasury,
+                escrow: escrow_pda(),
+                agent: self.agent.pubkey(),
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            cartoonist_auction::instruction::Initialize {
+                minimum_bid,
+                mint_overrides: 0,
+            },
+        )
+    }
+
+    // ---- transactions ----
+
+    /// Sends `instruction` paid for by the first signer, on a fresh blockhash
+    /// so identical retries are not rejected as duplicates.
+    pub async fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> TxResult {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signers[0].pubkey()),
+            signers,
+            blockhash,
+        );
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn as_agent(&mut self, instruction: Instruction) -> TxResult {
+        let agent = self.agent.insecure_clone();
+        self.send(instruction, &[&agent]).await
+    }
+
+    // ---- common flows ----
+
+    /// The leader's bid account, if someone other than `bidder` leads.
+    pub async fn leader_bid_for(&mut self, bidder: &Pubkey) -> Option<Pubkey> {
+        let state = self.state().await;
+        (state.leader != Pubkey::default() && state.leader != *bidder).then(|| bid_pda(&state.leader))
+    }
+
+    pub async fn place_bid(&mut self, bidder: &Keypair, amount: u64) -> TxResult {
+        let leader_bid = self.leader_bid_for(&bidder.pubkey()).await;
+        let accounts = place_bid_accounts(self.mint, bidder.pubkey(), self.usdc(&bidder.pubkey()), leader_bid);
+        self.send(
+            ix(accounts, cartoonist_auction::instruction::PlaceBid { bid_index: 0, amount, referrer: None }),
+            &[bidder],
+        )
+        .await
+    }
+
+    pub async fn withdraw_bid(&mut self, bidder: &Keypair) -> TxResult {
+        let instruction = ixs::withdraw_bid(self.mint, bidder.pubkey(), self.usdc(&bidder.pubkey()));
+        self.send(instruction, &[bidder]).await
+    }
+
+    pub async fn close_bid(&mut self, bidder: &Keypair) -> TxResult {
+        self.send(ixs::close_bid(bidder.pubkey(), None), &[bidder]).await
+    }
+
+    /// Payout accounts for `winner`: their token account only when there is
+    /// surplus to refund, and the charity only when a share is configured.
+    pub async fn payout(&mut self, winner: &Pubkey) -> PayoutAccounts {
+        let state = self.state().await;
+        let bid = self.bid(winner).await.expect("winner has a bid");
+        PayoutAccounts {
+            mint: self.mint,
+            escrow: escrow_pda(),
+            treasury: state.treasury,
+            winner_usdc: (bid.max_amount > bid.amount || bid.fee_discount_bps > 0)
+                .then(|| self.usdc(winner)),
+            charity: (state.charity_bps > 0).then_some(state.charity),
+            curator_usdc: None,
+            referrer_usdc: None,
+            prize: None,
+            accepted_mints: None,
+            rival_bids: self.rival_bids(winner).await,
+            round_result: ixs::round_result_pda(state.total_rounds_settled + 1),
+            win_receipt: ixs::win_receipt_pda(state.total_rounds_settled + 1, winner),
+            memo_program: None,
+        }
+    }
+
+    /// Active bids of every harness bidder other than `winner`.
+    pub async fn rival_bids(&mut self, winner: &Pubkey) -> Vec<Pubkey> {
+        let mut rivals = Vec::new();
+        for bidder in self.bidders.clone() {
+            if bidder != *winner && self.bid(&bidder).await.is_some_and(|bid| bid.active) {
+                rivals.push(bid_pda(&bidder));
+            }
+        }
+        rivals
+    }
+
+    pub async fn settle(&mut self, winner: &Pubkey) -> TxResult {
+        let payout = self.payout(winner).await;
+        let instruction = ixs::settle(self.agent.pubkey(), bid_pda(winner), &payout);
+        self.as_agent(instruction).await
+    }
+
+    pub async fn crank_refund(&mut self, cranker: &Keypair, bidder: &Pubkey) -> TxResult {
+        let instruction = ixs::crank_refund(self.mint, cranker.pubkey(), *bidder, self.usdc(bidder));
+        self.send(instruction, &[cranker]).await
+    }
+
+    /// Sets the end time to now, closing bidding.
+    pub async fn end_auction(&mut self) {
+        let end_time = self.now().await;
+        let instruction = admin::set_end_time(self.agent.pubkey(), end_time);
+        self.as_agent(instruction).await.unwrap();
+    }
+
+    // ---- accounts ----
+
+    pub async fn state(&mut self) -> AuctionState {
+        self.anchor_account(&auction_state_pda())
+            .await
+            .expect("auction not initialized")
+    }
+
+    pub async fn bid(&mut self, bidder: &Pubkey) -> Option<Bid> {
+        self.anchor_account(&bid_pda(bidder)).await
+    }
+
+    async fn anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap()?;
+        T::try_deserialize(&mut account.data.as_slice()).ok()
+    }
+
+    pub async fn balance(&mut self, token_account: Pubkey) -> u64 {
+        self.ctx
+            .banks_client
+            .get_packed_account_data::<TokenAccount>(token_account)
+            .await
+            .unwrap()
+            .amount
+    }
+
+    pub async fn escrow_balance(&mut self) -> u64 {
+        self.balance(escrow_pda()).await
+    }
+
+    pub fn set_mint(&mut self, address: Pubkey, decimals: u8) {
+        let account = mint_account(self.agent.pubkey(), decimals);
+        self.ctx.set_account(&address, &AccountSharedData::from(account));
+    }
+
+    /// Writes an SPL token account for `owner` at `address`.
+    pub fn set_token_account(&mut self, address: Pubkey, mint: Pubkey, owner: &Pubkey, amount: u64) {
+        let account = token_account(mint, *owner, amount);
+        self.ctx.set_account(&address, &AccountSharedData::from(account));
+    }
+
+    pub fn usdc(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+
+    /// A funded wallet with `usdc` in its associated token account.
+    pub fn bidder(&mut self, usdc: u64) -> Keypair {
+        let bidder = Keypair::new();
+        let wallet = Account::new(10_000_000_000, 0, &anchor_lang::system_program::ID);
+        self.ctx.set_account(&bidder.pubkey(), &AccountSharedData::from(wallet));
+        self.set_token_account(self.usdc(&bidder.pubkey()), self.mint, &bidder.pubkey(), usdc);
+        self.bidders.push(bidder.pubkey());
+        bidder
+    }
+
+    // ---- clock ----
+
+    pub async fn now(&mut self) -> i64 {
+        self.ctx
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    pub async fn warp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.ctx.set_sysvar(&clock);
+    }
+}
+
+fn custom_code(result: TxResult) -> u32 {
+    match result.expect_err("transaction should have failed").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("expected a custom program error, got {other:?}"),
+    }
+}
+
+pub fn assert_auction_error(result: TxResult, expected: AuctionError) {
+    assert_eq!(custom_code(result), u32::from(expected), "expected {expected:?}");
+}
+
+pub fn assert_anchor_error(result: TxResult, expected: anchor_lang::error::ErrorCode) {
+    assert_eq!(custom_code(result), u32::from(expected), "expected {expected:?}");
+}
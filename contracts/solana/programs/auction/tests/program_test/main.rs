@@ -0,0 +1,13 @@
+//! `solana-program-test` integration tests: multi-bidder flows and
+//! substituted accounts driven through `BanksClient`. Like the LiteSVM suite
+//! they run against `target/deploy/cartoonist_auction.so` (or `$SBF_OUT_DIR`)
+//! and are ignored by default: run `anchor build`, then
+//! `cargo test -p cartoonist-auction --test program_test -- --ignored`.
+
+mod harness;
+#[path = "../litesvm/ixs.rs"]
+#[allow(dead_code)]
+mod ixs;
+
+mod adversarial;
+mod flows;
@@ -7,6 +7,7 @@ import {
   createAssociatedTokenAccount,
   mintTo,
   getAccount,
+  transfer,
 } from "@solana/spl-token";
 import { assert } from "chai";
 import { CartoonistAuction } from "../target/types/cartoonist_auction";
@@ -102,7 +103,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder1.publicKey),
         bidderUsdc: bidder1Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder1.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId, leaderBid: null,
       }).signers([bidder1]).rpc();
 
     const bid = await program.account.bid.fetch(getBidPda(bidder1.publicKey));
@@ -118,7 +119,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder2.publicKey),
         bidderUsdc: bidder2Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder2.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId, leaderBid: getBidPda(bidder1.publicKey),
       }).signers([bidder2]).rpc();
 
     assert.equal(await getEscrowBalance(), 150_000_000);
@@ -133,7 +134,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
           auctionState: getAuctionStatePda(), bid: getBidPda(bidder3.publicKey),
           bidderUsdc: bidder3Usdc, escrow: getEscrowPda(), usdcMint,
           bidder: bidder3.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
+          systemProgram: SystemProgram.programId, leaderBid: null,
         }).signers([bidder3]).rpc();
       assert.fail("Should have thrown");
     } catch (err: any) {
@@ -147,6 +148,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder1.publicKey),
         bidderUsdc: bidder1Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder1.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+        leaderBid: getBidPda(bidder2.publicKey),
       }).signers([bidder1]).rpc();
 
     const bid = await program.account.bid.fetch(getBidPda(bidder1.publicKey));
@@ -163,7 +165,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(),
         winningBid: getBidPda(bidder2.publicKey), escrow: getEscrowPda(),
         treasury: agentTreasury, usdcMint, agent: agent.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: null, charity: null,
       }).rpc();
 
     const winnerBid = await program.account.bid.fetch(getBidPda(bidder2.publicKey));
@@ -184,7 +186,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
           auctionState: getAuctionStatePda(),
           winningBid: getBidPda(bidder2.publicKey), escrow: getEscrowPda(),
           treasury: agentTreasury, usdcMint, agent: agent.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: null, charity: null,
         }).rpc();
       assert.fail("Should have thrown");
     } catch (err: any) {
@@ -199,7 +201,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
           auctionState: getAuctionStatePda(),
           winningBid: getBidPda(bidder1.publicKey), escrow: getEscrowPda(),
           treasury: agentTreasury, usdcMint, agent: bidder1.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: null, charity: null,
         }).signers([bidder1]).rpc();
       assert.fail("Should have thrown");
     } catch (err: any) {
@@ -215,7 +217,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(),
         winningBid: getBidPda(bidder1.publicKey), escrow: getEscrowPda(),
         treasury: agentTreasury, usdcMint, agent: agent.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: null, charity: null,
       }).rpc();
 
     assert.equal(await getEscrowBalance(), 0);
@@ -237,7 +239,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder1.publicKey),
         bidderUsdc: bidder1Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder1.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId, leaderBid: null,
       }).signers([bidder1]).rpc();
 
     try {
@@ -285,7 +287,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder3.publicKey),
         bidderUsdc: bidder3Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder3.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId, leaderBid: null,
       }).signers([bidder3]).rpc();
 
     await program.methods.placeBid(new anchor.BN(40_000_000))
@@ -293,7 +295,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(), bid: getBidPda(bidder1.publicKey),
         bidderUsdc: bidder1Usdc, escrow: getEscrowPda(), usdcMint,
         bidder: bidder1.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId, leaderBid: getBidPda(bidder3.publicKey),
       }).signers([bidder1]).rpc();
 
     assert.equal((await program.account.auctionState.fetch(getAuctionStatePda())).activeBidCount.toNumber(), 2);
@@ -303,7 +305,7 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
         auctionState: getAuctionStatePda(),
         winningBid: getBidPda(bidder1.publicKey), escrow: getEscrowPda(),
         treasury: agentTreasury, usdcMint, agent: agent.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: null, charity: null,
       }).rpc();
 
     const b3 = await program.account.bid.fetch(getBidPda(bidder3.publicKey));
@@ -320,4 +322,179 @@ describe("Escrow Auction (No Rounds, No Text On-Chain)", () => {
       .reduce((sum, b) => sum + b.account.amount.toNumber(), 0);
     assert.equal(escrowBalance, activeTotal);
   });
+
+  it("early withdrawal retains a penalty that is swept to the treasury", async () => {
+    await program.methods.setWithdrawalPenalty(1_000) // 10%
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+
+    const balBefore = await getBidderBalance(bidder3Usdc);
+    await program.methods.withdrawBid()
+      .accounts({
+        auctionState: getAuctionStatePda(), bid: getBidPda(bidder3.publicKey),
+        bidderUsdc: bidder3Usdc, escrow: getEscrowPda(), usdcMint,
+        bidder: bidder3.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+      }).signers([bidder3]).rpc();
+
+    assert.equal(await getBidderBalance(bidder3Usdc), balBefore + 22_500_000);
+    let state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.equal(state.accruedPenalties.toNumber(), 2_500_000);
+
+    const treasuryBefore = await getTreasuryBalance();
+    await program.methods.sweepPenalties()
+      .accounts({
+        auctionState: getAuctionStatePda(), escrow: getEscrowPda(),
+        treasury: agentTreasury, usdcMint, agent: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }).rpc();
+
+    assert.equal(await getTreasuryBalance(), treasuryBefore + 2_500_000);
+    state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.equal(state.accruedPenalties.toNumber(), 0);
+    assert.equal(await getEscrowBalance(), 0);
+
+    await program.methods.setWithdrawalPenalty(0)
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+  });
+
+  it("proxy bid escalates one increment above a plain challenger", async () => {
+    await program.methods.setBidIncrement(new anchor.BN(1_000_000))
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+
+    await program.methods.placeProxyBid(new anchor.BN(80_000_000))
+      .accounts({
+        auctionState: getAuctionStatePda(), bid: getBidPda(bidder3.publicKey),
+        bidderUsdc: bidder3Usdc, escrow: getEscrowPda(), usdcMint,
+        bidder: bidder3.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId, leaderBid: null,
+      }).signers([bidder3]).rpc();
+
+    let proxy = await program.account.bid.fetch(getBidPda(bidder3.publicKey));
+    assert.equal(proxy.amount.toNumber(), MINIMUM_BID);
+    assert.equal(proxy.maxAmount.toNumber(), 80_000_000);
+
+    await program.methods.placeBid(new anchor.BN(30_000_000))
+      .accounts({
+        auctionState: getAuctionStatePda(), bid: getBidPda(bidder2.publicKey),
+        bidderUsdc: bidder2Usdc, escrow: getEscrowPda(), usdcMint,
+        bidder: bidder2.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId, leaderBid: getBidPda(bidder3.publicKey),
+      }).signers([bidder2]).rpc();
+
+    proxy = await program.account.bid.fetch(getBidPda(bidder3.publicKey));
+    assert.equal(proxy.amount.toNumber(), 31_000_000);
+    const state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.isTrue(state.leader.equals(bidder3.publicKey));
+    assert.equal(state.leadingAmount.toNumber(), 31_000_000);
+
+    const winnerBefore = await getBidderBalance(bidder3Usdc);
+    const treasuryBefore = await getTreasuryBalance();
+    await program.methods.settle()
+      .accounts({
+        auctionState: getAuctionStatePda(),
+        winningBid: getBidPda(bidder3.publicKey), escrow: getEscrowPda(),
+        treasury: agentTreasury, usdcMint, agent: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID, winnerUsdc: bidder3Usdc, charity: null,
+      }).rpc();
+
+    assert.equal(await getTreasuryBalance(), treasuryBefore + 31_000_000);
+    assert.equal(await getBidderBalance(bidder3Usdc), winnerBefore + 49_000_000);
+  });
+
+  it("buy-it-now settles immediately and closes bidding", async () => {
+    await program.methods.setBuyNowPrice(new anchor.BN(30_000_000))
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+
+    const treasuryBefore = await getTreasuryBalance();
+    await program.methods.buyNow()
+      .accounts({
+        auctionState: getAuctionStatePda(), bid: getBidPda(bidder2.publicKey),
+        bidderUsdc: bidder2Usdc, escrow: getEscrowPda(), treasury: agentTreasury,
+        usdcMint, bidder: bidder2.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+        charity: null,
+      }).signers([bidder2]).rpc();
+
+    assert.equal(await getTreasuryBalance(), treasuryBefore + 30_000_000);
+    const state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.deepEqual(state.status, { settled: {} });
+    assert.isFalse((await program.account.bid.fetch(getBidPda(bidder2.publicKey))).active);
+
+    await program.methods.setEndTime(new anchor.BN(0))
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+    await program.methods.setBuyNowPrice(new anchor.BN(0))
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+  });
+
+  it("settle_multi pays out the top K leaderboard bids", async () => {
+    await program.methods.setNumWinners(2)
+      .accounts({ auctionState: getAuctionStatePda(), agent: agent.publicKey }).rpc();
+
+    for (const [bidder, ata, amount, leader] of [
+      [bidder1, bidder1Usdc, 60_000_000, null],
+      [bidder3, bidder3Usdc, 45_000_000, getBidPda(bidder1.publicKey)],
+    ] as [Keypair, PublicKey, number, PublicKey | null][]) {
+      await program.methods.closeBid()
+        .accounts({ bid: getBidPda(bidder.publicKey), bidder: bidder.publicKey })
+        .signers([bidder]).rpc();
+      await program.methods.placeBid(new anchor.BN(amount))
+        .accounts({
+          auctionState: getAuctionStatePda(), bid: getBidPda(bidder.publicKey),
+          bidderUsdc: ata, escrow: getEscrowPda(), usdcMint,
+          bidder: bidder.publicKey, tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId, leaderBid: leader,
+        }).signers([bidder]).rpc();
+    }
+
+    const treasuryBefore = await getTreasuryBalance();
+    await program.methods.settleMulti()
+      .accounts({
+        auctionState: getAuctionStatePda(), escrow: getEscrowPda(),
+        treasury: agentTreasury, usdcMint, agent: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID, charity: null,
+      })
+      .remainingAccounts([
+        { pubkey: getBidPda(bidder1.publicKey), isSigner: false, isWritable: true },
+        { pubkey: bidder1Usdc, isSigner: false, isWritable: true },
+        { pubkey: getBidPda(bidder3.publicKey), isSigner: false, isWritable: true },
+        { pubkey: bidder3Usdc, isSigner: false, isWritable: true },
+      ]).rpc();
+
+    assert.equal(await getTreasuryBalance(), treasuryBefore + 105_000_000);
+    const state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.equal(state.leaderboard.len, 0);
+  });
+
+  it("agent rotates the treasury to another USDC account and back", async () => {
+    for (const newTreasury of [bidder1Usdc, agentTreasury]) {
+      await program.methods.setTreasury()
+        .accounts({
+          auctionState: getAuctionStatePda(), usdcMint, newTreasury, agent: agent.publicKey,
+        }).rpc();
+      const state = await program.account.auctionState.fetch(getAuctionStatePda());
+      assert.isTrue(state.treasury.equals(newTreasury));
+    }
+  });
+
+  it("total_escrowed tracks the escrow balance and assert_solvent passes", async () => {
+    const state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.equal(state.totalEscrowed.toNumber(), await getEscrowBalance());
+
+    await program.methods.assertSolvent()
+      .accounts({ auctionState: getAuctionStatePda(), escrow: getEscrowPda() }).rpc();
+  });
+
+  it("sweep_surplus moves only tokens sent directly to escrow", async () => {
+    await transfer(provider.connection, bidder1, bidder1Usdc, getEscrowPda(), bidder1, 5_000_000);
+
+    const treasuryBefore = await getTreasuryBalance();
+    await program.methods.sweepSurplus()
+      .accounts({
+        auctionState: getAuctionStatePda(), escrow: getEscrowPda(),
+        treasury: agentTreasury, usdcMint, agent: agent.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }).rpc();
+
+    assert.equal(await getTreasuryBalance(), treasuryBefore + 5_000_000);
+    const state = await program.account.auctionState.fetch(getAuctionStatePda());
+    assert.equal(await getEscrowBalance(), state.totalEscrowed.toNumber());
+  });
 });